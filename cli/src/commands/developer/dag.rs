@@ -0,0 +1,60 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkOS library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use anyhow::{bail, Result};
+use clap::Parser;
+use std::path::PathBuf;
+
+/// Export the recent DAG of a validator as GraphViz (DOT).
+#[derive(Debug, Parser)]
+pub struct Dag {
+    /// The endpoint of the validator to export the DAG from.
+    #[clap(long)]
+    endpoint: String,
+
+    /// The number of most recent rounds to export.
+    #[clap(long, default_value = "10")]
+    rounds: u64,
+
+    /// The path to write the DOT file to. If not set, the DOT is returned.
+    #[clap(short, long)]
+    output: Option<PathBuf>,
+}
+
+impl Dag {
+    pub fn parse(self) -> Result<String> {
+        // Send a request to the validator.
+        let response = ureq::get(&format!("{}/testnet3/dag/dot?rounds={}", self.endpoint, self.rounds)).call();
+
+        // Retrieve the DOT representation.
+        let dot = match response {
+            Ok(response) => response.into_string()?,
+            Err(err) => match err {
+                ureq::Error::Status(_status, response) => {
+                    bail!(response.into_string().unwrap_or("Response too large!".to_owned()))
+                }
+                err => bail!(err),
+            },
+        };
+
+        // Write the DOT file, if an output path is set.
+        match self.output {
+            Some(path) => {
+                std::fs::write(&path, dot)?;
+                Ok(format!("The DAG was exported to {}", path.display()))
+            }
+            None => Ok(dot),
+        }
+    }
+}
@@ -12,6 +12,9 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+mod dag;
+pub use dag::*;
+
 mod decrypt;
 pub use decrypt::*;
 
@@ -57,6 +60,8 @@ type CurrentNetwork = snarkvm::prelude::Testnet3;
 /// Commands to deploy and execute transactions
 #[derive(Debug, Parser)]
 pub enum Developer {
+    /// Export the recent DAG of a validator as GraphViz (DOT).
+    Dag(Dag),
     /// Decrypt a ciphertext.
     Decrypt(Decrypt),
     /// Deploy a program.
@@ -72,6 +77,7 @@ pub enum Developer {
 impl Developer {
     pub fn parse(self) -> Result<String> {
         match self {
+            Self::Dag(dag) => dag.parse(),
             Self::Decrypt(decrypt) => decrypt.parse(),
             Self::Deploy(deploy) => deploy.parse(),
             Self::Execute(execute) => execute.parse(),
@@ -14,7 +14,37 @@
 
 use snarkos_account::Account;
 use snarkos_display::Display;
-use snarkos_node::{bft::MEMORY_POOL_PORT, router::messages::NodeType, Node};
+use snarkos_node::{
+    bft::{
+        helpers::{
+            AdmissionOrder,
+            AlertNotifier,
+            CacheWindow,
+            CertificatePenaltyPolicy,
+            GCMemoryPolicy,
+            RetryPolicy,
+            RiskPolicy,
+            StaleProposalPolicy,
+            UnknownProgramPolicy,
+            WatchdogAction,
+            WebhookScorer,
+            WorkerAffinity,
+            BLOCK_EXPORT_TASK,
+            CACHE_PRUNING_TASK,
+            DEFAULT_SEND_RETRY_DELAY_IN_MS,
+            METRICS_TASK,
+        },
+        MAX_BATCH_DELAY_IN_MS,
+        MAX_GC_ROUNDS,
+        MEMORY_POOL_PORT,
+    },
+    consensus::Compression,
+    rest::{AdminConfig, AdminToken},
+    router::{messages::NodeType, BanPolicy, SyncRequestLimits},
+    BlockExportTarget,
+    Node,
+    NodeConfig,
+};
 use snarkvm::{
     console::{
         account::{Address, PrivateKey},
@@ -22,7 +52,7 @@ use snarkvm::{
         network::{Network, Testnet3},
     },
     ledger::{
-        block::Block,
+        block::{Block, Ratify},
         committee::{Committee, MIN_VALIDATOR_STAKE},
         store::{helpers::memory::ConsensusMemory, ConsensusStore},
     },
@@ -38,7 +68,12 @@ use colored::Colorize;
 use core::str::FromStr;
 use rand::SeedableRng;
 use rand_chacha::ChaChaRng;
-use std::{net::SocketAddr, path::PathBuf};
+use std::{
+    fmt,
+    net::{IpAddr, SocketAddr, TcpListener, TcpStream},
+    path::PathBuf,
+    time::Duration,
+};
 use tokio::runtime::{self, Runtime};
 
 /// The recommended minimum number of 'open files' limit for a validator.
@@ -46,6 +81,12 @@ use tokio::runtime::{self, Runtime};
 #[cfg(target_family = "unix")]
 const RECOMMENDED_MIN_NOFILES_LIMIT: u64 = 2048;
 
+/// The timeout for dialing each trusted peer in a dry run.
+const DRY_RUN_DIAL_TIMEOUT_IN_SECS: u64 = 3;
+
+/// The number of bytes in a mebibyte, in which the memory watermarks are specified.
+const BYTES_PER_MIB: u64 = 1024 * 1024;
+
 /// The development mode RNG seed.
 const DEVELOPMENT_MODE_RNG_SEED: u64 = 1234567890u64;
 /// The development mode number of genesis committee members.
@@ -67,6 +108,9 @@ pub struct Start {
     /// Specify this node as a client
     #[clap(long = "client")]
     pub client: bool,
+    /// If the flag is set, the client runs as a read replica of its trusted peers (requires `--client` and `--peers`)
+    #[clap(long = "read-replica")]
+    pub read_replica: bool,
 
     /// Specify the account private key of the node
     #[clap(long = "private-key")]
@@ -87,6 +131,18 @@ pub struct Start {
     /// Specify the IP address and port of the validator(s) to connect to
     #[clap(default_value = "", long = "validators")]
     pub validators: String,
+    /// Specify the path to a file, to which the peer bans are persisted across restarts
+    #[clap(long = "ban-file")]
+    pub ban_file: Option<PathBuf>,
+    /// Specify the maximum lifetime (in seconds) of a peer ban
+    #[clap(long = "max-ban-lifetime-secs")]
+    pub max_ban_lifetime_secs: Option<u64>,
+    /// Specify the cap on the total outbound rate (in bytes per second) to the peers
+    #[clap(long = "max-outbound-bytes-per-sec")]
+    pub max_outbound_bytes_per_sec: Option<u64>,
+    /// Specify the limits on the sync requests served to peers, as 'max_concurrent,max_queued,reserved_for_committee'
+    #[clap(long = "max-sync-requests")]
+    pub max_sync_requests: Option<String>,
 
     /// Specify the IP address and port for the REST server
     #[clap(default_value = "0.0.0.0:3033", long = "rest")]
@@ -97,6 +153,18 @@ pub struct Start {
     /// If the flag is set, the node will not initialize the REST server
     #[clap(long)]
     pub norest: bool,
+    /// Specify the IP address and port for the admin listener, which serves the admin endpoints over TLS
+    #[clap(long = "admin")]
+    pub admin: Option<SocketAddr>,
+    /// Specify the path to a file containing the bearer token for the admin listener
+    #[clap(long = "admin-token-file")]
+    pub admin_token_file: Option<PathBuf>,
+    /// Specify the path to the PEM-encoded TLS certificate for the admin listener
+    #[clap(long = "admin-tls-cert")]
+    pub admin_tls_cert: Option<PathBuf>,
+    /// Specify the path to the PEM-encoded TLS private key for the admin listener
+    #[clap(long = "admin-tls-key")]
+    pub admin_tls_key: Option<PathBuf>,
 
     /// If the flag is set, the node will not render the display
     #[clap(long)]
@@ -110,6 +178,16 @@ pub struct Start {
     /// Enables the metrics exporter
     #[clap(default_value = "false", long = "metrics")]
     pub metrics: bool,
+    /// Specify the URL of a webhook, to which alerts on critical node events are POSTed
+    #[clap(long = "alert-webhook")]
+    pub alert_webhook: Option<String>,
+    /// Specify the events to alert on
+    /// [options: partition, stuck-round, committee-removal, storage-corruption, committee-change]
+    #[clap(
+        default_value = "partition,stuck-round,committee-removal,storage-corruption,committee-change",
+        long = "alert-events"
+    )]
+    pub alert_events: String,
 
     /// Enables the node to prefetch initial blocks from a CDN
     #[clap(default_value = "https://s3.us-west-1.amazonaws.com/testnet3.blocks/phase3", long = "cdn")]
@@ -127,11 +205,174 @@ pub struct Start {
     /// Specify the path to a directory containing the ledger
     #[clap(long = "storage_path")]
     pub storage_path: Option<PathBuf>,
+
+    /// Specify the maximum number of rounds the BFT storage retains while memory usage is low (requires `--validator`)
+    #[clap(long = "gc-retained-rounds")]
+    pub gc_retained_rounds: Option<u64>,
+    /// Specify the memory usage (in MiB) below which extra rounds are retained, and above which they are released
+    #[clap(default_value = "4096,8192", long = "gc-memory-watermarks")]
+    pub gc_memory_watermarks: String,
+
+    /// If the flag is set, the validator runs as a warm standby, until it is promoted through the admin listener
+    #[clap(long = "standby")]
+    pub standby: bool,
+
+    /// Specify the path to a file, to which committed blocks are appended as lines of JSON (requires `--validator`)
+    #[clap(long = "export-blocks")]
+    pub export_blocks: Option<PathBuf>,
+    /// Specify the Kafka brokers and topic, to which committed blocks are published [format: brokers/topic]
+    #[cfg(feature = "kafka")]
+    #[clap(long = "export-blocks-kafka")]
+    pub export_blocks_kafka: Option<String>,
+    /// Specify the path to a file, to which the new address-to-transaction index entries of each block are appended
+    #[clap(long = "export-index")]
+    pub export_index: Option<PathBuf>,
+
+    /// Specify the maximum number of transmissions from any single submitter in a batch (requires `--validator`)
+    #[clap(long = "max-transmissions-per-submitter")]
+    pub max_transmissions_per_submitter: Option<usize>,
+    /// Specify the maximum number of transmissions that can be pinned through the admin API (requires `--validator`)
+    #[clap(long = "max-pinned-transmissions")]
+    pub max_pinned_transmissions: Option<usize>,
+    /// Specify the window (in rounds) of the peer events cached for rate limiting (requires `--validator`)
+    #[clap(long = "cache-events-window-rounds")]
+    pub cache_events_window_rounds: Option<u64>,
+    /// Specify the window (in rounds) of the peer requests cached for deduplication (requires `--validator`)
+    #[clap(long = "cache-requests-window-rounds")]
+    pub cache_requests_window_rounds: Option<u64>,
+    /// Specify the count of a cached event or request above which it is tallied as a spam hit (requires `--validator`)
+    #[clap(long = "cache-spam-threshold")]
+    pub cache_spam_threshold: Option<usize>,
+    /// Specify the comma-separated IDs of the workers dedicated to solutions (requires `--validator`)
+    #[clap(long = "solution-workers")]
+    pub solution_workers: Option<String>,
+    /// Specify the comma-separated IDs of the workers dedicated to transactions (requires `--validator`)
+    #[clap(long = "transaction-workers")]
+    pub transaction_workers: Option<String>,
+    /// Specify the watchdog timeout (in milliseconds) on the heartbeats of the critical tasks (requires `--validator`)
+    #[clap(long = "watchdog-timeout-ms")]
+    pub watchdog_timeout_ms: Option<u64>,
+    /// If the flag is set, the node shuts down and exits when a critical task stalls (requires `--watchdog-timeout-ms`)
+    #[clap(long = "watchdog-exit")]
+    pub watchdog_exit: bool,
+    /// Specify the maximum number of transactions from any single sender, per interval (requires `--validator`)
+    #[clap(long = "max-transactions-per-sender")]
+    pub max_transactions_per_sender: Option<usize>,
+    /// Specify the interval (in seconds) over which the transactions of each sender are counted
+    #[clap(default_value_t = 60, long = "sender-rate-limit-interval-secs")]
+    pub sender_rate_limit_interval_secs: i64,
+    /// Specify the maximum number of certificates in a DAG snapshot served to validators (requires `--validator`)
+    #[clap(long = "max-dag-snapshot-certificates")]
+    pub max_dag_snapshot_certificates: Option<usize>,
+    /// If the flag is set, the DAG is warmed up with a snapshot from a peer once synced (requires `--validator`)
+    #[clap(long = "dag-snapshot-warm-up")]
+    pub dag_snapshot_warm_up: bool,
+    /// Specify the comma-separated IPs of the probes (e.g. health checks) to exclude from the peer metrics
+    #[clap(default_value = "", long = "probe-sources")]
+    pub probe_sources: String,
+    /// Specify the minimum number of previous certificates for a batch to reference, beyond the quorum
+    #[clap(long = "min-previous-certificates")]
+    pub min_previous_certificates: Option<usize>,
+    /// Specify the handling of proposals that reference the GC round or below [options: process, refuse, sync]
+    #[clap(long = "stale-proposals")]
+    pub stale_proposals: Option<String>,
+    /// Specify the maximum fraction of stake that may change in a committee transition, above which it is alerted on
+    #[clap(long = "max-committee-change")]
+    pub max_committee_change: Option<f64>,
+
+    /// Specify the maximum number of attempts to deliver each of our certificates to a peer (requires `--validator`)
+    #[clap(long = "certificate-retry-attempts")]
+    pub certificate_retry_attempts: Option<u32>,
+    /// Specify the delay (in milliseconds) before the first retry of a certificate, which doubles on every retry
+    #[clap(default_value_t = DEFAULT_SEND_RETRY_DELAY_IN_MS, long = "certificate-retry-delay-ms")]
+    pub certificate_retry_delay_ms: u64,
+    /// Specify the maximum number of rounds ahead of the latest committed round to buffer (requires `--validator`)
+    #[clap(long = "max-open-rounds")]
+    pub max_open_rounds: Option<u64>,
+    /// Specify the fraction of committee stake that must be connected, below which the validator stops proposing
+    #[clap(long = "partition-threshold")]
+    pub partition_threshold: Option<f64>,
+    /// Specify the consensus loads (as 'high,low' unconfirmed transmissions) between which background tasks pause
+    #[clap(long = "maintenance-load")]
+    pub maintenance_load: Option<String>,
+    /// Specify the comma-separated background tasks that are paused under load (requires `--maintenance-load`)
+    #[clap(default_value = "block_export,metrics,cache_pruning", long = "maintenance-tasks")]
+    pub maintenance_tasks: String,
+    /// Specify the zstd level at which to compress the entries of the persistent BFT store (requires `--validator`)
+    #[clap(long = "bft-store-compression")]
+    pub bft_store_compression: Option<i32>,
+    /// Specify the write latency (in ms) beyond which the BFT store buffers its writes (requires `--validator`)
+    #[clap(long = "bft-store-degraded-threshold-ms")]
+    pub bft_store_degraded_threshold_ms: Option<u64>,
+    /// Specify the malformed certificates (as 'warn,throttle,ban') at which a peer is penalized, if any
+    #[clap(long = "certificate-penalties")]
+    pub certificate_penalties: Option<String>,
+    /// Specify the risk scores (as 'monitor,throttle,ban') at which a peer is acted on, if the risk score is enabled
+    #[clap(long = "risk-thresholds")]
+    pub risk_thresholds: Option<String>,
+    /// Specify the maximum number of recent connection attempts from each ASN (requires `--validator`)
+    #[clap(long = "max-connections-per-asn")]
+    pub max_connections_per_asn: Option<usize>,
+    /// Specify the maximum number of recent connection attempts from each subnet (requires `--validator`)
+    #[clap(long = "max-connections-per-subnet")]
+    pub max_connections_per_subnet: Option<usize>,
+    /// Specify the path to an IP-to-ASN dataset (in the `ip2asn` TSV format), to group the connections by ASN
+    #[clap(long = "asn-dataset")]
+    pub asn_dataset: Option<PathBuf>,
+
+    /// If the flag is set, the validator signs admission receipts for the unconfirmed transactions it admits
+    #[clap(long = "admission-receipts")]
+    pub admission_receipts: bool,
+    /// Specify the URL of an external scorer, which is consulted on the admission of transmissions
+    #[clap(long = "admission-scorer")]
+    pub admission_scorer: Option<String>,
+    /// Specify the maximum time (in milliseconds) to wait for a verdict from the admission scorer
+    #[clap(default_value = "200", long = "admission-scorer-timeout-ms")]
+    pub admission_scorer_timeout_ms: u64,
+    /// If the flag is set, transmissions are rejected when the admission scorer fails or times out
+    #[clap(long = "admission-scorer-fail-closed")]
+    pub admission_scorer_fail_closed: bool,
+    /// Specify the minimum fee bump (as a percentage) to replace a queued transaction, enabling replacement
+    #[clap(long = "replacement-fee-bump")]
+    pub replacement_fee_bump: Option<u64>,
+    /// Specify the handling of unconfirmed transactions that reference unknown programs [options: reject, defer]
+    #[clap(long = "unknown-programs")]
+    pub unknown_programs: Option<String>,
+    /// Specify the capacity of the ready queue, beyond which arriving transmissions are staged for admission
+    #[clap(long = "admission-queue-capacity")]
+    pub admission_queue_capacity: Option<usize>,
+    /// Specify the order in which the staged transmissions are admitted into the ready queue [options: fifo, fee]
+    #[clap(long = "admission-order")]
+    pub admission_order: Option<String>,
+    /// Specify the window (in seconds) in which the retry of a transaction dropped for a full queue is recognized
+    #[clap(long = "transient-rejection-window-secs")]
+    pub transient_rejection_window_secs: Option<u64>,
+
+    /// Specify the system load (as a fraction of capacity) above which the REST server sheds its expensive endpoints
+    #[clap(long = "rest-shed-load")]
+    pub rest_shed_load: Option<f64>,
+    /// Specify the TTL (in milliseconds) of the cached responses of the hot read endpoints of the REST server
+    #[clap(long = "rest-cache-ttl-ms")]
+    pub rest_cache_ttl_ms: Option<u64>,
+    /// If the flag is set, the REST server rejects the transactions that are already pending or confirmed with `409`
+    #[clap(long = "rest-reject-duplicates")]
+    pub rest_reject_duplicates: bool,
+
+    /// If the flag is set, the node validates its configurations and connectivity, prints a report, and exits
+    #[clap(long = "dry-run")]
+    pub dry_run: bool,
 }
 
 impl Start {
     /// Starts the snarkOS node.
     pub fn parse(self) -> Result<String> {
+        // If the flag is set, validate the configurations and connectivity, without starting the node.
+        if self.dry_run {
+            return match self.network {
+                3 => self.parse_dry_run::<Testnet3>(),
+                _ => bail!("Invalid network ID specified"),
+            };
+        }
         // Initialize the logger.
         let log_receiver = crate::helpers::initialize_logger(self.verbosity, self.nodisplay, self.logfile.clone());
         // Initialize the runtime.
@@ -368,6 +609,381 @@ impl Start {
         }
     }
 
+    /// Returns the admin listener configuration, if the admin listener is enabled.
+    fn parse_admin(&self) -> Result<Option<AdminConfig>> {
+        let Some(listener_ip) = self.admin else {
+            return Ok(None);
+        };
+        // Ensure the admin listener is served separately from the REST server.
+        ensure!(!self.norest, "The '--admin' flag requires the REST server");
+        ensure!(listener_ip != self.rest, "The admin listener must not use the REST server port");
+        // Ensure the admin listener requires TLS and a bearer token.
+        let (Some(token_file), Some(tls_cert_path), Some(tls_key_path)) =
+            (&self.admin_token_file, self.admin_tls_cert.clone(), self.admin_tls_key.clone())
+        else {
+            bail!("The '--admin' flag requires '--admin-token-file', '--admin-tls-cert', and '--admin-tls-key'")
+        };
+        // Load the bearer token.
+        check_permissions(token_file)?;
+        let token = AdminToken::new(std::fs::read_to_string(token_file)?.trim().to_string())?;
+        Ok(Some(AdminConfig { listener_ip, token, tls_cert_path, tls_key_path }))
+    }
+
+    /// Returns the webhook notifier for the critical node events, if alerting is enabled.
+    fn parse_alert_notifier(&self) -> Result<Option<AlertNotifier>> {
+        let Some(url) = &self.alert_webhook else {
+            return Ok(None);
+        };
+        // Parse the alerting events.
+        let events =
+            self.alert_events.split(',').filter(|name| !name.is_empty()).map(str::parse).collect::<Result<Vec<_>>>()?;
+        ensure!(!events.is_empty(), "The '--alert-webhook' flag requires at least one of '--alert-events'");
+        Ok(Some(AlertNotifier::new(url)?.with_events(events)))
+    }
+
+    /// Returns the optional configurations of the node, from the given configurations.
+    fn parse_node_config(&self) -> Result<NodeConfig> {
+        let mut config = NodeConfig::default();
+        // Parse the webhook notifier for the critical node events.
+        config.alert_notifier = self.parse_alert_notifier()?;
+        // Parse the read replica mode.
+        if self.read_replica {
+            ensure!(self.client, "The '--read-replica' flag requires the '--client' flag");
+            config.read_replica = true;
+        }
+        // Parse the peer ban policy.
+        if self.ban_file.is_some() || self.max_ban_lifetime_secs.is_some() {
+            let max_lifetime = self.max_ban_lifetime_secs.map_or(BanPolicy::DEFAULT_MAX_LIFETIME, Duration::from_secs);
+            ensure!(!max_lifetime.is_zero(), "The '--max-ban-lifetime-secs' flag must be nonzero");
+            let ban_policy = BanPolicy::new(max_lifetime);
+            config.ban_policy = Some(match &self.ban_file {
+                Some(path) => ban_policy.with_path(path),
+                None => ban_policy,
+            });
+        }
+        // Parse the cap on the outbound rate.
+        if let Some(max_bytes_per_sec) = self.max_outbound_bytes_per_sec {
+            ensure!(max_bytes_per_sec > 0, "The '--max-outbound-bytes-per-sec' flag must be nonzero");
+            config.max_outbound_bytes_per_sec = Some(max_bytes_per_sec);
+        }
+        // Parse the limits on the sync requests.
+        if let Some(limits) = &self.max_sync_requests {
+            let limits = limits.split(',').map(usize::from_str).collect::<Result<Vec<_>, _>>();
+            let Some(&[max_concurrent, max_queued, reserved]) = limits.as_deref().ok() else {
+                bail!("The '--max-sync-requests' flag must be three comma-separated values")
+            };
+            config.sync_request_limits = Some(SyncRequestLimits::new(max_concurrent, max_queued, reserved)?);
+        }
+        // Parse the GC memory policy.
+        if let Some(max_retained_rounds) = self.gc_retained_rounds {
+            ensure!(self.validator, "The '--gc-retained-rounds' flag requires the '--validator' flag");
+            ensure!(
+                max_retained_rounds >= MAX_GC_ROUNDS,
+                "The '--gc-retained-rounds' flag must be at least {MAX_GC_ROUNDS} (the maximum GC rounds)"
+            );
+            let (low_watermark, high_watermark) =
+                parse_pair::<u64>("--gc-memory-watermarks", &self.gc_memory_watermarks)?;
+            config.gc_memory_policy = Some(GCMemoryPolicy::new(
+                low_watermark.saturating_mul(BYTES_PER_MIB),
+                high_watermark.saturating_mul(BYTES_PER_MIB),
+                max_retained_rounds,
+            )?);
+        }
+        // Parse the warm standby mode.
+        if self.standby {
+            ensure!(self.validator, "The '--standby' flag requires the '--validator' flag");
+            ensure!(self.admin.is_some(), "The '--standby' flag requires the '--admin' flag, to promote the standby");
+            config.standby = true;
+        }
+        // Parse the block export target.
+        config.block_export = self.parse_block_export()?;
+        if config.block_export.is_some() {
+            ensure!(self.validator, "Exporting committed blocks requires the '--validator' flag");
+        }
+        // Parse the index export path.
+        if let Some(path) = &self.export_index {
+            ensure!(self.validator, "The '--export-index' flag requires the '--validator' flag");
+            config.index_export = Some(path.clone());
+        }
+        // Parse the fairness cap on the submitters in a batch.
+        if let Some(max_per_submitter) = self.max_transmissions_per_submitter {
+            ensure!(self.validator, "The '--max-transmissions-per-submitter' flag requires the '--validator' flag");
+            ensure!(max_per_submitter > 0, "The '--max-transmissions-per-submitter' flag must be nonzero");
+            config.max_transmissions_per_submitter = Some(max_per_submitter);
+        }
+        // Parse the maximum number of pinned transmissions.
+        if let Some(max_pinned) = self.max_pinned_transmissions {
+            ensure!(self.validator, "The '--max-pinned-transmissions' flag requires the '--validator' flag");
+            ensure!(max_pinned > 0, "The '--max-pinned-transmissions' flag must be nonzero");
+            config.max_pinned_transmissions = Some(max_pinned);
+        }
+        // Parse the windows of the gateway cache.
+        if let Some(num_rounds) = self.cache_events_window_rounds {
+            ensure!(self.validator, "The '--cache-events-window-rounds' flag requires the '--validator' flag");
+            ensure!(num_rounds > 0, "The '--cache-events-window-rounds' flag must be nonzero");
+            config.cache_events_window = Some(CacheWindow::Rounds(num_rounds));
+        }
+        if let Some(num_rounds) = self.cache_requests_window_rounds {
+            ensure!(self.validator, "The '--cache-requests-window-rounds' flag requires the '--validator' flag");
+            ensure!(num_rounds > 0, "The '--cache-requests-window-rounds' flag must be nonzero");
+            config.cache_requests_window = Some(CacheWindow::Rounds(num_rounds));
+        }
+        if let Some(threshold) = self.cache_spam_threshold {
+            ensure!(self.validator, "The '--cache-spam-threshold' flag requires the '--validator' flag");
+            ensure!(threshold > 0, "The '--cache-spam-threshold' flag must be nonzero");
+            config.cache_spam_threshold = Some(threshold);
+        }
+        // Parse the workers dedicated to each transmission type.
+        if self.solution_workers.is_some() || self.transaction_workers.is_some() {
+            ensure!(self.validator, "The '--solution-workers' and '--transaction-workers' flags require '--validator'");
+            let parse_workers = |workers: &Option<String>| match workers {
+                Some(workers) => workers.split(',').map(u8::from_str).collect::<Result<Vec<_>, _>>(),
+                None => Ok(vec![]),
+            };
+            let (solution_workers, transaction_workers) =
+                (parse_workers(&self.solution_workers)?, parse_workers(&self.transaction_workers)?);
+            config.worker_affinity = Some(WorkerAffinity::new(solution_workers, transaction_workers)?);
+        }
+        // Parse the watchdog of the critical tasks.
+        if let Some(timeout_in_ms) = self.watchdog_timeout_ms {
+            ensure!(self.validator, "The '--watchdog-timeout-ms' flag requires the '--validator' flag");
+            ensure!(
+                timeout_in_ms > MAX_BATCH_DELAY_IN_MS,
+                "The '--watchdog-timeout-ms' flag must exceed {MAX_BATCH_DELAY_IN_MS}ms (the maximum batch delay)"
+            );
+            config.watchdog_timeout = Some(Duration::from_millis(timeout_in_ms));
+        }
+        if self.watchdog_exit {
+            ensure!(
+                self.watchdog_timeout_ms.is_some(),
+                "The '--watchdog-exit' flag requires the '--watchdog-timeout-ms' flag"
+            );
+            config.watchdog_action = WatchdogAction::Exit;
+        }
+        // Parse the rate limit on the transactions from any single sender.
+        if let Some(max_transactions) = self.max_transactions_per_sender {
+            ensure!(self.validator, "The '--max-transactions-per-sender' flag requires the '--validator' flag");
+            ensure!(max_transactions > 0, "The '--max-transactions-per-sender' flag must be nonzero");
+            ensure!(
+                self.sender_rate_limit_interval_secs > 0,
+                "The '--sender-rate-limit-interval-secs' flag must be nonzero"
+            );
+            config.sender_rate_limit = Some((max_transactions, self.sender_rate_limit_interval_secs));
+        }
+        // Parse the DAG snapshots that are served to, and requested from, the validators.
+        if let Some(max_certificates) = self.max_dag_snapshot_certificates {
+            ensure!(self.validator, "The '--max-dag-snapshot-certificates' flag requires the '--validator' flag");
+            ensure!(max_certificates > 0, "The '--max-dag-snapshot-certificates' flag must be nonzero");
+            config.max_dag_snapshot_certificates = Some(max_certificates);
+        }
+        if self.dag_snapshot_warm_up {
+            ensure!(self.validator, "The '--dag-snapshot-warm-up' flag requires the '--validator' flag");
+            config.dag_snapshot_warm_up = true;
+        }
+        // Parse the IPs of the probes.
+        if !self.probe_sources.is_empty() {
+            ensure!(self.validator, "The '--probe-sources' flag requires the '--validator' flag");
+            let probe_sources = self.probe_sources.split(',').map(|ip| IpAddr::from_str(ip.trim()));
+            config.probe_sources = probe_sources.collect::<Result<_, _>>()?;
+        }
+        // Parse the minimum number of previous certificates for a batch to reference.
+        if let Some(min_certificates) = self.min_previous_certificates {
+            ensure!(self.validator, "The '--min-previous-certificates' flag requires the '--validator' flag");
+            ensure!(
+                min_certificates <= Committee::<Testnet3>::MAX_COMMITTEE_SIZE as usize,
+                "The '--min-previous-certificates' flag must be at most {}",
+                Committee::<Testnet3>::MAX_COMMITTEE_SIZE
+            );
+            config.min_previous_certificates = min_certificates;
+        }
+        // Parse the handling of the proposals that reference rounds at or below the GC round.
+        if let Some(policy) = &self.stale_proposals {
+            ensure!(self.validator, "The '--stale-proposals' flag requires the '--validator' flag");
+            config.stale_proposal_policy = match policy.as_str() {
+                "process" => StaleProposalPolicy::Process,
+                "refuse" => StaleProposalPolicy::Refuse,
+                "sync" => StaleProposalPolicy::RefuseAndSync,
+                _ => bail!("The '--stale-proposals' flag must be 'process', 'refuse', or 'sync', found '{policy}'"),
+            };
+        }
+        // Parse the maximum fraction of stake that may change in a committee transition.
+        if let Some(max_fraction) = self.max_committee_change {
+            ensure!(self.validator, "The '--max-committee-change' flag requires the '--validator' flag");
+            ensure!(max_fraction > 0.0 && max_fraction <= 1.0, "The '--max-committee-change' flag must be in (0, 1]");
+            config.max_committee_change = Some(max_fraction);
+        }
+        // Parse the retry policy for broadcasting certificates.
+        if let Some(max_attempts) = self.certificate_retry_attempts {
+            ensure!(self.validator, "The '--certificate-retry-attempts' flag requires the '--validator' flag");
+            config.certificate_retry_policy = Some(RetryPolicy::new(max_attempts, self.certificate_retry_delay_ms)?);
+        }
+        // Parse the maximum number of open rounds.
+        if let Some(max_open_rounds) = self.max_open_rounds {
+            ensure!(self.validator, "The '--max-open-rounds' flag requires the '--validator' flag");
+            ensure!(max_open_rounds > 0, "The '--max-open-rounds' flag must be nonzero");
+            config.max_open_rounds = Some(max_open_rounds);
+        }
+        // Parse the partition threshold.
+        if let Some(threshold) = self.partition_threshold {
+            ensure!(self.validator, "The '--partition-threshold' flag requires the '--validator' flag");
+            ensure!(threshold > 0.0 && threshold <= 1.0, "The '--partition-threshold' flag must be in (0, 1]");
+            config.partition_threshold = Some(threshold);
+        }
+        // Parse the maintenance window.
+        if let Some(loads) = &self.maintenance_load {
+            ensure!(self.validator, "The '--maintenance-load' flag requires the '--validator' flag");
+            let (high_load, low_load) = parse_pair::<usize>("--maintenance-load", loads)?;
+            ensure!(low_load < high_load, "The '--maintenance-load' flag must be 'high,low', with high above low");
+            let tasks = self.maintenance_tasks.split(',').map(str::trim).filter(|task| !task.is_empty());
+            let tasks = tasks.map(str::to_string).collect::<Vec<_>>();
+            for task in &tasks {
+                ensure!(
+                    [BLOCK_EXPORT_TASK, METRICS_TASK, CACHE_PRUNING_TASK].contains(&task.as_str()),
+                    "The '--maintenance-tasks' flag contains an unknown task '{task}'"
+                );
+            }
+            config.maintenance_window = Some((high_load, low_load, tasks));
+        }
+        // Parse the compression of the persistent BFT store.
+        if let Some(level) = self.bft_store_compression {
+            ensure!(self.validator, "The '--bft-store-compression' flag requires the '--validator' flag");
+            config.bft_store_compression = Some(Compression::zstd(level)?);
+        }
+        // Parse the degraded mode of the persistent BFT store.
+        if let Some(threshold_in_ms) = self.bft_store_degraded_threshold_ms {
+            ensure!(self.validator, "The '--bft-store-degraded-threshold-ms' flag requires the '--validator' flag");
+            ensure!(threshold_in_ms > 0, "The '--bft-store-degraded-threshold-ms' flag must be nonzero");
+            config.bft_store_degraded_threshold = Some(Duration::from_millis(threshold_in_ms));
+        }
+        // Parse the penalties for malformed certificates.
+        if let Some(thresholds) = &self.certificate_penalties {
+            ensure!(self.validator, "The '--certificate-penalties' flag requires the '--validator' flag");
+            let thresholds = thresholds.split(',').map(usize::from_str).collect::<Result<Vec<_>, _>>();
+            let Some(&[warn, throttle, ban]) = thresholds.as_deref().ok() else {
+                bail!("The '--certificate-penalties' flag must be three comma-separated values")
+            };
+            config.certificate_penalty_policy = Some(CertificatePenaltyPolicy::new(
+                warn,
+                throttle,
+                ban,
+                CertificatePenaltyPolicy::DEFAULT_WINDOW_IN_SECS,
+                CertificatePenaltyPolicy::DEFAULT_BAN_DURATION,
+            )?);
+        }
+        // Parse the thresholds of the combined risk score.
+        if let Some(thresholds) = &self.risk_thresholds {
+            ensure!(self.validator, "The '--risk-thresholds' flag requires the '--validator' flag");
+            let thresholds = thresholds.split(',').map(|threshold| f64::from_str(threshold.trim()));
+            let thresholds = thresholds.collect::<Result<Vec<_>, _>>();
+            let Some(&[monitor, throttle, ban]) = thresholds.as_deref().ok() else {
+                bail!("The '--risk-thresholds' flag must be three comma-separated values")
+            };
+            let ban_duration = RiskPolicy::DEFAULT_BAN_DURATION;
+            config.risk_policy = Some(RiskPolicy::new(Default::default(), monitor, throttle, ban, ban_duration)?);
+        }
+        // Parse the per-group connection limits.
+        if let Some(max_connections) = self.max_connections_per_asn {
+            ensure!(self.validator, "The '--max-connections-per-asn' flag requires the '--validator' flag");
+            ensure!(max_connections > 0, "The '--max-connections-per-asn' flag must be nonzero");
+            config.max_connections_per_asn = Some(max_connections);
+        }
+        if let Some(max_connections) = self.max_connections_per_subnet {
+            ensure!(self.validator, "The '--max-connections-per-subnet' flag requires the '--validator' flag");
+            ensure!(max_connections > 0, "The '--max-connections-per-subnet' flag must be nonzero");
+            config.max_connections_per_subnet = Some(max_connections);
+        }
+        if let Some(path) = &self.asn_dataset {
+            ensure!(
+                self.max_connections_per_asn.is_some(),
+                "The '--asn-dataset' flag requires the '--max-connections-per-asn' flag"
+            );
+            config.asn_dataset = Some(path.clone());
+        }
+        // Parse the admission receipts.
+        if self.admission_receipts {
+            ensure!(self.validator, "The '--admission-receipts' flag requires the '--validator' flag");
+            config.admission_receipts = true;
+        }
+        // Parse the external admission scorer.
+        if let Some(url) = &self.admission_scorer {
+            ensure!(self.validator, "The '--admission-scorer' flag requires the '--validator' flag");
+            ensure!(self.admission_scorer_timeout_ms > 0, "The '--admission-scorer-timeout-ms' flag must be nonzero");
+            let timeout = Duration::from_millis(self.admission_scorer_timeout_ms);
+            config.admission_scorer = Some((WebhookScorer::new(url)?, timeout, !self.admission_scorer_fail_closed));
+        }
+        // Parse the minimum fee bump of a replacement transaction.
+        if let Some(min_fee_bump_percent) = self.replacement_fee_bump {
+            ensure!(self.validator, "The '--replacement-fee-bump' flag requires the '--validator' flag");
+            config.replacement_fee_bump_percent = Some(min_fee_bump_percent);
+        }
+        // Parse the handling of the transactions that reference unknown programs.
+        if let Some(policy) = &self.unknown_programs {
+            ensure!(self.validator, "The '--unknown-programs' flag requires the '--validator' flag");
+            config.unknown_program_policy = Some(match policy.as_str() {
+                "reject" => UnknownProgramPolicy::Reject,
+                "defer" => UnknownProgramPolicy::Defer,
+                _ => bail!("The '--unknown-programs' flag must be 'reject' or 'defer', found '{policy}'"),
+            });
+        }
+        // Parse the bounded admission into the ready queue.
+        if let Some(capacity) = self.admission_queue_capacity {
+            ensure!(self.validator, "The '--admission-queue-capacity' flag requires the '--validator' flag");
+            ensure!(capacity > 0, "The '--admission-queue-capacity' flag must be nonzero");
+            let order = match self.admission_order.as_deref() {
+                None | Some("fifo") => AdmissionOrder::Fifo,
+                Some("fee") => AdmissionOrder::Fee,
+                Some(order) => bail!("The '--admission-order' flag must be 'fifo' or 'fee', found '{order}'"),
+            };
+            config.admission_queue = Some((order, capacity));
+        } else {
+            ensure!(
+                self.admission_order.is_none(),
+                "The '--admission-order' flag requires '--admission-queue-capacity'"
+            );
+        }
+        // Parse the retention window of the transient rejections.
+        if let Some(window_secs) = self.transient_rejection_window_secs {
+            ensure!(
+                config.admission_queue.is_some(),
+                "The '--transient-rejection-window-secs' flag requires '--admission-queue-capacity'"
+            );
+            ensure!(window_secs > 0, "The '--transient-rejection-window-secs' flag must be nonzero");
+            config.transient_rejection_window = Some(Duration::from_secs(window_secs));
+        }
+        // Parse the load threshold of the REST circuit breaker.
+        if let Some(threshold) = self.rest_shed_load {
+            ensure!(self.validator, "The '--rest-shed-load' flag requires the '--validator' flag");
+            ensure!(!self.norest, "The '--rest-shed-load' flag requires the REST server");
+            ensure!(threshold.is_finite() && threshold > 0.0, "The '--rest-shed-load' flag must be positive");
+            config.rest_shed_load_threshold = Some(threshold);
+        }
+        // Parse the TTL of the REST response cache.
+        if let Some(ttl_ms) = self.rest_cache_ttl_ms {
+            ensure!(!self.norest, "The '--rest-cache-ttl-ms' flag requires the REST server");
+            ensure!(ttl_ms > 0, "The '--rest-cache-ttl-ms' flag must be nonzero");
+            config.rest_cache_ttl = Some(Duration::from_millis(ttl_ms));
+        }
+        // Parse the handling of the duplicate transaction submissions.
+        if self.rest_reject_duplicates {
+            ensure!(!self.norest, "The '--rest-reject-duplicates' flag requires the REST server");
+            config.rest_reject_duplicates = true;
+        }
+        Ok(config)
+    }
+
+    /// Returns the destination to which committed blocks are exported, if block export is enabled.
+    fn parse_block_export(&self) -> Result<Option<BlockExportTarget>> {
+        #[cfg(feature = "kafka")]
+        if let Some(kafka) = &self.export_blocks_kafka {
+            ensure!(self.export_blocks.is_none(), "Cannot use '--export-blocks' and '--export-blocks-kafka' together");
+            let Some((brokers, topic)) = kafka.rsplit_once('/').filter(|(b, t)| !b.is_empty() && !t.is_empty()) else {
+                bail!("The '--export-blocks-kafka' flag must be of the form 'brokers/topic'")
+            };
+            return Ok(Some(BlockExportTarget::Kafka { brokers: brokers.to_string(), topic: topic.to_string() }));
+        }
+        Ok(self.export_blocks.clone().map(BlockExportTarget::File))
+    }
+
     /// Returns the node type, from the given configurations.
     const fn parse_node_type(&self) -> NodeType {
         if self.validator {
@@ -387,6 +1003,11 @@ impl Start {
 
         // Parse the trusted peers to connect to.
         let mut trusted_peers = self.parse_trusted_peers()?;
+        // Ensure a read replica is a client with trusted peers.
+        if self.read_replica {
+            ensure!(self.client, "The '--read-replica' flag requires the '--client' flag");
+            ensure!(!trusted_peers.is_empty(), "The '--read-replica' flag requires the '--peers' flag");
+        }
         // Parse the trusted validators to connect to.
         let mut trusted_validators = self.parse_trusted_validators()?;
         // Parse the development configurations.
@@ -407,6 +1028,13 @@ impl Start {
             true => None,
             false => Some(self.rest),
         };
+        // Parse the admin listener configuration.
+        let admin_config = self.parse_admin()?;
+        if admin_config.is_some() {
+            ensure!(!node_type.is_prover(), "The '--admin' flag is not available for a prover");
+        }
+        // Parse the optional configurations of the node.
+        let node_config = self.parse_node_config()?;
 
         // If the display is not enabled, render the welcome message.
         if self.nodisplay {
@@ -454,13 +1082,137 @@ impl Start {
 
         // Initialize the node.
         let bft_ip = if self.dev.is_some() { self.bft } else { None };
-        match node_type {
-            NodeType::Validator => Node::new_validator(self.node, bft_ip, rest_ip, self.rest_rps, account, &trusted_peers, &trusted_validators, genesis, cdn, storage_mode).await,
-            NodeType::Prover => Node::new_prover(self.node, account, &trusted_peers, genesis, storage_mode).await,
-            NodeType::Client => Node::new_client(self.node, rest_ip, self.rest_rps, account, &trusted_peers, genesis, cdn, storage_mode).await,
+        let node = match node_type {
+            NodeType::Validator => Node::new_validator(self.node, bft_ip, rest_ip, self.rest_rps, account, &trusted_peers, &trusted_validators, genesis, cdn, storage_mode, node_config).await?,
+            NodeType::Prover => Node::new_prover(self.node, account, &trusted_peers, genesis, storage_mode, node_config).await?,
+            NodeType::Client => Node::new_client(self.node, rest_ip, self.rest_rps, account, &trusted_peers, genesis, cdn, storage_mode, node_config).await?,
+        };
+        // If the admin listener is configured, start it.
+        if let Some(admin_config) = admin_config {
+            match &node {
+                Node::Validator(validator) => validator.enable_admin(admin_config).await?,
+                Node::Client(client) => client.enable_admin(admin_config).await?,
+                Node::Prover(_) => unreachable!("The admin listener is not available for a prover"),
+            }
+        }
+        Ok(node)
+    }
+
+    /// Validates the configurations, the private key, the committee membership, and the connectivity
+    /// to the trusted peers, without starting the node. Returns the report, or an error with the report
+    /// (i.e. a nonzero exit code) if any of the checks fails.
+    fn parse_dry_run<N: Network>(mut self) -> Result<String> {
+        let mut report = DryRunReport::default();
+
+        // Check the configurations.
+        let trusted_peers = match self.check_dry_run_config() {
+            Ok(trusted_peers) => {
+                let description = format!("{} at {}", self.parse_node_type().description(), self.node);
+                report.push("Configurations", DryRunOutcome::Pass(description));
+                Some(trusted_peers)
+            }
+            Err(error) => {
+                report.push("Configurations", DryRunOutcome::Fail(error.to_string()));
+                None
+            }
+        };
+
+        // Check the private key.
+        let address = match self.parse_private_key::<N>() {
+            Ok(account) => {
+                report.push("Private key", DryRunOutcome::Pass(account.address().to_string()));
+                Some(account.address())
+            }
+            Err(error) => {
+                report.push("Private key", DryRunOutcome::Fail(error.to_string()));
+                None
+            }
+        };
+
+        // Check the committee membership of a validator.
+        // Note: The membership is checked against the genesis committee, as the latest committee
+        // requires a synced ledger.
+        let committee = match (self.validator, address) {
+            (false, _) => DryRunOutcome::Skip("The node is not a validator".to_string()),
+            (true, None) => DryRunOutcome::Skip("The private key is invalid".to_string()),
+            (true, Some(address)) => match self.parse_genesis::<N>() {
+                Ok(genesis) => {
+                    let is_member = genesis.ratifications().iter().any(|ratify| match ratify {
+                        Ratify::Genesis(committee, ..) => committee.is_committee_member(address),
+                        _ => false,
+                    });
+                    match is_member {
+                        true => DryRunOutcome::Pass("The validator is in the genesis committee".to_string()),
+                        false => DryRunOutcome::Warn("The validator is not in the genesis committee".to_string()),
+                    }
+                }
+                Err(error) => DryRunOutcome::Fail(format!("Failed to load the genesis block - {error}")),
+            },
+        };
+        report.push("Committee", committee);
+
+        // Check the connectivity to the trusted peers.
+        let connectivity = match trusted_peers {
+            None => DryRunOutcome::Skip("The configurations are invalid".to_string()),
+            Some(trusted_peers) if trusted_peers.is_empty() => {
+                DryRunOutcome::Pass("No trusted peers are configured".to_string())
+            }
+            Some(trusted_peers) => {
+                let timeout = Duration::from_secs(DRY_RUN_DIAL_TIMEOUT_IN_SECS);
+                let unreachable = trusted_peers
+                    .iter()
+                    .filter(|peer_ip| TcpStream::connect_timeout(peer_ip, timeout).is_err())
+                    .map(|peer_ip| peer_ip.to_string())
+                    .collect::<Vec<_>>();
+                match unreachable.is_empty() {
+                    true => DryRunOutcome::Pass(format!("Reached all {} trusted peers", trusted_peers.len())),
+                    false => DryRunOutcome::Fail(format!("Failed to reach {}", unreachable.join(", "))),
+                }
+            }
+        };
+        report.push("Connectivity", connectivity);
+
+        match report.is_ok() {
+            true => Ok(report.to_string()),
+            false => bail!("{report}"),
         }
     }
 
+    /// Validates the configurations for a dry run, returning the trusted peers and validators to dial.
+    fn check_dry_run_config(&mut self) -> Result<Vec<SocketAddr>> {
+        // Ensure the peer lists are well-formed, as malformed IPs are otherwise skipped.
+        let mut trusted_peers = self.parse_trusted_peers()?;
+        let mut trusted_validators = self.parse_trusted_validators()?;
+        for (flag, ips, num_parsed) in [
+            ("--peers", &self.peers, trusted_peers.len()),
+            ("--validators", &self.validators, trusted_validators.len()),
+        ] {
+            ensure!(ips.is_empty() || ips.split(',').count() == num_parsed, "The '{flag}' flag has a malformed IP");
+        }
+        // Ensure a read replica is a client with trusted peers.
+        if self.read_replica {
+            ensure!(self.client, "The '--read-replica' flag requires the '--client' flag");
+            ensure!(!trusted_peers.is_empty(), "The '--read-replica' flag requires the '--peers' flag");
+        }
+        // Parse the development configurations.
+        self.parse_development(&mut trusted_peers, &mut trusted_validators)?;
+        // Ensure the admin listener is valid.
+        if self.parse_admin()?.is_some() {
+            ensure!(!self.parse_node_type().is_prover(), "The '--admin' flag is not available for a prover");
+        }
+        // Ensure the optional configurations of the node are valid.
+        self.parse_node_config()?;
+        // Ensure the listeners can be bound.
+        let listener_ips = [Some(self.node), (!self.norest).then_some(self.rest)];
+        for listener_ip in listener_ips.into_iter().flatten() {
+            if let Err(error) = TcpListener::bind(listener_ip) {
+                bail!("Failed to bind to '{listener_ip}' - {error}");
+            }
+        }
+        trusted_peers.extend(trusted_validators);
+        Ok(trusted_peers)
+    }
+
     /// Returns a runtime for the node.
     fn runtime() -> Runtime {
         // Retrieve the number of cores.
@@ -490,6 +1242,51 @@ impl Start {
     }
 }
 
+/// The outcome of a dry-run check.
+enum DryRunOutcome {
+    /// The check passed.
+    Pass(String),
+    /// The check passed, with a warning to the operator.
+    Warn(String),
+    /// The check does not apply, or depends on a check that failed.
+    Skip(String),
+    /// The check failed.
+    Fail(String),
+}
+
+/// The report of a dry run, with the outcome of each check.
+#[derive(Default)]
+struct DryRunReport {
+    checks: Vec<(&'static str, DryRunOutcome)>,
+}
+
+impl DryRunReport {
+    /// Adds the outcome of the given check to the report.
+    fn push(&mut self, check: &'static str, outcome: DryRunOutcome) {
+        self.checks.push((check, outcome));
+    }
+
+    /// Returns `true` if none of the checks failed.
+    fn is_ok(&self) -> bool {
+        !self.checks.iter().any(|(_, outcome)| matches!(outcome, DryRunOutcome::Fail(_)))
+    }
+}
+
+impl fmt::Display for DryRunReport {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "🔎 Dry run of the snarkOS node:")?;
+        for (check, outcome) in &self.checks {
+            match outcome {
+                DryRunOutcome::Pass(details) => write!(f, "\n  ✅ {check} - {details}")?,
+                DryRunOutcome::Warn(details) => write!(f, "\n  ⚠️  {check} - {}", details.yellow())?,
+                DryRunOutcome::Skip(details) => write!(f, "\n  ⏭️  {check} - {}", details.dimmed())?,
+                DryRunOutcome::Fail(details) => write!(f, "\n  ❌ {check} - {}", details.red())?,
+            }
+        }
+        Ok(())
+    }
+}
+
 fn check_permissions(path: &PathBuf) -> Result<(), snarkvm::prelude::Error> {
     #[cfg(target_family = "unix")]
     {
@@ -510,6 +1307,15 @@ fn check_permissions(path: &PathBuf) -> Result<(), snarkvm::prelude::Error> {
     Ok(())
 }
 
+/// Parses a pair of comma-separated values, for the given flag.
+fn parse_pair<T: FromStr>(flag: &str, values: &str) -> Result<(T, T)> {
+    let mut values = values.split(',').map(T::from_str);
+    match (values.next(), values.next(), values.next()) {
+        (Some(Ok(first)), Some(Ok(second)), None) => Ok((first, second)),
+        _ => bail!("The '{flag}' flag must be two comma-separated values"),
+    }
+}
+
 /// Loads or computes the genesis block.
 fn load_or_compute_genesis<N: Network>(
     genesis_private_key: PrivateKey<N>,
@@ -578,6 +1384,7 @@ fn load_or_compute_genesis<N: Network>(
 mod tests {
     use super::*;
     use crate::commands::{Command, CLI};
+    use snarkos_node::bft::helpers::AlertEvent;
     use snarkvm::prelude::Testnet3;
 
     type CurrentNetwork = Testnet3;
@@ -618,6 +1425,608 @@ mod tests {
         ]);
     }
 
+    #[test]
+    fn test_parse_alert_notifier() {
+        let config = Start::try_parse_from(["snarkos"].iter()).unwrap();
+        assert!(config.parse_alert_notifier().unwrap().is_none());
+
+        let config =
+            Start::try_parse_from(["snarkos", "--alert-webhook", "https://example.com/alerts"].iter()).unwrap();
+        let notifier = config.parse_alert_notifier().unwrap().unwrap();
+        assert_eq!(notifier.url().as_str(), "https://example.com/alerts");
+        assert_eq!(notifier.events().len(), AlertEvent::ALL.len());
+
+        let args = ["snarkos", "--alert-webhook", "https://example.com/alerts", "--alert-events", "partition"];
+        let config = Start::try_parse_from(args.iter()).unwrap();
+        assert_eq!(config.parse_alert_notifier().unwrap().unwrap().events().len(), 1);
+
+        let args = ["snarkos", "--alert-webhook", "https://example.com/alerts", "--alert-events", "unknown"];
+        let config = Start::try_parse_from(args.iter()).unwrap();
+        assert!(config.parse_alert_notifier().is_err());
+        let config = Start::try_parse_from(["snarkos", "--alert-webhook", "not a url"].iter()).unwrap();
+        assert!(config.parse_alert_notifier().is_err());
+
+        // Ensure the webhook notifier is passed to the node.
+        let config =
+            Start::try_parse_from(["snarkos", "--alert-webhook", "https://example.com/alerts"].iter()).unwrap();
+        assert!(config.parse_node_config().unwrap().alert_notifier.is_some());
+    }
+
+    #[test]
+    fn test_parse_ban_policy() {
+        // Ensure the ban policy is available to every node type, with a nonzero lifetime.
+        let args = ["snarkos", "--client", "--ban-file", "bans.txt", "--max-ban-lifetime-secs", "60"];
+        let config = Start::try_parse_from(args.iter()).unwrap().parse_node_config().unwrap();
+        assert_eq!(config.ban_policy, Some(BanPolicy::new(Duration::from_secs(60)).with_path("bans.txt")));
+        let args = ["snarkos", "--validator", "--ban-file", "bans.txt"];
+        let config = Start::try_parse_from(args.iter()).unwrap().parse_node_config().unwrap();
+        assert_eq!(config.ban_policy.unwrap().max_lifetime(), BanPolicy::DEFAULT_MAX_LIFETIME);
+        let args = ["snarkos", "--prover", "--max-ban-lifetime-secs", "0"];
+        assert!(Start::try_parse_from(args.iter()).unwrap().parse_node_config().is_err());
+    }
+
+    #[test]
+    fn test_parse_max_outbound_bytes_per_sec() {
+        // Ensure the outbound rate is unlimited by default, and its cap is nonzero.
+        let args = ["snarkos", "--client"];
+        let config = Start::try_parse_from(args.iter()).unwrap().parse_node_config().unwrap();
+        assert_eq!(config.max_outbound_bytes_per_sec, None);
+        let args = ["snarkos", "--client", "--max-outbound-bytes-per-sec", "1000000"];
+        let config = Start::try_parse_from(args.iter()).unwrap().parse_node_config().unwrap();
+        assert_eq!(config.max_outbound_bytes_per_sec, Some(1_000_000));
+        let args = ["snarkos", "--validator", "--max-outbound-bytes-per-sec", "0"];
+        assert!(Start::try_parse_from(args.iter()).unwrap().parse_node_config().is_err());
+    }
+
+    #[test]
+    fn test_parse_sync_request_limits() {
+        // Ensure the sync requests are unlimited by default, and their limits are validated.
+        let args = ["snarkos", "--client"];
+        let config = Start::try_parse_from(args.iter()).unwrap().parse_node_config().unwrap();
+        assert_eq!(config.sync_request_limits, None);
+        let args = ["snarkos", "--client", "--max-sync-requests", "8,32,2"];
+        let config = Start::try_parse_from(args.iter()).unwrap().parse_node_config().unwrap();
+        assert_eq!(config.sync_request_limits, Some(SyncRequestLimits::new(8, 32, 2).unwrap()));
+        let args = ["snarkos", "--validator", "--max-sync-requests", "2,32,2"];
+        assert!(Start::try_parse_from(args.iter()).unwrap().parse_node_config().is_err());
+        let args = ["snarkos", "--validator", "--max-sync-requests", "8,32"];
+        assert!(Start::try_parse_from(args.iter()).unwrap().parse_node_config().is_err());
+    }
+
+    #[test]
+    fn test_parse_read_replica() {
+        // Ensure the read replica mode is passed to the client.
+        let args = ["snarkos", "--client", "--read-replica", "--peers", "127.0.0.1:4130"];
+        assert!(Start::try_parse_from(args.iter()).unwrap().parse_node_config().unwrap().read_replica);
+        let args = ["snarkos", "--validator", "--read-replica"];
+        assert!(Start::try_parse_from(args.iter()).unwrap().parse_node_config().is_err());
+    }
+
+    #[test]
+    fn test_parse_gc_memory_policy() {
+        // Ensure the GC memory policy is disabled by default, and its rounds and watermarks are passed to the node.
+        let config = Start::try_parse_from(["snarkos", "--validator"].iter()).unwrap();
+        assert!(config.parse_node_config().unwrap().gc_memory_policy.is_none());
+
+        let args = ["snarkos", "--validator", "--gc-retained-rounds", "100", "--gc-memory-watermarks", "1024,2048"];
+        let config = Start::try_parse_from(args.iter()).unwrap();
+        let policy = config.parse_node_config().unwrap().gc_memory_policy.unwrap();
+        assert_eq!(policy.max_retained_rounds(), 100);
+        assert_eq!(policy.low_watermark(), 1024 * BYTES_PER_MIB);
+        assert_eq!(policy.high_watermark(), 2048 * BYTES_PER_MIB);
+
+        // Ensure the policy never retains fewer rounds than the BFT requires.
+        let config = Start::try_parse_from(["snarkos", "--validator", "--gc-retained-rounds", "1"].iter()).unwrap();
+        assert!(config.parse_node_config().is_err());
+        // Ensure the policy requires a validator, and well-formed watermarks.
+        let config = Start::try_parse_from(["snarkos", "--client", "--gc-retained-rounds", "100"].iter()).unwrap();
+        assert!(config.parse_node_config().is_err());
+        let args = ["snarkos", "--validator", "--gc-retained-rounds", "100", "--gc-memory-watermarks", "2048,1024"];
+        assert!(Start::try_parse_from(args.iter()).unwrap().parse_node_config().is_err());
+        let args = ["snarkos", "--validator", "--gc-retained-rounds", "100", "--gc-memory-watermarks", "1024"];
+        assert!(Start::try_parse_from(args.iter()).unwrap().parse_node_config().is_err());
+    }
+
+    #[test]
+    fn test_parse_standby() {
+        // Ensure the warm standby requires a validator, with the admin listener to promote it.
+        let args = ["snarkos", "--validator", "--standby", "--admin", "127.0.0.1:3034"];
+        assert!(Start::try_parse_from(args.iter()).unwrap().parse_node_config().unwrap().standby);
+        let config = Start::try_parse_from(["snarkos", "--validator", "--standby"].iter()).unwrap();
+        assert!(config.parse_node_config().is_err());
+        let args = ["snarkos", "--client", "--standby", "--admin", "127.0.0.1:3034"];
+        assert!(Start::try_parse_from(args.iter()).unwrap().parse_node_config().is_err());
+    }
+
+    #[test]
+    fn test_parse_block_export() {
+        // Ensure the block export requires a validator.
+        let args = ["snarkos", "--validator", "--export-blocks", "/tmp/blocks.jsonl"];
+        let config = Start::try_parse_from(args.iter()).unwrap().parse_node_config().unwrap();
+        assert_eq!(config.block_export, Some(BlockExportTarget::File(PathBuf::from("/tmp/blocks.jsonl"))));
+        let args = ["snarkos", "--client", "--export-blocks", "/tmp/blocks.jsonl"];
+        assert!(Start::try_parse_from(args.iter()).unwrap().parse_node_config().is_err());
+    }
+
+    #[test]
+    fn test_parse_index_export() {
+        // Ensure the index export requires a validator.
+        let args = ["snarkos", "--validator", "--export-index", "/tmp/index.jsonl"];
+        let config = Start::try_parse_from(args.iter()).unwrap().parse_node_config().unwrap();
+        assert_eq!(config.index_export, Some(PathBuf::from("/tmp/index.jsonl")));
+        let args = ["snarkos", "--client", "--export-index", "/tmp/index.jsonl"];
+        assert!(Start::try_parse_from(args.iter()).unwrap().parse_node_config().is_err());
+    }
+
+    #[test]
+    fn test_parse_max_transmissions_per_submitter() {
+        // Ensure the fairness cap requires a validator, and is nonzero.
+        let args = ["snarkos", "--validator", "--max-transmissions-per-submitter", "8"];
+        let config = Start::try_parse_from(args.iter()).unwrap().parse_node_config().unwrap();
+        assert_eq!(config.max_transmissions_per_submitter, Some(8));
+        let args = ["snarkos", "--validator", "--max-transmissions-per-submitter", "0"];
+        assert!(Start::try_parse_from(args.iter()).unwrap().parse_node_config().is_err());
+        let args = ["snarkos", "--client", "--max-transmissions-per-submitter", "8"];
+        assert!(Start::try_parse_from(args.iter()).unwrap().parse_node_config().is_err());
+    }
+
+    #[test]
+    fn test_parse_max_pinned_transmissions() {
+        // Ensure pinning is disabled by default, and the maximum requires a validator, and is nonzero.
+        let args = ["snarkos", "--validator"];
+        let config = Start::try_parse_from(args.iter()).unwrap().parse_node_config().unwrap();
+        assert_eq!(config.max_pinned_transmissions, None);
+        let args = ["snarkos", "--validator", "--max-pinned-transmissions", "4"];
+        let config = Start::try_parse_from(args.iter()).unwrap().parse_node_config().unwrap();
+        assert_eq!(config.max_pinned_transmissions, Some(4));
+        let args = ["snarkos", "--validator", "--max-pinned-transmissions", "0"];
+        assert!(Start::try_parse_from(args.iter()).unwrap().parse_node_config().is_err());
+        let args = ["snarkos", "--client", "--max-pinned-transmissions", "4"];
+        assert!(Start::try_parse_from(args.iter()).unwrap().parse_node_config().is_err());
+    }
+
+    #[test]
+    fn test_parse_cache_windows() {
+        // Ensure the gateway cache windows require a validator, and are nonzero.
+        let args =
+            ["snarkos", "--validator", "--cache-events-window-rounds", "2", "--cache-requests-window-rounds", "3"];
+        let config = Start::try_parse_from(args.iter()).unwrap().parse_node_config().unwrap();
+        assert_eq!(config.cache_events_window, Some(CacheWindow::Rounds(2)));
+        assert_eq!(config.cache_requests_window, Some(CacheWindow::Rounds(3)));
+        let args = ["snarkos", "--validator", "--cache-events-window-rounds", "0"];
+        assert!(Start::try_parse_from(args.iter()).unwrap().parse_node_config().is_err());
+        let args = ["snarkos", "--client", "--cache-requests-window-rounds", "3"];
+        assert!(Start::try_parse_from(args.iter()).unwrap().parse_node_config().is_err());
+        let args = ["snarkos", "--validator", "--cache-spam-threshold", "100"];
+        let config = Start::try_parse_from(args.iter()).unwrap().parse_node_config().unwrap();
+        assert_eq!(config.cache_spam_threshold, Some(100));
+        let args = ["snarkos", "--validator", "--cache-spam-threshold", "0"];
+        assert!(Start::try_parse_from(args.iter()).unwrap().parse_node_config().is_err());
+        let args = ["snarkos", "--client", "--cache-spam-threshold", "100"];
+        assert!(Start::try_parse_from(args.iter()).unwrap().parse_node_config().is_err());
+    }
+
+    #[test]
+    fn test_parse_worker_affinity() {
+        // Ensure the worker affinity requires a validator, and valid worker IDs.
+        let args = ["snarkos", "--validator"];
+        assert_eq!(Start::try_parse_from(args.iter()).unwrap().parse_node_config().unwrap().worker_affinity, None);
+        let args = ["snarkos", "--validator", "--solution-workers", "0"];
+        let config = Start::try_parse_from(args.iter()).unwrap().parse_node_config().unwrap();
+        assert_eq!(config.worker_affinity, Some(WorkerAffinity::new(vec![0], vec![]).unwrap()));
+        let args = ["snarkos", "--validator", "--solution-workers", "0", "--transaction-workers", "1,2"];
+        let config = Start::try_parse_from(args.iter()).unwrap().parse_node_config().unwrap();
+        assert_eq!(config.worker_affinity, Some(WorkerAffinity::new(vec![0], vec![1, 2]).unwrap()));
+        let args = ["snarkos", "--validator", "--transaction-workers", "1,x"];
+        assert!(Start::try_parse_from(args.iter()).unwrap().parse_node_config().is_err());
+        let args = ["snarkos", "--validator", "--transaction-workers", "255"];
+        assert!(Start::try_parse_from(args.iter()).unwrap().parse_node_config().is_err());
+        let args = ["snarkos", "--client", "--solution-workers", "0"];
+        assert!(Start::try_parse_from(args.iter()).unwrap().parse_node_config().is_err());
+    }
+
+    #[test]
+    fn test_parse_watchdog() {
+        // Ensure the watchdog requires a validator, and a timeout beyond the maximum batch delay.
+        let args = ["snarkos", "--validator"];
+        let config = Start::try_parse_from(args.iter()).unwrap().parse_node_config().unwrap();
+        assert_eq!((config.watchdog_timeout, config.watchdog_action), (None, WatchdogAction::Restart));
+        let args = ["snarkos", "--validator", "--watchdog-timeout-ms", "10000"];
+        let config = Start::try_parse_from(args.iter()).unwrap().parse_node_config().unwrap();
+        assert_eq!(config.watchdog_timeout, Some(Duration::from_millis(10000)));
+        assert_eq!(config.watchdog_action, WatchdogAction::Restart);
+        let args = ["snarkos", "--validator", "--watchdog-timeout-ms", "10000", "--watchdog-exit"];
+        let config = Start::try_parse_from(args.iter()).unwrap().parse_node_config().unwrap();
+        assert_eq!(config.watchdog_action, WatchdogAction::Exit);
+        let args = ["snarkos", "--validator", "--watchdog-timeout-ms", "1000"];
+        assert!(Start::try_parse_from(args.iter()).unwrap().parse_node_config().is_err());
+        let args = ["snarkos", "--validator", "--watchdog-exit"];
+        assert!(Start::try_parse_from(args.iter()).unwrap().parse_node_config().is_err());
+        let args = ["snarkos", "--client", "--watchdog-timeout-ms", "10000"];
+        assert!(Start::try_parse_from(args.iter()).unwrap().parse_node_config().is_err());
+    }
+
+    #[test]
+    fn test_parse_sender_rate_limit() {
+        // Ensure the sender rate limit requires a validator, and a nonzero limit and interval.
+        let args = ["snarkos", "--validator"];
+        assert_eq!(Start::try_parse_from(args.iter()).unwrap().parse_node_config().unwrap().sender_rate_limit, None);
+        let args = ["snarkos", "--validator", "--max-transactions-per-sender", "10"];
+        let config = Start::try_parse_from(args.iter()).unwrap().parse_node_config().unwrap();
+        assert_eq!(config.sender_rate_limit, Some((10, 60)));
+        let args =
+            ["snarkos", "--validator", "--max-transactions-per-sender", "10", "--sender-rate-limit-interval-secs", "5"];
+        let config = Start::try_parse_from(args.iter()).unwrap().parse_node_config().unwrap();
+        assert_eq!(config.sender_rate_limit, Some((10, 5)));
+        let args = ["snarkos", "--validator", "--max-transactions-per-sender", "0"];
+        assert!(Start::try_parse_from(args.iter()).unwrap().parse_node_config().is_err());
+        let args =
+            ["snarkos", "--validator", "--max-transactions-per-sender", "10", "--sender-rate-limit-interval-secs", "0"];
+        assert!(Start::try_parse_from(args.iter()).unwrap().parse_node_config().is_err());
+        let args = ["snarkos", "--client", "--max-transactions-per-sender", "10"];
+        assert!(Start::try_parse_from(args.iter()).unwrap().parse_node_config().is_err());
+    }
+
+    #[test]
+    fn test_parse_dag_snapshots() {
+        // Ensure the DAG snapshots require a validator, and a nonzero maximum.
+        let args = ["snarkos", "--validator", "--max-dag-snapshot-certificates", "1000", "--dag-snapshot-warm-up"];
+        let config = Start::try_parse_from(args.iter()).unwrap().parse_node_config().unwrap();
+        assert_eq!((config.max_dag_snapshot_certificates, config.dag_snapshot_warm_up), (Some(1000), true));
+        let args = ["snarkos", "--validator", "--max-dag-snapshot-certificates", "0"];
+        assert!(Start::try_parse_from(args.iter()).unwrap().parse_node_config().is_err());
+        let args = ["snarkos", "--client", "--max-dag-snapshot-certificates", "1000"];
+        assert!(Start::try_parse_from(args.iter()).unwrap().parse_node_config().is_err());
+        let args = ["snarkos", "--client", "--dag-snapshot-warm-up"];
+        assert!(Start::try_parse_from(args.iter()).unwrap().parse_node_config().is_err());
+    }
+
+    #[test]
+    fn test_parse_probe_sources() {
+        // Ensure the probe sources require a validator, and well-formed IPs.
+        let args = ["snarkos", "--validator"];
+        assert!(Start::try_parse_from(args.iter()).unwrap().parse_node_config().unwrap().probe_sources.is_empty());
+        let args = ["snarkos", "--validator", "--probe-sources", "10.0.0.1, ::1"];
+        let config = Start::try_parse_from(args.iter()).unwrap().parse_node_config().unwrap();
+        let expected = vec![IpAddr::from([10, 0, 0, 1]), IpAddr::from(std::net::Ipv6Addr::LOCALHOST)];
+        assert_eq!(config.probe_sources, expected);
+        let args = ["snarkos", "--validator", "--probe-sources", "10.0.0.1,localhost"];
+        assert!(Start::try_parse_from(args.iter()).unwrap().parse_node_config().is_err());
+        let args = ["snarkos", "--client", "--probe-sources", "10.0.0.1"];
+        assert!(Start::try_parse_from(args.iter()).unwrap().parse_node_config().is_err());
+    }
+
+    #[test]
+    fn test_parse_min_previous_certificates() {
+        // Ensure the minimum number of previous certificates requires a validator, and is achievable by a committee.
+        let args = ["snarkos", "--validator"];
+        let config = Start::try_parse_from(args.iter()).unwrap().parse_node_config().unwrap();
+        assert_eq!(config.min_previous_certificates, 0);
+        let args = ["snarkos", "--validator", "--min-previous-certificates", "10"];
+        let config = Start::try_parse_from(args.iter()).unwrap().parse_node_config().unwrap();
+        assert_eq!(config.min_previous_certificates, 10);
+        let max_certificates = (Committee::<CurrentNetwork>::MAX_COMMITTEE_SIZE as usize + 1).to_string();
+        let args = ["snarkos", "--validator", "--min-previous-certificates", &max_certificates];
+        assert!(Start::try_parse_from(args.iter()).unwrap().parse_node_config().is_err());
+        let args = ["snarkos", "--client", "--min-previous-certificates", "10"];
+        assert!(Start::try_parse_from(args.iter()).unwrap().parse_node_config().is_err());
+    }
+
+    #[test]
+    fn test_parse_stale_proposal_policy() {
+        // Ensure the handling of stale proposals requires a validator, and a known option.
+        let args = ["snarkos", "--validator"];
+        let config = Start::try_parse_from(args.iter()).unwrap().parse_node_config().unwrap();
+        assert_eq!(config.stale_proposal_policy, StaleProposalPolicy::Process);
+        let args = ["snarkos", "--validator", "--stale-proposals", "sync"];
+        let config = Start::try_parse_from(args.iter()).unwrap().parse_node_config().unwrap();
+        assert_eq!(config.stale_proposal_policy, StaleProposalPolicy::RefuseAndSync);
+        let args = ["snarkos", "--validator", "--stale-proposals", "ignore"];
+        assert!(Start::try_parse_from(args.iter()).unwrap().parse_node_config().is_err());
+        let args = ["snarkos", "--client", "--stale-proposals", "refuse"];
+        assert!(Start::try_parse_from(args.iter()).unwrap().parse_node_config().is_err());
+    }
+
+    #[test]
+    fn test_parse_max_committee_change() {
+        // Ensure the maximum committee change requires a validator, and is a fraction.
+        let args = ["snarkos", "--validator", "--max-committee-change", "0.33"];
+        let config = Start::try_parse_from(args.iter()).unwrap().parse_node_config().unwrap();
+        assert_eq!(config.max_committee_change, Some(0.33));
+        let args = ["snarkos", "--validator", "--max-committee-change", "1.5"];
+        assert!(Start::try_parse_from(args.iter()).unwrap().parse_node_config().is_err());
+        let args = ["snarkos", "--client", "--max-committee-change", "0.33"];
+        assert!(Start::try_parse_from(args.iter()).unwrap().parse_node_config().is_err());
+    }
+
+    #[test]
+    fn test_parse_certificate_retry_policy() {
+        // Ensure the certificate retry policy requires a validator, and at least one attempt.
+        let args =
+            ["snarkos", "--validator", "--certificate-retry-attempts", "5", "--certificate-retry-delay-ms", "100"];
+        let config = Start::try_parse_from(args.iter()).unwrap().parse_node_config().unwrap();
+        assert_eq!(config.certificate_retry_policy, Some(RetryPolicy::new(5, 100).unwrap()));
+        let args = ["snarkos", "--validator", "--certificate-retry-attempts", "0"];
+        assert!(Start::try_parse_from(args.iter()).unwrap().parse_node_config().is_err());
+        let args = ["snarkos", "--client", "--certificate-retry-attempts", "5"];
+        assert!(Start::try_parse_from(args.iter()).unwrap().parse_node_config().is_err());
+    }
+
+    #[test]
+    fn test_parse_max_open_rounds() {
+        // Ensure the open rounds require a validator, and are nonzero.
+        let args = ["snarkos", "--validator", "--max-open-rounds", "200"];
+        assert_eq!(Start::try_parse_from(args.iter()).unwrap().parse_node_config().unwrap().max_open_rounds, Some(200));
+        let args = ["snarkos", "--validator", "--max-open-rounds", "0"];
+        assert!(Start::try_parse_from(args.iter()).unwrap().parse_node_config().is_err());
+        let args = ["snarkos", "--client", "--max-open-rounds", "200"];
+        assert!(Start::try_parse_from(args.iter()).unwrap().parse_node_config().is_err());
+    }
+
+    #[test]
+    fn test_parse_partition_threshold() {
+        // Ensure the partition threshold requires a validator, and is a fraction.
+        let args = ["snarkos", "--validator", "--partition-threshold", "0.5"];
+        let config = Start::try_parse_from(args.iter()).unwrap().parse_node_config().unwrap();
+        assert_eq!(config.partition_threshold, Some(0.5));
+        let args = ["snarkos", "--validator", "--partition-threshold", "1.5"];
+        assert!(Start::try_parse_from(args.iter()).unwrap().parse_node_config().is_err());
+        let args = ["snarkos", "--client", "--partition-threshold", "0.5"];
+        assert!(Start::try_parse_from(args.iter()).unwrap().parse_node_config().is_err());
+    }
+
+    #[test]
+    fn test_parse_maintenance_window() {
+        // Ensure the maintenance window requires a validator, escalating loads, and known tasks.
+        let args = ["snarkos", "--validator", "--maintenance-load", "1000,200"];
+        let config = Start::try_parse_from(args.iter()).unwrap().parse_node_config().unwrap();
+        let tasks = vec!["block_export".to_string(), "metrics".to_string(), "cache_pruning".to_string()];
+        assert_eq!(config.maintenance_window, Some((1000, 200, tasks)));
+        let args = ["snarkos", "--validator", "--maintenance-load", "1000,200", "--maintenance-tasks", "metrics"];
+        let config = Start::try_parse_from(args.iter()).unwrap().parse_node_config().unwrap();
+        assert_eq!(config.maintenance_window, Some((1000, 200, vec!["metrics".to_string()])));
+        let args = ["snarkos", "--validator", "--maintenance-load", "200,1000"];
+        assert!(Start::try_parse_from(args.iter()).unwrap().parse_node_config().is_err());
+        let args = ["snarkos", "--validator", "--maintenance-load", "1000,200", "--maintenance-tasks", "sync"];
+        assert!(Start::try_parse_from(args.iter()).unwrap().parse_node_config().is_err());
+        let args = ["snarkos", "--client", "--maintenance-load", "1000,200"];
+        assert!(Start::try_parse_from(args.iter()).unwrap().parse_node_config().is_err());
+    }
+
+    #[test]
+    fn test_parse_bft_store_compression() {
+        // Ensure the compression of the BFT store requires a validator, and a supported level.
+        let args = ["snarkos", "--validator", "--bft-store-compression", "3"];
+        let config = Start::try_parse_from(args.iter()).unwrap().parse_node_config().unwrap();
+        assert_eq!(config.bft_store_compression, Some(Compression::Zstd(3)));
+        let args = ["snarkos", "--validator", "--bft-store-compression", "1000"];
+        assert!(Start::try_parse_from(args.iter()).unwrap().parse_node_config().is_err());
+        let args = ["snarkos", "--client", "--bft-store-compression", "3"];
+        assert!(Start::try_parse_from(args.iter()).unwrap().parse_node_config().is_err());
+    }
+
+    #[test]
+    fn test_parse_bft_store_degraded_threshold() {
+        // Ensure the degraded mode of the BFT store requires a validator, and a nonzero threshold.
+        let args = ["snarkos", "--validator", "--bft-store-degraded-threshold-ms", "200"];
+        let config = Start::try_parse_from(args.iter()).unwrap().parse_node_config().unwrap();
+        assert_eq!(config.bft_store_degraded_threshold, Some(Duration::from_millis(200)));
+        let args = ["snarkos", "--validator", "--bft-store-degraded-threshold-ms", "0"];
+        assert!(Start::try_parse_from(args.iter()).unwrap().parse_node_config().is_err());
+        let args = ["snarkos", "--client", "--bft-store-degraded-threshold-ms", "200"];
+        assert!(Start::try_parse_from(args.iter()).unwrap().parse_node_config().is_err());
+    }
+
+    #[test]
+    fn test_parse_certificate_penalty_policy() {
+        // Ensure the certificate penalties are disabled by default, and require a validator and escalating thresholds.
+        let args = ["snarkos", "--validator"];
+        let config = Start::try_parse_from(args.iter()).unwrap().parse_node_config().unwrap();
+        assert_eq!(config.certificate_penalty_policy, None);
+        let args = ["snarkos", "--validator", "--certificate-penalties", "1,3,5"];
+        let config = Start::try_parse_from(args.iter()).unwrap().parse_node_config().unwrap();
+        assert_eq!(config.certificate_penalty_policy, Some(CertificatePenaltyPolicy::default()));
+        let args = ["snarkos", "--validator", "--certificate-penalties", "5,3,1"];
+        assert!(Start::try_parse_from(args.iter()).unwrap().parse_node_config().is_err());
+        let args = ["snarkos", "--validator", "--certificate-penalties", "1,3"];
+        assert!(Start::try_parse_from(args.iter()).unwrap().parse_node_config().is_err());
+        let args = ["snarkos", "--client", "--certificate-penalties", "1,3,5"];
+        assert!(Start::try_parse_from(args.iter()).unwrap().parse_node_config().is_err());
+    }
+
+    #[test]
+    fn test_parse_risk_policy() {
+        // Ensure the risk score is disabled by default, and requires a validator and escalating thresholds.
+        let args = ["snarkos", "--validator"];
+        let config = Start::try_parse_from(args.iter()).unwrap().parse_node_config().unwrap();
+        assert_eq!(config.risk_policy, None);
+        let args = ["snarkos", "--validator", "--risk-thresholds", "0.5,0.75,1"];
+        let config = Start::try_parse_from(args.iter()).unwrap().parse_node_config().unwrap();
+        assert_eq!(config.risk_policy, Some(RiskPolicy::default()));
+        let args = ["snarkos", "--validator", "--risk-thresholds", "1,0.75,0.5"];
+        assert!(Start::try_parse_from(args.iter()).unwrap().parse_node_config().is_err());
+        let args = ["snarkos", "--validator", "--risk-thresholds", "0.5,0.75"];
+        assert!(Start::try_parse_from(args.iter()).unwrap().parse_node_config().is_err());
+        let args = ["snarkos", "--client", "--risk-thresholds", "0.5,0.75,1"];
+        assert!(Start::try_parse_from(args.iter()).unwrap().parse_node_config().is_err());
+    }
+
+    #[test]
+    fn test_parse_connection_group_limits() {
+        // Ensure the per-group connection limits require a validator, are nonzero, and the ASN dataset requires
+        // the ASN limit.
+        let args = ["snarkos", "--validator", "--max-connections-per-asn", "64", "--max-connections-per-subnet", "8"];
+        let config = Start::try_parse_from(args.iter()).unwrap().parse_node_config().unwrap();
+        assert_eq!((config.max_connections_per_asn, config.max_connections_per_subnet), (Some(64), Some(8)));
+        assert_eq!(config.asn_dataset, None);
+        let args = ["snarkos", "--validator", "--max-connections-per-asn", "64", "--asn-dataset", "/tmp/ip2asn.tsv"];
+        let config = Start::try_parse_from(args.iter()).unwrap().parse_node_config().unwrap();
+        assert_eq!(config.asn_dataset, Some(PathBuf::from("/tmp/ip2asn.tsv")));
+        let args = ["snarkos", "--validator", "--asn-dataset", "/tmp/ip2asn.tsv"];
+        assert!(Start::try_parse_from(args.iter()).unwrap().parse_node_config().is_err());
+        let args = ["snarkos", "--validator", "--max-connections-per-subnet", "0"];
+        assert!(Start::try_parse_from(args.iter()).unwrap().parse_node_config().is_err());
+        let args = ["snarkos", "--client", "--max-connections-per-asn", "64"];
+        assert!(Start::try_parse_from(args.iter()).unwrap().parse_node_config().is_err());
+    }
+
+    #[test]
+    fn test_parse_admission_receipts() {
+        // Ensure the admission receipts require a validator.
+        let args = ["snarkos", "--validator", "--admission-receipts"];
+        assert!(Start::try_parse_from(args.iter()).unwrap().parse_node_config().unwrap().admission_receipts);
+        let args = ["snarkos", "--client", "--admission-receipts"];
+        assert!(Start::try_parse_from(args.iter()).unwrap().parse_node_config().is_err());
+    }
+
+    #[test]
+    fn test_parse_admission_scorer() {
+        // Ensure the admission scorer requires a validator, an HTTP(S) URL, and a nonzero timeout.
+        let args = ["snarkos", "--validator", "--admission-scorer", "http://127.0.0.1:8080/score"];
+        let config = Start::try_parse_from(args.iter()).unwrap().parse_node_config().unwrap();
+        let (scorer, timeout, fail_open) = config.admission_scorer.unwrap();
+        assert_eq!(scorer.url().as_str(), "http://127.0.0.1:8080/score");
+        assert_eq!(timeout, Duration::from_millis(200));
+        assert!(fail_open);
+        let args =
+            ["snarkos", "--validator", "--admission-scorer", "http://127.0.0.1:8080", "--admission-scorer-fail-closed"];
+        let config = Start::try_parse_from(args.iter()).unwrap().parse_node_config().unwrap();
+        assert!(!config.admission_scorer.unwrap().2);
+        let args = ["snarkos", "--validator", "--admission-scorer", "ftp://127.0.0.1"];
+        assert!(Start::try_parse_from(args.iter()).unwrap().parse_node_config().is_err());
+        let args =
+            ["snarkos", "--validator", "--admission-scorer", "http://127.0.0.1", "--admission-scorer-timeout-ms", "0"];
+        assert!(Start::try_parse_from(args.iter()).unwrap().parse_node_config().is_err());
+        let args = ["snarkos", "--client", "--admission-scorer", "http://127.0.0.1:8080"];
+        assert!(Start::try_parse_from(args.iter()).unwrap().parse_node_config().is_err());
+    }
+
+    #[test]
+    fn test_parse_replacement_fee_bump() {
+        // Ensure transaction replacement requires a validator.
+        let args = ["snarkos", "--validator", "--replacement-fee-bump", "25"];
+        let config = Start::try_parse_from(args.iter()).unwrap().parse_node_config().unwrap();
+        assert_eq!(config.replacement_fee_bump_percent, Some(25));
+        let args = ["snarkos", "--client", "--replacement-fee-bump", "25"];
+        assert!(Start::try_parse_from(args.iter()).unwrap().parse_node_config().is_err());
+    }
+
+    #[test]
+    fn test_parse_unknown_program_policy() {
+        // Ensure the handling of unknown programs requires a validator, and a known option.
+        let args = ["snarkos", "--validator", "--unknown-programs", "defer"];
+        let config = Start::try_parse_from(args.iter()).unwrap().parse_node_config().unwrap();
+        assert_eq!(config.unknown_program_policy, Some(UnknownProgramPolicy::Defer));
+        let args = ["snarkos", "--validator", "--unknown-programs", "ignore"];
+        assert!(Start::try_parse_from(args.iter()).unwrap().parse_node_config().is_err());
+        let args = ["snarkos", "--client", "--unknown-programs", "reject"];
+        assert!(Start::try_parse_from(args.iter()).unwrap().parse_node_config().is_err());
+    }
+
+    #[test]
+    fn test_parse_admission_queue() {
+        // Ensure the admission queue requires a validator, a nonzero capacity, and a known order.
+        let args = ["snarkos", "--validator", "--admission-queue-capacity", "100"];
+        let config = Start::try_parse_from(args.iter()).unwrap().parse_node_config().unwrap();
+        assert_eq!(config.admission_queue, Some((AdmissionOrder::Fifo, 100)));
+        let args = ["snarkos", "--validator", "--admission-queue-capacity", "100", "--admission-order", "fee"];
+        let config = Start::try_parse_from(args.iter()).unwrap().parse_node_config().unwrap();
+        assert_eq!(config.admission_queue, Some((AdmissionOrder::Fee, 100)));
+        let args = ["snarkos", "--validator", "--admission-queue-capacity", "100", "--admission-order", "random"];
+        assert!(Start::try_parse_from(args.iter()).unwrap().parse_node_config().is_err());
+        let args = ["snarkos", "--validator", "--admission-queue-capacity", "0"];
+        assert!(Start::try_parse_from(args.iter()).unwrap().parse_node_config().is_err());
+        let args = ["snarkos", "--validator", "--admission-order", "fee"];
+        assert!(Start::try_parse_from(args.iter()).unwrap().parse_node_config().is_err());
+        let args = ["snarkos", "--client", "--admission-queue-capacity", "100"];
+        assert!(Start::try_parse_from(args.iter()).unwrap().parse_node_config().is_err());
+    }
+
+    #[test]
+    fn test_parse_transient_rejection_window() {
+        // Ensure the retention of transient rejections requires an admission queue, and a nonzero window.
+        let args =
+            ["snarkos", "--validator", "--admission-queue-capacity", "100", "--transient-rejection-window-secs", "30"];
+        let config = Start::try_parse_from(args.iter()).unwrap().parse_node_config().unwrap();
+        assert_eq!(config.transient_rejection_window, Some(Duration::from_secs(30)));
+        let args =
+            ["snarkos", "--validator", "--admission-queue-capacity", "100", "--transient-rejection-window-secs", "0"];
+        assert!(Start::try_parse_from(args.iter()).unwrap().parse_node_config().is_err());
+        let args = ["snarkos", "--validator", "--transient-rejection-window-secs", "30"];
+        assert!(Start::try_parse_from(args.iter()).unwrap().parse_node_config().is_err());
+    }
+
+    #[test]
+    fn test_parse_rest_shed_load() {
+        // Ensure the REST load threshold requires a validator with the REST server, and is positive.
+        let args = ["snarkos", "--validator", "--rest-shed-load", "0.9"];
+        let config = Start::try_parse_from(args.iter()).unwrap().parse_node_config().unwrap();
+        assert_eq!(config.rest_shed_load_threshold, Some(0.9));
+        let args = ["snarkos", "--validator", "--rest-shed-load", "0"];
+        assert!(Start::try_parse_from(args.iter()).unwrap().parse_node_config().is_err());
+        let args = ["snarkos", "--validator", "--norest", "--rest-shed-load", "0.9"];
+        assert!(Start::try_parse_from(args.iter()).unwrap().parse_node_config().is_err());
+        let args = ["snarkos", "--client", "--rest-shed-load", "0.9"];
+        assert!(Start::try_parse_from(args.iter()).unwrap().parse_node_config().is_err());
+    }
+
+    #[test]
+    fn test_parse_rest_cache_ttl() {
+        // Ensure the REST response cache is disabled by default, and requires the REST server and a nonzero TTL.
+        let args = ["snarkos", "--client"];
+        let config = Start::try_parse_from(args.iter()).unwrap().parse_node_config().unwrap();
+        assert_eq!(config.rest_cache_ttl, None);
+        let args = ["snarkos", "--client", "--rest-cache-ttl-ms", "500"];
+        let config = Start::try_parse_from(args.iter()).unwrap().parse_node_config().unwrap();
+        assert_eq!(config.rest_cache_ttl, Some(Duration::from_millis(500)));
+        let args = ["snarkos", "--client", "--rest-cache-ttl-ms", "0"];
+        assert!(Start::try_parse_from(args.iter()).unwrap().parse_node_config().is_err());
+        let args = ["snarkos", "--client", "--norest", "--rest-cache-ttl-ms", "500"];
+        assert!(Start::try_parse_from(args.iter()).unwrap().parse_node_config().is_err());
+    }
+
+    #[test]
+    fn test_parse_rest_reject_duplicates() {
+        // Ensure the duplicate submissions are idempotent by default, and their rejection requires the REST server.
+        let args = ["snarkos", "--client"];
+        assert!(!Start::try_parse_from(args.iter()).unwrap().parse_node_config().unwrap().rest_reject_duplicates);
+        let args = ["snarkos", "--client", "--rest-reject-duplicates"];
+        assert!(Start::try_parse_from(args.iter()).unwrap().parse_node_config().unwrap().rest_reject_duplicates);
+        let args = ["snarkos", "--client", "--norest", "--rest-reject-duplicates"];
+        assert!(Start::try_parse_from(args.iter()).unwrap().parse_node_config().is_err());
+    }
+
+    #[test]
+    fn test_dry_run() {
+        // Ensure a bad configuration fails the dry run, with the failed checks in the report.
+        let args = ["snarkos", "--dry-run", "--validator", "--peers", "127.0.0.1:4130,bad", "--norest"];
+        let report = Start::try_parse_from(args.iter()).unwrap().parse().unwrap_err().to_string();
+        assert!(report.contains("❌ Configurations"));
+        assert!(report.contains("The '--peers' flag has a malformed IP"));
+        assert!(report.contains("❌ Private key"));
+        assert!(report.contains("Missing the '--private-key' or '--private-key-file' argument"));
+        assert!(report.contains("⏭️  Committee"));
+        assert!(report.contains("⏭️  Connectivity"));
+
+        // Ensure an unreachable trusted peer fails the dry run.
+        let unreachable_ip = TcpListener::bind("127.0.0.1:0").unwrap().local_addr().unwrap().to_string();
+        let args =
+            ["snarkos", "--dry-run", "--client", "--node", "127.0.0.1:0", "--norest", "--peers", &unreachable_ip];
+        let report = Start::try_parse_from(args.iter()).unwrap().parse().unwrap_err().to_string();
+        assert!(report.contains("❌ Connectivity"));
+        assert!(report.contains(&unreachable_ip));
+
+        // Ensure a valid configuration passes the dry run.
+        let peer = TcpListener::bind("127.0.0.1:0").unwrap();
+        let peer_ip = peer.local_addr().unwrap().to_string();
+        let args = ["snarkos", "--dry-run", "--client", "--node", "127.0.0.1:0", "--norest", "--peers", &peer_ip];
+        let report = Start::try_parse_from(args.iter()).unwrap().parse().unwrap();
+        assert!(report.contains("✅ Configurations"));
+        assert!(report.contains("✅ Private key"));
+        assert!(report.contains("⏭️  Committee"));
+        assert!(report.contains("✅ Connectivity - Reached all 1 trusted peers"));
+    }
+
     #[test]
     fn test_parse_cdn() {
         // Validator (Prod)
@@ -18,19 +18,17 @@ use super::*;
 pub struct BatchPropose<N: Network> {
     pub round: u64,
     pub batch_header: Data<BatchHeader<N>>,
+    /// The number of workers of the proposer.
+    pub num_workers: u8,
+    /// The worker ID of the proposer that holds each transmission ID, in the order of the batch header.
+    /// Note: The worker IDs are not signed, so they are only a hint for routing the transmission requests.
+    pub worker_ids: Vec<u8>,
 }
 
 impl<N: Network> BatchPropose<N> {
     /// Initializes a new batch propose event.
-    pub fn new(round: u64, batch_header: Data<BatchHeader<N>>) -> Self {
-        Self { round, batch_header }
-    }
-}
-
-impl<N: Network> From<BatchHeader<N>> for BatchPropose<N> {
-    /// Initializes a new batch propose event.
-    fn from(batch_header: BatchHeader<N>) -> Self {
-        Self::new(batch_header.round(), Data::Object(batch_header))
+    pub fn new(round: u64, batch_header: Data<BatchHeader<N>>, num_workers: u8, worker_ids: Vec<u8>) -> Self {
+        Self { round, batch_header, num_workers, worker_ids }
     }
 }
 
@@ -46,6 +44,9 @@ impl<N: Network> ToBytes for BatchPropose<N> {
     fn write_le<W: Write>(&self, mut writer: W) -> IoResult<()> {
         self.round.write_le(&mut writer)?;
         self.batch_header.write_le(&mut writer)?;
+        self.num_workers.write_le(&mut writer)?;
+        u16::try_from(self.worker_ids.len()).map_err(error)?.write_le(&mut writer)?;
+        writer.write_all(&self.worker_ids)?;
         Ok(())
     }
 }
@@ -54,8 +55,12 @@ impl<N: Network> FromBytes for BatchPropose<N> {
     fn read_le<R: Read>(mut reader: R) -> IoResult<Self> {
         let round = u64::read_le(&mut reader)?;
         let batch_header = Data::read_le(&mut reader)?;
+        let num_workers = u8::read_le(&mut reader)?;
+        let num_worker_ids = u16::read_le(&mut reader)?;
+        let mut worker_ids = vec![0u8; num_worker_ids as usize];
+        reader.read_exact(&mut worker_ids)?;
 
-        Ok(Self { round, batch_header })
+        Ok(Self { round, batch_header, num_workers, worker_ids })
     }
 }
 
@@ -76,8 +81,10 @@ pub mod prop_tests {
 
     pub fn any_batch_propose() -> BoxedStrategy<BatchPropose<CurrentNetwork>> {
         any::<CommitteeContext>()
-            .prop_flat_map(|committee| (any::<u64>(), any_batch_header(&committee)))
-            .prop_map(|(round, batch_header)| BatchPropose::new(round, Data::Object(batch_header)))
+            .prop_flat_map(|committee| (any::<u64>(), any_batch_header(&committee), any::<u8>(), any::<Vec<u8>>()))
+            .prop_map(|(round, batch_header, num_workers, worker_ids)| {
+                BatchPropose::new(round, Data::Object(batch_header), num_workers, worker_ids)
+            })
             .boxed()
     }
 
@@ -89,6 +96,8 @@ pub mod prop_tests {
         let deserialized: BatchPropose<CurrentNetwork> = BatchPropose::read_le(buf.into_inner().reader()).unwrap();
         // because of the Data enum, we cannot compare the structs directly even though it derives PartialEq
         assert_eq!(original.round, deserialized.round);
+        assert_eq!(original.num_workers, deserialized.num_workers);
+        assert_eq!(original.worker_ids, deserialized.worker_ids);
         assert_eq!(
             original.batch_header.deserialize_blocking().unwrap(),
             deserialized.batch_header.deserialize_blocking().unwrap()
@@ -0,0 +1,385 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkOS library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::*;
+
+use bytes::{Bytes, BytesMut};
+use std::marker::PhantomData;
+
+/// A chunk of a serialized block response, for block responses that exceed the chunk size.
+#[derive(Clone, PartialEq, Eq)]
+pub struct BlockResponseChunk {
+    /// The original block request.
+    pub request: BlockRequest,
+    /// The index of this chunk.
+    pub index: u16,
+    /// The total number of chunks.
+    pub num_chunks: u16,
+    /// The total size (in bytes) of the serialized blocks.
+    pub total_size: u32,
+    /// The bytes of this chunk.
+    pub bytes: Bytes,
+}
+
+impl BlockResponseChunk {
+    /// The maximum size (in bytes) of a single chunk, above which a block response is chunked.
+    pub const CHUNK_SIZE: usize = 8 * 1024 * 1024;
+
+    /// Serializes the given blocks into chunks of at most the given chunk size, and passes each chunk to `on_chunk`
+    /// as soon as it is filled, so that at most one chunk of the serialized blocks is buffered at a time.
+    /// Returns `false`, without passing on any chunk, if the blocks fit within a single chunk and need not be chunked.
+    pub fn stream<N: Network>(
+        request: BlockRequest,
+        blocks: &DataBlocks<N>,
+        chunk_size: usize,
+        on_chunk: impl FnMut(Self) -> Result<()>,
+    ) -> Result<bool> {
+        // Ensure the chunk size is valid.
+        ensure!(chunk_size > 0 && chunk_size <= Self::CHUNK_SIZE, "Invalid block response chunk size ({chunk_size})");
+        // Determine the total size of the serialized blocks, without buffering them.
+        let mut counter = ByteCounter(0);
+        blocks.write_le(&mut counter)?;
+        // If the blocks fit within a single chunk, return early.
+        if counter.0 <= chunk_size {
+            return Ok(false);
+        }
+        // Retrieve the total size and number of chunks.
+        let total_size = u32::try_from(counter.0)?;
+        let num_chunks = u16::try_from(counter.0.div_ceil(chunk_size))?;
+        // Serialize the blocks, passing on the chunks as they are filled.
+        let mut writer = ChunkWriter {
+            request,
+            num_chunks,
+            total_size,
+            chunk_size,
+            index: 0,
+            buffer: BytesMut::with_capacity(chunk_size),
+            on_chunk,
+        };
+        blocks.write_le(&mut writer)?;
+        // Pass on the last chunk, if it is partially filled.
+        if !writer.buffer.is_empty() {
+            writer.emit()?;
+        }
+        ensure!(writer.index == num_chunks, "Block response was serialized into an unexpected number of chunks");
+        Ok(true)
+    }
+}
+
+/// A writer that counts the number of bytes written to it.
+struct ByteCounter(usize);
+
+impl Write for ByteCounter {
+    fn write(&mut self, bytes: &[u8]) -> IoResult<usize> {
+        self.0 += bytes.len();
+        Ok(bytes.len())
+    }
+
+    fn flush(&mut self) -> IoResult<()> {
+        Ok(())
+    }
+}
+
+/// A writer that cuts the serialized blocks into chunks, and passes on each chunk as soon as it is filled.
+struct ChunkWriter<F: FnMut(BlockResponseChunk) -> Result<()>> {
+    /// The original block request.
+    request: BlockRequest,
+    /// The total number of chunks.
+    num_chunks: u16,
+    /// The total size (in bytes) of the serialized blocks.
+    total_size: u32,
+    /// The maximum size (in bytes) of a chunk.
+    chunk_size: usize,
+    /// The index of the chunk being filled.
+    index: u16,
+    /// The bytes of the chunk being filled.
+    buffer: BytesMut,
+    /// The function that is passed each chunk.
+    on_chunk: F,
+}
+
+impl<F: FnMut(BlockResponseChunk) -> Result<()>> ChunkWriter<F> {
+    /// Passes on the chunk being filled, and starts the next chunk.
+    fn emit(&mut self) -> IoResult<()> {
+        let bytes = std::mem::replace(&mut self.buffer, BytesMut::with_capacity(self.chunk_size)).freeze();
+        let chunk = BlockResponseChunk {
+            request: self.request,
+            index: self.index,
+            num_chunks: self.num_chunks,
+            total_size: self.total_size,
+            bytes,
+        };
+        self.index += 1;
+        (self.on_chunk)(chunk).map_err(|err| error(err.to_string()))
+    }
+}
+
+impl<F: FnMut(BlockResponseChunk) -> Result<()>> Write for ChunkWriter<F> {
+    fn write(&mut self, bytes: &[u8]) -> IoResult<usize> {
+        // Fill the chunk, up to the chunk size.
+        let num_bytes = bytes.len().min(self.chunk_size - self.buffer.len());
+        self.buffer.extend_from_slice(&bytes[..num_bytes]);
+        // Pass on the chunk, once it is filled.
+        if self.buffer.len() == self.chunk_size {
+            self.emit()?;
+        }
+        Ok(num_bytes)
+    }
+
+    fn flush(&mut self) -> IoResult<()> {
+        Ok(())
+    }
+}
+
+impl EventTrait for BlockResponseChunk {
+    /// Returns the event name.
+    #[inline]
+    fn name(&self) -> Cow<'static, str> {
+        let start = self.request.start_height;
+        let end = self.request.end_height;
+        let (index, num_chunks) = (self.index + 1, self.num_chunks);
+        match start + 1 == end {
+            true => format!("BlockResponseChunk {start} ({index}/{num_chunks})"),
+            false => format!("BlockResponseChunk {start}..{end} ({index}/{num_chunks})"),
+        }
+        .into()
+    }
+}
+
+impl ToBytes for BlockResponseChunk {
+    fn write_le<W: Write>(&self, mut writer: W) -> IoResult<()> {
+        // Ensure the chunk is within the chunk size.
+        if self.bytes.len() > Self::CHUNK_SIZE {
+            return Err(error("Block response chunk exceeds the maximum chunk size"));
+        }
+        self.request.write_le(&mut writer)?;
+        self.index.write_le(&mut writer)?;
+        self.num_chunks.write_le(&mut writer)?;
+        self.total_size.write_le(&mut writer)?;
+        (self.bytes.len() as u32).write_le(&mut writer)?;
+        writer.write_all(&self.bytes)
+    }
+}
+
+impl FromBytes for BlockResponseChunk {
+    fn read_le<R: Read>(mut reader: R) -> IoResult<Self> {
+        let request = BlockRequest::read_le(&mut reader)?;
+        let index = u16::read_le(&mut reader)?;
+        let num_chunks = u16::read_le(&mut reader)?;
+        let total_size = u32::read_le(&mut reader)?;
+        let num_bytes = u32::read_le(&mut reader)? as usize;
+        // Ensure the chunk is within the chunk size.
+        if num_bytes > Self::CHUNK_SIZE {
+            return Err(error("Block response chunk exceeds the maximum chunk size"));
+        }
+        let mut bytes = vec![0u8; num_bytes];
+        reader.read_exact(&mut bytes)?;
+
+        Ok(Self { request, index, num_chunks, total_size, bytes: bytes.into() })
+    }
+}
+
+impl std::fmt::Debug for BlockResponseChunk {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+/// The reassembler of a chunked block response, which enforces a cap on the total size across chunks.
+#[derive(Clone, Debug)]
+pub struct BlockResponseAssembler<N: Network> {
+    /// The maximum total size (in bytes) of the block response.
+    max_size: usize,
+    /// The original block request, number of chunks, and total size, once the first chunk is received.
+    header: Option<(BlockRequest, u16, u32)>,
+    /// The index of the next expected chunk.
+    next_index: u16,
+    /// The bytes received so far.
+    buffer: BytesMut,
+    /// PhantomData.
+    _phantom: PhantomData<N>,
+}
+
+impl<N: Network> BlockResponseAssembler<N> {
+    /// The default maximum total size (in bytes) of a chunked block response. This is half of the maximum event
+    /// size, as a chunked block response is reassembled in memory, while it is pending from the peer.
+    pub const MAXIMUM_SIZE: usize = 64 * 1024 * 1024;
+
+    /// Initializes a new block response assembler, with the given maximum total size.
+    pub fn new(max_size: usize) -> Self {
+        Self { max_size, header: None, next_index: 0, buffer: Default::default(), _phantom: PhantomData }
+    }
+
+    /// Returns the number of bytes received so far.
+    pub fn len(&self) -> usize {
+        self.buffer.len()
+    }
+
+    /// Returns `true` if no bytes have been received.
+    pub fn is_empty(&self) -> bool {
+        self.buffer.is_empty()
+    }
+
+    /// Inserts the given chunk, and returns the reassembled block response once the last chunk is received.
+    pub fn insert(&mut self, chunk: BlockResponseChunk) -> Result<Option<BlockResponse<N>>> {
+        let BlockResponseChunk { request, index, num_chunks, total_size, bytes } = chunk;
+        // If this is the first chunk, check the declared sizes, and record them.
+        let (expected_request, expected_num_chunks, expected_total_size) = match self.header {
+            Some(header) => header,
+            None => {
+                ensure!(index == 0, "Block response chunk {index} was received before the first chunk");
+                ensure!(num_chunks > 1, "Block response must have more than one chunk");
+                ensure!(
+                    total_size as usize <= self.max_size,
+                    "Block response exceeds the maximum size ({total_size} > {} bytes)",
+                    self.max_size
+                );
+                *self.header.insert((request, num_chunks, total_size))
+            }
+        };
+        // Ensure the chunk is consistent with the first chunk.
+        ensure!(request == expected_request, "Block response chunk does not match the block request");
+        ensure!(num_chunks == expected_num_chunks, "Block response chunk has an inconsistent number of chunks");
+        ensure!(total_size == expected_total_size, "Block response chunk has an inconsistent total size");
+        // Ensure the chunks are received in order.
+        ensure!(index < num_chunks, "Block response chunk {index} is out of range ({num_chunks} chunks)");
+        ensure!(index == self.next_index, "Block response chunk {index} was received out of order");
+        ensure!(bytes.len() <= BlockResponseChunk::CHUNK_SIZE, "Block response chunk exceeds the maximum chunk size");
+        // Ensure the chunk does not exceed the total size.
+        ensure!(
+            self.buffer.len() + bytes.len() <= total_size as usize,
+            "Block response chunks exceed the declared total size ({total_size} bytes)"
+        );
+        self.buffer.extend_from_slice(&bytes);
+        self.next_index += 1;
+
+        // If this is not the last chunk, wait for the next chunk.
+        if index + 1 < num_chunks {
+            return Ok(None);
+        }
+        // Ensure all of the bytes were received.
+        ensure!(
+            self.buffer.len() == total_size as usize,
+            "Block response is incomplete ({} of {total_size} bytes)",
+            self.buffer.len()
+        );
+        // Return the reassembled block response, with the blocks to be deserialized.
+        let bytes = std::mem::take(&mut self.buffer).freeze();
+        Ok(Some(BlockResponse { request, blocks: Data::Buffer(bytes) }))
+    }
+}
+
+#[cfg(test)]
+pub mod prop_tests {
+    use crate::{
+        block_request::prop_tests::any_block_request,
+        BlockRequest,
+        BlockResponseAssembler,
+        BlockResponseChunk,
+        DataBlocks,
+    };
+    use snarkvm::{
+        ledger::ledger_test_helpers::sample_genesis_block,
+        prelude::{FromBytes, TestRng, ToBytes},
+    };
+
+    use bytes::{Buf, BufMut, Bytes, BytesMut};
+    use proptest::{
+        collection::vec,
+        prelude::{any, BoxedStrategy, Strategy},
+    };
+    use test_strategy::proptest;
+
+    type CurrentNetwork = snarkvm::prelude::Testnet3;
+
+    pub fn any_block_response_chunk() -> BoxedStrategy<BlockResponseChunk> {
+        (any_block_request(), any::<(u16, u16, u32)>(), vec(any::<u8>(), 0..1024))
+            .prop_map(|(request, (index, num_chunks, total_size), bytes)| BlockResponseChunk {
+                request,
+                index,
+                num_chunks,
+                total_size,
+                bytes: bytes.into(),
+            })
+            .boxed()
+    }
+
+    #[proptest]
+    fn block_response_chunk_roundtrip(#[strategy(any_block_response_chunk())] chunk: BlockResponseChunk) {
+        let mut bytes = BytesMut::default().writer();
+        chunk.write_le(&mut bytes).unwrap();
+        let decoded = BlockResponseChunk::read_le(&mut bytes.into_inner().reader()).unwrap();
+        assert_eq!(decoded, chunk);
+    }
+
+    #[test]
+    fn test_chunked_block_response_reassembly() {
+        let rng = &mut TestRng::default();
+        let request = BlockRequest::new(0, 1);
+
+        // Sample a block, which exceeds the chunk size below.
+        let blocks = DataBlocks::<CurrentNetwork>(vec![sample_genesis_block(rng)]);
+        let bytes = Bytes::from(blocks.to_bytes_le().unwrap());
+        let chunk_size = bytes.len() / 4 + 1;
+
+        // Ensure the blocks are not chunked, if they fit within a single chunk.
+        let mut chunks = Vec::new();
+        let on_chunk = |chunk| {
+            chunks.push(chunk);
+            Ok(())
+        };
+        assert!(!BlockResponseChunk::stream(request, &blocks, bytes.len(), on_chunk).unwrap());
+        assert!(chunks.is_empty());
+
+        // Serialize the blocks into chunks.
+        let on_chunk = |chunk| {
+            chunks.push(chunk);
+            Ok(())
+        };
+        assert!(BlockResponseChunk::stream(request, &blocks, chunk_size, on_chunk).unwrap());
+        assert_eq!(chunks.len(), 4);
+        assert!(chunks.iter().all(|chunk| chunk.bytes.len() <= chunk_size));
+        assert_eq!(chunks.iter().flat_map(|chunk| chunk.bytes.iter().copied()).collect::<Bytes>(), bytes);
+        // Ensure the serialization is aborted, if a chunk cannot be passed on.
+        let on_chunk = |_| Err(anyhow::anyhow!("The peer disconnected"));
+        assert!(BlockResponseChunk::stream(request, &blocks, chunk_size, on_chunk).is_err());
+
+        // Reassemble the chunks, and ensure the blocks are recovered.
+        let mut assembler = BlockResponseAssembler::<CurrentNetwork>::new(bytes.len());
+        let (last, rest) = chunks.split_last().unwrap();
+        for chunk in rest {
+            assert!(assembler.insert(chunk.clone()).unwrap().is_none());
+        }
+        let response = assembler.insert(last.clone()).unwrap().unwrap();
+        assert_eq!(response.request, request);
+        assert_eq!(response.blocks.deserialize_blocking().unwrap(), blocks);
+
+        // Ensure the blocks are rejected, if they exceed the size cap.
+        let mut assembler = BlockResponseAssembler::<CurrentNetwork>::new(bytes.len() - 1);
+        assert!(assembler.insert(chunks[0].clone()).is_err());
+        assert!(assembler.is_empty());
+
+        // Ensure the chunks are rejected, if they exceed the declared total size.
+        let mut assembler = BlockResponseAssembler::<CurrentNetwork>::new(bytes.len());
+        let mut oversized = chunks[0].clone();
+        oversized.total_size = chunk_size as u32;
+        assert!(assembler.insert(oversized.clone()).unwrap().is_none());
+        oversized.index = 1;
+        assert!(assembler.insert(oversized).is_err());
+
+        // Ensure the chunks are rejected, if they are received out of order.
+        let mut assembler = BlockResponseAssembler::<CurrentNetwork>::new(bytes.len());
+        assert!(assembler.insert(chunks[1].clone()).is_err());
+    }
+}
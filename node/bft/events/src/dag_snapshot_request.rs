@@ -0,0 +1,73 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkOS library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::*;
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct DagSnapshotRequest {
+    pub nonce: u64,
+}
+
+impl DagSnapshotRequest {
+    /// Initializes a new DAG snapshot request event.
+    pub const fn new(nonce: u64) -> Self {
+        Self { nonce }
+    }
+}
+
+impl EventTrait for DagSnapshotRequest {
+    /// Returns the event name.
+    #[inline]
+    fn name(&self) -> Cow<'static, str> {
+        "DagSnapshotRequest".into()
+    }
+}
+
+impl ToBytes for DagSnapshotRequest {
+    fn write_le<W: Write>(&self, mut writer: W) -> IoResult<()> {
+        self.nonce.write_le(&mut writer)?;
+        Ok(())
+    }
+}
+
+impl FromBytes for DagSnapshotRequest {
+    fn read_le<R: Read>(mut reader: R) -> IoResult<Self> {
+        let nonce = u64::read_le(&mut reader)?;
+
+        Ok(Self { nonce })
+    }
+}
+
+#[cfg(test)]
+pub mod prop_tests {
+    use crate::DagSnapshotRequest;
+    use snarkvm::console::prelude::{FromBytes, ToBytes};
+
+    use bytes::{Buf, BufMut, BytesMut};
+    use proptest::prelude::{any, BoxedStrategy, Strategy};
+    use test_strategy::proptest;
+
+    pub fn any_dag_snapshot_request() -> BoxedStrategy<DagSnapshotRequest> {
+        any::<u64>().prop_map(DagSnapshotRequest::new).boxed()
+    }
+
+    #[proptest]
+    fn serialize_deserialize(#[strategy(any_dag_snapshot_request())] original: DagSnapshotRequest) {
+        let mut buf = BytesMut::default().writer();
+        DagSnapshotRequest::write_le(&original, &mut buf).unwrap();
+
+        let deserialized = DagSnapshotRequest::read_le(buf.into_inner().reader()).unwrap();
+        assert_eq!(original, deserialized);
+    }
+}
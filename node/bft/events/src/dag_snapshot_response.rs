@@ -0,0 +1,134 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkOS library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::*;
+
+/// A snapshot of the recent DAG (i.e. the certificates above the GC round) of a validator,
+/// which is anchored to the latest block in its ledger.
+///
+/// Note: The snapshot is received over the authenticated channel of the validator, and its certificates are
+/// verified against the committed subdag of the anchoring block, so it is not signed separately.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DagSnapshotResponse<N: Network> {
+    /// The nonce of the DAG snapshot request.
+    pub nonce: u64,
+    /// The height of the latest block, at the time of the snapshot.
+    pub height: u32,
+    /// The hash of the latest block, at the time of the snapshot.
+    pub block_hash: N::BlockHash,
+    /// The certificates of the snapshot, in ascending order of rounds.
+    pub certificates: Vec<BatchCertificate<N>>,
+}
+
+impl<N: Network> DagSnapshotResponse<N> {
+    /// The maximum number of certificates in a DAG snapshot.
+    pub const MAXIMUM_CERTIFICATES: usize = 1 << 12;
+
+    /// Initializes a new DAG snapshot response event.
+    pub fn new(
+        nonce: u64,
+        height: u32,
+        block_hash: N::BlockHash,
+        certificates: Vec<BatchCertificate<N>>,
+    ) -> Result<Self> {
+        // Ensure the number of certificates is within the maximum.
+        ensure!(
+            certificates.len() <= Self::MAXIMUM_CERTIFICATES,
+            "A DAG snapshot must contain at most {} certificates",
+            Self::MAXIMUM_CERTIFICATES
+        );
+        Ok(Self { nonce, height, block_hash, certificates })
+    }
+}
+
+impl<N: Network> EventTrait for DagSnapshotResponse<N> {
+    /// Returns the event name.
+    #[inline]
+    fn name(&self) -> Cow<'static, str> {
+        "DagSnapshotResponse".into()
+    }
+}
+
+impl<N: Network> ToBytes for DagSnapshotResponse<N> {
+    fn write_le<W: Write>(&self, mut writer: W) -> IoResult<()> {
+        self.nonce.write_le(&mut writer)?;
+        self.height.write_le(&mut writer)?;
+        self.block_hash.write_le(&mut writer)?;
+        // Ensure the number of certificates is within the maximum.
+        if self.certificates.len() > Self::MAXIMUM_CERTIFICATES {
+            return Err(error("The DAG snapshot contains too many certificates"));
+        }
+        u16::try_from(self.certificates.len()).map_err(error)?.write_le(&mut writer)?;
+        for certificate in &self.certificates {
+            certificate.write_le(&mut writer)?;
+        }
+        Ok(())
+    }
+}
+
+impl<N: Network> FromBytes for DagSnapshotResponse<N> {
+    fn read_le<R: Read>(mut reader: R) -> IoResult<Self> {
+        let nonce = u64::read_le(&mut reader)?;
+        let height = u32::read_le(&mut reader)?;
+        let block_hash = N::BlockHash::read_le(&mut reader)?;
+        // Ensure the number of certificates is within the maximum.
+        let num_certificates = u16::read_le(&mut reader)? as usize;
+        if num_certificates > Self::MAXIMUM_CERTIFICATES {
+            return Err(error("The DAG snapshot contains too many certificates"));
+        }
+        let mut certificates = Vec::with_capacity(num_certificates);
+        for _ in 0..num_certificates {
+            certificates.push(BatchCertificate::read_le(&mut reader)?);
+        }
+
+        Ok(Self { nonce, height, block_hash, certificates })
+    }
+}
+
+#[cfg(test)]
+pub mod prop_tests {
+    use crate::{certificate_response::prop_tests::any_batch_certificate, DagSnapshotResponse};
+    use snarkvm::{
+        console::prelude::{FromBytes, ToBytes},
+        prelude::{Field, TestRng, Uniform},
+    };
+
+    use bytes::{Buf, BufMut, BytesMut};
+    use proptest::{
+        collection::vec,
+        prelude::{any, BoxedStrategy, Strategy},
+    };
+    use test_strategy::proptest;
+
+    type CurrentNetwork = snarkvm::prelude::Testnet3;
+
+    pub fn any_dag_snapshot_response() -> BoxedStrategy<DagSnapshotResponse<CurrentNetwork>> {
+        (any::<u64>(), any::<u32>(), vec(any_batch_certificate(), 0..4))
+            .prop_map(|(nonce, height, certificates)| {
+                let block_hash = Field::rand(&mut TestRng::default()).into();
+                DagSnapshotResponse::new(nonce, height, block_hash, certificates).unwrap()
+            })
+            .boxed()
+    }
+
+    #[proptest]
+    fn serialize_deserialize(#[strategy(any_dag_snapshot_response())] original: DagSnapshotResponse<CurrentNetwork>) {
+        let mut buf = BytesMut::default().writer();
+        DagSnapshotResponse::write_le(&original, &mut buf).unwrap();
+
+        let deserialized: DagSnapshotResponse<CurrentNetwork> =
+            DagSnapshotResponse::read_le(buf.into_inner().reader()).unwrap();
+        assert_eq!(original, deserialized);
+    }
+}
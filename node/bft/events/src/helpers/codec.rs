@@ -73,21 +73,20 @@ impl<N: Network> Encoder<Event<N>> for EventCodec<N> {
     }
 }
 
-impl<N: Network> Decoder for EventCodec<N> {
-    type Error = std::io::Error;
-    type Item = Event<N>;
-
-    fn decode(&mut self, source: &mut BytesMut) -> Result<Option<Self::Item>, Self::Error> {
+impl<N: Network> EventCodec<N> {
+    /// Decodes the next event, along with the length of its frame (in bytes).
+    fn decode_frame(&mut self, source: &mut BytesMut) -> Result<Option<(Event<N>, usize)>, std::io::Error> {
         // Decode a frame containing bytes belonging to an event.
         let bytes = match self.codec.decode(source)? {
             Some(bytes) => bytes,
             None => return Ok(None),
         };
+        let frame_length = bytes.len();
 
         // Convert the bytes to an event, or fail if it is not valid.
         let reader = bytes.reader();
         match Event::read_le(reader) {
-            Ok(event) => Ok(Some(event)),
+            Ok(event) => Ok(Some((event, frame_length))),
             Err(error) => {
                 error!("Failed to deserialize an event: {}", error);
                 Err(std::io::ErrorKind::InvalidData.into())
@@ -96,6 +95,34 @@ impl<N: Network> Decoder for EventCodec<N> {
     }
 }
 
+impl<N: Network> Decoder for EventCodec<N> {
+    type Error = std::io::Error;
+    type Item = Event<N>;
+
+    fn decode(&mut self, source: &mut BytesMut) -> Result<Option<Self::Item>, Self::Error> {
+        Ok(self.decode_frame(source)?.map(|(event, _)| event))
+    }
+}
+
+/// The codec used to decode network `Event`s, along with the length of their frames (in bytes).
+/// Note: This lets the size of an inbound event be observed, without serializing it again.
+pub struct SizedEventCodec<N: Network>(EventCodec<N>);
+
+impl<N: Network> Default for SizedEventCodec<N> {
+    fn default() -> Self {
+        Self(Default::default())
+    }
+}
+
+impl<N: Network> Decoder for SizedEventCodec<N> {
+    type Error = std::io::Error;
+    type Item = (Event<N>, usize);
+
+    fn decode(&mut self, source: &mut BytesMut) -> Result<Option<Self::Item>, Self::Error> {
+        self.0.decode_frame(source)
+    }
+}
+
 /* NOISE CODEC */
 
 // The maximum message size for noise messages. If the data to be encrypted exceeds it, it is chunked.
@@ -389,4 +416,15 @@ mod tests {
     fn event_roundtrip(#[strategy(any_event())] event: Event<CurrentNetwork>) {
         assert_roundtrip(EventOrBytes::Event(event))
     }
+
+    #[proptest]
+    fn sized_event_frame_length(#[strategy(any_event())] event: Event<CurrentNetwork>) {
+        let mut bytes = BytesMut::new();
+        assert!(EventCodec::default().encode(event.clone(), &mut bytes).is_ok());
+
+        // Ensure the frame length is the size of the serialized event.
+        let (decoded, frame_length) = SizedEventCodec::<CurrentNetwork>::default().decode(&mut bytes).unwrap().unwrap();
+        assert_eq!(decoded.to_bytes_le().unwrap(), event.to_bytes_le().unwrap());
+        assert_eq!(frame_length, event.to_bytes_le().unwrap().len());
+    }
 }
@@ -29,6 +29,9 @@ pub use block_request::BlockRequest;
 mod block_response;
 pub use block_response::{BlockResponse, DataBlocks};
 
+mod block_response_chunk;
+pub use block_response_chunk::{BlockResponseAssembler, BlockResponseChunk};
+
 mod certificate_request;
 pub use certificate_request::CertificateRequest;
 
@@ -41,6 +44,12 @@ pub use challenge_request::ChallengeRequest;
 mod challenge_response;
 pub use challenge_response::ChallengeResponse;
 
+mod dag_snapshot_request;
+pub use dag_snapshot_request::DagSnapshotRequest;
+
+mod dag_snapshot_response;
+pub use dag_snapshot_response::DagSnapshotResponse;
+
 mod disconnect;
 pub use disconnect::{Disconnect, DisconnectReason};
 
@@ -97,10 +106,13 @@ pub enum Event<N: Network> {
     BatchCertified(BatchCertified<N>),
     BlockRequest(BlockRequest),
     BlockResponse(BlockResponse<N>),
+    BlockResponseChunk(BlockResponseChunk),
     CertificateRequest(CertificateRequest<N>),
     CertificateResponse(CertificateResponse<N>),
     ChallengeRequest(ChallengeRequest<N>),
     ChallengeResponse(ChallengeResponse<N>),
+    DagSnapshotRequest(DagSnapshotRequest),
+    DagSnapshotResponse(DagSnapshotResponse<N>),
     Disconnect(Disconnect),
     PrimaryPing(PrimaryPing<N>),
     TransmissionRequest(TransmissionRequest<N>),
@@ -118,7 +130,7 @@ impl<N: Network> From<DisconnectReason> for Event<N> {
 
 impl<N: Network> Event<N> {
     /// The version of the event protocol; it can be incremented in order to force users to update.
-    pub const VERSION: u32 = 7;
+    pub const VERSION: u32 = 11;
 
     /// Returns the event name.
     #[inline]
@@ -129,10 +141,13 @@ impl<N: Network> Event<N> {
             Self::BatchCertified(event) => event.name(),
             Self::BlockRequest(event) => event.name(),
             Self::BlockResponse(event) => event.name(),
+            Self::BlockResponseChunk(event) => event.name(),
             Self::CertificateRequest(event) => event.name(),
             Self::CertificateResponse(event) => event.name(),
             Self::ChallengeRequest(event) => event.name(),
             Self::ChallengeResponse(event) => event.name(),
+            Self::DagSnapshotRequest(event) => event.name(),
+            Self::DagSnapshotResponse(event) => event.name(),
             Self::Disconnect(event) => event.name(),
             Self::PrimaryPing(event) => event.name(),
             Self::TransmissionRequest(event) => event.name(),
@@ -163,6 +178,9 @@ impl<N: Network> Event<N> {
             Self::ValidatorsRequest(..) => 13,
             Self::ValidatorsResponse(..) => 14,
             Self::WorkerPing(..) => 15,
+            Self::BlockResponseChunk(..) => 16,
+            Self::DagSnapshotRequest(..) => 17,
+            Self::DagSnapshotResponse(..) => 18,
         }
     }
 }
@@ -188,6 +206,9 @@ impl<N: Network> ToBytes for Event<N> {
             Self::ValidatorsRequest(event) => event.write_le(writer),
             Self::ValidatorsResponse(event) => event.write_le(writer),
             Self::WorkerPing(event) => event.write_le(writer),
+            Self::BlockResponseChunk(event) => event.write_le(writer),
+            Self::DagSnapshotRequest(event) => event.write_le(writer),
+            Self::DagSnapshotResponse(event) => event.write_le(writer),
         }
     }
 }
@@ -215,7 +236,10 @@ impl<N: Network> FromBytes for Event<N> {
             13 => Self::ValidatorsRequest(ValidatorsRequest::read_le(&mut reader)?),
             14 => Self::ValidatorsResponse(ValidatorsResponse::read_le(&mut reader)?),
             15 => Self::WorkerPing(WorkerPing::read_le(&mut reader)?),
-            16.. => return Err(error("Unknown event ID {id}")),
+            16 => Self::BlockResponseChunk(BlockResponseChunk::read_le(&mut reader)?),
+            17 => Self::DagSnapshotRequest(DagSnapshotRequest::read_le(&mut reader)?),
+            18 => Self::DagSnapshotResponse(DagSnapshotResponse::read_le(&mut reader)?),
+            19.. => return Err(error("Unknown event ID {id}")),
         };
 
         // Ensure that there are no "dangling" bytes.
@@ -252,10 +276,13 @@ pub mod prop_tests {
         batch_certified::prop_tests::any_batch_certified,
         batch_propose::prop_tests::any_batch_propose,
         batch_signature::prop_tests::any_batch_signature,
+        block_response_chunk::prop_tests::any_block_response_chunk,
         certificate_request::prop_tests::any_certificate_request,
         certificate_response::prop_tests::any_certificate_response,
         challenge_request::prop_tests::any_challenge_request,
         challenge_response::prop_tests::any_challenge_response,
+        dag_snapshot_request::prop_tests::any_dag_snapshot_request,
+        dag_snapshot_response::prop_tests::any_dag_snapshot_response,
         transmission_request::prop_tests::any_transmission_request,
         transmission_response::prop_tests::any_transmission_response,
         worker_ping::prop_tests::any_worker_ping,
@@ -306,10 +333,13 @@ pub mod prop_tests {
             any_batch_certified().prop_map(Event::BatchCertified),
             any_batch_propose().prop_map(Event::BatchPropose),
             any_batch_signature().prop_map(Event::BatchSignature),
+            any_block_response_chunk().prop_map(Event::BlockResponseChunk),
             any_certificate_request().prop_map(Event::CertificateRequest),
             any_certificate_response().prop_map(Event::CertificateResponse),
             any_challenge_request().prop_map(Event::ChallengeRequest),
             any_challenge_response().prop_map(Event::ChallengeResponse),
+            any_dag_snapshot_request().prop_map(Event::DagSnapshotRequest),
+            any_dag_snapshot_response().prop_map(Event::DagSnapshotResponse),
             (
                 Just(vec![
                     DisconnectReason::ProtocolViolation,
@@ -17,19 +17,21 @@ use super::*;
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub struct TransmissionRequest<N: Network> {
     pub transmission_id: TransmissionID<N>,
+    /// The ID of the peer's worker that the transmission is assigned to.
+    pub worker_id: u8,
 }
 
 impl<N: Network> TransmissionRequest<N> {
     /// Initializes a new transmission request event.
-    pub const fn new(transmission_id: TransmissionID<N>) -> Self {
-        Self { transmission_id }
+    pub const fn new(transmission_id: TransmissionID<N>, worker_id: u8) -> Self {
+        Self { transmission_id, worker_id }
     }
 }
 
-impl<N: Network> From<TransmissionID<N>> for TransmissionRequest<N> {
+impl<N: Network> From<(TransmissionID<N>, u8)> for TransmissionRequest<N> {
     /// Initializes a new transmission request event.
-    fn from(transmission_id: TransmissionID<N>) -> Self {
-        Self::new(transmission_id)
+    fn from((transmission_id, worker_id): (TransmissionID<N>, u8)) -> Self {
+        Self::new(transmission_id, worker_id)
     }
 }
 
@@ -44,6 +46,7 @@ impl<N: Network> EventTrait for TransmissionRequest<N> {
 impl<N: Network> ToBytes for TransmissionRequest<N> {
     fn write_le<W: Write>(&self, mut writer: W) -> IoResult<()> {
         self.transmission_id.write_le(&mut writer)?;
+        self.worker_id.write_le(&mut writer)?;
         Ok(())
     }
 }
@@ -51,8 +54,9 @@ impl<N: Network> ToBytes for TransmissionRequest<N> {
 impl<N: Network> FromBytes for TransmissionRequest<N> {
     fn read_le<R: Read>(mut reader: R) -> IoResult<Self> {
         let transmission_id = TransmissionID::read_le(&mut reader)?;
+        let worker_id = u8::read_le(&mut reader)?;
 
-        Ok(Self { transmission_id })
+        Ok(Self { transmission_id, worker_id })
     }
 }
 
@@ -69,7 +73,7 @@ pub mod prop_tests {
 
     use bytes::{Buf, BufMut, BytesMut};
     use proptest::{
-        prelude::{BoxedStrategy, Strategy},
+        prelude::{any, BoxedStrategy, Strategy},
         prop_oneof,
     };
     use test_strategy::proptest;
@@ -85,7 +89,7 @@ pub mod prop_tests {
     }
 
     pub fn any_transmission_request() -> BoxedStrategy<TransmissionRequest<CurrentNetwork>> {
-        any_transmission_id().prop_map(TransmissionRequest::new).boxed()
+        (any_transmission_id(), any::<u8>()).prop_map(TransmissionRequest::from).boxed()
     }
 
     #[proptest]
@@ -18,19 +18,14 @@ use super::*;
 pub struct TransmissionResponse<N: Network> {
     pub transmission_id: TransmissionID<N>,
     pub transmission: Transmission<N>,
+    /// The number of hops the transmission may still be forwarded, before it is no longer advertised.
+    pub ttl: u8,
 }
 
 impl<N: Network> TransmissionResponse<N> {
     /// Initializes a new transmission response event.
-    pub fn new(transmission_id: TransmissionID<N>, transmission: Transmission<N>) -> Self {
-        Self { transmission_id, transmission }
-    }
-}
-
-impl<N: Network> From<(TransmissionID<N>, Transmission<N>)> for TransmissionResponse<N> {
-    /// Initializes a new transmission response event.
-    fn from((transmission_id, transmission): (TransmissionID<N>, Transmission<N>)) -> Self {
-        Self::new(transmission_id, transmission)
+    pub fn new(transmission_id: TransmissionID<N>, transmission: Transmission<N>, ttl: u8) -> Self {
+        Self { transmission_id, transmission, ttl }
     }
 }
 
@@ -46,6 +41,7 @@ impl<N: Network> ToBytes for TransmissionResponse<N> {
     fn write_le<W: Write>(&self, mut writer: W) -> IoResult<()> {
         self.transmission_id.write_le(&mut writer)?;
         self.transmission.write_le(&mut writer)?;
+        self.ttl.write_le(&mut writer)?;
         Ok(())
     }
 }
@@ -54,8 +50,9 @@ impl<N: Network> FromBytes for TransmissionResponse<N> {
     fn read_le<R: Read>(mut reader: R) -> IoResult<Self> {
         let transmission_id = TransmissionID::read_le(&mut reader)?;
         let transmission = Transmission::read_le(&mut reader)?;
+        let ttl = u8::read_le(&mut reader)?;
 
-        Ok(Self { transmission_id, transmission })
+        Ok(Self { transmission_id, transmission, ttl })
     }
 }
 
@@ -95,7 +92,7 @@ pub mod prop_tests {
     }
 
     pub fn any_transmission_response() -> BoxedStrategy<TransmissionResponse<CurrentNetwork>> {
-        any_transmission().prop_map(|(id, t)| TransmissionResponse::new(id, t)).boxed()
+        (any_transmission(), any::<u8>()).prop_map(|((id, t), ttl)| TransmissionResponse::new(id, t, ttl)).boxed()
     }
 
     #[proptest]
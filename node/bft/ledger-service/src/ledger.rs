@@ -18,11 +18,11 @@ use snarkvm::{
         block::{Block, Transaction},
         coinbase::{CoinbaseVerifyingKey, ProverSolution, PuzzleCommitment},
         committee::Committee,
-        narwhal::{BatchCertificate, Data, Subdag, Transmission, TransmissionID},
+        narwhal::{BatchCertificate, BatchHeader, Data, Subdag, Transmission, TransmissionID},
         store::ConsensusStorage,
         Ledger,
     },
-    prelude::{bail, Field, Network, Result},
+    prelude::{bail, Field, Network, ProgramID, Result},
 };
 
 use indexmap::IndexMap;
@@ -182,6 +182,11 @@ impl<N: Network, C: ConsensusStorage<N>> LedgerService<N> for CoreLedgerService<
         self.ledger.contains_certificate(certificate_id)
     }
 
+    /// Returns `true` if the program exists in the ledger.
+    fn contains_program(&self, program_id: &ProgramID<N>) -> Result<bool> {
+        Ok(self.ledger.vm().contains_program(program_id))
+    }
+
     /// Returns `true` if the transmission exists in the ledger.
     fn contains_transmission(&self, transmission_id: &TransmissionID<N>) -> Result<bool> {
         match transmission_id {
@@ -304,6 +309,19 @@ impl<N: Network, C: ConsensusStorage<N>> LedgerService<N> for CoreLedgerService<
         self.ledger.check_next_block(block, &mut rand::thread_rng())
     }
 
+    /// Reports the given two conflicting signed batch headers, as equivocation evidence for slashing.
+    fn report_equivocation(&self, first: &BatchHeader<N>, second: &BatchHeader<N>) -> Result<()> {
+        // Note: The ledger does not yet support slashing, so the evidence is only logged for now.
+        tracing::warn!(
+            "Validator '{}' equivocated in round {} (batches {} and {})",
+            first.author(),
+            first.round(),
+            fmt_id(first.batch_id()),
+            fmt_id(second.batch_id())
+        );
+        Ok(())
+    }
+
     /// Returns a candidate for the next block in the ledger, using a committed subdag and its transmissions.
     #[cfg(feature = "ledger-write")]
     fn prepare_advance_to_next_quorum_block(
@@ -18,9 +18,9 @@ use snarkvm::{
         block::{Block, Transaction},
         coinbase::{ProverSolution, PuzzleCommitment},
         committee::Committee,
-        narwhal::{BatchCertificate, Data, Subdag, Transmission, TransmissionID},
+        narwhal::{BatchCertificate, BatchHeader, Data, Subdag, Transmission, TransmissionID},
     },
-    prelude::{bail, ensure, Field, Network, Result},
+    prelude::{bail, ensure, Field, Network, ProgramID, Result},
 };
 
 use indexmap::IndexMap;
@@ -31,14 +31,19 @@ use tracing::*;
 /// A mock ledger service that always returns `false`.
 #[derive(Debug)]
 pub struct MockLedgerService<N: Network> {
-    committee: Committee<N>,
+    committee: Mutex<Committee<N>>,
     height_to_hash: Mutex<BTreeMap<u32, N::BlockHash>>,
+    equivocation_reports: Mutex<Vec<(BatchHeader<N>, BatchHeader<N>)>>,
 }
 
 impl<N: Network> MockLedgerService<N> {
     /// Initializes a new mock ledger service.
     pub fn new(committee: Committee<N>) -> Self {
-        Self { committee, height_to_hash: Default::default() }
+        Self {
+            committee: Mutex::new(committee),
+            height_to_hash: Default::default(),
+            equivocation_reports: Default::default(),
+        }
     }
 
     /// Initializes a new mock ledger service at the specified height.
@@ -47,7 +52,21 @@ impl<N: Network> MockLedgerService<N> {
         for i in 0..=height {
             height_to_hash.insert(i, (Field::<N>::from_u32(i)).into());
         }
-        Self { committee, height_to_hash: Mutex::new(height_to_hash) }
+        Self {
+            committee: Mutex::new(committee),
+            height_to_hash: Mutex::new(height_to_hash),
+            equivocation_reports: Default::default(),
+        }
+    }
+
+    /// Sets the committee, to simulate a committee transition.
+    pub fn set_committee(&self, committee: Committee<N>) {
+        *self.committee.lock() = committee;
+    }
+
+    /// Returns the equivocation evidence that has been reported.
+    pub fn equivocation_reports(&self) -> Vec<(BatchHeader<N>, BatchHeader<N>)> {
+        self.equivocation_reports.lock().clone()
     }
 }
 
@@ -117,18 +136,18 @@ impl<N: Network> LedgerService<N> for MockLedgerService<N> {
 
     /// Returns the current committee.
     fn current_committee(&self) -> Result<Committee<N>> {
-        Ok(self.committee.clone())
+        Ok(self.committee.lock().clone())
     }
 
     /// Returns the committee for the given round.
     /// If the given round is in the future, then the current committee is returned.
     fn get_committee_for_round(&self, _round: u64) -> Result<Committee<N>> {
-        Ok(self.committee.clone())
+        Ok(self.committee.lock().clone())
     }
 
     /// Returns the committee lookback for the given round.
     fn get_committee_lookback_for_round(&self, _round: u64) -> Result<Committee<N>> {
-        Ok(self.committee.clone())
+        Ok(self.committee.lock().clone())
     }
 
     /// Returns `false` for all queries.
@@ -137,6 +156,12 @@ impl<N: Network> LedgerService<N> for MockLedgerService<N> {
         Ok(false)
     }
 
+    /// Returns `true` for all queries.
+    fn contains_program(&self, program_id: &ProgramID<N>) -> Result<bool> {
+        trace!("[MockLedgerService] Contains program ID {program_id} - true");
+        Ok(true)
+    }
+
     /// Returns `false` for all queries.
     fn contains_transmission(&self, transmission_id: &TransmissionID<N>) -> Result<bool> {
         trace!("[MockLedgerService] Contains transmission ID {} - false", fmt_id(transmission_id));
@@ -178,6 +203,12 @@ impl<N: Network> LedgerService<N> for MockLedgerService<N> {
         Ok(())
     }
 
+    /// Records the given equivocation evidence.
+    fn report_equivocation(&self, first: &BatchHeader<N>, second: &BatchHeader<N>) -> Result<()> {
+        self.equivocation_reports.lock().push((first.clone(), second.clone()));
+        Ok(())
+    }
+
     /// Returns a candidate for the next block in the ledger, using a committed subdag and its transmissions.
     #[cfg(feature = "ledger-write")]
     fn prepare_advance_to_next_quorum_block(
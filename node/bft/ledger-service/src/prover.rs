@@ -18,9 +18,9 @@ use snarkvm::{
         block::{Block, Transaction},
         coinbase::{ProverSolution, PuzzleCommitment},
         committee::Committee,
-        narwhal::{BatchCertificate, Data, Subdag, Transmission, TransmissionID},
+        narwhal::{BatchCertificate, BatchHeader, Data, Subdag, Transmission, TransmissionID},
     },
-    prelude::{bail, Field, Network, Result},
+    prelude::{bail, Field, Network, ProgramID, Result},
 };
 
 use indexmap::IndexMap;
@@ -119,6 +119,11 @@ impl<N: Network> LedgerService<N> for ProverLedgerService<N> {
         bail!("Certificate '{certificate_id}' does not exist in prover")
     }
 
+    /// Returns `true` if the program exists in the ledger.
+    fn contains_program(&self, program_id: &ProgramID<N>) -> Result<bool> {
+        bail!("Program '{program_id}' does not exist in prover")
+    }
+
     /// Returns `true` if the transmission exists in the ledger.
     fn contains_transmission(&self, transmission_id: &TransmissionID<N>) -> Result<bool> {
         bail!("Transmission '{transmission_id}' does not exist in prover")
@@ -156,6 +161,11 @@ impl<N: Network> LedgerService<N> for ProverLedgerService<N> {
         Ok(())
     }
 
+    /// Ignores the given equivocation evidence.
+    fn report_equivocation(&self, _first: &BatchHeader<N>, _second: &BatchHeader<N>) -> Result<()> {
+        Ok(())
+    }
+
     /// Returns a candidate for the next block in the ledger, using a committed subdag and its transmissions.
     #[cfg(feature = "ledger-write")]
     fn prepare_advance_to_next_quorum_block(
@@ -17,9 +17,9 @@ use snarkvm::{
         block::{Block, Transaction},
         coinbase::{ProverSolution, PuzzleCommitment},
         committee::Committee,
-        narwhal::{BatchCertificate, Data, Subdag, Transmission, TransmissionID},
+        narwhal::{BatchCertificate, BatchHeader, Data, Subdag, Transmission, TransmissionID},
     },
-    prelude::{Field, Network, Result},
+    prelude::{Field, Network, ProgramID, Result},
 };
 
 use indexmap::IndexMap;
@@ -75,6 +75,9 @@ pub trait LedgerService<N: Network>: Debug + Send + Sync {
     /// Returns `true` if the ledger contains the given certificate ID.
     fn contains_certificate(&self, certificate_id: &Field<N>) -> Result<bool>;
 
+    /// Returns `true` if the ledger contains the given program ID.
+    fn contains_program(&self, program_id: &ProgramID<N>) -> Result<bool>;
+
     /// Returns `true` if the ledger contains the given transmission ID.
     fn contains_transmission(&self, transmission_id: &TransmissionID<N>) -> Result<bool>;
 
@@ -102,6 +105,9 @@ pub trait LedgerService<N: Network>: Debug + Send + Sync {
     /// Checks the given block is valid next block.
     fn check_next_block(&self, block: &Block<N>) -> Result<()>;
 
+    /// Reports the given two conflicting signed batch headers, as equivocation evidence for slashing.
+    fn report_equivocation(&self, first: &BatchHeader<N>, second: &BatchHeader<N>) -> Result<()>;
+
     /// Returns a candidate for the next block in the ledger, using a committed subdag and its transmissions.
     #[cfg(feature = "ledger-write")]
     fn prepare_advance_to_next_quorum_block(
@@ -20,11 +20,11 @@ use snarkvm::{
         block::{Block, Transaction},
         coinbase::{ProverSolution, PuzzleCommitment},
         committee::Committee,
-        narwhal::{Data, Subdag, Transmission, TransmissionID},
+        narwhal::{BatchHeader, Data, Subdag, Transmission, TransmissionID},
         store::ConsensusStorage,
         Ledger,
     },
-    prelude::{narwhal::BatchCertificate, Field, Network, Result},
+    prelude::{narwhal::BatchCertificate, Field, Network, ProgramID, Result},
 };
 use std::{
     fmt,
@@ -129,6 +129,11 @@ impl<N: Network, C: ConsensusStorage<N>> LedgerService<N> for TranslucentLedgerS
         self.inner.contains_certificate(certificate_id)
     }
 
+    /// Returns `true` if the program exists in the ledger.
+    fn contains_program(&self, program_id: &ProgramID<N>) -> Result<bool> {
+        self.inner.contains_program(program_id)
+    }
+
     /// Returns `true` if the transmission exists in the ledger.
     fn contains_transmission(&self, transmission_id: &TransmissionID<N>) -> Result<bool> {
         self.inner.contains_transmission(transmission_id)
@@ -166,6 +171,11 @@ impl<N: Network, C: ConsensusStorage<N>> LedgerService<N> for TranslucentLedgerS
         Ok(())
     }
 
+    /// Reports the given two conflicting signed batch headers, as equivocation evidence for slashing.
+    fn report_equivocation(&self, first: &BatchHeader<N>, second: &BatchHeader<N>) -> Result<()> {
+        self.inner.report_equivocation(first, second)
+    }
+
     /// Returns a candidate for the next block in the ledger, using a committed subdag and its transmissions.
     fn prepare_advance_to_next_quorum_block(
         &self,
@@ -21,6 +21,7 @@ use crate::{
         ConsensusSender,
         PrimaryReceiver,
         PrimarySender,
+        RoundTimings,
         Storage,
         DAG,
     },
@@ -67,8 +68,12 @@ pub struct BFT<N: Network> {
     leader_certificate: Arc<RwLock<Option<BatchCertificate<N>>>>,
     /// The timer for the leader certificate to be received.
     leader_certificate_timer: Arc<AtomicI64>,
+    /// The extra time (in seconds) to wait for a referenced leader certificate to reach availability (`0` to disable).
+    leader_reference_grace_in_secs: Arc<AtomicI64>,
     /// The last election certificate IDs.
     last_election_certificate_ids: Arc<RwLock<IndexSet<Field<N>>>>,
+    /// The per-round consensus timing.
+    round_timings: Arc<RoundTimings>,
     /// The consensus sender.
     consensus_sender: Arc<OnceCell<ConsensusSender<N>>>,
     /// The spawned handles.
@@ -92,7 +97,9 @@ impl<N: Network> BFT<N> {
             dag: Default::default(),
             leader_certificate: Default::default(),
             leader_certificate_timer: Default::default(),
+            leader_reference_grace_in_secs: Default::default(),
             last_election_certificate_ids: Default::default(),
+            round_timings: Default::default(),
             consensus_sender: Default::default(),
             handles: Default::default(),
             lock: Default::default(),
@@ -146,6 +153,29 @@ impl<N: Network> BFT<N> {
         &self.leader_certificate
     }
 
+    /// Returns the extra time (in seconds) to wait for a referenced leader certificate to reach availability.
+    pub fn leader_reference_grace_in_secs(&self) -> i64 {
+        self.leader_reference_grace_in_secs.load(Ordering::SeqCst)
+    }
+
+    /// Sets the extra time (in seconds) to wait for a referenced leader certificate to reach availability,
+    /// before advancing the odd round without the leader (`0` to disable).
+    ///
+    /// Note: The leader (anchor) of each even round is fixed by the committee, and is never re-selected by its
+    /// reference count. The references are only known from each node's own view of the DAG, so selecting the anchor
+    /// by them would have the nodes commit different anchors, and the ledger rejects a subdag whose anchor is not
+    /// the committee leader. Instead, this grace only favors a leader that is already referenced by some stake,
+    /// by delaying the local round advancement, which never changes what is committed.
+    pub fn set_leader_reference_grace_in_secs(&self, grace_in_secs: i64) -> Result<()> {
+        // Ensure the grace is bounded, so that it does not harm liveness.
+        ensure!(
+            (0..=MAX_LEADER_CERTIFICATE_DELAY_IN_SECS).contains(&grace_in_secs),
+            "The leader reference grace must be between 0 and {MAX_LEADER_CERTIFICATE_DELAY_IN_SECS} seconds"
+        );
+        self.leader_reference_grace_in_secs.store(grace_in_secs, Ordering::SeqCst);
+        Ok(())
+    }
+
     /// Returns the last election certificate IDs.
     pub fn last_election_certificate_ids(&self) -> IndexSet<Field<N>> {
         self.last_election_certificate_ids.read().clone()
@@ -153,6 +183,11 @@ impl<N: Network> BFT<N> {
 }
 
 impl<N: Network> BFT<N> {
+    /// Returns the per-round consensus timing, which is emitted as a structured log (and optionally to a CSV sink).
+    pub fn round_timings(&self) -> &RoundTimings {
+        &self.round_timings
+    }
+
     /// Returns the number of unconfirmed transmissions.
     pub fn num_unconfirmed_transmissions(&self) -> usize {
         self.primary.num_unconfirmed_transmissions()
@@ -258,6 +293,8 @@ impl<N: Network> BFT<N> {
             }
             // Update the timer for the leader certificate.
             self.leader_certificate_timer.store(now(), Ordering::SeqCst);
+            // Record the opening of the next round.
+            self.round_timings.open(current_round + 1);
         }
 
         is_ready
@@ -391,7 +428,18 @@ impl<N: Network> BFT<N> {
         // Return 'true' if any of the following conditions hold:
         stake_with_leader >= committee_lookback.availability_threshold()
             || stake_without_leader >= committee_lookback.quorum_threshold()
-            || self.is_timer_expired()
+            || self.is_leader_timer_expired(stake_with_leader)
+    }
+
+    /// Returns `true` if the timer for the leader certificate has expired, given the stake that references it.
+    /// If the leader certificate is referenced by some stake, the leader reference grace is added to the timer.
+    fn is_leader_timer_expired(&self, stake_with_leader: u64) -> bool {
+        let grace_in_secs = match stake_with_leader > 0 {
+            true => self.leader_reference_grace_in_secs(),
+            false => 0,
+        };
+        self.leader_certificate_timer.load(Ordering::SeqCst) + MAX_LEADER_CERTIFICATE_DELAY_IN_SECS + grace_in_secs
+            <= now()
     }
 
     /// Computes the amount of stake that has & has not signed for the leader certificate.
@@ -435,8 +483,12 @@ impl<N: Network> BFT<N> {
 
         // Retrieve the certificate round.
         let certificate_round = certificate.round();
+        // Retrieve the certificate author.
+        let certificate_author = certificate.author();
         // Insert the certificate into the DAG.
         self.dag.write().insert(certificate);
+        // Record the timing of the certificate round.
+        self.record_certificate_timing(certificate_round, certificate_author);
 
         // Construct the commit round.
         let commit_round = certificate_round.saturating_sub(1);
@@ -492,6 +544,34 @@ impl<N: Network> BFT<N> {
         self.commit_leader_certificate::<ALLOW_LEDGER_ACCESS>(leader_certificate, election_certificate_ids).await
     }
 
+    /// Records the quorum and anchor timing of the given round, once a certificate of the round is in the DAG.
+    fn record_certificate_timing(&self, round: u64, author: Address<N>) {
+        let (has_quorum, has_anchor) = (self.round_timings.has_quorum(round), self.round_timings.has_anchor(round));
+        // Return early if the timing of the round is already recorded.
+        if has_quorum && (has_anchor || round % 2 != 0) {
+            return;
+        }
+        let Ok(committee_lookback) = self.ledger().get_committee_lookback_for_round(round) else {
+            return;
+        };
+        // Record whether the certificates of the round reached the quorum threshold.
+        if !has_quorum {
+            let authors = self
+                .dag
+                .read()
+                .get_certificates_for_round(round)
+                .map(|certificates| certificates.into_keys().collect())
+                .unwrap_or_default();
+            if committee_lookback.is_quorum_threshold_reached(&authors) {
+                self.round_timings.quorum(round);
+            }
+        }
+        // Record whether the certificate is from the leader (anchor) of the even round.
+        if !has_anchor && round % 2 == 0 && committee_lookback.get_leader(round).map_or(false, |l| l == author) {
+            self.round_timings.anchor(round);
+        }
+    }
+
     /// Commits the leader certificate, and all previous leader certificates since the last committed round.
     async fn commit_leader_certificate<const ALLOW_LEDGER_ACCESS: bool>(
         &self,
@@ -626,6 +706,8 @@ impl<N: Network> BFT<N> {
                 let mut last_election_certificate_ids = self.last_election_certificate_ids.write();
                 *last_election_certificate_ids = election_certificate_ids.clone();
             }
+            // Record the commit of the leader round.
+            self.round_timings.commit(leader_round);
         }
 
         // Perform garbage collection based on the latest committed leader round.
@@ -827,6 +909,7 @@ mod tests {
     use crate::{
         helpers::{now, Storage},
         BFT,
+        MAX_LEADER_CERTIFICATE_DELAY_IN_SECS,
     };
     use snarkos_account::Account;
     use snarkos_node_bft_ledger_service::MockLedgerService;
@@ -1068,6 +1151,67 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    #[tracing_test::traced_test]
+    fn test_leader_reference_grace_is_deterministic() -> Result<()> {
+        let rng = &mut TestRng::default();
+
+        // Sample a round 3 certificate, referencing the round 2 certificates.
+        let (certificate, previous_certificates) = snarkvm::ledger::narwhal::batch_certificate::test_helpers::sample_batch_certificate_with_previous_certificates(
+            3,
+            rng,
+        );
+        // Initialize the committee, such that a single round 3 certificate is below the availability threshold.
+        let mut members = previous_certificates.iter().map(|c| c.author()).collect::<Vec<_>>();
+        members.push(certificate.author());
+        let committee =
+            snarkvm::ledger::committee::test_helpers::sample_committee_for_round_and_members(2, members, rng);
+        // Retrieve the leader certificate for round 2, which is referenced by the round 3 certificate.
+        let leader = committee.get_leader(2)?;
+        let leader_certificate = previous_certificates.iter().find(|c| c.author() == leader).unwrap().clone();
+
+        // Initialize two nodes, with identical DAG state and leader timers.
+        let timer = now() - MAX_LEADER_CERTIFICATE_DELAY_IN_SECS - 1;
+        let mut nodes = Vec::new();
+        for _ in 0..2 {
+            let ledger = Arc::new(MockLedgerService::new(committee.clone()));
+            let storage = Storage::new(ledger.clone(), Arc::new(BFTMemoryService::new()), 10);
+            for previous_certificate in &previous_certificates {
+                storage.testing_only_insert_certificate_testing_only(previous_certificate.clone());
+            }
+            storage.testing_only_insert_certificate_testing_only(certificate.clone());
+            storage.increment_to_next_round(2)?;
+            assert_eq!(storage.current_round(), 3);
+
+            let bft = BFT::new(Account::new(rng)?, storage, ledger, None, &[], None)?;
+            assert!(bft.set_leader_reference_grace_in_secs(MAX_LEADER_CERTIFICATE_DELAY_IN_SECS + 1).is_err());
+            bft.set_leader_reference_grace_in_secs(MAX_LEADER_CERTIFICATE_DELAY_IN_SECS)?;
+            *bft.leader_certificate.write() = Some(leader_certificate.clone());
+            bft.leader_certificate_timer.store(timer, Ordering::SeqCst);
+            nodes.push(bft);
+        }
+
+        // Ensure both nodes wait for the referenced leader, despite the expired timer.
+        for bft in &nodes {
+            assert!(bft.is_timer_expired());
+            assert!(!bft.is_leader_quorum_or_nonleaders_available(3));
+            // Ensure the elected leader (anchor) is unchanged.
+            assert_eq!(bft.leader(), Some(leader));
+        }
+        // Ensure both nodes advance without an unreferenced leader, once the timer expires.
+        for bft in &nodes {
+            *bft.leader_certificate.write() = Some(sample_batch_certificate_for_round(2, rng));
+            assert!(bft.is_leader_quorum_or_nonleaders_available(3));
+        }
+        // Ensure both nodes advance without the referenced leader, once the grace expires.
+        for bft in &nodes {
+            *bft.leader_certificate.write() = Some(leader_certificate.clone());
+            bft.leader_certificate_timer.store(timer - MAX_LEADER_CERTIFICATE_DELAY_IN_SECS, Ordering::SeqCst);
+            assert!(bft.is_leader_quorum_or_nonleaders_available(3));
+        }
+        Ok(())
+    }
+
     #[tokio::test]
     #[tracing_test::traced_test]
     async fn test_order_dag_with_dfs() -> Result<()> {
@@ -1266,6 +1410,69 @@ mod tests {
         Ok(())
     }
 
+    #[tokio::test]
+    #[tracing_test::traced_test]
+    async fn test_round_timing_on_commit() -> Result<()> {
+        let rng = &mut TestRng::default();
+
+        // Initialize the round parameters.
+        let commit_round = 2;
+
+        // Sample the certificates.
+        let (_, certificates) = snarkvm::ledger::narwhal::batch_certificate::test_helpers::sample_batch_certificate_with_previous_certificates(
+            commit_round + 1,
+            rng,
+        );
+        // Initialize the committee.
+        let authors = certificates.iter().map(BatchCertificate::author).collect();
+        let committee =
+            snarkvm::ledger::committee::test_helpers::sample_committee_for_round_and_members(0, authors, rng);
+        // Initialize the ledger.
+        let ledger = Arc::new(MockLedgerService::new(committee.clone()));
+        // Initialize the storage.
+        let storage = Storage::new(ledger.clone(), Arc::new(BFTMemoryService::new()), 10);
+        for certificate in certificates.iter() {
+            storage.testing_only_insert_certificate_testing_only(certificate.clone());
+        }
+        // Get the leader certificate.
+        let leader = committee.get_leader(commit_round).unwrap();
+        let leader_certificate = storage.get_certificate_for_round_with_author(commit_round, leader).unwrap();
+
+        // Initialize the BFT, with a CSV sink for the round timing.
+        let account = Account::new(rng)?;
+        let bft = BFT::new(account, storage, ledger, None, &[], None)?;
+        *bft.dag.write() = crate::helpers::dag::test_helpers::mock_dag_with_modified_last_committed_round(commit_round);
+        let path = std::env::temp_dir().join(format!("bft-round-timing-{}.csv", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+        bft.round_timings().set_csv_sink(Some(&path))?;
+
+        // Open the round, and insert its certificates into the BFT.
+        bft.round_timings().open(commit_round);
+        for certificate in certificates {
+            bft.update_dag::<false>(certificate).await?;
+        }
+        assert!(bft.round_timings().has_quorum(commit_round));
+        assert!(bft.round_timings().has_anchor(commit_round));
+
+        // Commit the leader certificate, and ensure the record captures the timing fields.
+        bft.commit_leader_certificate::<false>(leader_certificate, Default::default()).await?;
+        let contents = std::fs::read_to_string(&path)?;
+        let rows = contents.lines().collect::<Vec<_>>();
+        assert_eq!(rows.len(), 2);
+        assert_eq!(rows[0], crate::helpers::RoundTiming::CSV_HEADER);
+        let fields = rows[1].split(',').collect::<Vec<_>>();
+        assert_eq!(fields[0], commit_round.to_string());
+        let timestamps = fields[1..].iter().map(|field| field.parse::<i64>()).collect::<Result<Vec<_>, _>>()?;
+        let [opened_at, quorum_at, anchor_at, committed_at]: [i64; 4] = timestamps.try_into().unwrap();
+        assert!(opened_at <= quorum_at.min(anchor_at));
+        assert!(quorum_at.max(anchor_at) <= committed_at);
+        // Ensure the committed round is no longer tracked.
+        assert!(!bft.round_timings().has_quorum(commit_round));
+
+        std::fs::remove_file(&path)?;
+        Ok(())
+    }
+
     #[tokio::test]
     #[tracing_test::traced_test]
     async fn test_sync_bft_dag_at_bootup() -> Result<()> {
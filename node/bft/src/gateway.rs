@@ -12,9 +12,34 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+#[cfg(feature = "mirror")]
+use crate::helpers::EventMirror;
+#[cfg(feature = "quic")]
+use crate::helpers::{QuicEndpoint, QuicPeer, QuicStream};
 use crate::{
-    events::{EventCodec, PrimaryPing},
-    helpers::{assign_to_worker, Cache, PrimarySender, Resolver, SyncSender, WorkerSender},
+    events::{EventCodec, PrimaryPing, SizedEventCodec},
+    helpers::{
+        now,
+        route_transmission_request,
+        AsnLookup,
+        Cache,
+        CacheWindow,
+        CacheWindows,
+        CertificatePenalty,
+        CertificatePenaltyPolicy,
+        ConnectionGroup,
+        MaintenanceWindow,
+        PrimarySender,
+        Resolver,
+        RiskAction,
+        RiskPolicy,
+        RiskSignals,
+        SyncSender,
+        WorkerAffinity,
+        WorkerSender,
+        CACHE_PRUNING_TASK,
+        METRICS_TASK,
+    },
     spawn_blocking,
     CONTEXT,
     MAX_BATCH_DELAY_IN_MS,
@@ -27,6 +52,8 @@ use snarkos_account::Account;
 use snarkos_node_bft_events::{
     BlockRequest,
     BlockResponse,
+    BlockResponseAssembler,
+    BlockResponseChunk,
     CertificateRequest,
     CertificateResponse,
     ChallengeRequest,
@@ -63,18 +90,28 @@ use futures::SinkExt;
 use indexmap::{IndexMap, IndexSet};
 use parking_lot::{Mutex, RwLock};
 use rand::seq::{IteratorRandom, SliceRandom};
-use std::{collections::HashSet, future::Future, io, net::SocketAddr, sync::Arc, time::Duration};
+use std::{
+    collections::HashSet,
+    future::Future,
+    io,
+    net::{IpAddr, SocketAddr},
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc,
+    },
+    time::{Duration, Instant},
+};
 use tokio::{
-    net::TcpStream,
+    io::{AsyncRead, AsyncWrite},
     sync::{oneshot, OnceCell},
     task::{self, JoinHandle},
 };
 use tokio_stream::StreamExt;
 use tokio_util::codec::Framed;
 
-/// The maximum interval of events to cache.
+/// The default interval of events to cache.
 const CACHE_EVENTS_INTERVAL: i64 = (MAX_BATCH_DELAY_IN_MS / 1000) as i64; // seconds
-/// The maximum interval of requests to cache.
+/// The default interval of requests to cache.
 const CACHE_REQUESTS_INTERVAL: i64 = (MAX_BATCH_DELAY_IN_MS / 1000) as i64; // seconds
 
 /// The maximum number of connection attempts in an interval.
@@ -82,6 +119,20 @@ const MAX_CONNECTION_ATTEMPTS: usize = 10;
 /// The maximum interval to restrict a peer.
 const RESTRICTED_INTERVAL: i64 = (MAX_CONNECTION_ATTEMPTS as u64 * MAX_BATCH_DELAY_IN_MS / 1000) as i64; // seconds
 
+/// The default deadline for a peer to complete the handshake, i.e. the default handshake timeout of the TCP stack.
+const DEFAULT_HANDSHAKE_DEADLINE_IN_MS: u64 = 3_000; // ms
+/// The maximum deadline for a peer to complete the handshake.
+const MAX_HANDSHAKE_DEADLINE_IN_MS: u64 = 60_000; // ms
+/// The number of connection attempts that a peer is penalized with, for failing to complete the handshake in time.
+const HANDSHAKE_DEADLINE_PENALTY: usize = MAX_CONNECTION_ATTEMPTS / 2;
+/// The round-trip time at which the round-trip time signal of the risk score is at its limit.
+/// Note: The round-trip time is sampled over the challenge of the handshake and the outstanding block requests,
+/// so it includes the time for the peer to process them.
+const RISK_RTT_LIMIT_IN_MS: u64 = 2_000; // ms
+/// The duration after which a chunked block response that is still pending from a peer is dropped,
+/// i.e. the block request timeout of the sync module.
+const BLOCK_RESPONSE_TTL_IN_SECS: u64 = 60; // seconds
+
 /// The minimum number of validators to maintain a connection to.
 const MIN_CONNECTED_VALIDATORS: usize = 175;
 /// The maximum number of validators to send in a validators response event.
@@ -116,12 +167,49 @@ pub struct Gateway<N: Network> {
     /// prevent simultaneous "two-way" connections between two peers (i.e. both nodes simultaneously
     /// attempt to connect to each other). This set is used to prevent this from happening.
     connecting_peers: Arc<Mutex<IndexSet<SocketAddr>>>,
+    /// The deadline (in milliseconds) for a peer to complete the handshake.
+    handshake_deadline_in_ms: Arc<AtomicU64>,
+    /// The set of IPs of the probes (e.g. health checks), which are exempt from the rate limits.
+    probe_sources: Arc<RwLock<IndexSet<IpAddr>>>,
+    /// The number of inbound connections from the probes.
+    num_probe_connections: Arc<AtomicU64>,
+    /// The maintenance window consulted before the deferrable heartbeat tasks, if one is set.
+    maintenance_window: Arc<RwLock<Option<Arc<MaintenanceWindow>>>>,
+    /// The lookup of the ASNs of the peers, for grouping their connections, if one is set.
+    asn_lookup: Arc<RwLock<Option<Arc<dyn AsnLookup>>>>,
+    /// The maximum number of recent connection attempts from each ASN, if one is set.
+    max_connections_per_asn: Arc<RwLock<Option<usize>>>,
+    /// The maximum number of recent connection attempts from each subnet, if one is set.
+    max_connections_per_subnet: Arc<RwLock<Option<usize>>>,
+    /// The map of IPs to the validator addresses that last completed a handshake from them, which are exempt
+    /// from the per-group connection limits while the validators are in the committee.
+    validator_ips: Arc<RwLock<IndexMap<IpAddr, Address<N>>>>,
+    /// The graduated penalties for peers that send invalid certificates, if the penalties are enabled.
+    certificate_penalty_policy: Arc<RwLock<Option<CertificatePenaltyPolicy>>>,
+    /// The dedication of workers to transmission types.
+    worker_affinity: Arc<RwLock<WorkerAffinity>>,
+    /// The graduated actions for peers by their combined risk score, if the risk score is enabled.
+    risk_policy: Arc<RwLock<Option<RiskPolicy>>>,
+    /// The map of temporarily-banned peer IPs to the UNIX timestamp (in seconds) at which their ban expires.
+    temp_banned_peers: Arc<RwLock<IndexMap<SocketAddr, i64>>>,
+    /// The map of peer IPs and outstanding block requests to the time of the request,
+    /// and the (partially) reassembled chunked block response.
+    pending_block_responses: Arc<Mutex<IndexMap<(SocketAddr, BlockRequest), (Instant, BlockResponseAssembler<N>)>>>,
     /// The primary sender.
     primary_sender: Arc<OnceCell<PrimarySender<N>>>,
     /// The worker senders.
     worker_senders: Arc<OnceCell<IndexMap<u8, WorkerSender<N>>>>,
     /// The sync sender.
     sync_sender: Arc<OnceCell<SyncSender<N>>>,
+    /// The QUIC endpoint, if QUIC is enabled.
+    #[cfg(feature = "quic")]
+    quic: Arc<OnceCell<QuicEndpoint>>,
+    /// The map of (ambiguous) peer addresses to the peers that are connected over QUIC.
+    #[cfg(feature = "quic")]
+    quic_peers: Arc<RwLock<IndexMap<SocketAddr, QuicPeer<N>>>>,
+    /// The mirror of the inbound event metadata, if an analysis pipeline is attached.
+    #[cfg(feature = "mirror")]
+    event_mirror: Arc<RwLock<Option<Arc<EventMirror>>>>,
     /// The spawned handles.
     handles: Arc<Mutex<Vec<JoinHandle<()>>>>,
     /// The development mode.
@@ -150,14 +238,39 @@ impl<N: Network> Gateway<N> {
             account,
             ledger,
             tcp,
-            cache: Default::default(),
+            cache: Arc::new(Cache::with_windows(CacheWindows {
+                connections: CacheWindow::Secs(RESTRICTED_INTERVAL),
+                events: CacheWindow::Secs(CACHE_EVENTS_INTERVAL),
+                requests: CacheWindow::Secs(CACHE_REQUESTS_INTERVAL),
+                invalid_certificates: CacheWindow::Secs(CertificatePenaltyPolicy::DEFAULT_WINDOW_IN_SECS),
+                ..Default::default()
+            })),
             resolver: Default::default(),
             trusted_validators: trusted_validators.iter().copied().collect(),
             connected_peers: Default::default(),
             connecting_peers: Default::default(),
+            handshake_deadline_in_ms: Arc::new(AtomicU64::new(DEFAULT_HANDSHAKE_DEADLINE_IN_MS)),
+            probe_sources: Default::default(),
+            num_probe_connections: Default::default(),
+            maintenance_window: Default::default(),
+            asn_lookup: Default::default(),
+            max_connections_per_asn: Default::default(),
+            max_connections_per_subnet: Default::default(),
+            validator_ips: Default::default(),
+            certificate_penalty_policy: Default::default(),
+            worker_affinity: Default::default(),
+            risk_policy: Default::default(),
+            temp_banned_peers: Default::default(),
+            pending_block_responses: Default::default(),
             primary_sender: Default::default(),
             worker_senders: Default::default(),
             sync_sender: Default::default(),
+            #[cfg(feature = "quic")]
+            quic: Default::default(),
+            #[cfg(feature = "quic")]
+            quic_peers: Default::default(),
+            #[cfg(feature = "mirror")]
+            event_mirror: Default::default(),
             handles: Default::default(),
             dev,
         })
@@ -191,6 +304,11 @@ impl<N: Network> Gateway<N> {
         self.enable_on_connect().await;
         // Enable the TCP listener. Note: This must be called after the above protocols.
         let _listening_addr = self.tcp.enable_listener().await.expect("Failed to enable the TCP listener");
+        // Enable the QUIC listener. Note: Peers that only speak TCP are still able to connect.
+        #[cfg(feature = "quic")]
+        if let Err(e) = self.enable_quic() {
+            warn!("{CONTEXT} Unable to enable the QUIC listener - {e}");
+        }
 
         // Initialize the heartbeat.
         self.initialize_heartbeat();
@@ -227,6 +345,44 @@ impl<N: Network> Gateway<N> {
     fn max_cache_duplicates(&self) -> usize {
         self.max_committee_size().pow(2)
     }
+
+    /// Returns the window of the events to cache, for rate limiting the events of each peer.
+    pub fn cache_events_window(&self) -> CacheWindow {
+        self.cache.windows().events
+    }
+
+    /// Sets the window of the events to cache, in seconds, or in rounds (which adapts to the round duration).
+    pub fn set_cache_events_window(&self, window: CacheWindow) -> Result<()> {
+        ensure!(window.is_nonzero(), "The window of the events to cache must be nonzero");
+        self.cache.set_windows(CacheWindows { events: window, ..self.cache.windows() })
+    }
+
+    /// Returns the window of the requests to cache, for deduplicating the requests for each item.
+    pub fn cache_requests_window(&self) -> CacheWindow {
+        self.cache.windows().requests
+    }
+
+    /// Sets the window of the requests to cache, in seconds, or in rounds (which adapts to the round duration).
+    pub fn set_cache_requests_window(&self, window: CacheWindow) -> Result<()> {
+        ensure!(window.is_nonzero(), "The window of the requests to cache must be nonzero");
+        self.cache.set_windows(CacheWindows { requests: window, ..self.cache.windows() })
+    }
+
+    /// Returns the count of an insert above which a cached key is a likely spam source, if one is set.
+    pub fn cache_spam_threshold(&self) -> Option<usize> {
+        self.cache.spam_threshold()
+    }
+
+    /// Sets the count of an insert above which a cached key is a likely spam source, for the spam hits metric.
+    /// If `None` is given, the spam hits are not counted.
+    pub fn set_cache_spam_threshold(&self, threshold: Option<usize>) {
+        self.cache.set_spam_threshold(threshold);
+    }
+
+    /// Records that the primary reached the given round, so the windows in rounds track the round duration.
+    pub fn observe_round(&self, round: u64) {
+        self.cache.observe_round(round);
+    }
 }
 
 #[async_trait]
@@ -374,6 +530,21 @@ impl<N: Network> Gateway<N> {
         let self_ = self.clone();
         Some(tokio::spawn(async move {
             debug!("Connecting to validator {peer_ip}...");
+            // If QUIC is enabled, attempt to connect to the peer over QUIC.
+            #[cfg(feature = "quic")]
+            if let Some(quic) = self_.quic.get() {
+                match quic.connect(peer_ip).await {
+                    Ok(stream) => {
+                        if let Err(error) = self_.handle_quic_stream(peer_ip, Some(peer_ip), stream).await {
+                            self_.connecting_peers.lock().shift_remove(&peer_ip);
+                            warn!("Unable to connect to '{peer_ip}' over QUIC - {error}");
+                        }
+                        return;
+                    }
+                    // Otherwise, fall back to TCP.
+                    Err(error) => debug!("Falling back to TCP for '{peer_ip}' - {error}"),
+                }
+            }
             // Attempt to connect to the peer.
             if let Err(error) = self_.tcp.connect(peer_ip).await {
                 self_.connecting_peers.lock().shift_remove(&peer_ip);
@@ -382,6 +553,381 @@ impl<N: Network> Gateway<N> {
         }))
     }
 
+    /// Returns the deadline for a peer to complete the handshake.
+    pub fn handshake_deadline(&self) -> Duration {
+        Duration::from_millis(self.handshake_deadline_in_ms.load(Ordering::SeqCst))
+    }
+
+    /// Sets the deadline for a peer to complete the handshake, after which the connection is dropped,
+    /// and an inbound peer is penalized as if it made multiple connection attempts.
+    pub fn set_handshake_deadline(&self, deadline: Duration) -> Result<()> {
+        // Ensure the deadline is nonzero, and within the maximum deadline.
+        let deadline_in_ms = u64::try_from(deadline.as_millis())?;
+        ensure!(deadline_in_ms > 0, "The handshake deadline must be nonzero");
+        ensure!(
+            deadline_in_ms <= MAX_HANDSHAKE_DEADLINE_IN_MS,
+            "The handshake deadline must be at most {MAX_HANDSHAKE_DEADLINE_IN_MS}ms"
+        );
+        self.handshake_deadline_in_ms.store(deadline_in_ms, Ordering::SeqCst);
+        Ok(())
+    }
+
+    /// Sets the maintenance window, which defers the pruning and the metrics of the cache under heavy load.
+    pub fn set_maintenance_window(&self, maintenance_window: Arc<MaintenanceWindow>) {
+        *self.maintenance_window.write() = Some(maintenance_window);
+    }
+
+    /// Returns `true` if the given background task may run now, as it is not deferred by the maintenance window.
+    fn is_permitted(&self, task: &str) -> bool {
+        self.maintenance_window.read().as_ref().map_or(true, |window| window.is_permitted(task))
+    }
+
+    /// Returns the IPs of the probes (e.g. health checks).
+    pub fn probe_sources(&self) -> IndexSet<IpAddr> {
+        self.probe_sources.read().clone()
+    }
+
+    /// Sets the IPs of the probes (e.g. health checks). The connections from a probe are still accepted,
+    /// but are excluded from the spam cache and the peer metrics, and are metered separately.
+    pub fn set_probe_sources(&self, probe_sources: impl IntoIterator<Item = IpAddr>) {
+        *self.probe_sources.write() = probe_sources.into_iter().collect();
+    }
+
+    /// Returns `true` if the given IP is a probe (e.g. a health check).
+    pub fn is_probe_source(&self, ip: IpAddr) -> bool {
+        self.probe_sources.read().contains(&ip)
+    }
+
+    /// Returns the number of inbound connections from the probes.
+    pub fn num_probe_connections(&self) -> u64 {
+        self.num_probe_connections.load(Ordering::Relaxed)
+    }
+
+    /// Sets the lookup of the ASNs of the peers (e.g. an `AsnTable` of a configured dataset), so that their
+    /// connections are grouped by ASN. If `None` is given, or the ASN of a peer is not known, the connections
+    /// are grouped by subnet.
+    pub fn set_asn_lookup(&self, asn_lookup: Option<Arc<dyn AsnLookup>>) {
+        *self.asn_lookup.write() = asn_lookup;
+    }
+
+    /// Returns the group (i.e. ASN, or subnet) that the connections from the given IP are counted in.
+    pub fn connection_group(&self, ip: IpAddr) -> ConnectionGroup {
+        ConnectionGroup::of(ip, self.asn_lookup.read().as_deref())
+    }
+
+    /// Returns the maximum number of recent connection attempts from each ASN, if one is set.
+    pub fn max_connections_per_asn(&self) -> Option<usize> {
+        *self.max_connections_per_asn.read()
+    }
+
+    /// Sets the maximum number of recent connection attempts from each ASN, which limits the attacks that span
+    /// many IPs within one network. If `None` is given, the connections from an ASN are not limited as a group.
+    pub fn set_max_connections_per_asn(&self, max_connections: Option<usize>) -> Result<()> {
+        ensure!(max_connections != Some(0), "The maximum number of connections per ASN must be nonzero");
+        *self.max_connections_per_asn.write() = max_connections;
+        Ok(())
+    }
+
+    /// Returns the maximum number of recent connection attempts from each subnet, if one is set.
+    pub fn max_connections_per_subnet(&self) -> Option<usize> {
+        *self.max_connections_per_subnet.read()
+    }
+
+    /// Sets the maximum number of recent connection attempts from each subnet, which applies to the IPs whose ASN
+    /// is not known. As a subnet is far smaller than an ASN, this limit is typically lower than the ASN limit.
+    /// If `None` is given, the connections from a subnet are not limited as a group.
+    pub fn set_max_connections_per_subnet(&self, max_connections: Option<usize>) -> Result<()> {
+        ensure!(max_connections != Some(0), "The maximum number of connections per subnet must be nonzero");
+        *self.max_connections_per_subnet.write() = max_connections;
+        Ok(())
+    }
+
+    /// Returns `true` if the given peer IP is exempt from the per-group connection limits, i.e. it is a trusted
+    /// validator, or a committee member last completed a handshake from its IP. Note: The address that a peer
+    /// claims before the handshake is not authenticated, so only the IPs of verified validators are exempt.
+    fn is_exempt_from_group_limits(&self, peer_ip: SocketAddr) -> bool {
+        self.trusted_validators.contains(&peer_ip)
+            || self
+                .validator_ips
+                .read()
+                .get(&peer_ip.ip())
+                .is_some_and(|address| self.is_authorized_validator_address(*address))
+    }
+
+    /// Records the IP from which the given validator completed a handshake, and prunes the IPs of the validators
+    /// that are no longer in the committee.
+    fn insert_validator_ip(&self, peer_ip: SocketAddr, address: Address<N>) {
+        let mut validator_ips = self.validator_ips.write();
+        validator_ips.retain(|_, validator| *validator != address && self.is_authorized_validator_address(*validator));
+        if self.is_authorized_validator_address(address) {
+            validator_ips.insert(peer_ip.ip(), address);
+        }
+    }
+
+    /// Sets the mirror of the inbound event metadata, or detaches the current mirror if `None` is given.
+    #[cfg(feature = "mirror")]
+    pub fn set_event_mirror(&self, event_mirror: Option<EventMirror>) {
+        *self.event_mirror.write() = event_mirror.map(Arc::new);
+    }
+
+    /// Returns the graduated penalties for peers that send invalid certificates, if the penalties are enabled.
+    pub fn certificate_penalty_policy(&self) -> Option<CertificatePenaltyPolicy> {
+        *self.certificate_penalty_policy.read()
+    }
+
+    /// Sets the graduated penalties for peers that send invalid certificates. If `None` is given,
+    /// the invalid certificates are still counted for the risk score, but no peer is penalized for them.
+    pub fn set_certificate_penalty_policy(&self, policy: Option<CertificatePenaltyPolicy>) {
+        // Retain the invalid certificates over the window of the policy.
+        let window = CacheWindow::Secs(policy.unwrap_or_default().window_in_secs());
+        let windows = CacheWindows { invalid_certificates: window, ..self.cache.windows() };
+        if let Err(error) = self.cache.set_windows(windows) {
+            warn!("{CONTEXT} Cannot set the window of the invalid certificates - {error}");
+        }
+        *self.certificate_penalty_policy.write() = policy;
+    }
+
+    /// Returns the graduated actions for peers by their combined risk score, if the risk score is enabled.
+    pub fn risk_policy(&self) -> Option<RiskPolicy> {
+        *self.risk_policy.read()
+    }
+
+    /// Sets the graduated actions for peers by their combined risk score. If `None` is given,
+    /// the risk score is disabled, and only the independent limits of the signals apply.
+    pub fn set_risk_policy(&self, policy: Option<RiskPolicy>) {
+        *self.risk_policy.write() = policy;
+    }
+
+    /// Returns the dedication of workers to transmission types.
+    pub fn worker_affinity(&self) -> WorkerAffinity {
+        self.worker_affinity.read().clone()
+    }
+
+    /// Sets the dedication of workers to transmission types, for the routing of incoming transmissions.
+    pub fn set_worker_affinity(&self, affinity: WorkerAffinity) {
+        *self.worker_affinity.write() = affinity;
+    }
+
+    /// Returns `true` if the given peer IP is temporarily banned, for sending too many invalid certificates.
+    pub fn is_temp_banned(&self, peer_ip: SocketAddr) -> bool {
+        let now = now();
+        let mut temp_banned_peers = self.temp_banned_peers.write();
+        // Remove the expired bans.
+        temp_banned_peers.retain(|_, expiry| *expiry > now);
+        temp_banned_peers.contains_key(&peer_ip)
+    }
+
+    /// Returns the current penalty of the given peer, for the invalid certificates it sent within the window.
+    pub fn certificate_penalty(&self, peer_ip: SocketAddr) -> CertificatePenalty {
+        if self.is_temp_banned(peer_ip) {
+            return CertificatePenalty::TempBan;
+        }
+        let Some(policy) = self.certificate_penalty_policy() else {
+            return CertificatePenalty::None;
+        };
+        let num_invalid = self.cache.num_invalid_certificates(peer_ip);
+        // Note: The ban is only imposed when reporting, so that an expired ban is not immediately reimposed.
+        policy.penalty(num_invalid).min(CertificatePenalty::Throttle)
+    }
+
+    /// Reports that the given peer sent an invalid certificate, and applies the resulting penalty.
+    /// If the penalties are disabled, the invalid certificate is only counted.
+    pub fn report_invalid_certificate(&self, peer_ip: SocketAddr) -> CertificatePenalty {
+        let policy = self.certificate_penalty_policy();
+        let num_invalid = self.cache.record_invalid_certificate(peer_ip);
+        let Some(policy) = policy else {
+            return CertificatePenalty::None;
+        };
+        let penalty = policy.penalty(num_invalid);
+        match penalty {
+            CertificatePenalty::None => {}
+            CertificatePenalty::Warn => {
+                warn!("{CONTEXT} Peer '{peer_ip}' sent {num_invalid} invalid certificate(s) recently")
+            }
+            CertificatePenalty::Throttle => {
+                warn!("{CONTEXT} Throttling the certificates from '{peer_ip}' ({num_invalid} invalid certificates)")
+            }
+            CertificatePenalty::TempBan => {
+                let expiry = now().saturating_add(policy.ban_duration().as_secs() as i64);
+                self.temp_banned_peers.write().insert(peer_ip, expiry);
+                warn!("{CONTEXT} Temporarily banning '{peer_ip}' ({num_invalid} invalid certificates)");
+                self.disconnect(peer_ip);
+            }
+        }
+        penalty
+    }
+
+    /// Returns the signals of the given peer for its risk score, each relative to its independent limit.
+    pub fn risk_signals(&self, peer_ip: SocketAddr) -> RiskSignals {
+        let certificate_policy = self.certificate_penalty_policy().unwrap_or_default();
+        let num_events = self.cache.num_inbound_events(peer_ip);
+        let num_connections = self.cache.num_inbound_connections(peer_ip.ip());
+        let num_invalid = self.cache.num_invalid_certificates(peer_ip);
+        let rtt = self.cache.peer_rtt(peer_ip).unwrap_or_default();
+        RiskSignals {
+            events: num_events as f64 / self.max_cache_events().max(1) as f64,
+            connections: num_connections as f64 / MAX_CONNECTION_ATTEMPTS as f64,
+            invalid_certificates: num_invalid as f64 / certificate_policy.ban_threshold() as f64,
+            rtt: rtt.as_millis() as f64 / RISK_RTT_LIMIT_IN_MS as f64,
+        }
+    }
+
+    /// Assesses the combined risk score of the given peer, and applies the resulting action.
+    /// If the risk score is disabled, no action is taken.
+    pub fn assess_risk(&self, peer_ip: SocketAddr) -> RiskAction {
+        let Some(policy) = self.risk_policy() else {
+            return RiskAction::None;
+        };
+        let score = policy.score(&self.risk_signals(peer_ip));
+        let mut action = policy.action(score);
+        // Note: The trusted validators, and the committee member without which the node loses its quorum, are only
+        // monitored, as throttling or banning them would stall the BFT; the independent limits still apply to them.
+        if action > RiskAction::Monitor && (self.trusted_validators.contains(&peer_ip) || self.is_quorum_peer(peer_ip))
+        {
+            action = RiskAction::Monitor;
+        }
+        match action {
+            // Note: The throttled events are logged as they are dropped.
+            RiskAction::None | RiskAction::Throttle => {}
+            RiskAction::Monitor => debug!("{CONTEXT} Monitoring '{peer_ip}' (risk score = {score:.2})"),
+            RiskAction::Ban => {
+                let expiry = now().saturating_add(policy.ban_duration().as_secs() as i64);
+                self.temp_banned_peers.write().insert(peer_ip, expiry);
+                warn!("{CONTEXT} Temporarily banning '{peer_ip}' (risk score = {score:.2})");
+                self.disconnect(peer_ip);
+            }
+        }
+        action
+    }
+
+    /// Returns `true` if the node and its other connected validators do not reach the quorum threshold without
+    /// the given peer, i.e. the node needs the peer for its quorum.
+    fn is_quorum_peer(&self, peer_ip: SocketAddr) -> bool {
+        let Some(address) = self.resolver.get_address(peer_ip) else {
+            return false;
+        };
+        let Ok(committee) = self.ledger.get_committee_lookback_for_round(self.ledger.latest_round()) else {
+            return false;
+        };
+        let mut connected_validators = self.connected_addresses();
+        connected_validators.insert(self.account.address());
+        connected_validators.remove(&address);
+        committee.is_committee_member(address) && !committee.is_quorum_threshold_reached(&connected_validators)
+    }
+
+    /// Penalizes the given inbound peer for failing to complete the handshake within the deadline.
+    fn penalize_handshake_straggler(&self, peer_addr: SocketAddr) {
+        // Note: A probe commonly connects without handshaking, and is not penalized for it.
+        if self.is_probe_source(peer_addr.ip()) {
+            return;
+        }
+        for _ in 0..HANDSHAKE_DEADLINE_PENALTY {
+            self.cache.record_inbound_connection(peer_addr.ip());
+        }
+    }
+
+    /// Processes the given block response from the peer.
+    async fn process_block_response(&self, peer_ip: SocketAddr, block_response: BlockResponse<N>) -> Result<()> {
+        // If a sync sender was provided, then process the block response.
+        if let Some(sync_sender) = self.sync_sender.get() {
+            // Retrieve the block response.
+            let BlockResponse { request, blocks } = block_response;
+            // Perform the deferred non-blocking deserialization of the blocks.
+            let blocks = blocks.deserialize().await.map_err(|error| anyhow!("[BlockResponse] {error}"))?;
+            // Ensure the block response is well-formed.
+            blocks.ensure_response_is_well_formed(peer_ip, request.start_height, request.end_height)?;
+            // Send the blocks to the sync module.
+            return sync_sender.advance_with_sync_blocks(peer_ip, blocks.0).await;
+        }
+        Ok(())
+    }
+
+    /// Sends the given blocks to the peer, in `BlockResponseChunk`s if they exceed the chunk size.
+    /// Note: The chunks are serialized as they are sent, and each chunk is sent once the previous one is delivered,
+    /// so that only a few chunks of the block response are buffered at a time.
+    async fn send_block_response(&self, peer_ip: SocketAddr, request: BlockRequest, blocks: DataBlocks<N>) {
+        // Serialize the blocks into chunks in the background, returning the blocks if they need not be chunked.
+        let (chunk_sender, mut chunk_receiver) = tokio::sync::mpsc::channel(1);
+        let handle = task::spawn_blocking(move || {
+            let send_chunk = |chunk| chunk_sender.blocking_send(chunk).map_err(|_| anyhow!("The peer disconnected"));
+            let is_chunked = BlockResponseChunk::stream(request, &blocks, BlockResponseChunk::CHUNK_SIZE, send_chunk)?;
+            Ok::<_, anyhow::Error>((!is_chunked).then_some(blocks))
+        });
+        // Send the `BlockResponseChunk` messages to the peer, in order.
+        while let Some(chunk) = chunk_receiver.recv().await {
+            let Some(delivery) = Transport::send(self, peer_ip, Event::BlockResponseChunk(chunk)).await else {
+                return;
+            };
+            if !matches!(delivery.await, Ok(Ok(()))) {
+                return;
+            }
+        }
+        match handle.await {
+            // Send the `BlockResponse` message to the peer.
+            Ok(Ok(Some(blocks))) => {
+                let event = Event::BlockResponse(BlockResponse { request, blocks: Data::Object(blocks) });
+                Transport::send(self, peer_ip, event).await;
+            }
+            Ok(Ok(None)) => (),
+            Ok(Err(error)) => warn!("Unable to chunk the block response for '{peer_ip}' - {error}"),
+            Err(error) => warn!("[BlockResponse] {error}"),
+        }
+    }
+
+    /// Records the given block request to the peer, so that its chunked block response is reassembled.
+    fn insert_pending_block_response(&self, peer_ip: SocketAddr, request: BlockRequest) {
+        let mut pending = self.pending_block_responses.lock();
+        // Remove the expired block responses.
+        let now = Instant::now();
+        pending.retain(|_, (requested_at, _)| now.duration_since(*requested_at).as_secs() < BLOCK_RESPONSE_TTL_IN_SECS);
+        // Insert the block request, restarting the reassembly if the block request is repeated.
+        let assembler = BlockResponseAssembler::new(BlockResponseAssembler::<N>::MAXIMUM_SIZE);
+        pending.insert((peer_ip, request), (now, assembler));
+    }
+
+    /// Inserts the given block response chunk from the peer, and returns the block response once it is reassembled.
+    /// Note: Only the chunks for outstanding block requests are reassembled, the pending block responses expire,
+    /// and the total size of the chunked block responses that are pending from a peer is capped.
+    fn insert_block_response_chunk(
+        &self,
+        peer_ip: SocketAddr,
+        chunk: BlockResponseChunk,
+    ) -> Result<Option<BlockResponse<N>>> {
+        let mut pending = self.pending_block_responses.lock();
+        // Remove the expired block responses.
+        let now = Instant::now();
+        pending.retain(|_, (requested_at, _)| now.duration_since(*requested_at).as_secs() < BLOCK_RESPONSE_TTL_IN_SECS);
+        // Ensure the chunk is for an outstanding block request.
+        let key = (peer_ip, chunk.request);
+        if !pending.contains_key(&key) {
+            bail!("Peer '{peer_ip}' sent an unsolicited block response chunk")
+        }
+        // Ensure the total size pending from the peer, including this chunk, is within the cap.
+        let num_pending: usize = pending.iter().filter(|((ip, _), _)| *ip == peer_ip).map(|(_, (_, a))| a.len()).sum();
+        if num_pending + chunk.bytes.len() > BlockResponseAssembler::<N>::MAXIMUM_SIZE {
+            pending.retain(|(ip, _), _| *ip != peer_ip);
+            bail!("Peer '{peer_ip}' exceeded the maximum size of pending block responses")
+        }
+        // Insert the chunk into the assembler for the block request.
+        let Some((_, assembler)) = pending.get_mut(&key) else {
+            bail!("Peer '{peer_ip}' sent an unsolicited block response chunk")
+        };
+        match assembler.insert(chunk) {
+            Ok(None) => Ok(None),
+            Ok(Some(block_response)) => {
+                // Sample the round-trip time of the peer, from the block request to its reassembled response.
+                if let Some((requested_at, _)) = pending.shift_remove(&key) {
+                    self.cache.insert_peer_rtt(peer_ip, requested_at.elapsed());
+                }
+                Ok(Some(block_response))
+            }
+            Err(error) => {
+                pending.shift_remove(&key);
+                bail!("Peer '{peer_ip}' sent an invalid block response chunk - {error}")
+            }
+        }
+    }
+
     /// Ensure we are allowed to connect to the given peer.
     fn check_connection_attempt(&self, peer_ip: SocketAddr) -> Result<()> {
         // Ensure the peer IP is not this node.
@@ -400,6 +946,10 @@ impl<N: Network> Gateway<N> {
         if self.is_connecting_ip(peer_ip) {
             bail!("{CONTEXT} Dropping connection attempt to '{peer_ip}' (already connecting)")
         }
+        // Ensure the peer is not temporarily banned.
+        if self.is_temp_banned(peer_ip) {
+            bail!("{CONTEXT} Dropping connection attempt to '{peer_ip}' (temporarily banned)")
+        }
         Ok(())
     }
 
@@ -409,6 +959,10 @@ impl<N: Network> Gateway<N> {
         if self.is_local_ip(peer_ip) {
             bail!("{CONTEXT} Dropping connection request from '{peer_ip}' (attempted to self-connect)")
         }
+        // Ensure the peer is not temporarily banned.
+        if self.is_temp_banned(peer_ip) {
+            bail!("{CONTEXT} Dropping connection request from '{peer_ip}' (temporarily banned)")
+        }
         // Ensure the node is not already connecting to this peer.
         if !self.connecting_peers.lock().insert(peer_ip) {
             bail!("{CONTEXT} Dropping connection request from '{peer_ip}' (already shaking hands as the initiator)")
@@ -418,13 +972,29 @@ impl<N: Network> Gateway<N> {
             bail!("{CONTEXT} Dropping connection request from '{peer_ip}' (already connected)")
         }
         // Ensure the peer is not spamming connection attempts.
-        if !peer_ip.ip().is_loopback() {
+        if !peer_ip.ip().is_loopback() && !self.is_probe_source(peer_ip.ip()) {
             // Add this connection attempt and retrieve the number of attempts.
-            let num_attempts = self.cache.insert_inbound_connection(peer_ip.ip(), RESTRICTED_INTERVAL);
+            let num_attempts = self.cache.record_inbound_connection(peer_ip.ip());
             // Ensure the connecting peer has not surpassed the connection attempt limit.
             if num_attempts > MAX_CONNECTION_ATTEMPTS {
                 bail!("Dropping connection request from '{peer_ip}' (tried {num_attempts} times)")
             }
+            // Ensure the group of the connecting peer has not surpassed its connection attempt limit, if one is set.
+            // Note: The trusted and committee validators are exempt, so that a flood from their network cannot
+            // lock them out.
+            if !self.is_exempt_from_group_limits(peer_ip) {
+                let group = self.connection_group(peer_ip.ip());
+                let max_connections = match group {
+                    ConnectionGroup::Asn(_) => self.max_connections_per_asn(),
+                    ConnectionGroup::Subnet(_) => self.max_connections_per_subnet(),
+                };
+                if let Some(max_connections) = max_connections {
+                    let num_attempts = self.cache.record_inbound_connection_group(group);
+                    if num_attempts > max_connections {
+                        bail!("Dropping connection request from '{peer_ip}' ({group} tried {num_attempts} times)")
+                    }
+                }
+            }
         }
         Ok(())
     }
@@ -432,7 +1002,8 @@ impl<N: Network> Gateway<N> {
     #[cfg(feature = "metrics")]
     fn update_metrics(&self) {
         metrics::gauge(metrics::bft::CONNECTED, self.connected_peers.read().len() as f64);
-        metrics::gauge(metrics::bft::CONNECTING, self.connecting_peers.lock().len() as f64);
+        let num_connecting = self.connecting_peers.lock().iter().filter(|ip| !self.is_probe_source(ip.ip())).count();
+        metrics::gauge(metrics::bft::CONNECTING, num_connecting as f64);
     }
 
     /// Inserts the given peer into the connected peers.
@@ -440,6 +1011,8 @@ impl<N: Network> Gateway<N> {
     fn insert_connected_peer(&self, peer_ip: SocketAddr, peer_addr: SocketAddr, address: Address<N>) {
         // Adds a bidirectional map between the listener address and (ambiguous) peer address.
         self.resolver.insert_peer(peer_ip, peer_addr, address);
+        // Record the verified IP of this validator.
+        self.insert_validator_ip(peer_ip, address);
         // Add a transmission for this peer in the connected peers.
         self.connected_peers.write().insert(peer_ip);
         #[cfg(feature = "metrics")]
@@ -452,6 +1025,8 @@ impl<N: Network> Gateway<N> {
     pub fn insert_connected_peer(&self, peer_ip: SocketAddr, peer_addr: SocketAddr, address: Address<N>) {
         // Adds a bidirectional map between the listener address and (ambiguous) peer address.
         self.resolver.insert_peer(peer_ip, peer_addr, address);
+        // Record the verified IP of this validator.
+        self.insert_validator_ip(peer_ip, address);
         // Add a transmission for this peer in the connected peers.
         self.connected_peers.write().insert(peer_ip);
     }
@@ -469,6 +1044,10 @@ impl<N: Network> Gateway<N> {
         }
         // Removes the bidirectional map between the listener address and (ambiguous) peer address.
         self.resolver.remove_peer(peer_ip);
+        // Remove any chunked block responses that are pending from this peer.
+        self.pending_block_responses.lock().retain(|(ip, _), _| *ip != peer_ip);
+        // Remove the round-trip time of this peer.
+        self.cache.remove_peer_rtt(peer_ip);
         // Remove this peer from the connected peers, if it exists.
         self.connected_peers.write().shift_remove(&peer_ip);
         #[cfg(feature = "metrics")]
@@ -490,6 +1069,12 @@ impl<N: Network> Gateway<N> {
         let name = event.name();
         // Send the event to the peer.
         trace!("{CONTEXT} Sending '{name}' to '{peer_ip}'");
+        #[cfg(feature = "quic")]
+        let result = match self.quic_peers.read().get(&peer_addr).cloned() {
+            Some(quic_peer) => quic_peer.send(event),
+            None => self.unicast(peer_addr, event),
+        };
+        #[cfg(not(feature = "quic"))]
         let result = self.unicast(peer_addr, event);
         // If the event was unable to be sent, disconnect.
         if let Err(e) = &result {
@@ -510,8 +1095,28 @@ impl<N: Network> Gateway<N> {
         if !self.is_authorized_validator_ip(peer_ip) {
             bail!("{CONTEXT} Dropping '{}' from '{peer_ip}' (not authorized)", event.name())
         }
+        // Drop the certificates from the peer, if it is penalized for sending invalid certificates.
+        if matches!(&event, Event::BatchCertified(_) | Event::CertificateResponse(_)) {
+            match self.certificate_penalty(peer_ip) {
+                CertificatePenalty::TempBan => bail!("{CONTEXT} Dropping '{peer_ip}' (temporarily banned)"),
+                CertificatePenalty::Throttle => {
+                    trace!("{CONTEXT} Dropping '{}' from '{peer_ip}' (throttled)", event.name());
+                    return Ok(());
+                }
+                CertificatePenalty::None | CertificatePenalty::Warn => {}
+            }
+        }
+        // Drop the events from the peer, if it is throttled or banned for its combined risk score.
+        match self.assess_risk(peer_ip) {
+            RiskAction::Ban => bail!("{CONTEXT} Dropping '{peer_ip}' (temporarily banned for its risk score)"),
+            RiskAction::Throttle => {
+                trace!("{CONTEXT} Dropping '{}' from '{peer_ip}' (throttled for its risk score)", event.name());
+                return Ok(());
+            }
+            RiskAction::None | RiskAction::Monitor => {}
+        }
         // Drop the peer, if they have exceeded the rate limit (i.e. they are requesting too much from us).
-        let num_events = self.cache.insert_inbound_event(peer_ip, CACHE_EVENTS_INTERVAL);
+        let num_events = self.cache.record_inbound_event(peer_ip);
         if num_events >= self.max_cache_events() {
             bail!("Dropping '{peer_ip}' for spamming events (num_events = {num_events})")
         }
@@ -524,19 +1129,19 @@ impl<N: Network> Gateway<N> {
                 _ => unreachable!(),
             };
             // Skip processing this certificate if the rate limit was exceed (i.e. someone is spamming a specific certificate).
-            let num_events = self.cache.insert_inbound_certificate(certificate_id, CACHE_REQUESTS_INTERVAL);
+            let num_events = self.cache.record_inbound_certificate(certificate_id);
             if num_events >= self.max_cache_duplicates() {
                 return Ok(());
             }
         } else if matches!(&event, &Event::TransmissionRequest(_) | Event::TransmissionResponse(_)) {
             // Retrieve the transmission ID.
             let transmission_id = match &event {
-                Event::TransmissionRequest(TransmissionRequest { transmission_id }) => *transmission_id,
+                Event::TransmissionRequest(TransmissionRequest { transmission_id, .. }) => *transmission_id,
                 Event::TransmissionResponse(TransmissionResponse { transmission_id, .. }) => *transmission_id,
                 _ => unreachable!(),
             };
             // Skip processing this certificate if the rate limit was exceeded (i.e. someone is spamming a specific certificate).
-            let num_events = self.cache.insert_inbound_transmission(transmission_id, CACHE_REQUESTS_INTERVAL);
+            let num_events = self.cache.record_inbound_transmission(transmission_id);
             if num_events >= self.max_cache_duplicates() {
                 return Ok(());
             }
@@ -577,7 +1182,7 @@ impl<N: Network> Gateway<N> {
                 let blocks = match task::spawn_blocking(move || {
                     // Retrieve the blocks within the requested range.
                     match self_.ledger.get_blocks(start_height..end_height) {
-                        Ok(blocks) => Ok(Data::Object(DataBlocks(blocks))),
+                        Ok(blocks) => Ok(DataBlocks(blocks)),
                         Err(error) => bail!("Missing blocks {start_height} to {end_height} from ledger - {error}"),
                     }
                 })
@@ -590,23 +1195,27 @@ impl<N: Network> Gateway<N> {
 
                 let self_ = self.clone();
                 tokio::spawn(async move {
-                    // Send the `BlockResponse` message to the peer.
-                    let event = Event::BlockResponse(BlockResponse { request: block_request, blocks });
-                    Transport::send(&self_, peer_ip, event).await;
+                    // Send the block response to the peer, in chunks if it exceeds the chunk size.
+                    self_.send_block_response(peer_ip, block_request, blocks).await;
                 });
                 Ok(())
             }
             Event::BlockResponse(block_response) => {
-                // If a sync sender was provided, then process the block response.
-                if let Some(sync_sender) = self.sync_sender.get() {
-                    // Retrieve the block response.
-                    let BlockResponse { request, blocks } = block_response;
-                    // Perform the deferred non-blocking deserialization of the blocks.
-                    let blocks = blocks.deserialize().await.map_err(|error| anyhow!("[BlockResponse] {error}"))?;
-                    // Ensure the block response is well-formed.
-                    blocks.ensure_response_is_well_formed(peer_ip, request.start_height, request.end_height)?;
-                    // Send the blocks to the sync module.
-                    return sync_sender.advance_with_sync_blocks(peer_ip, blocks.0).await;
+                // The block request is no longer outstanding, so sample the round-trip time of the peer.
+                let pending = self.pending_block_responses.lock().shift_remove(&(peer_ip, block_response.request));
+                if let Some((requested_at, _)) = pending {
+                    self.cache.insert_peer_rtt(peer_ip, requested_at.elapsed());
+                }
+                // Process the block response.
+                self.process_block_response(peer_ip, block_response).await
+            }
+            Event::BlockResponseChunk(chunk) => {
+                // If a sync sender was provided, then reassemble the block response.
+                if self.sync_sender.get().is_some() {
+                    // Once the last chunk is received, process the reassembled block response.
+                    if let Some(block_response) = self.insert_block_response_chunk(peer_ip, chunk)? {
+                        return self.process_block_response(peer_ip, block_response).await;
+                    }
                 }
                 Ok(())
             }
@@ -630,6 +1239,22 @@ impl<N: Network> Gateway<N> {
                 // Disconnect as the peer is not following the protocol.
                 bail!("{CONTEXT} Peer '{peer_ip}' is not following the protocol")
             }
+            Event::DagSnapshotRequest(dag_snapshot_request) => {
+                // If a sync sender was provided, send the DAG snapshot request to the sync module.
+                if let Some(sync_sender) = self.sync_sender.get() {
+                    // Send the DAG snapshot request to the sync module.
+                    let _ = sync_sender.tx_dag_snapshot_request.send((peer_ip, dag_snapshot_request)).await;
+                }
+                Ok(())
+            }
+            Event::DagSnapshotResponse(dag_snapshot_response) => {
+                // If a sync sender was provided, send the DAG snapshot response to the sync module.
+                if let Some(sync_sender) = self.sync_sender.get() {
+                    // Send the DAG snapshot response to the sync module.
+                    let _ = sync_sender.tx_dag_snapshot_response.send((peer_ip, dag_snapshot_response)).await;
+                }
+                Ok(())
+            }
             Event::Disconnect(disconnect) => {
                 bail!("{CONTEXT} {:?}", disconnect.reason)
             }
@@ -660,7 +1285,8 @@ impl<N: Network> Gateway<N> {
             Event::TransmissionRequest(request) => {
                 // TODO (howardwu): Add rate limiting checks on this event, on a per-peer basis.
                 // Determine the worker ID.
-                let Ok(worker_id) = assign_to_worker(request.transmission_id, self.num_workers()) else {
+                let Ok(worker_id) = route_transmission_request(&request, self.num_workers(), &self.worker_affinity())
+                else {
                     warn!("{CONTEXT} Unable to assign transmission ID '{}' to a worker", request.transmission_id);
                     return Ok(());
                 };
@@ -673,7 +1299,7 @@ impl<N: Network> Gateway<N> {
             }
             Event::TransmissionResponse(response) => {
                 // Determine the worker ID.
-                let Ok(worker_id) = assign_to_worker(response.transmission_id, self.num_workers()) else {
+                let Ok(worker_id) = self.worker_affinity().assign(response.transmission_id, self.num_workers()) else {
                     warn!("{CONTEXT} Unable to assign transmission ID '{}' to a worker", response.transmission_id);
                     return Ok(());
                 };
@@ -775,10 +1401,12 @@ impl<N: Network> Gateway<N> {
                 );
                 // Retrieve the number of workers.
                 let num_workers = self.num_workers();
+                // Retrieve the worker affinity.
+                let affinity = self.worker_affinity();
                 // Iterate over the transmission IDs.
                 for transmission_id in ping.transmission_ids.into_iter() {
                     // Determine the worker ID.
-                    let Ok(worker_id) = assign_to_worker(transmission_id, num_workers) else {
+                    let Ok(worker_id) = affinity.assign(transmission_id, num_workers) else {
                         warn!("{CONTEXT} Unable to assign transmission ID '{transmission_id}' to a worker");
                         continue;
                     };
@@ -798,6 +1426,12 @@ impl<N: Network> Gateway<N> {
         let gateway = self.clone();
         tokio::spawn(async move {
             if let Some(peer_addr) = gateway.resolver.get_ambiguous(peer_ip) {
+                // If the peer is connected over QUIC, close the connection.
+                #[cfg(feature = "quic")]
+                if let Some(quic_peer) = gateway.quic_peers.read().get(&peer_addr) {
+                    quic_peer.close();
+                    return;
+                }
                 // Disconnect from this peer.
                 let _disconnected = gateway.tcp.disconnect(peer_addr).await;
                 debug_assert!(_disconnected);
@@ -832,11 +1466,115 @@ impl<N: Network> Gateway<N> {
         info!("Shutting down the gateway...");
         // Abort the tasks.
         self.handles.lock().iter().for_each(|handle| handle.abort());
+        // Close the QUIC endpoint.
+        #[cfg(feature = "quic")]
+        if let Some(quic) = self.quic.get() {
+            quic.close();
+        }
         // Close the listener.
         self.tcp.shut_down().await;
     }
 }
 
+#[cfg(feature = "quic")]
+impl<N: Network> Gateway<N> {
+    /// Enables the QUIC listener, on the UDP port matching the TCP listener.
+    fn enable_quic(&self) -> Result<SocketAddr> {
+        // Bind the QUIC endpoint, accepting at most as many connections as the TCP listener.
+        let quic = QuicEndpoint::bind(self.local_ip(), self.tcp.config().max_connections as u32)?;
+        let local_addr = quic.local_addr()?;
+        if self.quic.set(quic.clone()).is_err() {
+            bail!("{CONTEXT} The QUIC listener is already enabled")
+        }
+        // Accept the inbound QUIC connections.
+        let self_ = self.clone();
+        self.spawn(async move {
+            while let Some(incoming) = quic.accept().await {
+                // Establish each connection in its own task, so that a slow peer does not stall the accept loop.
+                let self_ = self_.clone();
+                tokio::spawn(async move {
+                    let peer_addr = incoming.remote_addr();
+                    match incoming.establish().await {
+                        Ok(stream) => {
+                            if let Err(error) = self_.handle_quic_stream(peer_addr, None, stream).await {
+                                warn!("{CONTEXT} Unable to accept '{peer_addr}' over QUIC - {error}");
+                            }
+                        }
+                        Err(error) => debug!("{CONTEXT} Failed to accept a QUIC connection - {error}"),
+                    }
+                });
+            }
+        });
+        info!("{CONTEXT} Gateway is listening for QUIC connections at '{local_addr}'");
+        Ok(local_addr)
+    }
+
+    /// Performs the handshake over the given QUIC stream, and processes the events from the peer until it disconnects.
+    /// Note: The connected peer is tracked by its listener IP, exactly as it would be over TCP.
+    async fn handle_quic_stream(
+        &self,
+        peer_addr: SocketAddr,
+        mut peer_ip: Option<SocketAddr>,
+        mut stream: QuicStream,
+    ) -> io::Result<()> {
+        // Retrieve the keying material of the TLS session, to bind the session to the handshake.
+        let channel_binding = stream.channel_binding().map_err(|e| error(format!("{CONTEXT} {e}")))?;
+        // Perform the handshake.
+        let is_inbound = peer_ip.is_none();
+        let handshake = async {
+            match peer_ip {
+                Some(_) => self.handshake_inner_initiator(peer_addr, peer_ip, &channel_binding, &mut stream).await,
+                None => self.handshake_inner_responder(peer_addr, &mut peer_ip, &channel_binding, &mut stream).await,
+            }
+            .map(|(peer_ip, framed)| (peer_ip, framed.into_parts().read_buf))
+        };
+        // Ensure the handshake completes within the deadline.
+        let deadline = self.handshake_deadline();
+        let handshake_result = match tokio::time::timeout(deadline, handshake).await {
+            Ok(result) => result,
+            Err(_) => {
+                // If the peer initiated the connection, penalize it for stalling the handshake.
+                if is_inbound {
+                    self.penalize_handshake_straggler(peer_addr);
+                }
+                Err(error(format!(
+                    "{CONTEXT} '{peer_addr}' did not complete the handshake within {}ms",
+                    deadline.as_millis()
+                )))
+            }
+        };
+
+        // Remove the address from the collection of connecting peers (if the handshake got to the point where it's known).
+        if let Some(ip) = peer_ip {
+            self.connecting_peers.lock().shift_remove(&ip);
+        }
+        let (peer_ip, read_buffer) = handshake_result?;
+        info!("{CONTEXT} Gateway is connected to '{peer_ip}' over QUIC");
+
+        // Start the peer, carrying over any events that arrived with the handshake.
+        let (quic_peer, mut reader) = stream.into_peer::<N>(&read_buffer, <Self as Writing>::MESSAGE_QUEUE_DEPTH);
+        self.quic_peers.write().insert(peer_addr, quic_peer);
+
+        // Process the events from the peer, until the connection is closed.
+        while let Some(result) = reader.next().await {
+            match result {
+                Ok(event) => Reading::process_message(self, peer_addr, event).await?,
+                Err(error) => {
+                    debug!("{CONTEXT} Failed to read from '{peer_ip}' over QUIC - {error}");
+                    break;
+                }
+            }
+        }
+
+        // Remove the peer.
+        if let Some(quic_peer) = self.quic_peers.write().shift_remove(&peer_addr) {
+            quic_peer.close();
+        }
+        self.handle_disconnect(peer_addr).await;
+        Ok(())
+    }
+}
+
 impl<N: Network> Gateway<N> {
     /// Handles the heartbeat request.
     fn heartbeat(&self) {
@@ -847,6 +1585,16 @@ impl<N: Network> Gateway<N> {
         self.handle_unauthorized_validators();
         // If the number of connected validators is less than the minimum, send a `ValidatorsRequest`.
         self.handle_min_connected_validators();
+        // Remove the expired entries of the cache, including those of the maps that no longer receive inserts.
+        if self.is_permitted(CACHE_PRUNING_TASK) {
+            self.cache.prune();
+        }
+        // Update the number of distinct peers tracked in the cache, and the sizes of its maps.
+        #[cfg(feature = "metrics")]
+        if self.is_permitted(METRICS_TASK) {
+            metrics::gauge(metrics::bft::CACHE_PEERS, self.cache.num_distinct_peers() as f64);
+            self.cache.update_metrics();
+        }
     }
 
     /// Logs the connected validators.
@@ -940,9 +1688,9 @@ impl<N: Network> Transport<N> for Gateway<N> {
     /// which can be used to determine when and whether the event has been delivered.
     async fn send(&self, peer_ip: SocketAddr, event: Event<N>) -> Option<oneshot::Receiver<io::Result<()>>> {
         macro_rules! send {
-            ($self:ident, $cache_map:ident, $interval:expr, $freq:ident) => {{
+            ($self:ident, $cache_map:ident, $freq:ident) => {{
                 // Rate limit the number of certificate requests sent to the peer.
-                while $self.cache.$cache_map(peer_ip, $interval) > $self.$freq() {
+                while $self.cache.$cache_map(peer_ip) > $self.$freq() {
                     // Sleep for a short period of time to allow the cache to clear.
                     tokio::time::sleep(Duration::from_millis(10)).await;
                 }
@@ -951,24 +1699,31 @@ impl<N: Network> Transport<N> for Gateway<N> {
             }};
         }
 
+        // If the event type is a block request, record it, so that its chunked block response is reassembled.
+        if let Event::BlockRequest(block_request) = &event {
+            self.insert_pending_block_response(peer_ip, *block_request);
+        }
+
         // If the event type is a certificate request, increment the cache.
+        // Note: The certified batches are broadcast under the general rate limit, as the certificate retry policy
+        // bounds the number of their retries.
         if matches!(event, Event::CertificateRequest(_)) | matches!(event, Event::CertificateResponse(_)) {
             // Update the outbound event cache. This is necessary to ensure we don't under count the outbound events.
-            self.cache.insert_outbound_event(peer_ip, CACHE_EVENTS_INTERVAL);
+            self.cache.record_outbound_event(peer_ip);
             // Send the event to the peer.
-            send!(self, insert_outbound_certificate, CACHE_REQUESTS_INTERVAL, max_cache_certificates)
+            send!(self, record_outbound_certificate, max_cache_certificates)
         }
         // If the event type is a transmission request, increment the cache.
         else if matches!(event, Event::TransmissionRequest(_)) | matches!(event, Event::TransmissionResponse(_)) {
             // Update the outbound event cache. This is necessary to ensure we don't under count the outbound events.
-            self.cache.insert_outbound_event(peer_ip, CACHE_EVENTS_INTERVAL);
+            self.cache.record_outbound_event(peer_ip);
             // Send the event to the peer.
-            send!(self, insert_outbound_transmission, CACHE_REQUESTS_INTERVAL, max_cache_transmissions)
+            send!(self, record_outbound_transmission, max_cache_transmissions)
         }
         // Otherwise, employ a general rate limit.
         else {
             // Send the event to the peer.
-            send!(self, insert_outbound_event, CACHE_EVENTS_INTERVAL, max_cache_events)
+            send!(self, record_outbound_event, max_cache_events)
         }
     }
 
@@ -1000,8 +1755,8 @@ impl<N: Network> P2P for Gateway<N> {
 
 #[async_trait]
 impl<N: Network> Reading for Gateway<N> {
-    type Codec = EventCodec<N>;
-    type Message = Event<N>;
+    type Codec = SizedEventCodec<N>;
+    type Message = (Event<N>, usize);
 
     /// The maximum queue depth of incoming messages for a single peer.
     const MESSAGE_QUEUE_DEPTH: usize =
@@ -1014,9 +1769,18 @@ impl<N: Network> Reading for Gateway<N> {
     }
 
     /// Processes a message received from the network.
-    async fn process_message(&self, peer_addr: SocketAddr, message: Self::Message) -> io::Result<()> {
+    async fn process_message(&self, peer_addr: SocketAddr, (event, frame_length): Self::Message) -> io::Result<()> {
+        // Mirror the event metadata, if an analysis pipeline is attached.
+        #[cfg(feature = "mirror")]
+        if let Some(event_mirror) = self.event_mirror.read().as_ref() {
+            if let Some(peer_ip) = self.resolver.get_listener(peer_addr) {
+                event_mirror.mirror(peer_ip, &event, frame_length);
+            }
+        }
+        #[cfg(not(feature = "mirror"))]
+        let _ = frame_length;
         // Process the message. Disconnect if the peer violated the protocol.
-        if let Err(error) = self.inbound(peer_addr, message).await {
+        if let Err(error) = self.inbound(peer_addr, event).await {
             if let Some(peer_ip) = self.resolver.get_listener(peer_addr) {
                 warn!("{CONTEXT} Disconnecting from '{peer_ip}' - {error}");
                 let self_ = self.clone();
@@ -1066,6 +1830,10 @@ impl<N: Network> OnConnect for Gateway<N> {
 
 #[async_trait]
 impl<N: Network> Handshake for Gateway<N> {
+    /// The maximum time allowed for a connection to perform a handshake.
+    /// Note: The handshake deadline of the gateway is enforced in `perform_handshake`, up to this limit.
+    const TIMEOUT_MS: u64 = MAX_HANDSHAKE_DEADLINE_IN_MS;
+
     /// Performs the handshake protocol.
     async fn perform_handshake(&self, mut connection: Connection) -> io::Result<Connection> {
         // Perform the handshake.
@@ -1076,7 +1844,15 @@ impl<N: Network> Handshake for Gateway<N> {
         // If this is an inbound connection, we log it, but don't know the listening address yet.
         // Otherwise, we can immediately register the listening address.
         let mut peer_ip = if peer_side == ConnectionSide::Initiator {
-            debug!("{CONTEXT} Gateway received a connection request from '{peer_addr}'");
+            // Meter the connections from the probes separately from the peers.
+            if self.is_probe_source(peer_addr.ip()) {
+                self.num_probe_connections.fetch_add(1, Ordering::Relaxed);
+                #[cfg(feature = "metrics")]
+                metrics::increment_counter(metrics::bft::PROBE_CONNECTIONS);
+                trace!("{CONTEXT} Gateway received a probe from '{peer_addr}'");
+            } else {
+                debug!("{CONTEXT} Gateway received a connection request from '{peer_addr}'");
+            }
             None
         } else {
             debug!("{CONTEXT} Gateway is connecting to {peer_addr}...");
@@ -1084,10 +1860,27 @@ impl<N: Network> Handshake for Gateway<N> {
         };
 
         // Perform the handshake; we pass on a mutable reference to peer_ip in case the process is broken at any point in time.
-        let handshake_result = if peer_side == ConnectionSide::Responder {
-            self.handshake_inner_initiator(peer_addr, peer_ip, stream).await
-        } else {
-            self.handshake_inner_responder(peer_addr, &mut peer_ip, stream).await
+        let handshake = async {
+            if peer_side == ConnectionSide::Responder {
+                self.handshake_inner_initiator(peer_addr, peer_ip, &[], stream).await
+            } else {
+                self.handshake_inner_responder(peer_addr, &mut peer_ip, &[], stream).await
+            }
+        };
+        // Ensure the handshake completes within the deadline.
+        let deadline = self.handshake_deadline();
+        let handshake_result = match tokio::time::timeout(deadline, handshake).await {
+            Ok(result) => result,
+            Err(_) => {
+                // If the peer initiated the connection, penalize it for stalling the handshake.
+                if peer_side == ConnectionSide::Initiator {
+                    self.penalize_handshake_straggler(peer_addr);
+                }
+                Err(error(format!(
+                    "{CONTEXT} '{peer_addr}' did not complete the handshake within {}ms",
+                    deadline.as_millis()
+                )))
+            }
         };
 
         // Remove the address from the collection of connecting peers (if the handshake got to the point where it's known).
@@ -1135,9 +1928,14 @@ macro_rules! expect_event {
     };
 }
 
+/// Returns the data signed in a challenge response, for the given nonces and channel binding.
+fn challenge_data(request_nonce: u64, response_nonce: u64, channel_binding: &[u8]) -> Vec<u8> {
+    [&request_nonce.to_le_bytes()[..], &response_nonce.to_le_bytes(), channel_binding].concat()
+}
+
 /// Send the given message to the peer.
-async fn send_event<N: Network>(
-    framed: &mut Framed<&mut TcpStream, EventCodec<N>>,
+async fn send_event<N: Network, S: AsyncRead + AsyncWrite + Unpin>(
+    framed: &mut Framed<&mut S, EventCodec<N>>,
     peer_addr: SocketAddr,
     event: Event<N>,
 ) -> io::Result<()> {
@@ -1147,12 +1945,14 @@ async fn send_event<N: Network>(
 
 impl<N: Network> Gateway<N> {
     /// The connection initiator side of the handshake.
-    async fn handshake_inner_initiator<'a>(
+    /// The given channel binding (if any) is signed along with the nonces, to bind the underlying session to the peers.
+    async fn handshake_inner_initiator<'a, S: AsyncRead + AsyncWrite + Unpin + Send>(
         &'a self,
         peer_addr: SocketAddr,
         peer_ip: Option<SocketAddr>,
-        stream: &'a mut TcpStream,
-    ) -> io::Result<(SocketAddr, Framed<&mut TcpStream, EventCodec<N>>)> {
+        channel_binding: &[u8],
+        stream: &'a mut S,
+    ) -> io::Result<(SocketAddr, Framed<&'a mut S, EventCodec<N>>)> {
         // This value is immediately guaranteed to be present, so it can be unwrapped.
         let peer_ip = peer_ip.unwrap();
 
@@ -1169,17 +1969,20 @@ impl<N: Network> Gateway<N> {
         // Send a challenge request to the peer.
         let our_request = ChallengeRequest::new(self.local_ip().port(), self.account.address(), our_nonce);
         send_event(&mut framed, peer_addr, Event::ChallengeRequest(our_request)).await?;
+        let request_sent_at = Instant::now();
 
         /* Step 2: Receive the peer's challenge response followed by the challenge request. */
 
         // Listen for the challenge response message.
         let peer_response = expect_event!(Event::ChallengeResponse, framed, peer_addr);
+        let rtt = request_sent_at.elapsed();
         // Listen for the challenge request message.
         let peer_request = expect_event!(Event::ChallengeRequest, framed, peer_addr);
 
         // Verify the challenge response. If a disconnect reason was returned, send the disconnect message and abort.
-        if let Some(reason) =
-            self.verify_challenge_response(peer_addr, peer_request.address, peer_response, our_nonce).await
+        if let Some(reason) = self
+            .verify_challenge_response(peer_addr, peer_request.address, peer_response, our_nonce, channel_binding)
+            .await
         {
             send_event(&mut framed, peer_addr, reason.into()).await?;
             return Err(error(format!("Dropped '{peer_addr}' for reason: {reason:?}")));
@@ -1194,7 +1997,7 @@ impl<N: Network> Gateway<N> {
 
         // Sign the counterparty nonce.
         let response_nonce: u64 = rng.gen();
-        let data = [peer_request.nonce.to_le_bytes(), response_nonce.to_le_bytes()].concat();
+        let data = challenge_data(peer_request.nonce, response_nonce, channel_binding);
         let Ok(our_signature) = self.account.sign_bytes(&data, rng) else {
             return Err(error(format!("Failed to sign the challenge request nonce from '{peer_addr}'")));
         };
@@ -1202,19 +2005,22 @@ impl<N: Network> Gateway<N> {
         let our_response = ChallengeResponse { signature: Data::Object(our_signature), nonce: response_nonce };
         send_event(&mut framed, peer_addr, Event::ChallengeResponse(our_response)).await?;
 
-        // Add the peer to the gateway.
+        // Record the round-trip time of the challenge, and add the peer to the gateway.
+        self.cache.insert_peer_rtt(peer_ip, rtt);
         self.insert_connected_peer(peer_ip, peer_addr, peer_request.address);
 
         Ok((peer_ip, framed))
     }
 
     /// The connection responder side of the handshake.
-    async fn handshake_inner_responder<'a>(
+    /// The given channel binding (if any) is signed along with the nonces, to bind the underlying session to the peers.
+    async fn handshake_inner_responder<'a, S: AsyncRead + AsyncWrite + Unpin + Send>(
         &'a self,
         peer_addr: SocketAddr,
         peer_ip: &mut Option<SocketAddr>,
-        stream: &'a mut TcpStream,
-    ) -> io::Result<(SocketAddr, Framed<&mut TcpStream, EventCodec<N>>)> {
+        channel_binding: &[u8],
+        stream: &'a mut S,
+    ) -> io::Result<(SocketAddr, Framed<&'a mut S, EventCodec<N>>)> {
         // Construct the stream.
         let mut framed = Framed::new(stream, EventCodec::<N>::handshake());
 
@@ -1249,7 +2055,7 @@ impl<N: Network> Gateway<N> {
 
         // Sign the counterparty nonce.
         let response_nonce: u64 = rng.gen();
-        let data = [peer_request.nonce.to_le_bytes(), response_nonce.to_le_bytes()].concat();
+        let data = challenge_data(peer_request.nonce, response_nonce, channel_binding);
         let Ok(our_signature) = self.account.sign_bytes(&data, rng) else {
             return Err(error(format!("Failed to sign the challenge request nonce from '{peer_addr}'")));
         };
@@ -1262,19 +2068,23 @@ impl<N: Network> Gateway<N> {
         // Send the challenge request.
         let our_request = ChallengeRequest::new(self.local_ip().port(), self.account.address(), our_nonce);
         send_event(&mut framed, peer_addr, Event::ChallengeRequest(our_request)).await?;
+        let request_sent_at = Instant::now();
 
         /* Step 3: Receive the challenge response. */
 
         // Listen for the challenge response message.
         let peer_response = expect_event!(Event::ChallengeResponse, framed, peer_addr);
+        let rtt = request_sent_at.elapsed();
         // Verify the challenge response. If a disconnect reason was returned, send the disconnect message and abort.
-        if let Some(reason) =
-            self.verify_challenge_response(peer_addr, peer_request.address, peer_response, our_nonce).await
+        if let Some(reason) = self
+            .verify_challenge_response(peer_addr, peer_request.address, peer_response, our_nonce, channel_binding)
+            .await
         {
             send_event(&mut framed, peer_addr, reason.into()).await?;
             return Err(error(format!("Dropped '{peer_addr}' for reason: {reason:?}")));
         }
-        // Add the peer to the gateway.
+        // Record the round-trip time of the challenge, and add the peer to the gateway.
+        self.cache.insert_peer_rtt(peer_ip, rtt);
         self.insert_connected_peer(peer_ip, peer_addr, peer_request.address);
 
         Ok((peer_ip, framed))
@@ -1309,6 +2119,7 @@ impl<N: Network> Gateway<N> {
         peer_address: Address<N>,
         response: ChallengeResponse<N>,
         expected_nonce: u64,
+        channel_binding: &[u8],
     ) -> Option<DisconnectReason> {
         // Retrieve the components of the challenge response.
         let ChallengeResponse { signature, nonce } = response;
@@ -1318,7 +2129,7 @@ impl<N: Network> Gateway<N> {
             return Some(DisconnectReason::InvalidChallengeResponse);
         };
         // Verify the signature.
-        if !signature.verify_bytes(&peer_address, &[expected_nonce.to_le_bytes(), nonce.to_le_bytes()].concat()) {
+        if !signature.verify_bytes(&peer_address, &challenge_data(expected_nonce, nonce, channel_binding)) {
             warn!("{CONTEXT} Gateway handshake with '{peer_addr}' failed (invalid signature)");
             return Some(DisconnectReason::InvalidChallengeResponse);
         }
@@ -1326,6 +2137,391 @@ impl<N: Network> Gateway<N> {
     }
 }
 
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::helpers::{init_primary_channels, RiskWeights};
+    use snarkos_node_bft_ledger_service::MockLedgerService;
+    use snarkvm::{
+        ledger::{committee::test_helpers::sample_committee, ledger_test_helpers::sample_genesis_block},
+        utilities::TestRng,
+    };
+
+    use std::net::Ipv4Addr;
+    use tokio::{io::AsyncReadExt, net::TcpStream};
+
+    type CurrentNetwork = snarkvm::prelude::Testnet3;
+
+    #[tokio::test]
+    async fn test_handshake_deadline_drops_straggler() {
+        let rng = &mut TestRng::default();
+
+        // Initialize the gateway.
+        let ledger = Arc::new(MockLedgerService::new(sample_committee(rng)));
+        let account = Account::<CurrentNetwork>::new(rng).unwrap();
+        // Listen on any free port, so the tests do not collide.
+        let node_ip = SocketAddr::from((Ipv4Addr::LOCALHOST, 0));
+        let gateway = Gateway::new(account, ledger, Some(node_ip), &[], None).unwrap();
+        assert_eq!(gateway.handshake_deadline(), Duration::from_millis(DEFAULT_HANDSHAKE_DEADLINE_IN_MS));
+        assert!(gateway.set_handshake_deadline(Duration::ZERO).is_err());
+        assert!(gateway.set_handshake_deadline(Duration::from_millis(MAX_HANDSHAKE_DEADLINE_IN_MS + 1)).is_err());
+        let deadline = Duration::from_millis(500);
+        gateway.set_handshake_deadline(deadline).unwrap();
+
+        // Start the gateway.
+        let (primary_sender, _primary_receiver) = init_primary_channels();
+        gateway.run(primary_sender, Default::default(), None).await;
+
+        // Connect to the gateway, and stall the handshake.
+        let mut stream = TcpStream::connect(gateway.local_ip()).await.unwrap();
+        let peer_ip = stream.local_addr().unwrap().ip();
+        let mut buffer = [0u8; 1024];
+
+        // Ensure the connection is kept open before the deadline.
+        assert!(tokio::time::timeout(deadline / 2, stream.read(&mut buffer)).await.is_err());
+        // Ensure the connection is dropped at the deadline.
+        let result = tokio::time::timeout(deadline * 4, stream.read(&mut buffer)).await.unwrap();
+        assert!(matches!(result, Ok(0) | Err(_)));
+
+        // Ensure the IP of the straggler was penalized.
+        let num_attempts = gateway.cache.record_inbound_connection(peer_ip);
+        assert_eq!(num_attempts, HANDSHAKE_DEADLINE_PENALTY + 1);
+
+        gateway.shut_down().await;
+    }
+
+    #[tokio::test]
+    async fn test_probes_are_excluded_from_spam_cache() {
+        let rng = &mut TestRng::default();
+
+        // Initialize the gateway.
+        let ledger = Arc::new(MockLedgerService::new(sample_committee(rng)));
+        let account = Account::<CurrentNetwork>::new(rng).unwrap();
+        // Listen on any free port, so the tests do not collide.
+        let node_ip = SocketAddr::from((Ipv4Addr::LOCALHOST, 0));
+        let gateway = Gateway::new(account, ledger, Some(node_ip), &[], None).unwrap();
+        let deadline = Duration::from_millis(500);
+        gateway.set_handshake_deadline(deadline).unwrap();
+        let probe_ip = IpAddr::V4(Ipv4Addr::LOCALHOST);
+        gateway.set_probe_sources([probe_ip]);
+        assert!(gateway.is_probe_source(probe_ip));
+
+        // Start the gateway.
+        let (primary_sender, _primary_receiver) = init_primary_channels();
+        gateway.run(primary_sender, Default::default(), None).await;
+
+        // Connect to the gateway as a probe, and do not handshake.
+        let mut stream = TcpStream::connect(gateway.local_ip()).await.unwrap();
+        let mut buffer = [0u8; 1024];
+        // Ensure the probe is accepted, and its connection is dropped at the deadline.
+        assert!(tokio::time::timeout(deadline / 2, stream.read(&mut buffer)).await.is_err());
+        let result = tokio::time::timeout(deadline * 4, stream.read(&mut buffer)).await.unwrap();
+        assert!(matches!(result, Ok(0) | Err(_)));
+
+        // Ensure the probe is metered, but does not increment the spam cache.
+        assert_eq!(gateway.num_probe_connections(), 1);
+        assert_eq!(gateway.cache.record_inbound_connection(probe_ip), 1);
+
+        // Connect to the gateway as a peer, and do not handshake.
+        gateway.set_probe_sources([]);
+        let mut stream = TcpStream::connect(gateway.local_ip()).await.unwrap();
+        let result = tokio::time::timeout(deadline * 4, stream.read(&mut buffer)).await.unwrap();
+        assert!(matches!(result, Ok(0) | Err(_)));
+
+        // Ensure the peer is not metered as a probe, and increments the spam cache.
+        assert_eq!(gateway.num_probe_connections(), 1);
+        let num_attempts = gateway.cache.record_inbound_connection(probe_ip);
+        assert_eq!(num_attempts, HANDSHAKE_DEADLINE_PENALTY + 2);
+
+        gateway.shut_down().await;
+    }
+
+    /// An ASN lookup that maps `10.0.0.0/8` to one ASN.
+    struct MockAsnLookup;
+
+    impl AsnLookup for MockAsnLookup {
+        fn asn(&self, ip: IpAddr) -> Option<u32> {
+            matches!(ip, IpAddr::V4(ip) if ip.octets()[0] == 10).then_some(64500)
+        }
+    }
+
+    #[tokio::test]
+    async fn test_connections_are_limited_per_asn() {
+        let rng = &mut TestRng::default();
+
+        // Initialize the gateway.
+        let committee = sample_committee(rng);
+        let member = *committee.members().keys().next().unwrap();
+        let ledger = Arc::new(MockLedgerService::new(committee));
+        let account = Account::<CurrentNetwork>::new(rng).unwrap();
+        // Listen on any free port, so the tests do not collide.
+        let node_ip = SocketAddr::from((Ipv4Addr::LOCALHOST, 0));
+        let trusted_ip = SocketAddr::from(([10, 9, 0, 1], 5000));
+        let gateway = Gateway::new(account, ledger, Some(node_ip), &[trusted_ip], None).unwrap();
+        assert!(gateway.set_max_connections_per_asn(Some(0)).is_err());
+        assert!(gateway.set_max_connections_per_subnet(Some(0)).is_err());
+        gateway.set_max_connections_per_asn(Some(2)).unwrap();
+        gateway.set_max_connections_per_subnet(Some(1)).unwrap();
+        gateway.set_asn_lookup(Some(Arc::new(MockAsnLookup)));
+
+        // Start the gateway.
+        let (primary_sender, _primary_receiver) = init_primary_channels();
+        gateway.run(primary_sender, Default::default(), None).await;
+
+        // Ensure the cap is enforced across the different subnets of the same ASN.
+        let peer_ip = |ip: [u8; 4]| SocketAddr::from((ip, 5000));
+        assert!(gateway.ensure_peer_is_allowed(peer_ip([10, 0, 1, 1])).is_ok());
+        assert!(gateway.ensure_peer_is_allowed(peer_ip([10, 1, 2, 1])).is_ok());
+        let error = gateway.ensure_peer_is_allowed(peer_ip([10, 2, 3, 1])).unwrap_err();
+        assert!(error.to_string().contains("AS64500 tried 3 times"), "{error}");
+
+        // Ensure the trusted validators are exempt from the cap of their ASN.
+        assert!(gateway.ensure_peer_is_allowed(trusted_ip).is_ok());
+
+        // Ensure a committee member is exempt from the cap, once it completed a handshake from its IP.
+        let member_ip = peer_ip([10, 3, 0, 1]);
+        assert!(gateway.ensure_peer_is_allowed(member_ip).is_err());
+        gateway.connecting_peers.lock().clear();
+        gateway.insert_connected_peer(member_ip, member_ip, member);
+        gateway.remove_connected_peer(member_ip);
+        assert!(gateway.ensure_peer_is_allowed(member_ip).is_ok());
+
+        // Ensure the IPs of an unknown ASN fall back to the (separate) cap of their subnet.
+        assert_eq!(gateway.connection_group(IpAddr::from([20, 0, 0, 1])).to_string(), "subnet 20.0.0.0/24");
+        assert!(gateway.ensure_peer_is_allowed(peer_ip([20, 0, 0, 1])).is_ok());
+        let error = gateway.ensure_peer_is_allowed(peer_ip([20, 0, 0, 2])).unwrap_err();
+        assert!(error.to_string().contains("subnet 20.0.0.0/24 tried 2 times"), "{error}");
+        assert!(gateway.ensure_peer_is_allowed(peer_ip([20, 0, 1, 1])).is_ok());
+
+        // Ensure the IPv4-mapped IPv6 peers are counted in the subnet of their IPv4 address.
+        let mapped_ip = SocketAddr::new(Ipv4Addr::new(20, 0, 2, 1).to_ipv6_mapped().into(), 5000);
+        assert_eq!(gateway.connection_group(mapped_ip.ip()).to_string(), "subnet 20.0.2.0/24");
+        assert!(gateway.ensure_peer_is_allowed(mapped_ip).is_ok());
+        assert!(gateway.ensure_peer_is_allowed(peer_ip([20, 0, 2, 2])).is_err());
+
+        gateway.shut_down().await;
+    }
+
+    #[tokio::test]
+    async fn test_invalid_certificates_are_penalized_gradually() {
+        let rng = &mut TestRng::default();
+
+        // Initialize the gateway.
+        let ledger = Arc::new(MockLedgerService::new(sample_committee(rng)));
+        let account = Account::<CurrentNetwork>::new(rng).unwrap();
+        let gateway = Gateway::new(account, ledger, None, &[], None).unwrap();
+        let peer_ip = SocketAddr::from(([10, 0, 0, 1], 5000));
+        let other_ip = SocketAddr::from(([10, 0, 0, 2], 5000));
+
+        // Ensure the penalties are disabled by default.
+        assert_eq!(gateway.certificate_penalty_policy(), None);
+        for _ in 0..CertificatePenaltyPolicy::default().ban_threshold() {
+            assert_eq!(gateway.report_invalid_certificate(other_ip), CertificatePenalty::None);
+        }
+        assert_eq!(gateway.certificate_penalty(other_ip), CertificatePenalty::None);
+        assert!(!gateway.is_temp_banned(other_ip));
+
+        let policy = CertificatePenaltyPolicy::new(1, 2, 3, 2, Duration::from_secs(1)).unwrap();
+        gateway.set_certificate_penalty_policy(Some(policy));
+        assert_eq!(gateway.certificate_penalty_policy(), Some(policy));
+        assert_eq!(gateway.certificate_penalty(peer_ip), CertificatePenalty::None);
+
+        // Ensure the penalties escalate with the invalid certificates.
+        assert_eq!(gateway.report_invalid_certificate(peer_ip), CertificatePenalty::Warn);
+        assert_eq!(gateway.certificate_penalty(peer_ip), CertificatePenalty::Warn);
+        assert_eq!(gateway.report_invalid_certificate(peer_ip), CertificatePenalty::Throttle);
+        assert_eq!(gateway.certificate_penalty(peer_ip), CertificatePenalty::Throttle);
+        assert!(!gateway.is_temp_banned(peer_ip));
+        assert_eq!(gateway.report_invalid_certificate(peer_ip), CertificatePenalty::TempBan);
+        assert_eq!(gateway.certificate_penalty(peer_ip), CertificatePenalty::TempBan);
+        assert!(gateway.is_temp_banned(peer_ip));
+        // Ensure the other peers are not penalized.
+        let third_ip = SocketAddr::from(([10, 0, 0, 3], 5000));
+        assert_eq!(gateway.certificate_penalty(third_ip), CertificatePenalty::None);
+        assert!(!gateway.is_temp_banned(third_ip));
+
+        // Ensure the penalties reset after a clean period.
+        tokio::time::sleep(Duration::from_secs(3)).await;
+        assert!(!gateway.is_temp_banned(peer_ip));
+        assert_eq!(gateway.certificate_penalty(peer_ip), CertificatePenalty::None);
+        assert_eq!(gateway.report_invalid_certificate(peer_ip), CertificatePenalty::Warn);
+    }
+
+    #[tokio::test]
+    async fn test_combined_risk_score_escalates() {
+        let rng = &mut TestRng::default();
+
+        // Initialize the gateway.
+        let committee = sample_committee(rng);
+        let member = *committee.members().keys().next().unwrap();
+        let ledger = Arc::new(MockLedgerService::new(committee));
+        let account = Account::<CurrentNetwork>::new(rng).unwrap();
+        let gateway = Gateway::new(account, ledger, None, &[], None).unwrap();
+        let policy = RiskPolicy::new(RiskWeights::default(), 0.5, 0.75, 1.0, Duration::from_secs(60)).unwrap();
+        gateway.set_risk_policy(Some(policy));
+        assert_eq!(gateway.risk_policy(), Some(policy));
+        let peer_ip = SocketAddr::from(([10, 0, 0, 1], 5000));
+        let other_ip = SocketAddr::from(([10, 0, 0, 2], 5000));
+        assert_eq!(gateway.assess_risk(peer_ip), RiskAction::None);
+
+        // Raise the connection attempts and invalid certificates of the peer, each below its own limit.
+        for _ in 0..7 {
+            gateway.cache.record_inbound_connection(peer_ip.ip());
+        }
+        for _ in 0..2 {
+            gateway.cache.record_invalid_certificate(peer_ip);
+        }
+        assert!(gateway.cache.num_inbound_connections(peer_ip.ip()) < MAX_CONNECTION_ATTEMPTS);
+        assert!(gateway.certificate_penalty(peer_ip) < CertificatePenalty::Throttle);
+        // Ensure the combined score is monitored, unless the risk score is disabled.
+        assert_eq!(gateway.assess_risk(peer_ip), RiskAction::Monitor);
+        gateway.set_risk_policy(None);
+        assert_eq!(gateway.assess_risk(peer_ip), RiskAction::None);
+        gateway.set_risk_policy(Some(policy));
+
+        // Ensure a slow round-trip time adds to the score, and the peer is throttled.
+        gateway.cache.insert_peer_rtt(peer_ip, Duration::from_millis(1_800));
+        assert_eq!(gateway.assess_risk(peer_ip), RiskAction::Throttle);
+        assert!(!gateway.is_temp_banned(peer_ip));
+
+        // Ensure half of the event rate limit adds to the score, and the peer is banned.
+        for _ in 0..gateway.max_cache_events() / 2 {
+            gateway.cache.record_inbound_event(peer_ip);
+        }
+        assert!(gateway.cache.num_inbound_events(peer_ip) < gateway.max_cache_events());
+        assert_eq!(gateway.assess_risk(peer_ip), RiskAction::Ban);
+        assert!(gateway.is_temp_banned(peer_ip));
+
+        // Ensure the other peers are not affected.
+        assert_eq!(gateway.risk_signals(other_ip), RiskSignals::default());
+        assert_eq!(gateway.assess_risk(other_ip), RiskAction::None);
+        assert!(!gateway.is_temp_banned(other_ip));
+
+        // Ensure a committee member with the same signals is only monitored, while the node needs it for its quorum.
+        let member_ip = SocketAddr::from(([10, 0, 0, 3], 5000));
+        gateway.insert_connected_peer(member_ip, member_ip, member);
+        for _ in 0..7 {
+            gateway.cache.record_inbound_connection(member_ip.ip());
+        }
+        for _ in 0..2 {
+            gateway.cache.record_invalid_certificate(member_ip);
+        }
+        for _ in 0..gateway.max_cache_events() / 2 {
+            gateway.cache.record_inbound_event(member_ip);
+        }
+        gateway.cache.insert_peer_rtt(member_ip, Duration::from_millis(1_800));
+        assert!(policy.score(&gateway.risk_signals(member_ip)) >= 1.0);
+        assert_eq!(gateway.assess_risk(member_ip), RiskAction::Monitor);
+        assert!(!gateway.is_temp_banned(member_ip));
+    }
+
+    #[tokio::test]
+    async fn test_risk_score_is_applied_to_inbound_events() {
+        let rng = &mut TestRng::default();
+
+        // Initialize the gateway.
+        let committee = sample_committee(rng);
+        let members: Vec<_> = committee.members().keys().copied().collect();
+        let ledger = Arc::new(MockLedgerService::new(committee));
+        let account = Account::<CurrentNetwork>::new(rng).unwrap();
+        let gateway = Gateway::new(account, ledger, None, &[], None).unwrap();
+        gateway.set_risk_policy(Some(RiskPolicy::default()));
+        // Note: An unsolicited validators response is refused once it is processed.
+        let response = || Event::ValidatorsResponse(ValidatorsResponse { validators: Default::default() });
+
+        // Connect to a single committee member, and raise its signals, each below its own limit.
+        let peer_ip = SocketAddr::from(([10, 0, 0, 1], 5000));
+        gateway.insert_connected_peer(peer_ip, peer_ip, members[0]);
+        for _ in 0..7 {
+            gateway.cache.record_inbound_connection(peer_ip.ip());
+        }
+        for _ in 0..2 {
+            gateway.cache.record_invalid_certificate(peer_ip);
+        }
+        gateway.cache.insert_peer_rtt(peer_ip, Duration::from_millis(1_800));
+
+        // Ensure the event of the peer is processed, as the node needs the peer for its quorum.
+        let error = gateway.inbound(peer_ip, response()).await.unwrap_err();
+        assert!(error.to_string().contains("without a validators request"), "{error}");
+
+        // Connect to the other committee members, so the node keeps its quorum without the peer.
+        for (index, member) in members.iter().enumerate().skip(1) {
+            let member_ip = SocketAddr::from(([10, 0, 0, 1 + index as u8], 5000));
+            gateway.insert_connected_peer(member_ip, member_ip, *member);
+        }
+        // Ensure the events of the peer are throttled, and not counted against its rate limit.
+        let num_events = gateway.cache.num_inbound_events(peer_ip);
+        assert!(gateway.inbound(peer_ip, response()).await.is_ok());
+        assert_eq!(gateway.cache.num_inbound_events(peer_ip), num_events);
+
+        // Ensure the peer is banned, once its events add to its score.
+        for _ in 0..gateway.max_cache_events() / 2 {
+            gateway.cache.record_inbound_event(peer_ip);
+        }
+        let error = gateway.inbound(peer_ip, response()).await.unwrap_err();
+        assert!(error.to_string().contains("banned for its risk score"), "{error}");
+        assert!(gateway.is_temp_banned(peer_ip));
+    }
+
+    #[test]
+    fn test_block_response_chunk_reassembly() {
+        let rng = &mut TestRng::default();
+
+        // Initialize the gateway.
+        let ledger = Arc::new(MockLedgerService::new(sample_committee(rng)));
+        let account = Account::<CurrentNetwork>::new(rng).unwrap();
+        let gateway = Gateway::new(account, ledger, None, &[], None).unwrap();
+        let peer_ip = SocketAddr::from(([127, 0, 0, 1], 1234));
+
+        // Serialize a block into chunks.
+        let request = BlockRequest::new(0, 1);
+        let blocks = DataBlocks(vec![sample_genesis_block(rng)]);
+        let chunk_size = blocks.to_bytes_le().unwrap().len() / 3 + 1;
+        let mut chunks = Vec::new();
+        let on_chunk = |chunk| {
+            chunks.push(chunk);
+            Ok(())
+        };
+        assert!(BlockResponseChunk::stream(request, &blocks, chunk_size, on_chunk).unwrap());
+        assert_eq!(chunks.len(), 3);
+
+        // Ensure an unsolicited chunk is rejected, and not buffered.
+        assert!(gateway.insert_block_response_chunk(peer_ip, chunks[0].clone()).is_err());
+        assert!(gateway.pending_block_responses.lock().is_empty());
+
+        // Ensure the block response is reassembled once the last chunk is received.
+        gateway.insert_pending_block_response(peer_ip, request);
+        assert!(gateway.insert_block_response_chunk(peer_ip, chunks[0].clone()).unwrap().is_none());
+        assert!(gateway.insert_block_response_chunk(peer_ip, chunks[1].clone()).unwrap().is_none());
+        let block_response = gateway.insert_block_response_chunk(peer_ip, chunks[2].clone()).unwrap().unwrap();
+        assert_eq!(block_response.request, request);
+        assert_eq!(block_response.blocks.deserialize_blocking().unwrap(), blocks);
+        assert!(gateway.pending_block_responses.lock().is_empty());
+        // Ensure the round-trip time of the peer is sampled from the block request.
+        assert!(gateway.cache.peer_rtt(peer_ip).is_some());
+
+        // Ensure an out-of-order chunk is rejected, and the pending block response is dropped.
+        gateway.insert_pending_block_response(peer_ip, request);
+        assert!(gateway.insert_block_response_chunk(peer_ip, chunks[0].clone()).unwrap().is_none());
+        assert!(gateway.insert_block_response_chunk(peer_ip, chunks[2].clone()).is_err());
+        assert!(gateway.pending_block_responses.lock().is_empty());
+
+        // Ensure a chunk that declares a total size beyond the cap is rejected.
+        gateway.insert_pending_block_response(peer_ip, request);
+        let mut oversized = chunks[0].clone();
+        oversized.total_size = BlockResponseAssembler::<CurrentNetwork>::MAXIMUM_SIZE as u32 + 1;
+        assert!(gateway.insert_block_response_chunk(peer_ip, oversized).is_err());
+        assert!(gateway.pending_block_responses.lock().is_empty());
+
+        // Ensure the chunks of an expired block request are rejected.
+        gateway.insert_pending_block_response(peer_ip, request);
+        let expired = Instant::now().checked_sub(Duration::from_secs(BLOCK_RESPONSE_TTL_IN_SECS)).unwrap();
+        gateway.pending_block_responses.lock().get_mut(&(peer_ip, request)).unwrap().0 = expired;
+        assert!(gateway.insert_block_response_chunk(peer_ip, chunks[0].clone()).is_err());
+        assert!(gateway.pending_block_responses.lock().is_empty());
+    }
+}
+
 #[cfg(test)]
 mod prop_tests {
     use crate::{
@@ -0,0 +1,445 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkOS library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::helpers::{fmt_id, Ready};
+use snarkvm::{
+    console::prelude::*,
+    ledger::narwhal::{Transmission, TransmissionID},
+};
+
+use indexmap::IndexMap;
+use parking_lot::Mutex;
+use reqwest::{Client, Url};
+use serde::{Deserialize, Serialize};
+use std::{sync::Arc, time::Duration};
+
+/// An external (e.g. reputation or spam-scoring) service, that is consulted on the admission of transmissions.
+#[async_trait]
+pub trait AdmissionScorer<N: Network>: Send + Sync {
+    /// Returns `true` if the given transmission is borderline, and the scorer must be consulted.
+    /// Transmissions that are not borderline are admitted on the fast path, without consulting the scorer.
+    fn is_borderline(&self, _transmission_id: TransmissionID<N>, _transmission: &Transmission<N>) -> bool {
+        true
+    }
+
+    /// Returns `true` if the given transmission should be admitted.
+    async fn score(&self, transmission_id: TransmissionID<N>, transmission: Transmission<N>) -> Result<bool>;
+}
+
+/// The request POSTed to the endpoint of a webhook scorer, for each transmission.
+#[derive(Serialize)]
+struct ScoreRequest<'a, N: Network> {
+    /// The ID of the transmission.
+    transmission_id: TransmissionID<N>,
+    /// The transmission.
+    transmission: &'a Transmission<N>,
+}
+
+/// The verdict that the endpoint of a webhook scorer responds with.
+#[derive(Deserialize)]
+struct ScoreResponse {
+    /// The flag indicating whether the transmission should be admitted.
+    admit: bool,
+}
+
+/// A scorer that POSTs each transmission as JSON to an HTTP endpoint, which responds with `{"admit": <bool>}`.
+/// Note: The scorer is bounded by the timeout of its admission hook.
+#[derive(Clone, Debug)]
+pub struct WebhookScorer {
+    /// The URL of the endpoint.
+    url: Url,
+    /// The HTTP client.
+    client: Client,
+}
+
+impl WebhookScorer {
+    /// Initializes a new scorer for the given endpoint URL.
+    pub fn new(url: &str) -> Result<Self> {
+        let url = Url::parse(url)?;
+        ensure!(matches!(url.scheme(), "http" | "https"), "The admission scorer URL must be HTTP(S) - '{url}'");
+        Ok(Self { url, client: Client::new() })
+    }
+
+    /// Returns the URL of the endpoint.
+    pub const fn url(&self) -> &Url {
+        &self.url
+    }
+}
+
+#[async_trait]
+impl<N: Network> AdmissionScorer<N> for WebhookScorer {
+    /// Returns the verdict of the endpoint on the given transmission.
+    async fn score(&self, transmission_id: TransmissionID<N>, transmission: Transmission<N>) -> Result<bool> {
+        let request = ScoreRequest { transmission_id, transmission: &transmission };
+        let response = self.client.post(self.url.clone()).json(&request).send().await?.error_for_status()?;
+        Ok(response.json::<ScoreResponse>().await?.admit)
+    }
+}
+
+/// A hook that consults an external scorer on the admission of borderline transmissions, within a bounded timeout.
+pub struct AdmissionHook<N: Network> {
+    /// The external scorer.
+    scorer: Arc<dyn AdmissionScorer<N>>,
+    /// The maximum duration to wait for a verdict from the scorer.
+    timeout: Duration,
+    /// The flag indicating whether transmissions are admitted when the scorer fails or times out.
+    fail_open: bool,
+    /// The maximum number of verdicts to cache.
+    max_cached_verdicts: usize,
+    /// The cache of verdicts from the scorer, in insertion order.
+    verdicts: Mutex<IndexMap<TransmissionID<N>, bool>>,
+}
+
+impl<N: Network> AdmissionHook<N> {
+    /// The default maximum number of verdicts to cache.
+    pub const DEFAULT_MAX_CACHED_VERDICTS: usize = 1 << 16;
+
+    /// Initializes a new admission hook, with the given scorer and timeout, which fails open.
+    pub fn new(scorer: Arc<dyn AdmissionScorer<N>>, timeout: Duration) -> Self {
+        Self {
+            scorer,
+            timeout,
+            fail_open: true,
+            max_cached_verdicts: Self::DEFAULT_MAX_CACHED_VERDICTS,
+            verdicts: Default::default(),
+        }
+    }
+
+    /// Sets whether transmissions are admitted (fail-open) or rejected (fail-closed),
+    /// when the scorer fails or times out.
+    pub fn with_fail_open(mut self, fail_open: bool) -> Self {
+        self.fail_open = fail_open;
+        self
+    }
+
+    /// Sets the maximum number of verdicts to cache.
+    pub fn with_max_cached_verdicts(mut self, max_cached_verdicts: usize) -> Self {
+        self.max_cached_verdicts = max_cached_verdicts;
+        self
+    }
+
+    /// Returns the maximum duration to wait for a verdict from the scorer.
+    pub const fn timeout(&self) -> Duration {
+        self.timeout
+    }
+
+    /// Returns `true` if transmissions are admitted when the scorer fails or times out.
+    pub const fn is_fail_open(&self) -> bool {
+        self.fail_open
+    }
+
+    /// Returns the cached verdict for the given transmission ID, if one exists.
+    pub fn cached_verdict(&self, transmission_id: TransmissionID<N>) -> Option<bool> {
+        self.verdicts.lock().get(&transmission_id).copied()
+    }
+
+    /// Returns `true` if the given transmission is admitted.
+    pub async fn is_admitted(&self, transmission_id: TransmissionID<N>, transmission: &Transmission<N>) -> bool {
+        // If the transmission is not borderline, admit it on the fast path.
+        if !self.scorer.is_borderline(transmission_id, transmission) {
+            return true;
+        }
+        // If a verdict is cached, return it.
+        if let Some(verdict) = self.cached_verdict(transmission_id) {
+            return verdict;
+        }
+        // Consult the scorer, within the timeout.
+        match tokio::time::timeout(self.timeout, self.scorer.score(transmission_id, transmission.clone())).await {
+            Ok(Ok(verdict)) => {
+                // Cache the verdict, evicting the oldest verdict if the cache is full.
+                let mut verdicts = self.verdicts.lock();
+                if self.max_cached_verdicts > 0 {
+                    if verdicts.len() >= self.max_cached_verdicts {
+                        verdicts.shift_remove_index(0);
+                    }
+                    verdicts.insert(transmission_id, verdict);
+                }
+                verdict
+            }
+            // Note: Failures and timeouts are not cached, so the scorer is consulted again next time.
+            Ok(Err(error)) => {
+                warn!("Admission scorer failed on '{}' - {error}", fmt_id(transmission_id));
+                self.fail_open
+            }
+            Err(_) => {
+                warn!("Admission scorer timed out on '{}' after {:?}", fmt_id(transmission_id), self.timeout);
+                self.fail_open
+            }
+        }
+    }
+}
+
+/// The order in which the transmissions that arrive while the ready queue is full are admitted, as slots free up.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub enum AdmissionOrder {
+    /// The transmissions are admitted in order of arrival.
+    #[default]
+    Fifo,
+    /// The transmissions are admitted in descending order of priority fee. Transmissions with equal priority fees
+    /// are ordered by transmission ID, so that the order is independent of the order of arrival.
+    Fee,
+}
+
+/// A bounded ready queue, where the transmissions that arrive while it is full are staged, and are admitted
+/// in the configured order as slots free up.
+///
+/// Note: This only orders the admission into the ready queue; the transmissions are still drained into batches
+/// in the order of the ready queue (i.e. of admission, unless fee priority is enabled).
+pub struct AdmissionQueue<N: Network> {
+    /// The order in which the staged transmissions are admitted.
+    order: AdmissionOrder,
+    /// The maximum number of transmissions in the ready queue, and of staged transmissions.
+    capacity: usize,
+    /// The staged transmissions and their priority fees, in order of arrival.
+    staged: Mutex<IndexMap<TransmissionID<N>, (u64, Transmission<N>)>>,
+}
+
+impl<N: Network> AdmissionQueue<N> {
+    /// Initializes a new admission queue, with the given order and capacity.
+    pub fn new(order: AdmissionOrder, capacity: usize) -> Result<Self> {
+        ensure!(capacity > 0, "The capacity of the admission queue must be nonzero");
+        Ok(Self { order, capacity, staged: Default::default() })
+    }
+
+    /// Returns the order in which the staged transmissions are admitted.
+    pub const fn order(&self) -> AdmissionOrder {
+        self.order
+    }
+
+    /// Returns the maximum number of transmissions in the ready queue, and of staged transmissions.
+    pub const fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Returns the number of staged transmissions.
+    pub fn num_staged(&self) -> usize {
+        self.staged.lock().len()
+    }
+
+    /// Returns `true` if the specified transmission is staged.
+    pub fn contains(&self, transmission_id: impl Into<TransmissionID<N>>) -> bool {
+        self.staged.lock().contains_key(&transmission_id.into())
+    }
+
+    /// Returns the staged transmission, given the specified `transmission ID`.
+    pub fn get(&self, transmission_id: impl Into<TransmissionID<N>>) -> Option<Transmission<N>> {
+        self.staged.lock().get(&transmission_id.into()).map(|(_, transmission)| transmission.clone())
+    }
+
+    /// Returns the key by which a staged transmission is ordered for admission in fee order, i.e. by descending
+    /// priority fee, with ties broken by the transmission ID bytes.
+    fn fee_order_key(transmission_id: &TransmissionID<N>, priority_fee: u64) -> (std::cmp::Reverse<u64>, Vec<u8>) {
+        (std::cmp::Reverse(priority_fee), transmission_id.to_bytes_le().unwrap_or_default())
+    }
+
+    /// Sets the priority fee of the specified transmission, if it is staged.
+    /// Returns `true` if the priority fee was set.
+    pub fn set_priority_fee(&self, transmission_id: impl Into<TransmissionID<N>>, priority_fee: u64) -> bool {
+        match self.staged.lock().get_mut(&transmission_id.into()) {
+            Some((fee, _)) => {
+                *fee = priority_fee;
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Stages the given transmission with its priority fee, and admits the staged transmissions into the ready queue,
+    /// in order, up to its capacity. Returns `true` if the given transmission was admitted.
+    ///
+    /// Note: If the staged transmissions are at capacity, the given transmission is dropped. In fee order, it instead
+    /// evicts the staged transmission that would be admitted last, if its priority fee is strictly higher.
+    pub fn admit(
+        &self,
+        ready: &Ready<N>,
+        transmission_id: TransmissionID<N>,
+        transmission: Transmission<N>,
+        priority_fee: u64,
+    ) -> bool {
+        // Acquire the lock, so that concurrent arrivals are staged and admitted one at a time.
+        let mut staged = self.staged.lock();
+        // Ensure the transmission is new.
+        if ready.contains(transmission_id) || staged.contains_key(&transmission_id) {
+            return false;
+        }
+        // Ensure there is room to stage the transmission.
+        if staged.len() >= self.capacity {
+            let last = match self.order {
+                AdmissionOrder::Fifo => None,
+                AdmissionOrder::Fee => staged
+                    .iter()
+                    .map(|(id, (fee, _))| (*id, *fee))
+                    .max_by_key(|(id, fee)| Self::fee_order_key(id, *fee)),
+            };
+            match last {
+                Some((last_id, last_fee)) if priority_fee > last_fee => {
+                    debug!("Evicted '{}' from the admission queue, for a higher priority fee", fmt_id(last_id));
+                    staged.shift_remove(&last_id);
+                }
+                _ => return false,
+            }
+        }
+        staged.insert(transmission_id, (priority_fee, transmission));
+        // Admit the staged transmissions, up to the capacity of the ready queue.
+        self.admit_staged_inner(ready, &mut staged).contains(&transmission_id)
+    }
+
+    /// Admits the staged transmissions into the ready queue, in order, up to its capacity.
+    /// Returns the admitted transmission IDs, in order of admission.
+    pub fn admit_staged(&self, ready: &Ready<N>) -> Vec<TransmissionID<N>> {
+        self.admit_staged_inner(ready, &mut self.staged.lock())
+    }
+
+    /// Admits the given staged transmissions into the ready queue, in order, up to its capacity.
+    fn admit_staged_inner(
+        &self,
+        ready: &Ready<N>,
+        staged: &mut IndexMap<TransmissionID<N>, (u64, Transmission<N>)>,
+    ) -> Vec<TransmissionID<N>> {
+        // Determine the number of free slots in the ready queue.
+        let num_slots = self.capacity.saturating_sub(ready.num_transmissions()).min(staged.len());
+        if num_slots == 0 {
+            return vec![];
+        }
+        // Order the staged transmissions for admission.
+        if self.order == AdmissionOrder::Fee {
+            // Order by descending priority fee, breaking ties by the transmission ID bytes.
+            staged.sort_by(|id_a, (fee_a, _), id_b, (fee_b, _)| {
+                Self::fee_order_key(id_a, *fee_a).cmp(&Self::fee_order_key(id_b, *fee_b))
+            });
+        }
+        // Admit the first staged transmissions, in order.
+        staged
+            .drain(..num_slots)
+            .filter_map(|(id, (fee, transmission))| {
+                (ready.insert(id, transmission) && ready.set_priority_fee(id, fee)).then_some(id)
+            })
+            .collect()
+    }
+
+    /// Removes the staged transmissions, and returns them (with their priority fees) in order of arrival.
+    pub fn take_staged(&self) -> IndexMap<TransmissionID<N>, (u64, Transmission<N>)> {
+        std::mem::take(&mut *self.staged.lock())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use snarkvm::ledger::{coinbase::PuzzleCommitment, narwhal::Data};
+
+    use ::bytes::Bytes;
+    use std::thread;
+
+    type CurrentNetwork = snarkvm::prelude::Testnet3;
+
+    /// Samples the given number of solution transmissions.
+    fn sample_transmissions(
+        num_transmissions: usize,
+        rng: &mut TestRng,
+    ) -> Vec<(TransmissionID<CurrentNetwork>, Transmission<CurrentNetwork>)> {
+        (0..num_transmissions)
+            .map(|_| {
+                let id = TransmissionID::Solution(PuzzleCommitment::from_g1_affine(rng.gen()));
+                let bytes = Bytes::from((0..64).map(|_| rng.gen::<u8>()).collect::<Vec<_>>());
+                (id, Transmission::Solution(Data::Buffer(bytes)))
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_admission_order_is_honored_under_contention() {
+        const CAPACITY: usize = 16;
+        const NUM_THREADS: usize = 4;
+        const NUM_ARRIVALS_PER_THREAD: usize = 4;
+
+        let rng = &mut TestRng::default();
+        let transmissions = sample_transmissions(CAPACITY + NUM_THREADS * NUM_ARRIVALS_PER_THREAD + 2, rng);
+        let (initial, arrivals) = transmissions.split_at(CAPACITY);
+        let ((dropped_id, dropped), arrivals) = arrivals.split_last().unwrap();
+        let ((boosted_id, boosted), arrivals) = arrivals.split_last().unwrap();
+        // Assign the arrivals a few distinct priority fees, so that some fees are tied.
+        let fee = |index: usize| (index % 3) as u64 * 10;
+
+        for order in [AdmissionOrder::Fifo, AdmissionOrder::Fee] {
+            let ready = Ready::<CurrentNetwork>::new();
+            let queue = Arc::new(AdmissionQueue::new(order, CAPACITY).unwrap());
+            assert!(AdmissionQueue::<CurrentNetwork>::new(order, 0).is_err());
+
+            // Fill the ready queue to capacity.
+            for (id, transmission) in initial {
+                assert!(queue.admit(&ready, *id, transmission.clone(), 0));
+            }
+            assert_eq!(ready.num_transmissions(), CAPACITY);
+
+            // Stage the arrivals concurrently, while the ready queue is full.
+            let handles: Vec<_> = arrivals
+                .chunks(NUM_ARRIVALS_PER_THREAD)
+                .enumerate()
+                .map(|(chunk_index, chunk)| {
+                    let (ready, queue, chunk) = (ready.clone(), queue.clone(), chunk.to_vec());
+                    thread::spawn(move || {
+                        for (index, (id, transmission)) in chunk.into_iter().enumerate() {
+                            let priority_fee = fee(chunk_index * NUM_ARRIVALS_PER_THREAD + index);
+                            assert!(!queue.admit(&ready, id, transmission, priority_fee));
+                        }
+                    })
+                })
+                .collect();
+            handles.into_iter().for_each(|handle| handle.join().unwrap());
+
+            // Ensure the ready queue did not exceed its capacity, and an arrival beyond the capacity is dropped.
+            assert_eq!(ready.num_transmissions(), CAPACITY);
+            assert_eq!(queue.num_staged(), CAPACITY);
+            assert!(!queue.admit(&ready, *dropped_id, dropped.clone(), 0));
+            assert!(!queue.contains(*dropped_id));
+
+            // Ensure an arrival with a strictly higher priority fee evicts the last staged transmission in fee order.
+            let last_id = {
+                let staged = queue.staged.lock();
+                staged
+                    .iter()
+                    .max_by_key(|(id, (fee, _))| AdmissionQueue::<CurrentNetwork>::fee_order_key(id, *fee))
+                    .map(|(id, _)| *id)
+            };
+            assert!(!queue.admit(&ready, *boosted_id, boosted.clone(), 100));
+            assert_eq!(queue.num_staged(), CAPACITY);
+            match order {
+                AdmissionOrder::Fifo => assert!(!queue.contains(*boosted_id)),
+                AdmissionOrder::Fee => {
+                    assert!(queue.contains(*boosted_id));
+                    assert!(!queue.contains(last_id.unwrap()));
+                }
+            }
+            let staged = queue.staged.lock().iter().map(|(id, (fee, _))| (*id, *fee)).collect::<Vec<_>>();
+
+            // Free half of the slots, and admit the staged transmissions.
+            ready.drain(CAPACITY / 2);
+            let admitted = queue.admit_staged(&ready);
+            assert_eq!(admitted.len(), CAPACITY / 2);
+            assert_eq!(ready.num_transmissions(), CAPACITY);
+            assert_eq!(queue.num_staged(), CAPACITY / 2);
+
+            // Ensure the admitted transmissions are in the configured order.
+            let mut expected = staged;
+            if order == AdmissionOrder::Fee {
+                expected.sort_by_key(|(id, fee)| (std::cmp::Reverse(*fee), id.to_bytes_le().unwrap()));
+            }
+            let expected = expected.into_iter().take(CAPACITY / 2).map(|(id, _)| id).collect::<Vec<_>>();
+            assert_eq!(admitted, expected);
+            // Ensure the admitted transmissions carry over their priority fees.
+            assert!(admitted.iter().all(|id| ready.get_priority_fee(*id).is_some()));
+        }
+    }
+}
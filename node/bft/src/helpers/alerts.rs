@@ -0,0 +1,323 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkOS library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::helpers::RetryPolicy;
+use snarkvm::prelude::{bail, ensure, Result};
+
+use parking_lot::Mutex;
+use reqwest::{Client, Url};
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::{HashMap, HashSet},
+    fmt,
+    str::FromStr,
+    sync::Arc,
+    time::{Duration, Instant},
+};
+use time::OffsetDateTime;
+
+/// The default minimum interval between two alerts of the same event.
+pub const DEFAULT_ALERT_INTERVAL_IN_SECS: u64 = 300; // seconds
+/// The timeout of a single webhook delivery attempt.
+const ALERT_TIMEOUT_IN_SECS: u64 = 10; // seconds
+/// The timeout to deliver an alert before the node exits.
+pub const ALERT_EXIT_TIMEOUT_IN_SECS: u64 = 5; // seconds
+
+/// A critical node event, on which an alert is emitted.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum AlertEvent {
+    /// The primary is connected to too little committee stake, and is likely partitioned.
+    #[serde(rename = "partition")]
+    PartitionDetected,
+    /// The primary has not advanced its round for too long.
+    #[serde(rename = "stuck-round")]
+    StuckRound,
+    /// The validator was removed from the committee.
+    #[serde(rename = "committee-removal")]
+    CommitteeRemoval,
+    /// The storage of the node is corrupted.
+    #[serde(rename = "storage-corruption")]
+    StorageCorruption,
+    /// The committee changed too much stake in one step, so its quorums may not intersect with the previous ones.
+    #[serde(rename = "committee-change")]
+    UnsafeCommitteeChange,
+}
+
+impl AlertEvent {
+    /// The list of all critical node events.
+    pub const ALL: [Self; 5] = [
+        Self::PartitionDetected,
+        Self::StuckRound,
+        Self::CommitteeRemoval,
+        Self::StorageCorruption,
+        Self::UnsafeCommitteeChange,
+    ];
+
+    /// Returns the name of the event.
+    pub const fn as_str(&self) -> &'static str {
+        match self {
+            Self::PartitionDetected => "partition",
+            Self::StuckRound => "stuck-round",
+            Self::CommitteeRemoval => "committee-removal",
+            Self::StorageCorruption => "storage-corruption",
+            Self::UnsafeCommitteeChange => "committee-change",
+        }
+    }
+}
+
+impl FromStr for AlertEvent {
+    type Err = anyhow::Error;
+
+    /// Parses the event from its name.
+    fn from_str(name: &str) -> Result<Self> {
+        match Self::ALL.into_iter().find(|event| event.as_str() == name) {
+            Some(event) => Ok(event),
+            None => bail!("Unknown alert event '{name}'"),
+        }
+    }
+}
+
+impl fmt::Display for AlertEvent {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+/// The JSON payload of an alert, which is POSTed to the webhook.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Alert {
+    /// The critical node event.
+    pub event: AlertEvent,
+    /// The description of the event.
+    pub message: String,
+    /// The UNIX timestamp (in seconds) of the event.
+    pub timestamp: i64,
+    /// The number of alerts of the same event that were suppressed (by rate limiting) since the last alert.
+    pub suppressed: u64,
+}
+
+/// Notifies a webhook of the critical node events, with retries, and rate limiting per event.
+#[derive(Clone, Debug)]
+pub struct AlertNotifier {
+    /// The URL of the webhook.
+    url: Url,
+    /// The events that are alerted on.
+    events: HashSet<AlertEvent>,
+    /// The minimum interval between two alerts of the same event.
+    min_interval: Duration,
+    /// The retry policy for delivering an alert.
+    retry_policy: RetryPolicy,
+    /// The HTTP client.
+    client: Client,
+    /// The time of the last alert, and the number of suppressed alerts since, for each event.
+    history: Arc<Mutex<HashMap<AlertEvent, (Instant, u64)>>>,
+}
+
+impl AlertNotifier {
+    /// Initializes a new notifier for the given webhook URL, which alerts on every critical node event.
+    pub fn new(url: &str) -> Result<Self> {
+        let url = Url::parse(url)?;
+        ensure!(matches!(url.scheme(), "http" | "https"), "The alert webhook URL must be HTTP(S) - '{url}'");
+        Ok(Self {
+            url,
+            events: AlertEvent::ALL.into_iter().collect(),
+            min_interval: Duration::from_secs(DEFAULT_ALERT_INTERVAL_IN_SECS),
+            retry_policy: Default::default(),
+            client: Client::builder().timeout(Duration::from_secs(ALERT_TIMEOUT_IN_SECS)).build()?,
+            history: Default::default(),
+        })
+    }
+
+    /// Sets the events that are alerted on.
+    pub fn with_events(mut self, events: impl IntoIterator<Item = AlertEvent>) -> Self {
+        self.events = events.into_iter().collect();
+        self
+    }
+
+    /// Sets the minimum interval between two alerts of the same event.
+    pub fn with_min_interval(mut self, min_interval: Duration) -> Self {
+        self.min_interval = min_interval;
+        self
+    }
+
+    /// Sets the retry policy for delivering an alert.
+    pub fn with_retry_policy(mut self, retry_policy: RetryPolicy) -> Self {
+        self.retry_policy = retry_policy;
+        self
+    }
+
+    /// Returns the URL of the webhook.
+    pub const fn url(&self) -> &Url {
+        &self.url
+    }
+
+    /// Returns the events that are alerted on.
+    pub const fn events(&self) -> &HashSet<AlertEvent> {
+        &self.events
+    }
+
+    /// Alerts the webhook of the given event in the background, unless the event is not alerted on,
+    /// or an alert of the same event was sent within the minimum interval. Returns `true` if the alert is sent.
+    pub fn notify(&self, event: AlertEvent, message: impl Into<String>) -> bool {
+        let Some(alert) = self.prepare(event, message) else {
+            return false;
+        };
+        // Deliver the alert in the background, so the caller is never delayed by the webhook.
+        let Ok(handle) = tokio::runtime::Handle::try_current() else {
+            warn!("Failed to send the '{event}' alert (no runtime)");
+            return false;
+        };
+        let self_ = self.clone();
+        handle.spawn(async move { self_.send(&alert).await });
+        true
+    }
+
+    /// Alerts the webhook of the given event, waiting until the alert is delivered or the given timeout elapses,
+    /// e.g. before the node exits. Returns `true` if the alert was delivered.
+    pub async fn notify_and_wait(&self, event: AlertEvent, message: impl Into<String>, timeout: Duration) -> bool {
+        let Some(alert) = self.prepare(event, message) else {
+            return false;
+        };
+        match tokio::time::timeout(timeout, self.send(&alert)).await {
+            Ok(is_delivered) => is_delivered,
+            Err(_) => {
+                warn!("Failed to deliver the '{event}' alert within {}s", timeout.as_secs());
+                false
+            }
+        }
+    }
+
+    /// Returns the alert of the given event, unless the event is not alerted on,
+    /// or an alert of the same event was sent within the minimum interval.
+    fn prepare(&self, event: AlertEvent, message: impl Into<String>) -> Option<Alert> {
+        if !self.events.contains(&event) {
+            return None;
+        }
+        // Rate limit the alerts of the event, counting the suppressed alerts.
+        let suppressed = {
+            let mut history = self.history.lock();
+            let now = Instant::now();
+            if let Some((last_sent, suppressed)) = history.get_mut(&event) {
+                if now.duration_since(*last_sent) < self.min_interval {
+                    *suppressed += 1;
+                    return None;
+                }
+            }
+            history.insert(event, (now, 0)).map_or(0, |(_, suppressed)| suppressed)
+        };
+        let timestamp = OffsetDateTime::now_utc().unix_timestamp();
+        Some(Alert { event, message: message.into(), timestamp, suppressed })
+    }
+
+    /// Delivers the given alert to the webhook, retrying with backoff until the webhook accepts it,
+    /// or the maximum number of attempts is reached. Returns `true` if the alert was delivered.
+    pub async fn send(&self, alert: &Alert) -> bool {
+        for attempt in 1..=self.retry_policy.max_attempts() {
+            match self.client.post(self.url.clone()).json(alert).send().await {
+                Ok(response) if response.status().is_success() => return true,
+                Ok(response) => debug!("The alert webhook responded with '{}' (attempt {attempt})", response.status()),
+                Err(error) => debug!("Failed to reach the alert webhook (attempt {attempt}) - {error}"),
+            }
+            // If there are attempts remaining, back off before retrying.
+            if attempt < self.retry_policy.max_attempts() {
+                tokio::time::sleep(self.retry_policy.delay(attempt)).await;
+            }
+        }
+        warn!("Failed to deliver the '{}' alert after {} attempts", alert.event, self.retry_policy.max_attempts());
+        false
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use axum::{extract::State, http::StatusCode, routing::post, Json, Router};
+    use tokio::net::TcpListener;
+
+    /// Starts a webhook that fails the first delivery, and records the received alerts.
+    async fn sample_webhook() -> (String, Arc<Mutex<Vec<Alert>>>) {
+        let received = Arc::new(Mutex::new(Vec::new()));
+        let num_requests = Arc::new(Mutex::new(0));
+        let handler = |State((received, num_requests)): State<(Arc<Mutex<Vec<Alert>>>, Arc<Mutex<usize>>)>,
+                       Json(alert): Json<Alert>| async move {
+            let mut num_requests = num_requests.lock();
+            *num_requests += 1;
+            if *num_requests == 1 {
+                return StatusCode::SERVICE_UNAVAILABLE;
+            }
+            received.lock().push(alert);
+            StatusCode::OK
+        };
+        let router = Router::new().route("/alerts", post(handler)).with_state((received.clone(), num_requests));
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}/alerts", listener.local_addr().unwrap());
+        tokio::spawn(async move { axum::serve(listener, router).await });
+        (url, received)
+    }
+
+    #[test]
+    fn test_alert_event_names() {
+        for event in AlertEvent::ALL {
+            assert_eq!(event.as_str().parse::<AlertEvent>().unwrap(), event);
+        }
+        assert!("unknown".parse::<AlertEvent>().is_err());
+        assert!(AlertNotifier::new("ftp://127.0.0.1/alerts").is_err());
+    }
+
+    #[tokio::test]
+    async fn test_critical_event_is_alerted() {
+        let (url, received) = sample_webhook().await;
+        let notifier = AlertNotifier::new(&url)
+            .unwrap()
+            .with_events([AlertEvent::PartitionDetected, AlertEvent::StuckRound])
+            .with_retry_policy(RetryPolicy::new(3, 10).unwrap());
+
+        // Trigger a critical event, and ensure the webhook receives the alert (after a retry).
+        assert!(notifier.notify(AlertEvent::PartitionDetected, "Primary is likely partitioned in round 7"));
+        let deadline = Instant::now() + Duration::from_secs(5);
+        while received.lock().is_empty() && Instant::now() < deadline {
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+        let alerts = received.lock().clone();
+        assert_eq!(alerts.len(), 1);
+        assert_eq!(alerts[0].event, AlertEvent::PartitionDetected);
+        assert_eq!(alerts[0].message, "Primary is likely partitioned in round 7");
+        assert_eq!(alerts[0].suppressed, 0);
+
+        // Ensure a storm of the same event is rate limited.
+        for _ in 0..10 {
+            assert!(!notifier.notify(AlertEvent::PartitionDetected, "Primary is likely partitioned in round 8"));
+        }
+        // Ensure the events that are not alerted on are ignored.
+        assert!(!notifier.notify(AlertEvent::CommitteeRemoval, "Validator was removed from the committee"));
+
+        // Ensure the suppressed alerts are counted in the next alert, once the interval elapses.
+        let notifier = notifier.with_min_interval(Duration::ZERO);
+        assert!(notifier.notify(AlertEvent::PartitionDetected, "Primary is likely partitioned in round 9"));
+        let deadline = Instant::now() + Duration::from_secs(5);
+        while received.lock().len() < 2 && Instant::now() < deadline {
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+        let alerts = received.lock().clone();
+        assert_eq!(alerts.len(), 2);
+        assert_eq!(alerts[1].suppressed, 10);
+
+        // Ensure an alert before the node exits is delivered, once the call returns.
+        let notifier = notifier.with_events([AlertEvent::StorageCorruption]);
+        let timeout = Duration::from_secs(ALERT_EXIT_TIMEOUT_IN_SECS);
+        assert!(notifier.notify_and_wait(AlertEvent::StorageCorruption, "Storage corruption detected", timeout).await);
+        assert_eq!(received.lock().last().unwrap().event, AlertEvent::StorageCorruption);
+    }
+}
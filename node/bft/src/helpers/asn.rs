@@ -0,0 +1,184 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkOS library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use snarkvm::prelude::{bail, ensure, Result};
+
+use std::{
+    fmt,
+    io::BufRead,
+    net::{IpAddr, Ipv4Addr, Ipv6Addr},
+};
+
+/// The length of the prefix of the IPv4 subnets, by which the connections are grouped without an ASN.
+pub const IPV4_SUBNET_PREFIX_LEN: u32 = 24;
+/// The length of the prefix of the IPv6 subnets, by which the connections are grouped without an ASN.
+pub const IPV6_SUBNET_PREFIX_LEN: u32 = 48;
+
+/// A lookup of the autonomous system number (ASN) that announces an IP address.
+pub trait AsnLookup: Send + Sync {
+    /// Returns the ASN of the given IP address, if it is known.
+    fn asn(&self, ip: IpAddr) -> Option<u32>;
+}
+
+/// An IP-to-ASN dataset, as a sorted list of non-overlapping IP ranges and their ASNs.
+#[derive(Clone, Debug, Default)]
+pub struct AsnTable {
+    /// The `(start, end, ASN)` ranges, sorted by their start.
+    ranges: Vec<(IpAddr, IpAddr, u32)>,
+}
+
+impl AsnTable {
+    /// Initializes a new dataset from the given `(start, end, ASN)` ranges.
+    pub fn new(ranges: impl IntoIterator<Item = (IpAddr, IpAddr, u32)>) -> Result<Self> {
+        let mut ranges: Vec<_> = ranges.into_iter().collect();
+        ranges.sort_unstable_by_key(|(start, _, _)| *start);
+        for (start, end, asn) in &ranges {
+            ensure!(start.is_ipv4() == end.is_ipv4(), "The ASN range {start}-{end} mixes IPv4 and IPv6");
+            ensure!(start <= end, "The ASN range {start}-{end} of AS{asn} is empty");
+        }
+        // Ensure the ranges do not overlap.
+        for window in ranges.windows(2) {
+            let ((_, end, _), (start, _, _)) = (&window[0], &window[1]);
+            ensure!(end < start, "The ASN ranges overlap at {start}");
+        }
+        Ok(Self { ranges })
+    }
+
+    /// Parses the dataset from the given reader, in the (tab-separated) format of `ip2asn`, i.e. one
+    /// `range_start range_end AS_number ...` range per line. The ranges of `AS0` (i.e. not routed) are skipped.
+    pub fn from_reader(reader: impl BufRead) -> Result<Self> {
+        let mut ranges = vec![];
+        for (index, line) in reader.lines().enumerate() {
+            let line = line?;
+            let mut fields = line.split('\t').map(str::trim);
+            let (Some(start), Some(end), Some(asn)) = (fields.next(), fields.next(), fields.next()) else {
+                // Skip the blank lines.
+                if line.trim().is_empty() {
+                    continue;
+                }
+                bail!("Line {} of the ASN dataset is malformed", index + 1)
+            };
+            let range = (start.parse::<IpAddr>(), end.parse::<IpAddr>(), asn.trim_start_matches("AS").parse::<u32>());
+            let (Ok(start), Ok(end), Ok(asn)) = range else {
+                bail!("Line {} of the ASN dataset is malformed", index + 1)
+            };
+            if asn != 0 {
+                ranges.push((start, end, asn));
+            }
+        }
+        Self::new(ranges)
+    }
+
+    /// Returns the number of ranges in the dataset.
+    pub fn len(&self) -> usize {
+        self.ranges.len()
+    }
+
+    /// Returns `true` if the dataset is empty.
+    pub fn is_empty(&self) -> bool {
+        self.ranges.is_empty()
+    }
+}
+
+impl AsnLookup for AsnTable {
+    fn asn(&self, ip: IpAddr) -> Option<u32> {
+        // Find the last range that starts at or before the IP.
+        let index = self.ranges.partition_point(|(start, _, _)| *start <= ip).checked_sub(1)?;
+        let (start, end, asn) = self.ranges[index];
+        (start.is_ipv4() == ip.is_ipv4() && ip <= end).then_some(asn)
+    }
+}
+
+/// The group that an inbound connection is counted in, for the per-group connection limit.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum ConnectionGroup {
+    /// The autonomous system that announces the IP address.
+    Asn(u32),
+    /// The subnet of the IP address, if its ASN is not known.
+    Subnet(IpAddr),
+}
+
+impl ConnectionGroup {
+    /// Returns the group of the given IP address: its ASN, if the lookup knows it, and otherwise its subnet.
+    /// Note: An IPv4-mapped IPv6 address is grouped as its IPv4 address, as otherwise every such peer would
+    /// share the `::/48` subnet.
+    pub fn of(ip: IpAddr, lookup: Option<&dyn AsnLookup>) -> Self {
+        let ip = match ip {
+            IpAddr::V6(v6) => v6.to_ipv4_mapped().map_or(ip, IpAddr::V4),
+            ip => ip,
+        };
+        if let Some(asn) = lookup.and_then(|lookup| lookup.asn(ip)) {
+            return Self::Asn(asn);
+        }
+        match ip {
+            IpAddr::V4(ip) => {
+                let mask = u32::MAX.checked_shl(32 - IPV4_SUBNET_PREFIX_LEN).unwrap_or(0);
+                Self::Subnet(IpAddr::V4(Ipv4Addr::from(u32::from(ip) & mask)))
+            }
+            IpAddr::V6(ip) => {
+                let mask = u128::MAX.checked_shl(128 - IPV6_SUBNET_PREFIX_LEN).unwrap_or(0);
+                Self::Subnet(IpAddr::V6(Ipv6Addr::from(u128::from(ip) & mask)))
+            }
+        }
+    }
+}
+
+impl fmt::Display for ConnectionGroup {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Asn(asn) => write!(f, "AS{asn}"),
+            Self::Subnet(ip @ IpAddr::V4(_)) => write!(f, "subnet {ip}/{IPV4_SUBNET_PREFIX_LEN}"),
+            Self::Subnet(ip @ IpAddr::V6(_)) => write!(f, "subnet {ip}/{IPV6_SUBNET_PREFIX_LEN}"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_asn_table_lookup() {
+        let dataset = "1.0.0.0\t1.0.0.255\t13335\tUS\tCLOUDFLARENET\n\
+                       \n\
+                       1.0.4.0\t1.0.7.255\t38803\tAU\tGTELECOM\n\
+                       1.0.8.0\t1.0.15.255\t0\tNone\tNot routed\n\
+                       2001:200::\t2001:200:ffff:ffff:ffff:ffff:ffff:ffff\t2500\tJP\tWIDE-BB\n";
+        let table = AsnTable::from_reader(dataset.as_bytes()).unwrap();
+        assert_eq!(table.len(), 3);
+
+        // Ensure the IPs within a range are mapped to its ASN, and the others are unknown.
+        let ip = |ip: &str| ip.parse::<IpAddr>().unwrap();
+        assert_eq!(table.asn(ip("1.0.0.1")), Some(13335));
+        assert_eq!(table.asn(ip("1.0.5.9")), Some(38803));
+        assert_eq!(table.asn(ip("1.0.3.1")), None);
+        assert_eq!(table.asn(ip("1.0.9.1")), None);
+        assert_eq!(table.asn(ip("2001:200::1")), Some(2500));
+        assert_eq!(table.asn(ip("2001:201::1")), None);
+
+        // Ensure the IPs of an unknown ASN fall back to their subnet.
+        assert_eq!(ConnectionGroup::of(ip("1.0.0.1"), Some(&table)), ConnectionGroup::Asn(13335));
+        assert_eq!(ConnectionGroup::of(ip("1.0.9.1"), Some(&table)), ConnectionGroup::Subnet(ip("1.0.9.0")));
+        assert_eq!(ConnectionGroup::of(ip("1.0.0.1"), None), ConnectionGroup::Subnet(ip("1.0.0.0")));
+        assert_eq!(ConnectionGroup::of(ip("2001:201::1"), None).to_string(), "subnet 2001:201::/48");
+
+        // Ensure the IPv4-mapped IPv6 addresses are grouped as their IPv4 addresses.
+        assert_eq!(ConnectionGroup::of(ip("::ffff:1.0.0.1"), Some(&table)), ConnectionGroup::Asn(13335));
+        assert_eq!(ConnectionGroup::of(ip("::ffff:1.0.9.1"), None), ConnectionGroup::Subnet(ip("1.0.9.0")));
+
+        // Ensure a malformed or overlapping dataset is refused.
+        assert!(AsnTable::from_reader("1.0.0.0\t1.0.0.255\n".as_bytes()).is_err());
+        assert!(AsnTable::new([(ip("1.0.0.0"), ip("1.0.0.255"), 1), (ip("1.0.0.128"), ip("1.0.1.0"), 2)]).is_err());
+    }
+}
@@ -12,34 +12,317 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use snarkvm::{console::types::Field, ledger::narwhal::TransmissionID, prelude::Network};
+use crate::{helpers::ConnectionGroup, MAX_BATCH_DELAY_IN_MS};
+use snarkvm::{
+    console::types::Field,
+    ledger::narwhal::TransmissionID,
+    prelude::{Address, Network},
+};
 
-use core::hash::Hash;
+use anyhow::{ensure, Result};
+use core::{hash::Hash, ops::Deref};
 use parking_lot::RwLock;
 use std::{
-    collections::{BTreeMap, HashMap},
+    collections::{BTreeMap, HashMap, HashSet},
     net::{IpAddr, SocketAddr},
+    sync::atomic::{AtomicU32, AtomicU64, AtomicUsize, Ordering},
+    time::Duration,
 };
 use time::OffsetDateTime;
 
+/// The default maximum number of distinct peers to track in each peer map of the cache.
+pub const DEFAULT_MAX_CACHE_PEERS: usize = 10_000;
+/// The default interval (in seconds) of the windows of the cache.
+pub const DEFAULT_CACHE_INTERVAL_IN_SECS: i64 = (MAX_BATCH_DELAY_IN_MS / 1000) as i64; // seconds
+/// The assumed duration of a round, until the duration of a round is observed.
+pub const DEFAULT_ROUND_DURATION_IN_MS: u64 = MAX_BATCH_DELAY_IN_MS; // ms
+/// The weight of the past rounds in the average round duration, relative to the latest round.
+const ROUND_DURATION_SMOOTHING: u64 = 7;
+/// The weight of the past round-trip times of a peer in its average round-trip time, relative to the latest one.
+const RTT_SMOOTHING: u64 = 7;
+
+/// The window of a cache interval, either in seconds, or in rounds (which are translated to seconds
+/// using the observed average round duration, so the window stays meaningful regardless of network speed).
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum CacheWindow {
+    /// The window is the given number of seconds.
+    Secs(i64),
+    /// The window is the given number of rounds.
+    Rounds(u64),
+}
+
+impl CacheWindow {
+    /// Returns `true` if the window is nonzero.
+    pub const fn is_nonzero(&self) -> bool {
+        match self {
+            Self::Secs(interval_in_secs) => *interval_in_secs > 0,
+            Self::Rounds(num_rounds) => *num_rounds > 0,
+        }
+    }
+}
+
+/// The windows over which the maps of the cache are retained.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct CacheWindows {
+    /// The window of the inbound connections, and of their groups.
+    pub connections: CacheWindow,
+    /// The window of the inbound and outbound events.
+    pub events: CacheWindow,
+    /// The window of the inbound and outbound certificate and transmission requests.
+    pub requests: CacheWindow,
+    /// The window of the invalid certificates.
+    pub invalid_certificates: CacheWindow,
+    /// The window of the senders of the admitted transactions.
+    pub senders: CacheWindow,
+}
+
+impl Default for CacheWindows {
+    /// Initializes the windows with the default interval.
+    fn default() -> Self {
+        Self::uniform(DEFAULT_CACHE_INTERVAL_IN_SECS)
+    }
+}
+
+impl CacheWindows {
+    /// Initializes the windows, all with the given interval (in seconds).
+    pub const fn uniform(interval_in_secs: i64) -> Self {
+        let window = CacheWindow::Secs(interval_in_secs);
+        Self { connections: window, events: window, requests: window, invalid_certificates: window, senders: window }
+    }
+
+    /// Returns `true` if every window is nonzero.
+    pub const fn is_nonzero(&self) -> bool {
+        self.connections.is_nonzero()
+            && self.events.is_nonzero()
+            && self.requests.is_nonzero()
+            && self.invalid_certificates.is_nonzero()
+            && self.senders.is_nonzero()
+    }
+}
+
+/// The size of a map of the cache.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct CacheMapMetrics {
+    /// The number of `(timestamp, key)` entries.
+    pub num_entries: usize,
+    /// The number of distinct keys.
+    pub num_keys: usize,
+}
+
+/// A snapshot of the sizes of the maps of the cache, and of the likely spam sources.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct CacheMetrics {
+    /// The size of the map of peer connections.
+    pub inbound_connections: CacheMapMetrics,
+    /// The size of the map of inbound events.
+    pub inbound_events: CacheMapMetrics,
+    /// The size of the map of inbound certificate IDs.
+    pub inbound_certificates: CacheMapMetrics,
+    /// The size of the map of inbound transmission IDs.
+    pub inbound_transmissions: CacheMapMetrics,
+    /// The size of the map of outbound events.
+    pub outbound_events: CacheMapMetrics,
+    /// The size of the map of outbound certificate requests.
+    pub outbound_certificates: CacheMapMetrics,
+    /// The size of the map of outbound transmission requests.
+    pub outbound_transmissions: CacheMapMetrics,
+    /// The number of inserts that returned a count above the spam threshold.
+    pub num_spam_hits: u64,
+}
+
+/// The count of the cache hits of a key, in a bucket of a map of the cache.
+trait Counter {
+    /// Returns the count.
+    fn count(&self) -> u32;
+}
+
+impl Counter for u32 {
+    fn count(&self) -> u32 {
+        *self
+    }
+}
+
+impl Counter for AtomicU32 {
+    fn count(&self) -> u32 {
+        self.load(Ordering::Relaxed)
+    }
+}
+
+/// An ordered timestamp map of keys and their cache hits, along with an index of the keys by their latest
+/// timestamp, so that the distinct keys are counted, and the least-recently-active keys are found,
+/// without a scan of the map.
+#[derive(Debug)]
+struct CacheMap<K, V> {
+    /// The ordered timestamp map of keys and cache hits.
+    buckets: BTreeMap<i64, HashMap<K, V>>,
+    /// The latest timestamp of each key.
+    last_active: HashMap<K, i64>,
+    /// The keys by their latest timestamp, in order of least-recent activity.
+    by_last_active: BTreeMap<i64, HashSet<K>>,
+}
+
+impl<K, V> Default for CacheMap<K, V> {
+    fn default() -> Self {
+        Self { buckets: Default::default(), last_active: Default::default(), by_last_active: Default::default() }
+    }
+}
+
+impl<K, V> Deref for CacheMap<K, V> {
+    type Target = BTreeMap<i64, HashMap<K, V>>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.buckets
+    }
+}
+
+impl<K: Copy + Eq + Hash, V> CacheMap<K, V> {
+    /// Returns the number of distinct keys.
+    fn num_keys(&self) -> usize {
+        self.last_active.len()
+    }
+
+    /// Returns the cache hits of the given key at the given timestamp, inserting them if they are new.
+    fn entry(&mut self, timestamp: i64, key: K) -> &mut V
+    where
+        V: Default,
+    {
+        self.touch(key, timestamp);
+        self.buckets.entry(timestamp).or_default().entry(key).or_default()
+    }
+
+    /// Inserts the given keys and cache hits at the given timestamp.
+    #[cfg(test)]
+    fn insert_bucket(&mut self, timestamp: i64, keys: HashMap<K, V>) {
+        for (key, value) in keys {
+            self.touch(key, timestamp);
+            self.buckets.entry(timestamp).or_default().insert(key, value);
+        }
+    }
+
+    /// Updates the latest timestamp of the given key, if the given timestamp is later.
+    fn touch(&mut self, key: K, timestamp: i64) {
+        match self.last_active.get(&key) {
+            Some(last_active) if *last_active >= timestamp => return,
+            Some(last_active) => Self::unindex(&mut self.by_last_active, key, *last_active),
+            None => (),
+        }
+        self.last_active.insert(key, timestamp);
+        self.by_last_active.entry(timestamp).or_default().insert(key);
+    }
+
+    /// Removes the given key from the index at the given timestamp.
+    fn unindex(by_last_active: &mut BTreeMap<i64, HashSet<K>>, key: K, timestamp: i64) {
+        if let Some(keys) = by_last_active.get_mut(&timestamp) {
+            keys.remove(&key);
+            if keys.is_empty() {
+                by_last_active.remove(&timestamp);
+            }
+        }
+    }
+
+    /// Returns `true` if the map has entries with a timestamp below the cutoff.
+    fn has_expired(&self, cutoff: i64) -> bool {
+        self.buckets.first_key_value().is_some_and(|(oldest, _)| *oldest < cutoff)
+    }
+
+    /// Removes the entries with a timestamp below the cutoff.
+    fn remove_expired(&mut self, cutoff: i64) {
+        // If the oldest timestamp is below the cutoff value, remove the expired entries.
+        if self.has_expired(cutoff) {
+            // Extract the subtree after interval (i.e. non-expired entries), dropping the expired entries.
+            self.buckets = self.buckets.split_off(&cutoff);
+            // Remove the keys whose latest timestamp expired, as all of their entries expired.
+            let retained = self.by_last_active.split_off(&cutoff);
+            for key in std::mem::replace(&mut self.by_last_active, retained).into_values().flatten() {
+                self.last_active.remove(&key);
+            }
+        }
+    }
+
+    /// Removes the given key, along with any empty timestamps. Returns `true` if the key was present.
+    fn remove_key(&mut self, key: K) -> bool {
+        let Some(last_active) = self.last_active.remove(&key) else {
+            return false;
+        };
+        Self::unindex(&mut self.by_last_active, key, last_active);
+        // Remove the entries of the key, which are at or before its latest timestamp.
+        let mut emptied = Vec::new();
+        for (timestamp, keys) in self.buckets.range_mut(..=last_active) {
+            if keys.remove(&key).is_some() && keys.is_empty() {
+                emptied.push(*timestamp);
+            }
+        }
+        for timestamp in emptied {
+            self.buckets.remove(&timestamp);
+        }
+        true
+    }
+
+    /// Evicts the least-recently-active keys, until the map contains at most `max_keys` distinct keys.
+    /// Note: The given key is never evicted. Returns the number of evicted keys.
+    fn evict_least_recently_active(&mut self, key: K, max_keys: usize) -> usize {
+        let mut num_evicted = 0;
+        while self.num_keys() > max_keys {
+            // Select the least-recently-active key, excluding the given key.
+            let candidate = self.by_last_active.values().flatten().find(|candidate| **candidate != key).copied();
+            match candidate {
+                Some(candidate) => num_evicted += self.remove_key(candidate) as usize,
+                None => break,
+            }
+        }
+        num_evicted
+    }
+}
+
+/// The cache of the recent events, requests, and connections, bucketed by the second.
+///
+/// The `record_*` methods retain each map with its window, which is stored in the cache rather than given per call,
+/// so that intervals cannot be mixed on a map through them (i.e. a shorter interval never evicts the entries
+/// that are counted with a longer one). The `insert_*` methods take an interval per call, and should not be
+/// used on a map alongside the `record_*` methods with a different interval.
 #[derive(Debug)]
 pub struct Cache<N: Network> {
+    /// The windows over which the maps are retained.
+    windows: RwLock<CacheWindows>,
     /// The ordered timestamp map of peer connections and cache hits.
-    seen_inbound_connections: RwLock<BTreeMap<i64, HashMap<IpAddr, u32>>>,
+    seen_inbound_connections: RwLock<CacheMap<IpAddr, u32>>,
+    /// The ordered timestamp map of the groups (i.e. ASNs or subnets) of peer connections and cache hits.
+    seen_inbound_connection_groups: RwLock<CacheMap<ConnectionGroup, u32>>,
     /// The ordered timestamp map of peer IPs and cache hits.
-    seen_inbound_events: RwLock<BTreeMap<i64, HashMap<SocketAddr, u32>>>,
+    /// Note: The cache hits are atomic counters, as this map is updated on every inbound event.
+    seen_inbound_events: RwLock<CacheMap<SocketAddr, AtomicU32>>,
     /// The ordered timestamp map of certificate IDs and cache hits.
-    seen_inbound_certificates: RwLock<BTreeMap<i64, HashMap<Field<N>, u32>>>,
+    seen_inbound_certificates: RwLock<CacheMap<Field<N>, u32>>,
     /// The ordered timestamp map of transmission IDs and cache hits.
-    seen_inbound_transmissions: RwLock<BTreeMap<i64, HashMap<TransmissionID<N>, u32>>>,
+    seen_inbound_transmissions: RwLock<CacheMap<TransmissionID<N>, u32>>,
+    /// The ordered timestamp map of peer IPs and the invalid certificates they sent.
+    seen_invalid_certificates: RwLock<CacheMap<SocketAddr, u32>>,
+    /// The ordered timestamp map of the senders of the admitted transactions, and their cache hits.
+    seen_inbound_senders: RwLock<CacheMap<Address<N>, u32>>,
     /// The ordered timestamp map of peer IPs and their cache hits on outbound events.
-    seen_outbound_events: RwLock<BTreeMap<i64, HashMap<SocketAddr, u32>>>,
+    seen_outbound_events: RwLock<CacheMap<SocketAddr, u32>>,
     /// The ordered timestamp map of peer IPs and their cache hits on certificate requests.
-    seen_outbound_certificates: RwLock<BTreeMap<i64, HashMap<SocketAddr, u32>>>,
+    seen_outbound_certificates: RwLock<CacheMap<SocketAddr, u32>>,
     /// The ordered timestamp map of peer IPs and their cache hits on transmission requests.
-    seen_outbound_transmissions: RwLock<BTreeMap<i64, HashMap<SocketAddr, u32>>>,
+    seen_outbound_transmissions: RwLock<CacheMap<SocketAddr, u32>>,
     /// The map of IPs to the number of validators requests.
     seen_outbound_validators_requests: RwLock<HashMap<SocketAddr, u32>>,
+    /// The map of peer IPs to their (exponential moving) average round-trip time in milliseconds.
+    peer_rtts_in_ms: RwLock<HashMap<SocketAddr, u64>>,
+    /// The maximum number of distinct keys to retain in each map of certificate IDs or transmission IDs, if bounded.
+    max_keys: Option<usize>,
+    /// The maximum number of distinct peers to track in each peer map.
+    max_peers: AtomicUsize,
+    /// The number of peers evicted from the peer maps, due to the maximum number of distinct peers.
+    num_peer_evictions: AtomicU64,
+    /// The count of an insert above which the key is a likely spam source (`0` to disable).
+    spam_threshold: AtomicUsize,
+    /// The number of inserts that returned a count above the spam threshold.
+    num_spam_hits: AtomicU64,
+    /// The last observed round, and the UNIX timestamp (in milliseconds) at which it was observed.
+    last_observed_round: RwLock<Option<(u64, i64)>>,
+    /// The (exponential moving) average duration of a round in milliseconds, or `0` if none was observed.
+    avg_round_duration_in_ms: AtomicU64,
 }
 
 impl<N: Network> Default for Cache<N> {
@@ -52,55 +335,387 @@ impl<N: Network> Default for Cache<N> {
 impl<N: Network> Cache<N> {
     /// Initializes a new instance of the cache.
     pub fn new() -> Self {
+        Self::with_windows(Default::default())
+    }
+
+    /// Initializes a new instance of the cache, with the given interval (in seconds) for every map.
+    pub fn with_interval(interval_in_secs: i64) -> Self {
+        Self::with_windows(CacheWindows::uniform(interval_in_secs))
+    }
+
+    /// Initializes a new instance of the cache, with the given windows of the maps.
+    pub fn with_windows(windows: CacheWindows) -> Self {
         Self {
+            windows: RwLock::new(windows),
+            max_keys: None,
             seen_inbound_connections: Default::default(),
+            seen_inbound_connection_groups: Default::default(),
             seen_inbound_events: Default::default(),
             seen_inbound_certificates: Default::default(),
             seen_inbound_transmissions: Default::default(),
+            seen_invalid_certificates: Default::default(),
+            seen_inbound_senders: Default::default(),
             seen_outbound_events: Default::default(),
             seen_outbound_certificates: Default::default(),
             seen_outbound_transmissions: Default::default(),
             seen_outbound_validators_requests: Default::default(),
+            peer_rtts_in_ms: Default::default(),
+            max_peers: AtomicUsize::new(DEFAULT_MAX_CACHE_PEERS),
+            num_peer_evictions: Default::default(),
+            spam_threshold: Default::default(),
+            num_spam_hits: Default::default(),
+            last_observed_round: Default::default(),
+            avg_round_duration_in_ms: Default::default(),
+        }
+    }
+
+    /// Initializes a new instance of the cache, with the given interval (in seconds) for every map,
+    /// which retains at most `max_keys` distinct keys in each map of certificate IDs or transmission IDs.
+    ///
+    /// Note: When an insert exceeds the bound, the keys of the oldest timestamps are evicted first
+    /// (along with all of their entries, so the counts of the retained keys stay accurate).
+    pub fn with_limits(interval_in_secs: i64, max_keys: usize) -> Self {
+        Self { max_keys: Some(max_keys.max(1)), ..Self::with_interval(interval_in_secs) }
+    }
+
+    /// Returns the windows over which the maps are retained.
+    pub fn windows(&self) -> CacheWindows {
+        *self.windows.read()
+    }
+
+    /// Sets the windows over which the maps are retained, ensuring every window is nonzero.
+    pub fn set_windows(&self, windows: CacheWindows) -> Result<()> {
+        ensure!(windows.is_nonzero(), "The windows of the cache must be nonzero");
+        *self.windows.write() = windows;
+        Ok(())
+    }
+
+    /// Returns the maximum number of distinct keys to retain in each map of certificate IDs or transmission IDs,
+    /// if bounded.
+    pub const fn max_keys(&self) -> Option<usize> {
+        self.max_keys
+    }
+
+    /// Returns the maximum number of distinct peers to track in each peer map.
+    pub fn max_peers(&self) -> usize {
+        self.max_peers.load(Ordering::Relaxed)
+    }
+
+    /// Sets the maximum number of distinct peers to track in each peer map.
+    /// When exceeded, the least-recently-active peers are evicted.
+    pub fn set_max_peers(&self, max_peers: usize) {
+        self.max_peers.store(max_peers.max(1), Ordering::Relaxed);
+    }
+
+    /// Returns the number of peers evicted from the peer maps, due to the maximum number of distinct peers.
+    pub fn num_peer_evictions(&self) -> u64 {
+        self.num_peer_evictions.load(Ordering::Relaxed)
+    }
+
+    /// Returns the count of an insert above which the key is a likely spam source, if one is set.
+    pub fn spam_threshold(&self) -> Option<usize> {
+        match self.spam_threshold.load(Ordering::Relaxed) {
+            0 => None,
+            threshold => Some(threshold),
+        }
+    }
+
+    /// Sets the count of an insert above which the key is a likely spam source, for the spam hits metric.
+    /// If `None` is given, the spam hits are not counted.
+    pub fn set_spam_threshold(&self, threshold: Option<usize>) {
+        self.spam_threshold.store(threshold.unwrap_or(0), Ordering::Relaxed);
+    }
+
+    /// Returns a snapshot of the sizes of the maps, and of the number of spam hits.
+    ///
+    /// Note: This method only reads the maps, so the expired entries that were not yet removed are included.
+    pub fn metrics(&self) -> CacheMetrics {
+        CacheMetrics {
+            inbound_connections: Self::map_metrics(&self.seen_inbound_connections),
+            inbound_events: Self::map_metrics(&self.seen_inbound_events),
+            inbound_certificates: Self::map_metrics(&self.seen_inbound_certificates),
+            inbound_transmissions: Self::map_metrics(&self.seen_inbound_transmissions),
+            outbound_events: Self::map_metrics(&self.seen_outbound_events),
+            outbound_certificates: Self::map_metrics(&self.seen_outbound_certificates),
+            outbound_transmissions: Self::map_metrics(&self.seen_outbound_transmissions),
+            num_spam_hits: self.num_spam_hits.load(Ordering::Relaxed),
+        }
+    }
+
+    /// Updates the gauges of the sizes of the maps.
+    #[cfg(feature = "metrics")]
+    pub fn update_metrics(&self) {
+        use metrics::bft::*;
+
+        let snapshot = self.metrics();
+        for (map, entries, keys) in [
+            (snapshot.inbound_connections, CACHE_INBOUND_CONNECTIONS, CACHE_INBOUND_CONNECTION_KEYS),
+            (snapshot.inbound_events, CACHE_INBOUND_EVENTS, CACHE_INBOUND_EVENT_KEYS),
+            (snapshot.inbound_certificates, CACHE_INBOUND_CERTIFICATES, CACHE_INBOUND_CERTIFICATE_KEYS),
+            (snapshot.inbound_transmissions, CACHE_INBOUND_TRANSMISSIONS, CACHE_INBOUND_TRANSMISSION_KEYS),
+            (snapshot.outbound_events, CACHE_OUTBOUND_EVENTS, CACHE_OUTBOUND_EVENT_KEYS),
+            (snapshot.outbound_certificates, CACHE_OUTBOUND_CERTIFICATES, CACHE_OUTBOUND_CERTIFICATE_KEYS),
+            (snapshot.outbound_transmissions, CACHE_OUTBOUND_TRANSMISSIONS, CACHE_OUTBOUND_TRANSMISSION_KEYS),
+        ] {
+            metrics::gauge(entries, map.num_entries as f64);
+            metrics::gauge(keys, map.num_keys as f64);
         }
     }
+
+    /// Returns the largest number of distinct peers tracked in any peer map.
+    pub fn num_distinct_peers(&self) -> usize {
+        [
+            Self::num_distinct_keys(&self.seen_inbound_connections),
+            Self::num_distinct_keys(&self.seen_inbound_events),
+            Self::num_distinct_keys(&self.seen_invalid_certificates),
+            Self::num_distinct_keys(&self.seen_outbound_events),
+            Self::num_distinct_keys(&self.seen_outbound_certificates),
+            Self::num_distinct_keys(&self.seen_outbound_transmissions),
+        ]
+        .into_iter()
+        .max()
+        .unwrap_or(0)
+    }
+}
+
+impl<N: Network> Cache<N> {
+    /// Returns the observed average duration of a round, or the default round duration if none was observed.
+    pub fn avg_round_duration(&self) -> Duration {
+        match self.avg_round_duration_in_ms.load(Ordering::Relaxed) {
+            0 => Duration::from_millis(DEFAULT_ROUND_DURATION_IN_MS),
+            avg_round_duration_in_ms => Duration::from_millis(avg_round_duration_in_ms),
+        }
+    }
+
+    /// Records that the given round was reached now, updating the average round duration.
+    pub fn observe_round(&self, round: u64) {
+        self.observe_round_at(round, (OffsetDateTime::now_utc().unix_timestamp_nanos() / 1_000_000) as i64)
+    }
+
+    /// Records that the given round was reached at the given UNIX timestamp (in milliseconds),
+    /// updating the average round duration.
+    ///
+    /// Note: Only the advancements by a single round are measured, so that skipping rounds (e.g. when syncing)
+    /// does not skew the average round duration.
+    pub fn observe_round_at(&self, round: u64, timestamp_in_ms: i64) {
+        let mut last_observed_round = self.last_observed_round.write();
+        match *last_observed_round {
+            // Ignore the rounds that are not new.
+            Some((last_round, _)) if round <= last_round => return,
+            // Update the average round duration, if the round advanced by one.
+            Some((last_round, last_timestamp)) if round == last_round + 1 => {
+                let duration = timestamp_in_ms.saturating_sub(last_timestamp).max(1) as u64;
+                let average = match self.avg_round_duration_in_ms.load(Ordering::Relaxed) {
+                    0 => duration,
+                    average => (ROUND_DURATION_SMOOTHING * average + duration) / (ROUND_DURATION_SMOOTHING + 1),
+                };
+                self.avg_round_duration_in_ms.store(average.max(1), Ordering::Relaxed);
+            }
+            _ => (),
+        }
+        *last_observed_round = Some((round, timestamp_in_ms));
+    }
+
+    /// Returns the interval in seconds of the given window. A window in rounds is translated using
+    /// the observed average round duration, and is at least one second.
+    pub fn interval_in_secs(&self, window: CacheWindow) -> i64 {
+        match window {
+            CacheWindow::Secs(interval_in_secs) => interval_in_secs,
+            CacheWindow::Rounds(num_rounds) => {
+                let interval_in_ms = (num_rounds as u128).saturating_mul(self.avg_round_duration().as_millis());
+                i64::try_from(interval_in_ms.div_ceil(1000)).unwrap_or(i64::MAX).max(1)
+            }
+        }
+    }
+}
+
+impl<N: Network> Cache<N> {
+    /// Inserts a new timestamp for the given peer connection, returning the number of recent connection requests
+    /// (within the connections window).
+    pub fn record_inbound_connection(&self, peer_ip: IpAddr) -> usize {
+        self.insert_inbound_connection(peer_ip, self.interval_of(|windows| windows.connections))
+    }
+
+    /// Returns the number of recent connection requests from the given peer, without inserting a new timestamp.
+    pub fn num_inbound_connections(&self, peer_ip: IpAddr) -> usize {
+        Self::num_recent(&self.seen_inbound_connections, peer_ip, self.interval_of(|windows| windows.connections))
+    }
+
+    /// Inserts a new timestamp for the given connection group, returning the number of recent connection requests
+    /// from the group (within the connections window).
+    pub fn record_inbound_connection_group(&self, group: ConnectionGroup) -> usize {
+        self.insert_inbound_connection_group(group, self.interval_of(|windows| windows.connections))
+    }
+
+    /// Returns the number of recent connection requests from the given connection group,
+    /// without inserting a new timestamp.
+    pub fn num_inbound_connection_groups(&self, group: ConnectionGroup) -> usize {
+        let interval_in_secs = self.interval_of(|windows| windows.connections);
+        Self::num_recent(&self.seen_inbound_connection_groups, group, interval_in_secs)
+    }
+
+    /// Inserts a new timestamp for the given peer, returning the number of recent events (within the events window).
+    pub fn record_inbound_event(&self, peer_ip: SocketAddr) -> usize {
+        self.insert_inbound_event(peer_ip, self.interval_of(|windows| windows.events))
+    }
+
+    /// Returns the number of recent events from the given peer, without inserting a new timestamp.
+    pub fn num_inbound_events(&self, peer_ip: SocketAddr) -> usize {
+        Self::num_recent(&self.seen_inbound_events, peer_ip, self.interval_of(|windows| windows.events))
+    }
+
+    /// Inserts a certificate ID into the cache, returning the number of recent events (within the requests window).
+    pub fn record_inbound_certificate(&self, key: Field<N>) -> usize {
+        self.insert_inbound_certificate(key, self.interval_of(|windows| windows.requests))
+    }
+
+    /// Inserts a certificate ID into the cache, returning `true` if it was not seen within the requests window.
+    pub fn record_inbound_certificate_if_new(&self, key: Field<N>) -> bool {
+        self.insert_inbound_certificate_if_new(key, self.interval_of(|windows| windows.requests))
+    }
+
+    /// Returns the number of recent events for the given certificate ID, without inserting a new timestamp.
+    pub fn num_inbound_certificates(&self, key: Field<N>) -> usize {
+        Self::num_recent(&self.seen_inbound_certificates, key, self.interval_of(|windows| windows.requests))
+    }
+
+    /// Inserts a transmission ID into the cache, returning the number of recent events (within the requests window).
+    pub fn record_inbound_transmission(&self, key: TransmissionID<N>) -> usize {
+        self.insert_inbound_transmission(key, self.interval_of(|windows| windows.requests))
+    }
+
+    /// Inserts a transmission ID into the cache, returning `true` if it was not seen within the requests window.
+    pub fn record_inbound_transmission_if_new(&self, key: TransmissionID<N>) -> bool {
+        self.insert_inbound_transmission_if_new(key, self.interval_of(|windows| windows.requests))
+    }
+
+    /// Returns the number of recent events for the given transmission ID, without inserting a new timestamp.
+    pub fn num_inbound_transmissions(&self, key: TransmissionID<N>) -> usize {
+        Self::num_recent(&self.seen_inbound_transmissions, key, self.interval_of(|windows| windows.requests))
+    }
+
+    /// Inserts a new timestamp for the given peer, returning the number of recent invalid certificates
+    /// (within the invalid certificates window).
+    pub fn record_invalid_certificate(&self, peer_ip: SocketAddr) -> usize {
+        self.insert_invalid_certificate(peer_ip, self.interval_of(|windows| windows.invalid_certificates))
+    }
+
+    /// Returns the number of recent invalid certificates from the given peer.
+    pub fn num_invalid_certificates(&self, peer_ip: SocketAddr) -> usize {
+        let interval_in_secs = self.interval_of(|windows| windows.invalid_certificates);
+        Self::num_recent(&self.seen_invalid_certificates, peer_ip, interval_in_secs)
+    }
+
+    /// Inserts a new timestamp for the given sender, returning the number of its recent transactions
+    /// (within the senders window).
+    pub fn record_inbound_sender(&self, sender: Address<N>) -> usize {
+        self.insert_inbound_sender(sender, self.interval_of(|windows| windows.senders))
+    }
+
+    /// Returns the number of recent transactions from the given sender, without inserting a new timestamp.
+    pub fn num_inbound_senders(&self, sender: Address<N>) -> usize {
+        Self::num_recent(&self.seen_inbound_senders, sender, self.interval_of(|windows| windows.senders))
+    }
+
+    /// Inserts a new timestamp for the given peer, returning the number of recent events (within the events window).
+    pub fn record_outbound_event(&self, peer_ip: SocketAddr) -> usize {
+        self.insert_outbound_event(peer_ip, self.interval_of(|windows| windows.events))
+    }
+
+    /// Returns the number of recent events to the given peer, without inserting a new timestamp.
+    pub fn num_outbound_events(&self, peer_ip: SocketAddr) -> usize {
+        Self::num_recent(&self.seen_outbound_events, peer_ip, self.interval_of(|windows| windows.events))
+    }
+
+    /// Inserts a new timestamp for the given peer, returning the number of recent certificate requests
+    /// (within the requests window).
+    pub fn record_outbound_certificate(&self, peer_ip: SocketAddr) -> usize {
+        self.insert_outbound_certificate(peer_ip, self.interval_of(|windows| windows.requests))
+    }
+
+    /// Returns the number of recent certificate requests to the given peer, without inserting a new timestamp.
+    pub fn num_outbound_certificates(&self, peer_ip: SocketAddr) -> usize {
+        Self::num_recent(&self.seen_outbound_certificates, peer_ip, self.interval_of(|windows| windows.requests))
+    }
+
+    /// Inserts a new timestamp for the given peer, returning the number of recent transmission requests
+    /// (within the requests window).
+    pub fn record_outbound_transmission(&self, peer_ip: SocketAddr) -> usize {
+        self.insert_outbound_transmission(peer_ip, self.interval_of(|windows| windows.requests))
+    }
+
+    /// Returns the number of recent transmission requests to the given peer, without inserting a new timestamp.
+    pub fn num_outbound_transmissions(&self, peer_ip: SocketAddr) -> usize {
+        Self::num_recent(&self.seen_outbound_transmissions, peer_ip, self.interval_of(|windows| windows.requests))
+    }
 }
 
 impl<N: Network> Cache<N> {
+    /// Returns the interval in seconds of the window that is selected from the windows of the cache.
+    fn interval_of(&self, window: impl FnOnce(&CacheWindows) -> CacheWindow) -> i64 {
+        let window = window(&self.windows.read());
+        self.interval_in_secs(window)
+    }
+
     /// Inserts a new timestamp for the given peer connection, returning the number of recent connection requests.
     pub fn insert_inbound_connection(&self, peer_ip: IpAddr, interval_in_secs: i64) -> usize {
-        Self::retain_and_insert(&self.seen_inbound_connections, peer_ip, interval_in_secs)
+        self.retain_and_insert_peer(&self.seen_inbound_connections, peer_ip, interval_in_secs)
+    }
+
+    /// Inserts a new timestamp for the given connection group, returning the number of recent connection requests
+    /// from the group.
+    pub fn insert_inbound_connection_group(&self, group: ConnectionGroup, interval_in_secs: i64) -> usize {
+        Self::retain_and_insert(&self.seen_inbound_connection_groups, group, interval_in_secs)
     }
 
     /// Inserts a new timestamp for the given peer, returning the number of recent events.
     pub fn insert_inbound_event(&self, peer_ip: SocketAddr, interval_in_secs: i64) -> usize {
-        Self::retain_and_insert(&self.seen_inbound_events, peer_ip, interval_in_secs)
+        self.retain_and_increment_peer(&self.seen_inbound_events, peer_ip, interval_in_secs)
     }
 
     /// Inserts a certificate ID into the cache, returning the number of recent events.
     pub fn insert_inbound_certificate(&self, key: Field<N>, interval_in_secs: i64) -> usize {
-        Self::retain_and_insert(&self.seen_inbound_certificates, key, interval_in_secs)
+        self.retain_and_insert_key(&self.seen_inbound_certificates, key, interval_in_secs)
+    }
+
+    /// Inserts a certificate ID into the cache, returning `true` if it was not seen within the interval.
+    pub fn insert_inbound_certificate_if_new(&self, key: Field<N>, interval_in_secs: i64) -> bool {
+        self.retain_and_insert_if_new(&self.seen_inbound_certificates, key, interval_in_secs)
     }
 
     /// Inserts a transmission ID into the cache, returning the number of recent events.
     pub fn insert_inbound_transmission(&self, key: TransmissionID<N>, interval_in_secs: i64) -> usize {
-        Self::retain_and_insert(&self.seen_inbound_transmissions, key, interval_in_secs)
+        self.retain_and_insert_key(&self.seen_inbound_transmissions, key, interval_in_secs)
+    }
+
+    /// Inserts a transmission ID into the cache, returning `true` if it was not seen within the interval.
+    pub fn insert_inbound_transmission_if_new(&self, key: TransmissionID<N>, interval_in_secs: i64) -> bool {
+        self.retain_and_insert_if_new(&self.seen_inbound_transmissions, key, interval_in_secs)
+    }
+
+    /// Inserts a new timestamp for the given peer, returning the number of recent invalid certificates.
+    pub fn insert_invalid_certificate(&self, peer_ip: SocketAddr, interval_in_secs: i64) -> usize {
+        self.retain_and_insert_peer(&self.seen_invalid_certificates, peer_ip, interval_in_secs)
+    }
+
+    /// Inserts a new timestamp for the given sender, returning the number of its recent transactions.
+    pub fn insert_inbound_sender(&self, sender: Address<N>, interval_in_secs: i64) -> usize {
+        self.retain_and_insert_peer(&self.seen_inbound_senders, sender, interval_in_secs)
     }
-}
 
-impl<N: Network> Cache<N> {
     /// Inserts a new timestamp for the given peer, returning the number of recent events.
     pub fn insert_outbound_event(&self, peer_ip: SocketAddr, interval_in_secs: i64) -> usize {
-        Self::retain_and_insert(&self.seen_outbound_events, peer_ip, interval_in_secs)
+        self.retain_and_insert_peer(&self.seen_outbound_events, peer_ip, interval_in_secs)
     }
 
     /// Inserts a new timestamp for the given peer, returning the number of recent events.
     pub fn insert_outbound_certificate(&self, peer_ip: SocketAddr, interval_in_secs: i64) -> usize {
-        Self::retain_and_insert(&self.seen_outbound_certificates, peer_ip, interval_in_secs)
+        self.retain_and_insert_peer(&self.seen_outbound_certificates, peer_ip, interval_in_secs)
     }
 
     /// Inserts a new timestamp for the given peer, returning the number of recent events.
     pub fn insert_outbound_transmission(&self, peer_ip: SocketAddr, interval_in_secs: i64) -> usize {
-        Self::retain_and_insert(&self.seen_outbound_transmissions, peer_ip, interval_in_secs)
+        self.retain_and_insert_peer(&self.seen_outbound_transmissions, peer_ip, interval_in_secs)
     }
 }
 
@@ -121,44 +736,262 @@ impl<N: Network> Cache<N> {
     }
 }
 
+impl<N: Network> Cache<N> {
+    /// Records a round-trip time of the given peer, returning its updated average round-trip time.
+    ///
+    /// Note: Once the maximum number of distinct peers is tracked, the round-trip times of new peers are not recorded.
+    pub fn insert_peer_rtt(&self, peer_ip: SocketAddr, rtt: Duration) -> Duration {
+        let rtt_in_ms = u64::try_from(rtt.as_millis()).unwrap_or(u64::MAX);
+        let mut peer_rtts_in_ms = self.peer_rtts_in_ms.write();
+        let average = match peer_rtts_in_ms.get(&peer_ip) {
+            Some(average) => (RTT_SMOOTHING.saturating_mul(*average).saturating_add(rtt_in_ms)) / (RTT_SMOOTHING + 1),
+            None if peer_rtts_in_ms.len() >= self.max_peers() => return rtt,
+            None => rtt_in_ms,
+        };
+        peer_rtts_in_ms.insert(peer_ip, average);
+        Duration::from_millis(average)
+    }
+
+    /// Returns the average round-trip time of the given peer, if one was recorded.
+    pub fn peer_rtt(&self, peer_ip: SocketAddr) -> Option<Duration> {
+        self.peer_rtts_in_ms.read().get(&peer_ip).map(|rtt_in_ms| Duration::from_millis(*rtt_in_ms))
+    }
+
+    /// Removes the round-trip time of the given peer.
+    pub fn remove_peer_rtt(&self, peer_ip: SocketAddr) {
+        self.peer_rtts_in_ms.write().remove(&peer_ip);
+    }
+}
+
+impl<N: Network> Cache<N> {
+    /// Removes the entries of the given peer from the peer maps (e.g. once it disconnects, or is banned).
+    ///
+    /// Note: The invalid certificates of the peer are retained, so that reconnecting does not clear its penalty.
+    pub fn clear_peer(&self, peer_ip: SocketAddr) {
+        Self::remove_key(&self.seen_inbound_events, peer_ip);
+        Self::remove_key(&self.seen_outbound_events, peer_ip);
+        Self::remove_key(&self.seen_outbound_certificates, peer_ip);
+        Self::remove_key(&self.seen_outbound_transmissions, peer_ip);
+        self.remove_peer_rtt(peer_ip);
+    }
+
+    /// Removes the inbound connections of the given peer IP.
+    pub fn clear_peer_connections(&self, peer_ip: IpAddr) {
+        Self::remove_key(&self.seen_inbound_connections, peer_ip);
+    }
+
+    /// Removes the entries older than its window from every map, leaving the counts within the windows
+    /// unchanged. Otherwise, a map is only expired on an insert, so a map that stops receiving inserts retains
+    /// its entries indefinitely.
+    pub fn prune(&self) {
+        // Calculate the cutoff times for the entries to retain, by the window of each map.
+        let now = OffsetDateTime::now_utc().unix_timestamp();
+        let cutoff = |window: fn(&CacheWindows) -> CacheWindow| now.saturating_sub(self.interval_of(window));
+        let connections = cutoff(|windows| windows.connections);
+        let events = cutoff(|windows| windows.events);
+        let requests = cutoff(|windows| windows.requests);
+        Self::prune_map(&self.seen_inbound_connections, connections);
+        Self::prune_map(&self.seen_inbound_connection_groups, connections);
+        Self::prune_map(&self.seen_inbound_events, events);
+        Self::prune_map(&self.seen_inbound_certificates, requests);
+        Self::prune_map(&self.seen_inbound_transmissions, requests);
+        Self::prune_map(&self.seen_invalid_certificates, cutoff(|windows| windows.invalid_certificates));
+        Self::prune_map(&self.seen_inbound_senders, cutoff(|windows| windows.senders));
+        Self::prune_map(&self.seen_outbound_events, events);
+        Self::prune_map(&self.seen_outbound_certificates, requests);
+        Self::prune_map(&self.seen_outbound_transmissions, requests);
+    }
+}
+
 impl<N: Network> Cache<N> {
     /// Insert a new timestamp for the given key, returning the number of recent entries.
+    ///
+    /// Note: The entries are bucketed by whole seconds, so the map holds at most one bucket
+    /// per second of the interval, regardless of the insertion rate.
     fn retain_and_insert<K: Copy + Clone + PartialEq + Eq + Hash>(
-        map: &RwLock<BTreeMap<i64, HashMap<K, u32>>>,
+        map: &RwLock<CacheMap<K, u32>>,
         key: K,
         interval_in_secs: i64,
     ) -> usize {
-        // Fetch the current timestamp.
+        // Fetch the current timestamp, truncated to the second.
         let now = OffsetDateTime::now_utc().unix_timestamp();
 
         // Get the write lock.
         let mut map_write = map.write();
         // Insert the new timestamp and increment the frequency for the key.
-        *map_write.entry(now).or_default().entry(key).or_default() += 1;
+        *map_write.entry(now, key) += 1;
+        // Remove the expired entries.
+        map_write.remove_expired(now.saturating_sub(interval_in_secs));
+        // Sum the frequency of recent requests for `key`.
+        map_write.values().map(|cache_keys| *cache_keys.get(&key).unwrap_or(&0) as usize).sum()
+    }
+
+    /// Insert a new timestamp for the given key in a map of atomic counters, returning the number of recent entries.
+    ///
+    /// Note: If the key is already in the bucket of the current second, and no entries are expired, its counter is
+    /// incremented under the read lock. The write lock is only taken to insert a new bucket or key, or to remove
+    /// the expired entries.
+    fn retain_and_increment<K: Copy + Clone + PartialEq + Eq + Hash>(
+        map: &RwLock<CacheMap<K, AtomicU32>>,
+        key: K,
+        interval_in_secs: i64,
+    ) -> usize {
+        // Fetch the current timestamp, truncated to the second.
+        let now = OffsetDateTime::now_utc().unix_timestamp();
         // Calculate the cutoff time for the entries to retain.
         let cutoff = now.saturating_sub(interval_in_secs);
-        // Obtain the oldest timestamp from the map; it's guaranteed to exist at this point.
-        let (oldest, _) = map_write.first_key_value().unwrap();
-        // Track the number of cache hits of the key.
-        let mut cache_hits = 0;
-        // If the oldest timestamp is above the cutoff value, all the entries can be retained.
-        if cutoff <= *oldest {
-            for cache_keys in map_write.values() {
-                cache_hits += *cache_keys.get(&key).unwrap_or(&0);
-            }
-        } else {
-            // Extract the subtree after interval (i.e. non-expired entries)
-            let retained = map_write.split_off(&cutoff);
-            // Clear all the expired entries.
-            map_write.clear();
-            // Reinsert the entries into map and sum the frequency of recent requests for `key` while looping.
-            for (time, cache_keys) in retained {
-                cache_hits += *cache_keys.get(&key).unwrap_or(&0);
-                map_write.insert(time, cache_keys);
+
+        // Sums the frequency of recent requests for `key`.
+        let sum = |map: &CacheMap<K, AtomicU32>| -> usize {
+            map.values().map(|cache_keys| cache_keys.get(&key).map_or(0, |counter| counter.count() as usize)).sum()
+        };
+
+        // Attempt to increment the counter of the key in the current bucket, under the read lock.
+        {
+            let map_read = map.read();
+            if !map_read.has_expired(cutoff) {
+                if let Some(counter) = map_read.get(&now).and_then(|cache_keys| cache_keys.get(&key)) {
+                    counter.fetch_add(1, Ordering::Relaxed);
+                    return sum(&map_read);
+                }
             }
         }
-        // Return the frequency.
-        cache_hits as usize
+
+        // Get the write lock.
+        let mut map_write = map.write();
+        // Insert the new timestamp and increment the frequency for the key.
+        map_write.entry(now, key).fetch_add(1, Ordering::Relaxed);
+        // Remove the expired entries.
+        map_write.remove_expired(cutoff);
+        sum(&map_write)
+    }
+
+    /// Removes the expired entries from the map, taking the write lock only if there are expired entries.
+    fn prune_map<K: Copy + Eq + Hash, V>(map: &RwLock<CacheMap<K, V>>, cutoff: i64) {
+        if map.read().has_expired(cutoff) {
+            map.write().remove_expired(cutoff);
+        }
+    }
+
+    /// Insert a new timestamp for the given key, returning `true` if it is the first entry within the interval.
+    ///
+    /// Note: The insert and the count happen under the same write lock, so exactly one of the concurrent
+    /// inserts of a new key observes the first entry.
+    fn retain_and_insert_if_new<K: Copy + Clone + PartialEq + Eq + Hash>(
+        &self,
+        map: &RwLock<CacheMap<K, u32>>,
+        key: K,
+        interval_in_secs: i64,
+    ) -> bool {
+        self.retain_and_insert_key(map, key, interval_in_secs) == 1
+    }
+
+    /// Insert a new timestamp for the given key, returning the number of recent entries.
+    /// If the cache is bounded and the map exceeds the maximum number of distinct keys, the keys of
+    /// the oldest timestamps are evicted.
+    fn retain_and_insert_key<K: Copy + Clone + PartialEq + Eq + Hash>(
+        &self,
+        map: &RwLock<CacheMap<K, u32>>,
+        key: K,
+        interval_in_secs: i64,
+    ) -> usize {
+        // Insert the new timestamp for the key.
+        let cache_hits = Self::retain_and_insert(map, key, interval_in_secs);
+        self.observe_cache_hits(cache_hits);
+        // Evict the least-recently-active keys, if the map exceeds the maximum number of distinct keys.
+        if let Some(max_keys) = self.max_keys {
+            Self::evict_least_recently_active(map, key, max_keys);
+        }
+        cache_hits
+    }
+
+    /// Insert a new timestamp for the given peer, returning the number of recent entries.
+    /// If the map exceeds the maximum number of distinct peers, the least-recently-active peers are evicted.
+    fn retain_and_insert_peer<K: Copy + Clone + PartialEq + Eq + Hash>(
+        &self,
+        map: &RwLock<CacheMap<K, u32>>,
+        peer: K,
+        interval_in_secs: i64,
+    ) -> usize {
+        // Insert the new timestamp for the peer.
+        let cache_hits = Self::retain_and_insert(map, peer, interval_in_secs);
+        self.observe_peer_insert(map, peer, cache_hits);
+        cache_hits
+    }
+
+    /// Insert a new timestamp for the given peer in a map of atomic counters, returning the number of recent entries.
+    /// If the map exceeds the maximum number of distinct peers, the least-recently-active peers are evicted.
+    fn retain_and_increment_peer<K: Copy + Clone + PartialEq + Eq + Hash>(
+        &self,
+        map: &RwLock<CacheMap<K, AtomicU32>>,
+        peer: K,
+        interval_in_secs: i64,
+    ) -> usize {
+        // Insert the new timestamp for the peer.
+        let cache_hits = Self::retain_and_increment(map, peer, interval_in_secs);
+        self.observe_peer_insert(map, peer, cache_hits);
+        cache_hits
+    }
+
+    /// Counts the cache hits of an insert for the given peer, and evicts the least-recently-active peers,
+    /// if the map exceeds the maximum number of distinct peers.
+    fn observe_peer_insert<K: Copy + Eq + Hash, V>(&self, map: &RwLock<CacheMap<K, V>>, peer: K, cache_hits: usize) {
+        self.observe_cache_hits(cache_hits);
+        // Evict the least-recently-active peers, if the map exceeds the maximum number of distinct peers.
+        let num_evicted = Self::evict_least_recently_active(map, peer, self.max_peers());
+        if num_evicted > 0 {
+            self.num_peer_evictions.fetch_add(num_evicted as u64, Ordering::Relaxed);
+            #[cfg(feature = "metrics")]
+            metrics::counter(metrics::bft::CACHE_PEER_EVICTIONS, num_evicted as u64);
+        }
+    }
+
+    /// Counts a spam hit, if the given count of an insert is above the spam threshold.
+    fn observe_cache_hits(&self, cache_hits: usize) {
+        if self.spam_threshold().is_some_and(|threshold| cache_hits > threshold) {
+            self.num_spam_hits.fetch_add(1, Ordering::Relaxed);
+            #[cfg(feature = "metrics")]
+            metrics::counter(metrics::bft::CACHE_SPAM_HITS, 1);
+        }
+    }
+
+    /// Evicts the least-recently-active keys from the map, until it contains at most `max_keys` distinct keys.
+    /// Note: The given key is never evicted. Returns the number of evicted keys.
+    fn evict_least_recently_active<K: Copy + Eq + Hash, V>(
+        map: &RwLock<CacheMap<K, V>>,
+        key: K,
+        max_keys: usize,
+    ) -> usize {
+        // If the number of distinct keys is within the limit, return early, without taking the write lock.
+        if map.read().num_keys() <= max_keys {
+            return 0;
+        }
+        map.write().evict_least_recently_active(key, max_keys)
+    }
+
+    /// Removes the given key from the map, along with any empty timestamps.
+    fn remove_key<K: Copy + Eq + Hash, V>(map: &RwLock<CacheMap<K, V>>, key: K) {
+        map.write().remove_key(key);
+    }
+
+    /// Returns the number of entries for the given key, that are within the interval.
+    /// Note: The expired entries are skipped rather than removed, so the map is only read.
+    fn num_recent<K: Eq + Hash, V: Counter>(map: &RwLock<CacheMap<K, V>>, key: K, interval_in_secs: i64) -> usize {
+        // Calculate the cutoff time for the entries to count.
+        let cutoff = OffsetDateTime::now_utc().unix_timestamp().saturating_sub(interval_in_secs);
+        // Sum the frequency of the key in the non-expired entries.
+        map.read().range(cutoff..).map(|(_, keys)| keys.get(&key).map_or(0, |counter| counter.count() as usize)).sum()
+    }
+
+    /// Returns the number of entries and distinct keys in the map.
+    fn map_metrics<K: Copy + Eq + Hash, V>(map: &RwLock<CacheMap<K, V>>) -> CacheMapMetrics {
+        let map_read = map.read();
+        CacheMapMetrics { num_entries: map_read.values().map(|keys| keys.len()).sum(), num_keys: map_read.num_keys() }
+    }
+
+    /// Returns the number of distinct keys in the map.
+    fn num_distinct_keys<K: Copy + Eq + Hash, V>(map: &RwLock<CacheMap<K, V>>) -> usize {
+        map.read().num_keys()
     }
 
     /// Increments the key's counter in the map, returning the updated counter.
@@ -193,7 +1026,7 @@ mod tests {
     use super::*;
     use snarkvm::prelude::Testnet3;
 
-    use std::{net::Ipv4Addr, thread, time::Duration};
+    use std::{net::Ipv4Addr, sync::Arc, thread};
 
     type CurrentNetwork = Testnet3;
 
@@ -225,6 +1058,13 @@ mod tests {
         }
     }
 
+    impl Input for Address<CurrentNetwork> {
+        fn input() -> Self {
+            use snarkvm::console::prelude::{Rng, TestRng};
+            Address::new(TestRng::default().gen())
+        }
+    }
+
     const INTERVAL_IN_SECS: i64 = 3;
 
     macro_rules! test_cache_fields {
@@ -273,22 +1113,505 @@ mod tests {
                         assert_eq!(cache.[<seen_ $name s>].read().len(), 1);
 
                         // Check that the cache still contains the input.
-                        let counts: u32 = cache.[<seen_ $name s>].read().values().map(|hash_map| hash_map.get(&input).unwrap_or(&0)).cloned().sum();
+                        let counts: u32 = cache.[<seen_ $name s>]
+                            .read()
+                            .values()
+                            .map(|hash_map| hash_map.get(&input).map_or(0, |counter| counter.count()))
+                            .sum();
                         assert_eq!(counts, 1);
 
                         // Check that the cache contains the input and 1 timestamp entry.
                         assert_eq!(cache.[<seen_ $name s>].read().len(), 1);
                     }
+
+                    #[test]
+                    fn [<test_record_ $name s>]() {
+                        let cache = Cache::<CurrentNetwork>::default();
+                        let stored = Cache::<CurrentNetwork>::with_interval(INTERVAL_IN_SECS);
+                        assert_eq!(stored.windows(), CacheWindows::uniform(INTERVAL_IN_SECS));
+                        let input = Input::input();
+
+                        // Insert the input into both caches, and ensure the counts match.
+                        for _ in 0..2 {
+                            let num_recent = cache.[<insert_ $name>](input, INTERVAL_IN_SECS);
+                            assert_eq!(stored.[<record_ $name>](input), num_recent);
+                            // Wait for 1s so that the next entry doesn't overwrite the first one.
+                            thread::sleep(Duration::from_secs(1));
+                        }
+
+                        // Wait for the input to expire, and ensure both caches expire it identically.
+                        thread::sleep(Duration::from_secs(INTERVAL_IN_SECS as u64 + 1));
+                        assert_eq!(stored.[<record_ $name>](input), 1);
+                        assert_eq!(cache.[<insert_ $name>](input, INTERVAL_IN_SECS), 1);
+                        assert_eq!(stored.[<seen_ $name s>].read().len(), cache.[<seen_ $name s>].read().len());
+                    }
                 }
             )*
         }
     }
 
+    /// A metrics sink that records the emitted counters.
+    #[cfg(feature = "metrics")]
+    #[derive(Default)]
+    struct RecordingSink {
+        counters: parking_lot::Mutex<Vec<(&'static str, u64)>>,
+    }
+
+    #[cfg(feature = "metrics")]
+    impl metrics::MetricsSink for RecordingSink {
+        fn counter(&self, name: &'static str, _labels: &[metrics::MetricLabel], value: u64) {
+            self.counters.lock().push((name, value));
+        }
+
+        fn gauge(&self, _name: &'static str, _labels: &[metrics::MetricLabel], _value: f64) {}
+
+        fn increment_gauge(&self, _name: &'static str, _labels: &[metrics::MetricLabel], _value: f64) {}
+
+        fn decrement_gauge(&self, _name: &'static str, _labels: &[metrics::MetricLabel], _value: f64) {}
+
+        fn histogram(&self, _name: &'static str, _labels: &[metrics::MetricLabel], _value: f64) {}
+    }
+
+    #[cfg(feature = "metrics")]
+    #[test]
+    fn test_cache_insert_emits_metrics() {
+        // Record the metrics emitted by the node.
+        let sink = std::sync::Arc::new(RecordingSink::default());
+        metrics::with_metrics_sink(sink.clone(), || {
+            let cache = Cache::<CurrentNetwork>::default();
+            cache.set_max_peers(1);
+
+            // Insert two distinct peers, and ensure the eviction is emitted.
+            cache.insert_inbound_connection(IpAddr::V4(Ipv4Addr::new(10, 0, 0, 1)), 60);
+            cache.insert_inbound_connection(IpAddr::V4(Ipv4Addr::new(10, 0, 0, 2)), 60);
+            assert_eq!(cache.num_peer_evictions(), 1);
+        });
+        assert!(sink.counters.lock().contains(&(metrics::bft::CACHE_PEER_EVICTIONS, 1)));
+    }
+
+    #[test]
+    fn test_cache_metrics() {
+        let cache = Cache::<CurrentNetwork>::with_interval(60);
+        let (peer_a, peer_b) = (SocketAddr::from(([10, 0, 0, 1], 4130)), SocketAddr::from(([10, 0, 0, 2], 4130)));
+        let now = OffsetDateTime::now_utc().unix_timestamp();
+        assert_eq!(cache.metrics(), CacheMetrics::default());
+
+        // Insert the entries across the maps, with an entry of an earlier timestamp.
+        cache.insert_inbound_connection(peer_a.ip(), 60);
+        cache.insert_inbound_event(peer_a, 60);
+        cache.insert_inbound_event(peer_a, 60);
+        cache.insert_inbound_event(peer_b, 60);
+        cache.seen_inbound_events.write().insert_bucket(now - 30, [(peer_a, AtomicU32::new(1))].into_iter().collect());
+        cache.insert_inbound_transmission(TransmissionID::input(), 60);
+        cache.insert_outbound_certificate(peer_b, 60);
+
+        // Ensure the snapshot matches, and does not remove the expired entries.
+        let metrics = cache.metrics();
+        assert_eq!(metrics.inbound_connections, CacheMapMetrics { num_entries: 1, num_keys: 1 });
+        assert_eq!(metrics.inbound_events.num_keys, 2);
+        assert!(metrics.inbound_events.num_entries >= 3);
+        assert_eq!(metrics.inbound_certificates, CacheMapMetrics::default());
+        assert_eq!(metrics.inbound_transmissions, CacheMapMetrics { num_entries: 1, num_keys: 1 });
+        assert_eq!(metrics.outbound_events, CacheMapMetrics::default());
+        assert_eq!(metrics.outbound_certificates, CacheMapMetrics { num_entries: 1, num_keys: 1 });
+        assert_eq!(metrics.outbound_transmissions, CacheMapMetrics::default());
+        assert_eq!(metrics.num_spam_hits, 0);
+        assert_eq!(cache.metrics(), metrics);
+        assert_eq!(cache.num_inbound_events(peer_a), 3);
+
+        // Ensure the inserts above the spam threshold are counted.
+        cache.set_spam_threshold(Some(2));
+        assert_eq!(cache.spam_threshold(), Some(2));
+        cache.insert_inbound_event(peer_b, 60);
+        assert_eq!(cache.metrics().num_spam_hits, 0);
+        cache.insert_inbound_event(peer_a, 60);
+        cache.insert_inbound_event(peer_a, 60);
+        assert_eq!(cache.metrics().num_spam_hits, 2);
+    }
+
+    #[test]
+    fn test_max_peers_evicts_least_recently_active() {
+        let cache = Cache::<CurrentNetwork>::default();
+        cache.set_max_peers(3);
+        assert_eq!(cache.max_peers(), 3);
+
+        // Returns a distinct peer IP.
+        let peer = |i: u8| IpAddr::V4(Ipv4Addr::new(10, 0, 0, i));
+        // Returns the distinct peers in the cache.
+        let peers = |cache: &Cache<CurrentNetwork>| {
+            cache.seen_inbound_connections.read().values().flat_map(|keys| keys.keys().copied()).collect::<HashSet<_>>()
+        };
+
+        // Insert the peers in separate seconds, refreshing the first peer last.
+        for i in [1, 2, 3, 1] {
+            cache.insert_inbound_connection(peer(i), 60);
+            thread::sleep(Duration::from_secs(1));
+        }
+        assert_eq!(cache.num_distinct_peers(), 3);
+        assert_eq!(cache.num_peer_evictions(), 0);
+
+        // Insert a new peer, and ensure the least-recently-active peer is evicted.
+        assert_eq!(cache.insert_inbound_connection(peer(4), 60), 1);
+        assert_eq!(peers(&cache), [peer(1), peer(3), peer(4)].into_iter().collect());
+        // Insert another new peer, and ensure the next least-recently-active peer is evicted.
+        assert_eq!(cache.insert_inbound_connection(peer(5), 60), 1);
+        assert_eq!(peers(&cache), [peer(1), peer(4), peer(5)].into_iter().collect());
+        assert_eq!(cache.num_distinct_peers(), 3);
+        assert_eq!(cache.num_peer_evictions(), 2);
+
+        // Ensure the refreshed peer retained its history.
+        assert_eq!(cache.insert_inbound_connection(peer(1), 60), 3);
+        assert_eq!(cache.num_peer_evictions(), 2);
+    }
+
+    #[test]
+    fn test_round_based_window_tracks_round_duration() {
+        let cache = Cache::<CurrentNetwork>::default();
+
+        // Ensure a window in seconds is unchanged, and a window in rounds uses the default round duration.
+        assert_eq!(cache.interval_in_secs(CacheWindow::Secs(5)), 5);
+        assert_eq!(cache.avg_round_duration(), Duration::from_millis(DEFAULT_ROUND_DURATION_IN_MS));
+        let default_interval = (4 * DEFAULT_ROUND_DURATION_IN_MS).div_ceil(1000) as i64;
+        assert_eq!(cache.interval_in_secs(CacheWindow::Rounds(4)), default_interval);
+
+        // Observe rounds of 2 seconds each, and ensure a window of 4 rounds is 8 seconds.
+        for round in 1..=10 {
+            cache.observe_round_at(round, round as i64 * 2_000);
+        }
+        assert_eq!(cache.avg_round_duration(), Duration::from_secs(2));
+        assert_eq!(cache.interval_in_secs(CacheWindow::Rounds(4)), 8);
+
+        // Ensure skipping rounds (e.g. when syncing), and stale rounds, do not skew the average.
+        cache.observe_round_at(100, 20_100);
+        cache.observe_round_at(50, 20_200);
+        assert_eq!(cache.avg_round_duration(), Duration::from_secs(2));
+
+        // Observe the network slowing down to rounds of 10 seconds, and ensure the window widens accordingly.
+        for round in 101..=200 {
+            cache.observe_round_at(round, 20_100 + (round as i64 - 100) * 10_000);
+        }
+        let avg_round_duration = cache.avg_round_duration();
+        assert!(avg_round_duration > Duration::from_millis(9_900) && avg_round_duration <= Duration::from_secs(10));
+        assert_eq!(cache.interval_in_secs(CacheWindow::Rounds(4)), 40);
+
+        // Observe the network speeding up to rounds of 1 second, and ensure the window narrows (to at least 1s).
+        for round in 201..=300 {
+            cache.observe_round_at(round, 1_020_100 + (round as i64 - 200) * 1_000);
+        }
+        assert_eq!(cache.interval_in_secs(CacheWindow::Rounds(4)), 4);
+        assert_eq!(cache.interval_in_secs(CacheWindow::Rounds(0)), 1);
+    }
+
+    #[test]
+    fn test_num_recent_does_not_insert() {
+        let cache = Cache::<CurrentNetwork>::with_interval(60);
+        let peer_ip = SocketAddr::input();
+        let now = OffsetDateTime::now_utc().unix_timestamp();
+
+        // Insert events in the current second, and in an expired bucket.
+        cache.insert_inbound_event(peer_ip, 60);
+        cache.insert_inbound_event(peer_ip, 60);
+        cache.seen_inbound_events.write().insert_bucket(now - 30, [(peer_ip, AtomicU32::new(5))].into_iter().collect());
+        let num_buckets = cache.seen_inbound_events.read().len();
+
+        // Ensure reading the count repeatedly does not change it, nor the cache.
+        for _ in 0..10 {
+            assert_eq!(cache.num_inbound_events(peer_ip), 7);
+        }
+        // Ensure the expired bucket is excluded from the count, but is not removed.
+        assert_eq!(Cache::num_recent(&cache.seen_inbound_events, peer_ip, 10), 2);
+        assert_eq!(cache.seen_inbound_events.read().len(), num_buckets);
+        // Ensure the counts of the other keys are unaffected.
+        assert_eq!(cache.num_inbound_events(SocketAddr::from(([10, 0, 0, 1], 4130))), 0);
+        assert_eq!(cache.num_outbound_events(peer_ip), 0);
+
+        // Ensure the reads were not counted by the next insert, which removes the expired bucket.
+        assert_eq!(cache.insert_inbound_event(peer_ip, 10), 3);
+        assert_eq!(cache.num_inbound_events(peer_ip), 3);
+        assert!(!cache.seen_inbound_events.read().contains_key(&(now - 30)));
+    }
+
+    #[test]
+    fn test_clear_peer() {
+        let cache = Cache::<CurrentNetwork>::with_interval(60);
+        let (peer_a, peer_b) = (SocketAddr::from(([10, 0, 0, 1], 4130)), SocketAddr::from(([10, 0, 0, 2], 4130)));
+
+        // Insert the entries of both peers.
+        for peer_ip in [peer_a, peer_b] {
+            cache.insert_inbound_connection(peer_ip.ip(), 60);
+            cache.insert_inbound_event(peer_ip, 60);
+            cache.insert_invalid_certificate(peer_ip, 60);
+            cache.insert_outbound_event(peer_ip, 60);
+            cache.insert_outbound_certificate(peer_ip, 60);
+            cache.insert_outbound_transmission(peer_ip, 60);
+            cache.insert_peer_rtt(peer_ip, Duration::from_millis(100));
+        }
+        cache.insert_inbound_transmission(TransmissionID::input(), 60);
+        // Ensure the round-trip time of a peer is averaged.
+        assert_eq!(cache.insert_peer_rtt(peer_b, Duration::from_millis(900)), Duration::from_millis(200));
+
+        // Clear the first peer.
+        cache.clear_peer(peer_a);
+        cache.clear_peer_connections(peer_a.ip());
+
+        // Ensure the cleared peer reads back zero, except for its invalid certificates.
+        assert_eq!(Cache::num_recent(&cache.seen_inbound_connections, peer_a.ip(), 60), 0);
+        assert_eq!(Cache::num_recent(&cache.seen_inbound_events, peer_a, 60), 0);
+        for map in [&cache.seen_outbound_events, &cache.seen_outbound_certificates] {
+            assert_eq!(Cache::num_recent(map, peer_a, 60), 0);
+        }
+        assert_eq!(Cache::num_recent(&cache.seen_outbound_transmissions, peer_a, 60), 0);
+        assert_eq!(cache.num_invalid_certificates(peer_a), 1);
+        assert_eq!(cache.peer_rtt(peer_a), None);
+
+        // Ensure the other peer is untouched.
+        assert_eq!(Cache::num_recent(&cache.seen_inbound_connections, peer_b.ip(), 60), 1);
+        assert_eq!(Cache::num_recent(&cache.seen_inbound_events, peer_b, 60), 1);
+        for map in [&cache.seen_outbound_events, &cache.seen_outbound_certificates] {
+            assert_eq!(Cache::num_recent(map, peer_b, 60), 1);
+        }
+        assert_eq!(Cache::num_recent(&cache.seen_outbound_transmissions, peer_b, 60), 1);
+        assert_eq!(cache.num_invalid_certificates(peer_b), 1);
+        assert_eq!(cache.peer_rtt(peer_b), Some(Duration::from_millis(200)));
+        // Ensure the maps that are not per-peer are untouched.
+        assert_eq!(Cache::num_recent(&cache.seen_inbound_transmissions, TransmissionID::input(), 60), 1);
+
+        // Ensure clearing the last peer drops the empty timestamps.
+        cache.clear_peer(peer_b);
+        assert!(cache.seen_inbound_events.read().is_empty());
+        // Ensure the counts of a cleared peer restart.
+        assert_eq!(cache.insert_inbound_event(peer_a, 60), 1);
+    }
+
+    #[test]
+    fn test_inserts_within_a_second_share_a_bucket() {
+        const NUM_INSERTS: usize = 5_000;
+
+        let cache = Cache::<CurrentNetwork>::default();
+        let input = TransmissionID::<CurrentNetwork>::input();
+
+        // Wait for the start of the next second, so that the inserts fall within the same second.
+        let nanos_into_second = OffsetDateTime::now_utc().nanosecond();
+        thread::sleep(Duration::from_nanos(1_000_000_000 - nanos_into_second as u64));
+
+        // Insert the input repeatedly, and ensure the count of every insert matches the number of inserts.
+        for i in 1..=NUM_INSERTS {
+            assert_eq!(cache.insert_inbound_transmission(input, INTERVAL_IN_SECS), i);
+        }
+        // Ensure the inserts accumulated into a single bucket.
+        assert_eq!(cache.seen_inbound_transmissions.read().len(), 1);
+        assert_eq!(cache.seen_inbound_transmissions.read().values().next().unwrap()[&input], NUM_INSERTS as u32);
+    }
+
+    #[test]
+    fn test_prune() {
+        let cache = Cache::<CurrentNetwork>::with_interval(INTERVAL_IN_SECS);
+        let peer_ip = SocketAddr::input();
+
+        // Insert an entry into every map.
+        cache.insert_inbound_connection(peer_ip.ip(), INTERVAL_IN_SECS);
+        cache.insert_inbound_event(peer_ip, INTERVAL_IN_SECS);
+        cache.insert_inbound_certificate(Field::input(), INTERVAL_IN_SECS);
+        cache.insert_inbound_transmission(TransmissionID::input(), INTERVAL_IN_SECS);
+        cache.insert_invalid_certificate(peer_ip, INTERVAL_IN_SECS);
+        cache.insert_inbound_sender(Address::input(), INTERVAL_IN_SECS);
+        cache.insert_outbound_event(peer_ip, INTERVAL_IN_SECS);
+        cache.insert_outbound_certificate(peer_ip, INTERVAL_IN_SECS);
+        cache.insert_outbound_transmission(peer_ip, INTERVAL_IN_SECS);
+
+        // Ensure pruning leaves the counts within the interval unchanged.
+        cache.prune();
+        assert_eq!(cache.num_inbound_events(peer_ip), 1);
+        assert_eq!(cache.num_inbound_certificates(Field::input()), 1);
+        assert_eq!(cache.num_outbound_transmissions(peer_ip), 1);
+
+        // Sleep past the interval, and ensure pruning (without any further inserts) empties every map.
+        thread::sleep(Duration::from_secs(INTERVAL_IN_SECS as u64 + 1));
+        cache.prune();
+        assert!(cache.seen_inbound_connections.read().is_empty());
+        assert!(cache.seen_inbound_events.read().is_empty());
+        assert!(cache.seen_inbound_certificates.read().is_empty());
+        assert!(cache.seen_inbound_transmissions.read().is_empty());
+        assert!(cache.seen_invalid_certificates.read().is_empty());
+        assert!(cache.seen_inbound_senders.read().is_empty());
+        assert!(cache.seen_outbound_events.read().is_empty());
+        assert!(cache.seen_outbound_certificates.read().is_empty());
+        assert!(cache.seen_outbound_transmissions.read().is_empty());
+        assert_eq!(cache.metrics(), CacheMetrics::default());
+    }
+
+    #[test]
+    fn test_windows_retain_each_map() {
+        let windows = CacheWindows { events: CacheWindow::Secs(1), ..CacheWindows::uniform(60) };
+        let cache = Cache::<CurrentNetwork>::with_windows(windows);
+        assert_eq!(cache.windows(), windows);
+        let peer_ip = SocketAddr::input();
+
+        // Record an entry into maps of different windows.
+        assert_eq!(cache.record_inbound_event(peer_ip), 1);
+        assert_eq!(cache.record_inbound_connection(peer_ip.ip()), 1);
+
+        // Sleep past the shorter window, and ensure each map is retained with its own window.
+        thread::sleep(Duration::from_secs(2));
+        assert_eq!(cache.num_inbound_events(peer_ip), 0);
+        assert_eq!(cache.record_inbound_event(peer_ip), 1);
+        assert_eq!(cache.record_inbound_connection(peer_ip.ip()), 2);
+        cache.prune();
+        assert_eq!(cache.seen_inbound_events.read().len(), 1);
+        assert_eq!(cache.num_inbound_connections(peer_ip.ip()), 2);
+
+        // Ensure the windows must be nonzero.
+        assert!(cache.set_windows(CacheWindows { requests: CacheWindow::Rounds(0), ..windows }).is_err());
+        assert_eq!(cache.windows(), windows);
+        let windows = CacheWindows { requests: CacheWindow::Rounds(4), ..windows };
+        cache.set_windows(windows).unwrap();
+        assert_eq!(cache.windows(), windows);
+    }
+
+    #[test]
+    fn test_concurrent_inbound_events_are_not_lost() {
+        const NUM_THREADS: usize = 16;
+        const NUM_INSERTS_PER_THREAD: usize = 500;
+        const NUM_INSERTS: usize = NUM_THREADS * NUM_INSERTS_PER_THREAD;
+
+        let cache = Arc::new(Cache::<CurrentNetwork>::with_interval(INTERVAL_IN_SECS));
+        let peer_ip = SocketAddr::input();
+
+        // Wait for the start of the next second, so that the inserts fall within the same second.
+        let nanos_into_second = OffsetDateTime::now_utc().nanosecond();
+        thread::sleep(Duration::from_nanos(1_000_000_000 - nanos_into_second as u64));
+
+        // Insert the same peer concurrently.
+        let handles: Vec<_> = (0..NUM_THREADS)
+            .map(|_| {
+                let cache = cache.clone();
+                thread::spawn(move || {
+                    (0..NUM_INSERTS_PER_THREAD)
+                        .map(|_| cache.insert_inbound_event(peer_ip, INTERVAL_IN_SECS))
+                        .collect::<Vec<_>>()
+                })
+            })
+            .collect();
+        let counts: Vec<_> = handles.into_iter().flat_map(|handle| handle.join().unwrap()).collect();
+
+        // Ensure every insert counted itself, and no increments were lost.
+        assert!(counts.iter().all(|count| (1..=NUM_INSERTS).contains(count)));
+        assert_eq!(counts.iter().max(), Some(&NUM_INSERTS));
+        assert_eq!(cache.num_inbound_events(peer_ip), NUM_INSERTS);
+        assert_eq!(cache.seen_inbound_events.read().len(), 1);
+    }
+
+    #[test]
+    fn test_insert_if_new() {
+        const NUM_THREADS: usize = 8;
+
+        let cache = Arc::new(Cache::<CurrentNetwork>::with_interval(INTERVAL_IN_SECS));
+        let certificate_id = Field::<CurrentNetwork>::input();
+        let transmission_id = TransmissionID::<CurrentNetwork>::input();
+
+        // Insert the same keys concurrently, and ensure exactly one insert of each key is new.
+        let handles: Vec<_> = (0..NUM_THREADS)
+            .map(|_| {
+                let cache = cache.clone();
+                thread::spawn(move || {
+                    (
+                        cache.insert_inbound_certificate_if_new(certificate_id, INTERVAL_IN_SECS),
+                        cache.insert_inbound_transmission_if_new(transmission_id, INTERVAL_IN_SECS),
+                    )
+                })
+            })
+            .collect();
+        let results: Vec<_> = handles.into_iter().map(|handle| handle.join().unwrap()).collect();
+        assert_eq!(results.iter().filter(|(is_new, _)| *is_new).count(), 1);
+        assert_eq!(results.iter().filter(|(_, is_new)| *is_new).count(), 1);
+
+        // Ensure the subsequent inserts within the interval are not new.
+        assert!(!cache.insert_inbound_certificate_if_new(certificate_id, INTERVAL_IN_SECS));
+        assert!(!cache.insert_inbound_transmission_if_new(transmission_id, INTERVAL_IN_SECS));
+        assert_eq!(cache.num_inbound_certificates(certificate_id), NUM_THREADS + 1);
+
+        // Expire the entries, and ensure the next insert is new again.
+        let now = OffsetDateTime::now_utc().unix_timestamp();
+        *cache.seen_inbound_certificates.write() = Default::default();
+        cache.seen_inbound_certificates.write().insert_bucket(now - 30, [(certificate_id, 1)].into_iter().collect());
+        assert!(cache.insert_inbound_certificate_if_new(certificate_id, 10));
+        assert!(!cache.insert_inbound_certificate_if_new(certificate_id, 10));
+    }
+
+    #[test]
+    fn test_cache_map_indexes_last_active() {
+        let mut map = CacheMap::<u8, u32>::default();
+        // Insert the keys, refreshing the first key last.
+        *map.entry(10, 1) += 1;
+        *map.entry(11, 2) += 1;
+        *map.entry(12, 3) += 1;
+        *map.entry(13, 1) += 1;
+        // Ensure an earlier entry does not refresh a key.
+        map.insert_bucket(5, [(3, 1)].into_iter().collect());
+        assert_eq!(map.num_keys(), 3);
+        assert_eq!(map.len(), 5);
+
+        // Ensure the least-recently-active key is evicted first, and the given key is never evicted.
+        assert_eq!(map.evict_least_recently_active(2, 2), 1);
+        assert_eq!(map.last_active.keys().copied().collect::<HashSet<_>>(), [1, 2].into_iter().collect());
+        assert!(!map.contains_key(&5) && !map.contains_key(&12));
+
+        // Ensure the expired keys are removed from the index, while the refreshed keys are retained.
+        map.remove_expired(12);
+        assert_eq!(map.num_keys(), 1);
+        assert_eq!(map.last_active.get(&1), Some(&13));
+        assert_eq!(map.by_last_active.len(), 1);
+        assert!(map.remove_key(1));
+        assert!(map.is_empty() && map.by_last_active.is_empty());
+    }
+
+    #[test]
+    fn test_max_keys_evicts_oldest_keys() {
+        const MAX_KEYS: usize = 10;
+
+        let cache = Cache::<CurrentNetwork>::with_limits(60, MAX_KEYS);
+        assert_eq!(cache.max_keys(), Some(MAX_KEYS));
+        assert_eq!(Cache::<CurrentNetwork>::default().max_keys(), None);
+        let transmission_id = |i: u64| TransmissionID::Transaction(Field::<CurrentNetwork>::from_u64(i).into());
+        let num_distinct = || Cache::<CurrentNetwork>::num_distinct_keys(&cache.seen_inbound_transmissions);
+
+        // Insert the keys of an older timestamp.
+        let now = OffsetDateTime::now_utc().unix_timestamp();
+        let old_keys: HashMap<_, _> = (0..5).map(|i| (transmission_id(i), 1)).collect();
+        cache.seen_inbound_transmissions.write().insert_bucket(now - 30, old_keys.clone());
+
+        // Insert more distinct keys than the bound, alongside a key that is inserted every time.
+        let hot_key = transmission_id(1_000);
+        for i in 1..=(2 * MAX_KEYS as u64) {
+            let key = transmission_id(100 + i);
+            // Ensure the inserted keys are never evicted, and their counts are accurate.
+            assert_eq!(cache.insert_inbound_transmission(key, 60), 1);
+            assert_eq!(cache.num_inbound_transmissions(key), 1);
+            assert_eq!(cache.insert_inbound_transmission(hot_key, 60), i as usize);
+            // Ensure the map stays within the bound.
+            assert!(num_distinct() <= MAX_KEYS);
+        }
+        assert_eq!(num_distinct(), MAX_KEYS);
+        // Ensure the keys of the older timestamp were evicted first.
+        assert!(old_keys.keys().all(|key| cache.num_inbound_transmissions(*key) == 0));
+        assert!(!cache.seen_inbound_transmissions.read().contains_key(&(now - 30)));
+        // Ensure the most recently-inserted key survived, with its accurate count.
+        assert_eq!(cache.num_inbound_transmissions(hot_key), 2 * MAX_KEYS);
+
+        // Ensure the certificate IDs are bounded as well.
+        for i in 0..(2 * MAX_KEYS as u64) {
+            cache.insert_inbound_certificate(Field::from_u64(i), 60);
+        }
+        assert!(Cache::<CurrentNetwork>::num_distinct_keys(&cache.seen_inbound_certificates) <= MAX_KEYS);
+    }
+
     test_cache_fields! {
        inbound_connection,
        inbound_event,
        inbound_certificate,
        inbound_transmission,
+       invalid_certificate,
+       inbound_sender,
        outbound_event,
        outbound_certificate,
        outbound_transmission
@@ -12,13 +12,18 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use crate::events::{
-    BatchPropose,
+use crate::{
+    events::{
+        BatchPropose,
     BatchSignature,
     CertificateRequest,
     CertificateResponse,
-    TransmissionRequest,
-    TransmissionResponse,
+    DagSnapshotRequest,
+    DagSnapshotResponse,
+        TransmissionRequest,
+        TransmissionResponse,
+    },
+    helpers::AdmissionReceipt,
 };
 use snarkos_node_sync::locators::BlockLocators;
 use snarkvm::{
@@ -37,6 +42,9 @@ use tokio::sync::{mpsc, oneshot};
 
 const MAX_CHANNEL_SIZE: usize = 8192;
 
+/// The callback of an unconfirmed transaction, with the admission receipt, if receipts are enabled.
+pub type TransactionCallback<N> = oneshot::Sender<Result<Option<AdmissionReceipt<N>>>>;
+
 #[derive(Debug)]
 pub struct ConsensusSender<N: Network> {
     pub tx_consensus_subdag:
@@ -154,7 +162,7 @@ pub struct PrimarySender<N: Network> {
         mpsc::Sender<(SocketAddr, Data<BatchCertificate<N>>, IndexMap<Field<N>, Data<BatchCertificate<N>>>)>,
     pub tx_unconfirmed_solution:
         mpsc::Sender<(PuzzleCommitment<N>, Data<ProverSolution<N>>, oneshot::Sender<Result<()>>)>,
-    pub tx_unconfirmed_transaction: mpsc::Sender<(N::TransactionID, Data<Transaction<N>>, oneshot::Sender<Result<()>>)>,
+    pub tx_unconfirmed_transaction: mpsc::Sender<(N::TransactionID, Data<Transaction<N>>, TransactionCallback<N>)>,
 }
 
 impl<N: Network> PrimarySender<N> {
@@ -172,12 +180,12 @@ impl<N: Network> PrimarySender<N> {
         callback_receiver.await?
     }
 
-    /// Sends the unconfirmed transaction to the primary.
+    /// Sends the unconfirmed transaction to the primary, returning the admission receipt, if receipts are enabled.
     pub async fn send_unconfirmed_transaction(
         &self,
         transaction_id: N::TransactionID,
         transaction: Data<Transaction<N>>,
-    ) -> Result<()> {
+    ) -> Result<Option<AdmissionReceipt<N>>> {
         // Initialize a callback sender and receiver.
         let (callback_sender, callback_receiver) = oneshot::channel();
         // Send the unconfirmed transaction to the primary.
@@ -196,8 +204,7 @@ pub struct PrimaryReceiver<N: Network> {
         mpsc::Receiver<(SocketAddr, Data<BatchCertificate<N>>, IndexMap<Field<N>, Data<BatchCertificate<N>>>)>,
     pub rx_unconfirmed_solution:
         mpsc::Receiver<(PuzzleCommitment<N>, Data<ProverSolution<N>>, oneshot::Sender<Result<()>>)>,
-    pub rx_unconfirmed_transaction:
-        mpsc::Receiver<(N::TransactionID, Data<Transaction<N>>, oneshot::Sender<Result<()>>)>,
+    pub rx_unconfirmed_transaction: mpsc::Receiver<(N::TransactionID, Data<Transaction<N>>, TransactionCallback<N>)>,
 }
 
 /// Initializes the primary channels.
@@ -262,6 +269,8 @@ pub struct SyncSender<N: Network> {
     pub tx_block_sync_update_peer_locators: mpsc::Sender<(SocketAddr, BlockLocators<N>, oneshot::Sender<Result<()>>)>,
     pub tx_certificate_request: mpsc::Sender<(SocketAddr, CertificateRequest<N>)>,
     pub tx_certificate_response: mpsc::Sender<(SocketAddr, CertificateResponse<N>)>,
+    pub tx_dag_snapshot_request: mpsc::Sender<(SocketAddr, DagSnapshotRequest)>,
+    pub tx_dag_snapshot_response: mpsc::Sender<(SocketAddr, DagSnapshotResponse<N>)>,
 }
 
 impl<N: Network> SyncSender<N> {
@@ -294,6 +303,8 @@ pub struct SyncReceiver<N: Network> {
     pub rx_block_sync_update_peer_locators: mpsc::Receiver<(SocketAddr, BlockLocators<N>, oneshot::Sender<Result<()>>)>,
     pub rx_certificate_request: mpsc::Receiver<(SocketAddr, CertificateRequest<N>)>,
     pub rx_certificate_response: mpsc::Receiver<(SocketAddr, CertificateResponse<N>)>,
+    pub rx_dag_snapshot_request: mpsc::Receiver<(SocketAddr, DagSnapshotRequest)>,
+    pub rx_dag_snapshot_response: mpsc::Receiver<(SocketAddr, DagSnapshotResponse<N>)>,
 }
 
 /// Initializes the sync channels.
@@ -304,6 +315,8 @@ pub fn init_sync_channels<N: Network>() -> (SyncSender<N>, SyncReceiver<N>) {
     let (tx_block_sync_update_peer_locators, rx_block_sync_update_peer_locators) = mpsc::channel(MAX_CHANNEL_SIZE);
     let (tx_certificate_request, rx_certificate_request) = mpsc::channel(MAX_CHANNEL_SIZE);
     let (tx_certificate_response, rx_certificate_response) = mpsc::channel(MAX_CHANNEL_SIZE);
+    let (tx_dag_snapshot_request, rx_dag_snapshot_request) = mpsc::channel(MAX_CHANNEL_SIZE);
+    let (tx_dag_snapshot_response, rx_dag_snapshot_response) = mpsc::channel(MAX_CHANNEL_SIZE);
 
     let sender = SyncSender {
         tx_block_sync_advance_with_sync_blocks,
@@ -311,6 +324,8 @@ pub fn init_sync_channels<N: Network>() -> (SyncSender<N>, SyncReceiver<N>) {
         tx_block_sync_update_peer_locators,
         tx_certificate_request,
         tx_certificate_response,
+        tx_dag_snapshot_request,
+        tx_dag_snapshot_response,
     };
     let receiver = SyncReceiver {
         rx_block_sync_advance_with_sync_blocks,
@@ -318,6 +333,8 @@ pub fn init_sync_channels<N: Network>() -> (SyncSender<N>, SyncReceiver<N>) {
         rx_block_sync_update_peer_locators,
         rx_certificate_request,
         rx_certificate_response,
+        rx_dag_snapshot_request,
+        rx_dag_snapshot_response,
     };
 
     (sender, receiver)
@@ -0,0 +1,275 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkOS library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use snarkvm::{
+    ledger::committee::Committee,
+    prelude::{ensure, Network, Result},
+};
+
+use parking_lot::Mutex;
+
+/// The handling of the transmissions of a proposal that is abandoned at an epoch transition
+/// (i.e. a change of the committee).
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub enum EpochTransitionPolicy {
+    /// The transmissions are carried over into the workers, to be proposed under the new committee.
+    #[default]
+    CarryOver,
+    /// The transmissions are re-validated against the ledger, and only the valid ones are carried over.
+    Revalidate,
+    /// The transmissions are dropped.
+    Drop,
+}
+
+/// Returns `true` if the given committees are of different epochs, i.e. their sets of members differ.
+///
+/// Note: The starting round and the stakes are ignored, so that a committee which is carried over with the same
+/// members is the same epoch, even as its stakes change with every bond, unbond, and staking reward.
+pub fn is_epoch_transition<N: Network>(previous: &Committee<N>, next: &Committee<N>) -> bool {
+    previous.members().len() != next.members().len()
+        || previous.members().keys().any(|address| !next.is_committee_member(*address))
+}
+
+/// Returns the fraction of stake that changes in the transition between the given committees, i.e. the larger of
+/// the fraction of the previous stake that leaves, and the fraction of the next stake that joins.
+pub fn committee_transition_fraction<N: Network>(previous: &Committee<N>, next: &Committee<N>) -> f64 {
+    // Compute the stake that is retained across the transition.
+    let retained_stake: u64 =
+        previous.members().iter().map(|(address, (stake, _))| (*stake).min(next.get_stake(*address))).sum();
+    // Returns the fraction of the given total stake that is not retained.
+    let changed_fraction = |total_stake: u64| match total_stake {
+        0 => 0.0,
+        total_stake => total_stake.saturating_sub(retained_stake) as f64 / total_stake as f64,
+    };
+    changed_fraction(previous.total_stake()).max(changed_fraction(next.total_stake()))
+}
+
+/// Ensures the transition between the given committees maintains quorum intersection, i.e. at most the given
+/// fraction of stake changes in a single step, so that every quorum of the next committee retains enough
+/// of the stake of the previous committee.
+pub fn ensure_quorum_intersection<N: Network>(
+    previous: &Committee<N>,
+    next: &Committee<N>,
+    max_fraction: f64,
+) -> Result<()> {
+    let fraction = committee_transition_fraction(previous, next);
+    ensure!(
+        fraction <= max_fraction,
+        "The committee transition in round {} violates quorum intersection - {:.1}% of the stake changes in one step, \
+         above the maximum of {:.1}%",
+        next.starting_round(),
+        100.0 * fraction,
+        100.0 * max_fraction
+    );
+    Ok(())
+}
+
+/// Tracks the current committee epoch, which is numbered by the starting round of its first committee.
+///
+/// Note: An epoch that is joined midway (e.g. on restart) is numbered by the starting round of the first committee
+/// that is observed, which may be later than the starting round of the epoch.
+pub struct EpochTracker<N: Network> {
+    /// The latest observed committee, and the number of its epoch.
+    current: Mutex<Option<(Committee<N>, u64)>>,
+}
+
+impl<N: Network> Default for EpochTracker<N> {
+    /// Initializes a new epoch tracker, which has not observed a committee yet.
+    fn default() -> Self {
+        Self { current: Default::default() }
+    }
+}
+
+impl<N: Network> EpochTracker<N> {
+    /// Returns the number of the current epoch, if a committee was observed.
+    pub fn epoch(&self) -> Option<u64> {
+        self.current.lock().as_ref().map(|(_, epoch)| *epoch)
+    }
+
+    /// Returns the latest observed committee, if any.
+    pub fn committee(&self) -> Option<Committee<N>> {
+        self.current.lock().as_ref().map(|(committee, _)| committee.clone())
+    }
+
+    /// Observes the committee of the current round, and returns `true` if it starts a new epoch.
+    /// On an epoch transition, the epoch-segmented metrics are labeled with the new epoch from now on.
+    pub fn observe(&self, committee: &Committee<N>) -> bool {
+        let mut current = self.current.lock();
+        let epoch = match current.as_ref() {
+            Some((previous, epoch)) if !is_epoch_transition(previous, committee) => *epoch,
+            _ => committee.starting_round(),
+        };
+        let is_transition = current.as_ref().map(|(_, previous_epoch)| *previous_epoch) != Some(epoch);
+        *current = Some((committee.clone(), epoch));
+        // Note: The label is updated while the lock is held, so concurrent observers cannot reorder the transitions.
+        #[cfg(feature = "metrics")]
+        if is_transition {
+            metrics::set_metrics_epoch(Some(epoch));
+        }
+        is_transition
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use snarkvm::{
+        ledger::committee::MIN_VALIDATOR_STAKE,
+        prelude::{Address, PrivateKey, TestRng},
+    };
+
+    use indexmap::IndexMap;
+
+    type CurrentNetwork = snarkvm::prelude::Testnet3;
+
+    #[test]
+    fn test_is_epoch_transition() {
+        let rng = &mut TestRng::default();
+
+        // Sample the members of a committee.
+        let mut members = IndexMap::new();
+        for _ in 0..4 {
+            let address = Address::try_from(PrivateKey::<CurrentNetwork>::new(rng).unwrap()).unwrap();
+            members.insert(address, (MIN_VALIDATOR_STAKE, true));
+        }
+        let committee = Committee::<CurrentNetwork>::new(1, members.clone()).unwrap();
+
+        // Ensure the same members in a later round are not an epoch transition.
+        assert!(!is_epoch_transition(&committee, &committee));
+        assert!(!is_epoch_transition(&committee, &Committee::new(5, members.clone()).unwrap()));
+
+        // Ensure a change of stake is not an epoch transition.
+        let address = *members.first().unwrap().0;
+        members.insert(address, (2 * MIN_VALIDATOR_STAKE, true));
+        assert!(!is_epoch_transition(&committee, &Committee::new(5, members.clone()).unwrap()));
+
+        // Ensure a change of members is an epoch transition, even if the number of members is unchanged.
+        let address = Address::try_from(PrivateKey::<CurrentNetwork>::new(rng).unwrap()).unwrap();
+        members.insert(address, (MIN_VALIDATOR_STAKE, true));
+        assert!(is_epoch_transition(&committee, &Committee::new(5, members.clone()).unwrap()));
+        members.shift_remove_index(0);
+        assert!(is_epoch_transition(&committee, &Committee::new(5, members).unwrap()));
+    }
+
+    #[test]
+    fn test_quorum_intersection() {
+        let rng = &mut TestRng::default();
+
+        // Sample the members of a committee.
+        let mut members = IndexMap::new();
+        for _ in 0..4 {
+            let address = Address::try_from(PrivateKey::<CurrentNetwork>::new(rng).unwrap()).unwrap();
+            members.insert(address, (MIN_VALIDATOR_STAKE, true));
+        }
+        let committee = Committee::<CurrentNetwork>::new(1, members.clone()).unwrap();
+        assert_eq!(committee_transition_fraction(&committee, &committee), 0.0);
+
+        // Ensure a transition that adds a member is within the maximum change of a third of the stake.
+        let mut next_members = members.clone();
+        let address = Address::try_from(PrivateKey::<CurrentNetwork>::new(rng).unwrap()).unwrap();
+        next_members.insert(address, (MIN_VALIDATOR_STAKE, true));
+        let next = Committee::new(5, next_members).unwrap();
+        assert_eq!(committee_transition_fraction(&committee, &next), 0.2);
+        assert!(ensure_quorum_intersection(&committee, &next, 1.0 / 3.0).is_ok());
+
+        // Ensure a transition that swaps half of the members is refused, with the reason.
+        let mut next_members = members;
+        for _ in 0..2 {
+            next_members.shift_remove_index(0);
+            let address = Address::try_from(PrivateKey::<CurrentNetwork>::new(rng).unwrap()).unwrap();
+            next_members.insert(address, (MIN_VALIDATOR_STAKE, true));
+        }
+        let next = Committee::new(5, next_members).unwrap();
+        assert_eq!(committee_transition_fraction(&committee, &next), 0.5);
+        let error = ensure_quorum_intersection(&committee, &next, 1.0 / 3.0).unwrap_err();
+        assert!(error.to_string().contains("violates quorum intersection - 50.0% of the stake changes"), "{error}");
+    }
+
+    /// A metrics sink that records the labels of the emitted gauges.
+    #[cfg(feature = "metrics")]
+    #[derive(Default)]
+    struct RecordingSink {
+        gauges: parking_lot::Mutex<Vec<(&'static str, Vec<metrics::MetricLabel>)>>,
+    }
+
+    #[cfg(feature = "metrics")]
+    impl metrics::MetricsSink for RecordingSink {
+        fn counter(&self, _name: &'static str, _labels: &[metrics::MetricLabel], _value: u64) {}
+
+        fn gauge(&self, name: &'static str, labels: &[metrics::MetricLabel], _value: f64) {
+            self.gauges.lock().push((name, labels.to_vec()));
+        }
+
+        fn increment_gauge(&self, _name: &'static str, _labels: &[metrics::MetricLabel], _value: f64) {}
+
+        fn decrement_gauge(&self, _name: &'static str, _labels: &[metrics::MetricLabel], _value: f64) {}
+
+        fn histogram(&self, _name: &'static str, _labels: &[metrics::MetricLabel], _value: f64) {}
+    }
+
+    #[cfg(feature = "metrics")]
+    #[test]
+    fn test_metrics_carry_the_epoch_label() {
+        let rng = &mut TestRng::default();
+
+        // Sample the members of a committee.
+        let mut members = IndexMap::new();
+        for _ in 0..4 {
+            let address = Address::try_from(PrivateKey::<CurrentNetwork>::new(rng).unwrap()).unwrap();
+            members.insert(address, (MIN_VALIDATOR_STAKE, true));
+        }
+
+        let tracker = EpochTracker::<CurrentNetwork>::default();
+        let sink = std::sync::Arc::new(RecordingSink::default());
+        metrics::with_metrics_sink(sink.clone(), || {
+            // Observes the given committee, and emits an epoch-segmented and an unsegmented metric.
+            let observe_and_emit = |committee: Committee<CurrentNetwork>| {
+                let is_transition = tracker.observe(&committee);
+                metrics::gauge(metrics::consensus::LAST_COMMITTED_ROUND, committee.starting_round() as f64);
+                metrics::gauge(metrics::bft::CACHE_PEERS, 0.0);
+                is_transition
+            };
+            assert!(observe_and_emit(Committee::new(1, members.clone()).unwrap()));
+            // Ensure the same members in a later round are the same epoch.
+            assert!(!observe_and_emit(Committee::new(5, members.clone()).unwrap()));
+            // Ensure a change of stake is the same epoch.
+            let address = *members.first().unwrap().0;
+            members.insert(address, (2 * MIN_VALIDATOR_STAKE, true));
+            assert!(!observe_and_emit(Committee::new(7, members.clone()).unwrap()));
+            // Ensure a change of members starts a new epoch.
+            let address = Address::try_from(PrivateKey::<CurrentNetwork>::new(rng).unwrap()).unwrap();
+            members.insert(address, (MIN_VALIDATOR_STAKE, true));
+            assert!(observe_and_emit(Committee::new(9, members).unwrap()));
+            metrics::set_metrics_epoch(None);
+        });
+        assert_eq!(tracker.epoch(), Some(9));
+
+        // Ensure the epoch-segmented metric carries the epoch at the time it was emitted, and the other does not.
+        let epoch_labels = |epoch: &str| vec![(metrics::EPOCH_LABEL, epoch.to_string())];
+        let gauges = sink.gauges.lock().clone();
+        assert_eq!(
+            gauges,
+            vec![
+                (metrics::consensus::LAST_COMMITTED_ROUND, epoch_labels("1")),
+                (metrics::bft::CACHE_PEERS, vec![]),
+                (metrics::consensus::LAST_COMMITTED_ROUND, epoch_labels("1")),
+                (metrics::bft::CACHE_PEERS, vec![]),
+                (metrics::consensus::LAST_COMMITTED_ROUND, epoch_labels("1")),
+                (metrics::bft::CACHE_PEERS, vec![]),
+                (metrics::consensus::LAST_COMMITTED_ROUND, epoch_labels("9")),
+                (metrics::bft::CACHE_PEERS, vec![]),
+            ]
+        );
+    }
+}
@@ -0,0 +1,125 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkOS library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use snarkvm::{
+    console::{prelude::*, types::Address},
+    ledger::narwhal::BatchHeader,
+};
+
+use parking_lot::RwLock;
+use std::collections::HashMap;
+
+/// The evidence that a validator equivocated, consisting of two conflicting signed batch headers for the same round.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct EquivocationEvidence<N: Network> {
+    /// The first signed batch header.
+    first: BatchHeader<N>,
+    /// The second (conflicting) signed batch header.
+    second: BatchHeader<N>,
+}
+
+impl<N: Network> EquivocationEvidence<N> {
+    /// Initializes new equivocation evidence from the two given batch headers, ensuring it is verifiable.
+    pub fn new(first: BatchHeader<N>, second: BatchHeader<N>) -> Result<Self> {
+        let evidence = Self { first, second };
+        evidence.verify()?;
+        Ok(evidence)
+    }
+
+    /// Ensures the two batch headers are signed by the same author, for the same round, with different batch IDs.
+    pub fn verify(&self) -> Result<()> {
+        // Ensure the batch headers are from the same author.
+        ensure!(self.first.author() == self.second.author(), "Equivocation evidence is from different authors");
+        // Ensure the batch headers are for the same round.
+        ensure!(self.first.round() == self.second.round(), "Equivocation evidence is for different rounds");
+        // Ensure the batch headers conflict.
+        ensure!(self.first.batch_id() != self.second.batch_id(), "Equivocation evidence is for the same batch");
+        // Ensure both batch headers are signed by the author.
+        for header in [&self.first, &self.second] {
+            ensure!(
+                header.signature().verify(&header.author(), &[header.batch_id()]),
+                "Equivocation evidence contains an invalid signature for batch {}",
+                header.batch_id()
+            );
+        }
+        Ok(())
+    }
+
+    /// Returns the address of the equivocating validator.
+    pub fn author(&self) -> Address<N> {
+        self.first.author()
+    }
+
+    /// Returns the round of the equivocation.
+    pub fn round(&self) -> u64 {
+        self.first.round()
+    }
+
+    /// Returns the first signed batch header.
+    pub const fn first(&self) -> &BatchHeader<N> {
+        &self.first
+    }
+
+    /// Returns the second (conflicting) signed batch header.
+    pub const fn second(&self) -> &BatchHeader<N> {
+        &self.second
+    }
+}
+
+/// A tracker of the latest batch header from each validator, used to detect and deduplicate equivocations.
+#[derive(Debug)]
+pub struct EquivocationTracker<N: Network> {
+    /// The latest signed batch header from each validator.
+    headers: RwLock<HashMap<Address<N>, BatchHeader<N>>>,
+    /// The latest round in which each validator was reported for equivocating.
+    reported: RwLock<HashMap<Address<N>, u64>>,
+}
+
+impl<N: Network> Default for EquivocationTracker<N> {
+    /// Initializes a new equivocation tracker.
+    fn default() -> Self {
+        Self { headers: Default::default(), reported: Default::default() }
+    }
+}
+
+impl<N: Network> EquivocationTracker<N> {
+    /// Records the given batch header as the latest from its author, if it is not older than the current one.
+    pub fn insert_header(&self, header: BatchHeader<N>) {
+        let mut headers = self.headers.write();
+        match headers.get(&header.author()) {
+            Some(existing) if existing.round() > header.round() => (),
+            _ => {
+                headers.insert(header.author(), header);
+            }
+        }
+    }
+
+    /// Returns the equivocation evidence for the given batch header, if it conflicts with the recorded
+    /// batch header from the same author, and the equivocation has not already been reported.
+    pub fn detect(&self, header: &BatchHeader<N>) -> Option<EquivocationEvidence<N>> {
+        let author = header.author();
+        // Retrieve the recorded batch header from the author, for the same round.
+        let first = self.headers.read().get(&author).filter(|first| first.round() == header.round()).cloned()?;
+        // Skip the equivocation if it has already been reported.
+        let mut reported = self.reported.write();
+        if reported.get(&author).map_or(false, |round| *round >= header.round()) {
+            return None;
+        }
+        // Construct the evidence, skipping it if it is not verifiable.
+        let evidence = EquivocationEvidence::new(first, header.clone()).ok()?;
+        // Mark the equivocation as reported.
+        reported.insert(author, header.round());
+        Some(evidence)
+    }
+}
@@ -0,0 +1,159 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkOS library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use snarkvm::prelude::{ensure, Result};
+
+/// The handling of a batch proposal that references previous certificates at or below the GC round,
+/// which can no longer be fetched or validated.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub enum StaleProposalPolicy {
+    /// The batch proposal is processed, and is left unsigned once its references fail to validate.
+    #[default]
+    Process,
+    /// The batch proposal is refused, without fetching its references.
+    Refuse,
+    /// The batch proposal is refused, and the peer is sent the block locators, to direct it to sync.
+    RefuseAndSync,
+}
+
+/// The policy for dynamically retaining rounds beyond the maximum GC rounds, based on memory pressure.
+///
+/// Note: The maximum GC rounds is the window of the commit rule, which every validator must agree on,
+/// so it is a floor that is never tightened. The policy only retains extra rounds (e.g. to serve
+/// lagging peers) while there is memory headroom, and releases them under memory pressure.
+#[derive(Copy, Clone, Debug)]
+pub struct GCMemoryPolicy {
+    /// The memory usage (in bytes) above which the extra rounds are released.
+    high_watermark: u64,
+    /// The memory usage (in bytes) below which extra rounds are retained.
+    low_watermark: u64,
+    /// The maximum number of rounds to keep in storage while there is memory headroom.
+    max_retained_rounds: u64,
+    /// The function used to measure the current memory usage (in bytes).
+    memory_usage: fn() -> Option<u64>,
+}
+
+impl GCMemoryPolicy {
+    /// Initializes a new GC memory policy, measuring the resident memory of the current process.
+    pub fn new(low_watermark: u64, high_watermark: u64, max_retained_rounds: u64) -> Result<Self> {
+        Self::new_with_probe(low_watermark, high_watermark, max_retained_rounds, resident_memory_in_bytes)
+    }
+
+    /// Initializes a new GC memory policy, with the given function to measure memory usage.
+    pub fn new_with_probe(
+        low_watermark: u64,
+        high_watermark: u64,
+        max_retained_rounds: u64,
+        memory_usage: fn() -> Option<u64>,
+    ) -> Result<Self> {
+        // Ensure the low watermark is below the high watermark.
+        ensure!(low_watermark < high_watermark, "The low watermark must be less than the high watermark");
+        // Ensure the maximum retained rounds is nonzero.
+        ensure!(max_retained_rounds > 0, "The maximum retained rounds must be nonzero");
+        Ok(Self { high_watermark, low_watermark, max_retained_rounds, memory_usage })
+    }
+
+    /// Returns the memory usage (in bytes) above which the extra rounds are released.
+    pub const fn high_watermark(&self) -> u64 {
+        self.high_watermark
+    }
+
+    /// Returns the memory usage (in bytes) below which extra rounds are retained.
+    pub const fn low_watermark(&self) -> u64 {
+        self.low_watermark
+    }
+
+    /// Returns the maximum number of rounds to keep in storage while there is memory headroom.
+    pub const fn max_retained_rounds(&self) -> u64 {
+        self.max_retained_rounds
+    }
+
+    /// Returns the current memory usage (in bytes), if it can be measured.
+    pub fn memory_usage(&self) -> Option<u64> {
+        (self.memory_usage)()
+    }
+
+    /// Returns the next number of GC rounds, given the current GC rounds, the maximum GC rounds, and the memory usage.
+    ///
+    /// Under memory pressure, the extra rounds are halved (down to the maximum GC rounds).
+    /// With memory headroom, the GC rounds are incremented by one (up to the maximum retained rounds).
+    /// Otherwise, the GC rounds remain unchanged.
+    pub fn next_gc_rounds(&self, current_gc_rounds: u64, max_gc_rounds: u64, memory_usage: u64) -> u64 {
+        // Ensure the upper bound is never below the maximum GC rounds.
+        let max_retained_rounds = self.max_retained_rounds.max(max_gc_rounds);
+        // Compute the number of extra rounds that are currently retained.
+        let extra_rounds = current_gc_rounds.saturating_sub(max_gc_rounds);
+        // Compute the next GC rounds.
+        let next_gc_rounds = match memory_usage {
+            // If the memory usage is above the high watermark, release the extra rounds.
+            usage if usage > self.high_watermark => max_gc_rounds.saturating_add(extra_rounds / 2),
+            // If the memory usage is below the low watermark, retain an extra round.
+            usage if usage < self.low_watermark => current_gc_rounds.saturating_add(1),
+            // Otherwise, keep the GC rounds unchanged.
+            _ => current_gc_rounds,
+        };
+        // Clamp the GC rounds within the safe bounds.
+        next_gc_rounds.clamp(max_gc_rounds, max_retained_rounds)
+    }
+}
+
+/// Returns the resident memory (in bytes) of the current process, if it can be measured.
+pub fn resident_memory_in_bytes() -> Option<u64> {
+    // Note: The page size is assumed to be 4 KiB, which holds for the supported platforms.
+    const PAGE_SIZE_IN_BYTES: u64 = 4096;
+    // Read the memory statistics of the current process.
+    let statm = std::fs::read_to_string("/proc/self/statm").ok()?;
+    // Parse the resident set size (in pages).
+    let resident_pages = statm.split_whitespace().nth(1)?.parse::<u64>().ok()?;
+    // Return the resident set size (in bytes).
+    resident_pages.checked_mul(PAGE_SIZE_IN_BYTES)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Returns a memory usage that is always above the high watermark.
+    fn high_memory() -> Option<u64> {
+        Some(u64::MAX)
+    }
+
+    #[test]
+    fn test_gc_memory_policy_new() {
+        assert!(GCMemoryPolicy::new_with_probe(100, 200, 10, high_memory).is_ok());
+        assert!(GCMemoryPolicy::new_with_probe(200, 200, 10, high_memory).is_err());
+        assert!(GCMemoryPolicy::new_with_probe(300, 200, 10, high_memory).is_err());
+        assert!(GCMemoryPolicy::new_with_probe(100, 200, 0, high_memory).is_err());
+    }
+
+    #[test]
+    fn test_next_gc_rounds() {
+        let policy = GCMemoryPolicy::new_with_probe(100, 200, 50, high_memory).unwrap();
+        assert_eq!(policy.memory_usage(), Some(u64::MAX));
+
+        // Under memory pressure, the extra rounds are released down to the maximum GC rounds.
+        assert_eq!(policy.next_gc_rounds(50, 10, 201), 30);
+        assert_eq!(policy.next_gc_rounds(30, 10, 201), 20);
+        assert_eq!(policy.next_gc_rounds(11, 10, 201), 10);
+        assert_eq!(policy.next_gc_rounds(10, 10, 201), 10);
+        // Between the watermarks, the GC rounds are unchanged.
+        assert_eq!(policy.next_gc_rounds(20, 10, 150), 20);
+        // With headroom, the GC rounds relax up to the maximum retained rounds.
+        assert_eq!(policy.next_gc_rounds(20, 10, 99), 21);
+        assert_eq!(policy.next_gc_rounds(50, 10, 99), 50);
+        // The GC rounds never drop below the maximum GC rounds.
+        assert_eq!(policy.next_gc_rounds(10, 60, 201), 60);
+        assert_eq!(policy.next_gc_rounds(60, 60, 99), 60);
+    }
+}
@@ -0,0 +1,161 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkOS library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use snarkvm::{
+    console::prelude::*,
+    ledger::narwhal::{BatchCertificate, TransmissionID},
+    prelude::Field,
+};
+
+use indexmap::IndexMap;
+use serde::{Deserialize, Serialize};
+
+/// A proof that a transmission is committed by a leader certificate.
+///
+/// The proof consists of the chain of certificates from the leader certificate down to the certificate that
+/// includes the transmission, where each certificate is a previous certificate of the one before it.
+/// The certificate ID commits to the batch header, which commits to the transmission IDs and the previous
+/// certificate IDs, so the proof is anchored in the leader certificate that the committee signed and committed.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct InclusionProof<N: Network> {
+    /// The ID of the transmission.
+    transmission_id: TransmissionID<N>,
+    /// The chain of certificates, from the leader certificate to the certificate that includes the transmission.
+    certificates: Vec<BatchCertificate<N>>,
+}
+
+impl<N: Network> InclusionProof<N> {
+    /// Initializes a new inclusion proof for the given transmission ID, within the given committed certificates
+    /// (i.e. the subdag) of the given leader certificate.
+    pub fn new<'a>(
+        leader_certificate: &BatchCertificate<N>,
+        certificates: impl IntoIterator<Item = &'a BatchCertificate<N>>,
+        transmission_id: TransmissionID<N>,
+    ) -> Result<Self> {
+        // Index the committed certificates by their ID.
+        let mut certificates: IndexMap<_, _> = certificates.into_iter().map(|c| (c.id(), c)).collect();
+        certificates.insert(leader_certificate.id(), leader_certificate);
+        // Search the certificates from the leader certificate, in round order, recording the certificate
+        // from which each certificate was reached.
+        let mut parents = IndexMap::new();
+        parents.insert(leader_certificate.id(), None);
+        let mut index = 0;
+        while let Some((&certificate_id, _)) = parents.get_index(index) {
+            let certificate = certificates[&certificate_id];
+            // If the certificate includes the transmission, return the chain of certificates to it.
+            if certificate.transmission_ids().contains(&transmission_id) {
+                let mut chain = vec![certificate.clone()];
+                let mut current = certificate_id;
+                while let Some(Some(parent)) = parents.get(&current) {
+                    chain.push(certificates[parent].clone());
+                    current = *parent;
+                }
+                chain.reverse();
+                return Ok(Self { transmission_id, certificates: chain });
+            }
+            // Continue the search through the previous certificates that are committed.
+            for previous_id in certificate.previous_certificate_ids() {
+                if certificates.contains_key(previous_id) && !parents.contains_key(previous_id) {
+                    parents.insert(*previous_id, Some(certificate_id));
+                }
+            }
+            index += 1;
+        }
+        bail!("Transmission '{transmission_id}' is not committed by the given leader certificate")
+    }
+
+    /// Returns the ID of the transmission.
+    pub const fn transmission_id(&self) -> TransmissionID<N> {
+        self.transmission_id
+    }
+
+    /// Returns the chain of certificates, from the leader certificate to the one that includes the transmission.
+    pub fn certificates(&self) -> &[BatchCertificate<N>] {
+        &self.certificates
+    }
+
+    /// Returns `true` if the proof shows the transmission is committed by the leader certificate with the given ID.
+    pub fn verify(&self, leader_certificate_id: &Field<N>) -> bool {
+        // Ensure the chain starts at the leader certificate.
+        let Some(leader_certificate) = self.certificates.first() else {
+            return false;
+        };
+        if leader_certificate.id() != *leader_certificate_id {
+            return false;
+        }
+        // Ensure each certificate is a previous certificate of the one before it.
+        let is_linked = self.certificates.windows(2).all(|pair| {
+            pair[1].round() + 1 == pair[0].round() && pair[0].previous_certificate_ids().contains(&pair[1].id())
+        });
+        // Ensure the last certificate includes the transmission.
+        is_linked && self.certificates.last().map_or(false, |c| c.transmission_ids().contains(&self.transmission_id))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use snarkvm::{
+        ledger::narwhal::batch_certificate::test_helpers::{
+            sample_batch_certificate_for_round,
+            sample_batch_certificate_with_previous_certificates,
+        },
+        prelude::Testnet3,
+        utilities::TestRng,
+    };
+
+    type CurrentNetwork = Testnet3;
+
+    #[test]
+    fn test_inclusion_proof() {
+        let rng = &mut TestRng::default();
+
+        // Sample the leader certificate, and its committed previous certificates.
+        let (leader, previous): (BatchCertificate<CurrentNetwork>, _) =
+            sample_batch_certificate_with_previous_certificates(3, rng);
+        let committed: Vec<_> = previous.iter().chain([&leader]).collect();
+
+        // Ensure a proof for each committed transmission is valid.
+        for certificate in &committed {
+            for transmission_id in certificate.transmission_ids() {
+                let proof = InclusionProof::new(&leader, committed.iter().copied(), *transmission_id).unwrap();
+                assert_eq!(proof.transmission_id(), *transmission_id);
+                assert_eq!(proof.certificates().first(), Some(&leader));
+                assert!(proof.verify(&leader.id()));
+            }
+        }
+
+        // Ensure a proof cannot be generated for a transmission that is not committed.
+        let other = sample_batch_certificate_for_round(2, rng);
+        let excluded_id = *other.transmission_ids().first().unwrap();
+        assert!(InclusionProof::new(&leader, committed.iter().copied(), excluded_id).is_err());
+
+        // Ensure a proof is rejected against another leader certificate.
+        let included_id = *previous[0].transmission_ids().first().unwrap();
+        let proof = InclusionProof::new(&leader, committed.iter().copied(), included_id).unwrap();
+        assert_eq!(proof.certificates().len(), 2);
+        assert!(!proof.verify(&other.id()));
+
+        // Ensure a proof with a substituted transmission ID is rejected.
+        let mut forged = proof.clone();
+        forged.transmission_id = excluded_id;
+        assert!(!forged.verify(&leader.id()));
+
+        // Ensure a proof with an unlinked certificate is rejected.
+        let mut forged = proof;
+        forged.certificates[1] = other;
+        forged.transmission_id = excluded_id;
+        assert!(!forged.verify(&leader.id()));
+    }
+}
@@ -0,0 +1,112 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkOS library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use anyhow::{ensure, Result};
+use indexmap::IndexSet;
+use std::{
+    sync::atomic::{AtomicBool, Ordering},
+    time::Duration,
+};
+
+/// The name of the background task that exports committed blocks.
+pub const BLOCK_EXPORT_TASK: &str = "block_export";
+/// The name of the background task that aggregates the metrics of the gateway cache.
+pub const METRICS_TASK: &str = "metrics";
+/// The name of the background task that prunes the expired entries of the gateway cache.
+pub const CACHE_PRUNING_TASK: &str = "cache_pruning";
+
+/// The interval at which a paused background task checks whether it may resume.
+const MAINTENANCE_POLL_INTERVAL_IN_MS: u64 = 100; // ms
+
+/// A maintenance window, which pauses the deferrable (non-critical) background tasks while consensus is under
+/// heavy load, and resumes them once the load subsides.
+///
+/// The consensus load is measured as the number of unconfirmed transmissions. The tasks are paused once the load
+/// reaches the high threshold, and resumed once it falls to the low threshold, so they do not flap in between.
+#[derive(Debug)]
+pub struct MaintenanceWindow {
+    /// The consensus load at or above which the deferrable tasks are paused.
+    high_load: usize,
+    /// The consensus load at or below which the deferrable tasks are resumed.
+    low_load: usize,
+    /// The names of the deferrable background tasks.
+    deferrable_tasks: IndexSet<String>,
+    /// The flag indicating whether the deferrable tasks are paused.
+    paused: AtomicBool,
+}
+
+impl MaintenanceWindow {
+    /// Initializes a new maintenance window with the given load thresholds, and no deferrable tasks.
+    pub fn new(high_load: usize, low_load: usize) -> Result<Self> {
+        ensure!(low_load < high_load, "The low load threshold must be below the high load threshold");
+        Ok(Self { high_load, low_load, deferrable_tasks: Default::default(), paused: Default::default() })
+    }
+
+    /// Sets the names of the deferrable background tasks (e.g. `BLOCK_EXPORT_TASK`).
+    pub fn with_deferrable_tasks(mut self, tasks: impl IntoIterator<Item = impl ToString>) -> Self {
+        self.deferrable_tasks = tasks.into_iter().map(|task| task.to_string()).collect();
+        self
+    }
+
+    /// Returns the consensus load at or above which the deferrable tasks are paused.
+    pub const fn high_load(&self) -> usize {
+        self.high_load
+    }
+
+    /// Returns the consensus load at or below which the deferrable tasks are resumed.
+    pub const fn low_load(&self) -> usize {
+        self.low_load
+    }
+
+    /// Returns `true` if the given background task is deferrable.
+    pub fn is_deferrable(&self, task: &str) -> bool {
+        self.deferrable_tasks.contains(task)
+    }
+
+    /// Returns `true` if the deferrable tasks are paused.
+    pub fn is_paused(&self) -> bool {
+        self.paused.load(Ordering::SeqCst)
+    }
+
+    /// Updates the maintenance window with the current consensus load, returning `true` if the deferrable tasks
+    /// are paused.
+    pub fn update(&self, load: usize) -> bool {
+        let was_paused = self.is_paused();
+        let is_paused = match was_paused {
+            true => load > self.low_load,
+            false => load >= self.high_load,
+        };
+        if is_paused != was_paused {
+            self.paused.store(is_paused, Ordering::SeqCst);
+            match is_paused {
+                true => info!("Pausing the deferrable background tasks (consensus load is {load})"),
+                false => info!("Resuming the deferrable background tasks (consensus load is {load})"),
+            }
+        }
+        is_paused
+    }
+
+    /// Returns `true` if the given background task may run now.
+    pub fn is_permitted(&self, task: &str) -> bool {
+        !self.is_deferrable(task) || !self.is_paused()
+    }
+
+    /// Blocks the current thread until the given background task may run, or until the given flag is set
+    /// (e.g. when the task is shut down).
+    pub fn wait_until_permitted_blocking(&self, task: &str, is_cancelled: &AtomicBool) {
+        while !self.is_permitted(task) && !is_cancelled.load(Ordering::SeqCst) {
+            std::thread::sleep(Duration::from_millis(MAINTENANCE_POLL_INTERVAL_IN_MS));
+        }
+    }
+}
@@ -0,0 +1,127 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkOS library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::{events::Event, helpers::now};
+use snarkvm::prelude::Network;
+
+use std::{
+    borrow::Cow,
+    net::SocketAddr,
+    sync::atomic::{AtomicU64, Ordering},
+};
+use tokio::sync::mpsc;
+
+/// The metadata of an inbound event, as mirrored to an external analysis pipeline.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct EventMetadata {
+    /// The event name.
+    pub name: Cow<'static, str>,
+    /// The listener IP of the peer that sent the event.
+    pub peer_ip: SocketAddr,
+    /// The size of the event, as the length of its frame in bytes.
+    pub size: usize,
+    /// The UNIX timestamp (in seconds) at which the event was received.
+    pub timestamp: i64,
+}
+
+/// A non-blocking tee of the inbound event metadata into a bounded channel, which is consumed by an external sink.
+/// If the sink falls behind, the metadata is dropped, so that the mirror never slows down the gateway.
+#[derive(Debug)]
+pub struct EventMirror {
+    /// The sender of the bounded channel.
+    sender: mpsc::Sender<EventMetadata>,
+    /// The number of events that were dropped, because the channel was full or closed.
+    num_dropped: AtomicU64,
+}
+
+impl EventMirror {
+    /// Initializes a new event mirror with the given channel capacity, returning the mirror and the sink receiver.
+    pub fn new(capacity: usize) -> (Self, mpsc::Receiver<EventMetadata>) {
+        let (sender, receiver) = mpsc::channel(capacity.max(1));
+        (Self { sender, num_dropped: Default::default() }, receiver)
+    }
+
+    /// Returns the number of events that were dropped, because the channel was full or closed.
+    pub fn num_dropped(&self) -> u64 {
+        self.num_dropped.load(Ordering::Relaxed)
+    }
+
+    /// Mirrors the metadata of the given inbound event, with the length of its frame (as decoded by the codec),
+    /// returning `true` if it was sent to the sink.
+    /// Note: This never blocks; if the channel is full or closed, the metadata is dropped.
+    pub fn mirror<N: Network>(&self, peer_ip: SocketAddr, event: &Event<N>, size: usize) -> bool {
+        // Construct the metadata.
+        let metadata = EventMetadata { name: event.name(), peer_ip, size, timestamp: now() };
+        // Send the metadata, without waiting for capacity.
+        match self.sender.try_send(metadata) {
+            Ok(()) => true,
+            Err(_) => {
+                self.num_dropped.fetch_add(1, Ordering::Relaxed);
+                false
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::events::{BlockRequest, ValidatorsRequest};
+    use snarkvm::prelude::ToBytes;
+
+    type CurrentNetwork = snarkvm::prelude::Testnet3;
+
+    #[test]
+    fn test_event_metadata_is_mirrored() {
+        let (mirror, mut receiver) = EventMirror::new(4);
+        let peer_ip = "127.0.0.1:5000".parse().unwrap();
+
+        // Mirror an event.
+        let event = Event::<CurrentNetwork>::BlockRequest(BlockRequest { start_height: 0, end_height: 10 });
+        let size = event.to_bytes_le().unwrap().len();
+        assert!(mirror.mirror(peer_ip, &event, size));
+
+        // Ensure the metadata is received by the sink.
+        let metadata = receiver.try_recv().unwrap();
+        assert_eq!(metadata.name, event.name());
+        assert_eq!(metadata.peer_ip, peer_ip);
+        assert_eq!(metadata.size, size);
+        assert!(metadata.timestamp > 0);
+        assert_eq!(mirror.num_dropped(), 0);
+    }
+
+    #[test]
+    fn test_full_event_mirror_drops_without_blocking() {
+        let (mirror, mut receiver) = EventMirror::new(2);
+        let peer_ip = "127.0.0.1:5000".parse().unwrap();
+        let event = Event::<CurrentNetwork>::ValidatorsRequest(ValidatorsRequest);
+
+        // Fill the channel, and ensure the subsequent events are dropped (rather than blocking).
+        assert!(mirror.mirror(peer_ip, &event, 1));
+        assert!(mirror.mirror(peer_ip, &event, 1));
+        assert!(!mirror.mirror(peer_ip, &event, 1));
+        assert!(!mirror.mirror(peer_ip, &event, 1));
+        assert_eq!(mirror.num_dropped(), 2);
+
+        // Drain one event, and ensure the mirror resumes.
+        assert!(receiver.try_recv().is_ok());
+        assert!(mirror.mirror(peer_ip, &event, 1));
+        assert_eq!(mirror.num_dropped(), 2);
+
+        // Close the sink, and ensure the events are dropped.
+        drop(receiver);
+        assert!(!mirror.mirror(peer_ip, &event, 1));
+        assert_eq!(mirror.num_dropped(), 3);
+    }
+}
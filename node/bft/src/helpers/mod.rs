@@ -12,6 +12,15 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+pub mod admission;
+pub use admission::*;
+
+pub mod alerts;
+pub use alerts::*;
+
+pub mod asn;
+pub use asn::*;
+
 pub mod cache;
 pub use cache::*;
 
@@ -21,27 +30,85 @@ pub use channels::*;
 pub mod dag;
 pub use dag::*;
 
+pub mod epoch;
+pub use epoch::*;
+
+pub mod equivocation;
+pub use equivocation::*;
+
+pub mod gc;
+pub use gc::*;
+
+pub mod inclusion;
+pub use inclusion::*;
+
+pub mod maintenance;
+pub use maintenance::*;
+
+#[cfg(feature = "mirror")]
+pub mod mirror;
+#[cfg(feature = "mirror")]
+pub use mirror::*;
+
 pub mod partition;
 pub use partition::*;
 
+pub mod penalty;
+pub use penalty::*;
+
 pub mod pending;
 pub use pending::*;
 
+pub mod programs;
+pub use programs::*;
+
 pub mod proposal;
 pub use proposal::*;
 
+#[cfg(feature = "quic")]
+pub mod quic;
+#[cfg(feature = "quic")]
+pub use quic::*;
+
 pub mod ready;
 pub use ready::*;
 
+pub mod receipt;
+pub use receipt::*;
+
+pub mod rejections;
+pub use rejections::*;
+
+pub mod replacement;
+pub use replacement::*;
+
 pub mod resolver;
 pub use resolver::*;
 
+pub mod retry;
+pub use retry::*;
+
+pub mod risk;
+pub use risk::*;
+
+pub mod senders;
+pub use senders::*;
+
+pub mod signatures;
+pub use signatures::*;
+
 pub mod storage;
 pub use storage::*;
 
 pub mod timestamp;
 pub use timestamp::*;
 
+pub mod timing;
+pub use timing::*;
+
+pub mod watchdog;
+pub use watchdog::*;
+
 /// Formats an ID into a truncated identifier (for logging purposes).
 pub fn fmt_id(id: impl ToString) -> String {
     let id = id.to_string();
@@ -12,13 +12,13 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use crate::Worker;
+use crate::{events::TransmissionRequest, Worker, MAX_WORKERS};
 use snarkvm::{
     ledger::narwhal::{Transmission, TransmissionID},
     prelude::{Network, ToBytes},
 };
 
-use anyhow::{bail, Result};
+use anyhow::{bail, ensure, Result};
 use sha2::{Digest, Sha256};
 
 fn double_sha256(data: &[u8]) -> [u8; 32] {
@@ -35,6 +35,10 @@ pub fn sha256d_to_u128(data: &[u8]) -> u128 {
     u128::from_le_bytes(hash)
 }
 
+/// The worker ID of a transmission request, for which the requester does not know the worker of the peer.
+/// Note: This is never one of the peer's workers, so the peer routes the request by its own assignment.
+pub const UNKNOWN_WORKER_ID: u8 = u8::MAX;
+
 /// Returns the worker ID for the given transmission ID.
 pub fn assign_to_worker<N: Network>(transmission_id: impl Into<TransmissionID<N>>, num_workers: u8) -> Result<u8> {
     // If there is only one worker, return it.
@@ -49,18 +53,128 @@ pub fn assign_to_worker<N: Network>(transmission_id: impl Into<TransmissionID<N>
     Ok(worker_id)
 }
 
-/// Assigns the given `(transmission ID, transmission)` entries into the `workers` using the given `op`.
+/// The dedication of workers to transmission types, to isolate the load of each type.
+///
+/// Note: The affinity only applies to the local routing of transmissions. A proposer commits to the workers
+/// that hold its transmissions (i.e. with its affinity), so its peers request each from the right worker.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct WorkerAffinity {
+    /// The worker IDs dedicated to solutions (if empty, any worker).
+    solution_workers: Vec<u8>,
+    /// The worker IDs dedicated to transactions (if empty, any worker).
+    transaction_workers: Vec<u8>,
+}
+
+impl WorkerAffinity {
+    /// Initializes a new affinity, from the worker IDs dedicated to solutions and to transactions.
+    pub fn new(solution_workers: Vec<u8>, transaction_workers: Vec<u8>) -> Result<Self> {
+        // Ensure the worker IDs are valid.
+        for worker_id in solution_workers.iter().chain(&transaction_workers) {
+            ensure!(*worker_id < MAX_WORKERS, "Invalid worker ID '{worker_id}'");
+        }
+        Ok(Self { solution_workers, transaction_workers })
+    }
+
+    /// Returns the worker IDs dedicated to solutions.
+    pub fn solution_workers(&self) -> &[u8] {
+        &self.solution_workers
+    }
+
+    /// Returns the worker IDs dedicated to transactions.
+    pub fn transaction_workers(&self) -> &[u8] {
+        &self.transaction_workers
+    }
+
+    /// Returns `true` if no workers are dedicated to any transmission type.
+    pub fn is_empty(&self) -> bool {
+        self.solution_workers.is_empty() && self.transaction_workers.is_empty()
+    }
+
+    /// Returns the worker ID for the given transmission ID, among the workers dedicated to its type.
+    ///
+    /// If none of the dedicated workers is one of our workers, the transmission falls back to any worker.
+    pub fn assign<N: Network>(&self, transmission_id: impl Into<TransmissionID<N>>, num_workers: u8) -> Result<u8> {
+        let transmission_id = transmission_id.into();
+        // Retrieve the dedicated workers that are available.
+        let dedicated_workers = match transmission_id {
+            TransmissionID::Ratification => &[][..],
+            TransmissionID::Solution(..) => &self.solution_workers[..],
+            TransmissionID::Transaction(..) => &self.transaction_workers[..],
+        };
+        let available_workers: Vec<_> = dedicated_workers.iter().filter(|id| **id < num_workers).collect();
+        // If none of the dedicated workers are available, fall back to any worker.
+        if available_workers.is_empty() {
+            return assign_to_worker(transmission_id, num_workers);
+        }
+        // Assign the transmission among the available dedicated workers.
+        let index = assign_to_worker(transmission_id, u8::try_from(available_workers.len())?)?;
+        Ok(*available_workers[index as usize])
+    }
+}
+
+/// Returns the worker ID of each of the given transmission IDs, among the given number of workers (and `affinity`).
+pub fn worker_assignment<'a, N: Network>(
+    transmission_ids: impl IntoIterator<Item = &'a TransmissionID<N>>,
+    num_workers: u8,
+    affinity: &WorkerAffinity,
+) -> Result<Vec<u8>> {
+    transmission_ids.into_iter().map(|transmission_id| affinity.assign(*transmission_id, num_workers)).collect()
+}
+
+/// Ensures the given worker IDs assign each of the given transmission IDs to one of the given number of workers.
+///
+/// Note: The worker IDs are not signed by the proposer, so this only checks their shape; they are used as a hint
+/// for routing the transmission requests, which falls back to our own assignment.
+pub fn check_worker_assignment<'a, N: Network>(
+    transmission_ids: impl ExactSizeIterator<Item = &'a TransmissionID<N>>,
+    worker_ids: &[u8],
+    num_workers: u8,
+) -> Result<()> {
+    // Ensure there is at least one worker.
+    ensure!(num_workers > 0, "The number of workers must be nonzero");
+    // Ensure there is a worker ID for each transmission ID.
+    ensure!(
+        transmission_ids.len() == worker_ids.len(),
+        "Expected {} worker IDs, found {}",
+        transmission_ids.len(),
+        worker_ids.len()
+    );
+    // Ensure each worker ID is one of the workers.
+    for worker_id in worker_ids {
+        ensure!(*worker_id < num_workers, "Invalid worker ID '{worker_id}' for {num_workers} workers");
+    }
+    Ok(())
+}
+
+/// Returns the worker ID that should handle the given transmission request.
+///
+/// If the requested worker ID is not one of our workers, or a worker affinity is configured,
+/// the worker is determined from the transmission ID.
+pub fn route_transmission_request<N: Network>(
+    request: &TransmissionRequest<N>,
+    num_workers: u8,
+    affinity: &WorkerAffinity,
+) -> Result<u8> {
+    match affinity.is_empty() && request.worker_id < num_workers {
+        true => Ok(request.worker_id),
+        false => affinity.assign(request.transmission_id, num_workers),
+    }
+}
+
+/// Assigns the given `(transmission ID, transmission)` entries into the `workers` (with the given `affinity`)
+/// using the given `op`.
 pub fn assign_to_workers<N: Network>(
     workers: &[Worker<N>],
+    affinity: &WorkerAffinity,
     transmissions: impl Iterator<Item = (TransmissionID<N>, Transmission<N>)>,
-    op: impl Fn(&Worker<N>, TransmissionID<N>, Transmission<N>),
+    mut op: impl FnMut(&Worker<N>, TransmissionID<N>, Transmission<N>),
 ) -> Result<()> {
     // Set the number of workers.
     let num_workers = u8::try_from(workers.len()).expect("Too many workers");
     // Re-insert the transmissions into the workers.
     for (transmission_id, transmission) in transmissions.into_iter() {
         // Determine the worker ID.
-        let Ok(worker_id) = assign_to_worker(transmission_id, num_workers) else {
+        let Ok(worker_id) = affinity.assign(transmission_id, num_workers) else {
             bail!("Unable to assign transmission ID '{transmission_id}' to a worker")
         };
         // Retrieve the worker.
@@ -76,7 +190,9 @@ pub fn assign_to_workers<N: Network>(
 #[cfg(test)]
 mod tests {
     use super::*;
-    use snarkvm::prelude::coinbase::PuzzleCommitment;
+    use snarkvm::prelude::{coinbase::PuzzleCommitment, Field, Rng, TestRng, Uniform};
+
+    use std::collections::HashSet;
 
     type CurrentNetwork = snarkvm::prelude::Testnet3;
 
@@ -94,4 +210,96 @@ mod tests {
         let worker_id = assign_to_worker(transmission_id, 5).unwrap();
         assert_eq!(worker_id, 4);
     }
+
+    #[test]
+    fn test_worker_assignment_for_multi_worker_batch() {
+        let rng = &mut TestRng::default();
+
+        const NUM_WORKERS: u8 = 4;
+
+        // Sample the transmission IDs of a batch.
+        let transmission_ids: Vec<TransmissionID<CurrentNetwork>> = (0..100)
+            .map(|i| match i % 2 == 0 {
+                true => TransmissionID::Transaction(Field::<CurrentNetwork>::rand(rng).into()),
+                false => TransmissionID::Solution(PuzzleCommitment::from_g1_affine(rng.gen())),
+            })
+            .collect();
+        // Ensure the batch spans multiple workers.
+        let worker_ids = worker_assignment(&transmission_ids, NUM_WORKERS, &Default::default()).unwrap();
+        assert!(worker_ids.iter().collect::<HashSet<_>>().len() > 1);
+
+        // Ensure the assignment is well-formed.
+        assert!(check_worker_assignment(transmission_ids.iter(), &worker_ids, NUM_WORKERS).is_ok());
+        assert!(check_worker_assignment(transmission_ids.iter(), &worker_ids, 0).is_err());
+        // Ensure a worker ID must be assigned to each transmission, among the workers.
+        assert!(check_worker_assignment(transmission_ids.iter(), &worker_ids[1..], NUM_WORKERS).is_err());
+        let mut reassigned = worker_ids.clone();
+        reassigned[0] = NUM_WORKERS;
+        assert!(check_worker_assignment(transmission_ids.iter(), &reassigned, NUM_WORKERS).is_err());
+
+        // Ensure each transmission request routes to the assigned worker.
+        for transmission_id in &transmission_ids {
+            let worker_id = assign_to_worker(*transmission_id, NUM_WORKERS).unwrap();
+            let request = TransmissionRequest::new(*transmission_id, worker_id);
+            assert_eq!(route_transmission_request(&request, NUM_WORKERS, &Default::default()).unwrap(), worker_id);
+            // Ensure an unknown worker ID falls back to the transmission ID assignment.
+            let request = TransmissionRequest::new(*transmission_id, UNKNOWN_WORKER_ID);
+            assert_eq!(route_transmission_request(&request, NUM_WORKERS, &Default::default()).unwrap(), worker_id);
+        }
+    }
+
+    #[test]
+    fn test_worker_affinity() {
+        let rng = &mut TestRng::default();
+
+        const NUM_WORKERS: u8 = 4;
+
+        // Sample the transmission IDs.
+        let solution_ids: Vec<TransmissionID<CurrentNetwork>> =
+            (0..50).map(|_| TransmissionID::Solution(PuzzleCommitment::from_g1_affine(rng.gen()))).collect();
+        let transaction_ids: Vec<TransmissionID<CurrentNetwork>> =
+            (0..50).map(|_| TransmissionID::Transaction(Field::<CurrentNetwork>::rand(rng).into())).collect();
+
+        // Ensure an invalid worker ID is rejected.
+        assert!(WorkerAffinity::new(vec![MAX_WORKERS], vec![]).is_err());
+
+        // Ensure the default affinity matches the transmission ID assignment.
+        let affinity = WorkerAffinity::default();
+        for transmission_id in solution_ids.iter().chain(&transaction_ids) {
+            let worker_id = assign_to_worker(*transmission_id, NUM_WORKERS).unwrap();
+            assert_eq!(affinity.assign(*transmission_id, NUM_WORKERS).unwrap(), worker_id);
+        }
+
+        // Dedicate worker 0 to solutions, and workers 1 and 2 to transactions.
+        let affinity = WorkerAffinity { solution_workers: vec![0], transaction_workers: vec![1, 2] };
+        // Ensure the solutions route to the solution worker.
+        for solution_id in &solution_ids {
+            assert_eq!(affinity.assign(*solution_id, NUM_WORKERS).unwrap(), 0);
+        }
+        // Ensure the transactions route to (and span) the transaction workers.
+        let worker_ids: HashSet<_> =
+            transaction_ids.iter().map(|id| affinity.assign(*id, NUM_WORKERS).unwrap()).collect();
+        assert_eq!(worker_ids, HashSet::from([1, 2]));
+        // Ensure a transmission request routes to the dedicated worker, regardless of the requested worker ID.
+        let request = TransmissionRequest::new(solution_ids[0], 3);
+        assert_eq!(route_transmission_request(&request, NUM_WORKERS, &affinity).unwrap(), 0);
+        // Ensure the committed assignment follows the affinity.
+        let worker_ids = worker_assignment(&solution_ids, NUM_WORKERS, &affinity).unwrap();
+        assert!(worker_ids.iter().all(|worker_id| *worker_id == 0));
+
+        // Ensure the transactions fall back to the available transaction worker, if the other is unavailable.
+        for transaction_id in &transaction_ids {
+            assert_eq!(affinity.assign(*transaction_id, 2).unwrap(), 1);
+        }
+        // Ensure the transactions fall back to any worker, if every transaction worker is unavailable.
+        for transaction_id in &transaction_ids {
+            let worker_id = assign_to_worker(*transaction_id, 1).unwrap();
+            assert_eq!(affinity.assign(*transaction_id, 1).unwrap(), worker_id);
+        }
+        let affinity = WorkerAffinity { solution_workers: vec![0], transaction_workers: vec![5] };
+        for transaction_id in &transaction_ids {
+            let worker_id = assign_to_worker(*transaction_id, NUM_WORKERS).unwrap();
+            assert_eq!(affinity.assign(*transaction_id, NUM_WORKERS).unwrap(), worker_id);
+        }
+    }
 }
@@ -0,0 +1,132 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkOS library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use anyhow::{ensure, Result};
+use std::time::Duration;
+
+/// The penalty applied to a peer, for the number of invalid certificates it sent within the window.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum CertificatePenalty {
+    /// The peer is not penalized.
+    None,
+    /// A warning is logged for the peer.
+    Warn,
+    /// The certificates from the peer are dropped, until its count resets.
+    Throttle,
+    /// The peer is disconnected, and banned for the ban duration.
+    TempBan,
+}
+
+/// The graduated penalties for peers that send invalid certificates.
+/// A peer that occasionally sends an invalid certificate may be buggy rather than malicious,
+/// so the penalty escalates with the number of invalid certificates in the window,
+/// and resets once the peer has not sent one for the length of the window.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct CertificatePenaltyPolicy {
+    /// The number of invalid certificates at which a warning is logged.
+    warn_threshold: usize,
+    /// The number of invalid certificates at which the certificates from the peer are dropped.
+    throttle_threshold: usize,
+    /// The number of invalid certificates at which the peer is temporarily banned.
+    ban_threshold: usize,
+    /// The window (in seconds) over which the invalid certificates are counted.
+    window_in_secs: i64,
+    /// The duration of a temporary ban.
+    ban_duration: Duration,
+}
+
+impl Default for CertificatePenaltyPolicy {
+    /// Initializes a new penalty policy, with the default thresholds, window, and ban duration.
+    fn default() -> Self {
+        Self {
+            warn_threshold: 1,
+            throttle_threshold: 3,
+            ban_threshold: 5,
+            window_in_secs: Self::DEFAULT_WINDOW_IN_SECS,
+            ban_duration: Self::DEFAULT_BAN_DURATION,
+        }
+    }
+}
+
+impl CertificatePenaltyPolicy {
+    /// The default window (in seconds) over which the invalid certificates are counted.
+    pub const DEFAULT_WINDOW_IN_SECS: i64 = 10 * 60; // 10 minutes
+    /// The default duration of a temporary ban.
+    pub const DEFAULT_BAN_DURATION: Duration = Duration::from_secs(60 * 60); // 1 hour
+
+    /// Initializes a new penalty policy, ensuring the thresholds are nonzero and escalating.
+    pub fn new(
+        warn_threshold: usize,
+        throttle_threshold: usize,
+        ban_threshold: usize,
+        window_in_secs: i64,
+        ban_duration: Duration,
+    ) -> Result<Self> {
+        ensure!(warn_threshold > 0, "The warning threshold must be nonzero");
+        ensure!(throttle_threshold >= warn_threshold, "The throttle threshold must be at least the warning threshold");
+        ensure!(ban_threshold >= throttle_threshold, "The ban threshold must be at least the throttle threshold");
+        ensure!(window_in_secs > 0, "The penalty window must be nonzero");
+        ensure!(!ban_duration.is_zero(), "The ban duration must be nonzero");
+        Ok(Self { warn_threshold, throttle_threshold, ban_threshold, window_in_secs, ban_duration })
+    }
+
+    /// Returns the number of invalid certificates at which the peer is temporarily banned.
+    pub const fn ban_threshold(&self) -> usize {
+        self.ban_threshold
+    }
+
+    /// Returns the window (in seconds) over which the invalid certificates are counted.
+    pub const fn window_in_secs(&self) -> i64 {
+        self.window_in_secs
+    }
+
+    /// Returns the duration of a temporary ban.
+    pub const fn ban_duration(&self) -> Duration {
+        self.ban_duration
+    }
+
+    /// Returns the penalty for the given number of invalid certificates within the window.
+    pub fn penalty(&self, num_invalid_certificates: usize) -> CertificatePenalty {
+        match num_invalid_certificates {
+            n if n >= self.ban_threshold => CertificatePenalty::TempBan,
+            n if n >= self.throttle_threshold => CertificatePenalty::Throttle,
+            n if n >= self.warn_threshold => CertificatePenalty::Warn,
+            _ => CertificatePenalty::None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_penalty_escalates() {
+        let policy = CertificatePenaltyPolicy::new(2, 3, 5, 60, Duration::from_secs(60)).unwrap();
+        assert_eq!(policy.penalty(0), CertificatePenalty::None);
+        assert_eq!(policy.penalty(1), CertificatePenalty::None);
+        assert_eq!(policy.penalty(2), CertificatePenalty::Warn);
+        assert_eq!(policy.penalty(3), CertificatePenalty::Throttle);
+        assert_eq!(policy.penalty(4), CertificatePenalty::Throttle);
+        assert_eq!(policy.penalty(5), CertificatePenalty::TempBan);
+        assert_eq!(policy.penalty(usize::MAX), CertificatePenalty::TempBan);
+
+        // Ensure the thresholds must escalate.
+        assert!(CertificatePenaltyPolicy::new(0, 3, 5, 60, Duration::from_secs(60)).is_err());
+        assert!(CertificatePenaltyPolicy::new(3, 2, 5, 60, Duration::from_secs(60)).is_err());
+        assert!(CertificatePenaltyPolicy::new(2, 3, 2, 60, Duration::from_secs(60)).is_err());
+        assert!(CertificatePenaltyPolicy::new(2, 3, 5, 0, Duration::from_secs(60)).is_err());
+        assert!(CertificatePenaltyPolicy::new(2, 3, 5, 60, Duration::ZERO).is_err());
+    }
+}
@@ -0,0 +1,40 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkOS library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use snarkvm::{
+    console::{prelude::*, program::ProgramID},
+    ledger::block::Transaction,
+};
+
+use indexmap::IndexSet;
+
+/// The handling of unconfirmed transactions that reference programs unknown to the ledger
+/// (e.g. programs that are not yet deployed).
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum UnknownProgramPolicy {
+    /// The transaction is rejected at admission, with the unknown program as the reason.
+    Reject,
+    /// The transaction is deferred, and its admission is retried once the programs are known.
+    Defer,
+}
+
+/// Returns the IDs of the programs referenced by the given transaction,
+/// i.e. the programs of its transitions, and the imports of its deployment (if any).
+pub fn referenced_program_ids<N: Network>(transaction: &Transaction<N>) -> IndexSet<ProgramID<N>> {
+    let mut program_ids: IndexSet<_> = transaction.transitions().map(|transition| *transition.program_id()).collect();
+    if let Some(deployment) = transaction.deployment() {
+        program_ids.extend(deployment.program().imports().keys().copied());
+    }
+    program_ids
+}
@@ -26,9 +26,24 @@ use snarkvm::{
 };
 
 use indexmap::{IndexMap, IndexSet};
+use serde::Serialize;
 use std::collections::HashSet;
 
+/// A read-only snapshot of the contents of the next proposal, before it is signed, for inspection.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+#[serde(bound = "")]
+pub struct ProposalContents<N: Network> {
+    /// The round of the proposal.
+    pub round: u64,
+    /// The IDs of the selected transmissions.
+    pub transmission_ids: IndexSet<TransmissionID<N>>,
+    /// The IDs of the referenced certificates from the previous round.
+    pub previous_certificate_ids: IndexSet<Field<N>>,
+}
+
 pub struct Proposal<N: Network> {
+    /// The committee (lookback) of the proposed batch.
+    committee: Committee<N>,
     /// The proposed batch header.
     batch_header: BatchHeader<N>,
     /// The proposed transmissions.
@@ -59,7 +74,12 @@ impl<N: Network> Proposal<N> {
             ensure!(a == b, "The transmission IDs do not match in the batch header and transmissions");
         }
         // Return the proposal.
-        Ok(Self { batch_header, transmissions, signatures: Default::default() })
+        Ok(Self { committee, batch_header, transmissions, signatures: Default::default() })
+    }
+
+    /// Returns the committee (lookback) of the proposed batch.
+    pub const fn committee(&self) -> &Committee<N> {
+        &self.committee
     }
 
     /// Returns the proposed batch header.
@@ -137,6 +157,34 @@ impl<N: Network> Proposal<N> {
         signature: Signature<N>,
         committee: &Committee<N>,
     ) -> Result<()> {
+        // Ensure the signer is a new committee member.
+        self.ensure_new_signer(signer, committee)?;
+        // Verify the signature. If the signature is not valid, return an error.
+        // Note: This check ensures the peer's address matches the address of the signature.
+        if !signature.verify(&signer, &[self.batch_id()]) {
+            bail!("Signature verification failed")
+        }
+        // Insert the signature.
+        self.signatures.insert(signature);
+        Ok(())
+    }
+
+    /// Adds a signature to the proposal, which was already verified against the batch ID (e.g. in a batch).
+    pub fn add_verified_signature(
+        &mut self,
+        signer: Address<N>,
+        signature: Signature<N>,
+        committee: &Committee<N>,
+    ) -> Result<()> {
+        // Ensure the signer is a new committee member.
+        self.ensure_new_signer(signer, committee)?;
+        // Insert the signature.
+        self.signatures.insert(signature);
+        Ok(())
+    }
+
+    /// Ensures the given signer is in the committee, and has not signed the proposal yet.
+    fn ensure_new_signer(&self, signer: Address<N>, committee: &Committee<N>) -> Result<()> {
         // Ensure the signer is in the committee.
         if !committee.is_committee_member(signer) {
             bail!("Signature from a non-committee member - '{signer}'")
@@ -145,13 +193,6 @@ impl<N: Network> Proposal<N> {
         if self.signers().contains(&signer) {
             bail!("Duplicate signature from '{signer}'")
         }
-        // Verify the signature. If the signature is not valid, return an error.
-        // Note: This check ensures the peer's address matches the address of the signature.
-        if !signature.verify(&signer, &[self.batch_id()]) {
-            bail!("Signature verification failed")
-        }
-        // Insert the signature.
-        self.signatures.insert(signature);
         Ok(())
     }
 
@@ -0,0 +1,380 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkOS library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::events::{Event, EventCodec, SizedEventCodec};
+use snarkvm::prelude::Network;
+
+use anyhow::{anyhow, Result};
+use futures::SinkExt;
+use std::{
+    io,
+    net::SocketAddr,
+    pin::Pin,
+    sync::Arc,
+    task::{Context, Poll},
+    time::Duration,
+};
+use tokio::{
+    io::{AsyncRead, AsyncWrite, ReadBuf},
+    sync::{mpsc, oneshot},
+};
+use tokio_util::codec::{FramedRead, FramedWrite};
+
+/// The maximum amount of time to wait for a QUIC connection to be established, before falling back to TCP.
+pub const QUIC_CONNECT_TIMEOUT_IN_MS: u64 = 3000; // ms
+
+/// The server name used for QUIC connections.
+/// Note: The TLS certificate is self-signed; the TLS session is bound to the validator keys by the gateway handshake.
+const QUIC_SERVER_NAME: &str = "snarkos";
+
+/// The preamble written by the initiator when it opens the stream for events.
+/// Note: A QUIC stream is only announced to the peer once data is written to it, so the initiator
+/// writes the preamble to let the responder accept the stream before the handshake.
+const QUIC_STREAM_PREAMBLE: &[u8] = b"snarkos-bft";
+
+/// The label of the keying material exported from the TLS session, to bind the session in the gateway handshake.
+const QUIC_CHANNEL_BINDING_LABEL: &[u8] = b"snarkos-bft-channel-binding";
+
+/// An outbound event, along with the sender to notify of its delivery.
+type QuicOutbound<N> = (Event<N>, oneshot::Sender<io::Result<()>>);
+
+/// A QUIC endpoint, which accepts and initiates peer connections.
+#[derive(Clone)]
+pub struct QuicEndpoint {
+    /// The QUIC endpoint.
+    endpoint: quinn::Endpoint,
+}
+
+impl QuicEndpoint {
+    /// Binds a new QUIC endpoint to the given UDP address, which accepts at most the given number of concurrent
+    /// connections (including those still handshaking). Beyond the limit, new incoming connections are refused
+    /// before they are yielded by `accept`, so that they do not consume a task each.
+    pub fn bind(addr: SocketAddr, max_connections: u32) -> Result<Self> {
+        // Generate a self-signed certificate for the endpoint.
+        let certificate = rcgen::generate_simple_self_signed(vec![QUIC_SERVER_NAME.to_string()])?;
+        let certificate_chain = vec![rustls::Certificate(certificate.serialize_der()?)];
+        let private_key = rustls::PrivateKey(certificate.serialize_private_key_der());
+        // Initialize the server configuration.
+        let mut server_config = quinn::ServerConfig::with_single_cert(certificate_chain, private_key)?;
+        server_config.concurrent_connections(max_connections);
+        // Initialize the client configuration.
+        let client_crypto = rustls::ClientConfig::builder()
+            .with_safe_defaults()
+            .with_custom_certificate_verifier(Arc::new(SkipServerVerification))
+            .with_no_client_auth();
+        // Bind the endpoint.
+        let mut endpoint = quinn::Endpoint::server(server_config, addr)?;
+        endpoint.set_default_client_config(quinn::ClientConfig::new(Arc::new(client_crypto)));
+        Ok(Self { endpoint })
+    }
+
+    /// Returns the local UDP address of the endpoint.
+    pub fn local_addr(&self) -> Result<SocketAddr> {
+        Ok(self.endpoint.local_addr()?)
+    }
+
+    /// Attempts to open a QUIC stream to the given peer IP.
+    pub async fn connect(&self, peer_ip: SocketAddr) -> Result<QuicStream> {
+        let connect = async {
+            // Establish the connection.
+            let connection = self.endpoint.connect(peer_ip, QUIC_SERVER_NAME)?.await?;
+            // Open the bidirectional stream for events, and announce it to the peer.
+            let (mut send, recv) = connection.open_bi().await?;
+            send.write_all(QUIC_STREAM_PREAMBLE).await?;
+            Ok::<_, anyhow::Error>(QuicStream { connection, send, recv })
+        };
+        // Ensure the connection is established in a timely manner.
+        match tokio::time::timeout(Duration::from_millis(QUIC_CONNECT_TIMEOUT_IN_MS), connect).await {
+            Ok(result) => result,
+            Err(_) => Err(anyhow!("Timed out connecting to '{peer_ip}' over QUIC")),
+        }
+    }
+
+    /// Accepts the next incoming QUIC connection, returning `None` if the endpoint is closed.
+    /// Note: The connection is established separately, so that a slow peer does not stall the accept loop.
+    pub async fn accept(&self) -> Option<QuicIncoming> {
+        self.endpoint.accept().await.map(|connecting| QuicIncoming { connecting })
+    }
+
+    /// Closes the endpoint, and all of its connections.
+    pub fn close(&self) {
+        self.endpoint.close(0u32.into(), b"shutdown");
+    }
+}
+
+/// An incoming QUIC connection, which is yet to be established.
+pub struct QuicIncoming {
+    /// The connection in progress.
+    connecting: quinn::Connecting,
+}
+
+impl QuicIncoming {
+    /// Returns the remote address of the peer.
+    pub fn remote_addr(&self) -> SocketAddr {
+        self.connecting.remote_address()
+    }
+
+    /// Establishes the connection, and accepts the stream for events from the peer.
+    pub async fn establish(self) -> Result<QuicStream> {
+        let peer_addr = self.remote_addr();
+        let establish = async {
+            // Establish the connection.
+            let connection = self.connecting.await?;
+            // Accept the bidirectional stream for events, and ensure it begins with the preamble.
+            let (send, mut recv) = connection.accept_bi().await?;
+            let mut preamble = [0u8; QUIC_STREAM_PREAMBLE.len()];
+            recv.read_exact(&mut preamble).await?;
+            anyhow::ensure!(preamble == QUIC_STREAM_PREAMBLE, "Invalid QUIC stream preamble");
+            Ok::<_, anyhow::Error>(QuicStream { connection, send, recv })
+        };
+        // Ensure the connection is established in a timely manner.
+        match tokio::time::timeout(Duration::from_millis(QUIC_CONNECT_TIMEOUT_IN_MS), establish).await {
+            Ok(result) => result,
+            Err(_) => Err(anyhow!("Timed out accepting '{peer_addr}' over QUIC")),
+        }
+    }
+}
+
+/// A bidirectional QUIC stream to a peer.
+pub struct QuicStream {
+    /// The QUIC connection.
+    connection: quinn::Connection,
+    /// The sending half of the stream.
+    send: quinn::SendStream,
+    /// The receiving half of the stream.
+    recv: quinn::RecvStream,
+}
+
+impl QuicStream {
+    /// Returns the remote address of the peer.
+    pub fn remote_addr(&self) -> SocketAddr {
+        self.connection.remote_address()
+    }
+
+    /// Returns the keying material exported from the TLS session, which both peers sign in the gateway handshake.
+    /// Note: A man-in-the-middle terminates a separate TLS session with each peer, so their keying material differs.
+    pub fn channel_binding(&self) -> Result<[u8; 32]> {
+        let mut channel_binding = [0u8; 32];
+        self.connection
+            .export_keying_material(&mut channel_binding, QUIC_CHANNEL_BINDING_LABEL, &[])
+            .map_err(|_| anyhow!("Unable to export the keying material of the QUIC connection"))?;
+        Ok(channel_binding)
+    }
+
+    /// Starts writing outbound events to the stream in the background, carrying over any bytes that were
+    /// already read from the stream. Returns the handle to the peer, and the reader for inbound events.
+    pub fn into_peer<N: Network>(
+        self,
+        read_buffer: &[u8],
+        queue_depth: usize,
+    ) -> (QuicPeer<N>, FramedRead<quinn::RecvStream, SizedEventCodec<N>>) {
+        let Self { connection, send, recv } = self;
+        // Initialize the reader, carrying over the buffered bytes.
+        let mut reader = FramedRead::new(recv, SizedEventCodec::<N>::default());
+        reader.read_buffer_mut().extend_from_slice(read_buffer);
+        // Initialize the writer.
+        let mut writer = FramedWrite::new(send, EventCodec::<N>::default());
+        let (sender, mut receiver) = mpsc::channel::<QuicOutbound<N>>(queue_depth);
+        tokio::spawn(async move {
+            while let Some((event, delivered)) = receiver.recv().await {
+                // Write the event to the stream.
+                let result = writer.send(event).await;
+                let is_err = result.is_err();
+                // Notify the caller of the delivery.
+                let _ = delivered.send(result);
+                // If the stream is broken, stop writing.
+                if is_err {
+                    break;
+                }
+            }
+        });
+        (QuicPeer { sender, connection }, reader)
+    }
+}
+
+impl AsyncRead for QuicStream {
+    fn poll_read(mut self: Pin<&mut Self>, cx: &mut Context<'_>, buf: &mut ReadBuf<'_>) -> Poll<io::Result<()>> {
+        AsyncRead::poll_read(Pin::new(&mut self.recv), cx, buf)
+    }
+}
+
+impl AsyncWrite for QuicStream {
+    fn poll_write(mut self: Pin<&mut Self>, cx: &mut Context<'_>, buf: &[u8]) -> Poll<io::Result<usize>> {
+        AsyncWrite::poll_write(Pin::new(&mut self.send), cx, buf)
+    }
+
+    fn poll_flush(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        AsyncWrite::poll_flush(Pin::new(&mut self.send), cx)
+    }
+
+    fn poll_shutdown(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        AsyncWrite::poll_shutdown(Pin::new(&mut self.send), cx)
+    }
+}
+
+/// The handle to a connected QUIC peer.
+#[derive(Clone)]
+pub struct QuicPeer<N: Network> {
+    /// The sender for outbound events.
+    sender: mpsc::Sender<QuicOutbound<N>>,
+    /// The QUIC connection.
+    connection: quinn::Connection,
+}
+
+impl<N: Network> QuicPeer<N> {
+    /// Queues the given event to be sent to the peer.
+    pub fn send(&self, event: Event<N>) -> io::Result<oneshot::Receiver<io::Result<()>>> {
+        let (delivered, receiver) = oneshot::channel();
+        self.sender
+            .try_send((event, delivered))
+            .map_err(|e| io::Error::new(io::ErrorKind::Other, format!("Unable to queue the event - {e}")))?;
+        Ok(receiver)
+    }
+
+    /// Closes the connection to the peer.
+    pub fn close(&self) {
+        self.connection.close(0u32.into(), b"disconnect");
+    }
+}
+
+/// A certificate verifier that accepts any server certificate.
+/// Note: This is safe, as the self-signed certificate is not trusted for authentication. Instead, both peers sign
+/// the keying material of the TLS session (see `QuicStream::channel_binding`) along with the nonces of the gateway
+/// handshake, which binds the session to their validator keys, and fails the handshake if the session is intercepted.
+struct SkipServerVerification;
+
+impl rustls::client::ServerCertVerifier for SkipServerVerification {
+    fn verify_server_cert(
+        &self,
+        _end_entity: &rustls::Certificate,
+        _intermediates: &[rustls::Certificate],
+        _server_name: &rustls::ServerName,
+        _scts: &mut dyn Iterator<Item = &[u8]>,
+        _ocsp_response: &[u8],
+        _now: std::time::SystemTime,
+    ) -> Result<rustls::client::ServerCertVerified, rustls::Error> {
+        Ok(rustls::client::ServerCertVerified::assertion())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::events::{BlockRequest, ValidatorsRequest};
+
+    use tokio::net::{TcpListener, TcpStream};
+    use tokio_stream::StreamExt;
+
+    type CurrentNetwork = snarkvm::prelude::Testnet3;
+
+    /// Returns a new QUIC endpoint on a random local port.
+    fn sample_endpoint() -> QuicEndpoint {
+        QuicEndpoint::bind("127.0.0.1:0".parse().unwrap(), 16).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_quic_connections_are_bounded() {
+        // Initialize the endpoints, with a responder that accepts a single connection.
+        let initiator = sample_endpoint();
+        let responder = QuicEndpoint::bind("127.0.0.1:0".parse().unwrap(), 1).unwrap();
+        let responder_addr = responder.local_addr().unwrap();
+
+        // Connect the initiator to the responder.
+        let (initiator_stream, responder_stream) = tokio::join!(initiator.connect(responder_addr), async {
+            responder.accept().await.unwrap().establish().await
+        });
+        let (initiator_stream, responder_stream) = (initiator_stream.unwrap(), responder_stream.unwrap());
+
+        // Ensure another connection is refused, while the first one is open.
+        assert!(initiator.connect(responder_addr).await.is_err());
+
+        // Close the first connection, and ensure a new connection is accepted once it is drained.
+        let (initiator_peer, _) = initiator_stream.into_peer::<CurrentNetwork>(&[], 16);
+        initiator_peer.close();
+        drop(responder_stream);
+        let acceptor = {
+            let responder = responder.clone();
+            tokio::spawn(async move { responder.accept().await.unwrap().establish().await })
+        };
+        let mut reconnected = None;
+        for _ in 0..10 {
+            if let Ok(stream) = initiator.connect(responder_addr).await {
+                reconnected = Some(stream);
+                break;
+            }
+            tokio::time::sleep(Duration::from_millis(500)).await;
+        }
+        assert!(reconnected.is_some());
+        assert!(acceptor.await.unwrap().is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_quic_connection_exchanges_events() {
+        // Initialize the endpoints.
+        let initiator = sample_endpoint();
+        let responder = sample_endpoint();
+        let responder_addr = responder.local_addr().unwrap();
+
+        // Connect the initiator to the responder.
+        let (initiator_stream, responder_stream) = tokio::join!(initiator.connect(responder_addr), async {
+            responder.accept().await.unwrap().establish().await
+        });
+        let initiator_stream = initiator_stream.unwrap();
+        let responder_stream = responder_stream.unwrap();
+        assert_eq!(initiator_stream.remote_addr(), responder_addr);
+        assert_eq!(responder_stream.remote_addr(), initiator.local_addr().unwrap());
+        // Ensure both peers observe the same TLS session.
+        assert_eq!(initiator_stream.channel_binding().unwrap(), responder_stream.channel_binding().unwrap());
+
+        // Start the peers.
+        let (initiator_peer, mut initiator_reader) = initiator_stream.into_peer::<CurrentNetwork>(&[], 16);
+        let (responder_peer, mut responder_reader) = responder_stream.into_peer::<CurrentNetwork>(&[], 16);
+
+        // Send a few events from the initiator, and ensure they are received in order.
+        let events = vec![
+            Event::ValidatorsRequest(ValidatorsRequest),
+            Event::BlockRequest(BlockRequest { start_height: 0, end_height: 10 }),
+            Event::BlockRequest(BlockRequest { start_height: 10, end_height: 20 }),
+        ];
+        for event in &events {
+            initiator_peer.send(event.clone()).unwrap().await.unwrap().unwrap();
+        }
+        for event in &events {
+            assert_eq!(&responder_reader.next().await.unwrap().unwrap(), event);
+        }
+
+        // Send an event back from the responder.
+        responder_peer.send(Event::ValidatorsRequest(ValidatorsRequest)).unwrap().await.unwrap().unwrap();
+        assert_eq!(initiator_reader.next().await.unwrap().unwrap(), Event::ValidatorsRequest(ValidatorsRequest));
+
+        // Close the connection, and ensure the responder observes it.
+        initiator_peer.close();
+        assert!(responder_reader.next().await.map_or(true, |result| result.is_err()));
+    }
+
+    #[tokio::test]
+    async fn test_quic_falls_back_to_tcp() {
+        // Initialize a TCP-only peer.
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let peer_ip = listener.local_addr().unwrap();
+
+        // Ensure a QUIC connection to the TCP-only peer fails.
+        let endpoint = sample_endpoint();
+        assert!(endpoint.connect(peer_ip).await.is_err());
+
+        // Ensure the peer is still reachable over TCP.
+        let (stream, accepted) = tokio::join!(TcpStream::connect(peer_ip), listener.accept());
+        assert_eq!(stream.unwrap().local_addr().unwrap(), accepted.unwrap().1);
+    }
+}
@@ -19,6 +19,7 @@ use snarkvm::{
         coinbase::{ProverSolution, PuzzleCommitment},
         narwhal::{Data, Transmission, TransmissionID},
     },
+    prelude::Address,
 };
 
 use indexmap::{IndexMap, IndexSet};
@@ -29,6 +30,12 @@ use std::sync::Arc;
 pub struct Ready<N: Network> {
     /// The current map of `(transmission ID, transmission)` entries.
     transmissions: Arc<RwLock<IndexMap<TransmissionID<N>, Transmission<N>>>>,
+    /// The map of `(transmission ID, submitter)` entries, for the transmissions with a known submitter.
+    submitters: Arc<RwLock<IndexMap<TransmissionID<N>, Address<N>>>>,
+    /// The map of `(transmission ID, priority fee)` entries, for the transmissions with a known priority fee.
+    fees: Arc<RwLock<IndexMap<TransmissionID<N>, u64>>>,
+    /// The set of pinned transmission IDs, which are drained ahead of every other transmission.
+    pinned: Arc<RwLock<IndexSet<TransmissionID<N>>>>,
 }
 
 impl<N: Network> Default for Ready<N> {
@@ -41,7 +48,25 @@ impl<N: Network> Default for Ready<N> {
 impl<N: Network> Ready<N> {
     /// Initializes a new instance of the ready queue.
     pub fn new() -> Self {
-        Self { transmissions: Default::default() }
+        Self {
+            transmissions: Default::default(),
+            submitters: Default::default(),
+            fees: Default::default(),
+            pinned: Default::default(),
+        }
+    }
+
+    /// Returns a copy of the ready queue, which does not share its state with this ready queue.
+    pub fn snapshot(&self) -> Self {
+        // Acquire the read locks, so the copy is consistent.
+        let (transmissions, submitters) = (self.transmissions.read(), self.submitters.read());
+        let (fees, pinned) = (self.fees.read(), self.pinned.read());
+        Self {
+            transmissions: Arc::new(RwLock::new(transmissions.clone())),
+            submitters: Arc::new(RwLock::new(submitters.clone())),
+            fees: Arc::new(RwLock::new(fees.clone())),
+            pinned: Arc::new(RwLock::new(pinned.clone())),
+        }
     }
 
     /// Returns `true` if the ready queue is empty.
@@ -107,6 +132,16 @@ impl<N: Network> Ready<N> {
         self.transmissions.read().get(&transmission_id.into()).cloned()
     }
 
+    /// Returns the submitter of the transmission, given the specified `transmission ID`.
+    pub fn get_submitter(&self, transmission_id: impl Into<TransmissionID<N>>) -> Option<Address<N>> {
+        self.submitters.read().get(&transmission_id.into()).copied()
+    }
+
+    /// Returns the priority fee of the transmission, given the specified `transmission ID`.
+    pub fn get_priority_fee(&self, transmission_id: impl Into<TransmissionID<N>>) -> Option<u64> {
+        self.fees.read().get(&transmission_id.into()).copied()
+    }
+
     /// Inserts the specified (`transmission ID`, `transmission`) to the ready queue.
     /// Returns `true` if the transmission is new, and was added to the ready queue.
     pub fn insert(&self, transmission_id: impl Into<TransmissionID<N>>, transmission: Transmission<N>) -> bool {
@@ -117,6 +152,110 @@ impl<N: Network> Ready<N> {
         is_new
     }
 
+    /// Sets the submitter of the specified transmission, if it is in the ready queue.
+    /// Returns `true` if the submitter was set.
+    pub fn set_submitter(&self, transmission_id: impl Into<TransmissionID<N>>, submitter: Address<N>) -> bool {
+        let transmission_id = transmission_id.into();
+        // Acquire the read lock, to ensure the transmission is not drained concurrently.
+        let transmissions = self.transmissions.read();
+        // Ensure the transmission is in the ready queue.
+        if !transmissions.contains_key(&transmission_id) {
+            return false;
+        }
+        // Set the submitter.
+        self.submitters.write().insert(transmission_id, submitter);
+        true
+    }
+
+    /// Sets the priority fee of the specified transmission, if it is in the ready queue.
+    /// Returns `true` if the priority fee was set.
+    pub fn set_priority_fee(&self, transmission_id: impl Into<TransmissionID<N>>, priority_fee: u64) -> bool {
+        let transmission_id = transmission_id.into();
+        // Acquire the read lock, to ensure the transmission is not drained concurrently.
+        let transmissions = self.transmissions.read();
+        // Ensure the transmission is in the ready queue.
+        if !transmissions.contains_key(&transmission_id) {
+            return false;
+        }
+        // Set the priority fee.
+        self.fees.write().insert(transmission_id, priority_fee);
+        true
+    }
+
+    /// Returns the number of pinned transmissions in the ready queue.
+    pub fn num_pinned(&self) -> usize {
+        self.pinned.read().len()
+    }
+
+    /// Returns `true` if the specified transmission is pinned.
+    pub fn is_pinned(&self, transmission_id: impl Into<TransmissionID<N>>) -> bool {
+        self.pinned.read().contains(&transmission_id.into())
+    }
+
+    /// Pins the specified transmission, if it is in the ready queue, so it is drained ahead of the others.
+    /// Returns `true` if the transmission was newly pinned.
+    pub fn pin(&self, transmission_id: impl Into<TransmissionID<N>>) -> bool {
+        let transmission_id = transmission_id.into();
+        // Acquire the read lock, so the transmission is not drained while it is pinned.
+        let transmissions = self.transmissions.read();
+        // Ensure the transmission is in the ready queue.
+        if !transmissions.contains_key(&transmission_id) {
+            return false;
+        }
+        self.pinned.write().insert(transmission_id)
+    }
+
+    /// Removes up to the specified number of pinned transmissions and returns them, in the order they were pinned.
+    pub fn drain_pinned(&self, num_transmissions: usize) -> IndexMap<TransmissionID<N>, Transmission<N>> {
+        // Acquire the write locks.
+        let mut transmissions = self.transmissions.write();
+        let mut pinned = self.pinned.write();
+        // Determine the number of pinned transmissions to drain.
+        let range = 0..pinned.len().min(num_transmissions);
+        // Drain the pinned transmissions.
+        let drained = pinned
+            .drain(range)
+            .filter_map(|id| transmissions.shift_remove(&id).map(|transmission| (id, transmission)))
+            .collect::<IndexMap<_, _>>();
+        // Remove the submitters and priority fees of the drained transmissions.
+        self.submitters.write().retain(|id, _| !drained.contains_key(id));
+        self.fees.write().retain(|id, _| !drained.contains_key(id));
+        drained
+    }
+
+    /// Replaces the specified transmission with the given replacement, which inherits the submitter of the
+    /// replaced transmission, and has the given priority fee. Returns `true` if the replaced transmission was
+    /// in the ready queue, and was evicted; otherwise, the ready queue is unchanged.
+    pub fn replace(
+        &self,
+        replaced_id: impl Into<TransmissionID<N>>,
+        transmission_id: impl Into<TransmissionID<N>>,
+        transmission: Transmission<N>,
+        priority_fee: u64,
+    ) -> bool {
+        let (replaced_id, transmission_id) = (replaced_id.into(), transmission_id.into());
+        // Acquire the write locks, so the replacement is atomic.
+        let mut transmissions = self.transmissions.write();
+        let mut submitters = self.submitters.write();
+        let mut fees = self.fees.write();
+        // Ensure the replaced transmission is in the ready queue, and the replacement is not.
+        if !transmissions.contains_key(&replaced_id) || transmissions.contains_key(&transmission_id) {
+            return false;
+        }
+        // Evict the replaced transmission.
+        transmissions.shift_remove(&replaced_id);
+        fees.shift_remove(&replaced_id);
+        // Insert the replacement, with the submitter of the replaced transmission.
+        transmissions.insert(transmission_id, transmission);
+        if let Some(submitter) = submitters.shift_remove(&replaced_id) {
+            submitters.insert(transmission_id, submitter);
+        }
+        fees.insert(transmission_id, priority_fee);
+        // Unpin the replaced transmission.
+        self.pinned.write().shift_remove(&replaced_id);
+        true
+    }
+
     /// Removes up to the specified number of transmissions and returns them.
     pub fn drain(&self, num_transmissions: usize) -> IndexMap<TransmissionID<N>, Transmission<N>> {
         // Acquire the write lock.
@@ -124,7 +263,100 @@ impl<N: Network> Ready<N> {
         // Determine the number of transmissions to drain.
         let range = 0..transmissions.len().min(num_transmissions);
         // Drain the transmission IDs.
-        transmissions.drain(range).collect::<IndexMap<_, _>>()
+        let drained = transmissions.drain(range).collect::<IndexMap<_, _>>();
+        // Remove the submitters and priority fees of the drained transmissions.
+        self.submitters.write().retain(|id, _| !drained.contains_key(id));
+        self.fees.write().retain(|id, _| !drained.contains_key(id));
+        self.pinned.write().retain(|id| !drained.contains_key(id));
+        drained
+    }
+
+    /// Removes up to the specified number of transmissions and returns them, while taking at most
+    /// `max_per_submitter` transmissions from any single submitter. Transmissions without a known
+    /// submitter are not capped. The skipped transmissions remain in the ready queue, in order.
+    pub fn drain_fair(
+        &self,
+        num_transmissions: usize,
+        max_per_submitter: usize,
+    ) -> IndexMap<TransmissionID<N>, Transmission<N>> {
+        // Acquire the write locks.
+        let mut transmissions = self.transmissions.write();
+        let mut submitters = self.submitters.write();
+        // Select the transmission IDs in queue order, skipping the submitters that have reached the cap.
+        let mut num_per_submitter = IndexMap::<Address<N>, usize>::new();
+        let selected = transmissions
+            .keys()
+            .filter(|id| match submitters.get(*id) {
+                Some(submitter) => {
+                    let count = num_per_submitter.entry(*submitter).or_default();
+                    *count += 1;
+                    *count <= max_per_submitter
+                }
+                None => true,
+            })
+            .take(num_transmissions)
+            .copied()
+            .collect::<IndexSet<_>>();
+        // Split the ready queue into the drained and remaining transmissions.
+        let (drained, remaining) = std::mem::take(&mut *transmissions)
+            .into_iter()
+            .partition::<IndexMap<_, _>, _>(|(id, _)| selected.contains(id));
+        *transmissions = remaining;
+        // Remove the submitters and priority fees of the drained transmissions.
+        submitters.retain(|id, _| !drained.contains_key(id));
+        self.fees.write().retain(|id, _| !drained.contains_key(id));
+        self.pinned.write().retain(|id| !drained.contains_key(id));
+        drained
+    }
+
+    /// Removes up to the specified number of transmissions and returns them, in descending order of priority fee.
+    /// Transmissions with equal priority fees are ordered by transmission ID, so that every node selects
+    /// and orders them identically, regardless of insertion order. Transmissions without a known priority fee
+    /// are treated as having a priority fee of zero. If `max_per_submitter` is given, at most that many
+    /// transmissions are taken from any single submitter. The skipped transmissions remain in the ready queue, in order.
+    pub fn drain_by_fee(
+        &self,
+        num_transmissions: usize,
+        max_per_submitter: Option<usize>,
+    ) -> IndexMap<TransmissionID<N>, Transmission<N>> {
+        // Acquire the write locks.
+        let mut transmissions = self.transmissions.write();
+        let mut submitters = self.submitters.write();
+        let mut fees = self.fees.write();
+        // Order the transmission IDs by descending priority fee, breaking ties by the transmission ID bytes.
+        let mut ordered = transmissions
+            .keys()
+            .map(|id| (fees.get(id).copied().unwrap_or(0), id.to_bytes_le().unwrap_or_default(), *id))
+            .collect::<Vec<_>>();
+        ordered.sort_unstable_by(|(fee_a, bytes_a, _), (fee_b, bytes_b, _)| {
+            fee_b.cmp(fee_a).then_with(|| bytes_a.cmp(bytes_b))
+        });
+        // Select the transmission IDs in priority order, skipping the submitters that have reached the cap.
+        let mut num_per_submitter = IndexMap::<Address<N>, usize>::new();
+        let selected = ordered
+            .into_iter()
+            .map(|(_, _, id)| id)
+            .filter(|id| match (submitters.get(id), max_per_submitter) {
+                (Some(submitter), Some(max_per_submitter)) => {
+                    let count = num_per_submitter.entry(*submitter).or_default();
+                    *count += 1;
+                    *count <= max_per_submitter
+                }
+                _ => true,
+            })
+            .take(num_transmissions)
+            .collect::<IndexSet<_>>();
+        // Split the ready queue into the drained and remaining transmissions.
+        let (mut drained, remaining) = std::mem::take(&mut *transmissions)
+            .into_iter()
+            .partition::<IndexMap<_, _>, _>(|(id, _)| selected.contains(id));
+        *transmissions = remaining;
+        // Remove the submitters and priority fees of the drained transmissions.
+        submitters.retain(|id, _| !drained.contains_key(id));
+        fees.retain(|id, _| !drained.contains_key(id));
+        self.pinned.write().retain(|id| !drained.contains_key(id));
+        // Return the drained transmissions, in priority order.
+        selected.into_iter().filter_map(|id| drained.swap_remove(&id).map(|transmission| (id, transmission))).collect()
     }
 }
 
@@ -223,4 +455,51 @@ mod tests {
         // Check the number of transmissions.
         assert_eq!(ready.num_transmissions(), 1);
     }
+
+    #[test]
+    fn test_ready_drain_by_fee_is_deterministic() {
+        let rng = &mut TestRng::default();
+
+        // Sample random fake bytes.
+        let data = |rng: &mut TestRng| Data::Buffer(Bytes::from((0..512).map(|_| rng.gen::<u8>()).collect::<Vec<_>>()));
+
+        // Sample the transmissions, where all but the last have equal priority fees.
+        let transmissions = (0..8)
+            .map(|_| {
+                let id = TransmissionID::Solution(PuzzleCommitment::from_g1_affine(rng.gen()));
+                (id, Transmission::<CurrentNetwork>::Solution(data(rng)))
+            })
+            .collect::<Vec<_>>();
+        let high_fee_id = transmissions[7].0;
+        let fee = |id: &TransmissionID<CurrentNetwork>| if *id == high_fee_id { 100 } else { 10 };
+
+        // Drains the transmissions from a ready queue, after inserting them in the given order.
+        let drain_in_order = |order: &[usize]| {
+            let ready = Ready::<CurrentNetwork>::new();
+            for index in order {
+                let (id, transmission) = &transmissions[*index];
+                assert!(ready.insert(*id, transmission.clone()));
+                assert!(ready.set_priority_fee(*id, fee(id)));
+            }
+            let drained = ready.drain_by_fee(5, None).into_keys().collect::<Vec<_>>();
+            assert_eq!(ready.num_transmissions(), 3);
+            drained
+        };
+
+        // Ensure the drained order is independent of the insertion order.
+        let drained = drain_in_order(&[0, 1, 2, 3, 4, 5, 6, 7]);
+        assert_eq!(drained, drain_in_order(&[7, 6, 5, 4, 3, 2, 1, 0]));
+        assert_eq!(drained, drain_in_order(&[3, 0, 6, 7, 1, 5, 2, 4]));
+
+        // Ensure the transmission with the higher priority fee is drained first.
+        assert_eq!(drained[0], high_fee_id);
+        // Ensure the equal-fee transmissions are ordered by transmission ID.
+        let bytes = drained[1..].iter().map(|id| id.to_bytes_le().unwrap()).collect::<Vec<_>>();
+        assert!(bytes.windows(2).all(|pair| pair[0] < pair[1]));
+
+        // Ensure a priority fee is not set for a transmission that is not in the ready queue.
+        let ready = Ready::<CurrentNetwork>::new();
+        assert!(!ready.set_priority_fee(high_fee_id, 100));
+        assert_eq!(ready.get_priority_fee(high_fee_id), None);
+    }
 }
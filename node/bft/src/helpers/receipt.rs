@@ -0,0 +1,91 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkOS library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use snarkos_account::Account;
+use snarkvm::{
+    console::prelude::*,
+    ledger::narwhal::TransmissionID,
+    prelude::{Address, Signature},
+};
+
+use serde::Serialize;
+
+/// The domain separator of the message signed in an admission receipt, so that the signature
+/// cannot be replayed as (or from) a signature over another message of the validator.
+const ADMISSION_RECEIPT_DOMAIN: &[u8] = b"snarkos-admission-receipt";
+
+/// A receipt, signed by a validator, acknowledging the admission of a transmission into its memory pool.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct AdmissionReceipt<N: Network> {
+    /// The ID of the admitted transmission.
+    transmission_id: TransmissionID<N>,
+    /// The UNIX timestamp (in seconds) of the admission.
+    timestamp: i64,
+    /// The round of the validator at the time of admission.
+    round: u64,
+    /// The signature of the validator over the transmission ID, timestamp, and round.
+    signature: Signature<N>,
+}
+
+impl<N: Network> AdmissionReceipt<N> {
+    /// Signs a new admission receipt with the given account.
+    pub fn sign<R: Rng + CryptoRng>(
+        account: &Account<N>,
+        transmission_id: TransmissionID<N>,
+        timestamp: i64,
+        round: u64,
+        rng: &mut R,
+    ) -> Result<Self> {
+        // Sign the receipt message.
+        let signature = account.sign_bytes(&Self::message(transmission_id, timestamp, round)?, rng)?;
+        Ok(Self { transmission_id, timestamp, round, signature })
+    }
+
+    /// Returns the ID of the admitted transmission.
+    pub const fn transmission_id(&self) -> TransmissionID<N> {
+        self.transmission_id
+    }
+
+    /// Returns the UNIX timestamp (in seconds) of the admission.
+    pub const fn timestamp(&self) -> i64 {
+        self.timestamp
+    }
+
+    /// Returns the round of the validator at the time of admission.
+    pub const fn round(&self) -> u64 {
+        self.round
+    }
+
+    /// Returns the signature of the validator.
+    pub const fn signature(&self) -> &Signature<N> {
+        &self.signature
+    }
+
+    /// Returns `true` if the receipt was signed by the given validator address.
+    pub fn verify(&self, address: &Address<N>) -> bool {
+        match Self::message(self.transmission_id, self.timestamp, self.round) {
+            Ok(message) => self.signature.verify_bytes(address, &message),
+            Err(_) => false,
+        }
+    }
+
+    /// Returns the message signed by the validator.
+    fn message(transmission_id: TransmissionID<N>, timestamp: i64, round: u64) -> Result<Vec<u8>> {
+        let mut message = ADMISSION_RECEIPT_DOMAIN.to_vec();
+        message.extend_from_slice(&transmission_id.to_bytes_le()?);
+        message.extend_from_slice(&timestamp.to_le_bytes());
+        message.extend_from_slice(&round.to_le_bytes());
+        Ok(message)
+    }
+}
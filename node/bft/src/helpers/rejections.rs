@@ -0,0 +1,141 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkOS library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use snarkvm::{
+    ledger::narwhal::TransmissionID,
+    prelude::{ensure, Network, Result},
+};
+
+use indexmap::IndexMap;
+use parking_lot::Mutex;
+use sha2::{Digest, Sha256};
+use std::time::{Duration, Instant};
+
+/// The maximum number of transiently-rejected transmissions that are retained.
+pub const MAX_TRANSIENT_REJECTIONS: usize = 1 << 14;
+
+/// A short-lived record of the transmissions that were rejected for a transient reason (e.g. a full ready queue),
+/// so that a retry within the retention window is recognized as the same transmission.
+///
+/// Note: Permanently-rejected transmissions (e.g. invalid ones) are never retained, so they are always re-validated.
+/// The transmission ID does not commit to the full transmission (e.g. the proofs of a transaction), so a retry is only
+/// recognized as validated if its bytes hash to those of the validated transmission.
+pub struct TransientRejections<N: Network> {
+    /// The duration for which a rejection is retained.
+    window: Duration,
+    /// The time of each rejection, and the hash of the transmission bytes if it was validated before it was
+    /// rejected, in order of rejection.
+    rejected: Mutex<IndexMap<TransmissionID<N>, (Instant, Option<[u8; 32]>)>>,
+}
+
+impl<N: Network> TransientRejections<N> {
+    /// Initializes a new record of transient rejections, with the given retention window.
+    pub fn new(window: Duration) -> Result<Self> {
+        ensure!(!window.is_zero(), "The retention window of the transient rejections must be nonzero");
+        Ok(Self { window, rejected: Default::default() })
+    }
+
+    /// Returns the duration for which a rejection is retained.
+    pub const fn window(&self) -> Duration {
+        self.window
+    }
+
+    /// Returns the number of retained rejections, including the expired ones that are not pruned yet.
+    pub fn len(&self) -> usize {
+        self.rejected.lock().len()
+    }
+
+    /// Returns `true` if no rejections are retained.
+    pub fn is_empty(&self) -> bool {
+        self.rejected.lock().is_empty()
+    }
+
+    /// Records the transient rejection of the given transmission, with its bytes if it was validated before it was
+    /// rejected.
+    pub fn insert(&self, transmission_id: impl Into<TransmissionID<N>>, validated_bytes: Option<&[u8]>) {
+        let mut rejected = self.rejected.lock();
+        // Prune the expired rejections, and evict the oldest rejection if the record is full.
+        Self::prune_inner(&mut rejected, self.window);
+        if rejected.len() >= MAX_TRANSIENT_REJECTIONS {
+            rejected.shift_remove_index(0);
+        }
+        // Note: The rejection is moved to the back, so the rejections remain in order of rejection.
+        let transmission_id = transmission_id.into();
+        rejected.shift_remove(&transmission_id);
+        rejected.insert(transmission_id, (Instant::now(), validated_bytes.map(|bytes| Sha256::digest(bytes).into())));
+    }
+
+    /// Removes the rejection of the given transmission, as it is retried with the given bytes. Returns whether the
+    /// retried bytes are those that were validated before it was rejected, if it was rejected within the retention
+    /// window.
+    pub fn remove(&self, transmission_id: impl Into<TransmissionID<N>>, bytes: &[u8]) -> Option<bool> {
+        let (rejected_at, validated_hash) = self.rejected.lock().shift_remove(&transmission_id.into())?;
+        let is_validated = validated_hash.is_some_and(|hash| hash == <[u8; 32]>::from(Sha256::digest(bytes)));
+        (rejected_at.elapsed() < self.window).then_some(is_validated)
+    }
+
+    /// Removes the rejections that are older than the retention window.
+    pub fn prune(&self) {
+        Self::prune_inner(&mut self.rejected.lock(), self.window);
+    }
+
+    /// Removes the given rejections that are older than the retention window.
+    fn prune_inner(rejected: &mut IndexMap<TransmissionID<N>, (Instant, Option<[u8; 32]>)>, window: Duration) {
+        // Note: The rejections are in order of rejection, so the expired ones are at the front.
+        let num_expired = rejected.values().take_while(|(rejected_at, _)| rejected_at.elapsed() >= window).count();
+        rejected.drain(..num_expired);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use snarkvm::{
+        ledger::coinbase::PuzzleCommitment,
+        prelude::{Rng, TestRng},
+    };
+
+    type CurrentNetwork = snarkvm::prelude::Testnet3;
+
+    #[test]
+    fn test_transient_rejections_expire() {
+        let window = Duration::from_millis(50);
+        assert!(TransientRejections::<CurrentNetwork>::new(Duration::ZERO).is_err());
+        let rejections = TransientRejections::<CurrentNetwork>::new(window).unwrap();
+        let rng = &mut TestRng::default();
+        let [id_a, id_b, id_c] =
+            [(); 3].map(|_| TransmissionID::<CurrentNetwork>::Solution(PuzzleCommitment::from_g1_affine(rng.gen())));
+
+        // Ensure a retry within the window is recognized, once.
+        rejections.insert(id_a, Some(b"a"));
+        rejections.insert(id_b, None);
+        assert_eq!(rejections.remove(id_a, b"a"), Some(true));
+        assert_eq!(rejections.remove(id_a, b"a"), None);
+        assert_eq!(rejections.remove(id_b, b"b"), Some(false));
+
+        // Ensure a retry with different bytes under the same ID is not recognized as validated.
+        rejections.insert(id_a, Some(b"a"));
+        assert_eq!(rejections.remove(id_a, b"forged"), Some(false));
+        rejections.insert(id_b, None);
+        assert_eq!(rejections.len(), 1);
+
+        // Ensure a retry after the window is treated as a new transmission, and the expired rejections are pruned.
+        std::thread::sleep(2 * window);
+        rejections.insert(id_c, Some(b"c"));
+        assert_eq!(rejections.len(), 1);
+        assert_eq!(rejections.remove(id_b, b"b"), None);
+        assert_eq!(rejections.remove(id_c, b"c"), Some(true));
+        assert!(rejections.is_empty());
+    }
+}
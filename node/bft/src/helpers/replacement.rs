@@ -0,0 +1,114 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkOS library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use snarkvm::{console::prelude::*, prelude::Address};
+
+use indexmap::IndexMap;
+use parking_lot::Mutex;
+use std::collections::VecDeque;
+
+/// The policy for replacing a queued transaction with one from the same submitter, with a higher priority fee.
+pub struct ReplacementPolicy<N: Network> {
+    /// The minimum increase (as a percentage of the replaced priority fee) of the replacement priority fee.
+    min_fee_bump_percent: u64,
+    /// The maximum number of replacements from any single submitter, within the interval.
+    max_replacements: usize,
+    /// The interval (in seconds) over which the replacements are rate limited.
+    interval_in_secs: i64,
+    /// The timestamps of the recent replacements, for each submitter.
+    replacements: Mutex<IndexMap<Address<N>, VecDeque<i64>>>,
+}
+
+impl<N: Network> Default for ReplacementPolicy<N> {
+    /// Initializes a new replacement policy, with the default minimum fee bump.
+    fn default() -> Self {
+        Self::new(Self::DEFAULT_MIN_FEE_BUMP_PERCENT)
+    }
+}
+
+impl<N: Network> ReplacementPolicy<N> {
+    /// The default interval (in seconds) over which the replacements are rate limited.
+    pub const DEFAULT_INTERVAL_IN_SECS: i64 = 60;
+    /// The default maximum number of replacements from any single submitter, within the interval.
+    pub const DEFAULT_MAX_REPLACEMENTS: usize = 5;
+    /// The default minimum increase (as a percentage of the replaced priority fee) of the replacement priority fee.
+    pub const DEFAULT_MIN_FEE_BUMP_PERCENT: u64 = 10;
+
+    /// Initializes a new replacement policy, with the given minimum fee bump (as a percentage).
+    pub fn new(min_fee_bump_percent: u64) -> Self {
+        Self {
+            min_fee_bump_percent,
+            max_replacements: Self::DEFAULT_MAX_REPLACEMENTS,
+            interval_in_secs: Self::DEFAULT_INTERVAL_IN_SECS,
+            replacements: Default::default(),
+        }
+    }
+
+    /// Sets the maximum number of replacements from any single submitter, within the given interval (in seconds).
+    pub fn with_rate_limit(mut self, max_replacements: usize, interval_in_secs: i64) -> Self {
+        self.max_replacements = max_replacements;
+        self.interval_in_secs = interval_in_secs;
+        self
+    }
+
+    /// Returns the minimum increase (as a percentage of the replaced priority fee) of the replacement priority fee.
+    pub const fn min_fee_bump_percent(&self) -> u64 {
+        self.min_fee_bump_percent
+    }
+
+    /// Returns the maximum number of replacements from any single submitter, and the interval (in seconds).
+    pub const fn rate_limit(&self) -> (usize, i64) {
+        (self.max_replacements, self.interval_in_secs)
+    }
+
+    /// Returns the minimum priority fee of a replacement, for the given replaced priority fee.
+    pub fn min_replacement_fee(&self, replaced_fee: u64) -> u64 {
+        let bump = (replaced_fee as u128 * self.min_fee_bump_percent as u128).div_ceil(100);
+        // Note: The replacement priority fee must always be strictly higher.
+        replaced_fee.saturating_add(u64::try_from(bump).unwrap_or(u64::MAX).max(1))
+    }
+
+    /// Ensures the given replacement priority fee is sufficiently higher than the replaced priority fee.
+    pub fn check_fee_bump(&self, replaced_fee: u64, replacement_fee: u64) -> Result<()> {
+        let min_fee = self.min_replacement_fee(replaced_fee);
+        ensure!(
+            replacement_fee >= min_fee,
+            "Replacement priority fee is too low ({replacement_fee} < {min_fee} microcredits)"
+        );
+        Ok(())
+    }
+
+    /// Records a replacement from the given submitter at the given timestamp (in seconds),
+    /// or returns an error if the submitter has exceeded the rate limit.
+    pub fn record_replacement(&self, submitter: Address<N>, timestamp: i64) -> Result<()> {
+        let mut replacements = self.replacements.lock();
+        // Remove the replacements that are outside of the interval, across all submitters.
+        replacements.retain(|_, timestamps| {
+            while timestamps.front().map_or(false, |t| timestamp.saturating_sub(*t) >= self.interval_in_secs) {
+                timestamps.pop_front();
+            }
+            !timestamps.is_empty()
+        });
+        // Ensure the submitter has not exceeded the rate limit.
+        let timestamps = replacements.entry(submitter).or_default();
+        ensure!(
+            timestamps.len() < self.max_replacements,
+            "Submitter '{submitter}' exceeded {} replacements in {}s",
+            self.max_replacements,
+            self.interval_in_secs
+        );
+        timestamps.push_back(timestamp);
+        Ok(())
+    }
+}
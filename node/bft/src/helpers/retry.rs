@@ -0,0 +1,157 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkOS library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::{events::Event, Transport};
+use snarkvm::prelude::{ensure, Network, Result};
+
+use std::{net::SocketAddr, time::Duration};
+
+/// The default maximum number of attempts to deliver an event to a peer.
+pub const DEFAULT_MAX_SEND_ATTEMPTS: u32 = 3; // attempts
+/// The default delay before the first retry.
+pub const DEFAULT_SEND_RETRY_DELAY_IN_MS: u64 = 250; // ms
+/// The maximum delay between retries.
+pub const MAX_SEND_RETRY_DELAY_IN_MS: u64 = 4000; // ms
+
+/// The policy for retrying the delivery of an event to a peer, with exponential backoff.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct RetryPolicy {
+    /// The maximum number of attempts to deliver the event.
+    max_attempts: u32,
+    /// The delay before the first retry, which doubles on every subsequent retry.
+    base_delay_in_ms: u64,
+}
+
+impl Default for RetryPolicy {
+    /// Initializes a new retry policy with the default values.
+    fn default() -> Self {
+        Self { max_attempts: DEFAULT_MAX_SEND_ATTEMPTS, base_delay_in_ms: DEFAULT_SEND_RETRY_DELAY_IN_MS }
+    }
+}
+
+impl RetryPolicy {
+    /// Initializes a new retry policy.
+    pub fn new(max_attempts: u32, base_delay_in_ms: u64) -> Result<Self> {
+        // Ensure there is at least one attempt.
+        ensure!(max_attempts > 0, "The maximum number of attempts must be nonzero");
+        Ok(Self { max_attempts, base_delay_in_ms })
+    }
+
+    /// Returns the maximum number of attempts to deliver the event.
+    pub const fn max_attempts(&self) -> u32 {
+        self.max_attempts
+    }
+
+    /// Returns the delay before the given retry (starting from 1), capped at the maximum delay.
+    pub fn delay(&self, retry: u32) -> Duration {
+        let factor = 1u64.checked_shl(retry.saturating_sub(1)).unwrap_or(u64::MAX);
+        Duration::from_millis(self.base_delay_in_ms.saturating_mul(factor).min(MAX_SEND_RETRY_DELAY_IN_MS))
+    }
+}
+
+/// Sends the event to the peer, retrying with backoff until the peer acknowledges the delivery,
+/// or the maximum number of attempts is reached. Returns `true` if the event was delivered.
+pub async fn send_with_retry<N: Network, T: Transport<N> + ?Sized>(
+    transport: &T,
+    peer_ip: SocketAddr,
+    event: Event<N>,
+    policy: RetryPolicy,
+) -> bool {
+    for attempt in 1..=policy.max_attempts() {
+        // Send the event, and wait for the delivery to be acknowledged.
+        if let Some(delivery) = transport.send(peer_ip, event.clone()).await {
+            if let Ok(Ok(())) = delivery.await {
+                return true;
+            }
+        }
+        // If there are attempts remaining, back off before retrying.
+        if attempt < policy.max_attempts() {
+            debug!("Retrying '{}' to '{peer_ip}' (attempt {attempt} failed)", event.name());
+            tokio::time::sleep(policy.delay(attempt)).await;
+        }
+    }
+    warn!("Failed to deliver '{}' to '{peer_ip}' after {} attempts", event.name(), policy.max_attempts());
+    false
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::events::ValidatorsRequest;
+
+    use parking_lot::Mutex;
+    use std::io;
+    use tokio::sync::oneshot;
+
+    type CurrentNetwork = snarkvm::prelude::Testnet3;
+
+    /// A transport that fails to deliver the first `num_failures` events.
+    struct FlakyTransport {
+        num_failures: usize,
+        num_sends: Mutex<usize>,
+    }
+
+    #[async_trait]
+    impl Transport<CurrentNetwork> for FlakyTransport {
+        async fn send(
+            &self,
+            _peer_ip: SocketAddr,
+            _event: Event<CurrentNetwork>,
+        ) -> Option<oneshot::Receiver<io::Result<()>>> {
+            let mut num_sends = self.num_sends.lock();
+            *num_sends += 1;
+            // Fail the delivery, until the number of failures is reached.
+            let (tx, rx) = oneshot::channel();
+            match *num_sends <= self.num_failures {
+                true => tx.send(Err(io::ErrorKind::BrokenPipe.into())).unwrap(),
+                false => tx.send(Ok(())).unwrap(),
+            }
+            Some(rx)
+        }
+
+        fn broadcast(&self, _event: Event<CurrentNetwork>) {}
+    }
+
+    #[test]
+    fn test_retry_policy_delay() {
+        assert!(RetryPolicy::new(0, 100).is_err());
+        let policy = RetryPolicy::new(10, 100).unwrap();
+        assert_eq!(policy.delay(1), Duration::from_millis(100));
+        assert_eq!(policy.delay(2), Duration::from_millis(200));
+        assert_eq!(policy.delay(3), Duration::from_millis(400));
+        assert_eq!(policy.delay(100), Duration::from_millis(MAX_SEND_RETRY_DELAY_IN_MS));
+    }
+
+    #[tokio::test]
+    async fn test_send_with_retry_to_flaky_peer() {
+        let peer_ip = SocketAddr::from(([127, 0, 0, 1], 1234));
+        let event = Event::ValidatorsRequest(ValidatorsRequest);
+        let policy = RetryPolicy::new(4, 1).unwrap();
+
+        // Ensure the event is retried, and eventually delivered to the flaky peer.
+        let flaky = FlakyTransport { num_failures: 2, num_sends: Default::default() };
+        assert!(send_with_retry(&flaky, peer_ip, event.clone(), policy).await);
+        assert_eq!(*flaky.num_sends.lock(), 3);
+
+        // Ensure the event is sent exactly once to a healthy peer.
+        let healthy = FlakyTransport { num_failures: 0, num_sends: Default::default() };
+        assert!(send_with_retry(&healthy, peer_ip, event.clone(), policy).await);
+        assert_eq!(*healthy.num_sends.lock(), 1);
+
+        // Ensure the number of attempts is bounded.
+        let broken = FlakyTransport { num_failures: usize::MAX, num_sends: Default::default() };
+        assert!(!send_with_retry(&broken, peer_ip, event, policy).await);
+        assert_eq!(*broken.num_sends.lock(), 4);
+    }
+}
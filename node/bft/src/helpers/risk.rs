@@ -0,0 +1,182 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkOS library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use anyhow::{ensure, Result};
+use std::time::Duration;
+
+/// The action taken against a peer, for its combined risk score.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum RiskAction {
+    /// No action is taken.
+    None,
+    /// The risk score of the peer is logged.
+    Monitor,
+    /// The events from the peer are dropped, until its score decreases.
+    Throttle,
+    /// The peer is disconnected, and banned for the ban duration.
+    Ban,
+}
+
+/// The signals of a peer from which its risk score is combined. Each signal is a load relative to
+/// its own limit, i.e. `0.0` when it is absent and `1.0` when it reaches its limit.
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+pub struct RiskSignals {
+    /// The number of recent inbound events, relative to the event rate limit.
+    pub events: f64,
+    /// The number of recent connection attempts, relative to the connection attempt limit.
+    pub connections: f64,
+    /// The number of recent invalid certificates, relative to the certificate ban threshold.
+    pub invalid_certificates: f64,
+    /// The round-trip time of the peer, relative to the round-trip time limit.
+    pub rtt: f64,
+}
+
+/// The weights of the signals in the risk score.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct RiskWeights {
+    /// The weight of the inbound events.
+    pub events: f64,
+    /// The weight of the connection attempts.
+    pub connections: f64,
+    /// The weight of the invalid certificates.
+    pub invalid_certificates: f64,
+    /// The weight of the round-trip time.
+    pub rtt: f64,
+}
+
+impl Default for RiskWeights {
+    /// Initializes the default weights. Note: A slow peer is not misbehaving by itself, so the round-trip time
+    /// only adds to the other signals.
+    fn default() -> Self {
+        Self { events: 0.5, connections: 0.5, invalid_certificates: 0.5, rtt: 0.25 }
+    }
+}
+
+/// The graduated actions for peers, by a risk score that combines their signals.
+/// A peer may stay just below every independent limit, so the weighted sum of the signals is
+/// compared to escalating thresholds instead, and the action eases as the signals expire.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct RiskPolicy {
+    /// The weights of the signals.
+    weights: RiskWeights,
+    /// The score at which the peer is monitored.
+    monitor_threshold: f64,
+    /// The score at which the events from the peer are dropped.
+    throttle_threshold: f64,
+    /// The score at which the peer is temporarily banned.
+    ban_threshold: f64,
+    /// The duration of a temporary ban.
+    ban_duration: Duration,
+}
+
+impl Default for RiskPolicy {
+    /// Initializes a new risk policy, with the default weights, thresholds, and ban duration.
+    fn default() -> Self {
+        Self {
+            weights: Default::default(),
+            monitor_threshold: 0.5,
+            throttle_threshold: 0.75,
+            ban_threshold: 1.0,
+            ban_duration: Self::DEFAULT_BAN_DURATION,
+        }
+    }
+}
+
+impl RiskPolicy {
+    /// The default duration of a temporary ban.
+    pub const DEFAULT_BAN_DURATION: Duration = Duration::from_secs(60 * 60); // 1 hour
+
+    /// Initializes a new risk policy, ensuring the weights are non-negative, and the thresholds are nonzero
+    /// and escalating.
+    pub fn new(
+        weights: RiskWeights,
+        monitor_threshold: f64,
+        throttle_threshold: f64,
+        ban_threshold: f64,
+        ban_duration: Duration,
+    ) -> Result<Self> {
+        let RiskWeights { events, connections, invalid_certificates, rtt } = weights;
+        for weight in [events, connections, invalid_certificates, rtt] {
+            ensure!(weight.is_finite() && weight >= 0.0, "The risk weights must be non-negative, found {weight}");
+        }
+        ensure!(monitor_threshold > 0.0, "The monitor threshold must be nonzero");
+        ensure!(
+            throttle_threshold >= monitor_threshold,
+            "The throttle threshold must be at least the monitor threshold"
+        );
+        ensure!(ban_threshold >= throttle_threshold, "The ban threshold must be at least the throttle threshold");
+        ensure!(ban_threshold.is_finite(), "The ban threshold must be finite");
+        ensure!(!ban_duration.is_zero(), "The ban duration must be nonzero");
+        Ok(Self { weights, monitor_threshold, throttle_threshold, ban_threshold, ban_duration })
+    }
+
+    /// Returns the weights of the signals.
+    pub const fn weights(&self) -> RiskWeights {
+        self.weights
+    }
+
+    /// Returns the duration of a temporary ban.
+    pub const fn ban_duration(&self) -> Duration {
+        self.ban_duration
+    }
+
+    /// Returns the risk score of the given signals, i.e. their weighted sum.
+    pub fn score(&self, signals: &RiskSignals) -> f64 {
+        let RiskWeights { events, connections, invalid_certificates, rtt } = self.weights;
+        events * signals.events
+            + connections * signals.connections
+            + invalid_certificates * signals.invalid_certificates
+            + rtt * signals.rtt
+    }
+
+    /// Returns the action for the given risk score.
+    pub fn action(&self, score: f64) -> RiskAction {
+        match score {
+            score if score >= self.ban_threshold => RiskAction::Ban,
+            score if score >= self.throttle_threshold => RiskAction::Throttle,
+            score if score >= self.monitor_threshold => RiskAction::Monitor,
+            _ => RiskAction::None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_risk_action_escalates() {
+        let weights = RiskWeights { events: 1.0, connections: 0.5, invalid_certificates: 2.0, rtt: 0.0 };
+        let policy = RiskPolicy::new(weights, 0.5, 1.0, 2.0, Duration::from_secs(60)).unwrap();
+        assert_eq!(policy.action(policy.score(&RiskSignals::default())), RiskAction::None);
+
+        // Ensure the score is the weighted sum of the signals.
+        let signals = RiskSignals { events: 0.5, connections: 0.4, invalid_certificates: 0.25, rtt: 1.0 };
+        assert_eq!(policy.score(&signals), 0.5 + 0.2 + 0.5);
+        assert_eq!(policy.action(0.49), RiskAction::None);
+        assert_eq!(policy.action(0.5), RiskAction::Monitor);
+        assert_eq!(policy.action(1.2), RiskAction::Throttle);
+        assert_eq!(policy.action(2.0), RiskAction::Ban);
+        assert_eq!(policy.action(f64::INFINITY), RiskAction::Ban);
+
+        // Ensure the weights must be non-negative, and the thresholds must escalate.
+        let negative = RiskWeights { rtt: -1.0, ..weights };
+        assert!(RiskPolicy::new(negative, 0.5, 1.0, 2.0, Duration::from_secs(60)).is_err());
+        assert!(RiskPolicy::new(weights, 0.0, 1.0, 2.0, Duration::from_secs(60)).is_err());
+        assert!(RiskPolicy::new(weights, 1.0, 0.5, 2.0, Duration::from_secs(60)).is_err());
+        assert!(RiskPolicy::new(weights, 0.5, 1.0, 0.5, Duration::from_secs(60)).is_err());
+        assert!(RiskPolicy::new(weights, 0.5, 1.0, f64::INFINITY, Duration::from_secs(60)).is_err());
+        assert!(RiskPolicy::new(weights, 0.5, 1.0, 2.0, Duration::ZERO).is_err());
+    }
+}
@@ -0,0 +1,216 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkOS library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::helpers::Cache;
+use snarkvm::{
+    console::{
+        prelude::*,
+        program::{Argument, Literal, Plaintext},
+    },
+    ledger::{
+        block::{Output, Transaction},
+        narwhal::TransmissionID,
+    },
+    prelude::Address,
+};
+
+use indexmap::IndexSet;
+
+/// Returns the sender of the given transaction, i.e. the payer of its public fee.
+/// Note: The payer of a private fee is not revealed, in which case `None` is returned.
+pub fn transaction_sender<N: Network>(transaction: &Transaction<N>) -> Option<Address<N>> {
+    let fee = transaction.fee_transition()?;
+    if !fee.is_fee_public() {
+        return None;
+    }
+    // The payer is the first argument of the future of the `fee_public` transition.
+    fee.transition().outputs().iter().find_map(|output| match output {
+        Output::Future(_, Some(future)) => match future.arguments().first() {
+            Some(Argument::Plaintext(Plaintext::Literal(Literal::Address(address), _))) => Some(*address),
+            _ => None,
+        },
+        _ => None,
+    })
+}
+
+/// Returns the priority fee (in microcredits) of the given transaction, or `0` if it has no fee.
+pub fn transaction_priority_fee<N: Network>(transaction: &Transaction<N>) -> u64 {
+    transaction.fee_transition().and_then(|fee| fee.priority_amount().ok()).map_or(0, |amount| *amount)
+}
+
+/// An operator-local filter of the senders whose unconfirmed transactions are admitted (and gossiped).
+///
+/// Note: The filter does not affect consensus validity; the transactions of filtered senders
+/// are still accepted in the batches and certificates of peers.
+#[derive(Clone, Debug)]
+pub struct SenderFilter<N: Network> {
+    /// The senders whose transactions are rejected.
+    denylist: IndexSet<Address<N>>,
+    /// The only senders whose transactions are admitted, if allowlist-only mode is enabled.
+    allowlist: Option<IndexSet<Address<N>>>,
+}
+
+impl<N: Network> Default for SenderFilter<N> {
+    /// Initializes a new sender filter, that admits every sender.
+    fn default() -> Self {
+        Self { denylist: Default::default(), allowlist: None }
+    }
+}
+
+impl<N: Network> SenderFilter<N> {
+    /// Sets the senders whose transactions are rejected.
+    pub fn with_denylist(mut self, senders: impl IntoIterator<Item = Address<N>>) -> Self {
+        self.denylist = senders.into_iter().collect();
+        self
+    }
+
+    /// Enables allowlist-only mode, in which only the transactions of the given senders are admitted.
+    pub fn with_allowlist(mut self, senders: impl IntoIterator<Item = Address<N>>) -> Self {
+        self.allowlist = Some(senders.into_iter().collect());
+        self
+    }
+
+    /// Returns `true` if only the transactions of the allowlisted senders are admitted.
+    pub fn is_allowlist_only(&self) -> bool {
+        self.allowlist.is_some()
+    }
+
+    /// Returns `true` if the transactions of the given sender are admitted.
+    /// If the sender is unknown (e.g. the fee is private), it is only admitted outside of allowlist-only mode.
+    pub fn is_admitted(&self, sender: Option<Address<N>>) -> bool {
+        match sender {
+            Some(sender) => {
+                !self.denylist.contains(&sender) && self.allowlist.as_ref().map_or(true, |a| a.contains(&sender))
+            }
+            None => self.allowlist.is_none(),
+        }
+    }
+}
+
+/// The rate limit on the transactions admitted from any single sender, regardless of the peers that relay them.
+///
+/// Note: This complements the per-peer limits; the senders of private fees are unknown, and are not rate limited.
+/// The limit is held once by the primary, and shared by its workers.
+pub struct SenderRateLimit<N: Network> {
+    /// The maximum number of transactions from any single sender, within the interval.
+    max_transactions: usize,
+    /// The interval (in seconds) over which the transactions are rate limited.
+    interval_in_secs: i64,
+    /// The cache of the recent transactions, and of the number of distinct recent transactions of each sender.
+    cache: Cache<N>,
+}
+
+impl<N: Network> SenderRateLimit<N> {
+    /// Initializes a new rate limit, of the given number of transactions from any single sender, within the given
+    /// interval (in seconds).
+    pub fn new(max_transactions: usize, interval_in_secs: i64) -> Result<Self> {
+        ensure!(max_transactions > 0, "The maximum number of transactions per sender must be nonzero");
+        ensure!(interval_in_secs > 0, "The rate limit interval must be nonzero");
+        Ok(Self { max_transactions, interval_in_secs, cache: Cache::with_interval(interval_in_secs) })
+    }
+
+    /// Returns the maximum number of transactions from any single sender, and the interval (in seconds).
+    pub const fn rate_limit(&self) -> (usize, i64) {
+        (self.max_transactions, self.interval_in_secs)
+    }
+
+    /// Records a transaction from the given sender, returning `true` if the sender is within the rate limit.
+    ///
+    /// Note: Each transaction is counted once within the interval, so that relaying (or replaying) the transactions
+    /// of a sender does not throttle it. The throttled transactions are counted as well, so a sender that keeps
+    /// flooding stays throttled.
+    pub fn check(&self, sender: Address<N>, transaction_id: N::TransactionID) -> bool {
+        let is_new = self.cache.record_inbound_transmission_if_new(TransmissionID::Transaction(transaction_id));
+        let num_transactions = match is_new {
+            true => self.cache.record_inbound_sender(sender),
+            false => self.cache.num_inbound_senders(sender),
+        };
+        num_transactions <= self.max_transactions
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use snarkvm::console::types::Field;
+
+    type CurrentNetwork = snarkvm::prelude::Testnet3;
+
+    #[test]
+    fn test_sender_filter() {
+        let rng = &mut TestRng::default();
+        let [sender, other] = [(); 2].map(|_| Address::<CurrentNetwork>::new(rng.gen()));
+
+        // Ensure every sender is admitted by default.
+        let filter = SenderFilter::<CurrentNetwork>::default();
+        assert!(!filter.is_allowlist_only());
+        assert!(filter.is_admitted(Some(sender)));
+        assert!(filter.is_admitted(None));
+
+        // Ensure the denied senders are rejected.
+        let filter = SenderFilter::default().with_denylist([sender]);
+        assert!(!filter.is_admitted(Some(sender)));
+        assert!(filter.is_admitted(Some(other)));
+        assert!(filter.is_admitted(None));
+
+        // Ensure only the allowlisted senders are admitted in allowlist-only mode.
+        let filter = SenderFilter::default().with_allowlist([sender]);
+        assert!(filter.is_allowlist_only());
+        assert!(filter.is_admitted(Some(sender)));
+        assert!(!filter.is_admitted(Some(other)));
+        assert!(!filter.is_admitted(None));
+
+        // Ensure the denylist takes precedence over the allowlist.
+        let filter = SenderFilter::default().with_allowlist([sender, other]).with_denylist([sender]);
+        assert!(!filter.is_admitted(Some(sender)));
+        assert!(filter.is_admitted(Some(other)));
+    }
+
+    #[test]
+    fn test_sender_rate_limit() {
+        let rng = &mut TestRng::default();
+        let [sender, other] = [(); 2].map(|_| Address::<CurrentNetwork>::new(rng.gen()));
+
+        // Ensure the rate limit is nonzero.
+        assert!(SenderRateLimit::<CurrentNetwork>::new(0, 60).is_err());
+        assert!(SenderRateLimit::<CurrentNetwork>::new(3, 0).is_err());
+
+        let transaction_id = |i: u64| Field::<CurrentNetwork>::from_u64(i).into();
+
+        // Ensure the relays of the same transaction are counted once.
+        let rate_limit = SenderRateLimit::<CurrentNetwork>::new(3, 60).unwrap();
+        assert_eq!(rate_limit.rate_limit(), (3, 60));
+        assert!((0..10).all(|_| rate_limit.check(sender, transaction_id(0))));
+
+        // Ensure the sender is throttled once its distinct transactions exceed the rate limit, and stays throttled.
+        assert!(rate_limit.check(sender, transaction_id(1)));
+        assert!(rate_limit.check(sender, transaction_id(2)));
+        assert!(!rate_limit.check(sender, transaction_id(3)));
+        assert!(!rate_limit.check(sender, transaction_id(3)));
+        assert!(!rate_limit.check(sender, transaction_id(0)));
+        // Ensure the other senders are unaffected.
+        assert!(rate_limit.check(other, transaction_id(4)));
+    }
+
+    #[test]
+    fn test_transaction_sender() {
+        let rng = &mut TestRng::default();
+        // Ensure the payer of a public fee is the sender.
+        let transaction = snarkvm::ledger::ledger_test_helpers::sample_fee_public_transaction(rng);
+        assert!(transaction_sender(&transaction).is_some());
+        // Ensure the payer of a private fee is not revealed.
+        let transaction = snarkvm::ledger::ledger_test_helpers::sample_fee_private_transaction(rng);
+        assert_eq!(transaction_sender(&transaction), None);
+    }
+}
@@ -0,0 +1,68 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkOS library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use snarkvm::console::{
+    account::{Address, Signature},
+    network::Network,
+    types::Field,
+};
+
+use rayon::prelude::*;
+
+/// The maximum number of batch signatures that are accumulated into a batch, before it is verified.
+pub const MAX_SIGNATURE_BATCH_SIZE: usize = 64;
+
+/// Returns whether each of the given signatures by the given signers over the given messages is valid.
+///
+/// note: Aleo signatures do not support batch verification, so the batch is verified in parallel instead,
+/// which yields the same results as verifying each signature individually.
+pub fn verify_batch<N: Network>(batch: &[(Address<N>, Signature<N>, Field<N>)]) -> Vec<bool> {
+    match batch {
+        [(signer, signature, message)] => vec![signature.verify(signer, &[*message])],
+        _ => batch.par_iter().map(|(signer, signature, message)| signature.verify(signer, &[*message])).collect(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use snarkvm::prelude::{PrivateKey, TestRng, Uniform};
+
+    type CurrentNetwork = snarkvm::prelude::Testnet3;
+
+    #[test]
+    fn test_batched_verification_matches_individual_verification() {
+        let rng = &mut TestRng::default();
+
+        // Sample signatures, where every third one is over another message, and every fifth one is by another signer.
+        let other = Address::try_from(PrivateKey::<CurrentNetwork>::new(rng).unwrap()).unwrap();
+        let items = (0..20)
+            .map(|i| {
+                let private_key = PrivateKey::<CurrentNetwork>::new(rng).unwrap();
+                let message = Field::rand(rng);
+                let signed = if i % 3 == 0 { Field::rand(rng) } else { message };
+                let signature = private_key.sign(&[signed], rng).unwrap();
+                let signer = if i % 5 == 0 { other } else { Address::try_from(private_key).unwrap() };
+                (signer, signature, message)
+            })
+            .collect::<Vec<_>>();
+        let expected =
+            items.iter().map(|(signer, signature, message)| signature.verify(signer, &[*message])).collect::<Vec<_>>();
+        assert!(expected.contains(&true) && expected.contains(&false));
+
+        // Ensure the batch verification matches the individual verification.
+        assert_eq!(verify_batch(&items), expected);
+        assert_eq!(verify_batch(&items[1..2]), expected[1..2]);
+    }
+}
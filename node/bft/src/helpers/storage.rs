@@ -12,7 +12,7 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use crate::helpers::{check_timestamp_for_liveness, fmt_id};
+use crate::helpers::{check_timestamp_for_liveness, fmt_id, GCMemoryPolicy};
 use snarkos_node_bft_ledger_service::LedgerService;
 use snarkos_node_bft_storage_service::StorageService;
 use snarkvm::{
@@ -29,7 +29,7 @@ use rayon::prelude::*;
 use std::{
     collections::{HashMap, HashSet},
     sync::{
-        atomic::{AtomicU32, AtomicU64, Ordering},
+        atomic::{AtomicBool, AtomicU32, AtomicU64, Ordering},
         Arc,
     },
 };
@@ -78,6 +78,10 @@ pub struct StorageInner<N: Network> {
     gc_round: AtomicU64,
     /// The maximum number of rounds to keep in storage.
     max_gc_rounds: u64,
+    /// The number of rounds currently kept in storage, as extended by the GC memory policy.
+    effective_gc_rounds: AtomicU64,
+    /// The optional policy for retaining extra rounds based on memory pressure.
+    gc_memory_policy: RwLock<Option<GCMemoryPolicy>>,
     /* Once per batch */
     /// The map of `round` to a list of `(certificate ID, batch ID, author)` entries.
     rounds: RwLock<IndexMap<u64, IndexSet<(Field<N>, Field<N>, Address<N>)>>>,
@@ -87,6 +91,13 @@ pub struct StorageInner<N: Network> {
     batch_ids: RwLock<IndexMap<Field<N>, u64>>,
     /// The map of `transmission ID` to `(transmission, certificate IDs)` entries.
     transmissions: Arc<dyn StorageService<N>>,
+    /* Verification cache */
+    /// The flag indicating whether the batch headers that pass verification are cached.
+    header_cache_enabled: AtomicBool,
+    /// The map of verified `batch ID` to `round`, for the batch headers that passed verification (up to GC).
+    verified_headers: RwLock<IndexMap<Field<N>, u64>>,
+    /// The number of batch header verifications skipped, due to the batch header being verified already.
+    header_cache_hits: AtomicU64,
 }
 
 impl<N: Network> Storage<N> {
@@ -108,10 +119,15 @@ impl<N: Network> Storage<N> {
             current_round: Default::default(),
             gc_round: Default::default(),
             max_gc_rounds,
+            effective_gc_rounds: AtomicU64::new(max_gc_rounds),
+            gc_memory_policy: Default::default(),
             rounds: Default::default(),
             certificates: Default::default(),
             batch_ids: Default::default(),
             transmissions,
+            header_cache_enabled: AtomicBool::new(true),
+            verified_headers: Default::default(),
+            header_cache_hits: Default::default(),
         }));
         // Update the storage to the current round.
         storage.update_current_round(current_round);
@@ -146,6 +162,54 @@ impl<N: Network> Storage<N> {
         self.max_gc_rounds
     }
 
+    /// Returns the number of rounds currently kept in storage.
+    /// Note: This is at least the maximum GC rounds, which the BFT requires in order to commit.
+    pub fn effective_gc_rounds(&self) -> u64 {
+        self.effective_gc_rounds.load(Ordering::SeqCst)
+    }
+
+    /// Sets the policy for retaining extra rounds based on memory pressure.
+    /// If `None` is given, the GC rounds are reset to the maximum GC rounds.
+    pub fn set_gc_memory_policy(&self, policy: Option<GCMemoryPolicy>) -> Result<()> {
+        match policy {
+            // Ensure the policy never retains fewer rounds than the BFT requires.
+            Some(policy) => ensure!(
+                policy.max_retained_rounds() >= self.max_gc_rounds,
+                "The maximum retained rounds must be at least the maximum GC rounds ({})",
+                self.max_gc_rounds
+            ),
+            // If the policy is removed, reset the GC rounds.
+            None => self.effective_gc_rounds.store(self.max_gc_rounds, Ordering::SeqCst),
+        }
+        *self.gc_memory_policy.write() = policy;
+        Ok(())
+    }
+
+    /// Returns `true` if the batch headers that pass verification are cached.
+    pub fn is_header_cache_enabled(&self) -> bool {
+        self.header_cache_enabled.load(Ordering::SeqCst)
+    }
+
+    /// Sets whether the batch headers that pass verification are cached, so that the same batch header
+    /// arriving again (e.g. from another peer, or in a certificate) skips re-verification.
+    pub fn set_header_cache_enabled(&self, enabled: bool) {
+        self.header_cache_enabled.store(enabled, Ordering::SeqCst);
+        // If the cache is disabled, clear the verified batch headers.
+        if !enabled {
+            self.verified_headers.write().clear();
+        }
+    }
+
+    /// Returns `true` if the batch header for the given batch ID passed verification, and is cached.
+    pub fn is_verified_header(&self, batch_id: Field<N>) -> bool {
+        self.verified_headers.read().contains_key(&batch_id)
+    }
+
+    /// Returns the number of batch header verifications skipped, due to the batch header being verified already.
+    pub fn header_cache_hits(&self) -> u64 {
+        self.header_cache_hits.load(Ordering::SeqCst)
+    }
+
     /// Increments storage to the next round, updating the current round.
     /// Note: This method is only called once per round, upon certification of the primary's batch.
     pub fn increment_to_next_round(&self, current_round: u64) -> Result<u64> {
@@ -205,10 +269,13 @@ impl<N: Network> Storage<N> {
         // Update the current round.
         self.current_round.store(next_round, Ordering::SeqCst);
 
+        // Retain extra rounds based on memory pressure, if a policy is set.
+        self.update_effective_gc_rounds();
+
         // Fetch the current GC round.
         let current_gc_round = self.gc_round();
         // Compute the next GC round.
-        let next_gc_round = next_round.saturating_sub(self.max_gc_rounds);
+        let next_gc_round = next_round.saturating_sub(self.effective_gc_rounds());
         // Check if storage needs to be garbage collected.
         if next_gc_round > current_gc_round {
             // Remove the GC round(s) from storage.
@@ -219,10 +286,33 @@ impl<N: Network> Storage<N> {
                     self.remove_certificate(certificate.id());
                 }
             }
+            // Remove the verified batch headers for the GC round(s).
+            self.verified_headers.write().retain(|_, round| *round > next_gc_round);
             // Update the GC round.
             self.gc_round.store(next_gc_round, Ordering::SeqCst);
         }
     }
+
+    /// Updates the effective GC rounds, based on the current memory usage and the GC memory policy.
+    fn update_effective_gc_rounds(&self) {
+        // Retrieve the GC memory policy, if one is set.
+        let Some(policy) = *self.gc_memory_policy.read() else {
+            return;
+        };
+        // Measure the current memory usage.
+        let Some(memory_usage) = policy.memory_usage() else {
+            return;
+        };
+        // Compute the next GC rounds.
+        let current_gc_rounds = self.effective_gc_rounds();
+        let next_gc_rounds = policy.next_gc_rounds(current_gc_rounds, self.max_gc_rounds, memory_usage);
+        // Log the adjustment, if the GC rounds changed.
+        if next_gc_rounds != current_gc_rounds {
+            debug!("Adjusting the GC rounds from {current_gc_rounds} to {next_gc_rounds} (memory usage: {memory_usage} bytes)");
+        }
+        // Update the effective GC rounds.
+        self.effective_gc_rounds.store(next_gc_rounds, Ordering::SeqCst);
+    }
 }
 
 impl<N: Network> Storage<N> {
@@ -348,6 +438,15 @@ impl<N: Network> Storage<N> {
             bail!("Batch for round {round} already exists in storage {gc_log}")
         }
 
+        // If the batch header was verified already, skip re-verification, and only retrieve the missing transmissions.
+        if self.is_verified_header(batch_header.batch_id()) {
+            self.header_cache_hits.fetch_add(1, Ordering::SeqCst);
+            return self
+                .transmissions
+                .find_missing_transmissions(batch_header, transmissions)
+                .map_err(|e| anyhow!("{e} for round {round} {gc_log}"));
+        }
+
         // Retrieve the committee lookback for the batch round.
         let Ok(committee_lookback) = self.ledger.get_committee_lookback_for_round(round) else {
             bail!("Storage failed to retrieve the committee lookback for round {round} {gc_log}")
@@ -409,6 +508,11 @@ impl<N: Network> Storage<N> {
                 bail!("Previous certificates for a batch in round {round} did not reach quorum threshold {gc_log}")
             }
         }
+
+        // Cache the batch header as verified, as it passed every check. Note: Invalid batch headers are never cached.
+        if self.is_header_cache_enabled() && round > gc_round {
+            self.verified_headers.write().insert(batch_header.batch_id(), round);
+        }
         Ok(missing_transmissions)
     }
 
@@ -484,6 +588,46 @@ impl<N: Network> Storage<N> {
         Ok(missing_transmissions)
     }
 
+    /// Returns `true` if the given `certificate` is malformed, regardless of what is in storage.
+    ///
+    /// A certificate is malformed if its author or a signer is not in the committee for the batch round,
+    /// if its signatures did not reach the quorum threshold, or if it declares more previous certificates
+    /// than there are members in the committee for the previous round.
+    ///
+    /// Note: Unlike `check_certificate`, this does not fail for a certificate that is already in storage,
+    /// or whose previous certificates are not in storage yet, as those depend on the order of arrival.
+    /// If a committee is not available yet, the certificate is not considered malformed.
+    pub fn is_malformed_certificate(&self, certificate: &BatchCertificate<N>) -> bool {
+        // Retrieve the committee lookback for the batch round.
+        let Ok(committee_lookback) = self.ledger.get_committee_lookback_for_round(certificate.round()) else {
+            return false;
+        };
+        // Ensure the author is in the committee.
+        if !committee_lookback.is_committee_member(certificate.author()) {
+            return true;
+        }
+        // Ensure the signers are in the committee, and have reached the quorum threshold.
+        let mut signers = HashSet::with_capacity(certificate.signatures().len() + 1);
+        signers.insert(certificate.author());
+        for signature in certificate.signatures() {
+            if !committee_lookback.is_committee_member(signature.to_address()) {
+                return true;
+            }
+            signers.insert(signature.to_address());
+        }
+        if !committee_lookback.is_quorum_threshold_reached(&signers) {
+            return true;
+        }
+        // Ensure the number of previous certificate IDs is at or below the number of committee members.
+        let previous_round = certificate.round().saturating_sub(1);
+        match self.ledger.get_committee_lookback_for_round(previous_round) {
+            Ok(previous_committee_lookback) => {
+                certificate.batch_header().previous_certificate_ids().len() > previous_committee_lookback.num_members()
+            }
+            Err(_) => false,
+        }
+    }
+
     /// Inserts the given `certificate` into storage.
     ///
     /// This method triggers updates to the `rounds`, `certificates`, `batch_ids`, and `transmissions` maps.
@@ -685,6 +829,61 @@ impl<N: Network> Storage<N> {
             error!("Failed to insert certificate '{certificate_id}' from block {} - {error}", block.height());
         }
     }
+
+    /// Returns a GraphViz (DOT) representation of the DAG over the given number of most recent rounds in storage.
+    /// The nodes are the certificates, labeled by author and round, and the edges are the references to the
+    /// previous certificates within those rounds. The certificates committed to the ledger are styled distinctly.
+    pub fn export_dot(&self, num_rounds: u64) -> String {
+        self.export_dot_with(num_rounds, |certificate_id| {
+            self.ledger.contains_certificate(&certificate_id).unwrap_or(false)
+        })
+    }
+
+    /// Returns a GraphViz (DOT) representation of the DAG over the given number of most recent rounds in storage,
+    /// where the given function determines if a certificate is committed.
+    fn export_dot_with(&self, num_rounds: u64, is_committed: impl Fn(Field<N>) -> bool) -> String {
+        // Snapshot the certificates to export, in round order, so that the locks are not held across the lookups.
+        let exported = {
+            let rounds = self.rounds.read();
+            let certificates = self.certificates.read();
+            // Determine the rounds to export, which are bounded by the given number of rounds.
+            let latest_round = rounds.keys().max().copied().unwrap_or(0);
+            let start_round = latest_round.saturating_sub(num_rounds.saturating_sub(1));
+            let mut rounds_to_export = rounds.keys().copied().filter(|round| *round >= start_round).collect::<Vec<_>>();
+            rounds_to_export.sort_unstable();
+            if num_rounds == 0 {
+                rounds_to_export.clear();
+            }
+            rounds_to_export
+                .iter()
+                .flat_map(|round| rounds.get(round).into_iter().flatten())
+                .filter_map(|(certificate_id, _, _)| certificates.get(certificate_id))
+                .map(|certificate| {
+                    let previous_ids = certificate.previous_certificate_ids().iter().copied().collect::<Vec<_>>();
+                    (certificate.id(), certificate.author(), certificate.round(), previous_ids)
+                })
+                .collect::<Vec<_>>()
+        };
+        let exported_ids = exported.iter().map(|(certificate_id, ..)| *certificate_id).collect::<HashSet<_>>();
+
+        let mut dot = String::from("digraph DAG {\n    rankdir=BT;\n    node [shape=box];\n");
+        // Write the nodes.
+        for (certificate_id, author, round, _) in &exported {
+            let style = match is_committed(*certificate_id) {
+                true => ", style=filled, fillcolor=palegreen",
+                false => "",
+            };
+            dot.push_str(&format!("    \"{certificate_id}\" [label=\"{}\\nround {round}\"{style}];\n", fmt_id(author)));
+        }
+        // Write the edges, for the references within the exported rounds.
+        for (certificate_id, _, _, previous_ids) in &exported {
+            for previous_id in previous_ids.iter().filter(|id| exported_ids.contains(*id)) {
+                dot.push_str(&format!("    \"{certificate_id}\" -> \"{previous_id}\";\n"));
+            }
+        }
+        dot.push_str("}\n");
+        dot
+    }
 }
 
 #[cfg(test)]
@@ -825,6 +1024,53 @@ mod tests {
 
     // TODO (howardwu): Testing with 'max_gc_rounds' set to '0' should ensure everything is cleared after insertion.
 
+    #[test]
+    fn test_export_dot() {
+        let rng = &mut TestRng::default();
+
+        // Sample a committee.
+        let committee = snarkvm::ledger::committee::test_helpers::sample_committee(rng);
+        // Initialize the ledger.
+        let ledger = Arc::new(MockLedgerService::new(committee));
+        // Initialize the storage.
+        let storage = Storage::<CurrentNetwork>::new(ledger, Arc::new(BFTMemoryService::new()), 10);
+
+        // Sample a certificate for round 3, and its previous certificates for round 2.
+        let (certificate, previous_certificates) =
+            snarkvm::ledger::narwhal::batch_certificate::test_helpers::sample_batch_certificate_with_previous_certificates(
+                3, rng,
+            );
+        for previous_certificate in &previous_certificates {
+            storage.testing_only_insert_certificate_testing_only(previous_certificate.clone());
+        }
+        storage.testing_only_insert_certificate_testing_only(certificate.clone());
+
+        // Export the DAG, where the first previous certificate is committed.
+        let committed_id = previous_certificates[0].id();
+        let dot = storage.export_dot_with(2, |certificate_id| certificate_id == committed_id);
+        assert!(dot.starts_with("digraph DAG {"));
+        // Ensure the certificates are nodes, labeled by author and round.
+        for c in std::iter::once(&certificate).chain(&previous_certificates) {
+            let node = format!("\"{}\" [label=\"{}\\nround {}\"", c.id(), fmt_id(c.author()), c.round());
+            assert!(dot.contains(&node), "Missing node for certificate '{}'", c.id());
+        }
+        // Ensure the committed certificate is styled distinctly.
+        assert!(dot.contains(&format!("\\nround 2\", style=filled, fillcolor=palegreen];")));
+        assert_eq!(dot.matches("fillcolor=palegreen").count(), 1);
+        // Ensure the references are edges.
+        for previous_id in certificate.previous_certificate_ids() {
+            assert!(dot.contains(&format!("\"{}\" -> \"{previous_id}\";", certificate.id())));
+        }
+        assert_eq!(dot.matches(" -> ").count(), certificate.previous_certificate_ids().len());
+
+        // Ensure the number of rounds bounds the output, without dangling edges.
+        let dot = storage.export_dot(1);
+        assert!(dot.contains(&format!("\"{}\"", certificate.id())));
+        assert!(!dot.contains(&format!("\"{committed_id}\"")));
+        assert!(!dot.contains(" -> "));
+        assert_eq!(storage.export_dot(0), "digraph DAG {\n    rankdir=BT;\n    node [shape=box];\n}\n");
+    }
+
     #[test]
     fn test_certificate_insert_remove() {
         let rng = &mut TestRng::default();
@@ -946,6 +1192,64 @@ mod tests {
         // Check that the underlying storage representation remains unchanged.
         assert_storage(&storage, &rounds, &certificates, &batch_ids, &transmissions);
     }
+
+    #[test]
+    fn test_gc_rounds_under_memory_pressure() {
+        let rng = &mut TestRng::default();
+
+        // Simulates a memory usage above the high watermark.
+        fn high_memory() -> Option<u64> {
+            Some(2000)
+        }
+        // Simulates a memory usage below the low watermark.
+        fn low_memory() -> Option<u64> {
+            Some(100)
+        }
+
+        // Sample a committee.
+        let committee = snarkvm::ledger::committee::test_helpers::sample_committee(rng);
+        // Initialize the ledger.
+        let ledger = Arc::new(MockLedgerService::new(committee));
+        // Initialize the storage.
+        let max_gc_rounds = 10;
+        let storage = Storage::<CurrentNetwork>::new(ledger, Arc::new(BFTMemoryService::new()), max_gc_rounds);
+        assert_eq!(storage.effective_gc_rounds(), max_gc_rounds);
+
+        // Ensure the policy must retain at least the maximum GC rounds.
+        let policy = GCMemoryPolicy::new_with_probe(500, 1000, max_gc_rounds - 1, low_memory).unwrap();
+        assert!(storage.set_gc_memory_policy(Some(policy)).is_err());
+
+        // Simulate low memory usage.
+        let policy = GCMemoryPolicy::new_with_probe(500, 1000, 30, low_memory).unwrap();
+        storage.set_gc_memory_policy(Some(policy)).unwrap();
+
+        // Advance the rounds, and ensure extra rounds are retained up to the maximum retained rounds.
+        let starting_round = storage.current_round();
+        for round in starting_round + 1..=starting_round + 40 {
+            storage.update_current_round(round);
+        }
+        let relaxed_round = storage.current_round();
+        assert_eq!(storage.effective_gc_rounds(), 30);
+        assert_eq!(storage.gc_round(), relaxed_round - 30);
+
+        // Simulate high memory usage.
+        let policy = GCMemoryPolicy::new_with_probe(500, 1000, 30, high_memory).unwrap();
+        storage.set_gc_memory_policy(Some(policy)).unwrap();
+
+        // Advance the rounds, and ensure the extra rounds are released down to the maximum GC rounds.
+        for round in relaxed_round + 1..=relaxed_round + 40 {
+            storage.update_current_round(round);
+            // Ensure the GC rounds never drop below the maximum GC rounds.
+            assert!(storage.effective_gc_rounds() >= max_gc_rounds);
+            assert!(storage.gc_round() <= round - max_gc_rounds);
+        }
+        assert_eq!(storage.effective_gc_rounds(), max_gc_rounds);
+        assert_eq!(storage.gc_round(), storage.current_round() - max_gc_rounds);
+
+        // Remove the policy, and ensure the GC rounds are reset.
+        storage.set_gc_memory_policy(None).unwrap();
+        assert_eq!(storage.effective_gc_rounds(), max_gc_rounds);
+    }
 }
 
 #[cfg(test)]
@@ -1180,6 +1484,10 @@ pub mod prop_tests {
         // Check that the underlying storage representation is correct.
         assert_storage(&storage, &rounds, &certificates, &batch_ids, &internal_transmissions);
 
+        // Ensure a duplicate insertion fails, but the certificate is not malformed.
+        assert!(storage.insert_certificate(certificate.clone(), Default::default()).is_err());
+        assert!(!storage.is_malformed_certificate(&certificate));
+
         // Insert the certificate again - without any missing transmissions.
         storage.insert_certificate_atomic(certificate.clone(), Default::default());
         // Ensure the certificate exists in storage.
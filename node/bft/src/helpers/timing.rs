@@ -0,0 +1,225 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkOS library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use anyhow::Result;
+use parking_lot::Mutex;
+use std::{
+    collections::BTreeMap,
+    fs::{File, OpenOptions},
+    io::{BufWriter, Write},
+    path::Path,
+};
+use time::OffsetDateTime;
+
+/// The maximum number of uncommitted rounds to track the timing of.
+const MAX_TRACKED_ROUNDS: usize = 1 << 10;
+
+/// Returns the current UNIX timestamp in milliseconds.
+fn now_in_ms() -> i64 {
+    (OffsetDateTime::now_utc().unix_timestamp_nanos() / 1_000_000) as i64
+}
+
+/// The consensus timing of a round, as UNIX timestamps in milliseconds.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct RoundTiming {
+    /// The round number.
+    pub round: u64,
+    /// The time at which the round was opened, i.e. the BFT advanced to it.
+    pub opened_at: Option<i64>,
+    /// The time at which the certificates of the round reached the quorum threshold.
+    pub quorum_at: Option<i64>,
+    /// The time at which the certificate of the leader (anchor) of the round appeared, for even rounds.
+    pub anchor_at: Option<i64>,
+    /// The time at which the certificates of the round were committed in a subdag.
+    pub committed_at: Option<i64>,
+}
+
+impl RoundTiming {
+    /// The header of the CSV sink.
+    pub const CSV_HEADER: &'static str = "round,opened_at,quorum_at,anchor_at,committed_at";
+
+    /// Returns the round timing as a row of the CSV sink, where the missing timestamps are left empty.
+    pub fn to_csv_row(&self) -> String {
+        let fmt = |timestamp: Option<i64>| timestamp.map(|t| t.to_string()).unwrap_or_default();
+        format!(
+            "{},{},{},{},{}",
+            self.round,
+            fmt(self.opened_at),
+            fmt(self.quorum_at),
+            fmt(self.anchor_at),
+            fmt(self.committed_at)
+        )
+    }
+}
+
+/// The recorder of the per-round consensus timing. A record is emitted (as a structured log, and to
+/// the CSV sink if one is set) once the round is committed. Only a few timestamps are taken per round.
+#[derive(Debug, Default)]
+pub struct RoundTimings {
+    /// The timing of the uncommitted rounds.
+    rounds: Mutex<BTreeMap<u64, RoundTiming>>,
+    /// The CSV sink, if one is set.
+    csv_sink: Mutex<Option<BufWriter<File>>>,
+}
+
+impl RoundTimings {
+    /// Sets the CSV file to append the round timing records to, writing the header if the file is empty.
+    /// If `None` is given, the records are only emitted as structured logs.
+    pub fn set_csv_sink(&self, path: Option<&Path>) -> Result<()> {
+        let sink = match path {
+            Some(path) => {
+                let file = OpenOptions::new().create(true).append(true).open(path)?;
+                let is_empty = file.metadata()?.len() == 0;
+                let mut sink = BufWriter::new(file);
+                if is_empty {
+                    writeln!(sink, "{}", RoundTiming::CSV_HEADER)?;
+                    sink.flush()?;
+                }
+                Some(sink)
+            }
+            None => None,
+        };
+        *self.csv_sink.lock() = sink;
+        Ok(())
+    }
+
+    /// Records the time at which the given round was opened.
+    pub fn open(&self, round: u64) {
+        self.update(round, |timing| timing.opened_at = timing.opened_at.or(Some(now_in_ms())));
+    }
+
+    /// Records the time at which the certificates of the given round reached the quorum threshold.
+    pub fn quorum(&self, round: u64) {
+        self.update(round, |timing| timing.quorum_at = timing.quorum_at.or(Some(now_in_ms())));
+    }
+
+    /// Records the time at which the leader certificate of the given round appeared.
+    pub fn anchor(&self, round: u64) {
+        self.update(round, |timing| timing.anchor_at = timing.anchor_at.or(Some(now_in_ms())));
+    }
+
+    /// Returns `true` if the certificates of the given round were recorded as reaching the quorum threshold.
+    pub fn has_quorum(&self, round: u64) -> bool {
+        self.rounds.lock().get(&round).map_or(false, |timing| timing.quorum_at.is_some())
+    }
+
+    /// Returns `true` if the leader certificate of the given round was recorded.
+    pub fn has_anchor(&self, round: u64) -> bool {
+        self.rounds.lock().get(&round).map_or(false, |timing| timing.anchor_at.is_some())
+    }
+
+    /// Records the commit of the given round, along with every earlier round (as their certificates are
+    /// committed in the same subdag), and emits their records. Returns the emitted records.
+    pub fn commit(&self, round: u64) -> Vec<RoundTiming> {
+        let committed_at = now_in_ms();
+        // Remove the committed rounds.
+        let records = {
+            let mut rounds = self.rounds.lock();
+            let retained = rounds.split_off(&round.saturating_add(1));
+            std::mem::replace(&mut *rounds, retained)
+        };
+        let records = records
+            .into_values()
+            .map(|timing| RoundTiming { committed_at: timing.committed_at.or(Some(committed_at)), ..timing })
+            .collect::<Vec<_>>();
+        // Emit the records.
+        for timing in &records {
+            debug!(
+                round = timing.round,
+                opened_at = timing.opened_at,
+                quorum_at = timing.quorum_at,
+                anchor_at = timing.anchor_at,
+                committed_at = timing.committed_at,
+                "Round timing"
+            );
+        }
+        if let Some(sink) = self.csv_sink.lock().as_mut() {
+            let result = records.iter().try_for_each(|timing| writeln!(sink, "{}", timing.to_csv_row()));
+            if let Err(e) = result.and_then(|_| sink.flush()) {
+                warn!("Failed to write the round timing to the CSV sink - {e}");
+            }
+        }
+        records
+    }
+
+    /// Updates the timing of the given round.
+    fn update(&self, round: u64, f: impl FnOnce(&mut RoundTiming)) {
+        let mut rounds = self.rounds.lock();
+        f(rounds.entry(round).or_insert(RoundTiming { round, ..Default::default() }));
+        // Ensure the uncommitted rounds do not exceed the maximum, by dropping the oldest.
+        while rounds.len() > MAX_TRACKED_ROUNDS {
+            rounds.pop_first();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_round_timing_is_recorded() {
+        let timings = RoundTimings::default();
+
+        // Record the timing of two rounds.
+        timings.open(1);
+        timings.quorum(1);
+        timings.open(2);
+        timings.anchor(2);
+        assert!(timings.has_anchor(2));
+        assert!(!timings.has_quorum(2));
+        timings.quorum(2);
+        assert!(timings.has_quorum(2));
+        timings.open(3);
+
+        // Ensure the committed rounds are emitted, with the expected timing fields.
+        let records = timings.commit(2);
+        assert_eq!(records.len(), 2);
+        let [first, second] = [records[0], records[1]];
+        assert_eq!(first.round, 1);
+        assert!(first.anchor_at.is_none());
+        assert_eq!(second.round, 2);
+        let (opened_at, quorum_at) = (second.opened_at.unwrap(), second.quorum_at.unwrap());
+        let (anchor_at, committed_at) = (second.anchor_at.unwrap(), second.committed_at.unwrap());
+        assert!(first.opened_at.unwrap() <= opened_at);
+        assert!(opened_at <= anchor_at && anchor_at <= quorum_at && quorum_at <= committed_at);
+        // Ensure the rounds committed together share the commit time.
+        assert_eq!(first.committed_at, second.committed_at);
+
+        // Ensure the uncommitted round is retained.
+        assert!(!timings.has_quorum(1));
+        assert_eq!(timings.commit(3).len(), 1);
+        assert!(timings.commit(3).is_empty());
+    }
+
+    #[test]
+    fn test_round_timing_csv_sink() {
+        let path = std::env::temp_dir().join(format!("round-timing-{}.csv", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+        let timings = RoundTimings::default();
+        timings.set_csv_sink(Some(&path)).unwrap();
+
+        // Commit a round, and ensure it is written to the CSV sink.
+        timings.open(2);
+        let record = timings.commit(2)[0];
+        let contents = std::fs::read_to_string(&path).unwrap();
+        assert_eq!(contents, format!("{}\n{}\n", RoundTiming::CSV_HEADER, record.to_csv_row()));
+        assert!(record.to_csv_row().starts_with(&format!("2,{},,,", record.opened_at.unwrap())));
+
+        // Ensure the header is not repeated when the sink is reopened.
+        timings.set_csv_sink(Some(&path)).unwrap();
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), contents);
+        std::fs::remove_file(&path).unwrap();
+    }
+}
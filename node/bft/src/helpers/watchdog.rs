@@ -0,0 +1,262 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkOS library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use indexmap::IndexMap;
+use parking_lot::{Mutex, RwLock};
+use std::{
+    sync::{
+        atomic::{AtomicI64, Ordering},
+        Arc,
+    },
+    time::Duration,
+};
+use time::OffsetDateTime;
+use tokio::task::JoinHandle;
+
+/// The interval at which the watchdog checks the heartbeats of the subsystems.
+pub const WATCHDOG_CHECK_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Returns the current UNIX timestamp in milliseconds.
+fn now_in_ms() -> i64 {
+    (OffsetDateTime::now_utc().unix_timestamp_nanos() / 1_000_000) as i64
+}
+
+/// The action of the watchdog on a subsystem that missed its heartbeat.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub enum WatchdogAction {
+    /// The task of the subsystem is aborted, and the subsystem is started again.
+    ///
+    /// Note: A task that is blocked outside of an `.await` (e.g. deadlocked on a lock) cannot be aborted,
+    /// so `Exit` is preferable when the node runs under an external supervisor.
+    #[default]
+    Restart,
+    /// The process exits, so that an external supervisor restarts the node.
+    Exit,
+}
+
+/// The heartbeat signal of a subsystem, which the subsystem beats while it makes progress.
+#[derive(Clone, Debug)]
+pub struct Heartbeat {
+    /// The UNIX timestamp (in milliseconds) of the latest beat.
+    last_beat_in_ms: Arc<AtomicI64>,
+}
+
+impl Default for Heartbeat {
+    /// Initializes a new heartbeat, which beats on initialization.
+    fn default() -> Self {
+        Self { last_beat_in_ms: Arc::new(AtomicI64::new(now_in_ms())) }
+    }
+}
+
+impl Heartbeat {
+    /// Signals that the subsystem is making progress.
+    pub fn beat(&self) {
+        self.last_beat_in_ms.store(now_in_ms(), Ordering::Relaxed);
+    }
+
+    /// Returns the time since the latest beat.
+    pub fn elapsed(&self) -> Duration {
+        let elapsed_in_ms = now_in_ms().saturating_sub(self.last_beat_in_ms.load(Ordering::Relaxed));
+        Duration::from_millis(u64::try_from(elapsed_in_ms).unwrap_or_default())
+    }
+}
+
+/// The function that starts the task of a subsystem, which beats the given heartbeat.
+type StartFn = Box<dyn Fn(Heartbeat) -> JoinHandle<()> + Send + Sync>;
+
+/// A subsystem that is monitored by the watchdog.
+struct Subsystem {
+    /// The heartbeat of the running task.
+    heartbeat: Heartbeat,
+    /// The handle of the running task.
+    handle: JoinHandle<()>,
+    /// The function that starts the task.
+    start: StartFn,
+    /// The number of times the subsystem was restarted.
+    num_restarts: u64,
+}
+
+/// The self-monitoring watchdog, which detects the critical tasks that stall (i.e. miss their heartbeat)
+/// and takes the configured action on them.
+#[derive(Default)]
+pub struct Watchdog {
+    /// The maximum time between the heartbeats of a subsystem, if the watchdog is enabled.
+    timeout: RwLock<Option<Duration>>,
+    /// The action on a stalled subsystem.
+    action: RwLock<WatchdogAction>,
+    /// The monitored subsystems.
+    subsystems: Mutex<IndexMap<&'static str, Subsystem>>,
+}
+
+impl Watchdog {
+    /// Returns the maximum time between the heartbeats of a subsystem, if the watchdog is enabled.
+    pub fn timeout(&self) -> Option<Duration> {
+        *self.timeout.read()
+    }
+
+    /// Sets the maximum time between the heartbeats of a subsystem. If `None` is given, the watchdog is disabled.
+    pub fn set_timeout(&self, timeout: Option<Duration>) {
+        *self.timeout.write() = timeout;
+    }
+
+    /// Returns the action on a stalled subsystem.
+    pub fn action(&self) -> WatchdogAction {
+        *self.action.read()
+    }
+
+    /// Sets the action on a stalled subsystem.
+    pub fn set_action(&self, action: WatchdogAction) {
+        *self.action.write() = action;
+    }
+
+    /// Starts the given subsystem, and monitors it. The `start` function spawns the task of the subsystem,
+    /// and is called again to restart it.
+    pub fn register(&self, name: &'static str, start: impl Fn(Heartbeat) -> JoinHandle<()> + Send + Sync + 'static) {
+        let heartbeat = Heartbeat::default();
+        let handle = start(heartbeat.clone());
+        let subsystem = Subsystem { heartbeat, handle, start: Box::new(start), num_restarts: 0 };
+        // If the subsystem was already registered, abort its previous task.
+        if let Some(previous) = self.subsystems.lock().insert(name, subsystem) {
+            previous.handle.abort();
+        }
+    }
+
+    /// Returns the number of times the given subsystem was restarted, if it is registered.
+    pub fn num_restarts(&self, name: &str) -> Option<u64> {
+        self.subsystems.lock().get(name).map(|subsystem| subsystem.num_restarts)
+    }
+
+    /// Checks the heartbeats of the subsystems, and returns the names of the stalled ones.
+    ///
+    /// If the action is `Restart`, the stalled subsystems are restarted. If the action is `Exit`,
+    /// the caller is responsible for exiting the process.
+    pub fn check(&self) -> Vec<&'static str> {
+        // If the watchdog is disabled, return early.
+        let Some(timeout) = self.timeout() else {
+            return vec![];
+        };
+        let action = self.action();
+
+        let mut stalled = vec![];
+        for (name, subsystem) in self.subsystems.lock().iter_mut() {
+            // Skip the subsystem if it beat within the timeout.
+            let elapsed = subsystem.heartbeat.elapsed();
+            if elapsed <= timeout {
+                continue;
+            }
+            // Log the diagnostic of the stalled subsystem.
+            let state = match subsystem.handle.is_finished() {
+                true => "the task has ended",
+                false => "the task is still running",
+            };
+            error!(
+                "Watchdog - '{name}' missed its heartbeat for {}ms (timeout {}ms, {state}, {} restarts) - {action:?}",
+                elapsed.as_millis(),
+                timeout.as_millis(),
+                subsystem.num_restarts,
+            );
+            // Restart the subsystem, if configured.
+            if action == WatchdogAction::Restart {
+                subsystem.handle.abort();
+                subsystem.heartbeat = Heartbeat::default();
+                subsystem.handle = (subsystem.start)(subsystem.heartbeat.clone());
+                subsystem.num_restarts += 1;
+            }
+            stalled.push(*name);
+        }
+        stalled
+    }
+
+    /// Aborts the tasks of the subsystems, and stops monitoring them.
+    pub fn shut_down(&self) {
+        self.subsystems.lock().drain(..).for_each(|(_, subsystem)| subsystem.handle.abort());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::sync::atomic::AtomicUsize;
+
+    const TIMEOUT: Duration = Duration::from_millis(100);
+
+    /// Registers a subsystem that beats once at start, and then stalls; returns the counter of its starts.
+    fn register_stalled(watchdog: &Watchdog, name: &'static str) -> Arc<AtomicUsize> {
+        let num_starts = Arc::new(AtomicUsize::new(0));
+        let num_starts_ = num_starts.clone();
+        watchdog.register(name, move |heartbeat| {
+            num_starts_.fetch_add(1, Ordering::SeqCst);
+            tokio::spawn(async move {
+                heartbeat.beat();
+                std::future::pending::<()>().await
+            })
+        });
+        num_starts
+    }
+
+    /// Registers a subsystem that beats well within the timeout.
+    fn register_healthy(watchdog: &Watchdog, name: &'static str) {
+        watchdog.register(name, |heartbeat| {
+            tokio::spawn(async move {
+                loop {
+                    heartbeat.beat();
+                    tokio::time::sleep(TIMEOUT / 10).await;
+                }
+            })
+        });
+    }
+
+    #[tokio::test]
+    async fn test_watchdog_restarts_stalled_subsystem() {
+        let watchdog = Watchdog::default();
+        let num_starts = register_stalled(&watchdog, "stalled");
+        register_healthy(&watchdog, "healthy");
+
+        // Ensure a disabled watchdog does not detect the stalled subsystem.
+        tokio::time::sleep(2 * TIMEOUT).await;
+        assert!(watchdog.check().is_empty());
+
+        // Enable the watchdog, and ensure only the stalled subsystem is detected and restarted.
+        watchdog.set_timeout(Some(TIMEOUT));
+        assert_eq!(watchdog.check(), vec!["stalled"]);
+        assert_eq!(num_starts.load(Ordering::SeqCst), 2);
+        assert_eq!(watchdog.num_restarts("stalled"), Some(1));
+        assert_eq!(watchdog.num_restarts("healthy"), Some(0));
+
+        // Ensure the restarted subsystem is not detected until it misses its heartbeat again.
+        assert!(watchdog.check().is_empty());
+        tokio::time::sleep(2 * TIMEOUT).await;
+        assert_eq!(watchdog.check(), vec!["stalled"]);
+        assert_eq!(watchdog.num_restarts("stalled"), Some(2));
+
+        // Ensure the subsystems are no longer monitored after shutting down.
+        watchdog.shut_down();
+        assert_eq!(watchdog.num_restarts("stalled"), None);
+    }
+
+    #[tokio::test]
+    async fn test_watchdog_exit_does_not_restart() {
+        let watchdog = Watchdog::default();
+        watchdog.set_timeout(Some(TIMEOUT));
+        watchdog.set_action(WatchdogAction::Exit);
+        let num_starts = register_stalled(&watchdog, "stalled");
+
+        // Ensure the stalled subsystem is detected, and left to the caller to exit.
+        tokio::time::sleep(2 * TIMEOUT).await;
+        assert_eq!(watchdog.check(), vec!["stalled"]);
+        assert_eq!(num_starts.load(Ordering::SeqCst), 1);
+        assert_eq!(watchdog.num_restarts("stalled"), Some(0));
+    }
+}
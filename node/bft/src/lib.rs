@@ -53,8 +53,17 @@ pub const MAX_GC_ROUNDS: u64 = 50; // rounds
 
 /// The maximum number of milliseconds to wait before proposing a batch.
 pub const MAX_BATCH_DELAY_IN_MS: u64 = 2500; // ms
+/// The maximum number of milliseconds to wait for the rounds of peers after a restart, before proposing a batch.
+pub const MAX_RESUME_DELAY_IN_MS: u64 = 2 * PRIMARY_PING_IN_MS; // ms
 /// The maximum number of seconds allowed for the leader to send their certificate.
 pub const MAX_LEADER_CERTIFICATE_DELAY_IN_SECS: i64 = 2 * MAX_BATCH_DELAY_IN_MS as i64 / 1000; // seconds
+/// The largest configurable minimum interval between rounds, which keeps the round pacing from harming liveness.
+pub const MAX_MIN_ROUND_INTERVAL_IN_MS: u64 = MAX_BATCH_DELAY_IN_MS / 10; // ms
+/// The default maximum number of rounds ahead of the latest committed round to buffer, before deferring to block sync.
+pub const MAX_OPEN_ROUNDS: u64 = 2 * MAX_GC_ROUNDS; // rounds
+/// The largest configurable window over which batch signature verifications are accumulated, which keeps the
+/// batching from delaying certificate formation.
+pub const MAX_SIGNATURE_BATCH_WINDOW_IN_MS: u64 = 10; // ms
 /// The maximum number of seconds before the timestamp is considered expired.
 pub const MAX_TIMESTAMP_DELTA_IN_SECS: i64 = 10; // seconds
 /// The maximum number of transmissions allowed in a batch.
@@ -64,8 +73,13 @@ pub const MAX_TRANSMISSIONS_PER_WORKER_PING: usize = MAX_TRANSMISSIONS_PER_BATCH
 /// The maximum number of workers that can be spawned.
 pub const MAX_WORKERS: u8 = 1; // workers
 
+/// The number of seconds without a round advancement, after which the primary is considered stuck.
+pub const STUCK_ROUND_IN_SECS: u64 = 24 * MAX_BATCH_DELAY_IN_MS / 1000; // seconds
+
 /// The frequency at which each primary broadcasts a ping to every other node.
 pub const PRIMARY_PING_IN_MS: u64 = 4 * MAX_BATCH_DELAY_IN_MS; // ms
+/// The minimum interval at which a peer is sent a primary ping to direct it to sync, for its stale proposals.
+pub const STALE_PROPOSAL_SYNC_IN_MS: u64 = PRIMARY_PING_IN_MS; // ms
 /// The frequency at which each worker broadcasts a ping to every other node.
 pub const WORKER_PING_IN_MS: u64 = 4 * MAX_BATCH_DELAY_IN_MS; // ms
 
@@ -15,17 +15,40 @@
 use crate::{
     events::{BatchPropose, BatchSignature, Event},
     helpers::{
-        assign_to_worker,
         assign_to_workers,
+        check_worker_assignment,
+        ensure_quorum_intersection,
         fmt_id,
         init_sync_channels,
         init_worker_channels,
+        is_epoch_transition,
         now,
+        send_with_retry,
+        worker_assignment,
+        AdmissionReceipt,
+        AlertEvent,
+        AlertNotifier,
         BFTSender,
+        EpochTracker,
+        EpochTransitionPolicy,
+        EquivocationEvidence,
+        EquivocationTracker,
+        Heartbeat,
+        InclusionProof,
         PrimaryReceiver,
         PrimarySender,
         Proposal,
+        ProposalContents,
+        RetryPolicy,
+        SenderRateLimit,
+        StaleProposalPolicy,
         Storage,
+        Watchdog,
+        WatchdogAction,
+        verify_batch,
+        MAX_SIGNATURE_BATCH_SIZE,
+        UNKNOWN_WORKER_ID,
+        WATCHDOG_CHECK_INTERVAL,
     },
     spawn_blocking,
     Gateway,
@@ -33,9 +56,15 @@ use crate::{
     Transport,
     Worker,
     MAX_BATCH_DELAY_IN_MS,
+    MAX_MIN_ROUND_INTERVAL_IN_MS,
+    MAX_OPEN_ROUNDS,
+    MAX_RESUME_DELAY_IN_MS,
+    MAX_SIGNATURE_BATCH_WINDOW_IN_MS,
     MAX_TRANSMISSIONS_PER_BATCH,
     MAX_WORKERS,
     PRIMARY_PING_IN_MS,
+    STALE_PROPOSAL_SYNC_IN_MS,
+    STUCK_ROUND_IN_SECS,
     WORKER_PING_IN_MS,
 };
 use snarkos_account::Account;
@@ -48,6 +77,7 @@ use snarkvm::{
         types::{Address, Field},
     },
     ledger::{
+        authority::Authority,
         block::Transaction,
         coinbase::{ProverSolution, PuzzleCommitment},
         narwhal::{BatchCertificate, BatchHeader, Data, Transmission, TransmissionID},
@@ -63,11 +93,14 @@ use std::{
     collections::{HashMap, HashSet},
     future::Future,
     net::SocketAddr,
-    sync::Arc,
-    time::Duration,
+    sync::{
+        atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering},
+        Arc,
+    },
+    time::{Duration, Instant},
 };
 use tokio::{
-    sync::{Mutex as TMutex, OnceCell},
+    sync::{Mutex as TMutex, Notify, OnceCell},
     task::JoinHandle,
 };
 
@@ -92,10 +125,64 @@ pub struct Primary<N: Network> {
     proposed_batch: Arc<ProposedBatch<N>>,
     /// The recently-signed batch proposals (a map from the address to the round, batch ID, and signature).
     signed_proposals: Arc<RwLock<HashMap<Address<N>, (u64, Field<N>, Signature<N>)>>>,
+    /// The tracker of the signed batch headers from each validator, to detect equivocations.
+    equivocations: Arc<EquivocationTracker<N>>,
     /// The spawned handles.
     handles: Arc<Mutex<Vec<JoinHandle<()>>>>,
     /// The lock for propose_batch.
     propose_lock: Arc<TMutex<u64>>,
+    /// The flag indicating whether the primary is a warm standby (i.e. it tracks consensus without signing).
+    standby: Arc<AtomicBool>,
+    /// The latest round in which a standby primary observed activity from its own address.
+    self_activity_round: Arc<AtomicU64>,
+    /// The flag indicating whether the primary is an observer (i.e. it was removed from the committee).
+    observer: Arc<AtomicBool>,
+    /// The retry policy for broadcasting our certificates to peers.
+    certificate_retry_policy: Arc<RwLock<RetryPolicy>>,
+    /// The handling of the transmissions of a batch proposal that is abandoned at an epoch transition.
+    epoch_transition_policy: Arc<RwLock<EpochTransitionPolicy>>,
+    /// The tracker of the current committee epoch.
+    epoch_tracker: Arc<EpochTracker<N>>,
+    /// The committee that the primary last refused to participate in, for breaking quorum intersection.
+    refused_committee: Arc<Mutex<Option<Committee<N>>>>,
+    /// The handling of the batch proposals that reference certificates at or below the GC round.
+    stale_proposal_policy: Arc<RwLock<StaleProposalPolicy>>,
+    /// The time at which each peer was last directed to sync, for a stale proposal.
+    stale_proposal_syncs: Arc<Mutex<HashMap<SocketAddr, Instant>>>,
+    /// The maximum fraction of stake that may change in a committee transition, if quorum intersection is checked.
+    max_committee_change: Arc<RwLock<Option<f64>>>,
+    /// The maximum number of rounds ahead of the latest committed round to buffer, before deferring to block sync.
+    max_open_rounds: Arc<AtomicU64>,
+    /// The fraction of committee stake that must be connected, below which the primary is partitioned (if set).
+    partition_threshold: Arc<RwLock<Option<f64>>>,
+    /// The flag indicating whether the primary is partitioned (i.e. it is connected to too little committee stake).
+    partitioned: Arc<AtomicBool>,
+    /// The minimum stake of a validator, for its certificates from peers to be processed locally (`0` for no minimum).
+    min_certificate_stake: Arc<AtomicU64>,
+    /// The minimum interval (in milliseconds) between round advancements (`0` for no minimum).
+    min_round_interval_in_ms: Arc<AtomicU64>,
+    /// The minimum number of previous certificates for a batch to reference, when available (`0` for the quorum).
+    min_previous_certificates: Arc<AtomicUsize>,
+    /// The time at which the primary last advanced to the next round.
+    last_round_advance: Arc<Mutex<Option<Instant>>>,
+    /// The flag indicating whether the primary is stuck (i.e. it has not advanced its round for too long).
+    stuck: Arc<AtomicBool>,
+    /// The latest round in which each validator was observed with a certificate.
+    peer_rounds: Arc<RwLock<HashMap<Address<N>, u64>>>,
+    /// The time at which the primary started resuming after a restart, until it has caught up to the network round.
+    resuming_since: Arc<Mutex<Option<Instant>>>,
+    /// The window over which the batch signatures are accumulated, if signature verification batching is enabled.
+    signature_batch_window: Arc<RwLock<Option<Duration>>>,
+    /// The watchdog, which monitors the heartbeats of the critical tasks.
+    watchdog: Arc<Watchdog>,
+    /// The heartbeat of the running batch proposer, which is also beaten while a batch is being proposed.
+    proposer_heartbeat: Arc<RwLock<Option<Heartbeat>>>,
+    /// The notification that the watchdog requests the node to exit, as a critical task stalled.
+    watchdog_exit: Arc<Notify>,
+    /// The webhook notifier for the critical events, if alerting is enabled.
+    alert_notifier: Arc<RwLock<Option<AlertNotifier>>>,
+    /// The rate limit on the transactions admitted from any single sender, which is shared by the workers.
+    sender_rate_limit: Arc<RwLock<Option<Arc<SenderRateLimit<N>>>>>,
 }
 
 impl<N: Network> Primary<N> {
@@ -122,8 +209,35 @@ impl<N: Network> Primary<N> {
             bft_sender: Default::default(),
             proposed_batch: Default::default(),
             signed_proposals: Default::default(),
+            equivocations: Default::default(),
             handles: Default::default(),
             propose_lock: Default::default(),
+            standby: Default::default(),
+            self_activity_round: Default::default(),
+            observer: Default::default(),
+            certificate_retry_policy: Default::default(),
+            epoch_transition_policy: Default::default(),
+            epoch_tracker: Default::default(),
+            refused_committee: Default::default(),
+            stale_proposal_policy: Default::default(),
+            stale_proposal_syncs: Default::default(),
+            max_committee_change: Default::default(),
+            max_open_rounds: Arc::new(AtomicU64::new(MAX_OPEN_ROUNDS)),
+            partition_threshold: Default::default(),
+            partitioned: Default::default(),
+            min_certificate_stake: Default::default(),
+            min_round_interval_in_ms: Default::default(),
+            min_previous_certificates: Default::default(),
+            last_round_advance: Default::default(),
+            stuck: Default::default(),
+            peer_rounds: Default::default(),
+            resuming_since: Default::default(),
+            signature_batch_window: Default::default(),
+            watchdog: Default::default(),
+            proposer_heartbeat: Default::default(),
+            watchdog_exit: Default::default(),
+            alert_notifier: Default::default(),
+            sender_rate_limit: Default::default(),
         })
     }
 
@@ -158,6 +272,8 @@ impl<N: Network> Primary<N> {
                 self.ledger.clone(),
                 self.proposed_batch.clone(),
             )?;
+            // Share the sender rate limit with the worker, if one is set.
+            worker.set_sender_rate_limit(self.sender_rate_limit());
             // Run the worker instance.
             worker.run(rx_worker);
             // Add the worker to the list of workers.
@@ -168,6 +284,11 @@ impl<N: Network> Primary<N> {
         // Set the workers.
         self.workers = Arc::from(workers);
 
+        // If the primary is restarting from an earlier round, defer proposing until the network round is known.
+        if self.current_round() > 1 {
+            *self.resuming_since.lock() = Some(Instant::now());
+        }
+
         // First, initialize the sync channels.
         let (sync_sender, sync_receiver) = init_sync_channels();
         // Next, initialize the sync module.
@@ -196,6 +317,11 @@ impl<N: Network> Primary<N> {
         &self.storage
     }
 
+    /// Returns the sync module.
+    pub const fn sync(&self) -> &Sync<N> {
+        &self.sync
+    }
+
     /// Returns the ledger.
     pub const fn ledger(&self) -> &Arc<dyn LedgerService<N>> {
         &self.ledger
@@ -211,13 +337,571 @@ impl<N: Network> Primary<N> {
         &self.workers
     }
 
+    /// Replaces the queued transaction `replaced_id` with the given unconfirmed transaction, from the same
+    /// submitter with a higher priority fee, in the worker that queued the replaced transaction.
+    pub async fn replace_unconfirmed_transaction(
+        &self,
+        replaced_id: N::TransactionID,
+        transaction_id: N::TransactionID,
+        transaction: Data<Transaction<N>>,
+    ) -> Result<Option<AdmissionReceipt<N>>> {
+        let affinity = self.gateway.worker_affinity();
+        // Ensure the replacement is assigned to the same worker, so that peers fetch it from the worker that queues it.
+        let worker_id = affinity.assign::<N>(&replaced_id, self.num_workers())?;
+        ensure!(
+            affinity.assign::<N>(&transaction_id, self.num_workers())? == worker_id,
+            "Transaction '{}' is assigned to another worker than '{}'",
+            fmt_id(transaction_id),
+            fmt_id(replaced_id)
+        );
+        self.workers[worker_id as usize].process_replacement_transaction(replaced_id, transaction_id, transaction).await
+    }
+
     /// Returns the batch proposal of our primary, if one currently exists.
     pub fn proposed_batch(&self) -> &Arc<ProposedBatch<N>> {
         &self.proposed_batch
     }
+
+    /// Returns a snapshot of the contents of the next proposal, before it is signed, if there is one.
+    /// The transmissions are selected as the workers would drain them, without draining them.
+    ///
+    /// note: The transmissions are only checked for validity once the batch is proposed, so the proposal
+    /// may skip some of the previewed transmissions.
+    pub fn current_proposal_preview(&self) -> Option<ProposalContents<N>> {
+        // If a batch is already proposed, the next batch is not proposed until it is certified or expires.
+        if self.proposed_batch.read().is_some() {
+            return None;
+        }
+        // Retrieve the current round.
+        let round = self.current_round();
+        // Select the transmissions from the workers, skipping the ones that the proposal would skip.
+        let num_transmissions_per_worker = MAX_TRANSMISSIONS_PER_BATCH / self.num_workers() as usize;
+        let transmission_ids = self
+            .workers
+            .iter()
+            .flat_map(|worker| worker.preview_drain(num_transmissions_per_worker))
+            .filter(|id| *id != TransmissionID::Ratification)
+            .filter(|id| !self.ledger.contains_transmission(id).unwrap_or(true))
+            .collect::<IndexSet<_>>();
+        // If there are no transmissions to propose, there is no proposal.
+        if transmission_ids.is_empty() {
+            return None;
+        }
+        // Retrieve the IDs of the previous certificates.
+        let previous_certificates = self.storage.get_certificates_for_round(round.saturating_sub(1));
+        let previous_certificate_ids = previous_certificates.into_iter().map(|c| c.id()).collect();
+        Some(ProposalContents { round, transmission_ids, previous_certificate_ids })
+    }
+
+    /// Returns the retry policy for broadcasting our certificates to peers.
+    pub fn certificate_retry_policy(&self) -> RetryPolicy {
+        *self.certificate_retry_policy.read()
+    }
+
+    /// Sets the retry policy for broadcasting our certificates to peers.
+    pub fn set_certificate_retry_policy(&self, policy: RetryPolicy) {
+        *self.certificate_retry_policy.write() = policy;
+    }
+
+    /// Returns the handling of the transmissions of a batch proposal that is abandoned at an epoch transition.
+    pub fn epoch_transition_policy(&self) -> EpochTransitionPolicy {
+        *self.epoch_transition_policy.read()
+    }
+
+    /// Sets the handling of the transmissions of a batch proposal that is abandoned at an epoch transition.
+    pub fn set_epoch_transition_policy(&self, policy: EpochTransitionPolicy) {
+        *self.epoch_transition_policy.write() = policy;
+    }
+
+    /// Returns the handling of the batch proposals that reference certificates at or below the GC round.
+    pub fn stale_proposal_policy(&self) -> StaleProposalPolicy {
+        *self.stale_proposal_policy.read()
+    }
+
+    /// Sets the handling of the batch proposals that reference certificates at or below the GC round.
+    pub fn set_stale_proposal_policy(&self, policy: StaleProposalPolicy) {
+        *self.stale_proposal_policy.write() = policy;
+    }
+
+    /// Returns the current committee epoch (i.e. the starting round of its first committee), if it is known.
+    pub fn epoch(&self) -> Option<u64> {
+        self.epoch_tracker.epoch()
+    }
+
+    /// Returns the maximum number of rounds ahead of the latest committed round to buffer.
+    pub fn max_open_rounds(&self) -> u64 {
+        self.max_open_rounds.load(Ordering::SeqCst)
+    }
+
+    /// Sets the maximum number of rounds ahead of the latest committed round to buffer.
+    /// Certificates beyond this limit are refused, and the primary relies on block sync to catch up.
+    pub fn set_max_open_rounds(&self, max_open_rounds: u64) -> Result<()> {
+        // Ensure the limit allows for the primary to make progress.
+        ensure!(max_open_rounds > 0, "The maximum number of open rounds must be nonzero");
+        self.max_open_rounds.store(max_open_rounds, Ordering::SeqCst);
+        Ok(())
+    }
+
+    /// Returns the minimum interval between round advancements.
+    pub fn min_round_interval(&self) -> Duration {
+        Duration::from_millis(self.min_round_interval_in_ms.load(Ordering::SeqCst))
+    }
+
+    /// Sets the minimum interval between round advancements, which coalesces more work into each round
+    /// on very fast networks. A zero interval disables the pacing.
+    ///
+    /// Note: Only an advancement by a single round is paced, so catching up to peers over several rounds is not.
+    /// The pacing delays the path that advances the round (e.g. processing a certificate from a peer),
+    /// by at most `MAX_MIN_ROUND_INTERVAL_IN_MS`.
+    pub fn set_min_round_interval(&self, interval: Duration) -> Result<()> {
+        // Ensure the interval is small enough to not harm liveness.
+        let interval_in_ms = u64::try_from(interval.as_millis())?;
+        ensure!(
+            interval_in_ms <= MAX_MIN_ROUND_INTERVAL_IN_MS,
+            "The minimum round interval must be at most {MAX_MIN_ROUND_INTERVAL_IN_MS}ms"
+        );
+        self.min_round_interval_in_ms.store(interval_in_ms, Ordering::SeqCst);
+        Ok(())
+    }
+
+    /// Returns the minimum number of previous certificates for a batch to reference, when available.
+    pub fn min_previous_certificates(&self) -> usize {
+        self.min_previous_certificates.load(Ordering::SeqCst)
+    }
+
+    /// Sets the minimum number of previous certificates for a batch to reference, beyond the quorum, which waits
+    /// for the slower validators before proposing. If `0` is given, a batch is proposed once the quorum is reached.
+    ///
+    /// Note: The minimum is capped at the size of the committee, and the primary falls back to the quorum once
+    /// the certificates are not available within `MAX_BATCH_DELAY_IN_MS` of the round advancement.
+    pub fn set_min_previous_certificates(&self, min_certificates: usize) -> Result<()> {
+        // Ensure the minimum is achievable by a committee.
+        ensure!(
+            min_certificates <= Committee::<N>::MAX_COMMITTEE_SIZE as usize,
+            "The minimum number of previous certificates must be at most {}",
+            Committee::<N>::MAX_COMMITTEE_SIZE
+        );
+        self.min_previous_certificates.store(min_certificates, Ordering::SeqCst);
+        Ok(())
+    }
+
+    /// Returns the maximum time between the heartbeats of the critical tasks, if the watchdog is enabled.
+    pub fn watchdog_timeout(&self) -> Option<Duration> {
+        self.watchdog.timeout()
+    }
+
+    /// Sets the maximum time between the heartbeats of the critical tasks, beyond which the watchdog takes
+    /// its action on the stalled task. If `None` is given, the watchdog is disabled.
+    pub fn set_watchdog_timeout(&self, timeout: Option<Duration>) -> Result<()> {
+        // Ensure the timeout exceeds the interval of the batch proposer.
+        if let Some(timeout) = timeout {
+            ensure!(
+                timeout.as_millis() > MAX_BATCH_DELAY_IN_MS as u128,
+                "The watchdog timeout must exceed {MAX_BATCH_DELAY_IN_MS}ms"
+            );
+        }
+        self.watchdog.set_timeout(timeout);
+        Ok(())
+    }
+
+    /// Returns the action of the watchdog on a stalled task.
+    pub fn watchdog_action(&self) -> WatchdogAction {
+        self.watchdog.action()
+    }
+
+    /// Sets the action of the watchdog on a stalled task.
+    pub fn set_watchdog_action(&self, action: WatchdogAction) {
+        self.watchdog.set_action(action);
+    }
+
+    /// Waits until the watchdog requests the node to exit, as a critical task stalled and the action is `Exit`.
+    /// The caller is responsible for shutting down the node, and exiting the process.
+    pub async fn wait_for_watchdog_exit(&self) {
+        self.watchdog_exit.notified().await
+    }
+
+    /// Signals that the batch proposer is making progress, if it is running.
+    fn beat_proposer(&self) {
+        if let Some(heartbeat) = self.proposer_heartbeat.read().as_ref() {
+            heartbeat.beat();
+        }
+    }
+
+    /// Sets the webhook notifier for the critical events. If `None` is given, alerting is disabled.
+    pub fn set_alert_notifier(&self, notifier: Option<AlertNotifier>) {
+        *self.alert_notifier.write() = notifier;
+    }
+
+    /// Returns the rate limit on the transactions admitted from any single sender, if one is set.
+    pub fn sender_rate_limit(&self) -> Option<Arc<SenderRateLimit<N>>> {
+        self.sender_rate_limit.read().clone()
+    }
+
+    /// Sets the rate limit on the transactions admitted from any single sender. The limit is shared by the workers,
+    /// so the cap applies across them. If `None` is given, the senders are not rate limited.
+    pub fn set_sender_rate_limit(&self, sender_rate_limit: Option<SenderRateLimit<N>>) {
+        let sender_rate_limit = sender_rate_limit.map(Arc::new);
+        self.workers.iter().for_each(|worker| worker.set_sender_rate_limit(sender_rate_limit.clone()));
+        *self.sender_rate_limit.write() = sender_rate_limit;
+    }
+
+    /// Alerts the webhook of the given critical event, if alerting is enabled.
+    fn alert(&self, event: AlertEvent, message: String) {
+        if let Some(notifier) = self.alert_notifier.read().as_ref() {
+            notifier.notify(event, message);
+        }
+    }
+
+    /// Returns the window over which the batch signature verifications are accumulated, if batching is enabled.
+    pub fn signature_batch_window(&self) -> Option<Duration> {
+        *self.signature_batch_window.read()
+    }
+
+    /// Sets the window over which the batch signature verifications are accumulated and verified in a batch.
+    /// If `None` is given, each batch signature is verified individually.
+    pub fn set_signature_batch_window(&self, window: Option<Duration>) -> Result<()> {
+        // Ensure the window is small enough to not delay certificate formation.
+        if let Some(window) = window {
+            ensure!(
+                window.as_millis() <= MAX_SIGNATURE_BATCH_WINDOW_IN_MS as u128,
+                "The signature batch window must be at most {MAX_SIGNATURE_BATCH_WINDOW_IN_MS}ms"
+            );
+        }
+        *self.signature_batch_window.write() = window;
+        Ok(())
+    }
+}
+
+impl<N: Network> Primary<N> {
+    /// Returns a proof that the given transmission is committed by the leader certificate of the block
+    /// at the given height.
+    pub fn prove_transmission_inclusion(
+        &self,
+        height: u32,
+        transmission_id: TransmissionID<N>,
+    ) -> Result<InclusionProof<N>> {
+        match self.ledger.get_block(height)?.authority() {
+            Authority::Quorum(subdag) => {
+                InclusionProof::new(subdag.leader_certificate(), subdag.values().flatten(), transmission_id)
+            }
+            Authority::Beacon(_) => bail!("Block {height} was not committed by a quorum"),
+        }
+    }
+}
+
+impl<N: Network> Primary<N> {
+    /// Returns `true` if the primary is a warm standby.
+    pub fn is_standby(&self) -> bool {
+        self.standby.load(Ordering::SeqCst)
+    }
+
+    /// Sets the primary as a warm standby, which maintains the DAG and peer connections without proposing or signing.
+    pub fn enable_standby(&self) {
+        info!("Primary is running as a warm standby");
+        self.standby.store(true, Ordering::SeqCst);
+    }
+
+    /// Promotes the warm standby to an active primary, which begins proposing and signing batches.
+    ///
+    /// To guard against split-brain, the promotion is refused if there is evidence of another active
+    /// primary with the same address in the current round or the previous round.
+    pub fn promote(&self) -> Result<()> {
+        // Ensure the primary is a warm standby.
+        ensure!(self.is_standby(), "The primary is not a warm standby");
+
+        // Retrieve the address of the primary.
+        let address = self.gateway.account().address();
+        // Retrieve the current round.
+        let current_round = self.current_round();
+        // Determine the rounds to check for activity.
+        let rounds = [current_round.saturating_sub(1), current_round];
+        // Ensure no other primary has been active with the same address.
+        for round in rounds {
+            if self.storage.contains_certificate_in_round_from(round, address) {
+                bail!("Refusing to promote the standby - found a certificate from '{address}' in round {round}");
+            }
+        }
+        // Ensure no other primary has proposed a batch with the same address.
+        let self_activity_round = self.self_activity_round.load(Ordering::SeqCst);
+        if self_activity_round > 0 && rounds.contains(&self_activity_round) {
+            bail!("Refusing to promote the standby - found a proposal from '{address}' in round {self_activity_round}");
+        }
+
+        // Promote the primary.
+        self.standby.store(false, Ordering::SeqCst);
+        info!("Promoted the warm standby to an active primary in round {current_round}");
+        Ok(())
+    }
+}
+
+impl<N: Network> Primary<N> {
+    /// Returns `true` if the primary is an observer, as it is not a member of the committee.
+    pub fn is_observer(&self) -> bool {
+        self.observer.load(Ordering::SeqCst)
+    }
+
+    /// Updates the observer mode, based on the membership of the primary in the committee for the given round.
+    /// Returns `true` if the primary is an observer (i.e. it must not propose or sign batches).
+    fn update_observer_mode(&self, round: u64) -> Result<bool> {
+        // Retrieve the committee lookback for the round.
+        let committee_lookback = self.ledger.get_committee_lookback_for_round(round)?;
+        // Determine if the primary is a member of the committee.
+        let address = self.gateway.account().address();
+        let is_observer = !committee_lookback.is_committee_member(address);
+        // Update the observer mode, and log the transition.
+        match (self.observer.swap(is_observer, Ordering::SeqCst), is_observer) {
+            (false, true) => {
+                let message = format!("Validator '{address}' was removed from the committee in round {round}");
+                error!("ALERT - {message}");
+                self.alert(AlertEvent::CommitteeRemoval, message);
+                warn!("Primary is now an observer, and will no longer propose or sign batches");
+            }
+            (true, false) => {
+                info!("Validator '{address}' was re-admitted to the committee in round {round}");
+                info!("Primary is no longer an observer, and will resume proposing and signing batches");
+            }
+            _ => (),
+        }
+        Ok(is_observer)
+    }
+}
+
+impl<N: Network> Primary<N> {
+    /// Returns the minimum stake of a validator, for its certificates from peers to be processed locally.
+    pub fn min_certificate_stake(&self) -> u64 {
+        self.min_certificate_stake.load(Ordering::SeqCst)
+    }
+
+    /// Sets the minimum stake of a validator, for its certificates from peers to be processed locally.
+    /// If `0` is given, the certificates from every committee member are processed.
+    ///
+    /// Note: This is an operator-local filter, and does not change which certificates are valid. The certificates
+    /// of the validators below the minimum are still fetched, when they are referenced by other certificates.
+    pub fn set_min_certificate_stake(&self, min_stake: u64) {
+        self.min_certificate_stake.store(min_stake, Ordering::SeqCst);
+    }
+
+    /// Returns `true` if the given author is below the minimum stake for its certificates to be processed,
+    /// in the committee lookback for the given round.
+    fn is_below_min_certificate_stake(&self, author: Address<N>, round: u64) -> Result<bool> {
+        match self.min_certificate_stake() {
+            0 => Ok(false),
+            min_stake => Ok(self.ledger.get_committee_lookback_for_round(round)?.get_stake(author) < min_stake),
+        }
+    }
+}
+
+impl<N: Network> Primary<N> {
+    /// Returns the maximum fraction of stake that may change in a committee transition, if quorum intersection
+    /// is checked.
+    pub fn max_committee_change(&self) -> Option<f64> {
+        *self.max_committee_change.read()
+    }
+
+    /// Sets the maximum fraction of stake that may change in a committee transition. The primary refuses to
+    /// participate in a committee that changes more of the stake of the current epoch, as the quorums of the
+    /// committees may not intersect. If `None`, quorum intersection is not checked.
+    pub fn set_max_committee_change(&self, max_fraction: Option<f64>) -> Result<()> {
+        // Ensure the maximum is a valid fraction.
+        if let Some(max_fraction) = max_fraction {
+            ensure!(max_fraction > 0.0 && max_fraction <= 1.0, "The maximum committee change must be in (0, 1]");
+        }
+        *self.max_committee_change.write() = max_fraction;
+        Ok(())
+    }
+
+    /// Ensures the committee of the given round maintains quorum intersection with the committee of the current
+    /// epoch, if quorum intersection is checked. The primary refuses to participate in an unsafe committee
+    /// (i.e. to advance into, propose, or sign in its rounds), until quorum intersection is no longer checked.
+    fn ensure_safe_committee_transition(&self, round: u64) -> Result<()> {
+        let (Some(max_fraction), Some(previous)) = (self.max_committee_change(), self.epoch_tracker.committee()) else {
+            return Ok(());
+        };
+        // Note: If the committee of the round is not known yet, it is checked once it is.
+        let Ok(next) = self.ledger.get_committee_lookback_for_round(round) else {
+            return Ok(());
+        };
+        // Note: The stakes may shift without a change of members, so every transition is checked, not only epochs.
+        if let Err(e) = ensure_quorum_intersection(&previous, &next, max_fraction) {
+            let message = format!("Primary is refusing to participate in the committee of round {round} - {e}");
+            // Alert on each refused committee once, as the transition is checked on every attempt to participate.
+            if self.refused_committee.lock().replace(next.clone()).as_ref() != Some(&next) {
+                error!("ALERT - {message}");
+                self.alert(AlertEvent::UnsafeCommitteeChange, message.clone());
+            }
+            bail!(message)
+        }
+        Ok(())
+    }
+
+    /// Observes the committee of the given round, tracking its epoch.
+    ///
+    /// Note: This is called on every round advancement, and periodically while the primary syncs,
+    /// so that the transitions that are advanced through by block sync are tracked as well.
+    fn observe_committee(&self, round: u64) {
+        // Note: If the committee of the round is not known yet, it is observed once it is.
+        let Ok(committee_lookback) = self.ledger.get_committee_lookback_for_round(round) else {
+            return;
+        };
+        // Track the epoch of the round, so the metrics are segmented at the transition.
+        if self.epoch_tracker.observe(&committee_lookback) {
+            debug!("Primary entered epoch {} in round {round}", committee_lookback.starting_round());
+        }
+    }
+
+    /// Returns the fraction of committee stake that must be connected, below which the primary is partitioned.
+    pub fn partition_threshold(&self) -> Option<f64> {
+        *self.partition_threshold.read()
+    }
+
+    /// Sets the fraction of committee stake that must be connected, below which the primary is partitioned.
+    /// If `None`, partition detection is disabled.
+    pub fn set_partition_threshold(&self, threshold: Option<f64>) -> Result<()> {
+        // Ensure the threshold is a valid fraction.
+        if let Some(threshold) = threshold {
+            ensure!(threshold > 0.0 && threshold <= 1.0, "The partition threshold must be in (0, 1]");
+        }
+        *self.partition_threshold.write() = threshold;
+        // If partition detection is disabled, clear the partition-safe mode.
+        if threshold.is_none() {
+            self.partitioned.store(false, Ordering::SeqCst);
+        }
+        Ok(())
+    }
+
+    /// Returns `true` if the primary is in partition-safe mode, as it is connected to too little committee stake.
+    pub fn is_partitioned(&self) -> bool {
+        self.partitioned.load(Ordering::SeqCst)
+    }
+
+    /// Returns the fraction of the committee stake for the given round, that the primary is connected to.
+    /// Note: The stake of the primary itself is included.
+    pub fn connected_stake_fraction(&self, round: u64) -> Result<f64> {
+        // Retrieve the committee lookback for the round.
+        let committee_lookback = self.ledger.get_committee_lookback_for_round(round)?;
+        // Retrieve the connected validator addresses, including the primary.
+        let mut connected_validators = self.gateway.connected_addresses();
+        connected_validators.insert(self.gateway.account().address());
+        // Sum the stake of the connected committee members.
+        let connected_stake: u64 =
+            connected_validators.iter().map(|address| committee_lookback.get_stake(*address)).sum();
+        match committee_lookback.total_stake() {
+            0 => Ok(0.0),
+            total_stake => Ok(connected_stake as f64 / total_stake as f64),
+        }
+    }
+
+    /// Updates the partition-safe mode, based on the connected committee stake for the given round.
+    /// Returns `true` if the primary is partitioned (i.e. it must not propose batches).
+    fn update_partition_mode(&self, round: u64) -> Result<bool> {
+        // If partition detection is disabled, the primary is never partitioned.
+        let Some(threshold) = self.partition_threshold() else {
+            return Ok(false);
+        };
+        // Determine if the primary is partitioned.
+        let fraction = self.connected_stake_fraction(round)?;
+        let is_partitioned = fraction < threshold;
+        // Update the partition-safe mode, and log the transition.
+        match (self.partitioned.swap(is_partitioned, Ordering::SeqCst), is_partitioned) {
+            (false, true) => {
+                let message = format!(
+                    "Primary is likely partitioned in round {round} (connected to {:.1}% of committee stake)",
+                    fraction * 100.0
+                );
+                error!("ALERT - {message}");
+                self.alert(AlertEvent::PartitionDetected, message);
+                warn!("Primary is now in partition-safe mode, and will no longer propose batches");
+            }
+            (true, false) => {
+                info!("Primary is reconnected in round {round} ({:.1}% of committee stake)", fraction * 100.0);
+                info!("Primary is no longer in partition-safe mode, and will resume proposing batches");
+            }
+            _ => (),
+        }
+        Ok(is_partitioned)
+    }
 }
 
 impl<N: Network> Primary<N> {
+    /// Returns the current round of the network, i.e. the highest round in which validators with
+    /// at least the availability threshold of stake were observed, or `None` if it is not known yet.
+    pub fn network_round(&self) -> Result<Option<u64>> {
+        // Retrieve the current committee.
+        let committee = self.ledger.current_committee()?;
+        // Sort the latest rounds of the committee members, from the highest.
+        let mut peer_rounds = self
+            .peer_rounds
+            .read()
+            .iter()
+            .filter(|(address, _)| committee.is_committee_member(**address))
+            .map(|(address, round)| (*round, *address))
+            .collect::<Vec<_>>();
+        peer_rounds.sort_unstable_by(|a, b| b.0.cmp(&a.0));
+        // Find the highest round that validators with the availability threshold have reached.
+        // Note: This ensures a single (possibly malicious) validator cannot cause the primary to skip rounds.
+        let mut authors = HashSet::new();
+        for (round, address) in peer_rounds {
+            authors.insert(address);
+            if committee.is_availability_threshold_reached(&authors) {
+                return Ok(Some(round));
+            }
+        }
+        Ok(None)
+    }
+
+    /// Records the given round for the given validator, if it is later than its latest observed round.
+    fn update_peer_round(&self, address: Address<N>, round: u64) {
+        let mut peer_rounds = self.peer_rounds.write();
+        let latest_round = peer_rounds.entry(address).or_default();
+        *latest_round = (*latest_round).max(round);
+    }
+
+    /// Skips the rounds that the network has already moved past, if the primary is resuming after a restart.
+    /// Returns `true` if the batch proposal should be deferred, as the network round is not known yet,
+    /// or as the primary advanced to the network round (which proposes a batch, if it is ready).
+    ///
+    /// Note: The rounds of the peers are learned from the certificates in their primary pings, which every peer
+    /// broadcasts periodically, so the resume delay covers at least one ping from each connected peer.
+    #[async_recursion::async_recursion]
+    async fn skip_stale_rounds(&self) -> Result<bool> {
+        // If the primary is not resuming, there is nothing to skip.
+        let Some(resuming_since) = *self.resuming_since.lock() else {
+            return Ok(false);
+        };
+        let is_waiting = resuming_since.elapsed() < Duration::from_millis(MAX_RESUME_DELAY_IN_MS);
+        // Retrieve the current round.
+        let current_round = self.current_round();
+        match self.network_round()? {
+            // If the network is too far ahead to skip the rounds, defer the batch proposal to sync, which advances
+            // the rounds along with the ledger.
+            Some(network_round) if network_round > current_round + self.storage.max_gc_rounds() => match is_waiting {
+                true => {
+                    debug!("Primary is safely skipping a batch proposal {}", "(waiting for sync)".dimmed());
+                    return Ok(true);
+                }
+                false => warn!("Primary is resuming behind the network round {network_round} (not synced yet)"),
+            },
+            // If the network is ahead, advance to the network round.
+            Some(network_round) if network_round > current_round => {
+                // The primary has resumed.
+                *self.resuming_since.lock() = None;
+                self.try_increment_to_the_next_round(network_round).await?;
+                info!("Primary skipped the stale rounds {current_round} to {} after a restart", self.current_round());
+                return Ok(true);
+            }
+            Some(_) => {}
+            // If the network round is not known yet, defer the batch proposal.
+            None if is_waiting => {
+                debug!("Primary is safely skipping a batch proposal {}", "(waiting for the network round)".dimmed());
+                return Ok(true);
+            }
+            None => warn!("Primary is resuming without the network round (no response from peers)"),
+        }
+        // The primary has resumed.
+        *self.resuming_since.lock() = None;
+        Ok(false)
+    }
+
     /// Returns the number of unconfirmed transmissions.
     pub fn num_unconfirmed_transmissions(&self) -> usize {
         self.workers.iter().map(|worker| worker.num_transmissions()).sum()
@@ -259,6 +943,18 @@ impl<N: Network> Primary<N> {
     pub fn unconfirmed_transactions(&self) -> impl '_ + Iterator<Item = (N::TransactionID, Data<Transaction<N>>)> {
         self.workers.iter().flat_map(|worker| worker.transactions())
     }
+
+    /// Pins the specified unconfirmed transmission in its worker, so it is included in the next batch
+    /// ahead of every other transmission.
+    pub fn pin_transmission(&self, transmission_id: impl Into<TransmissionID<N>>) -> Result<()> {
+        let transmission_id = transmission_id.into();
+        // Retrieve the worker of the transmission.
+        let worker_id = self.gateway.worker_affinity().assign(transmission_id, self.num_workers())?;
+        let Some(worker) = self.workers.get(worker_id as usize) else {
+            bail!("Unable to pin transmission '{}' - missing worker {worker_id}", fmt_id(transmission_id));
+        };
+        worker.pin_transmission(transmission_id)
+    }
 }
 
 impl<N: Network> Primary<N> {
@@ -270,6 +966,28 @@ impl<N: Network> Primary<N> {
     /// 3. Set the batch proposal in the primary.
     /// 4. Broadcast the batch header to all validators for signing.
     pub async fn propose_batch(&self) -> Result<()> {
+        // If the primary is a warm standby, do not propose a batch.
+        if self.is_standby() {
+            return Ok(());
+        }
+        // If the primary is an observer, do not propose a batch.
+        if self.update_observer_mode(self.current_round())? {
+            return Ok(());
+        }
+        // If the primary is partitioned, do not propose a batch.
+        // Note: The primary continues to process events and sync, so it can rejoin once it is reconnected.
+        if self.update_partition_mode(self.current_round())? {
+            return Ok(());
+        }
+        // Ensure the primary does not propose in an unsafe committee.
+        self.ensure_safe_committee_transition(self.current_round())?;
+
+        // If the primary is resuming after a restart, skip the rounds that the network has already moved past.
+        // Note: This is checked before the proposal lock is acquired, as advancing the round proposes a batch.
+        if self.skip_stale_rounds().await? {
+            return Ok(());
+        }
+
         // This function isn't re-entrant.
         let mut lock_guard = self.propose_lock.lock().await;
 
@@ -284,7 +1002,7 @@ impl<N: Network> Primary<N> {
         if let Some(proposal) = self.proposed_batch.read().as_ref() {
             // Construct the event.
             // TODO(ljedrz): the BatchHeader should be serialized only once in advance before being sent to non-signers.
-            let event = Event::BatchPropose(proposal.batch_header().clone().into());
+            let event = Event::BatchPropose(self.batch_propose_for(proposal.batch_header().clone())?);
             // Iterate through the non-signers.
             for address in proposal.nonsigners(&self.ledger.get_committee_lookback_for_round(proposal.round())?) {
                 // Resolve the address to the peer IP.
@@ -332,10 +1050,13 @@ impl<N: Network> Primary<N> {
             bail!("Primary is safely skipping {}", format!("(round {round} was already certified)").dimmed());
         }
 
+        // Retrieve the committee lookback for the round.
+        // Note: The committee is retrieved once, so the batch is checked and proposed under the same committee,
+        // even if the ledger transitions to a new epoch meanwhile.
+        let committee_lookback = self.ledger.get_committee_lookback_for_round(round)?;
+
         // Check if the primary is connected to enough validators to reach quorum threshold.
         {
-            // Retrieve the committee to check against.
-            let committee_lookback = self.ledger.get_committee_lookback_for_round(round)?;
             // Retrieve the connected validator addresses.
             let mut connected_validators = self.gateway.connected_addresses();
             // Append the primary to the set.
@@ -371,6 +1092,23 @@ impl<N: Network> Primary<N> {
             if previous_committee_lookback.is_quorum_threshold_reached(&authors) {
                 is_ready = true;
             }
+            // Check if the previous certificates have reached the minimum number of certificates, if one is set.
+            let min_certificates = self.min_previous_certificates().min(previous_committee_lookback.num_members());
+            if is_ready && previous_certificates.len() < min_certificates {
+                // If the round advanced recently, wait for the remaining certificates; otherwise, use the quorum.
+                let elapsed = self.last_round_advance.lock().map(|instant| instant.elapsed());
+                if elapsed.is_some_and(|elapsed| elapsed < Duration::from_millis(MAX_BATCH_DELAY_IN_MS)) {
+                    debug!(
+                        "Primary is waiting to propose a batch {}",
+                        format!(
+                            "(previous round {previous_round} has {}/{min_certificates} certificates)",
+                            previous_certificates.len()
+                        )
+                        .dimmed()
+                    );
+                    return Ok(());
+                }
+            }
         }
         // If the batch is not ready to be proposed, return early.
         if !is_ready {
@@ -381,6 +1119,14 @@ impl<N: Network> Primary<N> {
             return Ok(());
         }
 
+        // If a batch was already proposed for the round (e.g. and abandoned at an epoch transition), return early,
+        // as proposing another batch for the round would equivocate.
+        // Note: This is checked before the workers are drained, so their transmissions are carried over.
+        if *lock_guard == round {
+            warn!("Primary is safely skipping a batch proposal - round {round} already proposed");
+            return Ok(());
+        }
+
         // Determined the required number of transmissions per worker.
         let num_transmissions_per_worker = MAX_TRANSMISSIONS_PER_BATCH / self.num_workers() as usize;
         // Initialize the map of transmissions.
@@ -390,6 +1136,8 @@ impl<N: Network> Primary<N> {
         // Take the transmissions from the workers.
         for worker in self.workers.iter() {
             for (id, transmission) in worker.drain(num_transmissions_per_worker) {
+                // Signal that the proposal is making progress, as a restart would lose the drained transmissions.
+                self.beat_proposer();
                 // Check if the ledger already contains the transmission.
                 if self.ledger.contains_transmission(&id).unwrap_or(true) {
                     trace!("Proposing - Skipping transmission '{}' - Already in ledger", fmt_id(id));
@@ -433,16 +1181,13 @@ impl<N: Network> Primary<N> {
             debug!("Primary is safely skipping a batch proposal {}", "(no unconfirmed transactions)".dimmed());
             return Ok(());
         }
-        // Ditto if the batch had already been proposed.
+        // Ensure the round is not the genesis round.
         ensure!(round > 0, "Round 0 cannot have transaction batches");
-        if *lock_guard == round {
-            warn!("Primary is safely skipping a batch proposal - round {round} already proposed");
-            return Ok(());
-        }
 
         *lock_guard = round;
 
         /* Proceeding to sign & propose the batch. */
+        self.beat_proposer();
         info!("Proposing a batch with {} transmissions for round {round}...", transmissions.len());
 
         // Retrieve the private key.
@@ -467,15 +1212,26 @@ impl<N: Network> Primary<N> {
             &mut rand::thread_rng()
         ))?;
         // Construct the proposal.
-        let proposal =
-            Proposal::new(self.ledger.get_committee_lookback_for_round(round)?, batch_header.clone(), transmissions)?;
+        let proposal = Proposal::new(committee_lookback, batch_header.clone(), transmissions)?;
         // Broadcast the batch to all validators for signing.
-        self.gateway.broadcast(Event::BatchPropose(batch_header.into()));
+        self.gateway.broadcast(Event::BatchPropose(self.batch_propose_for(batch_header)?));
         // Set the proposed batch.
         *self.proposed_batch.write() = Some(proposal);
         Ok(())
     }
 
+    /// Returns the batch propose event for the given batch header, with the workers that hold its transmissions.
+    fn batch_propose_for(&self, batch_header: BatchHeader<N>) -> Result<BatchPropose<N>> {
+        // Retrieve the number of workers.
+        let num_workers = self.num_workers();
+        // Determine the workers that hold the transmissions.
+        let transmission_ids = batch_header.transmission_ids();
+        let worker_ids = worker_assignment(transmission_ids, num_workers, &self.gateway.worker_affinity())?;
+        // Return the batch propose event.
+        let round = batch_header.round();
+        Ok(BatchPropose::new(round, Data::Object(batch_header), num_workers, worker_ids))
+    }
+
     /// Processes a batch propose from a peer.
     ///
     /// This method performs the following steps:
@@ -486,7 +1242,7 @@ impl<N: Network> Primary<N> {
     /// If our primary is ahead of the peer, we will not sign the batch.
     /// If our primary is behind the peer, but within GC range, we will sync up to the peer's round, and then sign the batch.
     async fn process_batch_propose_from_peer(&self, peer_ip: SocketAddr, batch_propose: BatchPropose<N>) -> Result<()> {
-        let BatchPropose { round: batch_round, batch_header } = batch_propose;
+        let BatchPropose { round: batch_round, batch_header, num_workers, worker_ids } = batch_propose;
 
         // Deserialize the batch header.
         let batch_header = spawn_blocking!(batch_header.deserialize_blocking())?;
@@ -496,6 +1252,21 @@ impl<N: Network> Primary<N> {
             self.gateway.disconnect(peer_ip);
             bail!("Malicious peer - proposed round {batch_round}, but sent batch for round {}", batch_header.round());
         }
+        // Ensure the primary does not sign in an unsafe committee.
+        self.ensure_safe_committee_transition(batch_round)?;
+        // Ensure the number of workers is valid.
+        if num_workers == 0 || num_workers > MAX_WORKERS {
+            // Proceed to disconnect the validator.
+            self.gateway.disconnect(peer_ip);
+            bail!("Malicious peer - proposed batch with an invalid number of workers ({num_workers})");
+        }
+        // Ensure the worker assignment is well-formed.
+        let transmission_ids = batch_header.transmission_ids().iter();
+        if let Err(e) = check_worker_assignment(transmission_ids, &worker_ids, num_workers) {
+            // Proceed to disconnect the validator.
+            self.gateway.disconnect(peer_ip);
+            bail!("Malicious peer - proposed batch with a malformed worker assignment - {e}");
+        }
 
         // Retrieve the batch author.
         let batch_author = batch_header.author();
@@ -520,6 +1291,10 @@ impl<N: Network> Primary<N> {
         }
         // Ensure the batch proposal is not from the current primary.
         if self.gateway.account().address() == batch_author {
+            // If the primary is a warm standby, record the activity from its own address.
+            if self.is_standby() {
+                self.self_activity_round.fetch_max(batch_round, Ordering::SeqCst);
+            }
             bail!("Invalid peer - proposed batch from myself ({batch_author})");
         }
 
@@ -536,6 +1311,8 @@ impl<N: Network> Primary<N> {
 
             // If the round matches and the batch ID differs, then the validator is malicious.
             if signed_round == batch_header.round() && signed_batch_id != batch_header.batch_id() {
+                // Report the equivocation, if it has not already been reported.
+                self.report_equivocation(&batch_header);
                 // Proceed to disconnect the validator.
                 self.gateway.disconnect(peer_ip);
                 bail!("Malicious peer - proposed another batch for the same round ({signed_round})");
@@ -557,8 +1334,12 @@ impl<N: Network> Primary<N> {
             }
         }
 
+        // Refuse the batch proposal if it references garbage-collected certificates, if configured.
+        self.ensure_proposal_is_not_stale(peer_ip, &batch_header)?;
+
         // If the peer is ahead, use the batch header to sync up to the peer.
-        let mut transmissions = self.sync_with_batch_header_from_peer(peer_ip, &batch_header).await?;
+        let mut transmissions =
+            self.sync_with_batch_header_from_peer(peer_ip, &batch_header, Some(&worker_ids)).await?;
 
         // Check that the transmission ids match and are not fee transactions.
         for (transmission_id, transmission) in transmissions.iter_mut() {
@@ -583,7 +1364,18 @@ impl<N: Network> Primary<N> {
         let header = batch_header.clone();
         let missing_transmissions = spawn_blocking!(storage.check_batch_header(&header, transmissions))?;
         // Inserts the missing transmissions into the workers.
-        self.insert_missing_transmissions_into_workers(peer_ip, missing_transmissions.into_iter())?;
+        self.insert_missing_transmissions_into_workers(peer_ip, missing_transmissions.into_iter()).await?;
+
+        // If the primary is a warm standby, do not sign the batch.
+        if self.is_standby() {
+            trace!("Standby primary is skipping the signature for a batch in round {batch_round} from '{peer_ip}'");
+            return Ok(());
+        }
+        // If the primary is an observer, do not sign the batch.
+        if self.update_observer_mode(batch_round)? {
+            trace!("Observer primary is skipping the signature for a batch in round {batch_round} from '{peer_ip}'");
+            return Ok(());
+        }
 
         /* Proceeding to sign the batch. */
 
@@ -616,6 +1408,8 @@ impl<N: Network> Primary<N> {
                 entry.insert((batch_round, batch_id, signature));
             }
         };
+        // Record the signed batch header, to detect if the validator equivocates.
+        self.equivocations.insert_header(batch_header);
 
         // Broadcast the signature back to the validator.
         let self_ = self.clone();
@@ -629,6 +1423,22 @@ impl<N: Network> Primary<N> {
         Ok(())
     }
 
+    /// Reports the equivocation evidence for the given batch header to the ledger, if the batch header
+    /// conflicts with a signed batch header from the same author and round, and has not already been reported.
+    /// Returns the reported evidence, if any.
+    fn report_equivocation(&self, batch_header: &BatchHeader<N>) -> Option<EquivocationEvidence<N>> {
+        // Construct the verifiable equivocation evidence.
+        let evidence = self.equivocations.detect(batch_header)?;
+        // Submit the evidence to the ledger.
+        match self.ledger.report_equivocation(evidence.first(), evidence.second()) {
+            Ok(()) => {
+                info!("Reported validator '{}' for equivocating in round {}", evidence.author(), evidence.round())
+            }
+            Err(e) => warn!("Failed to report validator '{}' for equivocating - {e}", evidence.author()),
+        }
+        Some(evidence)
+    }
+
     /// Processes a batch signature from a peer.
     ///
     /// This method performs the following steps:
@@ -641,6 +1451,41 @@ impl<N: Network> Primary<N> {
         &self,
         peer_ip: SocketAddr,
         batch_signature: BatchSignature<N>,
+    ) -> Result<()> {
+        self.process_batch_signature(peer_ip, batch_signature, None).await
+    }
+
+    /// Processes the accumulated batch signatures from peers, verifying the signatures together in a batch.
+    /// Note: The signatures are still stored one at a time, in the order they were received.
+    async fn process_batch_signatures_from_peers(&self, batch: Vec<(SocketAddr, BatchSignature<N>)>) {
+        // Verify the signatures together, against the addresses of their signers.
+        let items = batch.iter().map(|(_, batch_signature)| *batch_signature).collect::<Vec<_>>();
+        let results = match spawn_blocking!({
+            let items = items
+                .into_iter()
+                .map(|BatchSignature { batch_id, signature }| (signature.to_address(), signature, batch_id))
+                .collect::<Vec<_>>();
+            Ok(verify_batch(&items))
+        }) {
+            Ok(results) => results.into_iter().map(Some).collect::<Vec<_>>(),
+            // If the batch could not be verified, verify each signature individually.
+            Err(_) => vec![None; batch.len()],
+        };
+        // Process each batch signature, with the result of its verification.
+        for ((peer_ip, batch_signature), is_valid) in batch.into_iter().zip(results) {
+            if let Err(e) = self.process_batch_signature(peer_ip, batch_signature, is_valid).await {
+                warn!("Cannot store a signature from '{peer_ip}' - {e}");
+            }
+        }
+    }
+
+    /// Processes a batch signature from a peer, where `is_valid` is the result of its verification in a batch,
+    /// if it was already verified.
+    async fn process_batch_signature(
+        &self,
+        peer_ip: SocketAddr,
+        batch_signature: BatchSignature<N>,
+        is_valid: Option<bool>,
     ) -> Result<()> {
         // Ensure the proposed batch has not expired, and clear the proposed batch if it has expired.
         self.check_proposed_batch_for_expiration().await?;
@@ -661,6 +1506,13 @@ impl<N: Network> Primary<N> {
         if self.gateway.account().address() == signer {
             bail!("Invalid peer - received a batch signature from myself ({signer})");
         }
+        // If the signature was verified in a batch, use the result of its verification.
+        // Note: The batch ID is checked against the proposed batch below, before the signature is added.
+        let is_verified = match is_valid {
+            Some(true) => true,
+            Some(false) => bail!("Signature verification failed"),
+            None => false,
+        };
 
         let proposal = {
             // Acquire the write lock.
@@ -681,12 +1533,20 @@ impl<N: Network> Primary<N> {
                     }
                     // Retrieve the committee lookback for the round.
                     let committee_lookback = self.ledger.get_committee_lookback_for_round(proposal.round())?;
+                    // Ensure the batch does not span an epoch transition.
+                    // Note: The proposal is abandoned once its expiration is checked.
+                    if is_epoch_transition(proposal.committee(), &committee_lookback) {
+                        bail!("The committee changed since the batch for round {} was proposed", proposal.round());
+                    }
                     // Retrieve the address of the validator.
                     let Some(signer) = self.gateway.resolver().get_address(peer_ip) else {
                         bail!("Signature is from a disconnected validator");
                     };
-                    // Add the signature to the batch.
-                    proposal.add_signature(signer, signature, &committee_lookback)?;
+                    // Add the signature to the batch, verifying it unless it was already verified.
+                    match is_verified {
+                        true => proposal.add_verified_signature(signer, signature, &committee_lookback)?,
+                        false => proposal.add_signature(signer, signature, &committee_lookback)?,
+                    }
                     info!("Received a batch signature for round {} from '{peer_ip}'", proposal.round());
                     // Check if the batch is ready to be certified.
                     if !proposal.is_quorum_threshold_reached(&committee_lookback) {
@@ -751,9 +1611,16 @@ impl<N: Network> Primary<N> {
         if self.gateway.account().address() == author {
             bail!("Received a batch certificate for myself ({author})");
         }
+        // Skip the certificate if its author is below the minimum stake for its certificates to be processed.
+        if self.is_below_min_certificate_stake(author, certificate_round)? {
+            trace!("Skipping a batch certificate from '{peer_ip}' (author is below the minimum certificate stake)");
+            return Ok(());
+        }
 
         // Store the certificate, after ensuring it is valid.
         self.sync_with_certificate_from_peer(peer_ip, certificate).await?;
+        // Record the round of the author, to track the network round.
+        self.update_peer_round(author, certificate_round);
 
         // If there are enough certificates to reach quorum threshold for the certificate round,
         // then proceed to advance to the next round.
@@ -810,6 +1677,11 @@ impl<N: Network> Primary<N> {
             self.gateway.disconnect(peer_ip);
             bail!("Malicious peer - Received a batch certificate from an unauthorized validator IP ({peer_ip})");
         }
+        // Skip the certificate if its author is below the minimum stake for its certificates to be processed.
+        if self.is_below_min_certificate_stake(certificate.author(), certificate.round())? {
+            trace!("Skipping a batch certificate from '{peer_ip}' (author is below the minimum certificate stake)");
+            return Ok(());
+        }
 
         // Store the certificate, after ensuring it is valid.
         self.sync_with_certificate_from_peer(peer_ip, certificate).await?;
@@ -818,8 +1690,72 @@ impl<N: Network> Primary<N> {
 }
 
 impl<N: Network> Primary<N> {
-    /// Starts the primary handlers.
-    fn start_handlers(&self, primary_receiver: PrimaryReceiver<N>) {
+    /// Prepares a primary ping, with the block locators, the latest certificate of the primary,
+    /// and the certificates of the current round (or of the previous round, if there are none yet).
+    fn prepare_primary_ping(&self) -> Option<PrimaryPing<N>> {
+        // Retrieve the block locators.
+        let block_locators = match self.sync.get_block_locators() {
+            Ok(block_locators) => block_locators,
+            Err(e) => {
+                warn!("Failed to retrieve block locators - {e}");
+                return None;
+            }
+        };
+
+        // Retrieve the latest certificate of the primary.
+        let primary_certificate = {
+            // Retrieve the primary address.
+            let primary_address = self.gateway.account().address();
+
+            // Iterate backwards from the latest round to find the primary certificate.
+            let mut certificate = None;
+            let mut current_round = self.current_round();
+            while certificate.is_none() {
+                // If the current round is 0, then break the while loop.
+                if current_round == 0 {
+                    break;
+                }
+                // Retrieve the certificates.
+                let certificates = self.storage.get_certificates_for_round(current_round);
+                // Retrieve the primary certificate.
+                certificate = certificates.into_iter().find(|certificate| certificate.author() == primary_address);
+                // If the primary certificate was not found, decrement the round.
+                if certificate.is_none() {
+                    current_round = current_round.saturating_sub(1);
+                }
+            }
+
+            // Determine if the primary certificate was found.
+            match certificate {
+                Some(certificate) => certificate,
+                // Do not send a primary ping.
+                None => return None,
+            }
+        };
+
+        // Retrieve the batch certificates.
+        let batch_certificates = {
+            // Retrieve the current round.
+            let current_round = self.current_round();
+            // Retrieve the batch certificates for the current round.
+            let mut current_certificates = self.storage.get_certificates_for_round(current_round);
+            // If there are no batch certificates for the current round,
+            // then retrieve the batch certificates for the previous round.
+            if current_certificates.is_empty() {
+                // Retrieve the previous round.
+                let previous_round = current_round.saturating_sub(1);
+                // Retrieve the batch certificates for the previous round.
+                current_certificates = self.storage.get_certificates_for_round(previous_round);
+            }
+            current_certificates
+        };
+
+        // Construct the primary ping.
+        Some(PrimaryPing::from((<Event<N>>::VERSION, block_locators, primary_certificate, batch_certificates)))
+    }
+
+    /// Starts the primary handlers.
+    fn start_handlers(&self, primary_receiver: PrimaryReceiver<N>) {
         let PrimaryReceiver {
             mut rx_batch_propose,
             mut rx_batch_signature,
@@ -837,73 +1773,10 @@ impl<N: Network> Primary<N> {
                     // Sleep briefly.
                     tokio::time::sleep(Duration::from_millis(PRIMARY_PING_IN_MS)).await;
 
-                    // Retrieve the block locators.
-                    let block_locators = match self_.sync.get_block_locators() {
-                        Ok(block_locators) => block_locators,
-                        Err(e) => {
-                            warn!("Failed to retrieve block locators - {e}");
-                            continue;
-                        }
-                    };
-
-                    // Retrieve the latest certificate of the primary.
-                    let primary_certificate = {
-                        // Retrieve the primary address.
-                        let primary_address = self_.gateway.account().address();
-
-                        // Iterate backwards from the latest round to find the primary certificate.
-                        let mut certificate = None;
-                        let mut current_round = self_.current_round();
-                        while certificate.is_none() {
-                            // If the current round is 0, then break the while loop.
-                            if current_round == 0 {
-                                break;
-                            }
-                            // Retrieve the certificates.
-                            let certificates = self_.storage.get_certificates_for_round(current_round);
-                            // Retrieve the primary certificate.
-                            certificate =
-                                certificates.into_iter().find(|certificate| certificate.author() == primary_address);
-                            // If the primary certificate was not found, decrement the round.
-                            if certificate.is_none() {
-                                current_round = current_round.saturating_sub(1);
-                            }
-                        }
-
-                        // Determine if the primary certificate was found.
-                        match certificate {
-                            Some(certificate) => certificate,
-                            // Skip this iteration of the loop (do not send a primary ping).
-                            None => continue,
-                        }
-                    };
-
-                    // Retrieve the batch certificates.
-                    let batch_certificates = {
-                        // Retrieve the current round.
-                        let current_round = self_.current_round();
-                        // Retrieve the batch certificates for the current round.
-                        let mut current_certificates = self_.storage.get_certificates_for_round(current_round);
-                        // If there are no batch certificates for the current round,
-                        // then retrieve the batch certificates for the previous round.
-                        if current_certificates.is_empty() {
-                            // Retrieve the previous round.
-                            let previous_round = current_round.saturating_sub(1);
-                            // Retrieve the batch certificates for the previous round.
-                            current_certificates = self_.storage.get_certificates_for_round(previous_round);
-                        }
-                        current_certificates
-                    };
-
-                    // Construct the primary ping.
-                    let primary_ping = PrimaryPing::from((
-                        <Event<N>>::VERSION,
-                        block_locators,
-                        primary_certificate,
-                        batch_certificates,
-                    ));
-                    // Broadcast the event.
-                    self_.gateway.broadcast(Event::PrimaryPing(primary_ping));
+                    // Broadcast the primary ping, if it can be prepared.
+                    if let Some(primary_ping) = self_.prepare_primary_ping() {
+                        self_.gateway.broadcast(Event::PrimaryPing(primary_ping));
+                    }
                 }
             });
         }
@@ -984,28 +1857,58 @@ impl<N: Network> Primary<N> {
             });
         }
 
-        // Start the batch proposer.
+        // Start the batch proposer, monitored by the watchdog.
+        let self_ = self.clone();
+        self.watchdog.register("batch proposer", move |heartbeat| {
+            let self_ = self_.clone();
+            // Share the heartbeat, so that it is also beaten while a batch is being proposed.
+            *self_.proposer_heartbeat.write() = Some(heartbeat.clone());
+            tokio::spawn(async move {
+                loop {
+                    // Sleep briefly, but longer than if there were no batch.
+                    tokio::time::sleep(Duration::from_millis(MAX_BATCH_DELAY_IN_MS)).await;
+                    // Signal that the batch proposer is making progress.
+                    heartbeat.beat();
+                    // If the primary is not synced, then do not propose a batch.
+                    if !self_.sync.is_synced() {
+                        debug!("Skipping batch proposal {}", "(node is syncing)".dimmed());
+                        // Check the committee of the round that block sync advanced to, and observe it if it is safe.
+                        let round = self_.current_round();
+                        if self_.ensure_safe_committee_transition(round).is_ok() {
+                            self_.observe_committee(round);
+                        }
+                        continue;
+                    }
+                    // Check if the primary is stuck in its current round.
+                    self_.update_stuck_mode();
+                    // A best-effort attempt to skip the scheduled batch proposal if
+                    // round progression already triggered one.
+                    if self_.propose_lock.try_lock().is_err() {
+                        trace!("Skipping batch proposal {}", "(node is already proposing)".dimmed());
+                        continue;
+                    };
+                    // If there is no proposed batch, attempt to propose a batch.
+                    // Note: Do NOT spawn a task around this function call. Proposing a batch is a critical path,
+                    // and only one batch needs be proposed at a time.
+                    if let Err(e) = self_.propose_batch().await {
+                        warn!("Cannot propose a batch - {e}");
+                    }
+                }
+            })
+        });
+
+        // Start the watchdog.
         let self_ = self.clone();
         self.spawn(async move {
             loop {
-                // Sleep briefly, but longer than if there were no batch.
-                tokio::time::sleep(Duration::from_millis(MAX_BATCH_DELAY_IN_MS)).await;
-                // If the primary is not synced, then do not propose a batch.
-                if !self_.sync.is_synced() {
-                    debug!("Skipping batch proposal {}", "(node is syncing)".dimmed());
-                    continue;
-                }
-                // A best-effort attempt to skip the scheduled batch proposal if
-                // round progression already triggered one.
-                if self_.propose_lock.try_lock().is_err() {
-                    trace!("Skipping batch proposal {}", "(node is already proposing)".dimmed());
-                    continue;
-                };
-                // If there is no proposed batch, attempt to propose a batch.
-                // Note: Do NOT spawn a task around this function call. Proposing a batch is a critical path,
-                // and only one batch needs be proposed at a time.
-                if let Err(e) = self_.propose_batch().await {
-                    warn!("Cannot propose a batch - {e}");
+                tokio::time::sleep(WATCHDOG_CHECK_INTERVAL).await;
+                // Check the heartbeats of the critical tasks; the stalled tasks are restarted, if configured.
+                let stalled = self_.watchdog.check();
+                // Otherwise, request the node to shut down and exit, so that an external supervisor restarts it.
+                if !stalled.is_empty() && self_.watchdog_action() == WatchdogAction::Exit {
+                    error!("Watchdog - Shutting down the node, as {} stalled", stalled.join(", "));
+                    self_.watchdog_exit.notify_one();
+                    break;
                 }
             }
         });
@@ -1039,6 +1942,21 @@ impl<N: Network> Primary<N> {
                     trace!("Skipping a batch signature from '{peer_ip}' {}", "(node is syncing)".dimmed());
                     continue;
                 }
+                // If signature verification batching is enabled, accumulate the signatures received within the
+                // window, and process them together.
+                if let Some(window) = self_.signature_batch_window() {
+                    let mut batch = vec![(peer_ip, batch_signature)];
+                    let deadline = tokio::time::Instant::now() + window;
+                    while batch.len() < MAX_SIGNATURE_BATCH_SIZE {
+                        match tokio::time::timeout_at(deadline, rx_batch_signature.recv()).await {
+                            Ok(Some(signature)) => batch.push(signature),
+                            _ => break,
+                        }
+                    }
+                    // Note: As below, the signatures are processed in this task, one at a time.
+                    self_.process_batch_signatures_from_peers(batch).await;
+                    continue;
+                }
                 // Process the batch signature.
                 // Note: Do NOT spawn a task around this function call. Processing signatures from peers
                 // is a critical path, and we should only store the minimum required number of signatures.
@@ -1065,6 +1983,7 @@ impl<N: Network> Primary<N> {
                     // Deserialize the batch certificate.
                     let Ok(batch_certificate) = spawn_blocking!(batch_certificate.deserialize_blocking()) else {
                         warn!("Failed to deserialize the batch certificate from '{peer_ip}'");
+                        self_.gateway.report_invalid_certificate(peer_ip);
                         return;
                     };
                     // Process the batch certificate.
@@ -1080,7 +1999,8 @@ impl<N: Network> Primary<N> {
         self.spawn(async move {
             while let Some((puzzle_commitment, prover_solution, callback)) = rx_unconfirmed_solution.recv().await {
                 // Compute the worker ID.
-                let Ok(worker_id) = assign_to_worker(puzzle_commitment, self_.num_workers()) else {
+                let Ok(worker_id) = self_.gateway.worker_affinity().assign(puzzle_commitment, self_.num_workers())
+                else {
                     error!("Unable to determine the worker ID for the unconfirmed solution");
                     continue;
                 };
@@ -1102,7 +2022,8 @@ impl<N: Network> Primary<N> {
             while let Some((transaction_id, transaction, callback)) = rx_unconfirmed_transaction.recv().await {
                 trace!("Primary - Received an unconfirmed transaction '{}'", fmt_id(transaction_id));
                 // Compute the worker ID.
-                let Ok(worker_id) = assign_to_worker::<N>(&transaction_id, self_.num_workers()) else {
+                let Ok(worker_id) = self_.gateway.worker_affinity().assign::<N>(&transaction_id, self_.num_workers())
+                else {
                     error!("Unable to determine the worker ID for the unconfirmed transaction");
                     continue;
                 };
@@ -1121,9 +2042,15 @@ impl<N: Network> Primary<N> {
 
     /// Checks if the proposed batch is expired, and clears the proposed batch if it has expired.
     async fn check_proposed_batch_for_expiration(&self) -> Result<()> {
-        // Check if the proposed batch is timed out or stale.
+        // Check if the proposed batch is timed out or stale, or if its committee is no longer current.
         let is_expired = match self.proposed_batch.read().as_ref() {
-            Some(proposal) => proposal.round() < self.current_round(),
+            Some(proposal) => {
+                proposal.round() < self.current_round()
+                    || is_epoch_transition(
+                        proposal.committee(),
+                        &self.ledger.get_committee_lookback_for_round(proposal.round())?,
+                    )
+            }
             None => false,
         };
         // If the batch is expired, clear the proposed batch.
@@ -1131,14 +2058,81 @@ impl<N: Network> Primary<N> {
             // Reset the proposed batch.
             let proposal = self.proposed_batch.write().take();
             if let Some(proposal) = proposal {
-                self.reinsert_transmissions_into_workers(proposal)?;
+                self.release_expired_proposal(proposal).await?;
             }
         }
         Ok(())
     }
 
+    /// Releases the transmissions of the given expired proposal back into the workers.
+    /// If the proposal spans an epoch transition (i.e. its committee is not the committee of the current round),
+    /// the transmissions are handled per the epoch transition policy.
+    ///
+    /// Note: The transmissions that are already in storage or in the ledger are never reinserted,
+    /// so a carried-over transmission is not included twice.
+    async fn release_expired_proposal(&self, proposal: Proposal<N>) -> Result<()> {
+        // Retrieve the committee lookback for the current round.
+        let committee_lookback = self.ledger.get_committee_lookback_for_round(self.current_round())?;
+        // If the proposal is within the epoch, carry over its transmissions.
+        if !is_epoch_transition(proposal.committee(), &committee_lookback) {
+            return self.reinsert_transmissions_into_workers(proposal);
+        }
+        let (round, num_transmissions) = (proposal.round(), proposal.transmissions().len());
+        match self.epoch_transition_policy() {
+            EpochTransitionPolicy::CarryOver => {
+                debug!("Carrying over {num_transmissions} transmissions from round {round} into the new epoch");
+                self.reinsert_transmissions_into_workers(proposal)
+            }
+            EpochTransitionPolicy::Revalidate => {
+                // Re-validate the transmissions against the ledger, in order.
+                let mut transmissions = IndexMap::new();
+                for (transmission_id, transmission) in proposal.into_transmissions() {
+                    if self.is_transmission_still_valid(transmission_id, transmission.clone()).await {
+                        transmissions.insert(transmission_id, transmission);
+                    }
+                }
+                debug!(
+                    "Carrying over {} of {num_transmissions} transmissions from round {round} into the new epoch",
+                    transmissions.len()
+                );
+                assign_to_workers(
+                    &self.workers,
+                    &self.gateway.worker_affinity(),
+                    transmissions.into_iter(),
+                    |worker, transmission_id, transmission| {
+                        worker.reinsert(transmission_id, transmission);
+                    },
+                )
+            }
+            EpochTransitionPolicy::Drop => {
+                debug!("Dropping {num_transmissions} transmissions from round {round} at the epoch transition");
+                Ok(())
+            }
+        }
+    }
+
+    /// Returns `true` if the given transmission is not in the ledger, and is still valid.
+    async fn is_transmission_still_valid(&self, id: TransmissionID<N>, transmission: Transmission<N>) -> bool {
+        // Check if the ledger already contains the transmission.
+        if self.ledger.contains_transmission(&id).unwrap_or(true) {
+            return false;
+        }
+        match (id, transmission) {
+            (TransmissionID::Solution(solution_id), Transmission::Solution(solution)) => {
+                self.ledger.check_solution_basic(solution_id, solution).await.is_ok()
+            }
+            (TransmissionID::Transaction(transaction_id), Transmission::Transaction(transaction)) => {
+                self.ledger.check_transaction_basic(transaction_id, transaction).await.is_ok()
+            }
+            // Note: Ratifications are not supported by the protocol, and all other combinations are invalid.
+            _ => false,
+        }
+    }
+
     /// Increments to the next round.
     async fn try_increment_to_the_next_round(&self, next_round: u64) -> Result<()> {
+        // Determine whether the primary is catching up over several rounds, in which case it is not paced.
+        let is_catching_up = next_round > self.current_round().saturating_add(1);
         // If the next round is within GC range, then iterate to the penultimate round.
         if self.current_round() + self.storage.max_gc_rounds() >= next_round {
             let mut fast_forward_round = self.current_round();
@@ -1146,15 +2140,27 @@ impl<N: Network> Primary<N> {
             while fast_forward_round < next_round.saturating_sub(1) {
                 // Update to the next round in storage.
                 fast_forward_round = self.storage.increment_to_next_round(fast_forward_round)?;
-                // Clear the proposed batch.
-                *self.proposed_batch.write() = None;
+                // Clear the proposed batch, releasing its transmissions.
+                let proposal = self.proposed_batch.write().take();
+                if let Some(proposal) = proposal {
+                    if let Err(e) = self.release_expired_proposal(proposal).await {
+                        warn!("Failed to release the transmissions of the expired batch proposal - {e}");
+                    }
+                }
             }
         }
 
+        // Pace the round advancement, if a minimum interval between rounds is set, unless the primary is catching up.
+        if !is_catching_up && self.current_round() < next_round {
+            self.wait_for_min_round_interval().await;
+        }
+
         // Retrieve the current round.
         let current_round = self.current_round();
         // Attempt to advance to the next round.
         if current_round < next_round {
+            // Ensure the committee transition into the next round (if any) is safe.
+            self.ensure_safe_committee_transition(next_round)?;
             // If a BFT sender was provided, send the current round to the BFT.
             let is_ready = if let Some(bft_sender) = self.bft_sender.get() {
                 match bft_sender.send_primary_round_to_bft(current_round).await {
@@ -1172,6 +2178,13 @@ impl<N: Network> Primary<N> {
                 // Set 'is_ready' to 'true'.
                 true
             };
+            // Record the time of the round advancement.
+            if self.current_round() > current_round {
+                *self.last_round_advance.lock() = Some(Instant::now());
+                self.gateway.observe_round(self.current_round());
+                // Observe the committee of the new round.
+                self.observe_committee(self.current_round());
+            }
 
             // Log whether the next round is ready.
             match is_ready {
@@ -1187,6 +2200,46 @@ impl<N: Network> Primary<N> {
         Ok(())
     }
 
+    /// Returns `true` if the primary is stuck, as it has not advanced its round for too long.
+    pub fn is_stuck(&self) -> bool {
+        self.stuck.load(Ordering::SeqCst)
+    }
+
+    /// Updates the stuck mode, based on the time since the last round advancement.
+    /// Returns `true` if the primary is stuck.
+    ///
+    /// Note: The primary is only considered stuck once it has advanced its round at least once.
+    fn update_stuck_mode(&self) -> bool {
+        let elapsed = self.last_round_advance.lock().map(|instant| instant.elapsed());
+        let is_stuck = elapsed.map_or(false, |elapsed| elapsed >= Duration::from_secs(STUCK_ROUND_IN_SECS));
+        // Update the stuck mode, and log the transition.
+        let round = self.current_round();
+        match (self.stuck.swap(is_stuck, Ordering::SeqCst), is_stuck) {
+            (false, true) => {
+                let message =
+                    format!("Primary is stuck in round {round} (no round advancement in {STUCK_ROUND_IN_SECS}s)");
+                error!("ALERT - {message}");
+                self.alert(AlertEvent::StuckRound, message);
+            }
+            (true, false) => info!("Primary is no longer stuck, and advanced to round {round}"),
+            _ => (),
+        }
+        is_stuck
+    }
+
+    /// Waits until the minimum interval between rounds has elapsed since the last round advancement.
+    async fn wait_for_min_round_interval(&self) {
+        let min_round_interval = self.min_round_interval();
+        if min_round_interval.is_zero() {
+            return;
+        }
+        // Note: The lock is released before sleeping.
+        let elapsed = self.last_round_advance.lock().map(|instant| instant.elapsed());
+        if let Some(remaining) = elapsed.and_then(|elapsed| min_round_interval.checked_sub(elapsed)) {
+            tokio::time::sleep(remaining).await;
+        }
+    }
+
     /// Ensures the primary is signing for the specified batch round.
     /// This method is used to ensure: for a given round, as soon as the primary starts proposing,
     /// it will no longer sign for the previous round (as it has enough previous certificates to proceed).
@@ -1233,7 +2286,7 @@ impl<N: Network> Primary<N> {
             };
         }
         // Broadcast the certified batch to all validators.
-        self.gateway.broadcast(Event::BatchCertified(certificate.clone().into()));
+        self.broadcast_certificate(&certificate);
         // Log the certified batch.
         let num_transmissions = certificate.transmission_ids().len();
         let round = certificate.round();
@@ -1242,16 +2295,41 @@ impl<N: Network> Primary<N> {
         self.try_increment_to_the_next_round(round + 1).await
     }
 
+    /// Broadcasts the given certificate to all connected validators, retrying the delivery to the
+    /// validators that did not acknowledge it, as configured by the certificate retry policy.
+    fn broadcast_certificate(&self, certificate: &BatchCertificate<N>) {
+        // Construct the event.
+        let event = Event::BatchCertified(certificate.clone().into());
+        // Retrieve the retry policy.
+        let policy = self.certificate_retry_policy();
+        // Send the certificate to each connected validator.
+        for peer_ip in self.gateway.connected_peers().read().iter().copied() {
+            let (gateway, event_) = (self.gateway.clone(), event.clone());
+            tokio::spawn(async move {
+                send_with_retry(&gateway, peer_ip, event_, policy).await;
+            });
+        }
+    }
+
     /// Inserts the missing transmissions from the proposal into the workers.
-    fn insert_missing_transmissions_into_workers(
+    async fn insert_missing_transmissions_into_workers(
         &self,
         peer_ip: SocketAddr,
         transmissions: impl Iterator<Item = (TransmissionID<N>, Transmission<N>)>,
     ) -> Result<()> {
+        // Assign the transmissions to the workers.
+        let mut assigned = Vec::new();
+        assign_to_workers(
+            &self.workers,
+            &self.gateway.worker_affinity(),
+            transmissions,
+            |worker, transmission_id, transmission| assigned.push((worker.clone(), transmission_id, transmission)),
+        )?;
         // Insert the transmissions into the workers.
-        assign_to_workers(&self.workers, transmissions, |worker, transmission_id, transmission| {
-            worker.process_transmission_from_peer(peer_ip, transmission_id, transmission);
-        })
+        for (worker, transmission_id, transmission) in assigned {
+            worker.process_transmission_from_peer(peer_ip, transmission_id, transmission).await;
+        }
+        Ok(())
     }
 
     /// Re-inserts the transmissions from the proposal into the workers.
@@ -1259,6 +2337,7 @@ impl<N: Network> Primary<N> {
         // Re-insert the transmissions into the workers.
         assign_to_workers(
             &self.workers,
+            &self.gateway.worker_affinity(),
             proposal.into_transmissions().into_iter(),
             |worker, transmission_id, transmission| {
                 worker.reinsert(transmission_id, transmission);
@@ -1296,14 +2375,23 @@ impl<N: Network> Primary<N> {
         }
 
         // If the peer is ahead, use the batch header to sync up to the peer.
-        let missing_transmissions = self.sync_with_batch_header_from_peer(peer_ip, batch_header).await?;
+        // Note: The peer is not necessarily the author, so the worker of each transmission is left to the peer.
+        let missing_transmissions = self.sync_with_batch_header_from_peer(peer_ip, batch_header, None).await?;
 
         // Check if the certificate needs to be stored.
         if !self.storage.contains_certificate(certificate.id()) {
             // Store the batch certificate.
             let storage = self.storage.clone();
             let certificate_clone = certificate.clone();
-            spawn_blocking!(storage.insert_certificate(certificate_clone, missing_transmissions))?;
+            if let Err(e) = spawn_blocking!(storage.insert_certificate(certificate_clone, missing_transmissions)) {
+                // If the certificate is malformed, penalize the peer.
+                // Note: A valid certificate may still fail to be inserted, e.g. if it arrived concurrently
+                // from another peer, so only the failures that do not depend on the order of arrival are counted.
+                if self.storage.is_malformed_certificate(&certificate) {
+                    self.gateway.report_invalid_certificate(peer_ip);
+                }
+                return Err(e);
+            }
             debug!("Stored a batch certificate for round {batch_round} from '{peer_ip}'");
             // If a BFT sender was provided, send the round and certificate to the BFT.
             if let Some(bft_sender) = self.bft_sender.get() {
@@ -1317,11 +2405,12 @@ impl<N: Network> Primary<N> {
         Ok(())
     }
 
-    /// Recursively syncs using the given batch header.
+    /// Recursively syncs using the given batch header, fetching transmissions from the given peer workers (if known).
     async fn sync_with_batch_header_from_peer(
         &self,
         peer_ip: SocketAddr,
         batch_header: &BatchHeader<N>,
+        peer_worker_ids: Option<&[u8]>,
     ) -> Result<HashMap<TransmissionID<N>, Transmission<N>>> {
         // Retrieve the batch round.
         let batch_round = batch_header.round();
@@ -1330,6 +2419,8 @@ impl<N: Network> Primary<N> {
         if batch_round <= self.storage.gc_round() {
             bail!("Round {batch_round} is too far in the past")
         }
+        // If the certificate round is too far in the future, do not buffer it.
+        self.ensure_within_open_rounds(peer_ip, batch_round)?;
 
         // Determine if quorum threshold is reached on the batch round.
         let is_quorum_threshold_reached = {
@@ -1370,9 +2461,10 @@ impl<N: Network> Primary<N> {
             }
         };
         // Ensure the primary has all of the transmissions.
-        let missing_transmissions = self.fetch_missing_transmissions(peer_ip, batch_header).await.map_err(|e| {
-            anyhow!("Failed to fetch missing transmissions for round {batch_round} from '{peer_ip}' - {e}")
-        })?;
+        let missing_transmissions =
+            self.fetch_missing_transmissions(peer_ip, batch_header, peer_worker_ids).await.map_err(|e| {
+                anyhow!("Failed to fetch missing transmissions for round {batch_round} from '{peer_ip}' - {e}")
+            })?;
 
         // Iterate through the missing previous certificates.
         for batch_certificate in missing_previous_certificates {
@@ -1387,12 +2479,70 @@ impl<N: Network> Primary<N> {
         Ok(missing_transmissions)
     }
 
+    /// Ensures the given batch header does not reference previous certificates at or below the GC round,
+    /// which can no longer be validated, if the stale proposal policy refuses such proposals.
+    fn ensure_proposal_is_not_stale(&self, peer_ip: SocketAddr, batch_header: &BatchHeader<N>) -> Result<()> {
+        let policy = self.stale_proposal_policy();
+        if policy == StaleProposalPolicy::Process || batch_header.previous_certificate_ids().is_empty() {
+            return Ok(());
+        }
+        // Ensure the previous round is above the GC round.
+        let (previous_round, gc_round) = (batch_header.round().saturating_sub(1), self.storage.gc_round());
+        if previous_round > gc_round {
+            return Ok(());
+        }
+        // Direct the peer to sync, by sending it the block locators in a primary ping, at most once per interval.
+        if policy == StaleProposalPolicy::RefuseAndSync && self.is_stale_proposal_sync_due(peer_ip) {
+            if let Some(primary_ping) = self.prepare_primary_ping() {
+                let gateway = self.gateway.clone();
+                tokio::spawn(async move { gateway.send(peer_ip, Event::PrimaryPing(primary_ping)).await });
+            }
+        }
+        bail!(
+            "Refusing a proposal for round {} from '{peer_ip}' (references round {previous_round}, at or below the GC round {gc_round})",
+            batch_header.round()
+        )
+    }
+
+    /// Returns `true` if the given peer was not directed to sync within the interval, and records it as directed.
+    fn is_stale_proposal_sync_due(&self, peer_ip: SocketAddr) -> bool {
+        let mut stale_proposal_syncs = self.stale_proposal_syncs.lock();
+        // Remove the expired entries, so that only the recently-directed peers are retained.
+        let interval = Duration::from_millis(STALE_PROPOSAL_SYNC_IN_MS);
+        stale_proposal_syncs.retain(|_, directed_at| directed_at.elapsed() < interval);
+        if stale_proposal_syncs.contains_key(&peer_ip) {
+            return false;
+        }
+        stale_proposal_syncs.insert(peer_ip, Instant::now());
+        true
+    }
+
+    /// Ensures the given round is within the open rounds, ahead of the latest committed round.
+    /// Otherwise, the round is refused, and the primary starts a block sync round to catch up to the peer.
+    fn ensure_within_open_rounds(&self, peer_ip: SocketAddr, round: u64) -> Result<()> {
+        // Retrieve the latest committed round.
+        let committed_round = self.ledger.latest_round();
+        // Determine the highest round that may be buffered.
+        let max_round = committed_round.saturating_add(self.max_open_rounds());
+        // Ensure the round is within the open rounds.
+        if round > max_round {
+            // Catch up to the peer through block sync, instead of waiting for the next sync interval.
+            self.sync.try_block_sync_now();
+            bail!(
+                "Round {round} from '{peer_ip}' is beyond the open rounds (committed round {committed_round}, max round {max_round}) - deferring to block sync"
+            )
+        }
+        Ok(())
+    }
+
     /// Fetches any missing transmissions for the specified batch header.
-    /// If a transmission does not exist, it will be fetched from the specified peer IP.
+    /// If a transmission does not exist, it will be fetched from the given worker of the specified peer IP,
+    /// or from the worker that the peer assigns it to, if the worker is unknown.
     async fn fetch_missing_transmissions(
         &self,
         peer_ip: SocketAddr,
         batch_header: &BatchHeader<N>,
+        peer_worker_ids: Option<&[u8]>,
     ) -> Result<HashMap<TransmissionID<N>, Transmission<N>>> {
         // If the round is <= the GC round, return early.
         if batch_header.round() <= self.storage.gc_round() {
@@ -1413,18 +2563,23 @@ impl<N: Network> Primary<N> {
 
         // Retrieve the number of workers.
         let num_workers = self.num_workers();
+        // Retrieve the worker affinity.
+        let affinity = self.gateway.worker_affinity();
         // Iterate through the transmission IDs.
-        for transmission_id in batch_header.transmission_ids() {
+        for (index, transmission_id) in batch_header.transmission_ids().iter().enumerate() {
             // If the transmission does not exist in storage, proceed to fetch the transmission.
             if !self.storage.contains_transmission(*transmission_id) {
                 // Determine the worker ID.
-                let Ok(worker_id) = assign_to_worker(*transmission_id, num_workers) else {
+                let Ok(worker_id) = affinity.assign(*transmission_id, num_workers) else {
                     bail!("Unable to assign transmission ID '{transmission_id}' to a worker")
                 };
                 // Retrieve the worker.
                 let Some(worker) = workers.get(worker_id as usize) else { bail!("Unable to find worker {worker_id}") };
+                // Determine the worker ID of the peer.
+                let peer_worker_id =
+                    peer_worker_ids.and_then(|worker_ids| worker_ids.get(index).copied()).unwrap_or(UNKNOWN_WORKER_ID);
                 // Push the callback onto the list.
-                fetch_transmissions.push(worker.get_or_fetch_transmission(peer_ip, *transmission_id));
+                fetch_transmissions.push(worker.get_or_fetch_transmission(peer_ip, *transmission_id, peer_worker_id));
             }
         }
 
@@ -1551,6 +2706,8 @@ impl<N: Network> Primary<N> {
         self.workers.iter().for_each(|worker| worker.shut_down());
         // Abort the tasks.
         self.handles.lock().iter().for_each(|handle| handle.abort());
+        // Abort the tasks monitored by the watchdog.
+        self.watchdog.shut_down();
         // Close the gateway.
         self.gateway.shut_down().await;
     }
@@ -1576,6 +2733,15 @@ mod tests {
     async fn primary_without_handlers(
         rng: &mut TestRng,
     ) -> (Primary<CurrentNetwork>, Vec<(SocketAddr, Account<CurrentNetwork>)>) {
+        let (primary, accounts, _) = primary_with_mock_ledger(rng).await;
+        (primary, accounts)
+    }
+
+    // Returns a primary, a list of accounts in the configured committee, and the mock ledger.
+    async fn primary_with_mock_ledger(
+        rng: &mut TestRng,
+    ) -> (Primary<CurrentNetwork>, Vec<(SocketAddr, Account<CurrentNetwork>)>, Arc<MockLedgerService<CurrentNetwork>>)
+    {
         // Create a committee containing the primary's account.
         let (accounts, committee) = {
             const COMMITTEE_SIZE: usize = 4;
@@ -1597,7 +2763,7 @@ mod tests {
         let storage = Storage::new(ledger.clone(), Arc::new(BFTMemoryService::new()), 10);
 
         // Initialize the primary.
-        let mut primary = Primary::new(account, storage, ledger, None, &[], None).unwrap();
+        let mut primary = Primary::new(account, storage, ledger.clone(), None, &[], None).unwrap();
 
         // Construct a worker instance.
         primary.workers = Arc::from([Worker::new(
@@ -1612,7 +2778,7 @@ mod tests {
             primary.gateway.insert_connected_peer(a.0, a.0, a.1.address());
         }
 
-        (primary, accounts)
+        (primary, accounts, ledger)
     }
 
     // Creates a mock solution.
@@ -1826,6 +2992,102 @@ mod tests {
         assert!(primary.proposed_batch.read().is_some());
     }
 
+    #[tokio::test]
+    async fn test_current_proposal_preview() {
+        let mut rng = TestRng::default();
+        let (primary, _) = primary_without_handlers(&mut rng).await;
+
+        // Ensure there is no preview, while there are no transmissions to propose.
+        assert!(primary.current_proposal_preview().is_none());
+
+        // Generate a solution and a transaction, and store them on one of the workers.
+        let (solution_commitment, solution) = sample_unconfirmed_solution(&mut rng);
+        let (transaction_id, transaction) = sample_unconfirmed_transaction(&mut rng);
+        primary.workers[0].process_unconfirmed_solution(solution_commitment, solution).await.unwrap();
+        primary.workers[0].process_unconfirmed_transaction(transaction_id, transaction).await.unwrap();
+
+        // Preview the proposal, and ensure the preview does not drain the workers.
+        let preview = primary.current_proposal_preview().unwrap();
+        assert_eq!(primary.current_proposal_preview().unwrap(), preview);
+        assert_eq!(primary.workers[0].num_transmissions(), 2);
+        assert!(preview.transmission_ids.contains(&TransmissionID::Solution(solution_commitment)));
+        assert!(preview.transmission_ids.contains(&TransmissionID::Transaction(transaction_id)));
+
+        // Propose a batch.
+        assert!(primary.propose_batch().await.is_ok());
+
+        // Ensure the preview matches the proposed batch.
+        {
+            let proposed_batch = primary.proposed_batch.read();
+            let batch_header = proposed_batch.as_ref().unwrap().batch_header();
+            assert_eq!(preview.round, batch_header.round());
+            assert_eq!(&preview.transmission_ids, batch_header.transmission_ids());
+            assert_eq!(&preview.previous_certificate_ids, batch_header.previous_certificate_ids());
+        }
+        // Ensure there is no preview, while the batch is being proposed.
+        assert!(primary.current_proposal_preview().is_none());
+    }
+
+    #[tokio::test]
+    async fn test_epoch_transition_with_queued_transmissions() {
+        let mut rng = TestRng::default();
+
+        use EpochTransitionPolicy::*;
+
+        for policy in [CarryOver, Revalidate, Drop] {
+            let (primary, accounts, ledger) = primary_with_mock_ledger(&mut rng).await;
+            primary.set_epoch_transition_policy(policy);
+            assert_eq!(primary.epoch_transition_policy(), policy);
+
+            // Propose a batch with a solution and a transaction.
+            let (solution_commitment, solution) = sample_unconfirmed_solution(&mut rng);
+            let (transaction_id, transaction) = sample_unconfirmed_transaction(&mut rng);
+            primary.workers[0].process_unconfirmed_solution(solution_commitment, solution).await.unwrap();
+            primary.workers[0].process_unconfirmed_transaction(transaction_id, transaction).await.unwrap();
+            assert!(primary.propose_batch().await.is_ok());
+            let proposed_ids =
+                primary.proposed_batch.read().as_ref().unwrap().batch_header().transmission_ids().clone();
+            assert_eq!(proposed_ids.len(), 2);
+            assert_eq!(primary.workers[0].num_transmissions(), 0);
+
+            // Queue another transaction, while the batch is being proposed.
+            let (queued_id, queued_transaction) = sample_unconfirmed_transaction(&mut rng);
+            primary.workers[0].process_unconfirmed_transaction(queued_id, queued_transaction).await.unwrap();
+
+            // Transition to a new epoch, in which the stake of a validator changes.
+            let committee = ledger.current_committee().unwrap();
+            let mut members = committee.members().clone();
+            members.insert(accounts[1].1.address(), (2 * MIN_VALIDATOR_STAKE, true));
+            ledger.set_committee(Committee::new(committee.starting_round(), members).unwrap());
+
+            // Ensure the signatures for the batch are rejected, as it spans the epoch transition.
+            for (peer_ip, signature) in peer_signatures_for_proposal(&primary, &accounts, &mut rng) {
+                assert!(primary.process_batch_signature_from_peer(peer_ip, signature).await.is_err());
+            }
+            assert!(!primary.storage.contains_certificate_in_round_from(1, primary.gateway.account().address()));
+
+            // Ensure the batch is abandoned, and no other batch is proposed for the round (as it would equivocate).
+            assert!(primary.propose_batch().await.is_ok());
+            assert!(primary.proposed_batch.read().is_none());
+
+            // Ensure the transmissions of the batch are handled per the policy, and the queued one is kept.
+            let ready_ids = primary.workers[0].transmission_ids();
+            assert!(ready_ids.contains(&TransmissionID::Transaction(queued_id)));
+            match policy {
+                CarryOver | Revalidate => {
+                    assert_eq!(ready_ids.len(), 3);
+                    assert!(proposed_ids.iter().all(|id| ready_ids.contains(id)));
+                }
+                Drop => assert_eq!(ready_ids.len(), 1),
+            }
+
+            // Ensure repeated proposals in the round leave the transmissions unchanged (i.e. none are duplicated).
+            assert!(primary.propose_batch().await.is_ok());
+            assert!(primary.proposed_batch.read().is_none());
+            assert_eq!(primary.workers[0].transmission_ids(), ready_ids);
+        }
+    }
+
     #[tokio::test]
     async fn test_propose_batch_in_round() {
         let round = 3;
@@ -1853,6 +3115,214 @@ mod tests {
         assert!(primary.proposed_batch.read().is_some());
     }
 
+    #[tokio::test]
+    async fn test_unsafe_committee_transition_is_refused() {
+        let mut rng = TestRng::default();
+        let (primary, accounts, ledger) = primary_with_mock_ledger(&mut rng).await;
+        assert!(primary.set_max_committee_change(Some(0.0)).is_err());
+        primary.set_max_committee_change(Some(1.0 / 3.0)).unwrap();
+
+        // Advance to the next round, to observe the committee of the current epoch.
+        let _ = primary.try_increment_to_the_next_round(primary.current_round() + 1).await;
+        let committee = ledger.current_committee().unwrap();
+
+        // Apply a safe transition, in which the stake of a validator changes, and ensure the primary advances.
+        let mut members = committee.members().clone();
+        members.insert(accounts[1].1.address(), (2 * MIN_VALIDATOR_STAKE, true));
+        ledger.set_committee(Committee::new(committee.starting_round(), members.clone()).unwrap());
+        let round = primary.current_round();
+        let _ = primary.try_increment_to_the_next_round(round + 1).await;
+        assert_eq!(primary.current_round(), round + 1);
+
+        // Apply an unsafe transition, in which two of the four validators are swapped out in one step.
+        for (_, account) in &accounts[2..] {
+            members.swap_remove(&account.address());
+            members.insert(Account::<CurrentNetwork>::new(&mut rng).unwrap().address(), (MIN_VALIDATOR_STAKE, true));
+        }
+        let next = Committee::new(committee.starting_round(), members).unwrap();
+        ledger.set_committee(next.clone());
+        let previous = primary.epoch_tracker.committee().unwrap();
+
+        // Ensure the primary refuses to advance into the unsafe committee, with the reason.
+        let error = primary.try_increment_to_the_next_round(round + 2).await.unwrap_err();
+        assert!(error.to_string().contains("violates quorum intersection"), "{error}");
+        assert!(error.to_string().contains("refusing to participate"), "{error}");
+        assert_eq!(primary.current_round(), round + 1);
+        assert_eq!(primary.refused_committee.lock().clone(), Some(next.clone()));
+        // Ensure the primary does not propose in the unsafe committee, nor track it as its epoch.
+        let error = primary.propose_batch().await.unwrap_err();
+        assert!(error.to_string().contains("violates quorum intersection"), "{error}");
+        assert!(primary.proposed_batch.read().is_none());
+        assert_eq!(primary.epoch_tracker.committee(), Some(previous));
+
+        // Ensure the primary advances once quorum intersection is no longer checked.
+        primary.set_max_committee_change(None).unwrap();
+        let _ = primary.try_increment_to_the_next_round(round + 2).await;
+        assert_eq!(primary.current_round(), round + 2);
+        assert_eq!(primary.epoch_tracker.committee(), Some(next));
+    }
+
+    #[tokio::test]
+    async fn test_propose_batch_with_min_previous_certificates() {
+        let mut rng = TestRng::default();
+
+        // Initializes a primary in round 3, whose previous round has certificates from the given number of authors,
+        // and which requires a batch to reference the entire committee.
+        async fn setup(
+            num_authors: usize,
+            rng: &mut TestRng,
+        ) -> (Primary<CurrentNetwork>, Vec<(SocketAddr, Account<CurrentNetwork>)>) {
+            let round = 3;
+            let (primary, accounts) = primary_without_handlers(rng).await;
+            let previous_certificate_ids = store_certificate_chain(&primary, &accounts, round - 1, rng);
+            for (_, account) in accounts.iter().take(num_authors) {
+                let (certificate, transmissions) = create_batch_certificate(
+                    account.address(),
+                    &accounts,
+                    round - 1,
+                    previous_certificate_ids.clone(),
+                    rng,
+                );
+                primary.storage.insert_certificate(certificate, transmissions).unwrap();
+            }
+            primary.storage.increment_to_next_round(round - 1).unwrap();
+            // Queue a transaction, and set the round as just advanced.
+            let (transaction_id, transaction) = sample_unconfirmed_transaction(rng);
+            primary.workers[0].process_unconfirmed_transaction(transaction_id, transaction).await.unwrap();
+            *primary.last_round_advance.lock() = Some(Instant::now());
+            primary.set_min_previous_certificates(accounts.len()).unwrap();
+            (primary, accounts)
+        }
+        // Returns the number of previous certificates referenced by the proposed batch.
+        let num_referenced = |primary: &Primary<CurrentNetwork>| {
+            let proposal = primary.proposed_batch.read();
+            proposal.as_ref().map(|proposal| proposal.batch_header().previous_certificate_ids().len())
+        };
+
+        // Ensure the minimum is bounded by the maximum committee size.
+        let (primary, accounts) = setup(4, &mut rng).await;
+        let max_committee_size = Committee::<CurrentNetwork>::MAX_COMMITTEE_SIZE as usize;
+        assert!(primary.set_min_previous_certificates(max_committee_size + 1).is_err());
+
+        // Ensure the batch references at least the minimum number of certificates, when enough exist.
+        assert!(primary.propose_batch().await.is_ok());
+        assert!(num_referenced(&primary).unwrap() >= accounts.len());
+
+        // Ensure the primary waits for the remaining certificates, when only a quorum exists.
+        let (primary, accounts) = setup(accounts.len() - 1, &mut rng).await;
+        assert!(primary.propose_batch().await.is_ok());
+        assert!(primary.proposed_batch.read().is_none());
+
+        // Ensure the primary falls back to the quorum, once the remaining certificates are not available in time.
+        let delay = Duration::from_millis(MAX_BATCH_DELAY_IN_MS);
+        *primary.last_round_advance.lock() = Instant::now().checked_sub(delay);
+        assert!(primary.propose_batch().await.is_ok());
+        assert_eq!(num_referenced(&primary), Some(accounts.len() - 1));
+    }
+
+    #[tokio::test]
+    async fn test_propose_batch_skips_stale_rounds_after_restart() {
+        let network_round = 5;
+        let mut rng = TestRng::default();
+        let (primary, accounts) = primary_without_handlers(&mut rng).await;
+
+        // Store the certificates of the peers, as the network moved ahead while the primary was offline.
+        let mut previous_certificate_ids = IndexSet::new();
+        for round in 1..=network_round {
+            let mut certificate_ids = IndexSet::new();
+            for (_, account) in accounts.iter().skip(1) {
+                let (certificate, transmissions) = create_batch_certificate(
+                    account.address(),
+                    &accounts,
+                    round,
+                    previous_certificate_ids.clone(),
+                    &mut rng,
+                );
+                certificate_ids.insert(certificate.id());
+                primary.storage.insert_certificate(certificate, transmissions).unwrap();
+            }
+            previous_certificate_ids = certificate_ids;
+        }
+        assert_eq!(primary.current_round(), 1);
+
+        // Restart the primary, with transmissions ready to be proposed.
+        *primary.resuming_since.lock() = Some(Instant::now());
+        let (transaction_id, transaction) = sample_unconfirmed_transaction(&mut rng);
+        primary.workers[0].process_unconfirmed_transaction(transaction_id, transaction).await.unwrap();
+
+        // Ensure the primary does not propose, while the network round is not known.
+        assert_eq!(primary.network_round().unwrap(), None);
+        assert!(primary.propose_batch().await.is_ok());
+        assert!(primary.proposed_batch.read().is_none());
+        // Ensure a single peer is not enough to determine the network round.
+        primary.update_peer_round(accounts[1].1.address(), network_round);
+        assert_eq!(primary.network_round().unwrap(), None);
+        assert!(primary.propose_batch().await.is_ok());
+        assert!(primary.proposed_batch.read().is_none());
+
+        // Ensure the primary skips the stale rounds, and proposes at the network round.
+        for (_, account) in accounts.iter().skip(2) {
+            primary.update_peer_round(account.address(), network_round);
+        }
+        assert_eq!(primary.network_round().unwrap(), Some(network_round));
+        assert!(primary.propose_batch().await.is_ok());
+        assert_eq!(primary.current_round(), network_round);
+        assert_eq!(primary.proposed_batch.read().as_ref().map(Proposal::round), Some(network_round));
+        assert!(primary.resuming_since.lock().is_none());
+
+        // Ensure the primary defers to sync, if the network is beyond the GC range on restart.
+        let far_round = network_round + primary.storage.max_gc_rounds() + 1;
+        for (_, account) in accounts.iter().skip(1) {
+            primary.update_peer_round(account.address(), far_round);
+        }
+        *primary.resuming_since.lock() = Some(Instant::now());
+        assert!(primary.propose_batch().await.is_ok());
+        assert_eq!(primary.current_round(), network_round);
+        assert!(primary.resuming_since.lock().is_some());
+        // Ensure the primary resumes at its own round, once the resume delay has elapsed.
+        *primary.resuming_since.lock() = Instant::now().checked_sub(Duration::from_millis(MAX_RESUME_DELAY_IN_MS));
+        assert!(primary.propose_batch().await.is_ok());
+        assert_eq!(primary.current_round(), network_round);
+        assert!(primary.resuming_since.lock().is_none());
+    }
+
+    #[tokio::test]
+    async fn test_min_round_interval_paces_rounds() {
+        let mut rng = TestRng::default();
+        let (primary, _) = primary_without_handlers(&mut rng).await;
+
+        // Set the minimum round interval.
+        let min_round_interval = Duration::from_millis(50);
+        assert!(primary.set_min_round_interval(Duration::from_millis(MAX_MIN_ROUND_INTERVAL_IN_MS + 1)).is_err());
+        primary.set_min_round_interval(min_round_interval).unwrap();
+        assert_eq!(primary.min_round_interval(), min_round_interval);
+
+        // Drive the rounds forward as fast as possible.
+        const NUM_ROUNDS: u64 = 5;
+        let start_round = primary.current_round();
+        let start = Instant::now();
+        for _ in 0..NUM_ROUNDS {
+            let _ = primary.try_increment_to_the_next_round(primary.current_round() + 1).await;
+        }
+        // Ensure every round advanced, but no faster than the minimum interval.
+        assert_eq!(primary.current_round(), start_round + NUM_ROUNDS);
+        assert!(start.elapsed() >= min_round_interval * (NUM_ROUNDS as u32 - 1));
+
+        // Ensure catching up over several rounds is not paced, including its final round.
+        *primary.last_round_advance.lock() = Some(Instant::now());
+        let start = Instant::now();
+        let _ = primary.try_increment_to_the_next_round(primary.current_round() + 3).await;
+        assert_eq!(primary.current_round(), start_round + NUM_ROUNDS + 3);
+        assert!(start.elapsed() < min_round_interval);
+
+        // Disable the pacing, and ensure the rounds still advance.
+        primary.set_min_round_interval(Duration::ZERO).unwrap();
+        for _ in 0..NUM_ROUNDS {
+            let _ = primary.try_increment_to_the_next_round(primary.current_round() + 1).await;
+        }
+        assert_eq!(primary.current_round(), start_round + 2 * NUM_ROUNDS + 3);
+    }
+
     #[tokio::test]
     async fn test_batch_propose_from_peer() {
         let mut rng = TestRng::default();
@@ -1874,16 +3344,330 @@ mod tests {
 
         // Make sure the primary is aware of the transmissions in the proposal.
         for (transmission_id, transmission) in proposal.transmissions() {
-            primary.workers[0].process_transmission_from_peer(peer_ip, *transmission_id, transmission.clone())
+            primary.workers[0].process_transmission_from_peer(peer_ip, *transmission_id, transmission.clone()).await
         }
 
         // The author must be known to resolver to pass propose checks.
         primary.gateway.resolver().insert_peer(peer_ip, peer_ip, peer_account.1.address());
 
+        // Construct the batch propose event.
+        let batch_propose = primary.batch_propose_for(proposal.batch_header().clone()).unwrap();
         // Try to process the batch proposal from the peer, should succeed.
-        assert!(
-            primary.process_batch_propose_from_peer(peer_ip, (*proposal.batch_header()).clone().into()).await.is_ok()
+        assert!(primary.process_batch_propose_from_peer(peer_ip, batch_propose).await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_batch_header_verification_is_cached() {
+        let mut rng = TestRng::default();
+        let (primary, accounts) = primary_without_handlers(&mut rng).await;
+        assert!(primary.storage.is_header_cache_enabled());
+        // Set the primary as a warm standby, so that it verifies batch proposals without signing them.
+        primary.enable_standby();
+
+        // Create a valid proposal with an author that isn't the primary.
+        let round = 1;
+        let peer_account = &accounts[1];
+        let peer_ip = peer_account.0;
+        let timestamp = now();
+        let proposal = create_test_proposal(
+            &peer_account.1,
+            primary.ledger.current_committee().unwrap(),
+            round,
+            Default::default(),
+            timestamp,
+            &mut rng,
         );
+        // Make sure the primary is aware of the transmissions in the proposal.
+        for (transmission_id, transmission) in proposal.transmissions() {
+            primary.workers[0].process_transmission_from_peer(peer_ip, *transmission_id, transmission.clone()).await
+        }
+        // The author must be known to resolver to pass propose checks.
+        primary.gateway.resolver().insert_peer(peer_ip, peer_ip, peer_account.1.address());
+
+        // Receive the same batch header twice, and ensure it is only verified once.
+        let batch_id = proposal.batch_header().batch_id();
+        for _ in 0..2 {
+            let batch_propose = primary.batch_propose_for(proposal.batch_header().clone()).unwrap();
+            assert!(primary.process_batch_propose_from_peer(peer_ip, batch_propose).await.is_ok());
+        }
+        assert!(primary.storage.is_verified_header(batch_id));
+        assert_eq!(primary.storage.header_cache_hits(), 1);
+
+        // Ensure an invalid batch header (missing its previous certificates) is never cached as valid.
+        let invalid = create_test_proposal(
+            &peer_account.1,
+            primary.ledger.current_committee().unwrap(),
+            round + 2,
+            Default::default(),
+            timestamp,
+            &mut rng,
+        );
+        for _ in 0..2 {
+            assert!(primary.storage.check_batch_header(invalid.batch_header(), Default::default()).is_err());
+        }
+        assert!(!primary.storage.is_verified_header(invalid.batch_header().batch_id()));
+        assert_eq!(primary.storage.header_cache_hits(), 1);
+
+        // Ensure disabling the cache clears the verified batch headers.
+        primary.storage.set_header_cache_enabled(false);
+        assert!(!primary.storage.is_verified_header(batch_id));
+    }
+
+    #[tokio::test]
+    async fn test_equivocation_is_reported_once() {
+        let mut rng = TestRng::default();
+        let (primary, accounts, ledger) = primary_with_mock_ledger(&mut rng).await;
+
+        // Create two conflicting proposals for the same round, from an author that isn't the primary.
+        let round = 1;
+        let peer_account = &accounts[1];
+        let peer_ip = peer_account.0;
+        let committee = primary.ledger.current_committee().unwrap();
+        let timestamp = now();
+        let first =
+            create_test_proposal(&peer_account.1, committee.clone(), round, Default::default(), timestamp, &mut rng);
+        let second =
+            create_test_proposal(&peer_account.1, committee, round, Default::default(), timestamp + 1, &mut rng);
+
+        // Make sure the primary is aware of the transmissions in the proposals.
+        for (transmission_id, transmission) in first.transmissions().iter().chain(second.transmissions()) {
+            primary.workers[0].process_transmission_from_peer(peer_ip, *transmission_id, transmission.clone()).await
+        }
+        // The author must be known to resolver to pass propose checks.
+        primary.gateway.resolver().insert_peer(peer_ip, peer_ip, peer_account.1.address());
+
+        // Sign the first proposal.
+        let batch_propose = primary.batch_propose_for(first.batch_header().clone()).unwrap();
+        assert!(primary.process_batch_propose_from_peer(peer_ip, batch_propose).await.is_ok());
+        assert!(ledger.equivocation_reports().is_empty());
+
+        // Ensure the conflicting proposal is rejected, and reported as equivocation.
+        let batch_propose = primary.batch_propose_for(second.batch_header().clone()).unwrap();
+        assert!(primary.process_batch_propose_from_peer(peer_ip, batch_propose).await.is_err());
+        let reports = ledger.equivocation_reports();
+        assert_eq!(reports.len(), 1);
+        // Ensure the reported evidence is verifiable.
+        let (reported_first, reported_second) = reports[0].clone();
+        assert_eq!(&reported_first, first.batch_header());
+        assert_eq!(&reported_second, second.batch_header());
+        assert!(EquivocationEvidence::new(reported_first, reported_second).is_ok());
+
+        // Ensure the same equivocation is not reported again.
+        assert!(primary.report_equivocation(second.batch_header()).is_none());
+        assert_eq!(ledger.equivocation_reports().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_batch_certificate_beyond_open_rounds() {
+        let mut rng = TestRng::default();
+        let (primary, accounts) = primary_without_handlers(&mut rng).await;
+        assert_eq!(primary.max_open_rounds(), MAX_OPEN_ROUNDS);
+        assert!(primary.set_max_open_rounds(0).is_err());
+        primary.set_max_open_rounds(5).unwrap();
+
+        // Create certificates from a peer, far ahead of the latest committed round.
+        let peer_account = &accounts[1];
+        let peer_ip = peer_account.0;
+        for round in [6, 100, 10_000] {
+            let (certificate, _) =
+                create_batch_certificate(peer_account.1.address(), &accounts, round, Default::default(), &mut rng);
+            // Ensure the certificate is refused, and the primary defers to block sync.
+            let error = primary.process_batch_certificate_from_peer(peer_ip, certificate.clone()).await.unwrap_err();
+            assert!(error.to_string().contains("deferring to block sync"));
+            assert!(!primary.storage.contains_certificate(certificate.id()));
+        }
+        // Ensure the primary did not advance to the refused rounds.
+        assert_eq!(primary.current_round(), 1);
+
+        // Ensure a certificate within the open rounds is not refused for being too far ahead.
+        let (certificate, _) =
+            create_batch_certificate(peer_account.1.address(), &accounts, 5, Default::default(), &mut rng);
+        let result = primary.process_batch_certificate_from_peer(peer_ip, certificate).await;
+        assert!(result.map_or_else(|e| !e.to_string().contains("deferring to block sync"), |_| true));
+    }
+
+    #[tokio::test]
+    async fn test_batch_certificate_below_min_stake() {
+        let mut rng = TestRng::default();
+        let (primary, accounts) = primary_without_handlers(&mut rng).await;
+        assert_eq!(primary.min_certificate_stake(), 0);
+
+        // Create a certificate from a peer, whose stake is below the minimum.
+        let peer_account = &accounts[1];
+        let peer_ip = peer_account.0;
+        let (certificate, transmissions) =
+            create_batch_certificate(peer_account.1.address(), &accounts, 1, Default::default(), &mut rng);
+        // Make sure the primary is aware of the transmissions in the certificate.
+        for (transmission_id, transmission) in transmissions {
+            primary.workers[0].process_transmission_from_peer(peer_ip, transmission_id, transmission).await;
+        }
+        primary.set_min_certificate_stake(MIN_VALIDATOR_STAKE + 1);
+
+        // Ensure the certificate is not processed, from the peer or from a ping.
+        assert!(primary.process_batch_certificate_from_peer(peer_ip, certificate.clone()).await.is_ok());
+        assert!(primary.process_batch_certificate_from_ping(peer_ip, certificate.clone()).await.is_ok());
+        assert!(!primary.storage.contains_certificate(certificate.id()));
+        // Ensure the peer is still connected.
+        assert!(primary.gateway.is_connected_ip(peer_ip));
+
+        // Ensure the certificate is processed, once the peer meets the minimum stake.
+        primary.set_min_certificate_stake(MIN_VALIDATOR_STAKE);
+        assert!(primary.process_batch_certificate_from_peer(peer_ip, certificate.clone()).await.is_ok());
+        assert!(primary.storage.contains_certificate(certificate.id()));
+    }
+
+    #[tokio::test]
+    async fn test_standby_promotion_begins_signing() {
+        let mut rng = TestRng::default();
+        let (primary, accounts) = primary_without_handlers(&mut rng).await;
+
+        // Set the primary as a warm standby.
+        primary.enable_standby();
+        assert!(primary.is_standby());
+
+        // Create a valid proposal with an author that isn't the primary.
+        let round = 1;
+        let peer_account = &accounts[1];
+        let peer_ip = peer_account.0;
+        let timestamp = now();
+        let proposal = create_test_proposal(
+            &peer_account.1,
+            primary.ledger.current_committee().unwrap(),
+            round,
+            Default::default(),
+            timestamp,
+            &mut rng,
+        );
+
+        // Make sure the primary is aware of the transmissions in the proposal.
+        for (transmission_id, transmission) in proposal.transmissions() {
+            primary.workers[0].process_transmission_from_peer(peer_ip, *transmission_id, transmission.clone()).await
+        }
+
+        // The author must be known to resolver to pass propose checks.
+        primary.gateway.resolver().insert_peer(peer_ip, peer_ip, peer_account.1.address());
+
+        // Construct the batch propose event.
+        let batch_propose = primary.batch_propose_for(proposal.batch_header().clone()).unwrap();
+        // Process the batch proposal as a standby, and ensure the primary does not sign it.
+        primary.process_batch_propose_from_peer(peer_ip, batch_propose.clone()).await.unwrap();
+        assert!(!primary.signed_proposals.read().contains_key(&peer_account.1.address()));
+
+        // Promote the standby.
+        primary.promote().unwrap();
+        assert!(!primary.is_standby());
+        // Ensure the primary cannot be promoted twice.
+        assert!(primary.promote().is_err());
+
+        // Process the batch proposal again, and ensure the primary signs it without a restart.
+        primary.process_batch_propose_from_peer(peer_ip, batch_propose).await.unwrap();
+        assert!(primary.signed_proposals.read().contains_key(&peer_account.1.address()));
+    }
+
+    #[tokio::test]
+    async fn test_removed_from_committee_becomes_observer() {
+        let mut rng = TestRng::default();
+        let (primary, accounts, ledger) = primary_with_mock_ledger(&mut rng).await;
+        let committee = ledger.current_committee().unwrap();
+        assert!(!primary.is_observer());
+
+        // Create a valid proposal with an author that isn't the primary.
+        let round = 1;
+        let peer_account = &accounts[1];
+        let peer_ip = peer_account.0;
+        let proposal =
+            create_test_proposal(&peer_account.1, committee.clone(), round, Default::default(), now(), &mut rng);
+        // Make sure the primary is aware of the transmissions in the proposal.
+        for (transmission_id, transmission) in proposal.transmissions() {
+            primary.workers[0].process_transmission_from_peer(peer_ip, *transmission_id, transmission.clone()).await
+        }
+        // The author must be known to resolver to pass propose checks.
+        primary.gateway.resolver().insert_peer(peer_ip, peer_ip, peer_account.1.address());
+        let batch_propose = primary.batch_propose_for(proposal.batch_header().clone()).unwrap();
+
+        // Replace the primary with a new validator in the committee.
+        let mut members = committee
+            .members()
+            .iter()
+            .filter(|(address, _)| **address != primary.gateway.account().address())
+            .map(|(address, stake)| (*address, *stake))
+            .collect::<IndexMap<_, _>>();
+        members.insert(Account::<CurrentNetwork>::new(&mut rng).unwrap().address(), (MIN_VALIDATOR_STAKE, true));
+        ledger.set_committee(Committee::new(committee.starting_round(), members).unwrap());
+
+        // Process the batch proposal, and ensure the primary does not sign it, and becomes an observer.
+        primary.process_batch_propose_from_peer(peer_ip, batch_propose.clone()).await.unwrap();
+        assert!(!primary.signed_proposals.read().contains_key(&peer_account.1.address()));
+        assert!(primary.is_observer());
+        // Ensure the observer does not propose a batch.
+        primary.propose_batch().await.unwrap();
+        assert!(primary.proposed_batch.read().is_none());
+        assert!(primary.is_observer());
+
+        // Re-admit the primary to the committee.
+        ledger.set_committee(committee);
+
+        // Process the batch proposal again, and ensure the primary signs it.
+        primary.process_batch_propose_from_peer(peer_ip, batch_propose).await.unwrap();
+        assert!(primary.signed_proposals.read().contains_key(&peer_account.1.address()));
+        assert!(!primary.is_observer());
+    }
+
+    #[tokio::test]
+    async fn test_partition_safe_mode() {
+        let mut rng = TestRng::default();
+        let (primary, accounts) = primary_without_handlers(&mut rng).await;
+
+        // Enable partition detection, which requires 3 of the 4 (equally-staked) validators.
+        assert!(primary.set_partition_threshold(Some(0.0)).is_err());
+        assert!(primary.set_partition_threshold(Some(1.5)).is_err());
+        primary.set_partition_threshold(Some(0.75)).unwrap();
+        assert_eq!(primary.partition_threshold(), Some(0.75));
+
+        // Store a solution and a transaction on one of the workers.
+        let (solution_commitment, solution) = sample_unconfirmed_solution(&mut rng);
+        let (transaction_id, transaction) = sample_unconfirmed_transaction(&mut rng);
+        primary.workers[0].process_unconfirmed_solution(solution_commitment, solution).await.unwrap();
+        primary.workers[0].process_unconfirmed_transaction(transaction_id, transaction).await.unwrap();
+
+        // Drop the connections to two of the peers.
+        for (peer_ip, _) in &accounts[2..] {
+            primary.gateway.connected_peers().write().shift_remove(peer_ip);
+        }
+        assert_eq!(primary.connected_stake_fraction(primary.current_round()).unwrap(), 0.5);
+
+        // Ensure the primary enters partition-safe mode, and does not propose a batch.
+        assert!(primary.propose_batch().await.is_ok());
+        assert!(primary.is_partitioned());
+        assert!(primary.proposed_batch.read().is_none());
+
+        // Restore the connections to the peers.
+        for (peer_ip, account) in &accounts[2..] {
+            primary.gateway.insert_connected_peer(*peer_ip, *peer_ip, account.address());
+        }
+
+        // Ensure the primary resumes, and proposes a batch.
+        assert!(primary.propose_batch().await.is_ok());
+        assert!(!primary.is_partitioned());
+        assert!(primary.proposed_batch.read().is_some());
+    }
+
+    #[tokio::test]
+    async fn test_standby_promotion_with_another_active() {
+        let round = 3;
+        let mut rng = TestRng::default();
+        let (primary, accounts) = primary_without_handlers(&mut rng).await;
+
+        // Set the primary as a warm standby.
+        primary.enable_standby();
+
+        // Generate certificates, including certificates authored by another primary with the same address.
+        store_certificate_chain(&primary, &accounts, round, &mut rng);
+        assert!(primary.storage.contains_certificate_in_round_from(round - 1, primary.gateway.account().address()));
+
+        // Ensure the standby is not promoted.
+        assert!(primary.promote().is_err());
+        assert!(primary.is_standby());
     }
 
     #[tokio::test]
@@ -1910,14 +3694,66 @@ mod tests {
 
         // Make sure the primary is aware of the transmissions in the proposal.
         for (transmission_id, transmission) in proposal.transmissions() {
-            primary.workers[0].process_transmission_from_peer(peer_ip, *transmission_id, transmission.clone())
+            primary.workers[0].process_transmission_from_peer(peer_ip, *transmission_id, transmission.clone()).await
         }
 
         // The author must be known to resolver to pass propose checks.
         primary.gateway.resolver().insert_peer(peer_ip, peer_ip, peer_account.1.address());
 
+        // Construct the batch propose event.
+        let batch_propose = primary.batch_propose_for(proposal.batch_header().clone()).unwrap();
         // Try to process the batch proposal from the peer, should succeed.
-        primary.process_batch_propose_from_peer(peer_ip, (*proposal.batch_header()).clone().into()).await.unwrap();
+        primary.process_batch_propose_from_peer(peer_ip, batch_propose).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_batch_propose_from_peer_below_gc_round() {
+        let mut rng = TestRng::default();
+        let (primary, accounts) = primary_without_handlers(&mut rng).await;
+
+        // Advance the primary, so that the GC round is ahead of the proposal's previous round.
+        for round in 1..=primary.storage.max_gc_rounds() + 2 {
+            primary.storage.increment_to_next_round(round).unwrap();
+        }
+        let round = primary.storage.gc_round() + 1;
+
+        // Create a proposal that references a certificate in the GC round.
+        let peer_account = &accounts[1];
+        let peer_ip = peer_account.0;
+        let previous_certificate_ids = [Field::rand(&mut rng)].into();
+        let proposal = create_test_proposal(
+            &peer_account.1,
+            primary.ledger.current_committee().unwrap(),
+            round,
+            previous_certificate_ids,
+            now(),
+            &mut rng,
+        );
+        // Make sure the primary is aware of the transmissions in the proposal.
+        for (transmission_id, transmission) in proposal.transmissions() {
+            primary.workers[0].process_transmission_from_peer(peer_ip, *transmission_id, transmission.clone()).await
+        }
+        primary.gateway.resolver().insert_peer(peer_ip, peer_ip, peer_account.1.address());
+        let batch_propose = || primary.batch_propose_for(proposal.batch_header().clone()).unwrap();
+
+        // Ensure the proposal is not signed by default.
+        assert_eq!(primary.stale_proposal_policy(), StaleProposalPolicy::Process);
+        primary.process_batch_propose_from_peer(peer_ip, batch_propose()).await.unwrap();
+        assert!(!primary.signed_proposals.read().contains_key(&peer_account.1.address()));
+
+        // Ensure the proposal is refused with a clear reason, if configured.
+        for policy in [StaleProposalPolicy::Refuse, StaleProposalPolicy::RefuseAndSync] {
+            primary.set_stale_proposal_policy(policy);
+            let error = primary.process_batch_propose_from_peer(peer_ip, batch_propose()).await.unwrap_err();
+            assert!(error.to_string().contains("at or below the GC round"), "{error}");
+            assert!(!primary.signed_proposals.read().contains_key(&peer_account.1.address()));
+        }
+
+        // Ensure the peer is directed to sync at most once per interval.
+        let directed_at = primary.stale_proposal_syncs.lock().get(&peer_ip).copied();
+        assert!(directed_at.is_some());
+        assert!(primary.process_batch_propose_from_peer(peer_ip, batch_propose()).await.is_err());
+        assert_eq!(primary.stale_proposal_syncs.lock().get(&peer_ip).copied(), directed_at);
     }
 
     #[tokio::test]
@@ -1941,7 +3777,7 @@ mod tests {
 
         // Make sure the primary is aware of the transmissions in the proposal.
         for (transmission_id, transmission) in proposal.transmissions() {
-            primary.workers[0].process_transmission_from_peer(peer_ip, *transmission_id, transmission.clone())
+            primary.workers[0].process_transmission_from_peer(peer_ip, *transmission_id, transmission.clone()).await
         }
 
         // The author must be known to resolver to pass propose checks.
@@ -1952,7 +3788,7 @@ mod tests {
             primary
                 .process_batch_propose_from_peer(peer_ip, BatchPropose {
                     round: round + 1,
-                    batch_header: Data::Object(proposal.batch_header().clone())
+                    ..primary.batch_propose_for(proposal.batch_header().clone()).unwrap()
                 })
                 .await
                 .is_err()
@@ -1983,7 +3819,7 @@ mod tests {
 
         // Make sure the primary is aware of the transmissions in the proposal.
         for (transmission_id, transmission) in proposal.transmissions() {
-            primary.workers[0].process_transmission_from_peer(peer_ip, *transmission_id, transmission.clone())
+            primary.workers[0].process_transmission_from_peer(peer_ip, *transmission_id, transmission.clone()).await
         }
 
         // The author must be known to resolver to pass propose checks.
@@ -1994,7 +3830,7 @@ mod tests {
             primary
                 .process_batch_propose_from_peer(peer_ip, BatchPropose {
                     round: round + 1,
-                    batch_header: Data::Object(proposal.batch_header().clone())
+                    ..primary.batch_propose_for(proposal.batch_header().clone()).unwrap()
                 })
                 .await
                 .is_err()
@@ -2036,6 +3872,42 @@ mod tests {
         assert_eq!(primary.current_round(), round + 1);
     }
 
+    #[tokio::test]
+    async fn test_batch_signatures_from_peers_verified_together() {
+        let mut rng = TestRng::default();
+        let (primary, accounts) = primary_without_handlers(&mut rng).await;
+        map_account_addresses(&primary, &accounts);
+
+        // Create a valid proposal.
+        let round = 1;
+        let timestamp = now();
+        let proposal = create_test_proposal(
+            primary.gateway.account(),
+            primary.ledger.current_committee().unwrap(),
+            round,
+            Default::default(),
+            timestamp,
+            &mut rng,
+        );
+
+        // Store the proposal on the primary.
+        *primary.proposed_batch.write() = Some(proposal);
+
+        // Each committee member signs the batch, and one signature is swapped for an invalid one.
+        let mut signatures = peer_signatures_for_proposal(&primary, &accounts, &mut rng);
+        let (socket_addr, signature) = signatures[0];
+        let invalid = BatchSignature::new(Field::rand(&mut rng), signature.signature);
+        signatures.insert(0, (socket_addr, invalid));
+
+        // Have the primary process the signatures together, in a batch.
+        primary.process_batch_signatures_from_peers(signatures).await;
+
+        // Check the certificate was created and stored by the primary, despite the invalid signature.
+        assert!(primary.storage.contains_certificate_in_round_from(round, primary.gateway.account().address()));
+        // Check the round was incremented.
+        assert_eq!(primary.current_round(), round + 1);
+    }
+
     #[tokio::test]
     async fn test_batch_signature_from_peer_in_round() {
         let round = 5;
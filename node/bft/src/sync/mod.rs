@@ -13,29 +13,56 @@
 // limitations under the License.
 
 use crate::{
-    helpers::{fmt_id, BFTSender, Pending, Storage, SyncReceiver},
+    helpers::{fmt_id, now, BFTSender, Pending, Storage, SyncReceiver},
     spawn_blocking,
     Gateway,
     Transport,
     MAX_BATCH_DELAY_IN_MS,
     PRIMARY_PING_IN_MS,
 };
-use snarkos_node_bft_events::{CertificateRequest, CertificateResponse, Event};
+use snarkos_node_bft_events::{
+    CertificateRequest,
+    CertificateResponse,
+    DagSnapshotRequest,
+    DagSnapshotResponse,
+    Event,
+};
 use snarkos_node_bft_ledger_service::LedgerService;
 use snarkos_node_sync::{locators::BlockLocators, BlockSync, BlockSyncMode};
 use snarkvm::{
     console::{network::Network, types::Field},
-    ledger::{authority::Authority, block::Block, narwhal::BatchCertificate},
+    ledger::{
+        authority::Authority,
+        block::Block,
+        narwhal::{BatchCertificate, Data},
+    },
 };
 
-use anyhow::{bail, Result};
-use parking_lot::Mutex;
-use std::{future::Future, net::SocketAddr, sync::Arc};
+use anyhow::{bail, ensure, Result};
+use parking_lot::{Mutex, RwLock};
+use rand::seq::IteratorRandom;
+use std::{
+    collections::HashMap,
+    future::Future,
+    net::SocketAddr,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+};
 use tokio::{
     sync::{oneshot, Mutex as TMutex, OnceCell},
     task::JoinHandle,
 };
 
+/// The minimum interval (in seconds) between the DAG snapshots that are served to a peer.
+const DAG_SNAPSHOT_INTERVAL_IN_SECS: i64 = 60; // seconds
+/// The maximum time (in milliseconds) to wait for a DAG snapshot from a peer.
+const DAG_SNAPSHOT_TIMEOUT_IN_MS: u64 = 2 * PRIMARY_PING_IN_MS; // ms
+
+/// The callback of a pending DAG snapshot request.
+type DagSnapshotCallback<N> = oneshot::Sender<Result<Vec<BatchCertificate<N>>>>;
+
 #[derive(Clone)]
 pub struct Sync<N: Network> {
     /// The gateway.
@@ -48,12 +75,22 @@ pub struct Sync<N: Network> {
     block_sync: BlockSync<N>,
     /// The pending certificates queue.
     pending: Arc<Pending<Field<N>, BatchCertificate<N>>>,
+    /// The maximum number of certificates in a served DAG snapshot, if serving DAG snapshots is enabled.
+    max_dag_snapshot_certificates: Arc<RwLock<Option<usize>>>,
+    /// The map of peer IPs to the time (in seconds) at which a DAG snapshot was last served to them.
+    served_dag_snapshots: Arc<Mutex<HashMap<SocketAddr, i64>>>,
+    /// The map of peer IPs to the nonce and callback of the pending DAG snapshot request to them.
+    pending_dag_snapshots: Arc<Mutex<HashMap<SocketAddr, (u64, DagSnapshotCallback<N>)>>>,
+    /// The flag indicating whether the DAG is to be warmed up with a snapshot from a peer, once the ledger is synced.
+    dag_snapshot_warm_up: Arc<AtomicBool>,
     /// The BFT sender.
     bft_sender: Arc<OnceCell<BFTSender<N>>>,
     /// The spawned handles.
     handles: Arc<Mutex<Vec<JoinHandle<()>>>>,
     /// The sync lock.
     lock: Arc<TMutex<()>>,
+    /// The lock of a block sync round.
+    block_sync_lock: Arc<TMutex<()>>,
 }
 
 impl<N: Network> Sync<N> {
@@ -68,9 +105,14 @@ impl<N: Network> Sync<N> {
             ledger,
             block_sync,
             pending: Default::default(),
+            max_dag_snapshot_certificates: Default::default(),
+            served_dag_snapshots: Default::default(),
+            pending_dag_snapshots: Default::default(),
+            dag_snapshot_warm_up: Default::default(),
             bft_sender: Default::default(),
             handles: Default::default(),
             lock: Default::default(),
+            block_sync_lock: Default::default(),
         }
     }
 
@@ -95,9 +137,9 @@ impl<N: Network> Sync<N> {
                 // Sleep briefly to avoid triggering spam detection.
                 tokio::time::sleep(std::time::Duration::from_millis(PRIMARY_PING_IN_MS)).await;
                 // Perform the sync routine.
-                let communication = &self_.gateway;
-                // let communication = &node.router;
-                self_.block_sync.try_block_sync(communication).await;
+                self_.try_block_sync().await;
+                // Warm up the DAG with a snapshot from a peer, once the ledger is synced.
+                self_.try_warm_up_with_dag_snapshot().await;
             }
         }));
 
@@ -108,6 +150,8 @@ impl<N: Network> Sync<N> {
             mut rx_block_sync_update_peer_locators,
             mut rx_certificate_request,
             mut rx_certificate_response,
+            mut rx_dag_snapshot_request,
+            mut rx_dag_snapshot_response,
         } = sync_receiver;
 
         // Process the block sync request to advance with sync blocks.
@@ -171,6 +215,22 @@ impl<N: Network> Sync<N> {
             }
         });
 
+        // Process the DAG snapshot request.
+        let self_ = self.clone();
+        self.spawn(async move {
+            while let Some((peer_ip, dag_snapshot_request)) = rx_dag_snapshot_request.recv().await {
+                self_.send_dag_snapshot_response(peer_ip, dag_snapshot_request);
+            }
+        });
+
+        // Process the DAG snapshot response.
+        let self_ = self.clone();
+        self.spawn(async move {
+            while let Some((peer_ip, dag_snapshot_response)) = rx_dag_snapshot_response.recv().await {
+                self_.finish_dag_snapshot_request(peer_ip, dag_snapshot_response)
+            }
+        });
+
         Ok(())
     }
 }
@@ -310,6 +370,20 @@ impl<N: Network> Sync<N> {
     pub fn get_block_locators(&self) -> Result<BlockLocators<N>> {
         self.block_sync.get_block_locators()
     }
+
+    /// Starts a block sync round immediately, rather than at the next sync interval (e.g. once the primary
+    /// observes that it lags behind a peer). If a block sync round is in progress, this is a no-op.
+    pub fn try_block_sync_now(&self) {
+        let self_ = self.clone();
+        tokio::spawn(async move { self_.try_block_sync().await });
+    }
+
+    /// Performs a block sync round with the peers, unless a block sync round is in progress.
+    async fn try_block_sync(&self) {
+        if let Ok(_lock) = self.block_sync_lock.try_lock() {
+            self.block_sync.try_block_sync(&self.gateway).await;
+        }
+    }
 }
 
 // Methods to assist with fetching batch certificates from peers.
@@ -365,6 +439,234 @@ impl<N: Network> Sync<N> {
     }
 }
 
+// Methods to assist with warming up the DAG from the snapshots of peers.
+impl<N: Network> Sync<N> {
+    /// Returns the maximum number of certificates in a served DAG snapshot, if serving DAG snapshots is enabled.
+    pub fn max_dag_snapshot_certificates(&self) -> Option<usize> {
+        *self.max_dag_snapshot_certificates.read()
+    }
+
+    /// Sets the maximum number of certificates in a served DAG snapshot, which enables serving DAG snapshots
+    /// to the connected validators. If `None` is given, DAG snapshots are not served.
+    pub fn set_max_dag_snapshot_certificates(&self, max_certificates: Option<usize>) -> Result<()> {
+        // Ensure the maximum is nonzero, and within the maximum of the event.
+        if let Some(max_certificates) = max_certificates {
+            ensure!(
+                (1..=DagSnapshotResponse::<N>::MAXIMUM_CERTIFICATES).contains(&max_certificates),
+                "The maximum number of DAG snapshot certificates must be between 1 and {}",
+                DagSnapshotResponse::<N>::MAXIMUM_CERTIFICATES
+            );
+        }
+        *self.max_dag_snapshot_certificates.write() = max_certificates;
+        Ok(())
+    }
+
+    /// Returns `true` if the DAG is still to be warmed up with a snapshot from a peer.
+    pub fn is_dag_snapshot_warm_up_pending(&self) -> bool {
+        self.dag_snapshot_warm_up.load(Ordering::SeqCst)
+    }
+
+    /// Enables warming up the DAG with a snapshot from a connected validator, once the ledger is synced.
+    pub fn enable_dag_snapshot_warm_up(&self) {
+        info!("The DAG will be warmed up with a snapshot from a peer, once the ledger is synced");
+        self.dag_snapshot_warm_up.store(true, Ordering::SeqCst);
+    }
+
+    /// Warms up the DAG with a snapshot from a random connected validator, if the warm up is still pending,
+    /// and the ledger is synced. If the snapshot fails, the warm up is retried on the next sync routine.
+    async fn try_warm_up_with_dag_snapshot(&self) {
+        if !self.is_dag_snapshot_warm_up_pending() || !self.is_synced() {
+            return;
+        }
+        // Select a random connected validator.
+        let peer_ip = self.gateway.connected_peers().read().iter().choose(&mut rand::thread_rng()).copied();
+        let Some(peer_ip) = peer_ip else {
+            return;
+        };
+        match self.warm_up_with_dag_snapshot(peer_ip).await {
+            Ok(num_certificates) => {
+                info!("Warmed up the DAG with {num_certificates} certificates from '{peer_ip}'");
+                self.dag_snapshot_warm_up.store(false, Ordering::SeqCst);
+            }
+            Err(e) => debug!("Failed to warm up the DAG with a snapshot from '{peer_ip}' - {e}"),
+        }
+    }
+
+    /// Warms up the DAG with a snapshot from the specified peer, returning the number of its certificates.
+    ///
+    /// Note: The certificates of the snapshot are processed as if the peer sent them as certified batches,
+    /// so each certificate is fully validated, and its missing transmissions and previous certificates are fetched.
+    pub async fn warm_up_with_dag_snapshot(&self, peer_ip: SocketAddr) -> Result<usize> {
+        // Request and validate the DAG snapshot.
+        let certificates = self.send_dag_snapshot_request(peer_ip).await?;
+        let num_certificates = certificates.len();
+        // Process the certificates, in ascending order of rounds.
+        for certificate in certificates {
+            self.gateway.primary_sender().tx_batch_certified.send((peer_ip, Data::Object(certificate))).await?;
+        }
+        Ok(num_certificates)
+    }
+
+    /// Sends a DAG snapshot request to the specified peer, and returns the certificates of the validated snapshot.
+    pub async fn send_dag_snapshot_request(&self, peer_ip: SocketAddr) -> Result<Vec<BatchCertificate<N>>> {
+        // Initialize a oneshot channel.
+        let (callback_sender, callback_receiver) = oneshot::channel();
+        // Insert the nonce into the pending DAG snapshot requests.
+        let nonce = rand::random::<u64>();
+        self.pending_dag_snapshots.lock().insert(peer_ip, (nonce, callback_sender));
+        // Send the DAG snapshot request to the peer.
+        if self.gateway.send(peer_ip, Event::DagSnapshotRequest(DagSnapshotRequest::new(nonce))).await.is_none() {
+            self.pending_dag_snapshots.lock().remove(&peer_ip);
+            bail!("Unable to fetch the DAG snapshot from '{peer_ip}' - failed to send request")
+        }
+        // Wait for the DAG snapshot to be fetched.
+        let timeout = core::time::Duration::from_millis(DAG_SNAPSHOT_TIMEOUT_IN_MS);
+        match tokio::time::timeout(timeout, callback_receiver).await {
+            // If the DAG snapshot was fetched, return its certificates.
+            Ok(result) => result?,
+            // If the DAG snapshot was not fetched, return an error.
+            Err(e) => {
+                self.pending_dag_snapshots.lock().remove(&peer_ip);
+                bail!("Unable to fetch the DAG snapshot from '{peer_ip}' - (timeout) {e}")
+            }
+        }
+    }
+
+    /// Handles the incoming DAG snapshot request.
+    fn send_dag_snapshot_response(&self, peer_ip: SocketAddr, request: DagSnapshotRequest) {
+        let self_ = self.clone();
+        tokio::spawn(async move {
+            // Prepare the DAG snapshot.
+            let self_clone = self_.clone();
+            match spawn_blocking!(self_clone.prepare_dag_snapshot(peer_ip, request.nonce)) {
+                // Send the DAG snapshot response to the peer.
+                Ok(response) => {
+                    let _ = self_.gateway.send(peer_ip, Event::DagSnapshotResponse(response)).await;
+                }
+                Err(e) => debug!("Unable to serve a DAG snapshot to '{peer_ip}' - {e}"),
+            }
+        });
+    }
+
+    /// Returns the DAG snapshot for the given peer, if serving DAG snapshots is enabled,
+    /// and the peer did not request one too recently.
+    ///
+    /// The snapshot contains the certificates of the most recent rounds above the GC round, such that it is
+    /// within the configured maximum, and is anchored to the latest block in the ledger.
+    fn prepare_dag_snapshot(&self, peer_ip: SocketAddr, nonce: u64) -> Result<DagSnapshotResponse<N>> {
+        // Ensure serving DAG snapshots is enabled.
+        let Some(max_certificates) = self.max_dag_snapshot_certificates() else {
+            bail!("Serving DAG snapshots is disabled")
+        };
+        // Ensure the peer is an authenticated validator.
+        ensure!(self.gateway.resolver().get_address(peer_ip).is_some(), "Peer '{peer_ip}' is not authenticated");
+        // Ensure the peer did not request a DAG snapshot too recently.
+        {
+            let now = now();
+            let mut served_dag_snapshots = self.served_dag_snapshots.lock();
+            served_dag_snapshots.retain(|_, served_at| now - *served_at < DAG_SNAPSHOT_INTERVAL_IN_SECS);
+            ensure!(
+                !served_dag_snapshots.contains_key(&peer_ip),
+                "Peer '{peer_ip}' requested a DAG snapshot within {DAG_SNAPSHOT_INTERVAL_IN_SECS} seconds"
+            );
+            served_dag_snapshots.insert(peer_ip, now);
+        }
+
+        // Retrieve the certificates of the most recent rounds, such that the whole rounds are within the maximum.
+        let mut rounds = Vec::new();
+        let mut num_certificates = 0;
+        for round in (self.storage.gc_round() + 1..=self.storage.current_round()).rev() {
+            let certificates = self.storage.get_certificates_for_round(round);
+            if num_certificates + certificates.len() > max_certificates {
+                break;
+            }
+            num_certificates += certificates.len();
+            rounds.push(certificates);
+        }
+        let certificates = rounds.into_iter().rev().flatten().collect();
+
+        // Anchor the snapshot to the latest block.
+        let height = self.ledger.latest_block_height();
+        let block_hash = self.ledger.get_block_hash(height)?;
+        DagSnapshotResponse::new(nonce, height, block_hash, certificates)
+    }
+
+    /// Handles the incoming DAG snapshot response.
+    /// This method ensures the DAG snapshot is verifiable against the committed blocks.
+    fn finish_dag_snapshot_request(&self, peer_ip: SocketAddr, response: DagSnapshotResponse<N>) {
+        // Retrieve the pending DAG snapshot request to the peer, if it exists.
+        let Some((nonce, callback)) = self.pending_dag_snapshots.lock().remove(&peer_ip) else {
+            return;
+        };
+        // Verify the DAG snapshot, against the committed blocks.
+        callback.send(self.verify_dag_snapshot(nonce, response)).ok();
+    }
+
+    /// Verifies the DAG snapshot against the committed blocks, returning its certificates.
+    fn verify_dag_snapshot(&self, nonce: u64, response: DagSnapshotResponse<N>) -> Result<Vec<BatchCertificate<N>>> {
+        // Ensure the DAG snapshot is for the request, and anchored to a committed block.
+        self.verify_dag_snapshot_anchor(nonce, &response)?;
+        // Retrieve the committed certificates of the anchoring block.
+        let committed_certificates = match self.ledger.get_block(response.height)?.authority() {
+            Authority::Quorum(subdag) => subdag.values().flatten().cloned().collect(),
+            Authority::Beacon(_) => vec![],
+        };
+        // Ensure the certificates are consistent with the committed certificates.
+        self.verify_dag_snapshot_certificates(&committed_certificates, response.certificates)
+    }
+
+    /// Ensures the DAG snapshot is for the request, and anchored to a block in the ledger.
+    fn verify_dag_snapshot_anchor(&self, nonce: u64, response: &DagSnapshotResponse<N>) -> Result<()> {
+        ensure!(response.nonce == nonce, "The DAG snapshot is for a different request");
+        let height = response.height;
+        let Ok(block_hash) = self.ledger.get_block_hash(height) else {
+            bail!("The DAG snapshot is anchored to block {height}, which is not in the ledger")
+        };
+        ensure!(block_hash == response.block_hash, "The DAG snapshot is anchored to a non-canonical block {height}");
+        Ok(())
+    }
+
+    /// Ensures the certificates of the DAG snapshot are unique, in ascending order of rounds, within the GC range,
+    /// and consistent with the given committed certificates of the anchoring block. That is, from the earliest round
+    /// of the snapshot onwards, each committed certificate is in the snapshot, and no other certificate of its author
+    /// in its round is.
+    fn verify_dag_snapshot_certificates(
+        &self,
+        committed_certificates: &[BatchCertificate<N>],
+        certificates: Vec<BatchCertificate<N>>,
+    ) -> Result<Vec<BatchCertificate<N>>> {
+        // Ensure the certificates are unique, in ascending order of rounds, and within the GC range.
+        let gc_round = self.storage.gc_round();
+        let max_round = self.storage.current_round() + self.storage.max_gc_rounds();
+        let mut certificate_ids = HashMap::with_capacity(certificates.len());
+        let mut previous_round = 0;
+        for certificate in &certificates {
+            let round = certificate.round();
+            ensure!(gc_round < round && round <= max_round, "The DAG snapshot has a certificate beyond the GC range");
+            ensure!(previous_round <= round, "The DAG snapshot is not in ascending order of rounds");
+            ensure!(
+                certificate_ids.insert((round, certificate.author()), certificate.id()).is_none(),
+                "The DAG snapshot has more than one certificate from an author in round {round}"
+            );
+            previous_round = round;
+        }
+
+        // Ensure the certificates are consistent with the committed certificates, from the earliest round onwards.
+        let earliest_round = certificates.first().map_or(u64::MAX, |certificate| certificate.round());
+        for committed in committed_certificates.iter().filter(|certificate| certificate.round() >= earliest_round) {
+            let round = committed.round();
+            match certificate_ids.get(&(round, committed.author())) {
+                Some(certificate_id) => ensure!(
+                    *certificate_id == committed.id(),
+                    "The DAG snapshot conflicts with a committed certificate in round {round}"
+                ),
+                None => bail!("The DAG snapshot omits a committed certificate in round {round}"),
+            }
+        }
+        Ok(certificates)
+    }
+}
+
 impl<N: Network> Sync<N> {
     /// Spawns a task with the given future; it should only be used for long-running tasks.
     fn spawn<T: Future<Output = ()> + Send + 'static>(&self, future: T) {
@@ -380,3 +682,106 @@ impl<N: Network> Sync<N> {
         self.handles.lock().iter().for_each(|handle| handle.abort());
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use snarkos_account::Account;
+    use snarkos_node_bft_ledger_service::MockLedgerService;
+    use snarkos_node_bft_storage_service::BFTMemoryService;
+    use snarkvm::{
+        console::types::Address,
+        ledger::{
+            committee::test_helpers::sample_committee,
+            narwhal::batch_certificate::test_helpers::{
+                sample_batch_certificate_for_round,
+                sample_batch_certificate_with_previous_certificates,
+            },
+        },
+        prelude::TestRng,
+    };
+
+    type CurrentNetwork = snarkvm::prelude::Testnet3;
+
+    /// Returns a sync instance of a validator with a ledger at the given height, and its address.
+    fn sample_sync(height: u32, rng: &mut TestRng) -> (Sync<CurrentNetwork>, Address<CurrentNetwork>) {
+        let ledger = Arc::new(MockLedgerService::new_at_height(sample_committee(rng), height));
+        let storage = Storage::new(ledger.clone(), Arc::new(BFTMemoryService::new()), 10);
+        let account = Account::new(rng).unwrap();
+        let address = account.address();
+        let gateway = Gateway::new(account, ledger.clone(), None, &[], None).unwrap();
+        (Sync::new(gateway, storage, ledger), address)
+    }
+
+    #[test]
+    fn test_dag_snapshot() {
+        let rng = &mut TestRng::default();
+
+        // Initialize a validator with a recent DAG, and a joining validator.
+        let (peer, _) = sample_sync(5, rng);
+        let (joining, joining_address) = sample_sync(5, rng);
+        let (certificate, previous_certificates) = sample_batch_certificate_with_previous_certificates(3, rng);
+        for previous_certificate in &previous_certificates {
+            peer.storage.testing_only_insert_certificate_testing_only(previous_certificate.clone());
+        }
+        peer.storage.testing_only_insert_certificate_testing_only(certificate.clone());
+        peer.storage.increment_to_next_round(1).unwrap();
+        peer.storage.increment_to_next_round(2).unwrap();
+
+        // Ensure the DAG snapshot is not served until it is enabled, and only to an authenticated validator.
+        let joining_ip = SocketAddr::from(([127, 0, 0, 1], 5000));
+        assert!(peer.prepare_dag_snapshot(joining_ip, 0).is_err());
+        const MAXIMUM_CERTIFICATES: usize = DagSnapshotResponse::<CurrentNetwork>::MAXIMUM_CERTIFICATES;
+        assert!(peer.set_max_dag_snapshot_certificates(Some(0)).is_err());
+        assert!(peer.set_max_dag_snapshot_certificates(Some(MAXIMUM_CERTIFICATES + 1)).is_err());
+        peer.set_max_dag_snapshot_certificates(Some(MAXIMUM_CERTIFICATES)).unwrap();
+        assert!(peer.prepare_dag_snapshot(joining_ip, 0).is_err());
+        peer.gateway.resolver().insert_peer(joining_ip, joining_ip, joining_address);
+
+        // Request a DAG snapshot, and ensure it contains the recent DAG, in ascending order of rounds.
+        let nonce = 1234;
+        let response = peer.prepare_dag_snapshot(joining_ip, nonce).unwrap();
+        let expected = previous_certificates.iter().chain([&certificate]).cloned().collect::<Vec<_>>();
+        assert_eq!(response.certificates.len(), expected.len());
+        assert!(expected.iter().all(|certificate| response.certificates.contains(certificate)));
+        assert_eq!(response.certificates.last(), Some(&certificate));
+
+        // Ensure the joining validator validates the DAG snapshot, against the committed certificates.
+        assert!(joining.verify_dag_snapshot_anchor(nonce, &response).is_ok());
+        let certificates =
+            joining.verify_dag_snapshot_certificates(&previous_certificates, response.certificates.clone());
+        assert_eq!(certificates.unwrap(), response.certificates);
+
+        // Ensure a DAG snapshot for another request is rejected.
+        assert!(joining.verify_dag_snapshot_anchor(nonce + 1, &response).is_err());
+        // Ensure a DAG snapshot that is not anchored to a block in the ledger is rejected.
+        let (behind, _) = sample_sync(2, rng);
+        assert!(behind.verify_dag_snapshot_anchor(nonce, &response).is_err());
+        // Ensure a DAG snapshot that omits a committed certificate is rejected.
+        let mut tampered = response.certificates.clone();
+        let omitted = tampered.remove(1);
+        assert!(previous_certificates.contains(&omitted));
+        assert!(joining.verify_dag_snapshot_certificates(&previous_certificates, tampered).is_err());
+        // Ensure a DAG snapshot with more than one certificate from an author in a round is rejected.
+        let mut tampered = response.certificates.clone();
+        tampered.insert(1, tampered[0].clone());
+        assert!(joining.verify_dag_snapshot_certificates(&[], tampered).is_err());
+        // Ensure a DAG snapshot with a certificate beyond the GC range is rejected.
+        let round = joining.storage.current_round() + joining.storage.max_gc_rounds() + 1;
+        let tampered = vec![sample_batch_certificate_for_round(round, rng)];
+        assert!(joining.verify_dag_snapshot_certificates(&[], tampered).is_err());
+        // Ensure a DAG snapshot from a later round does not need the earlier committed certificates.
+        let later = vec![certificate.clone()];
+        assert_eq!(joining.verify_dag_snapshot_certificates(&previous_certificates, later.clone()).unwrap(), later);
+
+        // Ensure the peer is rate limited.
+        assert!(peer.prepare_dag_snapshot(joining_ip, nonce).is_err());
+
+        // Ensure the DAG snapshot is size bound, to the most recent whole rounds.
+        let other_ip = SocketAddr::from(([127, 0, 0, 1], 5001));
+        peer.gateway.resolver().insert_peer(other_ip, other_ip, joining_address);
+        peer.set_max_dag_snapshot_certificates(Some(previous_certificates.len())).unwrap();
+        let response = peer.prepare_dag_snapshot(other_ip, nonce).unwrap();
+        assert_eq!(response.certificates, vec![certificate]);
+    }
+}
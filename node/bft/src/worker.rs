@@ -14,7 +14,27 @@
 
 use crate::{
     events::{Event, TransmissionRequest, TransmissionResponse},
-    helpers::{fmt_id, Pending, Ready, Storage, WorkerReceiver},
+    helpers::{
+        fmt_id,
+        now,
+        AdmissionHook,
+        AdmissionOrder,
+        AdmissionQueue,
+        AdmissionReceipt,
+        Pending,
+        Ready,
+        referenced_program_ids,
+        ReplacementPolicy,
+        SenderFilter,
+        SenderRateLimit,
+        Storage,
+        transaction_priority_fee,
+        transaction_sender,
+        TransientRejections,
+        UnknownProgramPolicy,
+        WorkerReceiver,
+    },
+    spawn_blocking,
     ProposedBatch,
     Transport,
     MAX_BATCH_DELAY_IN_MS,
@@ -22,6 +42,7 @@ use crate::{
     MAX_TRANSMISSIONS_PER_WORKER_PING,
     MAX_WORKERS,
 };
+use snarkos_account::Account;
 use snarkos_node_bft_ledger_service::LedgerService;
 use snarkvm::{
     console::prelude::*,
@@ -29,15 +50,34 @@ use snarkvm::{
     prelude::{
         block::Transaction,
         coinbase::{ProverSolution, PuzzleCommitment},
+        Address,
+        ProgramID,
     },
 };
 
 use indexmap::{IndexMap, IndexSet};
-use parking_lot::Mutex;
-use std::{future::Future, net::SocketAddr, sync::Arc, time::Duration};
+use parking_lot::{Mutex, RwLock};
+use std::{
+    collections::VecDeque,
+    future::Future,
+    net::SocketAddr,
+    sync::{
+        atomic::{AtomicBool, AtomicU8, AtomicUsize, Ordering},
+        Arc,
+    },
+    time::Duration,
+};
 use tokio::{sync::oneshot, task::JoinHandle, time::timeout};
 
 const MAX_TRANSMISSIONS_PER_WORKER: usize = MAX_TRANSMISSIONS_PER_BATCH / MAX_WORKERS as usize;
+/// The maximum number of deferred transactions (that reference unknown programs) per worker.
+const MAX_DEFERRED_TRANSACTIONS_PER_WORKER: usize = 1 << 10;
+/// The interval at which the admission of the deferred transactions is retried.
+const DEFERRED_TRANSACTIONS_RETRY_INTERVAL: Duration = Duration::from_secs(10);
+/// The default number of hops a transmission is forwarded, i.e. advertised by the peers that fetched it.
+const DEFAULT_TRANSMISSION_TTL: u8 = 8;
+/// The interval (in seconds) over which the pins are rate-limited, to the maximum number of pinned transmissions.
+const PIN_RATE_LIMIT_INTERVAL_IN_SECS: i64 = 60;
 
 #[derive(Clone)]
 pub struct Worker<N: Network> {
@@ -53,8 +93,42 @@ pub struct Worker<N: Network> {
     proposed_batch: Arc<ProposedBatch<N>>,
     /// The ready queue.
     ready: Ready<N>,
-    /// The pending transmissions queue.
-    pending: Arc<Pending<TransmissionID<N>, Transmission<N>>>,
+    /// The pending transmissions queue, with the remaining number of hops of each fetched transmission.
+    pending: Arc<Pending<TransmissionID<N>, (Transmission<N>, u8)>>,
+    /// The maximum number of transmissions from any single submitter in a batch, if fairness is enabled.
+    max_transmissions_per_submitter: Arc<RwLock<Option<usize>>>,
+    /// The flag indicating whether transmissions are drained in order of priority fee.
+    fee_priority: Arc<AtomicBool>,
+    /// The account used to sign admission receipts, if receipts are enabled.
+    receipt_signer: Arc<RwLock<Option<Account<N>>>>,
+    /// The hook consulted on the admission of unconfirmed transmissions, if one is set.
+    admission_hook: Arc<RwLock<Option<Arc<AdmissionHook<N>>>>>,
+    /// The bounded admission into the ready queue, which orders the arrivals under contention, if one is set.
+    admission_queue: Arc<RwLock<Option<Arc<AdmissionQueue<N>>>>>,
+    /// The short-lived record of the transiently-rejected transmissions, if retention is enabled.
+    transient_rejections: Arc<RwLock<Option<Arc<TransientRejections<N>>>>>,
+    /// The policy for replacing queued transactions with higher-fee transactions, if replacement is enabled.
+    replacement_policy: Arc<RwLock<Option<Arc<ReplacementPolicy<N>>>>>,
+    /// The operator-local filter of the senders whose unconfirmed transactions are admitted, if one is set.
+    sender_filter: Arc<RwLock<Option<Arc<SenderFilter<N>>>>>,
+    /// The rate limit on the transactions admitted from any single sender, if one is set.
+    sender_rate_limit: Arc<RwLock<Option<Arc<SenderRateLimit<N>>>>>,
+    /// The interval at which locally-submitted transmission IDs are advertised together, if coalescing is enabled.
+    local_gossip_interval: Arc<RwLock<Option<Duration>>>,
+    /// The locally-submitted transmission IDs that are awaiting advertisement.
+    local_gossip: Arc<Mutex<IndexSet<TransmissionID<N>>>>,
+    /// The handling of unconfirmed transactions that reference unknown programs, if detection is enabled.
+    unknown_program_policy: Arc<RwLock<Option<UnknownProgramPolicy>>>,
+    /// The deferred transactions that reference unknown programs, awaiting another admission attempt.
+    deferred: Arc<Mutex<IndexMap<N::TransactionID, Data<Transaction<N>>>>>,
+    /// The number of hops that the locally-received transmissions may be forwarded.
+    transmission_ttl: Arc<AtomicU8>,
+    /// The remaining number of hops of the transmissions fetched from peers.
+    remaining_ttls: Arc<Mutex<IndexMap<TransmissionID<N>, u8>>>,
+    /// The maximum number of pinned transmissions (`0` to disable pinning).
+    max_pinned_transmissions: Arc<AtomicUsize>,
+    /// The UNIX timestamps (in seconds) of the recent pins, for rate limiting.
+    pin_timestamps: Arc<Mutex<VecDeque<i64>>>,
     /// The spawned handles.
     handles: Arc<Mutex<Vec<JoinHandle<()>>>>,
 }
@@ -79,6 +153,23 @@ impl<N: Network> Worker<N> {
             proposed_batch,
             ready: Default::default(),
             pending: Default::default(),
+            max_transmissions_per_submitter: Default::default(),
+            fee_priority: Default::default(),
+            receipt_signer: Default::default(),
+            admission_hook: Default::default(),
+            admission_queue: Default::default(),
+            transient_rejections: Default::default(),
+            replacement_policy: Default::default(),
+            sender_filter: Default::default(),
+            sender_rate_limit: Default::default(),
+            local_gossip_interval: Default::default(),
+            local_gossip: Default::default(),
+            unknown_program_policy: Default::default(),
+            deferred: Default::default(),
+            transmission_ttl: Arc::new(AtomicU8::new(DEFAULT_TRANSMISSION_TTL)),
+            remaining_ttls: Default::default(),
+            max_pinned_transmissions: Default::default(),
+            pin_timestamps: Default::default(),
             handles: Default::default(),
         })
     }
@@ -94,6 +185,245 @@ impl<N: Network> Worker<N> {
     pub const fn id(&self) -> u8 {
         self.id
     }
+
+    /// Returns the maximum number of transmissions from any single submitter in a batch, if fairness is enabled.
+    pub fn max_transmissions_per_submitter(&self) -> Option<usize> {
+        *self.max_transmissions_per_submitter.read()
+    }
+
+    /// Sets the maximum number of transmissions from any single submitter in a batch.
+    /// If `None` is given, fairness is disabled, and transmissions are included in queue order.
+    pub fn set_max_transmissions_per_submitter(&self, max_transmissions_per_submitter: Option<usize>) -> Result<()> {
+        // Ensure the cap is nonzero.
+        if max_transmissions_per_submitter == Some(0) {
+            bail!("The maximum number of transmissions per submitter must be nonzero")
+        }
+        *self.max_transmissions_per_submitter.write() = max_transmissions_per_submitter;
+        Ok(())
+    }
+
+    /// Returns `true` if transmissions are drained in order of priority fee.
+    pub fn is_fee_priority_enabled(&self) -> bool {
+        self.fee_priority.load(Ordering::SeqCst)
+    }
+
+    /// Sets whether transmissions are drained in order of priority fee, with ties broken by transmission ID.
+    /// If disabled, transmissions are drained in queue order.
+    pub fn set_fee_priority(&self, fee_priority: bool) {
+        self.fee_priority.store(fee_priority, Ordering::SeqCst);
+    }
+
+    /// Sets the priority fee of the specified transmission, if it is in the ready queue.
+    /// Returns `true` if the priority fee was set.
+    pub fn set_priority_fee(&self, transmission_id: impl Into<TransmissionID<N>>, priority_fee: u64) -> bool {
+        let transmission_id = transmission_id.into();
+        self.ready.set_priority_fee(transmission_id, priority_fee)
+            || self.admission_queue().is_some_and(|queue| queue.set_priority_fee(transmission_id, priority_fee))
+    }
+
+    /// Returns `true` if the worker signs admission receipts for unconfirmed transmissions.
+    pub fn is_receipts_enabled(&self) -> bool {
+        self.receipt_signer.read().is_some()
+    }
+
+    /// Sets the account used to sign admission receipts for unconfirmed transmissions.
+    /// If `None` is given, admission receipts are disabled.
+    pub fn set_receipt_signer(&self, account: Option<Account<N>>) {
+        *self.receipt_signer.write() = account;
+    }
+
+    /// Returns the hook consulted on the admission of unconfirmed transmissions, if one is set.
+    pub fn admission_hook(&self) -> Option<Arc<AdmissionHook<N>>> {
+        self.admission_hook.read().clone()
+    }
+
+    /// Sets the hook consulted on the admission of unconfirmed transmissions.
+    /// If `None` is given, unconfirmed transmissions are admitted without consulting a hook.
+    pub fn set_admission_hook(&self, admission_hook: Option<AdmissionHook<N>>) {
+        *self.admission_hook.write() = admission_hook.map(Arc::new);
+    }
+
+    /// Returns the bounded admission into the ready queue, if one is set.
+    pub fn admission_queue(&self) -> Option<Arc<AdmissionQueue<N>>> {
+        self.admission_queue.read().clone()
+    }
+
+    /// Sets the bounded admission into the ready queue, where the transmissions that arrive while it is full
+    /// are staged, and are admitted in the configured order as slots free up. If `None` is given, the ready
+    /// queue is unbounded. The staged transmissions of the previous admission queue are carried over.
+    pub fn set_admission_queue(&self, admission_queue: Option<AdmissionQueue<N>>) {
+        let admission_queue = admission_queue.map(Arc::new);
+        let previous = std::mem::replace(&mut *self.admission_queue.write(), admission_queue.clone());
+        // Carry over the staged transmissions, in order of arrival.
+        let staged = previous.map(|previous| previous.take_staged()).unwrap_or_default();
+        for (transmission_id, (priority_fee, transmission)) in staged {
+            self.admit(transmission_id, transmission, priority_fee);
+        }
+    }
+
+    /// Returns the number of transmissions that are staged for admission into the ready queue.
+    pub fn num_staged_transmissions(&self) -> usize {
+        self.admission_queue().map_or(0, |queue| queue.num_staged())
+    }
+
+    /// Returns the record of the transiently-rejected transmissions, if retention is enabled.
+    pub fn transient_rejections(&self) -> Option<Arc<TransientRejections<N>>> {
+        self.transient_rejections.read().clone()
+    }
+
+    /// Sets the retention window of the transmissions that are rejected for a transient reason (i.e. a full
+    /// ready queue), so that a retry within the window is recognized, and skips re-validation if the rejected
+    /// transmission was validated. If `None` is given, the rejections are not retained.
+    pub fn set_transient_rejection_window(&self, window: Option<Duration>) -> Result<()> {
+        *self.transient_rejections.write() = window.map(TransientRejections::new).transpose()?.map(Arc::new);
+        Ok(())
+    }
+
+    /// Returns the policy for replacing queued transactions, if replacement is enabled.
+    pub fn replacement_policy(&self) -> Option<Arc<ReplacementPolicy<N>>> {
+        self.replacement_policy.read().clone()
+    }
+
+    /// Sets the policy for replacing queued transactions with higher-fee transactions from the same submitter.
+    /// If `None` is given, transaction replacement is disabled.
+    pub fn set_replacement_policy(&self, replacement_policy: Option<ReplacementPolicy<N>>) {
+        *self.replacement_policy.write() = replacement_policy.map(Arc::new);
+    }
+
+    /// Returns the filter of the senders whose unconfirmed transactions are admitted, if one is set.
+    pub fn sender_filter(&self) -> Option<Arc<SenderFilter<N>>> {
+        self.sender_filter.read().clone()
+    }
+
+    /// Sets the filter of the senders whose unconfirmed transactions are admitted (and gossiped).
+    /// Note: The filter is operator-local, and does not affect the transmissions fetched for the batches of peers.
+    /// If `None` is given, the unconfirmed transactions of every sender are admitted.
+    pub fn set_sender_filter(&self, sender_filter: Option<SenderFilter<N>>) {
+        *self.sender_filter.write() = sender_filter.map(Arc::new);
+    }
+
+    /// Returns the rate limit on the transactions admitted from any single sender, if one is set.
+    pub fn sender_rate_limit(&self) -> Option<Arc<SenderRateLimit<N>>> {
+        self.sender_rate_limit.read().clone()
+    }
+
+    /// Sets the rate limit on the transactions admitted from any single sender, which applies to both the
+    /// unconfirmed transactions and the transactions gossiped by peers, regardless of the relaying peer.
+    /// Note: The limit is shared by the workers of the primary, so the cap does not scale with the number of workers.
+    /// If `None` is given, the senders are not rate limited.
+    pub(crate) fn set_sender_rate_limit(&self, sender_rate_limit: Option<Arc<SenderRateLimit<N>>>) {
+        *self.sender_rate_limit.write() = sender_rate_limit;
+    }
+
+    /// Returns the interval at which locally-submitted transmission IDs are advertised, if coalescing is enabled.
+    pub fn local_gossip_interval(&self) -> Option<Duration> {
+        *self.local_gossip_interval.read()
+    }
+
+    /// Sets the interval at which locally-submitted transmission IDs are coalesced into a single worker ping.
+    /// If `None` is given, coalescing is disabled, and local submissions are only advertised by the worker ping.
+    pub fn set_local_gossip_interval(&self, interval: Option<Duration>) -> Result<()> {
+        // Ensure the interval is nonzero.
+        if interval == Some(Duration::ZERO) {
+            bail!("The local gossip interval must be nonzero")
+        }
+        *self.local_gossip_interval.write() = interval;
+        Ok(())
+    }
+
+    /// Returns the handling of unconfirmed transactions that reference unknown programs, if detection is enabled.
+    pub fn unknown_program_policy(&self) -> Option<UnknownProgramPolicy> {
+        *self.unknown_program_policy.read()
+    }
+
+    /// Sets the handling of unconfirmed transactions that reference programs unknown to the ledger.
+    /// If `None` is given, detection is disabled, and such transactions fail in the ledger checks.
+    pub fn set_unknown_program_policy(&self, policy: Option<UnknownProgramPolicy>) {
+        // If transactions are no longer deferred, drop the deferred transactions.
+        if policy != Some(UnknownProgramPolicy::Defer) {
+            self.deferred.lock().clear();
+        }
+        *self.unknown_program_policy.write() = policy;
+    }
+
+    /// Returns the number of deferred transactions, that reference unknown programs.
+    pub fn num_deferred_transactions(&self) -> usize {
+        self.deferred.lock().len()
+    }
+
+    /// Returns the number of hops that the locally-received transmissions may be forwarded.
+    pub fn transmission_ttl(&self) -> u8 {
+        self.transmission_ttl.load(Ordering::SeqCst)
+    }
+
+    /// Sets the number of hops that the locally-received transmissions may be forwarded.
+    /// Each peer that fetches a transmission decrements its hop count, and stops advertising it at zero.
+    pub fn set_transmission_ttl(&self, ttl: u8) -> Result<()> {
+        // Ensure the TTL is nonzero.
+        ensure!(ttl > 0, "The transmission TTL must be nonzero");
+        self.transmission_ttl.store(ttl, Ordering::SeqCst);
+        Ok(())
+    }
+
+    /// Returns the maximum number of pinned transmissions, where `0` indicates pinning is disabled.
+    pub fn max_pinned_transmissions(&self) -> usize {
+        self.max_pinned_transmissions.load(Ordering::SeqCst)
+    }
+
+    /// Sets the maximum number of pinned transmissions, which also bounds the number of pins per minute.
+    /// If `0` is given, pinning is disabled.
+    pub fn set_max_pinned_transmissions(&self, max_pinned: usize) -> Result<()> {
+        // Ensure the maximum is within the capacity of a batch.
+        ensure!(
+            max_pinned <= MAX_TRANSMISSIONS_PER_WORKER,
+            "The maximum number of pinned transmissions must be at most {MAX_TRANSMISSIONS_PER_WORKER}"
+        );
+        self.max_pinned_transmissions.store(max_pinned, Ordering::SeqCst);
+        Ok(())
+    }
+
+    /// Returns the number of pinned transmissions in the ready queue.
+    pub fn num_pinned_transmissions(&self) -> usize {
+        self.ready.num_pinned()
+    }
+
+    /// Pins the specified transmission, so it is included in the next batch ahead of every other transmission.
+    /// Note: Only a transmission in the ready queue can be pinned, as it was validated on admission.
+    /// Pinned transmissions are validated again when the batch is proposed.
+    pub fn pin_transmission(&self, transmission_id: impl Into<TransmissionID<N>>) -> Result<()> {
+        let transmission_id = transmission_id.into();
+        let max_pinned = self.max_pinned_transmissions();
+        // Ensure pinning is enabled.
+        ensure!(max_pinned > 0, "Pinning transmissions is disabled");
+        // Ensure the transmission is in the ready queue.
+        ensure!(self.ready.contains(transmission_id), "Transmission '{}' is not queued", fmt_id(transmission_id));
+        // Ensure the transmission is not already pinned.
+        if self.ready.is_pinned(transmission_id) {
+            return Ok(());
+        }
+        // Ensure the pinned transmissions do not exceed the maximum.
+        ensure!(self.ready.num_pinned() < max_pinned, "Unable to pin - too many pinned transmissions");
+        // Ensure the pins do not exceed the rate limit.
+        let now = now();
+        let mut pin_timestamps = self.pin_timestamps.lock();
+        while pin_timestamps.front().map_or(false, |t| *t <= now - PIN_RATE_LIMIT_INTERVAL_IN_SECS) {
+            pin_timestamps.pop_front();
+        }
+        ensure!(pin_timestamps.len() < max_pinned, "Unable to pin - too many recent pins");
+        // Pin the transmission.
+        ensure!(self.ready.pin(transmission_id), "Transmission '{}' is no longer queued", fmt_id(transmission_id));
+        pin_timestamps.push_back(now);
+        debug!("Worker {} - Pinned transmission '{}'", self.id, fmt_id(transmission_id));
+        Ok(())
+    }
+
+    /// Returns the remaining number of hops that the specified transmission may be forwarded.
+    fn remaining_ttl(&self, transmission_id: TransmissionID<N>) -> u8 {
+        match self.remaining_ttls.lock().get(&transmission_id) {
+            Some(ttl) => *ttl,
+            None => self.transmission_ttl(),
+        }
+    }
 }
 
 impl<N: Network> Worker<N> {
@@ -142,10 +472,12 @@ impl<N: Network> Worker<N> {
 
 impl<N: Network> Worker<N> {
     /// Returns `true` if the transmission ID exists in the ready queue, proposed batch, storage, or ledger.
+    /// Note: The transmissions that are staged for admission are considered part of the ready queue.
     pub fn contains_transmission(&self, transmission_id: impl Into<TransmissionID<N>>) -> bool {
         let transmission_id = transmission_id.into();
-        // Check if the transmission ID exists in the ready queue, proposed batch, storage, or ledger.
+        // Check if the transmission ID exists in the ready queue, admission queue, proposed batch, storage, or ledger.
         self.ready.contains(transmission_id)
+            || self.admission_queue().is_some_and(|queue| queue.contains(transmission_id))
             || self.proposed_batch.read().as_ref().map_or(false, |p| p.contains_transmission(transmission_id))
             || self.storage.contains_transmission(transmission_id)
             || self.ledger.contains_transmission(&transmission_id).unwrap_or(false)
@@ -160,6 +492,10 @@ impl<N: Network> Worker<N> {
         if let Some(transmission) = self.ready.get(transmission_id) {
             return Some(transmission);
         }
+        // Check if the transmission ID exists in the admission queue.
+        if let Some(transmission) = self.admission_queue().and_then(|queue| queue.get(transmission_id)) {
+            return Some(transmission);
+        }
         // Check if the transmission ID exists in storage.
         if let Some(transmission) = self.storage.get_transmission(transmission_id) {
             return Some(transmission);
@@ -173,18 +509,20 @@ impl<N: Network> Worker<N> {
         None
     }
 
-    /// Returns the transmissions if it exists in the worker, or requests it from the specified peer.
+    /// Returns the transmissions if it exists in the worker, or requests it from the specified peer's worker.
     pub async fn get_or_fetch_transmission(
         &self,
         peer_ip: SocketAddr,
         transmission_id: TransmissionID<N>,
+        peer_worker_id: u8,
     ) -> Result<(TransmissionID<N>, Transmission<N>)> {
         // Attempt to get the transmission from the worker.
         if let Some(transmission) = self.get_transmission(transmission_id) {
             return Ok((transmission_id, transmission));
         }
         // Send a transmission request to the peer.
-        let (candidate_id, transmission) = self.send_transmission_request(peer_ip, transmission_id).await?;
+        let (candidate_id, transmission, _) =
+            self.send_transmission_request(peer_ip, transmission_id, peer_worker_id).await?;
         // Ensure the transmission ID matches.
         ensure!(candidate_id == transmission_id, "Invalid transmission ID");
         // Return the transmission.
@@ -192,8 +530,67 @@ impl<N: Network> Worker<N> {
     }
 
     /// Removes up to the specified number of transmissions from the ready queue, and returns them.
+    /// The pinned transmissions are drained first, ahead of every other transmission.
     pub(crate) fn drain(&self, num_transmissions: usize) -> impl Iterator<Item = (TransmissionID<N>, Transmission<N>)> {
-        self.ready.drain(num_transmissions).into_iter()
+        let drained = self.drain_from(&self.ready, num_transmissions);
+        // Admit the staged transmissions into the freed slots, if the admission is bounded.
+        if let Some(admission_queue) = self.admission_queue() {
+            admission_queue.admit_staged(&self.ready);
+        }
+        drained
+    }
+
+    /// Returns the IDs of the transmissions that the next `drain` would take, without draining them.
+    pub(crate) fn preview_drain(&self, num_transmissions: usize) -> IndexSet<TransmissionID<N>> {
+        self.drain_from(&self.ready.snapshot(), num_transmissions).map(|(id, _)| id).collect()
+    }
+
+    /// Removes the specified number of transmissions from the given ready queue, and returns them.
+    fn drain_from(
+        &self,
+        ready: &Ready<N>,
+        num_transmissions: usize,
+    ) -> impl Iterator<Item = (TransmissionID<N>, Transmission<N>)> {
+        // Drain the pinned transmissions.
+        let pinned = ready.drain_pinned(num_transmissions);
+        let num_transmissions = num_transmissions.saturating_sub(pinned.len());
+        // If fee priority is enabled, drain in order of priority fee, capping each submitter if fairness is enabled.
+        let drained = if self.is_fee_priority_enabled() {
+            ready.drain_by_fee(num_transmissions, self.max_transmissions_per_submitter())
+        } else {
+            match self.max_transmissions_per_submitter() {
+                // If fairness is enabled, cap the number of transmissions from any single submitter.
+                Some(max_per_submitter) => ready.drain_fair(num_transmissions, max_per_submitter),
+                None => ready.drain(num_transmissions),
+            }
+        };
+        pinned.into_iter().chain(drained)
+    }
+
+    /// Inserts the specified transmission into the ready queue, or stages it if the ready queue is full,
+    /// with the given priority fee. Returns `true` if the transmission was inserted into the ready queue.
+    fn admit(&self, transmission_id: TransmissionID<N>, transmission: Transmission<N>, priority_fee: u64) -> bool {
+        match self.admission_queue() {
+            // Stage the transmission with its priority fee, so that the fee is weighed on arrival.
+            Some(admission_queue) => admission_queue.admit(&self.ready, transmission_id, transmission, priority_fee),
+            None => {
+                let is_new = self.ready.insert(transmission_id, transmission);
+                self.ready.set_priority_fee(transmission_id, priority_fee);
+                is_new
+            }
+        }
+    }
+
+    /// Returns `true` if the transmissions are ordered by priority fee, in the ready queue or on admission.
+    fn is_fee_ordered(&self) -> bool {
+        self.is_fee_priority_enabled()
+            || self.admission_queue().is_some_and(|queue| queue.order() == AdmissionOrder::Fee)
+    }
+
+    /// Returns `true` if the transactions are ordered by their submitter or priority fee, in which case
+    /// they are deserialized on admission.
+    fn is_transaction_ordered(&self) -> bool {
+        self.max_transmissions_per_submitter().is_some() || self.is_fee_ordered()
     }
 
     /// Reinserts the specified transmission into the ready queue.
@@ -206,11 +603,49 @@ impl<N: Network> Worker<N> {
         false
     }
 
+    /// Queues the given locally-submitted transmission ID for advertisement, if coalescing is enabled.
+    /// If a full worker ping of transmission IDs is queued, they are advertised immediately.
+    fn queue_local_gossip(&self, transmission_id: TransmissionID<N>) {
+        if self.local_gossip_interval().is_none() {
+            return;
+        }
+        let is_full = {
+            let mut local_gossip = self.local_gossip.lock();
+            local_gossip.insert(transmission_id);
+            local_gossip.len() >= MAX_TRANSMISSIONS_PER_WORKER_PING
+        };
+        if is_full {
+            self.flush_local_gossip();
+        }
+    }
+
+    /// Advertises the queued locally-submitted transmission IDs, that are still in the ready queue, in worker pings.
+    ///
+    /// Note: The worker pings are broadcast through the gateway, which rate limits the outbound events to each peer.
+    pub(crate) fn flush_local_gossip(&self) {
+        // Take the queued transmission IDs, so that the lock is not held while broadcasting.
+        let queued = std::mem::take(&mut *self.local_gossip.lock());
+        // Skip the transmissions that have since left the ready queue.
+        let transmission_ids = queued.into_iter().filter(|id| self.ready.contains(*id)).collect::<Vec<_>>();
+        // Broadcast the transmission IDs, in as few worker pings as possible.
+        for chunk in transmission_ids.chunks(MAX_TRANSMISSIONS_PER_WORKER_PING) {
+            let chunk = chunk.iter().copied().collect::<IndexSet<_>>();
+            self.gateway.broadcast(Event::WorkerPing(chunk.into()));
+        }
+    }
+
     /// Broadcasts a worker ping event.
     pub(crate) fn broadcast_ping(&self) {
-        // Retrieve the transmission IDs.
-        let transmission_ids =
-            self.ready.transmission_ids().into_iter().take(MAX_TRANSMISSIONS_PER_WORKER_PING).collect::<IndexSet<_>>();
+        // Remove the remaining hop counts of the transmissions that have left the ready queue.
+        self.remaining_ttls.lock().retain(|transmission_id, _| self.ready.contains(*transmission_id));
+        // Retrieve the transmission IDs, that may still be forwarded.
+        let transmission_ids = self
+            .ready
+            .transmission_ids()
+            .into_iter()
+            .filter(|transmission_id| self.remaining_ttl(*transmission_id) > 0)
+            .take(MAX_TRANSMISSIONS_PER_WORKER_PING)
+            .collect::<IndexSet<_>>();
 
         // Broadcast the ping event.
         if !transmission_ids.is_empty() {
@@ -235,15 +670,16 @@ impl<N: Network> Worker<N> {
         let self_ = self.clone();
         tokio::spawn(async move {
             // Send a transmission request to the peer.
-            match self_.send_transmission_request(peer_ip, transmission_id).await {
+            // Note: The worker ping does not specify the peer's worker, so the peer is assumed to use the same worker ID.
+            match self_.send_transmission_request(peer_ip, transmission_id, self_.id).await {
                 // If the transmission was fetched, then process it.
-                Ok((candidate_id, transmission)) => {
+                Ok((candidate_id, transmission, ttl)) => {
                     // Ensure the transmission ID matches.
                     if candidate_id == transmission_id {
-                        // Insert the transmission into the ready queue.
+                        // Insert the transmission into the ready queue, with its remaining hop count.
                         // Note: This method checks `contains_transmission` again, because by the time the transmission is fetched,
                         // it could have already been inserted into the ready queue.
-                        self_.process_transmission_from_peer(peer_ip, transmission_id, transmission);
+                        self_.process_forwarded_transmission(peer_ip, transmission_id, transmission, Some(ttl)).await;
                     }
                 }
                 // If the transmission was not fetched, then attempt to fetch it again.
@@ -259,11 +695,23 @@ impl<N: Network> Worker<N> {
     }
 
     /// Handles the incoming transmission from a peer.
-    pub(crate) fn process_transmission_from_peer(
+    pub(crate) async fn process_transmission_from_peer(
+        &self,
+        peer_ip: SocketAddr,
+        transmission_id: TransmissionID<N>,
+        transmission: Transmission<N>,
+    ) {
+        self.process_forwarded_transmission(peer_ip, transmission_id, transmission, None).await
+    }
+
+    /// Handles the incoming transmission from a peer, with its remaining number of hops, if it was fetched
+    /// from the peer's worker ping (otherwise, the transmission may be forwarded the full number of hops).
+    async fn process_forwarded_transmission(
         &self,
         peer_ip: SocketAddr,
         transmission_id: TransmissionID<N>,
         transmission: Transmission<N>,
+        remaining_ttl: Option<u8>,
     ) {
         // If the transmission ID already exists, then do not store it.
         if self.contains_transmission(transmission_id) {
@@ -279,9 +727,53 @@ impl<N: Network> Worker<N> {
             // All other combinations are clearly invalid.
             _ => false,
         };
+        if !is_well_formed {
+            return;
+        }
+        // Determine whether the sender of the gossiped transaction is rate limited, if a limit is set.
+        // Note: The transmissions of the batches of peers are not rate limited, so that their certificates complete.
+        let is_rate_limited = remaining_ttl.is_some() && self.sender_rate_limit().is_some();
+        // Deserialize the transaction once (off the runtime), if its sender is rate limited, or its submitter
+        // or priority fee is needed to order it.
+        let transaction = match (transmission_id, &transmission) {
+            (TransmissionID::Transaction(transaction_id), Transmission::Transaction(transaction))
+                if is_rate_limited || self.is_transaction_ordered() =>
+            {
+                let transaction = transaction.clone();
+                match spawn_blocking!(transaction.deserialize_blocking()) {
+                    Ok(transaction) => {
+                        // Ensure the sender of the gossiped transaction is within the rate limit.
+                        if is_rate_limited && !self.is_within_sender_rate_limit(transaction_id, &transaction) {
+                            let id = fmt_id(transmission_id);
+                            trace!("Worker {} - Throttled transmission '{id}' from '{peer_ip}'", self.id);
+                            return;
+                        }
+                        Some(transaction)
+                    }
+                    // Note: A malformed transaction cannot be rate limited, so it is rejected if a limit is set.
+                    Err(_) if is_rate_limited => return,
+                    Err(_) => None,
+                }
+            }
+            _ => None,
+        };
+        let priority_fee = transaction.as_ref().map_or(0, transaction_priority_fee);
+        // Lock the remaining hop counts across the admission, so that a concurrent worker ping neither evicts
+        // the hop count before the transmission is in the ready queue, nor advertises it with the full hop count.
+        let mut remaining_ttls = self.remaining_ttls.lock();
         // If the transmission ID and transmission type matches, then insert the transmission into the ready queue.
-        if is_well_formed && self.ready.insert(transmission_id, transmission) {
+        let is_admitted = self.admit(transmission_id, transmission, priority_fee);
+        // Record the remaining hop count, only once the transmission is admitted.
+        if let Some(remaining_ttl) = remaining_ttl.filter(|_| is_admitted) {
+            remaining_ttls.insert(transmission_id, remaining_ttl);
+        }
+        drop(remaining_ttls);
+        if is_admitted {
             trace!("Worker {} - Added transmission '{}' from '{peer_ip}'", self.id, fmt_id(transmission_id));
+            // Record the submitter of the transaction, if it was deserialized.
+            if let Some(transaction) = &transaction {
+                self.record_submitter(transmission_id, transaction);
+            }
         }
     }
 
@@ -312,29 +804,302 @@ impl<N: Network> Worker<N> {
     }
 
     /// Handles the incoming unconfirmed transaction.
+    /// If admission receipts are enabled, this method returns a receipt signed by the validator.
     pub(crate) async fn process_unconfirmed_transaction(
         &self,
         transaction_id: N::TransactionID,
         transaction: Data<Transaction<N>>,
-    ) -> Result<()> {
+    ) -> Result<Option<AdmissionReceipt<N>>> {
         // Construct the transmission.
         let transmission = Transmission::Transaction(transaction.clone());
         // Remove the transaction from the pending queue.
-        self.pending.remove(&transaction_id, Some(transmission.clone()));
+        self.pending.remove(&transaction_id, Some((transmission.clone(), self.transmission_ttl())));
         // Check if the transaction ID exists.
         if self.contains_transmission(&transaction_id) {
             bail!("Transaction '{}' already exists.", fmt_id(transaction_id));
         }
-        // Check that the transaction is well-formed and unique.
-        if let Err(e) = self.ledger.check_transaction_basic(transaction_id, transaction).await {
-            bail!("Invalid unconfirmed transaction '{}': {e}", fmt_id(transaction_id));
+        // Check that the sender of the transaction is admitted, if a sender filter is set.
+        self.check_sender(transaction_id, &transaction).await?;
+        // Check that the transaction does not reference unknown programs, if detection is enabled.
+        if let Some(policy) = self.unknown_program_policy() {
+            if let Some(program_id) = self.find_unknown_program(transaction_id, &transaction).await? {
+                match policy {
+                    UnknownProgramPolicy::Reject => bail!(
+                        "Unconfirmed transaction '{}' references an unknown program '{program_id}'",
+                        fmt_id(transaction_id)
+                    ),
+                    UnknownProgramPolicy::Defer => {
+                        self.defer_transaction(transaction_id, transaction)?;
+                        debug!(
+                            "Deferred unconfirmed transaction '{}' (unknown program '{program_id}')",
+                            fmt_id(transaction_id)
+                        );
+                        return Ok(None);
+                    }
+                }
+            }
+        }
+        // Check if the transaction is the retry of a transiently-rejected transaction, which was already validated.
+        // Note: The transaction ID does not commit to the proofs, so the retry must carry the same bytes.
+        let transient_rejections = self.transient_rejections();
+        let transaction_bytes = match transient_rejections {
+            Some(_) => Some(transaction.to_bytes_le()?),
+            None => None,
+        };
+        let is_validated = transient_rejections
+            .zip(transaction_bytes.as_deref())
+            .and_then(|(rejections, bytes)| rejections.remove(&transaction_id, bytes))
+            .unwrap_or(false);
+        if is_validated {
+            debug!("Worker {} - Recognized the retry of unconfirmed transaction '{}'", self.id, fmt_id(transaction_id));
+        }
+        // Check that the transaction is well-formed and unique, unless it was validated on an earlier attempt.
+        if !is_validated {
+            if let Err(e) = self.ledger.check_transaction_basic(transaction_id, transaction.clone()).await {
+                bail!("Invalid unconfirmed transaction '{}': {e}", fmt_id(transaction_id));
+            }
+        }
+        // Check that the transaction is admitted by the admission hook, if one is set.
+        if let Some(admission_hook) = self.admission_hook() {
+            if !admission_hook.is_admitted((&transaction_id).into(), &transmission).await {
+                bail!("Unconfirmed transaction '{}' was rejected by the admission hook", fmt_id(transaction_id));
+            }
         }
+        // Derive the submitter and priority fee of the transaction, if they are needed to order or replace it.
+        let transaction = match self.is_transaction_ordered() || self.replacement_policy().is_some() {
+            true => spawn_blocking!(transaction.deserialize_blocking()).ok(),
+            false => None,
+        };
+        let priority_fee = transaction.as_ref().map_or(0, transaction_priority_fee);
         // Adds the transaction to the ready queue.
-        if self.ready.insert(&transaction_id, transmission) {
+        if self.admit((&transaction_id).into(), transmission, priority_fee) {
             trace!("Worker {} - Added unconfirmed transaction '{}'", self.id, fmt_id(transaction_id));
+            // Record the submitter of the transaction.
+            if let Some(transaction) = &transaction {
+                self.record_submitter((&transaction_id).into(), transaction);
+            }
+            // Queue the transaction ID for advertisement.
+            self.queue_local_gossip((&transaction_id).into());
+        } else if let Some(admission_queue) = self.admission_queue() {
+            // If the ready queue is full, the transaction is either staged for admission, or dropped.
+            if admission_queue.contains(&transaction_id) {
+                debug!("Worker {} - Staged unconfirmed transaction '{}'", self.id, fmt_id(transaction_id));
+                return Ok(None);
+            }
+            if !self.ready.contains(&transaction_id) {
+                // Retain the rejection, so that a retry is recognized.
+                if let (Some(transient_rejections), Some(bytes)) = (self.transient_rejections(), &transaction_bytes) {
+                    transient_rejections.insert(&transaction_id, Some(bytes));
+                }
+                bail!("Unconfirmed transaction '{}' was dropped (the ready queue is full)", fmt_id(transaction_id));
+            }
+        }
+        // Sign the admission receipt, if receipts are enabled.
+        self.sign_receipt((&transaction_id).into())
+    }
+
+    /// Ensures the sender of the given transaction is admitted by the sender filter, and is within
+    /// the sender rate limit, if either is set.
+    async fn check_sender(&self, transaction_id: N::TransactionID, transaction: &Data<Transaction<N>>) -> Result<()> {
+        let (sender_filter, sender_rate_limit) = (self.sender_filter(), self.sender_rate_limit());
+        if sender_filter.is_none() && sender_rate_limit.is_none() {
+            return Ok(());
+        }
+        // Deserialize the transaction.
+        let transaction = transaction.clone();
+        let transaction = spawn_blocking!(transaction.deserialize_blocking())?;
+        let sender = transaction_sender(&transaction);
+        // Ensure the sender is admitted.
+        if let Some(sender_filter) = sender_filter {
+            if !sender_filter.is_admitted(sender) {
+                bail!("Unconfirmed transaction '{}' was rejected by the sender filter", fmt_id(transaction_id));
+            }
+        }
+        // Ensure the sender is within the rate limit.
+        if let (Some(sender_rate_limit), Some(sender)) = (sender_rate_limit, sender) {
+            if !sender_rate_limit.check(sender, transaction_id) {
+                bail!(
+                    "Unconfirmed transaction '{}' was throttled (sender '{sender}' is rate limited)",
+                    fmt_id(transaction_id)
+                );
+            }
+        }
+        Ok(())
+    }
+
+    /// Records the submitter of the given transaction in the ready queue, i.e. the payer of its public fee.
+    /// Note: The transactions with a private fee have no known submitter, and are not capped by fairness.
+    fn record_submitter(&self, transmission_id: TransmissionID<N>, transaction: &Transaction<N>) {
+        if let Some(submitter) = transaction_sender(transaction) {
+            self.ready.set_submitter(transmission_id, submitter);
+        }
+    }
+
+    /// Returns `true` if the sender of the given (deserialized) transaction is within the sender rate limit,
+    /// if one is set. Note: Only the transactions with a known sender are rate limited.
+    fn is_within_sender_rate_limit(&self, transaction_id: N::TransactionID, transaction: &Transaction<N>) -> bool {
+        let Some(sender_rate_limit) = self.sender_rate_limit() else {
+            return true;
+        };
+        transaction_sender(transaction).map_or(true, |sender| sender_rate_limit.check(sender, transaction_id))
+    }
+
+    /// Returns the first program referenced by the given transaction that is unknown to the ledger, if any.
+    ///
+    /// Note: As a transaction with an unknown program cannot be fully checked, it is only returned (to be
+    /// rejected or deferred) if the checks that do not depend on its programs pass, so that malformed
+    /// transactions cannot fill the deferred transactions.
+    async fn find_unknown_program(
+        &self,
+        transaction_id: N::TransactionID,
+        transaction: &Data<Transaction<N>>,
+    ) -> Result<Option<ProgramID<N>>> {
+        // Deserialize the transaction.
+        let transaction = transaction.clone();
+        let transaction = spawn_blocking!(transaction.deserialize_blocking())?;
+        // Find the first referenced program that is not in the ledger.
+        let mut unknown_program = None;
+        for program_id in referenced_program_ids(&transaction) {
+            if !self.ledger.contains_program(&program_id)? {
+                unknown_program = Some(program_id);
+                break;
+            }
+        }
+        let Some(program_id) = unknown_program else {
+            return Ok(None);
+        };
+        // Ensure the transaction matches its ID.
+        if transaction.id() != transaction_id {
+            bail!("Unconfirmed transaction '{}' does not match its ID", fmt_id(transaction_id));
+        }
+        // Ensure the transaction pays a fee, as only a 'credits.aleo' execution may omit it.
+        if transaction.fee_transition().is_none() {
+            bail!("Unconfirmed transaction '{}' does not pay a fee", fmt_id(transaction_id));
+        }
+        // Ensure the transaction is not already in the ledger.
+        if self.ledger.contains_transmission(&(&transaction_id).into())? {
+            bail!("Unconfirmed transaction '{}' already exists in the ledger", fmt_id(transaction_id));
+        }
+        Ok(Some(program_id))
+    }
+
+    /// Defers the given transaction, until the programs it references are known.
+    fn defer_transaction(&self, transaction_id: N::TransactionID, transaction: Data<Transaction<N>>) -> Result<()> {
+        let mut deferred = self.deferred.lock();
+        // Ensure the deferred transactions do not exceed the maximum.
+        if deferred.len() >= MAX_DEFERRED_TRANSACTIONS_PER_WORKER && !deferred.contains_key(&transaction_id) {
+            bail!("Unable to defer transaction '{}' - too many deferred transactions", fmt_id(transaction_id));
         }
+        deferred.insert(transaction_id, transaction);
         Ok(())
     }
+
+    /// Retries the admission of the deferred transactions. The transactions that still reference
+    /// unknown programs are deferred again, while the invalid transactions are dropped.
+    pub(crate) async fn retry_deferred_transactions(&self) {
+        // Take the deferred transactions.
+        let deferred = std::mem::take(&mut *self.deferred.lock());
+        for (transaction_id, transaction) in deferred {
+            if let Err(e) = self.process_unconfirmed_transaction(transaction_id, transaction).await {
+                debug!("Worker {} - Dropped deferred transaction '{}': {e}", self.id, fmt_id(transaction_id));
+            }
+        }
+    }
+
+    /// Handles the incoming replacement of the queued transaction `replaced_id`, by the given unconfirmed
+    /// transaction from the same submitter, with a higher priority fee. On success, the replaced transaction
+    /// is evicted from the ready queue.
+    ///
+    /// Note: The submitter and the priority fee are derived from the transaction, so a transaction with
+    /// a private fee (which does not reveal its payer) cannot replace another.
+    pub async fn process_replacement_transaction(
+        &self,
+        replaced_id: N::TransactionID,
+        transaction_id: N::TransactionID,
+        transaction: Data<Transaction<N>>,
+    ) -> Result<Option<AdmissionReceipt<N>>> {
+        // Ensure transaction replacement is enabled.
+        if self.replacement_policy().is_none() {
+            bail!("Transaction replacement is disabled");
+        }
+        // Deserialize the transaction, to derive its submitter and priority fee.
+        let transaction_ = transaction.clone();
+        let deserialized = spawn_blocking!(transaction_.deserialize_blocking())?;
+        let Some(submitter) = transaction_sender(&deserialized) else {
+            bail!("Replacement transaction '{}' does not reveal its fee payer", fmt_id(transaction_id));
+        };
+        let priority_fee = transaction_priority_fee(&deserialized);
+        self.replace_transaction(replaced_id, transaction_id, transaction, submitter, priority_fee).await
+    }
+
+    /// Replaces the queued transaction `replaced_id` with the given unconfirmed transaction, from the given
+    /// submitter with the given priority fee.
+    async fn replace_transaction(
+        &self,
+        replaced_id: N::TransactionID,
+        transaction_id: N::TransactionID,
+        transaction: Data<Transaction<N>>,
+        submitter: Address<N>,
+        priority_fee: u64,
+    ) -> Result<Option<AdmissionReceipt<N>>> {
+        // Ensure transaction replacement is enabled.
+        let Some(replacement_policy) = self.replacement_policy() else {
+            bail!("Transaction replacement is disabled");
+        };
+        // Ensure the replaced transaction is queued, from the same submitter.
+        if self.ready.get_submitter(&replaced_id) != Some(submitter) {
+            bail!("Transaction '{}' is not queued from '{submitter}'", fmt_id(replaced_id));
+        }
+        // Ensure the replacement priority fee is sufficiently higher.
+        replacement_policy.check_fee_bump(self.ready.get_priority_fee(&replaced_id).unwrap_or(0), priority_fee)?;
+        // Construct the transmission.
+        let transmission = Transmission::Transaction(transaction.clone());
+        // Remove the transaction from the pending queue.
+        self.pending.remove(&transaction_id, Some((transmission.clone(), self.transmission_ttl())));
+        // Check if the transaction ID exists.
+        if self.contains_transmission(&transaction_id) {
+            bail!("Transaction '{}' already exists.", fmt_id(transaction_id));
+        }
+        // Check that the sender of the transaction is admitted, if a sender filter is set.
+        self.check_sender(transaction_id, &transaction).await?;
+        // Check that the transaction is well-formed and unique.
+        if let Err(e) = self.ledger.check_transaction_basic(transaction_id, transaction).await {
+            bail!("Invalid replacement transaction '{}': {e}", fmt_id(transaction_id));
+        }
+        // Check that the transaction is admitted by the admission hook, if one is set.
+        if let Some(admission_hook) = self.admission_hook() {
+            if !admission_hook.is_admitted((&transaction_id).into(), &transmission).await {
+                bail!("Replacement transaction '{}' was rejected by the admission hook", fmt_id(transaction_id));
+            }
+        }
+        // Ensure the submitter has not exceeded the rate limit on replacements.
+        replacement_policy.record_replacement(submitter, now())?;
+        // Replace the transaction in the ready queue.
+        if !self.ready.replace(&replaced_id, &transaction_id, transmission, priority_fee) {
+            bail!("Transaction '{}' is no longer queued", fmt_id(replaced_id));
+        }
+        debug!(
+            "Worker {} - Replaced unconfirmed transaction '{}' with '{}'",
+            self.id,
+            fmt_id(replaced_id),
+            fmt_id(transaction_id)
+        );
+        // Queue the transaction ID for advertisement.
+        self.queue_local_gossip((&transaction_id).into());
+        // Sign the admission receipt, if receipts are enabled.
+        self.sign_receipt((&transaction_id).into())
+    }
+
+    /// Returns a signed admission receipt for the given transmission ID, if receipts are enabled.
+    fn sign_receipt(&self, transmission_id: TransmissionID<N>) -> Result<Option<AdmissionReceipt<N>>> {
+        match &*self.receipt_signer.read() {
+            Some(account) => {
+                let round = self.storage.current_round();
+                Ok(Some(AdmissionReceipt::sign(account, transmission_id, now(), round, &mut rand::rngs::OsRng)?))
+            }
+            None => Ok(None),
+        }
+    }
 }
 
 impl<N: Network> Worker<N> {
@@ -342,6 +1107,31 @@ impl<N: Network> Worker<N> {
     fn start_handlers(&self, receiver: WorkerReceiver<N>) {
         let WorkerReceiver { mut rx_worker_ping, mut rx_transmission_request, mut rx_transmission_response } = receiver;
 
+        // Periodically advertise the locally-submitted transmission IDs, if coalescing is enabled.
+        let self_ = self.clone();
+        self.spawn(async move {
+            loop {
+                match self_.local_gossip_interval() {
+                    Some(interval) => {
+                        tokio::time::sleep(interval).await;
+                        self_.flush_local_gossip();
+                    }
+                    None => tokio::time::sleep(Duration::from_millis(MAX_BATCH_DELAY_IN_MS)).await,
+                }
+            }
+        });
+
+        // Periodically retry the admission of the deferred transactions, if transactions are deferred.
+        let self_ = self.clone();
+        self.spawn(async move {
+            loop {
+                tokio::time::sleep(DEFERRED_TRANSACTIONS_RETRY_INTERVAL).await;
+                if self_.unknown_program_policy() == Some(UnknownProgramPolicy::Defer) {
+                    self_.retry_deferred_transactions().await;
+                }
+            }
+        });
+
         // Process the ping events.
         let self_ = self.clone();
         self.spawn(async move {
@@ -368,24 +1158,29 @@ impl<N: Network> Worker<N> {
         });
     }
 
-    /// Sends a transmission request to the specified peer.
+    /// Sends a transmission request to the specified worker of the specified peer.
     async fn send_transmission_request(
         &self,
         peer_ip: SocketAddr,
         transmission_id: TransmissionID<N>,
-    ) -> Result<(TransmissionID<N>, Transmission<N>)> {
+        peer_worker_id: u8,
+    ) -> Result<(TransmissionID<N>, Transmission<N>, u8)> {
         // Initialize a oneshot channel.
         let (callback_sender, callback_receiver) = oneshot::channel();
         // Insert the transmission ID into the pending queue.
         self.pending.insert(transmission_id, peer_ip, Some(callback_sender));
         // Send the transmission request to the peer.
-        if self.gateway.send(peer_ip, Event::TransmissionRequest(transmission_id.into())).await.is_none() {
+        let request = TransmissionRequest::new(transmission_id, peer_worker_id);
+        if self.gateway.send(peer_ip, Event::TransmissionRequest(request)).await.is_none() {
             bail!("Unable to fetch transmission - failed to send request")
         }
         // Wait for the transmission to be fetched.
         match timeout(Duration::from_millis(MAX_BATCH_DELAY_IN_MS), callback_receiver).await {
-            // If the transmission was fetched, return it.
-            Ok(result) => Ok((transmission_id, result?)),
+            // If the transmission was fetched, return it, with its remaining hop count.
+            Ok(result) => {
+                let (transmission, remaining_ttl) = result?;
+                Ok((transmission_id, transmission, remaining_ttl))
+            }
             // If the transmission was not fetched, return an error.
             Err(e) => bail!("Unable to fetch transmission - (timeout) {e}"),
         }
@@ -394,7 +1189,7 @@ impl<N: Network> Worker<N> {
     /// Handles the incoming transmission response.
     /// This method ensures the transmission response is well-formed and matches the transmission ID.
     fn finish_transmission_request(&self, peer_ip: SocketAddr, response: TransmissionResponse<N>) {
-        let TransmissionResponse { transmission_id, mut transmission } = response;
+        let TransmissionResponse { transmission_id, mut transmission, ttl } = response;
         // Check if the peer IP exists in the pending queue for the given transmission ID.
         let exists = self.pending.get(transmission_id).unwrap_or_default().contains(&peer_ip);
         // If the peer IP exists, finish the pending request.
@@ -402,8 +1197,11 @@ impl<N: Network> Worker<N> {
             // Ensure the transmission is not a fee and matches the transmission ID.
             match self.ledger.ensure_transmission_is_well_formed(transmission_id, &mut transmission) {
                 Ok(()) => {
+                    // Decrement the hop count, which is capped by the local TTL to bound the gossip amplification.
+                    // Note: The hop count is recorded once the transmission is admitted into the ready queue.
+                    let remaining_ttl = ttl.min(self.transmission_ttl()).saturating_sub(1);
                     // Remove the transmission ID from the pending queue.
-                    self.pending.remove(transmission_id, Some(transmission));
+                    self.pending.remove(transmission_id, Some((transmission, remaining_ttl)));
                 }
                 Err(err) => warn!("Failed to finish transmission response from peer '{peer_ip}': {err}"),
             };
@@ -412,13 +1210,15 @@ impl<N: Network> Worker<N> {
 
     /// Sends the requested transmission to the specified peer.
     fn send_transmission_response(&self, peer_ip: SocketAddr, request: TransmissionRequest<N>) {
-        let TransmissionRequest { transmission_id } = request;
+        let TransmissionRequest { transmission_id, .. } = request;
         // Attempt to retrieve the transmission.
         if let Some(transmission) = self.get_transmission(transmission_id) {
-            // Send the transmission response to the peer.
+            // Send the transmission response to the peer, with the remaining hop count.
+            let ttl = self.remaining_ttl(transmission_id);
+            let response = TransmissionResponse::new(transmission_id, transmission, ttl);
             let self_ = self.clone();
             tokio::spawn(async move {
-                self_.gateway.send(peer_ip, Event::TransmissionResponse((transmission_id, transmission).into())).await;
+                self_.gateway.send(peer_ip, Event::TransmissionResponse(response)).await;
             });
         }
     }
@@ -439,6 +1239,7 @@ impl<N: Network> Worker<N> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::helpers::AdmissionScorer;
     use snarkos_node_bft_ledger_service::LedgerService;
     use snarkos_node_bft_storage_service::BFTMemoryService;
     use snarkvm::{
@@ -446,14 +1247,14 @@ mod tests {
         ledger::{
             block::Block,
             committee::Committee,
-            narwhal::{BatchCertificate, Subdag, Transmission, TransmissionID},
+            narwhal::{BatchCertificate, BatchHeader, Subdag, Transmission, TransmissionID},
         },
     };
 
     use bytes::Bytes;
     use indexmap::IndexMap;
     use mockall::mock;
-    use std::{io, ops::Range};
+    use std::{io, ops::Range, sync::atomic::AtomicUsize};
 
     type CurrentNetwork = snarkvm::prelude::Testnet3;
 
@@ -486,6 +1287,7 @@ mod tests {
             fn get_committee_for_round(&self, round: u64) -> Result<Committee<N>>;
             fn get_committee_lookback_for_round(&self, round: u64) -> Result<Committee<N>>;
             fn contains_certificate(&self, certificate_id: &Field<N>) -> Result<bool>;
+            fn contains_program(&self, program_id: &ProgramID<N>) -> Result<bool>;
             fn contains_transmission(&self, transmission_id: &TransmissionID<N>) -> Result<bool>;
             fn ensure_transmission_is_well_formed(
                 &self,
@@ -503,6 +1305,7 @@ mod tests {
                 transaction: Data<Transaction<N>>,
             ) -> Result<()>;
             fn check_next_block(&self, block: &Block<N>) -> Result<()>;
+            fn report_equivocation(&self, first: &BatchHeader<N>, second: &BatchHeader<N>) -> Result<()>;
             fn prepare_advance_to_next_quorum_block(
                 &self,
                 subdag: Subdag<N>,
@@ -535,7 +1338,7 @@ mod tests {
         let transmission = Transmission::Solution(data(rng));
 
         // Process the transmission.
-        worker.process_transmission_from_peer(peer_ip, transmission_id, transmission.clone());
+        worker.process_transmission_from_peer(peer_ip, transmission_id, transmission.clone()).await;
         assert!(worker.contains_transmission(transmission_id));
         assert!(worker.ready.contains(transmission_id));
         assert_eq!(worker.get_transmission(transmission_id), Some(transmission));
@@ -545,6 +1348,103 @@ mod tests {
         assert!(!worker.ready.contains(transmission_id));
     }
 
+    #[tokio::test]
+    async fn test_drain_with_submitter_fairness() {
+        let rng = &mut TestRng::default();
+        // Setup the mock gateway and ledger.
+        let gateway = MockGateway::default();
+        let mut mock_ledger = MockLedger::default();
+        mock_ledger.expect_contains_transmission().returning(|_| Ok(false));
+        let ledger: Arc<dyn LedgerService<CurrentNetwork>> = Arc::new(mock_ledger);
+        // Initialize the storage.
+        let storage = Storage::<CurrentNetwork>::new(ledger.clone(), Arc::new(BFTMemoryService::new()), 1);
+
+        // Create the Worker, with a cap of 2 transmissions per submitter.
+        let worker = Worker::new(0, Arc::new(gateway), storage, ledger, Default::default()).unwrap();
+        assert!(worker.set_max_transmissions_per_submitter(Some(0)).is_err());
+        worker.set_max_transmissions_per_submitter(Some(2)).unwrap();
+        assert_eq!(worker.max_transmissions_per_submitter(), Some(2));
+
+        // Sample the submitters.
+        let dominant = Address::<CurrentNetwork>::new(rng.gen());
+        let others = (0..3).map(|_| Address::<CurrentNetwork>::new(rng.gen())).collect::<Vec<_>>();
+        // Insert a transmission for the given submitter.
+        let data = |rng: &mut TestRng| Data::Buffer(Bytes::from((0..512).map(|_| rng.gen::<u8>()).collect::<Vec<_>>()));
+        let mut insert = |submitter: Address<CurrentNetwork>| {
+            let transmission_id = TransmissionID::Solution(PuzzleCommitment::from_g1_affine(rng.gen()));
+            assert!(worker.ready.insert(transmission_id, Transmission::Solution(data(rng))));
+            assert!(worker.ready.set_submitter(transmission_id, submitter));
+            transmission_id
+        };
+        // The dominant submitter fills the front of the queue, followed by one transmission from each other submitter.
+        let dominant_ids = (0..10).map(|_| insert(dominant)).collect::<Vec<_>>();
+        let other_ids = others.iter().map(|submitter| insert(*submitter)).collect::<Vec<_>>();
+
+        // Drain a batch, and ensure the cap is respected while the others are included.
+        let batch = worker.drain(8).map(|(id, _)| id).collect::<IndexSet<_>>();
+        assert_eq!(batch.len(), 2 + others.len());
+        assert_eq!(batch.iter().filter(|id| dominant_ids.contains(id)).count(), 2);
+        assert!(other_ids.iter().all(|id| batch.contains(id)));
+        // Ensure the skipped transmissions remain in the ready queue, in order.
+        assert_eq!(worker.transmission_ids().into_iter().collect::<Vec<_>>(), dominant_ids[2..].to_vec());
+        assert_eq!(worker.ready.get_submitter(dominant_ids[2]), Some(dominant));
+        assert_eq!(worker.ready.get_submitter(dominant_ids[0]), None);
+
+        // Disable fairness, and ensure the remaining transmissions are drained in queue order.
+        worker.set_max_transmissions_per_submitter(None).unwrap();
+        let batch = worker.drain(8).map(|(id, _)| id).collect::<Vec<_>>();
+        assert_eq!(batch, dominant_ids[2..].to_vec());
+    }
+
+    #[tokio::test]
+    async fn test_drain_pinned_transmissions_first() {
+        let rng = &mut TestRng::default();
+        // Setup the mock gateway and ledger.
+        let gateway = MockGateway::default();
+        let mut mock_ledger = MockLedger::default();
+        mock_ledger.expect_contains_transmission().returning(|_| Ok(false));
+        let ledger: Arc<dyn LedgerService<CurrentNetwork>> = Arc::new(mock_ledger);
+        // Initialize the storage.
+        let storage = Storage::<CurrentNetwork>::new(ledger.clone(), Arc::new(BFTMemoryService::new()), 1);
+
+        // Create the Worker, with fee priority enabled.
+        let worker = Worker::new(0, Arc::new(gateway), storage, ledger, Default::default()).unwrap();
+        worker.set_fee_priority(true);
+        assert_eq!(worker.max_pinned_transmissions(), 0);
+        assert!(worker.set_max_pinned_transmissions(MAX_TRANSMISSIONS_PER_WORKER + 1).is_err());
+
+        // Insert the transmissions, with increasing priority fees.
+        let data = |rng: &mut TestRng| Data::Buffer(Bytes::from((0..512).map(|_| rng.gen::<u8>()).collect::<Vec<_>>()));
+        let ids = (0..4u64)
+            .map(|fee| {
+                let transmission_id = TransmissionID::Solution(PuzzleCommitment::from_g1_affine(rng.gen()));
+                assert!(worker.ready.insert(transmission_id, Transmission::Solution(data(rng))));
+                assert!(worker.set_priority_fee(transmission_id, fee * 100));
+                transmission_id
+            })
+            .collect::<Vec<_>>();
+
+        // Ensure pinning is disabled by default, and only queued transmissions are pinned.
+        assert!(worker.pin_transmission(ids[0]).is_err());
+        worker.set_max_pinned_transmissions(1).unwrap();
+        let unknown_id = TransmissionID::Solution(PuzzleCommitment::from_g1_affine(rng.gen()));
+        assert!(worker.pin_transmission(unknown_id).is_err());
+        // Pin the lowest-fee transmission, and ensure the maximum is enforced.
+        worker.pin_transmission(ids[0]).unwrap();
+        assert!(worker.pin_transmission(ids[0]).is_ok());
+        assert!(worker.pin_transmission(ids[1]).is_err());
+        assert_eq!(worker.num_pinned_transmissions(), 1);
+
+        // Ensure the pinned transmission is included in the next batch, ahead of the higher-fee transmissions.
+        let batch = worker.drain(2).map(|(id, _)| id).collect::<Vec<_>>();
+        assert_eq!(batch, vec![ids[0], ids[3]]);
+        assert_eq!(worker.num_pinned_transmissions(), 0);
+        // Ensure the pins are rate-limited, even once the pinned transmission is drained.
+        assert!(worker.pin_transmission(ids[1]).is_err());
+        let batch = worker.drain(2).map(|(id, _)| id).collect::<Vec<_>>();
+        assert_eq!(batch, vec![ids[2], ids[1]]);
+    }
+
     #[tokio::test]
     async fn test_send_transmission() {
         let rng = &mut TestRng::default();
@@ -570,18 +1470,116 @@ mod tests {
         let transmission_id = TransmissionID::Solution(PuzzleCommitment::from_g1_affine(rng.gen()));
         let worker_ = worker.clone();
         let peer_ip = SocketAddr::from(([127, 0, 0, 1], 1234));
-        let _ = worker_.send_transmission_request(peer_ip, transmission_id).await;
+        let _ = worker_.send_transmission_request(peer_ip, transmission_id, worker_.id()).await;
         assert!(worker.pending.contains(transmission_id));
         let peer_ip = SocketAddr::from(([127, 0, 0, 1], 1234));
         // Fake the transmission response.
         worker.finish_transmission_request(peer_ip, TransmissionResponse {
             transmission_id,
             transmission: Transmission::Solution(Data::Buffer(Bytes::from(vec![0; 512]))),
+            ttl: DEFAULT_TRANSMISSION_TTL,
         });
         // Check the transmission was removed from the pending set.
         assert!(!worker.pending.contains(transmission_id));
     }
 
+    #[tokio::test]
+    async fn test_transmission_gossip_stops_at_ttl() {
+        let rng = &mut TestRng::default();
+        let transmission_id = TransmissionID::Solution(PuzzleCommitment::from_g1_affine(rng.gen()));
+        let transmission = Transmission::Solution(Data::Buffer(Bytes::from(vec![0; 512])));
+        let peer_ip = SocketAddr::from(([127, 0, 0, 1], 1234));
+
+        // Initializes a worker with a TTL of 3, that is expected to advertise the transmission `num_pings` times.
+        let sample_worker = |num_pings: usize| {
+            let mut gateway = MockGateway::default();
+            gateway.expect_broadcast().times(num_pings).return_const(());
+            let mut mock_ledger = MockLedger::default();
+            mock_ledger.expect_contains_transmission().returning(|_| Ok(false));
+            mock_ledger.expect_ensure_transmission_is_well_formed().returning(|_, _| Ok(()));
+            let ledger: Arc<dyn LedgerService<CurrentNetwork>> = Arc::new(mock_ledger);
+            let storage = Storage::<CurrentNetwork>::new(ledger.clone(), Arc::new(BFTMemoryService::new()), 1);
+            let worker = Worker::new(0, Arc::new(gateway), storage, ledger, Default::default()).unwrap();
+            assert!(worker.set_transmission_ttl(0).is_err());
+            worker.set_transmission_ttl(3).unwrap();
+            worker
+        };
+
+        // The origin advertises the transmission with the full TTL.
+        let origin = sample_worker(1);
+        assert!(origin.ready.insert(transmission_id, transmission.clone()));
+        origin.broadcast_ping();
+        let mut ttl = origin.remaining_ttl(transmission_id);
+        assert_eq!(ttl, 3);
+
+        // Fetches the transmission from a peer with the given hop count, and inserts it into the ready queue.
+        let fetch = |worker: &Worker<CurrentNetwork>, ttl: u8| {
+            let (callback_sender, mut callback_receiver) = oneshot::channel();
+            worker.pending.insert(transmission_id, peer_ip, Some(callback_sender));
+            let response = TransmissionResponse::new(transmission_id, transmission.clone(), ttl);
+            worker.finish_transmission_request(peer_ip, response);
+            // Ensure a worker ping before the admission does not evict (and so reset) the hop count.
+            worker.broadcast_ping();
+            let (transmission, remaining_ttl) = callback_receiver.try_recv().unwrap();
+            worker.process_forwarded_transmission(peer_ip, transmission_id, transmission, Some(remaining_ttl)).await;
+        };
+
+        // Forward the transmission through several hops, and ensure it is no longer advertised at the TTL.
+        for hop in 1..=4u8 {
+            let worker = sample_worker(usize::from(hop < 3));
+            // Fetch the transmission from the previous hop.
+            fetch(&worker, ttl);
+            assert!(worker.ready.contains(transmission_id));
+            // Advertise the transmission, if it may still be forwarded.
+            worker.broadcast_ping();
+            ttl = worker.remaining_ttl(transmission_id);
+            assert_eq!(ttl, 3u8.saturating_sub(hop));
+        }
+
+        // Ensure an inflated hop count from a peer is capped by the local TTL.
+        let worker = sample_worker(1);
+        fetch(&worker, u8::MAX);
+        worker.broadcast_ping();
+        assert_eq!(worker.remaining_ttl(transmission_id), 2);
+    }
+
+    #[tokio::test]
+    async fn test_fetch_transmission_from_peer_worker() {
+        let rng = &mut TestRng::default();
+        // Sample a committee.
+        let committee = snarkvm::ledger::committee::test_helpers::sample_committee(rng);
+        // Sample a transmission ID, and determine its worker ID for a peer with multiple workers.
+        let transmission_id = TransmissionID::Solution(PuzzleCommitment::from_g1_affine(rng.gen()));
+        let peer_worker_id = crate::helpers::assign_to_worker(transmission_id, 4).unwrap();
+        // Setup the mock gateway and ledger.
+        let mut gateway = MockGateway::default();
+        gateway
+            .expect_send()
+            .withf(move |_, event| match event {
+                Event::TransmissionRequest(request) => {
+                    request.transmission_id == transmission_id && request.worker_id == peer_worker_id
+                }
+                _ => false,
+            })
+            .times(1)
+            .returning(|_, _| {
+                let (_tx, rx) = oneshot::channel();
+                Some(rx)
+            });
+        let mut mock_ledger = MockLedger::default();
+        mock_ledger.expect_current_committee().returning(move || Ok(committee.clone()));
+        let ledger: Arc<dyn LedgerService<CurrentNetwork>> = Arc::new(mock_ledger);
+        // Initialize the storage.
+        let storage = Storage::<CurrentNetwork>::new(ledger.clone(), Arc::new(BFTMemoryService::new()), 1);
+
+        // Create the Worker.
+        let worker = Worker::new(0, Arc::new(gateway), storage, ledger, Default::default()).unwrap();
+        let peer_ip = SocketAddr::from(([127, 0, 0, 1], 1234));
+        // Fetch the transmission, which sends the request to the assigned worker of the peer.
+        let _ = worker.get_or_fetch_transmission(peer_ip, transmission_id, peer_worker_id).await;
+        assert!(worker.pending.contains(transmission_id));
+    }
+
     #[ignore]
     #[tokio::test]
     async fn test_process_solution_ok() {
@@ -608,7 +1606,7 @@ mod tests {
         let transmission_id = TransmissionID::Solution(puzzle);
         let worker_ = worker.clone();
         let peer_ip = SocketAddr::from(([127, 0, 0, 1], 1234));
-        let _ = worker_.send_transmission_request(peer_ip, transmission_id).await;
+        let _ = worker_.send_transmission_request(peer_ip, transmission_id, worker_.id()).await;
         assert!(worker.pending.contains(transmission_id));
         let result = worker
             .process_unconfirmed_solution(
@@ -647,7 +1645,7 @@ mod tests {
         let transmission_id = TransmissionID::Solution(puzzle);
         let worker_ = worker.clone();
         let peer_ip = SocketAddr::from(([127, 0, 0, 1], 1234));
-        let _ = worker_.send_transmission_request(peer_ip, transmission_id).await;
+        let _ = worker_.send_transmission_request(peer_ip, transmission_id, worker_.id()).await;
         assert!(worker.pending.contains(transmission_id));
         let result = worker
             .process_unconfirmed_solution(
@@ -685,7 +1683,7 @@ mod tests {
         let transmission_id = TransmissionID::Transaction(transaction_id);
         let worker_ = worker.clone();
         let peer_ip = SocketAddr::from(([127, 0, 0, 1], 1234));
-        let _ = worker_.send_transmission_request(peer_ip, transmission_id).await;
+        let _ = worker_.send_transmission_request(peer_ip, transmission_id, worker_.id()).await;
         assert!(worker.pending.contains(transmission_id));
         let result = worker
             .process_unconfirmed_transaction(
@@ -698,6 +1696,341 @@ mod tests {
         assert!(worker.ready.contains(transmission_id));
     }
 
+    #[tokio::test]
+    async fn test_local_gossip_is_coalesced() {
+        let mut rng = &mut TestRng::default();
+        // Sample a committee.
+        let committee = snarkvm::ledger::committee::test_helpers::sample_committee(rng);
+        // Setup the mock gateway and ledger, recording the number of advertised transmission IDs per broadcast.
+        let advertised = Arc::new(Mutex::new(Vec::new()));
+        let advertised_ = advertised.clone();
+        let mut gateway = MockGateway::default();
+        gateway.expect_broadcast().returning(move |event| match event {
+            Event::WorkerPing(ping) => advertised_.lock().push(ping.transmission_ids.len()),
+            _ => panic!("Unexpected broadcast"),
+        });
+        let mut mock_ledger = MockLedger::default();
+        mock_ledger.expect_current_committee().returning(move || Ok(committee.clone()));
+        mock_ledger.expect_contains_transmission().returning(|_| Ok(false));
+        mock_ledger.expect_check_transaction_basic().returning(|_, _| Ok(()));
+        let ledger: Arc<dyn LedgerService<CurrentNetwork>> = Arc::new(mock_ledger);
+        // Initialize the storage.
+        let storage = Storage::<CurrentNetwork>::new(ledger.clone(), Arc::new(BFTMemoryService::new()), 1);
+
+        // Create the Worker, with local gossip coalescing enabled.
+        let worker = Worker::new(0, Arc::new(gateway), storage, ledger, Default::default()).unwrap();
+        assert!(worker.set_local_gossip_interval(Some(Duration::ZERO)).is_err());
+        worker.set_local_gossip_interval(Some(Duration::from_secs(60))).unwrap();
+
+        // Submit a burst of transactions, spanning more than a single worker ping.
+        let num_transactions = MAX_TRANSMISSIONS_PER_WORKER_PING + 5;
+        for _ in 0..num_transactions {
+            let transaction_id: <CurrentNetwork as Network>::TransactionID =
+                Field::<CurrentNetwork>::rand(&mut rng).into();
+            let transaction = Data::Buffer(Bytes::from((0..512).map(|_| rng.gen::<u8>()).collect::<Vec<_>>()));
+            worker.process_unconfirmed_transaction(transaction_id, transaction).await.unwrap();
+        }
+        // Ensure a full worker ping was advertised as soon as it was queued, and the rest is still queued.
+        assert_eq!(*advertised.lock(), vec![MAX_TRANSMISSIONS_PER_WORKER_PING]);
+        assert_eq!(worker.local_gossip.lock().len(), 5);
+
+        // Ensure the remaining transmission IDs are advertised together.
+        worker.flush_local_gossip();
+        assert_eq!(*advertised.lock(), vec![MAX_TRANSMISSIONS_PER_WORKER_PING, 5]);
+        assert!(worker.local_gossip.lock().is_empty());
+        // Ensure nothing is advertised when there is nothing queued.
+        worker.flush_local_gossip();
+        assert_eq!(advertised.lock().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_process_transaction_with_unknown_program() {
+        let rng = &mut TestRng::default();
+        // Sample a committee.
+        let committee = snarkvm::ledger::committee::test_helpers::sample_committee(rng);
+        // Setup the mock gateway and ledger, where the programs are unknown until they are deployed.
+        let deployed = Arc::new(AtomicBool::new(false));
+        let deployed_ = deployed.clone();
+        let gateway = MockGateway::default();
+        let mut mock_ledger = MockLedger::default();
+        mock_ledger.expect_current_committee().returning(move || Ok(committee.clone()));
+        mock_ledger.expect_contains_transmission().returning(|_| Ok(false));
+        mock_ledger.expect_contains_program().returning(move |_| Ok(deployed_.load(Ordering::SeqCst)));
+        mock_ledger.expect_check_transaction_basic().returning(|_, _| Ok(()));
+        let ledger: Arc<dyn LedgerService<CurrentNetwork>> = Arc::new(mock_ledger);
+        // Initialize the storage.
+        let storage = Storage::<CurrentNetwork>::new(ledger.clone(), Arc::new(BFTMemoryService::new()), 1);
+
+        // Create the Worker.
+        let worker = Worker::new(0, Arc::new(gateway), storage, ledger, Default::default()).unwrap();
+        assert_eq!(worker.unknown_program_policy(), None);
+        let transaction = snarkvm::ledger::ledger_test_helpers::sample_fee_public_transaction(rng);
+        let transaction_id = transaction.id();
+        let transmission_id = TransmissionID::Transaction(transaction_id);
+
+        // Ensure the transaction is rejected early, with the unknown program as the reason.
+        worker.set_unknown_program_policy(Some(UnknownProgramPolicy::Reject));
+        let error = worker
+            .process_unconfirmed_transaction(transaction_id, Data::Object(transaction.clone()))
+            .await
+            .unwrap_err();
+        assert!(error.to_string().contains("references an unknown program"));
+        assert!(!worker.ready.contains(transmission_id));
+
+        // Ensure the transactions that fail the checks independent of their programs are not deferred.
+        worker.set_unknown_program_policy(Some(UnknownProgramPolicy::Defer));
+        let mismatched_id: <CurrentNetwork as Network>::TransactionID = Field::<CurrentNetwork>::rand(rng).into();
+        let result = worker.process_unconfirmed_transaction(mismatched_id, Data::Object(transaction.clone())).await;
+        assert!(result.unwrap_err().to_string().contains("does not match its ID"));
+        let garbage = Data::Buffer(Bytes::from((0..512).map(|_| rng.gen::<u8>()).collect::<Vec<_>>()));
+        assert!(worker.process_unconfirmed_transaction(mismatched_id, garbage).await.is_err());
+        assert_eq!(worker.num_deferred_transactions(), 0);
+
+        // Ensure the transaction is deferred, and admitted once its programs are deployed.
+        let result = worker.process_unconfirmed_transaction(transaction_id, Data::Object(transaction)).await;
+        assert!(result.unwrap().is_none());
+        assert_eq!(worker.num_deferred_transactions(), 1);
+        assert!(!worker.ready.contains(transmission_id));
+        // Ensure the transaction is deferred again, while its programs are still unknown.
+        worker.retry_deferred_transactions().await;
+        assert_eq!(worker.num_deferred_transactions(), 1);
+        deployed.store(true, Ordering::SeqCst);
+        worker.retry_deferred_transactions().await;
+        assert_eq!(worker.num_deferred_transactions(), 0);
+        assert!(worker.ready.contains(transmission_id));
+    }
+
+    #[tokio::test]
+    async fn test_process_transaction_derives_priority_fee() {
+        let rng = &mut TestRng::default();
+        // Sample a committee.
+        let committee = snarkvm::ledger::committee::test_helpers::sample_committee(rng);
+        // Setup the mock gateway and ledger.
+        let gateway = MockGateway::default();
+        let mut mock_ledger = MockLedger::default();
+        mock_ledger.expect_current_committee().returning(move || Ok(committee.clone()));
+        mock_ledger.expect_contains_transmission().returning(|_| Ok(false));
+        mock_ledger.expect_check_transaction_basic().returning(|_, _| Ok(()));
+        let ledger: Arc<dyn LedgerService<CurrentNetwork>> = Arc::new(mock_ledger);
+        // Initialize the storage.
+        let storage = Storage::<CurrentNetwork>::new(ledger.clone(), Arc::new(BFTMemoryService::new()), 1);
+
+        // Create the Worker.
+        let worker = Worker::new(0, Arc::new(gateway), storage, ledger, Default::default()).unwrap();
+        let transaction = snarkvm::ledger::ledger_test_helpers::sample_fee_public_transaction(rng);
+        let transaction_id = transaction.id();
+        let priority_fee = transaction_priority_fee(&transaction);
+
+        // Ensure the priority fee is derived from the transaction on admission.
+        let result = worker.process_unconfirmed_transaction(transaction_id, Data::Object(transaction)).await;
+        assert!(result.is_ok());
+        assert_eq!(worker.ready.get_priority_fee(transaction_id), Some(priority_fee));
+    }
+
+    #[tokio::test]
+    async fn test_process_transaction_with_sender_filter() {
+        let rng = &mut TestRng::default();
+        // Sample a committee.
+        let committee = snarkvm::ledger::committee::test_helpers::sample_committee(rng);
+        // Setup the mock gateway and ledger.
+        let gateway = MockGateway::default();
+        let mut mock_ledger = MockLedger::default();
+        mock_ledger.expect_current_committee().returning(move || Ok(committee.clone()));
+        mock_ledger.expect_contains_transmission().returning(|_| Ok(false));
+        mock_ledger.expect_check_transaction_basic().returning(|_, _| Ok(()));
+        let ledger: Arc<dyn LedgerService<CurrentNetwork>> = Arc::new(mock_ledger);
+        // Initialize the storage.
+        let storage = Storage::<CurrentNetwork>::new(ledger.clone(), Arc::new(BFTMemoryService::new()), 1);
+
+        // Create the Worker.
+        let worker = Worker::new(0, Arc::new(gateway), storage, ledger, Default::default()).unwrap();
+        assert!(worker.sender_filter().is_none());
+        let transaction = snarkvm::ledger::ledger_test_helpers::sample_fee_public_transaction(rng);
+        let transaction_id = transaction.id();
+        let transmission_id = TransmissionID::Transaction(transaction_id);
+        let sender = transaction_sender(&transaction).unwrap();
+        let other = Address::new(rng.gen());
+
+        // Ensure the transaction of a denied sender is rejected, and not queued for gossip.
+        worker.set_sender_filter(Some(SenderFilter::default().with_denylist([sender])));
+        let error = worker
+            .process_unconfirmed_transaction(transaction_id, Data::Object(transaction.clone()))
+            .await
+            .unwrap_err();
+        assert!(error.to_string().contains("rejected by the sender filter"));
+        assert!(!worker.ready.contains(transmission_id));
+
+        // Ensure the transaction of a sender outside of the allowlist is rejected, in allowlist-only mode.
+        worker.set_sender_filter(Some(SenderFilter::default().with_allowlist([other])));
+        let result = worker.process_unconfirmed_transaction(transaction_id, Data::Object(transaction.clone())).await;
+        assert!(result.is_err());
+        assert!(!worker.ready.contains(transmission_id));
+
+        // Ensure the transaction of an allowlisted sender is admitted.
+        worker.set_sender_filter(Some(SenderFilter::default().with_allowlist([other, sender])));
+        let result = worker.process_unconfirmed_transaction(transaction_id, Data::Object(transaction.clone())).await;
+        assert!(result.is_ok());
+        assert!(worker.ready.contains(transmission_id));
+
+        // Ensure the transaction is admitted by default.
+        let transaction = snarkvm::ledger::ledger_test_helpers::sample_fee_public_transaction(rng);
+        let transaction_id = transaction.id();
+        worker.set_sender_filter(None);
+        let result = worker.process_unconfirmed_transaction(transaction_id, Data::Object(transaction)).await;
+        assert!(result.is_ok());
+        assert!(worker.ready.contains(TransmissionID::Transaction(transaction_id)));
+    }
+
+    #[tokio::test]
+    async fn test_process_transaction_with_sender_rate_limit() {
+        let rng = &mut TestRng::default();
+        // Sample a committee.
+        let committee = snarkvm::ledger::committee::test_helpers::sample_committee(rng);
+        // Setup the mock gateway and ledger.
+        let gateway = MockGateway::default();
+        let mut mock_ledger = MockLedger::default();
+        mock_ledger.expect_current_committee().returning(move || Ok(committee.clone()));
+        mock_ledger.expect_contains_transmission().returning(|_| Ok(false));
+        mock_ledger.expect_check_transaction_basic().returning(|_, _| Ok(()));
+        let ledger: Arc<dyn LedgerService<CurrentNetwork>> = Arc::new(mock_ledger);
+        // Initialize the storage.
+        let storage = Storage::<CurrentNetwork>::new(ledger.clone(), Arc::new(BFTMemoryService::new()), 1);
+
+        // Create the Worker.
+        let worker = Worker::new(0, Arc::new(gateway), storage, ledger, Default::default()).unwrap();
+        assert!(worker.sender_rate_limit().is_none());
+        let sender_rate_limit = Arc::new(SenderRateLimit::new(2, 60).unwrap());
+        worker.set_sender_rate_limit(Some(sender_rate_limit.clone()));
+        let transaction = snarkvm::ledger::ledger_test_helpers::sample_fee_public_transaction(rng);
+        let transaction_id = transaction.id();
+        let transmission_id = TransmissionID::Transaction(transaction_id);
+        let transmission = Transmission::Transaction(Data::Object(transaction.clone()));
+        let sender = transaction_sender(&transaction).unwrap();
+
+        // Gossip the transaction from different peers, and ensure its relays count against the sender once.
+        for i in 1..=3 {
+            let peer_ip = SocketAddr::from(([127, 0, 0, i], 1234));
+            worker.process_forwarded_transmission(peer_ip, transmission_id, transmission.clone(), Some(1)).await;
+            assert!(worker.ready.contains(transmission_id));
+            // Drain the transaction, so that it may be relayed again.
+            worker.ready.drain(1);
+        }
+
+        // Flood the distinct transactions of the sender (e.g. through the other workers), and ensure
+        // the sender is throttled, regardless of the relaying peer.
+        assert!(sender_rate_limit.check(sender, Field::<CurrentNetwork>::from_u64(1).into()));
+        assert!(!sender_rate_limit.check(sender, Field::<CurrentNetwork>::from_u64(2).into()));
+        let peer_ip = SocketAddr::from(([127, 0, 0, 4], 1234));
+        worker.process_forwarded_transmission(peer_ip, transmission_id, transmission.clone(), Some(1)).await;
+        assert!(!worker.ready.contains(transmission_id));
+        // Ensure the transactions of the batches of peers are not rate limited.
+        worker.process_transmission_from_peer(peer_ip, transmission_id, transmission).await;
+        assert!(worker.ready.contains(transmission_id));
+        worker.ready.drain(1);
+        // Ensure the sender is throttled for the unconfirmed transactions as well.
+        let error = worker
+            .process_unconfirmed_transaction(transaction_id, Data::Object(transaction.clone()))
+            .await
+            .unwrap_err();
+        assert!(error.to_string().contains("throttled"));
+        assert!(!worker.ready.contains(transmission_id));
+
+        // Ensure the transaction is admitted, once the rate limit is removed.
+        worker.set_sender_rate_limit(None);
+        let result = worker.process_unconfirmed_transaction(transaction_id, Data::Object(transaction)).await;
+        assert!(result.is_ok());
+        assert!(worker.ready.contains(transmission_id));
+    }
+
+    #[tokio::test]
+    async fn test_process_transaction_with_receipt() {
+        let mut rng = &mut TestRng::default();
+        // Sample a committee.
+        let committee = snarkvm::ledger::committee::test_helpers::sample_committee(rng);
+        // Setup the mock gateway and ledger.
+        let gateway = MockGateway::default();
+        let mut mock_ledger = MockLedger::default();
+        mock_ledger.expect_current_committee().returning(move || Ok(committee.clone()));
+        mock_ledger.expect_contains_transmission().returning(|_| Ok(false));
+        mock_ledger.expect_check_transaction_basic().returning(|_, _| Ok(()));
+        let ledger: Arc<dyn LedgerService<CurrentNetwork>> = Arc::new(mock_ledger);
+        // Initialize the storage.
+        let storage = Storage::<CurrentNetwork>::new(ledger.clone(), Arc::new(BFTMemoryService::new()), 1);
+
+        // Create the Worker, with admission receipts enabled.
+        let worker = Worker::new(0, Arc::new(gateway), storage, ledger, Default::default()).unwrap();
+        let account = Account::<CurrentNetwork>::new(rng).unwrap();
+        assert!(!worker.is_receipts_enabled());
+        worker.set_receipt_signer(Some(account.clone()));
+        assert!(worker.is_receipts_enabled());
+
+        // Submit a transaction, and retrieve the receipt.
+        let transaction_id: <CurrentNetwork as Network>::TransactionID = Field::<CurrentNetwork>::rand(&mut rng).into();
+        let transaction = Data::Buffer(Bytes::from((0..512).map(|_| rng.gen::<u8>()).collect::<Vec<_>>()));
+        let receipt = worker.process_unconfirmed_transaction(transaction_id, transaction).await.unwrap().unwrap();
+        assert_eq!(receipt.transmission_id(), TransmissionID::Transaction(transaction_id));
+        assert_eq!(receipt.round(), worker.storage.current_round());
+
+        // Ensure the receipt is signed by the validator.
+        assert!(receipt.verify(&account.address()));
+        assert!(!receipt.verify(&Account::<CurrentNetwork>::new(rng).unwrap().address()));
+
+        // Ensure no receipt is returned once receipts are disabled.
+        worker.set_receipt_signer(None);
+        let transaction_id: <CurrentNetwork as Network>::TransactionID = Field::<CurrentNetwork>::rand(&mut rng).into();
+        let transaction = Data::Buffer(Bytes::from((0..512).map(|_| rng.gen::<u8>()).collect::<Vec<_>>()));
+        assert!(worker.process_unconfirmed_transaction(transaction_id, transaction).await.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn test_process_transaction_retry_after_transient_rejection() {
+        let mut rng = &mut TestRng::default();
+        // Sample a committee.
+        let committee = snarkvm::ledger::committee::test_helpers::sample_committee(rng);
+        // Setup the mock gateway and ledger, which ensures each transaction is validated once.
+        let gateway = MockGateway::default();
+        let mut mock_ledger = MockLedger::default();
+        mock_ledger.expect_current_committee().returning(move || Ok(committee.clone()));
+        mock_ledger.expect_contains_transmission().returning(|_| Ok(false));
+        mock_ledger.expect_check_transaction_basic().times(4).returning(|_, _| Ok(()));
+        let ledger: Arc<dyn LedgerService<CurrentNetwork>> = Arc::new(mock_ledger);
+        // Initialize the storage.
+        let storage = Storage::<CurrentNetwork>::new(ledger.clone(), Arc::new(BFTMemoryService::new()), 1);
+
+        // Create the Worker, with a ready queue (and staging area) of one transmission, and retention enabled.
+        let worker = Worker::new(0, Arc::new(gateway), storage, ledger, Default::default()).unwrap();
+        worker.set_admission_queue(Some(AdmissionQueue::new(AdmissionOrder::Fifo, 1).unwrap()));
+        assert!(worker.set_transient_rejection_window(Some(Duration::ZERO)).is_err());
+        worker.set_transient_rejection_window(Some(Duration::from_secs(60))).unwrap();
+
+        // Fill the ready queue and the staging area, and ensure the next transaction is transiently rejected.
+        let mut sample_transaction = || {
+            let transaction_id: <CurrentNetwork as Network>::TransactionID =
+                Field::<CurrentNetwork>::rand(&mut rng).into();
+            let transaction = Data::Buffer(Bytes::from((0..512).map(|_| rng.gen::<u8>()).collect::<Vec<_>>()));
+            (transaction_id, transaction)
+        };
+        let [(id_a, transaction_a), (id_b, transaction_b), (id_c, transaction_c)] =
+            [(); 3].map(|_| sample_transaction());
+        worker.process_unconfirmed_transaction(id_a, transaction_a).await.unwrap();
+        worker.process_unconfirmed_transaction(id_b, transaction_b).await.unwrap();
+        let error = worker.process_unconfirmed_transaction(id_c, transaction_c).await.unwrap_err();
+        assert!(error.to_string().contains("the ready queue is full"));
+        assert_eq!(worker.transient_rejections().unwrap().len(), 1);
+
+        // Ensure a retry of the same ID with different bytes is validated again, and replaces the rejection.
+        let (_, forged_c) = sample_transaction();
+        let error = worker.process_unconfirmed_transaction(id_c, forged_c.clone()).await.unwrap_err();
+        assert!(error.to_string().contains("the ready queue is full"));
+        assert_eq!(worker.transient_rejections().unwrap().len(), 1);
+
+        // Free a slot, and ensure the retry is recognized and admitted, without being validated again.
+        assert_eq!(worker.drain(1).count(), 1);
+        assert!(worker.process_unconfirmed_transaction(id_c, forged_c).await.is_ok());
+        assert!(worker.contains_transmission(TransmissionID::Transaction(id_c)));
+        assert!(worker.transient_rejections().unwrap().is_empty());
+    }
+
     #[tokio::test]
     async fn test_process_transaction_nok() {
         let mut rng = &mut TestRng::default();
@@ -723,7 +2056,7 @@ mod tests {
         let transmission_id = TransmissionID::Transaction(transaction_id);
         let worker_ = worker.clone();
         let peer_ip = SocketAddr::from(([127, 0, 0, 1], 1234));
-        let _ = worker_.send_transmission_request(peer_ip, transmission_id).await;
+        let _ = worker_.send_transmission_request(peer_ip, transmission_id, worker_.id()).await;
         assert!(worker.pending.contains(transmission_id));
         let result = worker
             .process_unconfirmed_transaction(
@@ -735,6 +2068,203 @@ mod tests {
         assert!(!worker.pending.contains(transmission_id));
         assert!(!worker.ready.contains(transmission_id));
     }
+
+    /// A scorer that returns a fixed verdict after a delay, and counts the number of calls.
+    struct SampleScorer {
+        verdict: bool,
+        delay: Duration,
+        num_calls: AtomicUsize,
+    }
+
+    #[async_trait]
+    impl AdmissionScorer<CurrentNetwork> for SampleScorer {
+        async fn score(
+            &self,
+            _transmission_id: TransmissionID<CurrentNetwork>,
+            _transmission: Transmission<CurrentNetwork>,
+        ) -> Result<bool> {
+            self.num_calls.fetch_add(1, Ordering::SeqCst);
+            tokio::time::sleep(self.delay).await;
+            Ok(self.verdict)
+        }
+    }
+
+    #[tokio::test]
+    async fn test_process_transaction_with_admission_hook() {
+        let mut rng = &mut TestRng::default();
+        // Sample a committee.
+        let committee = snarkvm::ledger::committee::test_helpers::sample_committee(rng);
+        // Setup the mock gateway and ledger.
+        let gateway = MockGateway::default();
+        let mut mock_ledger = MockLedger::default();
+        mock_ledger.expect_current_committee().returning(move || Ok(committee.clone()));
+        mock_ledger.expect_contains_transmission().returning(|_| Ok(false));
+        mock_ledger.expect_check_transaction_basic().returning(|_, _| Ok(()));
+        let ledger: Arc<dyn LedgerService<CurrentNetwork>> = Arc::new(mock_ledger);
+        // Initialize the storage.
+        let storage = Storage::<CurrentNetwork>::new(ledger.clone(), Arc::new(BFTMemoryService::new()), 1);
+        // Create the Worker.
+        let worker = Worker::new(0, Arc::new(gateway), storage, ledger, Default::default()).unwrap();
+
+        // Returns a sample unconfirmed transaction.
+        let mut sample_transaction = || {
+            let transaction_id: <CurrentNetwork as Network>::TransactionID =
+                Field::<CurrentNetwork>::rand(&mut rng).into();
+            let transaction = Data::Buffer(Bytes::from((0..512).map(|_| rng.gen::<u8>()).collect::<Vec<_>>()));
+            (transaction_id, transaction)
+        };
+        let timeout = Duration::from_millis(100);
+
+        // Ensure a transaction rejected by the scorer is not admitted.
+        let scorer = Arc::new(SampleScorer { verdict: false, delay: Duration::ZERO, num_calls: Default::default() });
+        worker.set_admission_hook(Some(AdmissionHook::new(scorer.clone(), timeout)));
+        let (transaction_id, transaction) = sample_transaction();
+        assert!(worker.process_unconfirmed_transaction(transaction_id, transaction.clone()).await.is_err());
+        assert!(!worker.ready.contains(transaction_id));
+        // Ensure the verdict is cached, and the scorer is not consulted again for the same transaction.
+        let admission_hook = worker.admission_hook().unwrap();
+        assert_eq!(admission_hook.cached_verdict(TransmissionID::Transaction(transaction_id)), Some(false));
+        assert!(worker.process_unconfirmed_transaction(transaction_id, transaction).await.is_err());
+        assert_eq!(scorer.num_calls.load(Ordering::SeqCst), 1);
+
+        // Ensure a transaction accepted by the scorer is admitted.
+        let scorer = Arc::new(SampleScorer { verdict: true, delay: Duration::ZERO, num_calls: Default::default() });
+        worker.set_admission_hook(Some(AdmissionHook::new(scorer, timeout)));
+        let (transaction_id, transaction) = sample_transaction();
+        assert!(worker.process_unconfirmed_transaction(transaction_id, transaction).await.is_ok());
+        assert!(worker.ready.contains(transaction_id));
+
+        // Ensure a slow scorer honors the timeout, and a fail-closed hook rejects the transaction.
+        let slow_scorer =
+            Arc::new(SampleScorer { verdict: true, delay: Duration::from_secs(10), num_calls: Default::default() });
+        worker.set_admission_hook(Some(AdmissionHook::new(slow_scorer.clone(), timeout).with_fail_open(false)));
+        let (transaction_id, transaction) = sample_transaction();
+        let start = std::time::Instant::now();
+        assert!(worker.process_unconfirmed_transaction(transaction_id, transaction).await.is_err());
+        assert!(start.elapsed() < Duration::from_secs(1));
+        assert!(!worker.ready.contains(transaction_id));
+        // Ensure the timeout is not cached.
+        assert!(worker.admission_hook().unwrap().cached_verdict(TransmissionID::Transaction(transaction_id)).is_none());
+
+        // Ensure a slow scorer honors the timeout, and a fail-open hook admits the transaction.
+        worker.set_admission_hook(Some(AdmissionHook::new(slow_scorer, timeout)));
+        let (transaction_id, transaction) = sample_transaction();
+        let start = std::time::Instant::now();
+        assert!(worker.process_unconfirmed_transaction(transaction_id, transaction).await.is_ok());
+        assert!(start.elapsed() < Duration::from_secs(1));
+        assert!(worker.ready.contains(transaction_id));
+    }
+
+    #[tokio::test]
+    async fn test_replace_transaction() {
+        let mut rng = &mut TestRng::default();
+        // Sample a committee.
+        let committee = snarkvm::ledger::committee::test_helpers::sample_committee(rng);
+        // Setup the mock gateway and ledger.
+        let gateway = MockGateway::default();
+        let mut mock_ledger = MockLedger::default();
+        mock_ledger.expect_current_committee().returning(move || Ok(committee.clone()));
+        mock_ledger.expect_contains_transmission().returning(|_| Ok(false));
+        mock_ledger.expect_check_transaction_basic().returning(|_, _| Ok(()));
+        let ledger: Arc<dyn LedgerService<CurrentNetwork>> = Arc::new(mock_ledger);
+        // Initialize the storage.
+        let storage = Storage::<CurrentNetwork>::new(ledger.clone(), Arc::new(BFTMemoryService::new()), 1);
+        // Create the Worker.
+        let worker = Worker::new(0, Arc::new(gateway), storage, ledger, Default::default()).unwrap();
+
+        // Returns a sample unconfirmed transaction.
+        let mut sample_transaction = || {
+            let transaction_id: <CurrentNetwork as Network>::TransactionID =
+                Field::<CurrentNetwork>::rand(&mut rng).into();
+            let transaction = Data::Buffer(Bytes::from((0..512).map(|_| rng.gen::<u8>()).collect::<Vec<_>>()));
+            (transaction_id, transaction)
+        };
+        let submitter = Account::<CurrentNetwork>::new(&mut rand::thread_rng()).unwrap().address();
+        let other = Account::<CurrentNetwork>::new(&mut rand::thread_rng()).unwrap().address();
+
+        // Queue a transaction, with a priority fee.
+        let (queued_id, queued) = sample_transaction();
+        worker.process_unconfirmed_transaction(queued_id, queued).await.unwrap();
+        assert!(worker.ready.set_submitter(queued_id, submitter));
+        assert!(worker.set_priority_fee(queued_id, 100));
+
+        // Ensure the transaction is not replaced while replacement is disabled.
+        let (replacement_id, replacement) = sample_transaction();
+        let result = worker.replace_transaction(queued_id, replacement_id, replacement.clone(), submitter, 200).await;
+        assert!(result.is_err());
+
+        // Enable replacement, with a 10% fee bump, and at most 2 replacements per minute.
+        worker.set_replacement_policy(Some(ReplacementPolicy::new(10).with_rate_limit(2, 60)));
+        // Ensure the transaction is not replaced by another submitter.
+        let result = worker.replace_transaction(queued_id, replacement_id, replacement.clone(), other, 200).await;
+        assert!(result.is_err());
+        // Ensure the transaction is not replaced with an insufficient fee bump.
+        let result = worker.replace_transaction(queued_id, replacement_id, replacement.clone(), submitter, 109).await;
+        assert!(result.is_err());
+        assert!(worker.ready.contains(queued_id));
+        assert!(!worker.ready.contains(replacement_id));
+
+        // Replace the transaction, and ensure the queued transaction is evicted.
+        worker.replace_transaction(queued_id, replacement_id, replacement, submitter, 110).await.unwrap();
+        assert!(!worker.ready.contains(queued_id));
+        assert!(worker.ready.contains(replacement_id));
+        assert_eq!(worker.ready.get_submitter(replacement_id), Some(submitter));
+        assert_eq!(worker.ready.get_priority_fee(replacement_id), Some(110));
+        assert_eq!(worker.num_transmissions(), 1);
+
+        // Ensure the evicted transaction cannot be replaced again.
+        let (next_id, next) = sample_transaction();
+        assert!(worker.replace_transaction(queued_id, next_id, next.clone(), submitter, 500).await.is_err());
+
+        // Ensure the submitter is rate limited, after the maximum number of replacements.
+        worker.replace_transaction(replacement_id, next_id, next, submitter, 200).await.unwrap();
+        let (last_id, last) = sample_transaction();
+        assert!(worker.replace_transaction(next_id, last_id, last, submitter, 1000).await.is_err());
+        assert!(worker.ready.contains(next_id));
+        assert_eq!(worker.num_transmissions(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_process_replacement_transaction_derives_submitter() {
+        let rng = &mut TestRng::default();
+        // Sample a committee.
+        let committee = snarkvm::ledger::committee::test_helpers::sample_committee(rng);
+        // Setup the mock gateway and ledger.
+        let gateway = MockGateway::default();
+        let mut mock_ledger = MockLedger::default();
+        mock_ledger.expect_current_committee().returning(move || Ok(committee.clone()));
+        mock_ledger.expect_contains_transmission().returning(|_| Ok(false));
+        mock_ledger.expect_check_transaction_basic().returning(|_, _| Ok(()));
+        let ledger: Arc<dyn LedgerService<CurrentNetwork>> = Arc::new(mock_ledger);
+        // Initialize the storage.
+        let storage = Storage::<CurrentNetwork>::new(ledger.clone(), Arc::new(BFTMemoryService::new()), 1);
+
+        // Create the Worker, with replacement enabled.
+        let worker = Worker::new(0, Arc::new(gateway), storage, ledger, Default::default()).unwrap();
+        worker.set_replacement_policy(Some(ReplacementPolicy::new(10)));
+        let transaction = snarkvm::ledger::ledger_test_helpers::sample_fee_public_transaction(rng);
+        let transaction_id = transaction.id();
+        let sender = transaction_sender(&transaction).unwrap();
+
+        // Queue a transaction, from another submitter.
+        let queued_id: <CurrentNetwork as Network>::TransactionID = Field::<CurrentNetwork>::rand(rng).into();
+        let queued = Data::Buffer(Bytes::from((0..512).map(|_| rng.gen::<u8>()).collect::<Vec<_>>()));
+        worker.process_unconfirmed_transaction(queued_id, queued).await.unwrap();
+        let other = Address::new(rng.gen());
+        assert!(worker.ready.set_submitter(queued_id, other));
+
+        // Ensure the submitter is derived from the transaction, rather than trusted from the caller.
+        let result = worker.process_replacement_transaction(queued_id, transaction_id, Data::Object(transaction)).await;
+        let error = result.unwrap_err().to_string();
+        assert!(error.contains(&format!("is not queued from '{sender}'")), "{error}");
+        assert!(worker.ready.contains(queued_id));
+        assert!(!worker.ready.contains(transaction_id));
+
+        // Ensure a transaction that cannot be deserialized is not a replacement.
+        let garbage = Data::Buffer(Bytes::from((0..512).map(|_| rng.gen::<u8>()).collect::<Vec<_>>()));
+        assert!(worker.process_replacement_transaction(queued_id, transaction_id, garbage).await.is_err());
+        assert!(worker.ready.contains(queued_id));
+    }
 }
 
 #[cfg(test)]
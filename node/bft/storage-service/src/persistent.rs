@@ -17,7 +17,6 @@ use snarkvm::{
     ledger::{
         narwhal::{BatchHeader, Transmission, TransmissionID},
         store::{
-            cow_to_cloned,
             helpers::{
                 rocksdb::{
                     internal::{self, BFTMap, Database, MapID},
@@ -28,39 +27,450 @@ use snarkvm::{
             },
         },
     },
-    prelude::{bail, Field, Network, Result},
+    prelude::{bail, ensure, Field, FromBytes, Network, Result, ToBytes},
 };
 
 use aleo_std::StorageMode;
-use indexmap::{indexset, IndexSet};
-use std::{borrow::Cow, collections::HashMap};
-use tracing::error;
+use indexmap::{indexset, IndexMap, IndexSet};
+use parking_lot::Mutex;
+use std::{
+    collections::HashMap,
+    io::Write,
+    path::PathBuf,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+    time::{Duration, Instant},
+};
+use tracing::{error, info, warn};
+
+/// The current version of the on-disk format of the BFT store.
+pub const BFT_STORE_VERSION: u16 = 1;
+/// The `(transmission, certificate IDs)` entries of the unversioned BFT store, which do not record their codec.
+type LegacyEntry<N> = (Transmission<N>, IndexSet<Field<N>>);
+/// The maximum number of entries that are buffered in memory while the BFT store is degraded.
+/// Note: Once the buffer is full, the writes of the other entries are synchronous again.
+pub const MAX_BUFFERED_ENTRIES: usize = 1 << 16;
+/// The interval at which the buffered entries are checked for, while the BFT store is degraded.
+const FLUSH_POLL_INTERVAL: Duration = Duration::from_millis(50);
+
+/// The on-disk compression of the entries in the BFT store.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Compression {
+    /// The entries are compressed with zstd, at the given level.
+    Zstd(i32),
+}
+
+impl Compression {
+    /// The default zstd compression level.
+    pub const DEFAULT_ZSTD_LEVEL: i32 = zstd::DEFAULT_COMPRESSION_LEVEL;
+
+    /// Initializes a new zstd compression, ensuring the level is supported.
+    pub fn zstd(level: i32) -> Result<Self> {
+        let levels = zstd::compression_level_range();
+        ensure!(levels.contains(&level), "The zstd compression level must be within {levels:?}");
+        Ok(Self::Zstd(level))
+    }
+}
+
+/// The codec of an entry in the BFT store, which is recorded as the first byte of the entry,
+/// so that the entries are read back regardless of the compression they were written with.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[repr(u8)]
+enum Codec {
+    /// The entry is not compressed.
+    Uncompressed = 0,
+    /// The entry is compressed with zstd.
+    Zstd = 1,
+}
+
+/// The degraded mode of the BFT store, in which the writes are buffered in memory and flushed to disk
+/// asynchronously, while the disk is slow.
+#[derive(Debug)]
+struct WriteBehind<N: Network> {
+    /// The write latency, above which the BFT store degrades.
+    threshold: Duration,
+    /// The flag indicating whether the BFT store is degraded.
+    is_degraded: AtomicBool,
+    /// The buffered entries (or `None` for a removed entry), awaiting the flush to disk.
+    pending: Mutex<IndexMap<TransmissionID<N>, Option<Vec<u8>>>>,
+    /// The delay injected into each write to disk, to simulate a slow disk.
+    #[cfg(test)]
+    injected_delay: parking_lot::RwLock<Duration>,
+}
 
 /// A BFT persistent storage service.
 #[derive(Debug)]
 pub struct BFTPersistentStorage<N: Network> {
-    /// The map of `transmission ID` to `(transmission, certificate IDs)` entries.
-    transmissions: DataMap<TransmissionID<N>, (Transmission<N>, IndexSet<Field<N>>)>,
+    /// The map of `transmission ID` to the encoded `(transmission, certificate IDs)` entries.
+    transmissions: DataMap<TransmissionID<N>, Vec<u8>>,
+    /// The compression of the entries written to the store, if any.
+    compression: Option<Compression>,
+    /// The degraded mode, if it is enabled.
+    write_behind: Option<Arc<WriteBehind<N>>>,
+    /// The storage mode of the store, for opening its map with the value type of an older version.
+    /// Note: This is `None` for a testing store, which is always new.
+    storage_mode: Option<StorageMode>,
 }
 
 impl<N: Network> BFTPersistentStorage<N> {
+    /// The key under which the version of the BFT store is recorded, in the store itself.
+    /// Note: Ratifications are not supported by the protocol, so the key is never used by a stored transmission.
+    const VERSION_KEY: TransmissionID<N> = TransmissionID::Ratification;
+
     /// Initializes a new BFT persistent storage service.
+    /// If the store was created by an older version, it is upgraded to the current format.
     pub fn open(storage_mode: StorageMode) -> Result<Self> {
-        Ok(Self { transmissions: internal::RocksDB::open_map(N::ID, storage_mode, MapID::BFT(BFTMap::Transmissions))? })
+        Self::open_with_compression(storage_mode, None)
+    }
+
+    /// Initializes a new BFT persistent storage service, which writes its entries with the given compression.
+    /// Note: The entries that were written with a different compression (or none) are still read back.
+    pub fn open_with_compression(storage_mode: StorageMode, compression: Option<Compression>) -> Result<Self> {
+        let map_id = MapID::BFT(BFTMap::Transmissions);
+        let transmissions = internal::RocksDB::open_map(N::ID, storage_mode.clone(), map_id)?;
+        let storage = Self { transmissions, compression, write_behind: None, storage_mode: Some(storage_mode) };
+        storage.upgrade()?;
+        if let Some(compression) = compression {
+            info!("Compressing the BFT store entries ({compression:?})");
+        }
+        Ok(storage)
     }
 
     /// Initializes a new BFT persistent storage service.
     #[cfg(any(test, feature = "test"))]
-    pub fn open_testing(temp_dir: std::path::PathBuf, dev: Option<u16>) -> Result<Self> {
-        Ok(Self {
-            transmissions: internal::RocksDB::open_map_testing(temp_dir, dev, MapID::BFT(BFTMap::Transmissions))?,
-        })
+    pub fn open_testing(temp_dir: PathBuf, dev: Option<u16>) -> Result<Self> {
+        let map_id = MapID::BFT(BFTMap::Transmissions);
+        let transmissions = internal::RocksDB::open_map_testing(temp_dir, dev, map_id)?;
+        let storage = Self { transmissions, compression: None, write_behind: None, storage_mode: None };
+        storage.upgrade()?;
+        Ok(storage)
+    }
+
+    /// Returns the compression of the entries written to the store, if any.
+    pub const fn compression(&self) -> Option<Compression> {
+        self.compression
+    }
+
+    /// Enables the degraded mode, in which a write to disk that takes longer than the given threshold degrades
+    /// the store to buffering the writes in memory, and flushing them to disk asynchronously, so that a slow disk
+    /// does not stall consensus. The store reverts to synchronous writes once the disk recovers.
+    ///
+    /// Note: While the store is degraded, the buffered writes are lost if the node crashes.
+    pub fn with_degraded_mode(self, threshold: Duration) -> Result<Self> {
+        ensure!(!threshold.is_zero(), "The write latency threshold of the BFT store must be nonzero");
+        let write_behind = WriteBehind {
+            threshold,
+            is_degraded: Default::default(),
+            pending: Default::default(),
+            #[cfg(test)]
+            injected_delay: Default::default(),
+        };
+        Ok(Self { write_behind: Some(Arc::new(write_behind)), ..self })
+    }
+
+    /// Returns `true` if the store is degraded, i.e. its writes are buffered in memory.
+    pub fn is_degraded(&self) -> bool {
+        self.write_behind.as_ref().is_some_and(|write_behind| write_behind.is_degraded.load(Ordering::SeqCst))
+    }
+
+    /// Returns the number of buffered entries, awaiting the flush to disk.
+    pub fn num_buffered_entries(&self) -> usize {
+        self.write_behind.as_ref().map_or(0, |write_behind| write_behind.pending.lock().len())
+    }
+
+    /// Encodes the given entry, with the given compression.
+    fn encode_entry(
+        (transmission, certificate_ids): &(Transmission<N>, IndexSet<Field<N>>),
+        compression: Option<Compression>,
+    ) -> Result<Vec<u8>> {
+        // Serialize the entry.
+        let mut bytes = transmission.to_bytes_le()?;
+        u32::try_from(certificate_ids.len())?.write_le(&mut bytes)?;
+        for certificate_id in certificate_ids {
+            certificate_id.write_le(&mut bytes)?;
+        }
+        // Compress the entry, prefixed with its codec.
+        match compression {
+            None => Ok([&[Codec::Uncompressed as u8], bytes.as_slice()].concat()),
+            Some(Compression::Zstd(level)) => {
+                let mut encoder = zstd::Encoder::new(vec![Codec::Zstd as u8], level)?;
+                encoder.write_all(&bytes)?;
+                Ok(encoder.finish()?)
+            }
+        }
+    }
+
+    /// Decodes the given entry, according to its codec.
+    fn decode_entry(entry: &[u8]) -> Result<(Transmission<N>, IndexSet<Field<N>>)> {
+        let Some((codec, bytes)) = entry.split_first() else {
+            bail!("The BFT store entry is empty");
+        };
+        // Decompress the entry.
+        let bytes = match *codec {
+            codec if codec == Codec::Uncompressed as u8 => bytes.to_vec(),
+            codec if codec == Codec::Zstd as u8 => zstd::decode_all(bytes)?,
+            codec => bail!("The BFT store entry has an unknown codec ({codec})"),
+        };
+        // Deserialize the entry.
+        let mut reader = bytes.as_slice();
+        let transmission = Transmission::read_le(&mut reader)?;
+        let num_certificate_ids = u32::read_le(&mut reader)?;
+        // Note: The certificate IDs are not preallocated, as their number is untrusted, so that a malformed entry
+        // fails once its bytes run out, instead of allocating for up to `u32::MAX` certificate IDs.
+        let mut certificate_ids = IndexSet::new();
+        for _ in 0..num_certificate_ids {
+            certificate_ids.insert(Field::read_le(&mut reader)?);
+        }
+        ensure!(reader.is_empty(), "The BFT store entry has trailing bytes");
+        Ok((transmission, certificate_ids))
+    }
+
+    /// Returns the buffered entry (or `None` for a removed entry) for the given `transmission ID`, if it is buffered.
+    fn get_buffered_entry(&self, transmission_id: &TransmissionID<N>) -> Option<Option<Vec<u8>>> {
+        self.write_behind.as_ref()?.pending.lock().get(transmission_id).cloned()
+    }
+
+    /// Returns the `(transmission, certificate IDs)` entry for the given `transmission ID`, if it exists.
+    fn get_entry(&self, transmission_id: &TransmissionID<N>) -> Result<Option<(Transmission<N>, IndexSet<Field<N>>)>> {
+        // Note: The version of the store is not a transmission.
+        if *transmission_id == Self::VERSION_KEY {
+            return Ok(None);
+        }
+        // Note: A buffered entry is more recent than the entry on disk.
+        if let Some(entry) = self.get_buffered_entry(transmission_id) {
+            return entry.map(|entry| Self::decode_entry(&entry)).transpose();
+        }
+        match self.transmissions.get_confirmed(transmission_id)? {
+            Some(entry) => Ok(Some(Self::decode_entry(&entry)?)),
+            None => Ok(None),
+        }
+    }
+
+    /// Inserts the given `(transmission, certificate IDs)` entry for the given `transmission ID`.
+    fn insert_entry(
+        &self,
+        transmission_id: TransmissionID<N>,
+        entry: &(Transmission<N>, IndexSet<Field<N>>),
+    ) -> Result<()> {
+        self.write_entry(transmission_id, Some(Self::encode_entry(entry, self.compression)?))
+    }
+
+    /// Removes the entry for the given `transmission ID`.
+    fn remove_entry(&self, transmission_id: TransmissionID<N>) -> Result<()> {
+        self.write_entry(transmission_id, None)
+    }
+
+    /// Writes the given encoded entry (or removes the entry, if `None` is given) for the given `transmission ID`.
+    /// If the store is degraded, the entry is buffered; otherwise, it is written to disk, and the store degrades
+    /// if the write is slow.
+    fn write_entry(&self, transmission_id: TransmissionID<N>, entry: Option<Vec<u8>>) -> Result<()> {
+        // Ensure the version of the store is not overwritten.
+        ensure!(transmission_id != Self::VERSION_KEY, "The transmission ID is reserved for the BFT store version");
+        let Some(write_behind) = &self.write_behind else {
+            return Self::write_to_disk(&self.transmissions, transmission_id, &entry);
+        };
+        {
+            let mut pending = write_behind.pending.lock();
+            // Buffer the entry if the store is degraded, unless the buffer is full.
+            // Note: An entry that is already buffered is always replaced, so that it is not flushed out of order.
+            if write_behind.is_degraded.load(Ordering::SeqCst)
+                && (pending.len() < MAX_BUFFERED_ENTRIES || pending.contains_key(&transmission_id))
+            {
+                pending.insert(transmission_id, entry);
+                return Ok(());
+            }
+        }
+        // Write the entry to disk, and degrade the store if the write is slow.
+        let start = Instant::now();
+        #[cfg(test)]
+        std::thread::sleep(*write_behind.injected_delay.read());
+        Self::write_to_disk(&self.transmissions, transmission_id, &entry)?;
+        let latency = start.elapsed();
+        if latency > write_behind.threshold {
+            Self::degrade(&self.transmissions, write_behind, latency);
+        }
+        Ok(())
+    }
+
+    /// Writes the given encoded entry (or removes the entry, if `None` is given) to disk.
+    fn write_to_disk(
+        transmissions: &DataMap<TransmissionID<N>, Vec<u8>>,
+        transmission_id: TransmissionID<N>,
+        entry: &Option<Vec<u8>>,
+    ) -> Result<()> {
+        match entry {
+            Some(entry) => transmissions.insert(transmission_id, entry.clone()),
+            None => transmissions.remove(&transmission_id),
+        }
+    }
+
+    /// Degrades the store to buffering its writes in memory, and starts flushing them to disk asynchronously,
+    /// if the store is not degraded yet.
+    fn degrade(
+        transmissions: &DataMap<TransmissionID<N>, Vec<u8>>,
+        write_behind: &Arc<WriteBehind<N>>,
+        latency: Duration,
+    ) {
+        // Note: The flag is set while the lock is held, so that no entry is buffered after the flusher exits.
+        let _pending = write_behind.pending.lock();
+        if write_behind.is_degraded.swap(true, Ordering::SeqCst) {
+            return;
+        }
+        error!(
+            "A write to the BFT store took {}ms (threshold {}ms) - buffering the writes in memory until the disk \
+             recovers, so the writes since are lost if the node crashes",
+            latency.as_millis(),
+            write_behind.threshold.as_millis()
+        );
+        let (transmissions, write_behind_) = (transmissions.clone(), write_behind.clone());
+        let spawned = std::thread::Builder::new()
+            .name("bft-store-flusher".to_string())
+            .spawn(move || Self::flush(transmissions, write_behind_));
+        if let Err(e) = spawned {
+            error!("Failed to start flushing the BFT store - {e}");
+            write_behind.is_degraded.store(false, Ordering::SeqCst);
+        }
+    }
+
+    /// Flushes the buffered entries to disk, in order, until the buffer is empty and the disk has recovered
+    /// (i.e. the latest flushed write was within the threshold), and then reverts to synchronous writes.
+    fn flush(transmissions: DataMap<TransmissionID<N>, Vec<u8>>, write_behind: Arc<WriteBehind<N>>) {
+        let mut has_recovered = false;
+        loop {
+            // Retrieve the next buffered entry.
+            let next = write_behind.pending.lock().first().map(|(id, entry)| (*id, entry.clone()));
+            let Some((transmission_id, entry)) = next else {
+                let pending = write_behind.pending.lock();
+                // If the buffer is empty and the disk has recovered, revert to synchronous writes.
+                if pending.is_empty() && has_recovered {
+                    write_behind.is_degraded.store(false, Ordering::SeqCst);
+                    info!("The BFT store has recovered - writing to disk synchronously again");
+                    return;
+                }
+                drop(pending);
+                std::thread::sleep(FLUSH_POLL_INTERVAL);
+                continue;
+            };
+            // Write the entry to disk.
+            let start = Instant::now();
+            #[cfg(test)]
+            std::thread::sleep(*write_behind.injected_delay.read());
+            match Self::write_to_disk(&transmissions, transmission_id, &entry) {
+                Ok(()) => {
+                    has_recovered = start.elapsed() <= write_behind.threshold;
+                    // Remove the entry from the buffer, unless it was replaced in the meantime.
+                    let mut pending = write_behind.pending.lock();
+                    if pending.get(&transmission_id) == Some(&entry) {
+                        pending.shift_remove(&transmission_id);
+                    }
+                }
+                Err(e) => {
+                    error!("Failed to flush transmission {transmission_id} to the BFT store - {e}");
+                    std::thread::sleep(FLUSH_POLL_INTERVAL);
+                }
+            }
+        }
+    }
+
+    /// Returns the version of the BFT store, as recorded in the store.
+    /// Note: The stores that were created before the format was versioned are version `0`.
+    pub fn version(&self) -> Result<u16> {
+        match self.transmissions.get_confirmed(&Self::VERSION_KEY)? {
+            Some(version) => match <[u8; 2]>::try_from(version.as_slice()) {
+                Ok(version) => Ok(u16::from_le_bytes(version)),
+                Err(_) => bail!("The BFT store version is malformed - run `snarkos clean` to resync"),
+            },
+            None => Ok(0),
+        }
+    }
+
+    /// Records the version of the BFT store, in the store.
+    fn write_version(&self, version: u16) -> Result<()> {
+        self.transmissions.insert(Self::VERSION_KEY, version.to_le_bytes().to_vec())
+    }
+
+    /// Returns `true` if the store holds no transmissions, i.e. it is new (or was cleaned).
+    fn is_empty(&self) -> bool {
+        self.transmissions.keys_confirmed().all(|transmission_id| *transmission_id == Self::VERSION_KEY)
+    }
+
+    /// Upgrades the BFT store to the current format, one version at a time, recording the version in the store
+    /// after each migration. If the store cannot be migrated, a resync is required.
+    pub fn upgrade(&self) -> Result<()> {
+        let mut version = self.version()?;
+        // If the store is new, record the current version, as there is nothing to migrate.
+        if version == 0 && self.is_empty() {
+            return self.write_version(BFT_STORE_VERSION);
+        }
+        // Ensure the store was not created by a newer version.
+        if version > BFT_STORE_VERSION {
+            bail!(
+                "The BFT store is version {version}, but at most version {BFT_STORE_VERSION} is supported - \
+                 upgrade snarkOS, or run `snarkos clean` to resync"
+            )
+        }
+        while version < BFT_STORE_VERSION {
+            self.migrate_from(version)?;
+            version += 1;
+            self.write_version(version)?;
+            info!("Upgraded the BFT store to version {version}");
+        }
+        Ok(())
+    }
+
+    /// Migrates the BFT store from the given version to the next version.
+    fn migrate_from(&self, version: u16) -> Result<()> {
+        match version {
+            // Version 1 records the codec of each entry, so the unversioned entries are read with their former
+            // value type, and rewritten with the codec of the store.
+            0 => {
+                let Some(storage_mode) = &self.storage_mode else {
+                    bail!("Unable to migrate a testing BFT store - run `snarkos clean` to resync")
+                };
+                let map_id = MapID::BFT(BFTMap::Transmissions);
+                let legacy: DataMap<TransmissionID<N>, LegacyEntry<N>> =
+                    internal::RocksDB::open_map(N::ID, storage_mode.clone(), map_id)?;
+                let transmission_ids = self
+                    .transmissions
+                    .keys_confirmed()
+                    .map(|id| *id)
+                    .filter(|id| *id != Self::VERSION_KEY)
+                    .collect::<Vec<_>>();
+                for transmission_id in &transmission_ids {
+                    match legacy.get_confirmed(transmission_id) {
+                        Ok(Some(entry)) => {
+                            let entry = Self::encode_entry(&entry, self.compression)?;
+                            self.transmissions.insert(*transmission_id, entry)?;
+                        }
+                        // Note: The store only holds the transmissions of uncommitted certificates, so an entry
+                        // that cannot be read is removed, and the transmission is refetched as needed.
+                        Ok(None) | Err(_) => {
+                            warn!("Removed the unreadable transmission {transmission_id} from the BFT store");
+                            self.transmissions.remove(transmission_id)?;
+                        }
+                    }
+                }
+                info!("Migrated {} transmissions in the BFT store", transmission_ids.len());
+                Ok(())
+            }
+            _ => bail!("Unable to migrate the BFT store from version {version} - run `snarkos clean` to resync"),
+        }
     }
 }
 
 impl<N: Network> StorageService<N> for BFTPersistentStorage<N> {
     /// Returns `true` if the storage contains the specified `transmission ID`.
     fn contains_transmission(&self, transmission_id: TransmissionID<N>) -> bool {
+        // Note: The version of the store is not a transmission.
+        if transmission_id == Self::VERSION_KEY {
+            return false;
+        }
+        // Check if the transmission ID is buffered.
+        if let Some(entry) = self.get_buffered_entry(&transmission_id) {
+            return entry.is_some();
+        }
         // Check if the transmission ID exists in storage.
         let result = self.transmissions.contains_key_confirmed(&transmission_id);
         // If the result is an error, log the error.
@@ -75,9 +485,8 @@ impl<N: Network> StorageService<N> for BFTPersistentStorage<N> {
     /// If the transmission ID does not exist in storage, `None` is returned.
     fn get_transmission(&self, transmission_id: TransmissionID<N>) -> Option<Transmission<N>> {
         // Get the transmission.
-        match self.transmissions.get_confirmed(&transmission_id) {
-            Ok(Some(Cow::Owned((transmission, _)))) => Some(transmission),
-            Ok(Some(Cow::Borrowed((transmission, _)))) => Some(transmission.clone()),
+        match self.get_entry(&transmission_id) {
+            Ok(Some((transmission, _))) => Some(transmission),
             Ok(None) => None,
             Err(error) => {
                 error!("Failed to get transmission from storage - {error}");
@@ -121,13 +530,12 @@ impl<N: Network> StorageService<N> for BFTPersistentStorage<N> {
         //   - Inserts the certificate ID into the corresponding set for **all** transmissions.
         'outer: for transmission_id in transmission_ids {
             // Retrieve the transmission entry.
-            match self.transmissions.get_confirmed(&transmission_id) {
-                Ok(Some(entry)) => {
-                    let (transmission, mut certificate_ids) = cow_to_cloned!(entry);
+            match self.get_entry(&transmission_id) {
+                Ok(Some((transmission, mut certificate_ids))) => {
                     // Insert the certificate ID into the set.
                     certificate_ids.insert(certificate_id);
                     // Update the transmission entry.
-                    if let Err(e) = self.transmissions.insert(transmission_id, (transmission, certificate_ids)) {
+                    if let Err(e) = self.insert_entry(transmission_id, &(transmission, certificate_ids)) {
                         error!("Failed to insert transmission {transmission_id} into storage - {e}");
                         continue 'outer;
                     }
@@ -141,7 +549,7 @@ impl<N: Network> StorageService<N> for BFTPersistentStorage<N> {
                     // Prepare the set of certificate IDs.
                     let certificate_ids = indexset! { certificate_id };
                     // Insert the transmission and a new set with the certificate ID.
-                    if let Err(e) = self.transmissions.insert(transmission_id, (transmission, certificate_ids)) {
+                    if let Err(e) = self.insert_entry(transmission_id, &(transmission, certificate_ids)) {
                         error!("Failed to insert transmission {transmission_id} into storage - {e}");
                         continue 'outer;
                     }
@@ -161,15 +569,14 @@ impl<N: Network> StorageService<N> for BFTPersistentStorage<N> {
         // If this is the last certificate ID for the transmission ID, remove the transmission.
         'outer: for transmission_id in transmission_ids {
             // Retrieve the transmission entry.
-            match self.transmissions.get_confirmed(transmission_id) {
-                Ok(Some(entry)) => {
-                    let (transmission, mut certificate_ids) = cow_to_cloned!(entry);
+            match self.get_entry(transmission_id) {
+                Ok(Some((transmission, mut certificate_ids))) => {
                     // Insert the certificate ID into the set.
                     certificate_ids.swap_remove(certificate_id);
                     // If there are no more certificate IDs for the transmission ID, remove the transmission.
                     if certificate_ids.is_empty() {
                         // Remove the transmission entry.
-                        if let Err(e) = self.transmissions.remove(transmission_id) {
+                        if let Err(e) = self.remove_entry(*transmission_id) {
                             error!("Failed to remove transmission {transmission_id} (now empty) from storage - {e}");
                             continue 'outer;
                         }
@@ -177,7 +584,7 @@ impl<N: Network> StorageService<N> for BFTPersistentStorage<N> {
                     // Otherwise, update the transmission entry.
                     else {
                         // Update the transmission entry.
-                        if let Err(e) = self.transmissions.insert(*transmission_id, (transmission, certificate_ids)) {
+                        if let Err(e) = self.insert_entry(*transmission_id, &(transmission, certificate_ids)) {
                             error!(
                                 "Failed to remove transmission {transmission_id} for certificate {certificate_id} from storage - {e}"
                             );
@@ -198,6 +605,184 @@ impl<N: Network> StorageService<N> for BFTPersistentStorage<N> {
     #[cfg(any(test, feature = "test"))]
     fn as_hashmap(&self) -> HashMap<TransmissionID<N>, (Transmission<N>, IndexSet<Field<N>>)> {
         use snarkvm::ledger::store::cow_to_copied;
-        self.transmissions.iter_confirmed().map(|(k, v)| (cow_to_copied!(k), cow_to_cloned!(v))).collect()
+        let mut entries: HashMap<_, _> = self
+            .transmissions
+            .iter_confirmed()
+            .filter(|(k, _)| **k != Self::VERSION_KEY)
+            .filter_map(|(k, v)| Some((cow_to_copied!(k), Self::decode_entry(&v).ok()?)))
+            .collect();
+        // Apply the buffered entries.
+        if let Some(write_behind) = &self.write_behind {
+            for (transmission_id, entry) in write_behind.pending.lock().iter() {
+                match entry.as_ref().and_then(|entry| Self::decode_entry(entry).ok()) {
+                    Some(entry) => entries.insert(*transmission_id, entry),
+                    None => entries.remove(transmission_id),
+                };
+            }
+        }
+        entries
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use snarkvm::ledger::narwhal::Data;
+
+    use bytes::Bytes;
+
+    type CurrentNetwork = snarkvm::prelude::Testnet3;
+
+    /// Returns a new, unique storage directory.
+    fn sample_directory() -> PathBuf {
+        let nanos = std::time::SystemTime::now().duration_since(std::time::UNIX_EPOCH).unwrap().as_nanos();
+        std::env::temp_dir().join(format!("bft-store-{}-{nanos}", std::process::id()))
+    }
+
+    #[test]
+    fn test_old_format_store_is_migrated() {
+
+        // Initialize a store of the unversioned format, with a transmission.
+        // Note: This is the only test to open the store of a ledger, as the database is shared by the process.
+        let storage_mode = StorageMode::Custom(sample_directory());
+        let transmission_id = TransmissionID::Transaction(Field::from_u64(1));
+        let transmission = Transmission::Transaction(Data::Buffer(Bytes::from(vec![7u8; 64])));
+        let entry = (transmission, indexset! { Field::from_u64(2) });
+        let map_id = MapID::BFT(BFTMap::Transmissions);
+        let legacy: DataMap<TransmissionID<CurrentNetwork>, LegacyEntry<CurrentNetwork>> =
+            internal::RocksDB::open_map(CurrentNetwork::ID, storage_mode.clone(), map_id).unwrap();
+        legacy.insert(transmission_id, entry.clone()).unwrap();
+
+        // Open the store, and ensure it is upgraded to the current format, with the data intact.
+        let storage = BFTPersistentStorage::<CurrentNetwork>::open(storage_mode.clone()).unwrap();
+        assert_eq!(storage.version().unwrap(), BFT_STORE_VERSION);
+        let expected = [(transmission_id, entry.clone())].into_iter().collect::<HashMap<_, _>>();
+        assert_eq!(storage.as_hashmap(), expected);
+        assert_eq!(storage.get_transmission(transmission_id), Some(entry.0.clone()));
+        assert!(!storage.contains_transmission(BFTPersistentStorage::<CurrentNetwork>::VERSION_KEY));
+
+        // Ensure the upgraded store is writable.
+        let missing_transmissions = [(transmission_id, entry.0.clone())].into_iter().collect();
+        storage.insert_transmissions(Field::from_u64(3), indexset! { transmission_id }, missing_transmissions);
+        let expected = [(transmission_id, (entry.0, indexset! { Field::from_u64(2), Field::from_u64(3) }))]
+            .into_iter()
+            .collect::<HashMap<_, _>>();
+        assert_eq!(storage.as_hashmap(), expected);
+
+        // Ensure the store is not migrated again, once it is reopened.
+        let storage = BFTPersistentStorage::<CurrentNetwork>::open(storage_mode).unwrap();
+        assert_eq!(storage.version().unwrap(), BFT_STORE_VERSION);
+        assert_eq!(storage.as_hashmap(), expected);
+
+        // Ensure a store from a newer version is refused, with instructions to resync.
+        storage.write_version(BFT_STORE_VERSION + 1).unwrap();
+        let error = storage.upgrade().unwrap_err();
+        assert!(error.to_string().contains("snarkos clean"));
+        assert_eq!(storage.as_hashmap(), expected);
+    }
+
+    #[test]
+    fn test_new_store_is_current() {
+        // Ensure a new store records the current version, without migrating.
+        let storage = BFTPersistentStorage::<CurrentNetwork>::open_testing(sample_directory(), None).unwrap();
+        assert_eq!(storage.version().unwrap(), BFT_STORE_VERSION);
+        assert!(storage.as_hashmap().is_empty());
+    }
+
+    #[test]
+    fn test_compressed_entries_are_read_back() {
+        // Initialize the store, with zstd compression.
+        assert!(Compression::zstd(i32::MAX).is_err());
+        let compression = Compression::zstd(Compression::DEFAULT_ZSTD_LEVEL).unwrap();
+        let storage = BFTPersistentStorage::<CurrentNetwork>::open_testing(sample_directory(), None).unwrap();
+        let storage = BFTPersistentStorage { compression: Some(compression), ..storage };
+        assert_eq!(storage.compression(), Some(compression));
+
+        // Insert a (compressible) transmission.
+        let transmission_id = TransmissionID::Transaction(Field::from_u64(1));
+        let transmission = Transmission::Transaction(Data::Buffer(Bytes::from(vec![7u8; 1 << 16])));
+        let missing_transmissions = [(transmission_id, transmission.clone())].into_iter().collect();
+        let certificate_ids = indexset! { Field::from_u64(2), Field::from_u64(3) };
+        for certificate_id in &certificate_ids {
+            storage.insert_transmissions(*certificate_id, indexset! { transmission_id }, missing_transmissions.clone());
+        }
+
+        // Ensure the entry is read back intact.
+        assert!(storage.contains_transmission(transmission_id));
+        assert_eq!(storage.get_transmission(transmission_id), Some(transmission.clone()));
+        let entry = (transmission, certificate_ids);
+        assert_eq!(storage.as_hashmap(), [(transmission_id, entry.clone())].into_iter().collect());
+
+        // Ensure the entry is smaller when compressed.
+        let uncompressed = BFTPersistentStorage::<CurrentNetwork>::encode_entry(&entry, None).unwrap();
+        let compressed = storage.transmissions.get_confirmed(&transmission_id).unwrap().unwrap().into_owned();
+        assert!(compressed.len() * 10 < uncompressed.len(), "{} vs {}", compressed.len(), uncompressed.len());
+        // Ensure both encodings are decoded, regardless of the compression of the store.
+        assert_eq!(BFTPersistentStorage::<CurrentNetwork>::decode_entry(&uncompressed).unwrap(), entry);
+        assert_eq!(BFTPersistentStorage::<CurrentNetwork>::decode_entry(&compressed).unwrap(), entry);
+        // Ensure an entry with an unknown codec is refused.
+        assert!(BFTPersistentStorage::<CurrentNetwork>::decode_entry(&[u8::MAX]).is_err());
+        // Ensure an entry that declares more certificate IDs than it holds is refused.
+        let mut truncated = uncompressed[..uncompressed.len() - 2 * 32].to_vec();
+        let count_offset = truncated.len() - 4;
+        truncated[count_offset..].copy_from_slice(&u32::MAX.to_le_bytes());
+        assert!(BFTPersistentStorage::<CurrentNetwork>::decode_entry(&truncated).is_err());
+    }
+
+    #[test]
+    fn test_slow_disk_degrades_to_buffered_writes() {
+        const THRESHOLD: Duration = Duration::from_millis(50);
+        const DELAY: Duration = Duration::from_millis(200);
+
+        // Initialize the store, with the degraded mode enabled.
+        let storage = BFTPersistentStorage::<CurrentNetwork>::open_testing(sample_directory(), None).unwrap();
+        assert!(BFTPersistentStorage::<CurrentNetwork>::open_testing(sample_directory(), None)
+            .unwrap()
+            .with_degraded_mode(Duration::ZERO)
+            .is_err());
+        let storage = storage.with_degraded_mode(THRESHOLD).unwrap();
+        let set_delay = |delay: Duration| *storage.write_behind.as_ref().unwrap().injected_delay.write() = delay;
+        // Inserts a transmission for the given certificate, and returns the latency of the insert.
+        let insert = |id: u64| {
+            let transmission_id = TransmissionID::Transaction(Field::from_u64(id));
+            let transmission = Transmission::Transaction(Data::Buffer(Bytes::from(vec![id as u8; 64])));
+            let missing_transmissions = [(transmission_id, transmission)].into_iter().collect();
+            let start = Instant::now();
+            storage.insert_transmissions(Field::from_u64(id), indexset! { transmission_id }, missing_transmissions);
+            (transmission_id, start.elapsed())
+        };
+
+        // Ensure a fast write is synchronous.
+        let (id_a, _) = insert(1);
+        assert!(!storage.is_degraded());
+        assert!(storage.transmissions.contains_key_confirmed(&id_a).unwrap());
+
+        // Inject a slow disk, and ensure a slow write degrades the store.
+        set_delay(DELAY);
+        let (id_b, latency) = insert(2);
+        assert!(latency >= DELAY);
+        assert!(storage.is_degraded());
+
+        // Ensure the subsequent writes are buffered (i.e. are not slowed down), and are readable.
+        let (id_c, latency) = insert(3);
+        assert!(latency < DELAY, "{}ms", latency.as_millis());
+        assert!(storage.contains_transmission(id_c));
+        storage.remove_transmissions(&Field::from_u64(1), &indexset! { id_a });
+        assert!(!storage.contains_transmission(id_a));
+        assert!(storage.is_degraded());
+
+        // Recover the disk, and ensure the store flushes the buffered writes, and reverts to synchronous writes.
+        set_delay(Duration::ZERO);
+        let (id_d, _) = insert(4);
+        let start = Instant::now();
+        while storage.is_degraded() {
+            assert!(start.elapsed() < Duration::from_secs(10), "The BFT store did not recover");
+            std::thread::sleep(FLUSH_POLL_INTERVAL);
+        }
+        assert_eq!(storage.num_buffered_entries(), 0);
+        assert!(!storage.transmissions.contains_key_confirmed(&id_a).unwrap());
+        for transmission_id in [id_b, id_c, id_d] {
+            assert!(storage.transmissions.contains_key_confirmed(&transmission_id).unwrap());
+        }
     }
 }
@@ -0,0 +1,236 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkOS library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::BlockSink;
+use snarkos_node_bft::helpers::transaction_sender;
+use snarkvm::{
+    console::program::{Argument, Literal, Plaintext},
+    ledger::block::{Block, Input, Output, Transaction},
+    prelude::{Address, Network},
+};
+
+use anyhow::Result;
+use indexmap::IndexSet;
+use std::{
+    fs::{File, OpenOptions},
+    io::{BufWriter, Write},
+    path::Path,
+};
+
+/// An entry of the address-to-transaction index, i.e. an address that is involved in a committed transaction.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct IndexEntry<N: Network> {
+    /// The address.
+    address: Address<N>,
+    /// The ID of the transaction.
+    transaction_id: N::TransactionID,
+    /// The height of the block that contains the transaction.
+    height: u32,
+}
+
+impl<N: Network> IndexEntry<N> {
+    /// Returns the address.
+    pub const fn address(&self) -> Address<N> {
+        self.address
+    }
+
+    /// Returns the ID of the transaction.
+    pub const fn transaction_id(&self) -> N::TransactionID {
+        self.transaction_id
+    }
+
+    /// Returns the height of the block that contains the transaction.
+    pub const fn height(&self) -> u32 {
+        self.height
+    }
+}
+
+/// Returns the addresses involved in the given transaction, in order of appearance: the payer of a public fee,
+/// followed by the public address inputs, outputs, and future arguments of its transitions.
+/// Note: The addresses in private inputs and outputs (e.g. the owners of records) are not revealed.
+pub fn transaction_addresses<N: Network>(transaction: &Transaction<N>) -> IndexSet<Address<N>> {
+    // Returns the address in the given plaintext, if it is an address literal.
+    let as_address = |plaintext: &Plaintext<N>| match plaintext {
+        Plaintext::Literal(Literal::Address(address), _) => Some(*address),
+        _ => None,
+    };
+    let mut addresses: IndexSet<_> = transaction_sender(transaction).into_iter().collect();
+    for transition in transaction.transitions() {
+        for input in transition.inputs() {
+            if let Input::Public(_, Some(plaintext)) = input {
+                addresses.extend(as_address(plaintext));
+            }
+        }
+        for output in transition.outputs() {
+            match output {
+                Output::Public(_, Some(plaintext)) => addresses.extend(as_address(plaintext)),
+                Output::Future(_, Some(future)) => {
+                    for argument in future.arguments() {
+                        if let Argument::Plaintext(plaintext) = argument {
+                            addresses.extend(as_address(plaintext));
+                        }
+                    }
+                }
+                _ => (),
+            }
+        }
+    }
+    addresses
+}
+
+/// Returns the new entries of the address-to-transaction index for the given block, in order of the transactions.
+pub fn index_entries<N: Network>(block: &Block<N>) -> Vec<IndexEntry<N>> {
+    let height = block.height();
+    block
+        .transactions()
+        .iter()
+        .flat_map(|confirmed| {
+            let transaction = confirmed.transaction();
+            let transaction_id = transaction.id();
+            transaction_addresses(transaction).into_iter().map(move |address| IndexEntry {
+                address,
+                transaction_id,
+                height,
+            })
+        })
+        .collect()
+}
+
+/// A destination for the incremental entries of the address-to-transaction index.
+pub trait IndexSink<N: Network>: Send {
+    /// Writes the new index entries of the committed block at the given height to the sink.
+    /// Note: This method is retried until it succeeds, so it must be safe to call again for the same block.
+    fn write_entries(&mut self, height: u32, entries: &[IndexEntry<N>]) -> Result<()>;
+}
+
+/// A block sink that derives the new index entries of each committed block, and writes them to an index sink.
+///
+/// Note: Combined with a `BlockExporter`, the entries are written in order of the blocks, and at least once.
+/// An entry may be written more than once (e.g. if a write is retried), so consumers should treat the entries
+/// as idempotent upserts of `(address, transaction ID)`.
+pub struct IndexBlockSink<S> {
+    /// The index sink.
+    sink: S,
+}
+
+impl<S> IndexBlockSink<S> {
+    /// Initializes a new block sink, writing the index entries to the given index sink.
+    pub const fn new(sink: S) -> Self {
+        Self { sink }
+    }
+}
+
+impl<N: Network, S: IndexSink<N>> BlockSink<N> for IndexBlockSink<S> {
+    fn write_block(&mut self, block: &Block<N>) -> Result<()> {
+        self.sink.write_entries(block.height(), &index_entries(block))
+    }
+}
+
+/// An index sink that appends each index entry as a line of JSON to a file.
+pub struct FileIndexSink {
+    /// The file writer.
+    writer: BufWriter<File>,
+}
+
+impl FileIndexSink {
+    /// Opens (or creates) the file at the given path, appending index entries to the end of the file.
+    pub fn open(path: impl AsRef<Path>) -> Result<Self> {
+        let file = OpenOptions::new().create(true).append(true).open(path)?;
+        Ok(Self { writer: BufWriter::new(file) })
+    }
+}
+
+impl<N: Network> IndexSink<N> for FileIndexSink {
+    fn write_entries(&mut self, _height: u32, entries: &[IndexEntry<N>]) -> Result<()> {
+        for entry in entries {
+            // Serialize the entry as a single line of JSON.
+            let mut line = serde_json::to_vec(&serde_json::json!({
+                "address": entry.address(),
+                "transaction_id": entry.transaction_id(),
+                "height": entry.height(),
+            }))?;
+            line.push(b'\n');
+            self.writer.write_all(&line)?;
+        }
+        // Flush the entries of the block to the file.
+        self.writer.flush()?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::BlockExporter;
+    use snarkvm::{ledger::ledger_test_helpers::sample_genesis_block, prelude::TestRng};
+
+    use parking_lot::Mutex;
+    use std::sync::Arc;
+
+    type CurrentNetwork = snarkvm::prelude::Testnet3;
+
+    /// An index sink that records the written entries, and fails the first write of every block.
+    #[derive(Clone, Default)]
+    struct FlakyIndexSink {
+        /// The written entries, per block.
+        written: Arc<Mutex<Vec<Vec<IndexEntry<CurrentNetwork>>>>>,
+        /// The flag indicating whether the latest write failed.
+        has_failed: bool,
+    }
+
+    impl IndexSink<CurrentNetwork> for FlakyIndexSink {
+        fn write_entries(&mut self, _height: u32, entries: &[IndexEntry<CurrentNetwork>]) -> Result<()> {
+            self.has_failed = !self.has_failed;
+            anyhow::ensure!(!self.has_failed, "The index sink is temporarily unavailable");
+            self.written.lock().push(entries.to_vec());
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_index_entries_are_exported_in_order() {
+        // Sample the blocks.
+        let blocks: Vec<Block<CurrentNetwork>> =
+            (0..3).map(|seed| sample_genesis_block(&mut TestRng::fixed(seed))).collect();
+
+        // Ensure the index entries are derived in order of the transactions, with the addresses that they involve.
+        for block in &blocks {
+            let entries = index_entries(block);
+            let expected: Vec<_> = block
+                .transactions()
+                .iter()
+                .flat_map(|confirmed| {
+                    let transaction = confirmed.transaction();
+                    let transaction_id = transaction.id();
+                    transaction_addresses(transaction).into_iter().map(move |address| (address, transaction_id))
+                })
+                .collect();
+            let candidate: Vec<_> = entries.iter().map(|entry| (entry.address(), entry.transaction_id())).collect();
+            assert_eq!(candidate, expected);
+            assert!(entries.iter().all(|entry| entry.height() == block.height()));
+        }
+
+        // Export the blocks to a sink that fails transiently.
+        let sink = FlakyIndexSink::default();
+        let exporter = BlockExporter::new(IndexBlockSink::new(sink.clone()), 16).unwrap();
+        for block in &blocks {
+            assert!(exporter.export(block));
+        }
+        exporter.shut_down();
+
+        // Ensure the entries of every block were written (despite the failures), in order of the blocks.
+        let expected: Vec<_> = blocks.iter().map(index_entries).collect();
+        assert_eq!(*sink.written.lock(), expected);
+    }
+}
@@ -17,11 +17,21 @@
 #[macro_use]
 extern crate tracing;
 
+mod index;
+pub use index::*;
+
+pub use snarkos_node_bft::helpers::{MaintenanceWindow, BLOCK_EXPORT_TASK, CACHE_PRUNING_TASK, METRICS_TASK};
+pub use snarkos_node_bft_storage_service::Compression;
+
+mod sink;
+pub use sink::*;
+
 use snarkos_account::Account;
 use snarkos_node_bft::{
     helpers::{
         fmt_id,
         init_consensus_channels,
+        AdmissionReceipt,
         ConsensusReceiver,
         PrimaryReceiver,
         PrimarySender,
@@ -49,12 +59,20 @@ use colored::Colorize;
 use indexmap::IndexMap;
 use lru::LruCache;
 use parking_lot::Mutex;
-use std::{future::Future, net::SocketAddr, num::NonZeroUsize, sync::Arc};
+use std::{future::Future, net::SocketAddr, num::NonZeroUsize, sync::Arc, time::Duration};
 use tokio::{
-    sync::{oneshot, OnceCell},
+    sync::{oneshot, Notify, OnceCell},
     task::JoinHandle,
 };
 
+/// The maximum number of recent admission receipts that are retained, for their submitters to retrieve.
+const MAX_ADMISSION_RECEIPTS: usize = 1 << 16;
+/// The interval at which the maintenance window is updated with the consensus load.
+const MAINTENANCE_UPDATE_INTERVAL_IN_MS: u64 = 1000; // ms
+/// The interval at which the index exporter catches up to the ledger, for the blocks that are not advanced by
+/// consensus (e.g. the blocks from block sync).
+const INDEX_EXPORT_INTERVAL_IN_MS: u64 = 1000; // ms
+
 #[allow(dead_code)]
 #[derive(Clone)]
 pub struct Consensus<N: Network> {
@@ -72,6 +90,18 @@ pub struct Consensus<N: Network> {
     seen_solutions: Arc<Mutex<LruCache<PuzzleCommitment<N>, ()>>>,
     /// The recently-seen unconfirmed transactions.
     seen_transactions: Arc<Mutex<LruCache<N::TransactionID, ()>>>,
+    /// The recent admission receipts of the unconfirmed transactions, if receipts are enabled.
+    admission_receipts: Arc<Mutex<LruCache<N::TransactionID, AdmissionReceipt<N>>>>,
+    /// The exporter for committed blocks.
+    block_exporter: Arc<OnceCell<BlockExporter<N>>>,
+    /// The exporter for the incremental address-to-transaction index of committed blocks.
+    index_exporter: Arc<OnceCell<BlockExporter<N>>>,
+    /// The height of the next block to export to the index exporter.
+    index_export_height: Arc<Mutex<u32>>,
+    /// The notification that the ledger advanced to a block, for the index exporter to catch up.
+    index_export_notify: Arc<Notify>,
+    /// The maintenance window, which pauses the deferrable background tasks under heavy load, if one is set.
+    maintenance_window: Arc<OnceCell<Arc<MaintenanceWindow>>>,
     /// The spawned handles.
     handles: Arc<Mutex<Vec<JoinHandle<()>>>>,
 }
@@ -84,6 +114,8 @@ impl<N: Network> Consensus<N> {
         ip: Option<SocketAddr>,
         trusted_validators: &[SocketAddr],
         storage_mode: StorageMode,
+        bft_store_compression: Option<Compression>,
+        bft_store_degraded_threshold: Option<Duration>,
     ) -> Result<Self> {
         // Recover the development ID, if it is present.
         let dev = match storage_mode {
@@ -91,7 +123,12 @@ impl<N: Network> Consensus<N> {
             StorageMode::Production | StorageMode::Custom(..) => None,
         };
         // Initialize the Narwhal transmissions.
-        let transmissions = Arc::new(BFTPersistentStorage::open(storage_mode)?);
+        let mut transmissions = BFTPersistentStorage::open_with_compression(storage_mode, bft_store_compression)?;
+        // Enable the degraded mode of the Narwhal transmissions, if configured.
+        if let Some(threshold) = bft_store_degraded_threshold {
+            transmissions = transmissions.with_degraded_mode(threshold)?;
+        }
+        let transmissions = Arc::new(transmissions);
         // Initialize the Narwhal storage.
         let storage = NarwhalStorage::new(ledger.clone(), transmissions, MAX_GC_ROUNDS);
         // Initialize the BFT.
@@ -109,6 +146,12 @@ impl<N: Network> Consensus<N> {
             ))),
             seen_solutions: Arc::new(Mutex::new(LruCache::new(NonZeroUsize::new(1 << 16).unwrap()))),
             seen_transactions: Arc::new(Mutex::new(LruCache::new(NonZeroUsize::new(1 << 16).unwrap()))),
+            admission_receipts: Arc::new(Mutex::new(LruCache::new(NonZeroUsize::new(MAX_ADMISSION_RECEIPTS).unwrap()))),
+            block_exporter: Default::default(),
+            index_exporter: Default::default(),
+            index_export_height: Default::default(),
+            index_export_notify: Default::default(),
+            maintenance_window: Default::default(),
             handles: Default::default(),
         })
     }
@@ -142,6 +185,64 @@ impl<N: Network> Consensus<N> {
     pub fn primary_sender(&self) -> &PrimarySender<N> {
         self.primary_sender.get().expect("Primary sender not set")
     }
+
+    /// Returns the exporter for committed blocks, if one is set.
+    pub fn block_exporter(&self) -> Option<&BlockExporter<N>> {
+        self.block_exporter.get()
+    }
+
+    /// Sets the exporter for committed blocks.
+    pub fn set_block_exporter(&self, block_exporter: BlockExporter<N>) -> Result<()> {
+        // If a maintenance window is set, the block exporter consults it.
+        if let Some(maintenance_window) = self.maintenance_window() {
+            block_exporter.set_maintenance_window(maintenance_window.clone());
+        }
+        self.block_exporter.set(block_exporter).map_err(|_| anyhow::anyhow!("Block exporter already set"))
+    }
+
+    /// Returns the exporter for the incremental address-to-transaction index, if one is set.
+    pub fn index_exporter(&self) -> Option<&BlockExporter<N>> {
+        self.index_exporter.get()
+    }
+
+    /// Sets the exporter for the incremental address-to-transaction index, which emits the new index entries
+    /// of each block that the ledger advances to (e.g. to an `IndexBlockSink`), so that external systems can
+    /// maintain their own index. If the exporter resumes from a cursor, the blocks after the cursor are exported
+    /// first, so that the entries remain in order, and none are skipped across a restart.
+    pub fn set_index_exporter(&self, index_exporter: BlockExporter<N>) -> Result<()> {
+        // If a maintenance window is set, the index exporter consults it.
+        if let Some(maintenance_window) = self.maintenance_window() {
+            index_exporter.set_maintenance_window(maintenance_window.clone());
+        }
+        // Resume after the cursor, or export from the next block if there is no cursor.
+        let next_height = match index_exporter.resume_height() {
+            Some(height) => height.saturating_add(1),
+            None => self.ledger.latest_block_height().saturating_add(1),
+        };
+        *self.index_export_height.lock() = next_height;
+        self.index_exporter.set(index_exporter).map_err(|_| anyhow::anyhow!("Index exporter already set"))
+    }
+
+    /// Returns the maintenance window, if one is set.
+    pub fn maintenance_window(&self) -> Option<&Arc<MaintenanceWindow>> {
+        self.maintenance_window.get()
+    }
+
+    /// Sets the maintenance window, which pauses the deferrable background tasks while consensus is under
+    /// heavy load (i.e. while there are many unconfirmed transmissions).
+    pub fn set_maintenance_window(&self, maintenance_window: MaintenanceWindow) -> Result<()> {
+        let maintenance_window = Arc::new(maintenance_window);
+        self.maintenance_window
+            .set(maintenance_window.clone())
+            .map_err(|_| anyhow::anyhow!("Maintenance window already set"))?;
+        // If a block or index exporter is set, it consults the maintenance window.
+        for exporter in [self.block_exporter(), self.index_exporter()].into_iter().flatten() {
+            exporter.set_maintenance_window(maintenance_window.clone());
+        }
+        // The gateway consults the maintenance window, before pruning its cache and aggregating its metrics.
+        self.bft.primary().gateway().set_maintenance_window(maintenance_window);
+        Ok(())
+    }
 }
 
 impl<N: Network> Consensus<N> {
@@ -167,6 +268,13 @@ impl<N: Network> Consensus<N> {
 }
 
 impl<N: Network> Consensus<N> {
+    /// Returns `true` if the given transaction is in the memory pool, i.e. queued, or in one of the workers.
+    pub fn contains_unconfirmed_transaction(&self, transaction_id: &N::TransactionID) -> bool {
+        let transmission_id = TransmissionID::from(transaction_id);
+        self.transactions_queue.lock().contains(transaction_id)
+            || self.bft.primary().workers().iter().any(|worker| worker.contains_transmission(transmission_id))
+    }
+
     /// Returns the unconfirmed transmission IDs.
     pub fn unconfirmed_transmission_ids(&self) -> impl '_ + Iterator<Item = TransmissionID<N>> {
         self.bft.unconfirmed_transmission_ids()
@@ -288,14 +396,53 @@ impl<N: Network> Consensus<N> {
             let transaction_id = transaction.id();
             trace!("Adding unconfirmed transaction '{}' to the memory pool...", fmt_id(transaction_id));
             // Send the unconfirmed transaction to the primary.
-            if let Err(e) =
-                self.primary_sender().send_unconfirmed_transaction(transaction_id, Data::Object(transaction)).await
-            {
-                warn!("Failed to add unconfirmed transaction '{}' to the memory pool - {e}", fmt_id(transaction_id));
+            match self.primary_sender().send_unconfirmed_transaction(transaction_id, Data::Object(transaction)).await {
+                // Retain the admission receipt, if receipts are enabled.
+                Ok(Some(receipt)) => {
+                    self.admission_receipts.lock().put(transaction_id, receipt);
+                }
+                Ok(None) => (),
+                Err(e) => {
+                    warn!("Failed to add unconfirmed transaction '{}' to the memory pool - {e}", fmt_id(transaction_id))
+                }
             }
         }
         Ok(())
     }
+
+    /// Replaces the queued transaction `replaced_id` in the memory pool with the given unconfirmed transaction,
+    /// from the same submitter with a higher priority fee.
+    pub async fn replace_unconfirmed_transaction(
+        &self,
+        replaced_id: N::TransactionID,
+        transaction: Transaction<N>,
+    ) -> Result<()> {
+        let transaction_id = transaction.id();
+        // Check that the transaction is not a fee transaction.
+        if transaction.is_fee() {
+            bail!("Transaction '{}' is a fee transaction {}", fmt_id(transaction_id), "(skipping)".dimmed());
+        }
+        // Check if the transaction already exists in the ledger.
+        if self.ledger.contains_transmission(&TransmissionID::from(&transaction_id))? {
+            bail!("Transaction '{}' exists in the ledger {}", fmt_id(transaction_id), "(skipping)".dimmed());
+        }
+        // Replace the queued transaction, in the worker that queued it.
+        let primary = self.bft.primary();
+        let receipt = primary.replace_unconfirmed_transaction(replaced_id, transaction_id, Data::Object(transaction));
+        // Retain the admission receipt, if receipts are enabled.
+        if let Some(receipt) = receipt.await? {
+            self.admission_receipts.lock().put(transaction_id, receipt);
+        }
+        // Mark the transaction as seen, so that its relay is not processed again.
+        self.seen_transactions.lock().put(transaction_id, ());
+        Ok(())
+    }
+
+    /// Returns the admission receipt of the given unconfirmed transaction, if it was admitted recently,
+    /// and receipts are enabled.
+    pub fn admission_receipt(&self, transaction_id: &N::TransactionID) -> Option<AdmissionReceipt<N>> {
+        self.admission_receipts.lock().get(transaction_id).cloned()
+    }
 }
 
 impl<N: Network> Consensus<N> {
@@ -303,6 +450,30 @@ impl<N: Network> Consensus<N> {
     fn start_handlers(&self, consensus_receiver: ConsensusReceiver<N>) {
         let ConsensusReceiver { mut rx_consensus_subdag } = consensus_receiver;
 
+        // Periodically update the maintenance window with the consensus load, if one is set.
+        let self_ = self.clone();
+        self.spawn(async move {
+            loop {
+                tokio::time::sleep(Duration::from_millis(MAINTENANCE_UPDATE_INTERVAL_IN_MS)).await;
+                if let Some(maintenance_window) = self_.maintenance_window() {
+                    maintenance_window.update(self_.num_unconfirmed_transmissions());
+                }
+            }
+        });
+
+        // Export the index entries of the blocks that the ledger advances to, if an index exporter is set.
+        if self.index_exporter().is_some() {
+            let self_ = self.clone();
+            self.spawn(async move {
+                loop {
+                    // Wait for the ledger to advance by consensus, or for the interval to catch up on block sync.
+                    let notified = self_.index_export_notify.notified();
+                    let _ = tokio::time::timeout(Duration::from_millis(INDEX_EXPORT_INTERVAL_IN_MS), notified).await;
+                    self_.export_index_entries().await;
+                }
+            });
+        }
+
         // Process the committed subdag and transmissions from the BFT.
         let self_ = self.clone();
         self.spawn(async move {
@@ -335,6 +506,35 @@ impl<N: Network> Consensus<N> {
         callback.send(result).ok();
     }
 
+    /// Exports the blocks after the last exported block to the index exporter, in order, up to the latest block
+    /// of the ledger, whether it was advanced by consensus or by block sync.
+    /// Note: The export waits for room in the queue of the index exporter, so that no block is dropped.
+    async fn export_index_entries(&self) {
+        let Some(index_exporter) = self.index_exporter() else {
+            return;
+        };
+        let latest_height = self.ledger.latest_block_height();
+        loop {
+            let next_height = *self.index_export_height.lock();
+            if next_height > latest_height {
+                break;
+            }
+            // Retrieve the block, and queue it for export.
+            let block = match self.ledger.get_block(next_height) {
+                Ok(block) => block,
+                Err(e) => {
+                    warn!("Failed to retrieve block {next_height} for the index export - {e}");
+                    break;
+                }
+            };
+            if let Err(e) = index_exporter.export_with_backpressure(block).await {
+                error!("{e}");
+                break;
+            }
+            *self.index_export_height.lock() = next_height + 1;
+        }
+    }
+
     /// Attempts to advance to the next block.
     fn try_advance_to_next_block(
         &self,
@@ -354,6 +554,14 @@ impl<N: Network> Consensus<N> {
         self.ledger.check_next_block(&next_block)?;
         // Advance to the next block.
         self.ledger.advance_to_next_block(&next_block)?;
+        // Export the block, if a block exporter is set.
+        if let Some(block_exporter) = self.block_exporter.get() {
+            block_exporter.export(&next_block);
+        }
+        // Notify the index exporter of the new block, if one is set.
+        if self.index_exporter.get().is_some() {
+            self.index_export_notify.notify_one();
+        }
 
         #[cfg(feature = "metrics")]
         {
@@ -388,23 +596,23 @@ impl<N: Network> Consensus<N> {
         transmission_id: TransmissionID<N>,
         transmission: Transmission<N>,
     ) -> Result<()> {
-        // Initialize a callback sender and receiver.
-        let (callback, callback_receiver) = oneshot::channel();
         // Send the transmission to the primary.
         match (transmission_id, transmission) {
-            (TransmissionID::Ratification, Transmission::Ratification) => return Ok(()),
+            (TransmissionID::Ratification, Transmission::Ratification) => Ok(()),
             (TransmissionID::Solution(commitment), Transmission::Solution(solution)) => {
                 // Send the solution to the primary.
-                self.primary_sender().tx_unconfirmed_solution.send((commitment, solution, callback)).await?;
+                self.primary_sender().send_unconfirmed_solution(commitment, solution).await
             }
             (TransmissionID::Transaction(transaction_id), Transmission::Transaction(transaction)) => {
-                // Send the transaction to the primary.
-                self.primary_sender().tx_unconfirmed_transaction.send((transaction_id, transaction, callback)).await?;
+                // Send the transaction to the primary, renewing its admission receipt.
+                let receipt = self.primary_sender().send_unconfirmed_transaction(transaction_id, transaction).await?;
+                if let Some(receipt) = receipt {
+                    self.admission_receipts.lock().put(transaction_id, receipt);
+                }
+                Ok(())
             }
             _ => bail!("Mismatching `(transmission_id, transmission)` pair in consensus"),
         }
-        // Await the callback.
-        callback_receiver.await?
     }
 
     /// Spawns a task with the given future; it should only be used for long-running tasks.
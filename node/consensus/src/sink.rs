@@ -0,0 +1,460 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkOS library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::{MaintenanceWindow, BLOCK_EXPORT_TASK};
+use snarkvm::prelude::{block::Block, Network};
+
+use anyhow::{anyhow, Result};
+use parking_lot::{Mutex, RwLock};
+use std::{
+    fs::{self, File, OpenOptions},
+    io::{BufWriter, ErrorKind, Write},
+    path::{Path, PathBuf},
+    sync::{
+        atomic::{AtomicBool, AtomicU64, Ordering},
+        Arc,
+    },
+    thread::JoinHandle,
+    time::Duration,
+};
+use tokio::sync::mpsc;
+
+/// The maximum delay between attempts to write a block to the sink.
+const MAX_SINK_RETRY_DELAY_IN_MS: u64 = 5000; // ms
+
+/// A destination for committed blocks.
+pub trait BlockSink<N: Network>: Send {
+    /// Writes the given block to the sink.
+    /// Note: This method is retried until it succeeds, so it must be safe to call again for the same block.
+    fn write_block(&mut self, block: &Block<N>) -> Result<()>;
+}
+
+/// A block sink that appends each block as a line of JSON to a file.
+pub struct FileBlockSink {
+    /// The file writer.
+    writer: BufWriter<File>,
+}
+
+impl FileBlockSink {
+    /// Opens (or creates) the file at the given path, appending blocks to the end of the file.
+    pub fn open(path: impl AsRef<Path>) -> Result<Self> {
+        let file = OpenOptions::new().create(true).append(true).open(path)?;
+        Ok(Self { writer: BufWriter::new(file) })
+    }
+}
+
+impl<N: Network> BlockSink<N> for FileBlockSink {
+    fn write_block(&mut self, block: &Block<N>) -> Result<()> {
+        // Serialize the block as a single line of JSON.
+        let mut line = serde_json::to_vec(block)?;
+        line.push(b'\n');
+        // Write the line, and flush it to the file.
+        self.writer.write_all(&line)?;
+        self.writer.flush()?;
+        Ok(())
+    }
+}
+
+/// A block sink that publishes each block as JSON to a Kafka topic, keyed by the block height.
+#[cfg(feature = "kafka")]
+pub struct KafkaBlockSink {
+    /// The Kafka producer.
+    producer: rdkafka::producer::BaseProducer,
+    /// The Kafka topic.
+    topic: String,
+}
+
+#[cfg(feature = "kafka")]
+impl KafkaBlockSink {
+    /// Initializes a new Kafka block sink for the given brokers and topic.
+    pub fn new(brokers: &str, topic: &str) -> Result<Self> {
+        let producer = rdkafka::config::ClientConfig::new()
+            .set("bootstrap.servers", brokers)
+            .set("enable.idempotence", "true")
+            .create()?;
+        Ok(Self { producer, topic: topic.to_string() })
+    }
+}
+
+#[cfg(feature = "kafka")]
+impl<N: Network> BlockSink<N> for KafkaBlockSink {
+    fn write_block(&mut self, block: &Block<N>) -> Result<()> {
+        use rdkafka::producer::{BaseRecord, Producer};
+
+        // Serialize the block as JSON.
+        let payload = serde_json::to_vec(block)?;
+        let key = block.height().to_string();
+        // Publish the block, and wait for it to be delivered.
+        self.producer
+            .send(BaseRecord::to(&self.topic).key(&key).payload(&payload))
+            .map_err(|(e, _)| anyhow::anyhow!("Failed to publish block {key} - {e}"))?;
+        self.producer.flush(Duration::from_millis(MAX_SINK_RETRY_DELAY_IN_MS))?;
+        Ok(())
+    }
+}
+
+/// The cursor of an export, i.e. the height of the last block that was written to the sink, which is persisted
+/// to a file so that the export resumes after a restart.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ExportCursor {
+    /// The path of the cursor file.
+    path: PathBuf,
+}
+
+impl ExportCursor {
+    /// Initializes a new export cursor, which is persisted to the file at the given path.
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self { path: path.into() }
+    }
+
+    /// Returns the height of the last block that was written to the sink, if any block was written.
+    pub fn load(&self) -> Result<Option<u32>> {
+        match fs::read_to_string(&self.path) {
+            Ok(contents) => contents
+                .trim()
+                .parse()
+                .map(Some)
+                .map_err(|error| anyhow!("Malformed export cursor at '{}' - {error}", self.path.display())),
+            Err(error) if error.kind() == ErrorKind::NotFound => Ok(None),
+            Err(error) => Err(anyhow!("Failed to read the export cursor at '{}' - {error}", self.path.display())),
+        }
+    }
+
+    /// Saves the height of the last block that was written to the sink. The height is written to a temporary file,
+    /// which then replaces the cursor file, so that the cursor is never left partially written.
+    pub fn save(&self, height: u32) -> Result<()> {
+        let mut temp_path = self.path.as_os_str().to_owned();
+        temp_path.push(".tmp");
+        let temp_path = PathBuf::from(temp_path);
+        fs::write(&temp_path, height.to_string())
+            .and_then(|()| fs::rename(&temp_path, &self.path))
+            .map_err(|error| anyhow!("Failed to write the export cursor at '{}' - {error}", self.path.display()))
+    }
+}
+
+/// Exports committed blocks to a block sink in real time.
+///
+/// Blocks are buffered in a bounded queue, and written to the sink in order on a dedicated thread.
+/// A block in the queue is retried until it is written (at-least-once). If the sink is too slow and
+/// the queue is full, `export` drops new blocks (and logs them), so that consensus is never stalled,
+/// while `export_with_backpressure` waits for room in the queue, so that no block is dropped.
+/// If an export cursor is given, it is advanced as each block is written, so that the export resumes after a restart.
+/// If a maintenance window defers block export, the writes are paused while consensus is under heavy load.
+pub struct BlockExporter<N: Network> {
+    /// The sender for the queue of blocks to export.
+    sender: mpsc::Sender<Block<N>>,
+    /// The height of the last block that was written before the exporter was initialized, if it resumes from a cursor.
+    resume_height: Option<u32>,
+    /// The number of blocks that were dropped because the queue was full.
+    num_dropped: Arc<AtomicU64>,
+    /// The maintenance window consulted before writing each block, if one is set.
+    maintenance_window: Arc<RwLock<Option<Arc<MaintenanceWindow>>>>,
+    /// The flag indicating the exporter is shutting down, so that the queued blocks are no longer deferred.
+    shutdown: Arc<AtomicBool>,
+    /// The handle to the writer thread.
+    handle: Mutex<Option<JoinHandle<()>>>,
+}
+
+impl<N: Network> BlockExporter<N> {
+    /// Initializes a new block exporter for the given sink, buffering up to `buffer_size` blocks.
+    pub fn new(sink: impl BlockSink<N> + 'static, buffer_size: usize) -> Result<Self> {
+        Self::new_inner(sink, buffer_size, None)
+    }
+
+    /// Initializes a new block exporter for the given sink, buffering up to `buffer_size` blocks,
+    /// which advances the given cursor as each block is written.
+    pub fn new_with_cursor(
+        sink: impl BlockSink<N> + 'static,
+        buffer_size: usize,
+        cursor: ExportCursor,
+    ) -> Result<Self> {
+        Self::new_inner(sink, buffer_size, Some(cursor))
+    }
+
+    /// Initializes a new block exporter for the given sink, buffering up to `buffer_size` blocks,
+    /// which advances the given cursor as each block is written, if one is given.
+    fn new_inner(
+        mut sink: impl BlockSink<N> + 'static,
+        buffer_size: usize,
+        cursor: Option<ExportCursor>,
+    ) -> Result<Self> {
+        // Load the height of the last block that was written, if the export resumes from a cursor.
+        let resume_height = cursor.as_ref().map(ExportCursor::load).transpose()?.flatten();
+        // Initialize the bounded queue.
+        let (sender, mut receiver) = mpsc::channel::<Block<N>>(buffer_size.max(1));
+        // Initialize the maintenance window.
+        let maintenance_window: Arc<RwLock<Option<Arc<MaintenanceWindow>>>> = Default::default();
+        let maintenance_window_ = maintenance_window.clone();
+        // Initialize the shutdown flag.
+        let shutdown: Arc<AtomicBool> = Default::default();
+        let shutdown_ = shutdown.clone();
+        // Spawn the writer thread.
+        let handle = std::thread::Builder::new().name("block-exporter".to_string()).spawn(move || {
+            while let Some(block) = receiver.blocking_recv() {
+                // If block export is deferred by the maintenance window, wait until it is permitted,
+                // or until the exporter is shut down.
+                let window = maintenance_window_.read().clone();
+                if let Some(window) = window {
+                    window.wait_until_permitted_blocking(BLOCK_EXPORT_TASK, &shutdown_);
+                }
+                // Write the block, retrying with a backoff until it succeeds.
+                let mut num_attempts = 0u64;
+                while let Err(e) = sink.write_block(&block) {
+                    num_attempts += 1;
+                    warn!("Failed to export block {} (attempt {num_attempts}) - {e}", block.height());
+                    std::thread::sleep(Duration::from_millis((100 * num_attempts).min(MAX_SINK_RETRY_DELAY_IN_MS)));
+                }
+                // Advance the cursor, if one is set.
+                // Note: If the cursor is not saved, the block is written again after a restart (at-least-once).
+                if let Some(cursor) = &cursor {
+                    if let Err(e) = cursor.save(block.height()) {
+                        warn!("{e}");
+                    }
+                }
+            }
+        })?;
+        Ok(Self {
+            sender,
+            resume_height,
+            num_dropped: Default::default(),
+            maintenance_window,
+            shutdown,
+            handle: Mutex::new(Some(handle)),
+        })
+    }
+
+    /// Sets the maintenance window consulted before writing each block.
+    pub fn set_maintenance_window(&self, maintenance_window: Arc<MaintenanceWindow>) {
+        *self.maintenance_window.write() = Some(maintenance_window);
+    }
+
+    /// Returns the number of blocks that were dropped because the queue was full.
+    pub fn num_dropped(&self) -> u64 {
+        self.num_dropped.load(Ordering::SeqCst)
+    }
+
+    /// Returns the height of the last block that was written before the exporter was initialized,
+    /// if it resumes from a cursor.
+    pub const fn resume_height(&self) -> Option<u32> {
+        self.resume_height
+    }
+
+    /// Queues the given block for export, returning `false` if the block was dropped.
+    pub fn export(&self, block: &Block<N>) -> bool {
+        match self.sender.try_send(block.clone()) {
+            Ok(()) => true,
+            Err(e) => {
+                let num_dropped = self.num_dropped.fetch_add(1, Ordering::SeqCst) + 1;
+                error!("Dropped block {} from the block export queue ({num_dropped} dropped) - {e}", block.height());
+                false
+            }
+        }
+    }
+
+    /// Queues the given block for export, waiting for room in the queue if it is full, so that no block is dropped.
+    pub async fn export_with_backpressure(&self, block: Block<N>) -> Result<()> {
+        let height = block.height();
+        self.sender
+            .send(block)
+            .await
+            .map_err(|_| anyhow!("Failed to queue block {height} for export - the queue is closed"))
+    }
+
+    /// Shuts down the block exporter, waiting for all of the queued blocks to be written.
+    /// Note: The queued blocks are written even if block export is deferred by the maintenance window.
+    pub fn shut_down(self) {
+        let Self { sender, shutdown, handle, .. } = self;
+        // Stop deferring the queued blocks, and close the queue.
+        shutdown.store(true, Ordering::SeqCst);
+        drop(sender);
+        // Wait for the writer thread to finish.
+        if let Some(handle) = handle.lock().take() {
+            if handle.join().is_err() {
+                error!("The block exporter thread panicked");
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use snarkvm::{
+        ledger::ledger_test_helpers::sample_genesis_block,
+        prelude::{FromBytes, TestRng},
+    };
+
+    use std::io::{BufRead, BufReader};
+
+    type CurrentNetwork = snarkvm::prelude::Testnet3;
+
+    /// Returns a unique path for a temporary sink file.
+    fn sample_sink_path(name: &str) -> std::path::PathBuf {
+        std::env::temp_dir().join(format!("snarkos-block-sink-{name}-{}.ndjson", std::process::id()))
+    }
+
+    /// A block sink that is slow to write.
+    struct SlowBlockSink;
+
+    impl<N: Network> BlockSink<N> for SlowBlockSink {
+        fn write_block(&mut self, _block: &Block<N>) -> Result<()> {
+            std::thread::sleep(Duration::from_millis(200));
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_file_sink_writes_blocks_in_order() {
+        // Sample the blocks.
+        let blocks: Vec<Block<CurrentNetwork>> =
+            (0..3).map(|seed| sample_genesis_block(&mut TestRng::fixed(seed))).collect();
+
+        // Initialize the exporter.
+        let path = sample_sink_path("order");
+        let _ = std::fs::remove_file(&path);
+        let exporter = BlockExporter::new(FileBlockSink::open(&path).unwrap(), 16).unwrap();
+        // Export the blocks.
+        for block in &blocks {
+            assert!(exporter.export(block));
+        }
+        // Wait for the blocks to be written.
+        exporter.shut_down();
+
+        // Ensure the blocks were written in order.
+        let reader = BufReader::new(File::open(&path).unwrap());
+        let written: Vec<Block<CurrentNetwork>> =
+            reader.lines().map(|line| serde_json::from_str(&line.unwrap()).unwrap()).collect();
+        assert_eq!(written.len(), blocks.len());
+        for (expected, candidate) in blocks.iter().zip(written.iter()) {
+            assert_eq!(expected.hash(), candidate.hash());
+        }
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_export_is_deferred_under_high_load() {
+        // Load the genesis block.
+        let block = Block::<CurrentNetwork>::from_bytes_le(CurrentNetwork::genesis_bytes()).unwrap();
+
+        // Initialize the exporter, with block export deferred under high load.
+        let path = sample_sink_path("deferred");
+        let _ = std::fs::remove_file(&path);
+        let exporter = BlockExporter::new(FileBlockSink::open(&path).unwrap(), 16).unwrap();
+        let window = Arc::new(MaintenanceWindow::new(100, 10).unwrap().with_deferrable_tasks([BLOCK_EXPORT_TASK]));
+        exporter.set_maintenance_window(window.clone());
+        let num_written = || std::fs::read_to_string(&path).unwrap().lines().count();
+
+        // Simulate high load, and ensure the export is postponed.
+        assert!(window.update(100));
+        assert!(exporter.export(&block));
+        std::thread::sleep(Duration::from_millis(500));
+        assert_eq!(num_written(), 0);
+        // Ensure the export remains postponed until the load falls to the low threshold.
+        assert!(window.update(50));
+        std::thread::sleep(Duration::from_millis(500));
+        assert_eq!(num_written(), 0);
+
+        // Ensure the export runs once the load drops.
+        assert!(!window.update(10));
+        exporter.shut_down();
+        assert_eq!(num_written(), 1);
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_shut_down_is_not_deferred() {
+        // Load the genesis block.
+        let block = Block::<CurrentNetwork>::from_bytes_le(CurrentNetwork::genesis_bytes()).unwrap();
+
+        // Initialize the exporter, with block export deferred under high load.
+        let path = sample_sink_path("shutdown");
+        let _ = std::fs::remove_file(&path);
+        let exporter = BlockExporter::new(FileBlockSink::open(&path).unwrap(), 16).unwrap();
+        let window = Arc::new(MaintenanceWindow::new(100, 10).unwrap().with_deferrable_tasks([BLOCK_EXPORT_TASK]));
+        exporter.set_maintenance_window(window.clone());
+
+        // Ensure the exporter shuts down while the export is paused, writing the queued block.
+        assert!(window.update(100));
+        assert!(exporter.export(&block));
+        exporter.shut_down();
+        assert!(window.is_paused());
+        assert_eq!(std::fs::read_to_string(&path).unwrap().lines().count(), 1);
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_slow_sink_drops_blocks() {
+        // Load the genesis block.
+        let block = Block::<CurrentNetwork>::from_bytes_le(CurrentNetwork::genesis_bytes()).unwrap();
+
+        // Initialize the exporter, with a small buffer.
+        let exporter = BlockExporter::new(SlowBlockSink, 1).unwrap();
+        // Export more blocks than the sink can keep up with, and ensure the excess is dropped.
+        let num_exported = (0..10).filter(|_| exporter.export(&block)).count();
+        assert!(num_exported < 10);
+        assert_eq!(exporter.num_dropped(), 10 - num_exported as u64);
+        exporter.shut_down();
+    }
+
+    #[tokio::test]
+    async fn test_slow_sink_applies_backpressure() {
+        // Load the genesis block.
+        let block = Block::<CurrentNetwork>::from_bytes_le(CurrentNetwork::genesis_bytes()).unwrap();
+
+        // Initialize the exporter, with a small buffer.
+        let path = sample_sink_path("backpressure");
+        let _ = std::fs::remove_file(&path);
+        let exporter = BlockExporter::new(FileBlockSink::open(&path).unwrap(), 1).unwrap();
+        // Export more blocks than the buffer holds, and ensure none is dropped.
+        for _ in 0..5 {
+            exporter.export_with_backpressure(block.clone()).await.unwrap();
+        }
+        assert_eq!(exporter.num_dropped(), 0);
+        exporter.shut_down();
+        assert_eq!(std::fs::read_to_string(&path).unwrap().lines().count(), 5);
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_export_resumes_from_cursor() {
+        // Sample the blocks.
+        let blocks: Vec<Block<CurrentNetwork>> =
+            (0..3).map(|seed| sample_genesis_block(&mut TestRng::fixed(seed))).collect();
+
+        // Initialize the exporter, with a cursor that was never written.
+        let (path, cursor_path) = (sample_sink_path("cursor"), sample_sink_path("cursor").with_extension("cursor"));
+        let _ = std::fs::remove_file(&path);
+        let _ = std::fs::remove_file(&cursor_path);
+        let cursor = ExportCursor::new(&cursor_path);
+        let exporter = BlockExporter::new_with_cursor(FileBlockSink::open(&path).unwrap(), 16, cursor.clone()).unwrap();
+        assert_eq!(exporter.resume_height(), None);
+        // Export the blocks, and ensure the cursor is advanced to the last written block.
+        for block in &blocks {
+            assert!(exporter.export(block));
+        }
+        exporter.shut_down();
+        assert_eq!(cursor.load().unwrap(), Some(blocks.last().unwrap().height()));
+
+        // Ensure a restarted exporter resumes from the cursor.
+        let exporter = BlockExporter::<CurrentNetwork>::new_with_cursor(SlowBlockSink, 16, cursor.clone()).unwrap();
+        assert_eq!(exporter.resume_height(), Some(blocks.last().unwrap().height()));
+        exporter.shut_down();
+
+        // Ensure a malformed cursor is refused.
+        std::fs::write(&cursor_path, "not a height").unwrap();
+        assert!(BlockExporter::<CurrentNetwork>::new_with_cursor(SlowBlockSink, 16, cursor).is_err());
+        std::fs::remove_file(&path).unwrap();
+        std::fs::remove_file(&cursor_path).unwrap();
+    }
+}
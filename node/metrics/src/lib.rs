@@ -13,16 +13,39 @@
 // limitations under the License.
 
 mod names;
+mod sink;
 
 // Expose the names at the crate level for easy access.
 pub use names::*;
-// Re-export the snarkVM metrics.
-pub use snarkvm::metrics::*;
+// Expose the metrics sinks, and the functions that record metrics through the current sink.
+pub use sink::{
+    counter,
+    decrement_gauge,
+    gauge,
+    histogram,
+    increment_counter,
+    increment_gauge,
+    metric_labels,
+    metrics_epoch,
+    metrics_sink,
+    set_metrics_epoch,
+    set_metrics_sink,
+    with_metrics_sink,
+    MetricLabel,
+    MetricsSink,
+    NoopSink,
+    PrometheusSink,
+};
+
+use snarkvm::metrics::{register_counter, register_gauge, register_histogram};
+use std::sync::Arc;
 
 /// Initializes the metrics and returns a handle to the task running the metrics exporter.
 pub fn initialize_metrics() {
     // Build the Prometheus exporter.
     metrics_exporter_prometheus::PrometheusBuilder::new().install().expect("can't build the prometheus exporter");
+    // Record the metrics for the Prometheus exporter.
+    set_metrics_sink(Arc::new(PrometheusSink));
 
     // Register the snarkVM metrics.
     snarkvm::metrics::register_metrics();
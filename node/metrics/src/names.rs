@@ -12,9 +12,25 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-pub(super) const COUNTER_NAMES: [&str; 1] = [bft::LEADERS_ELECTED];
+pub(super) const COUNTER_NAMES: [&str; 4] =
+    [bft::CACHE_PEER_EVICTIONS, bft::CACHE_SPAM_HITS, bft::LEADERS_ELECTED, bft::PROBE_CONNECTIONS];
 
-pub(super) const GAUGE_NAMES: [&str; 12] = [
+pub(super) const GAUGE_NAMES: [&str; 28] = [
+    bft::CACHE_PEERS,
+    bft::CACHE_INBOUND_CONNECTIONS,
+    bft::CACHE_INBOUND_CONNECTION_KEYS,
+    bft::CACHE_INBOUND_EVENTS,
+    bft::CACHE_INBOUND_EVENT_KEYS,
+    bft::CACHE_INBOUND_CERTIFICATES,
+    bft::CACHE_INBOUND_CERTIFICATE_KEYS,
+    bft::CACHE_INBOUND_TRANSMISSIONS,
+    bft::CACHE_INBOUND_TRANSMISSION_KEYS,
+    bft::CACHE_OUTBOUND_EVENTS,
+    bft::CACHE_OUTBOUND_EVENT_KEYS,
+    bft::CACHE_OUTBOUND_CERTIFICATES,
+    bft::CACHE_OUTBOUND_CERTIFICATE_KEYS,
+    bft::CACHE_OUTBOUND_TRANSMISSIONS,
+    bft::CACHE_OUTBOUND_TRANSMISSION_KEYS,
     bft::CONNECTED,
     bft::CONNECTING,
     bft::LAST_STORED_ROUND,
@@ -26,9 +42,28 @@ pub(super) const GAUGE_NAMES: [&str; 12] = [
     router::CONNECTED,
     router::CANDIDATE,
     router::RESTRICTED,
+    router::BANNED,
     tcp::TCP_TASKS,
 ];
 
+/// The metrics that are labeled with the current committee epoch, so they can be segmented by epoch.
+pub(super) const EPOCH_SEGMENTED_NAMES: [&str; 11] = [
+    bft::COMMIT_ROUNDS_LATENCY,
+    bft::CONNECTED,
+    bft::LEADERS_ELECTED,
+    bft::PROPOSAL_ROUND,
+    blocks::TRANSACTIONS,
+    consensus::BLOCK_LATENCY,
+    consensus::CERTIFICATE_COMMIT_LATENCY,
+    consensus::COMMITTED_CERTIFICATES,
+    consensus::LAST_COMMITTED_ROUND,
+    tcp::NOISE_CODEC_ENCRYPTION_SIZE,
+    tcp::NOISE_CODEC_DECRYPTION_SIZE,
+];
+
+/// The label of the current committee epoch.
+pub const EPOCH_LABEL: &str = "epoch";
+
 pub(super) const HISTOGRAM_NAMES: [&str; 7] = [
     bft::COMMIT_ROUNDS_LATENCY,
     consensus::CERTIFICATE_COMMIT_LATENCY,
@@ -40,11 +75,29 @@ pub(super) const HISTOGRAM_NAMES: [&str; 7] = [
 ];
 
 pub mod bft {
+    pub const CACHE_PEERS: &str = "snarkos_bft_cache_peers_total";
+    pub const CACHE_PEER_EVICTIONS: &str = "snarkos_bft_cache_peer_evictions_total";
+    pub const CACHE_SPAM_HITS: &str = "snarkos_bft_cache_spam_hits_total";
+    pub const CACHE_INBOUND_CONNECTIONS: &str = "snarkos_bft_cache_inbound_connections_entries";
+    pub const CACHE_INBOUND_CONNECTION_KEYS: &str = "snarkos_bft_cache_inbound_connections_keys";
+    pub const CACHE_INBOUND_EVENTS: &str = "snarkos_bft_cache_inbound_events_entries";
+    pub const CACHE_INBOUND_EVENT_KEYS: &str = "snarkos_bft_cache_inbound_events_keys";
+    pub const CACHE_INBOUND_CERTIFICATES: &str = "snarkos_bft_cache_inbound_certificates_entries";
+    pub const CACHE_INBOUND_CERTIFICATE_KEYS: &str = "snarkos_bft_cache_inbound_certificates_keys";
+    pub const CACHE_INBOUND_TRANSMISSIONS: &str = "snarkos_bft_cache_inbound_transmissions_entries";
+    pub const CACHE_INBOUND_TRANSMISSION_KEYS: &str = "snarkos_bft_cache_inbound_transmissions_keys";
+    pub const CACHE_OUTBOUND_EVENTS: &str = "snarkos_bft_cache_outbound_events_entries";
+    pub const CACHE_OUTBOUND_EVENT_KEYS: &str = "snarkos_bft_cache_outbound_events_keys";
+    pub const CACHE_OUTBOUND_CERTIFICATES: &str = "snarkos_bft_cache_outbound_certificates_entries";
+    pub const CACHE_OUTBOUND_CERTIFICATE_KEYS: &str = "snarkos_bft_cache_outbound_certificates_keys";
+    pub const CACHE_OUTBOUND_TRANSMISSIONS: &str = "snarkos_bft_cache_outbound_transmissions_entries";
+    pub const CACHE_OUTBOUND_TRANSMISSION_KEYS: &str = "snarkos_bft_cache_outbound_transmissions_keys";
     pub const COMMIT_ROUNDS_LATENCY: &str = "snarkos_bft_commit_rounds_latency_secs"; // <-- This one doesn't even make sense.
     pub const CONNECTED: &str = "snarkos_bft_connected_total";
     pub const CONNECTING: &str = "snarkos_bft_connecting_total";
     pub const LAST_STORED_ROUND: &str = "snarkos_bft_last_stored_round";
     pub const LEADERS_ELECTED: &str = "snarkos_bft_leaders_elected_total";
+    pub const PROBE_CONNECTIONS: &str = "snarkos_bft_probe_connections_total";
     pub const PROPOSAL_ROUND: &str = "snarkos_bft_primary_proposal_round";
 }
 
@@ -64,6 +117,7 @@ pub mod router {
     pub const CONNECTED: &str = "snarkos_router_connected_total";
     pub const CANDIDATE: &str = "snarkos_router_candidate_total";
     pub const RESTRICTED: &str = "snarkos_router_restricted_total";
+    pub const BANNED: &str = "snarkos_router_banned_total";
 }
 
 pub mod tcp {
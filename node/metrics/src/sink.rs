@@ -0,0 +1,185 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkOS library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::names::{EPOCH_LABEL, EPOCH_SEGMENTED_NAMES};
+
+use std::sync::{Arc, Mutex, RwLock};
+
+/// The metrics sink of the node, or `None` if the metrics are discarded (i.e. until they are initialized).
+static METRICS_SINK: RwLock<Option<Arc<dyn MetricsSink>>> = RwLock::new(None);
+/// The lock that serializes the callers of `with_metrics_sink`.
+static METRICS_SINK_SCOPE: Mutex<()> = Mutex::new(());
+/// The current committee epoch, with which the epoch-segmented metrics are labeled, or `None` if it is unknown.
+static METRICS_EPOCH: RwLock<Option<u64>> = RwLock::new(None);
+
+/// A label of a metric, as a `(key, value)` pair.
+pub type MetricLabel = (&'static str, String);
+
+/// A backend for the metrics emitted by the node.
+pub trait MetricsSink: Send + Sync {
+    /// Increments the counter with the given name and labels by the given value.
+    fn counter(&self, name: &'static str, labels: &[MetricLabel], value: u64);
+
+    /// Sets the gauge with the given name and labels to the given value.
+    fn gauge(&self, name: &'static str, labels: &[MetricLabel], value: f64);
+
+    /// Increments the gauge with the given name and labels by the given value.
+    fn increment_gauge(&self, name: &'static str, labels: &[MetricLabel], value: f64);
+
+    /// Decrements the gauge with the given name and labels by the given value.
+    fn decrement_gauge(&self, name: &'static str, labels: &[MetricLabel], value: f64);
+
+    /// Records the given value in the histogram with the given name and labels.
+    fn histogram(&self, name: &'static str, labels: &[MetricLabel], value: f64);
+}
+
+/// A metrics sink that records metrics for the Prometheus exporter.
+#[derive(Copy, Clone, Debug, Default)]
+pub struct PrometheusSink;
+
+impl PrometheusSink {
+    /// Returns the given labels, for the Prometheus exporter.
+    fn labels(labels: &[MetricLabel]) -> Vec<::metrics::Label> {
+        labels.iter().map(|(key, value)| ::metrics::Label::new(*key, value.clone())).collect()
+    }
+}
+
+impl MetricsSink for PrometheusSink {
+    fn counter(&self, name: &'static str, labels: &[MetricLabel], value: u64) {
+        match labels.is_empty() {
+            true => snarkvm::metrics::counter(name, value),
+            false => ::metrics::counter!(name, Self::labels(labels)).increment(value),
+        }
+    }
+
+    fn gauge(&self, name: &'static str, labels: &[MetricLabel], value: f64) {
+        match labels.is_empty() {
+            true => snarkvm::metrics::gauge(name, value),
+            false => ::metrics::gauge!(name, Self::labels(labels)).set(value),
+        }
+    }
+
+    fn increment_gauge(&self, name: &'static str, labels: &[MetricLabel], value: f64) {
+        match labels.is_empty() {
+            true => snarkvm::metrics::increment_gauge(name, value),
+            false => ::metrics::gauge!(name, Self::labels(labels)).increment(value),
+        }
+    }
+
+    fn decrement_gauge(&self, name: &'static str, labels: &[MetricLabel], value: f64) {
+        match labels.is_empty() {
+            true => snarkvm::metrics::decrement_gauge(name, value),
+            false => ::metrics::gauge!(name, Self::labels(labels)).decrement(value),
+        }
+    }
+
+    fn histogram(&self, name: &'static str, labels: &[MetricLabel], value: f64) {
+        match labels.is_empty() {
+            true => snarkvm::metrics::histogram(name, value),
+            false => ::metrics::histogram!(name, Self::labels(labels)).record(value),
+        }
+    }
+}
+
+/// A metrics sink that discards all metrics.
+#[derive(Copy, Clone, Debug, Default)]
+pub struct NoopSink;
+
+impl MetricsSink for NoopSink {
+    fn counter(&self, _name: &'static str, _labels: &[MetricLabel], _value: u64) {}
+
+    fn gauge(&self, _name: &'static str, _labels: &[MetricLabel], _value: f64) {}
+
+    fn increment_gauge(&self, _name: &'static str, _labels: &[MetricLabel], _value: f64) {}
+
+    fn decrement_gauge(&self, _name: &'static str, _labels: &[MetricLabel], _value: f64) {}
+
+    fn histogram(&self, _name: &'static str, _labels: &[MetricLabel], _value: f64) {}
+}
+
+/// Sets the metrics sink of the node, replacing the current sink.
+pub fn set_metrics_sink(sink: Arc<dyn MetricsSink>) {
+    *METRICS_SINK.write().unwrap_or_else(|e| e.into_inner()) = Some(sink);
+}
+
+/// Returns the metrics sink of the node, or `None` if the metrics are discarded.
+pub fn metrics_sink() -> Option<Arc<dyn MetricsSink>> {
+    METRICS_SINK.read().unwrap_or_else(|e| e.into_inner()).clone()
+}
+
+/// Runs the given function with the metrics sink of the node, if the metrics are not discarded.
+/// Note: The sink is borrowed under the read lock, so recording a metric does not clone the sink.
+fn record(f: impl FnOnce(&dyn MetricsSink)) {
+    if let Some(sink) = &*METRICS_SINK.read().unwrap_or_else(|e| e.into_inner()) {
+        f(sink.as_ref())
+    }
+}
+
+/// Runs the given function with the given metrics sink, and restores the previous sink afterwards.
+/// Note: The calls are serialized, so that concurrent callers (e.g. tests) do not record each other's metrics.
+pub fn with_metrics_sink<R>(sink: Arc<dyn MetricsSink>, f: impl FnOnce() -> R) -> R {
+    let _scope = METRICS_SINK_SCOPE.lock().unwrap_or_else(|e| e.into_inner());
+    let previous = std::mem::replace(&mut *METRICS_SINK.write().unwrap_or_else(|e| e.into_inner()), Some(sink));
+    let result = f();
+    *METRICS_SINK.write().unwrap_or_else(|e| e.into_inner()) = previous;
+    result
+}
+
+/// Returns the current committee epoch, with which the epoch-segmented metrics are labeled, if it is known.
+pub fn metrics_epoch() -> Option<u64> {
+    *METRICS_EPOCH.read().unwrap_or_else(|e| e.into_inner())
+}
+
+/// Sets the current committee epoch, with which the epoch-segmented metrics are labeled from now on.
+pub fn set_metrics_epoch(epoch: Option<u64>) {
+    *METRICS_EPOCH.write().unwrap_or_else(|e| e.into_inner()) = epoch;
+}
+
+/// Returns the labels of the metric with the given name, i.e. the current epoch if the metric is epoch-segmented.
+pub fn metric_labels(name: &'static str) -> Vec<MetricLabel> {
+    match metrics_epoch() {
+        Some(epoch) if EPOCH_SEGMENTED_NAMES.contains(&name) => vec![(EPOCH_LABEL, epoch.to_string())],
+        _ => vec![],
+    }
+}
+
+/// Increments the counter with the given name by one.
+pub fn increment_counter(name: &'static str) {
+    record(|sink| sink.counter(name, &metric_labels(name), 1));
+}
+
+/// Increments the counter with the given name by the given value.
+pub fn counter(name: &'static str, value: u64) {
+    record(|sink| sink.counter(name, &metric_labels(name), value));
+}
+
+/// Sets the gauge with the given name to the given value.
+pub fn gauge(name: &'static str, value: f64) {
+    record(|sink| sink.gauge(name, &metric_labels(name), value));
+}
+
+/// Increments the gauge with the given name by the given value.
+pub fn increment_gauge(name: &'static str, value: f64) {
+    record(|sink| sink.increment_gauge(name, &metric_labels(name), value));
+}
+
+/// Decrements the gauge with the given name by the given value.
+pub fn decrement_gauge(name: &'static str, value: f64) {
+    record(|sink| sink.decrement_gauge(name, &metric_labels(name), value));
+}
+
+/// Records the given value in the histogram with the given name.
+pub fn histogram(name: &'static str, value: f64) {
+    record(|sink| sink.histogram(name, &metric_labels(name), value));
+}
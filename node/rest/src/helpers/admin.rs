@@ -0,0 +1,153 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkOS library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use anyhow::{ensure, Result};
+use axum::{
+    body::Body,
+    extract::State,
+    http::{Request, StatusCode},
+    middleware::Next,
+    response::{IntoResponse, Response},
+    RequestPartsExt,
+};
+use axum_extra::{
+    headers::authorization::{Authorization, Bearer},
+    TypedHeader,
+};
+use std::{net::SocketAddr, path::PathBuf, sync::Arc};
+
+/// The minimum length of the admin bearer token.
+pub const MIN_ADMIN_TOKEN_LENGTH: usize = 32;
+
+/// The bearer token required by the admin endpoints.
+#[derive(Clone)]
+pub struct AdminToken(Arc<String>);
+
+impl AdminToken {
+    /// Initializes a new admin token, ensuring it is long enough to not be guessed.
+    pub fn new(token: String) -> Result<Self> {
+        ensure!(
+            token.len() >= MIN_ADMIN_TOKEN_LENGTH,
+            "The admin token must be at least {MIN_ADMIN_TOKEN_LENGTH} characters"
+        );
+        Ok(Self(Arc::new(token)))
+    }
+
+    /// Returns `true` if the given token matches the admin token.
+    /// Note: The comparison takes constant time for tokens of the same length.
+    pub fn is_valid(&self, token: &str) -> bool {
+        let (expected, candidate) = (self.0.as_bytes(), token.as_bytes());
+        expected.len() == candidate.len() && expected.iter().zip(candidate).fold(0u8, |acc, (a, b)| acc | (a ^ b)) == 0
+    }
+}
+
+impl std::fmt::Debug for AdminToken {
+    /// Formats the admin token, without revealing it.
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("AdminToken(..)")
+    }
+}
+
+/// The configuration of the admin listener, which serves the admin endpoints separately from the public REST server.
+#[derive(Clone, Debug)]
+pub struct AdminConfig {
+    /// The IP address and port of the admin listener.
+    pub listener_ip: SocketAddr,
+    /// The bearer token required by the admin endpoints.
+    pub token: AdminToken,
+    /// The path to the PEM-encoded TLS certificate (chain) of the admin listener.
+    pub tls_cert_path: PathBuf,
+    /// The path to the PEM-encoded TLS private key of the admin listener.
+    pub tls_key_path: PathBuf,
+}
+
+/// The middleware that rejects the requests to the admin listener without a valid bearer token.
+/// Note: This is layered over the entire admin router (including its fallback), so that an
+/// unauthenticated request receives `401 Unauthorized` whether or not the endpoint exists.
+pub async fn admin_auth_middleware(
+    State(token): State<AdminToken>,
+    request: Request<Body>,
+    next: Next,
+) -> Result<Response, Response> {
+    // Deconstruct the request to extract the bearer token.
+    let (mut parts, body) = request.into_parts();
+    let auth: TypedHeader<Authorization<Bearer>> =
+        parts.extract().await.map_err(|_| StatusCode::UNAUTHORIZED.into_response())?;
+    // Ensure the bearer token is valid.
+    if !token.is_valid(auth.token()) {
+        warn!("Rejecting an admin request to '{}' (invalid token)", parts.uri);
+        return Err(StatusCode::UNAUTHORIZED.into_response());
+    }
+    // Reconstruct the request.
+    let request = Request::from_parts(parts, body);
+
+    Ok(next.run(request).await)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{
+        http::header::AUTHORIZATION,
+        middleware,
+        routing::{get, post},
+        Router,
+    };
+    use tower::ServiceExt;
+
+    const TOKEN: &str = "0123456789abcdef0123456789abcdef";
+
+    /// Returns the status code of a POST request to the given path, with the given bearer token.
+    async fn status(router: &Router, path: &str, token: Option<&str>) -> StatusCode {
+        let mut request = Request::builder().method("POST").uri(path);
+        if let Some(token) = token {
+            request = request.header(AUTHORIZATION, format!("Bearer {token}"));
+        }
+        router.clone().oneshot(request.body(Body::empty()).unwrap()).await.unwrap().status()
+    }
+
+    #[test]
+    fn test_admin_token_is_validated() {
+        assert!(AdminToken::new("short".to_string()).is_err());
+        let token = AdminToken::new(TOKEN.to_string()).unwrap();
+        assert!(token.is_valid(TOKEN));
+        assert!(!token.is_valid(&TOKEN[1..]));
+        assert!(!token.is_valid(&TOKEN.replace('0', "1")));
+        assert!(!format!("{token:?}").contains(TOKEN));
+    }
+
+    #[tokio::test]
+    async fn test_admin_endpoints_require_valid_auth() {
+        // Initialize the admin router.
+        let token = AdminToken::new(TOKEN.to_string()).unwrap();
+        let router = Router::new()
+            .route("/admin/peers/disconnect", post(|| async { "OK" }))
+            .route("/admin/health", get(|| async { "OK" }))
+            .fallback(|| async { StatusCode::NOT_FOUND })
+            .layer(middleware::from_fn_with_state(token, admin_auth_middleware));
+
+        // Ensure the unauthenticated requests are rejected.
+        assert_eq!(status(&router, "/admin/peers/disconnect", None).await, StatusCode::UNAUTHORIZED);
+        assert_eq!(status(&router, "/admin/peers/disconnect", Some("invalid")).await, StatusCode::UNAUTHORIZED);
+        assert_eq!(status(&router, "/admin/peers/disconnect", Some(&TOKEN[1..])).await, StatusCode::UNAUTHORIZED);
+        // Ensure the unauthenticated requests do not reveal whether the endpoint exists.
+        assert_eq!(status(&router, "/admin/unknown", None).await, StatusCode::UNAUTHORIZED);
+        assert_eq!(status(&router, "/admin/health", Some("invalid")).await, StatusCode::UNAUTHORIZED);
+
+        // Ensure the authenticated requests are served.
+        assert_eq!(status(&router, "/admin/peers/disconnect", Some(TOKEN)).await, StatusCode::OK);
+        assert_eq!(status(&router, "/admin/unknown", Some(TOKEN)).await, StatusCode::NOT_FOUND);
+        assert_eq!(status(&router, "/admin/health", Some(TOKEN)).await, StatusCode::METHOD_NOT_ALLOWED);
+    }
+}
@@ -0,0 +1,293 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkOS library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use axum::{
+    body::{Body, Bytes},
+    extract::State,
+    http::{HeaderMap, Method, Request, StatusCode},
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use parking_lot::{Mutex, RwLock};
+use std::{
+    collections::HashMap,
+    sync::Arc,
+    time::{Duration, Instant},
+};
+
+/// The default TTL of the cached responses.
+pub const DEFAULT_CACHE_TTL: Duration = Duration::from_secs(1);
+/// The default paths of the hot read endpoints (relative to the network), whose responses are cached.
+pub const DEFAULT_CACHED_ENDPOINTS: &[&str] =
+    &["/block/height/latest", "/block/hash/latest", "/block/latest", "/committee/latest", "/stateRoot/latest"];
+/// The maximum number of cached responses, after which the expired responses are evicted.
+const MAX_CACHED_RESPONSES: usize = 1 << 10;
+/// The maximum size of a cached response body.
+const MAX_CACHED_BODY_SIZE: usize = 8 << 20; // 8 MiB
+
+/// A source of the version of the node state, which changes whenever the responses may change (e.g. a new block).
+pub trait StateVersion: Send + Sync {
+    /// Returns the current version of the node state.
+    fn version(&self) -> u64;
+}
+
+/// A cached response.
+#[derive(Clone)]
+struct CachedResponse {
+    /// The status code of the response.
+    status: StatusCode,
+    /// The headers of the response.
+    headers: HeaderMap,
+    /// The body of the response.
+    body: Bytes,
+    /// The time at which the response expires.
+    expires_at: Instant,
+    /// The version of the node state, at which the response was cached.
+    version: Option<u64>,
+}
+
+/// A cache of the responses of the hot read endpoints, which serves identical requests within the TTL
+/// of their endpoint, until the node state changes.
+/// Note: Only the requests without a query are cached, as the cached endpoints do not take one.
+#[derive(Clone)]
+pub struct ResponseCache {
+    /// The paths of the cached endpoints (relative to the network), and their TTLs.
+    ttls: Vec<(String, Duration)>,
+    /// The source of the version of the node state, if one is set.
+    state_version: Option<Arc<dyn StateVersion>>,
+    /// The cached responses, by request path.
+    responses: Arc<Mutex<HashMap<String, CachedResponse>>>,
+}
+
+impl Default for ResponseCache {
+    /// Initializes a new response cache, for the default cached endpoints with the default TTL.
+    fn default() -> Self {
+        Self::new(DEFAULT_CACHE_TTL)
+    }
+}
+
+impl ResponseCache {
+    /// Initializes a new response cache, for the default cached endpoints with the given TTL.
+    pub fn new(ttl: Duration) -> Self {
+        let ttls = match ttl.is_zero() {
+            true => Vec::new(),
+            false => DEFAULT_CACHED_ENDPOINTS.iter().map(|path| (path.to_string(), ttl)).collect(),
+        };
+        Self { ttls, state_version: None, responses: Default::default() }
+    }
+
+    /// Sets the TTL of the responses of the given endpoint (relative to the network, e.g. `/block/latest`).
+    /// If the TTL is zero, the endpoint is not cached.
+    pub fn with_ttl(mut self, path: impl Into<String>, ttl: Duration) -> Self {
+        let path = path.into();
+        self.ttls.retain(|(cached_path, _)| *cached_path != path);
+        if !ttl.is_zero() {
+            self.ttls.push((path, ttl));
+        }
+        self
+    }
+
+    /// Sets the source of the version of the node state, on whose change the cached responses are invalidated.
+    pub fn with_state_version(mut self, state_version: Arc<dyn StateVersion>) -> Self {
+        self.state_version = Some(state_version);
+        self
+    }
+
+    /// Returns the TTL of the responses of the given request path (e.g. `/testnet3/block/latest`), if it is cached.
+    pub fn ttl(&self, path: &str) -> Option<Duration> {
+        // Strip the network from the path.
+        let endpoint = path.strip_prefix('/').and_then(|path| path.find('/').map(|index| &path[index..]))?;
+        self.ttls.iter().find(|(cached_path, _)| cached_path == endpoint).map(|(_, ttl)| *ttl)
+    }
+
+    /// Returns the number of cached responses.
+    pub fn len(&self) -> usize {
+        self.responses.lock().len()
+    }
+
+    /// Returns `true` if there are no cached responses.
+    pub fn is_empty(&self) -> bool {
+        self.responses.lock().is_empty()
+    }
+
+    /// Removes every cached response.
+    pub fn invalidate(&self) {
+        self.responses.lock().clear();
+    }
+
+    /// Returns the current version of the node state, if a source is set.
+    fn version(&self) -> Option<u64> {
+        self.state_version.as_ref().map(|state_version| state_version.version())
+    }
+
+    /// Returns the cached response for the given request key, if it is fresh.
+    fn get(&self, key: &str) -> Option<Response> {
+        let version = self.version();
+        let mut responses = self.responses.lock();
+        let cached = responses.get(key)?;
+        // If the response expired, or the node state changed, remove it.
+        if cached.expires_at <= Instant::now() || cached.version != version {
+            responses.remove(key);
+            return None;
+        }
+        let mut response = Response::new(Body::from(cached.body.clone()));
+        *response.status_mut() = cached.status;
+        *response.headers_mut() = cached.headers.clone();
+        Some(response)
+    }
+
+    /// Caches the given response for the given request key.
+    fn insert(&self, key: String, response: CachedResponse) {
+        let now = Instant::now();
+        let mut responses = self.responses.lock();
+        // If the cache is full, evict the expired responses, and skip caching if it is still full.
+        if responses.len() >= MAX_CACHED_RESPONSES {
+            responses.retain(|_, cached| cached.expires_at > now);
+            if responses.len() >= MAX_CACHED_RESPONSES {
+                return;
+            }
+        }
+        responses.insert(key, response);
+    }
+}
+
+/// The middleware that serves the requests to the cached endpoints from the response cache (if one is set),
+/// and caches the successful responses to them.
+pub async fn response_cache_middleware(
+    State(response_cache): State<Arc<RwLock<Option<ResponseCache>>>>,
+    request: Request<Body>,
+    next: Next,
+) -> Result<Response, Response> {
+    // Retrieve the response cache, so that the lock is not held across the request.
+    let response_cache = response_cache.read().clone();
+    // If the requested (read) endpoint is not cached, or the request has a query, serve the request.
+    // Note: Keying on the query would let arbitrary queries evict the cached responses.
+    let is_read = request.method() == Method::GET && request.uri().query().is_none();
+    let ttl = response_cache.as_ref().filter(|_| is_read).and_then(|cache| cache.ttl(request.uri().path()));
+    let (Some(cache), Some(ttl)) = (response_cache, ttl) else {
+        return Ok(next.run(request).await);
+    };
+
+    // Serve the cached response, if it is fresh.
+    let key = request.uri().path().to_string();
+    if let Some(response) = cache.get(&key) {
+        trace!("Serving '{} {}' from the response cache", request.method(), request.uri());
+        return Ok(response);
+    }
+
+    // Sample the version of the node state before the response is produced, so a concurrent change is not masked.
+    let version = cache.version();
+    let response = next.run(request).await;
+    // Only cache the successful responses.
+    if response.status() != StatusCode::OK {
+        return Ok(response);
+    }
+    // Note: The body is already produced in full by the handler, so it is buffered regardless of its size.
+    let (parts, body) = response.into_parts();
+    let body =
+        axum::body::to_bytes(body, usize::MAX).await.map_err(|_| StatusCode::INTERNAL_SERVER_ERROR.into_response())?;
+    // Only cache the responses that are small enough.
+    if body.len() <= MAX_CACHED_BODY_SIZE {
+        let expires_at = Instant::now() + ttl;
+        let (status, headers) = (parts.status, parts.headers.clone());
+        cache.insert(key, CachedResponse { status, headers, body: body.clone(), expires_at, version });
+    }
+
+    Ok(Response::from_parts(parts, Body::from(body)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{middleware, routing::get, Router};
+    use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+    use tower::ServiceExt;
+
+    /// A state version that is set manually.
+    #[derive(Default)]
+    struct SampleVersion(AtomicU64);
+
+    impl StateVersion for SampleVersion {
+        fn version(&self) -> u64 {
+            self.0.load(Ordering::SeqCst)
+        }
+    }
+
+    /// Returns the body of a GET request to the given path.
+    async fn body(router: &Router, path: &str) -> String {
+        let request = Request::builder().uri(path).body(Body::empty()).unwrap();
+        let response = router.clone().oneshot(request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        String::from_utf8(axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap().to_vec()).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_cached_response_is_served_within_ttl() {
+        const TTL: Duration = Duration::from_millis(200);
+
+        // Initialize the response cache, with a simulated state version.
+        let version = Arc::new(SampleVersion::default());
+        let cache = ResponseCache::default()
+            .with_ttl("/block/latest", TTL)
+            .with_ttl("/committee/latest", Duration::ZERO)
+            .with_state_version(version.clone());
+        assert_eq!(cache.ttl("/testnet3/block/latest"), Some(TTL));
+        assert_eq!(cache.ttl("/mainnet/block/latest"), Some(TTL));
+        assert_eq!(cache.ttl("/testnet3/stateRoot/latest"), Some(DEFAULT_CACHE_TTL));
+        assert_eq!(cache.ttl("/testnet3/committee/latest"), None);
+        assert_eq!(cache.ttl("/block/latest"), None);
+        assert!(ResponseCache::new(Duration::ZERO).ttl("/testnet3/block/latest").is_none());
+        let state = Arc::new(RwLock::new(Some(cache.clone())));
+
+        // Initialize the router, whose endpoints respond with the number of times they were served.
+        let counter = Arc::new(AtomicUsize::new(0));
+        let handler = {
+            let counter = counter.clone();
+            move || async move { (counter.fetch_add(1, Ordering::SeqCst) + 1).to_string() }
+        };
+        let router = Router::new()
+            .route("/testnet3/block/latest", get(handler.clone()))
+            .route("/testnet3/committee/latest", get(handler))
+            .layer(middleware::from_fn_with_state(state.clone(), response_cache_middleware));
+
+        // Ensure the cached response is served within the TTL.
+        assert_eq!(body(&router, "/testnet3/block/latest").await, "1");
+        assert_eq!(body(&router, "/testnet3/block/latest").await, "1");
+        assert_eq!(cache.len(), 1);
+        // Ensure the requests with a query are served, without being cached.
+        assert_eq!(body(&router, "/testnet3/block/latest?nonce=1").await, "2");
+        assert_eq!(body(&router, "/testnet3/block/latest?nonce=1").await, "3");
+        assert_eq!(cache.len(), 1);
+        // Ensure the uncached endpoints are always served.
+        assert_eq!(body(&router, "/testnet3/committee/latest").await, "4");
+        assert_eq!(body(&router, "/testnet3/committee/latest").await, "5");
+
+        // Ensure the cached response is refreshed, once the node state changes (e.g. a new block).
+        version.0.fetch_add(1, Ordering::SeqCst);
+        assert_eq!(body(&router, "/testnet3/block/latest").await, "6");
+        assert_eq!(body(&router, "/testnet3/block/latest").await, "6");
+        // Ensure the cached response is refreshed, once it is invalidated.
+        cache.invalidate();
+        assert!(cache.is_empty());
+        assert_eq!(body(&router, "/testnet3/block/latest").await, "7");
+        // Ensure the cached response is refreshed, once the TTL elapses.
+        tokio::time::sleep(TTL).await;
+        assert_eq!(body(&router, "/testnet3/block/latest").await, "8");
+
+        // Ensure no response is cached once the response cache is removed.
+        *state.write() = None;
+        assert_eq!(body(&router, "/testnet3/block/latest").await, "9");
+        assert_eq!(body(&router, "/testnet3/block/latest").await, "10");
+    }
+}
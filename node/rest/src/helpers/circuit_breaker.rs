@@ -0,0 +1,223 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkOS library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use anyhow::{ensure, Result};
+use axum::{
+    body::Body,
+    extract::State,
+    http::{Method, Request, StatusCode},
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use parking_lot::RwLock;
+use std::sync::Arc;
+
+/// The default path prefixes of the expensive read endpoints, which are shed under high load.
+pub const DEFAULT_SHEDDABLE_ENDPOINTS: &[&str] = &[
+    "/testnet3/blocks",
+    "/testnet3/block/",
+    "/testnet3/dag/",
+    "/testnet3/find/",
+    "/testnet3/memoryPool/",
+    "/testnet3/peers/all/metrics",
+    "/testnet3/program/",
+    "/testnet3/statePath/",
+    "/testnet3/transaction/",
+];
+
+/// A source of the current system load.
+pub trait LoadMonitor: Send + Sync {
+    /// Returns the current system load, as a fraction of capacity (where `1.0` is fully loaded).
+    fn load(&self) -> f64;
+}
+
+/// A load monitor for the CPU and memory pressure of the system.
+/// The load is the maximum of the 1-minute load average per core, and the fraction of memory in use.
+#[derive(Copy, Clone, Debug, Default)]
+pub struct SystemLoad;
+
+impl SystemLoad {
+    /// Returns the 1-minute load average per core, if it is available.
+    fn cpu_load() -> Option<f64> {
+        let loadavg = std::fs::read_to_string("/proc/loadavg").ok()?;
+        let load = loadavg.split_whitespace().next()?.parse::<f64>().ok()?;
+        let num_cores = std::thread::available_parallelism().map(|n| n.get()).unwrap_or(1);
+        Some(load / num_cores as f64)
+    }
+
+    /// Returns the fraction of memory in use, if it is available.
+    fn memory_load() -> Option<f64> {
+        let meminfo = std::fs::read_to_string("/proc/meminfo").ok()?;
+        let field = |name: &str| {
+            meminfo.lines().find(|line| line.starts_with(name))?.split_whitespace().nth(1)?.parse::<f64>().ok()
+        };
+        let (total, available) = (field("MemTotal:")?, field("MemAvailable:")?);
+        match total > 0.0 {
+            true => Some(1.0 - available / total),
+            false => None,
+        }
+    }
+}
+
+impl LoadMonitor for SystemLoad {
+    fn load(&self) -> f64 {
+        Self::cpu_load().unwrap_or(0.0).max(Self::memory_load().unwrap_or(0.0))
+    }
+}
+
+/// A circuit breaker that sheds the expensive read endpoints, while the system load exceeds a threshold.
+#[derive(Clone)]
+pub struct CircuitBreaker {
+    /// The system load above which the sheddable endpoints are shed.
+    threshold: f64,
+    /// The path prefixes of the sheddable endpoints.
+    sheddable_endpoints: Vec<String>,
+    /// The source of the system load.
+    monitor: Arc<dyn LoadMonitor>,
+}
+
+impl CircuitBreaker {
+    /// Initializes a new circuit breaker with the given load threshold, for the default sheddable endpoints.
+    pub fn new(threshold: f64) -> Result<Self> {
+        // Ensure the threshold is positive.
+        ensure!(threshold.is_finite() && threshold > 0.0, "The circuit breaker threshold must be positive");
+        Ok(Self {
+            threshold,
+            sheddable_endpoints: DEFAULT_SHEDDABLE_ENDPOINTS.iter().map(|path| path.to_string()).collect(),
+            monitor: Arc::new(SystemLoad),
+        })
+    }
+
+    /// Sets the path prefixes of the sheddable endpoints.
+    pub fn with_sheddable_endpoints(mut self, endpoints: impl IntoIterator<Item = impl Into<String>>) -> Self {
+        self.sheddable_endpoints = endpoints.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// Sets the source of the system load.
+    pub fn with_monitor(mut self, monitor: Arc<dyn LoadMonitor>) -> Self {
+        self.monitor = monitor;
+        self
+    }
+
+    /// Returns the system load above which the sheddable endpoints are shed.
+    pub const fn threshold(&self) -> f64 {
+        self.threshold
+    }
+
+    /// Returns the path prefixes of the sheddable endpoints.
+    pub fn sheddable_endpoints(&self) -> &[String] {
+        &self.sheddable_endpoints
+    }
+
+    /// Returns `true` if the given path is a sheddable endpoint.
+    pub fn is_sheddable(&self, path: &str) -> bool {
+        self.sheddable_endpoints.iter().any(|prefix| path.starts_with(prefix.as_str()))
+    }
+
+    /// Returns `true` if a request to the given path must be shed, under the current system load.
+    pub fn should_shed(&self, path: &str) -> bool {
+        // Note: The path is checked first, so that the load is not sampled for cheap endpoints.
+        self.is_sheddable(path) && self.monitor.load() > self.threshold
+    }
+}
+
+/// The middleware that sheds requests to the sheddable endpoints with `503 Service Unavailable`,
+/// while the circuit breaker (if one is set) is open.
+pub async fn circuit_breaker_middleware(
+    State(circuit_breaker): State<Arc<RwLock<Option<CircuitBreaker>>>>,
+    request: Request<Body>,
+    next: Next,
+) -> Result<Response, Response> {
+    // Retrieve the circuit breaker, so that the lock is not held across the request.
+    let circuit_breaker = circuit_breaker.read().clone();
+    // If the circuit breaker is open for the requested (read) endpoint, shed the request.
+    if let Some(circuit_breaker) = circuit_breaker {
+        if request.method() == Method::GET && circuit_breaker.should_shed(request.uri().path()) {
+            debug!("Shedding '{} {}' under high load", request.method(), request.uri());
+            return Err((StatusCode::SERVICE_UNAVAILABLE, "Node is under high load").into_response());
+        }
+    }
+
+    Ok(next.run(request).await)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{middleware, routing::get, Router};
+    use std::sync::atomic::{AtomicU64, Ordering};
+    use tower::ServiceExt;
+
+    /// A load monitor that returns a fixed load.
+    #[derive(Default)]
+    struct SampleLoad(AtomicU64);
+
+    impl SampleLoad {
+        fn set(&self, load: f64) {
+            self.0.store(load.to_bits(), Ordering::SeqCst);
+        }
+    }
+
+    impl LoadMonitor for SampleLoad {
+        fn load(&self) -> f64 {
+            f64::from_bits(self.0.load(Ordering::SeqCst))
+        }
+    }
+
+    /// Returns the status code of a GET request to the given path.
+    async fn status(router: &Router, path: &str) -> StatusCode {
+        let request = Request::builder().uri(path).body(Body::empty()).unwrap();
+        router.clone().oneshot(request).await.unwrap().status()
+    }
+
+    #[tokio::test]
+    async fn test_circuit_breaker_sheds_expensive_endpoints() {
+        // Initialize the circuit breaker, with a simulated load.
+        let load = Arc::new(SampleLoad::default());
+        let circuit_breaker = CircuitBreaker::new(0.8).unwrap().with_monitor(load.clone());
+        assert!(CircuitBreaker::new(0.0).is_err());
+        let state = Arc::new(RwLock::new(Some(circuit_breaker)));
+
+        // Initialize the router.
+        let router = Router::new()
+            .route("/testnet3/health", get(|| async { "OK" }))
+            .route("/testnet3/blocks", get(|| async { "[]" }))
+            .route("/testnet3/program/:id", get(|| async { "{}" }))
+            .layer(middleware::from_fn_with_state(state.clone(), circuit_breaker_middleware));
+
+        // Ensure every endpoint responds under low load.
+        load.set(0.5);
+        assert_eq!(status(&router, "/testnet3/health").await, StatusCode::OK);
+        assert_eq!(status(&router, "/testnet3/blocks").await, StatusCode::OK);
+        assert_eq!(status(&router, "/testnet3/program/credits.aleo").await, StatusCode::OK);
+
+        // Ensure the expensive endpoints are shed under high load, while the health endpoint still responds.
+        load.set(0.9);
+        assert_eq!(status(&router, "/testnet3/health").await, StatusCode::OK);
+        assert_eq!(status(&router, "/testnet3/blocks").await, StatusCode::SERVICE_UNAVAILABLE);
+        assert_eq!(status(&router, "/testnet3/program/credits.aleo").await, StatusCode::SERVICE_UNAVAILABLE);
+
+        // Ensure only the configured endpoints are shed.
+        let circuit_breaker =
+            CircuitBreaker::new(0.8).unwrap().with_monitor(load.clone()).with_sheddable_endpoints(["/testnet3/blocks"]);
+        *state.write() = Some(circuit_breaker);
+        assert_eq!(status(&router, "/testnet3/blocks").await, StatusCode::SERVICE_UNAVAILABLE);
+        assert_eq!(status(&router, "/testnet3/program/credits.aleo").await, StatusCode::OK);
+
+        // Ensure no endpoint is shed once the circuit breaker is removed.
+        *state.write() = None;
+        assert_eq!(status(&router, "/testnet3/blocks").await, StatusCode::OK);
+    }
+}
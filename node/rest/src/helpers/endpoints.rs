@@ -0,0 +1,165 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkOS library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use axum::{
+    body::Body,
+    extract::State,
+    http::{Request, StatusCode},
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use parking_lot::RwLock;
+use std::sync::Arc;
+
+/// The path prefixes of the endpoints that are disabled in the validator profile.
+/// A validator only needs to serve the endpoints used to monitor it, and to sync from it.
+pub const VALIDATOR_DISABLED_ENDPOINTS: &[&str] = &[
+    "/testnet3/dag/",
+    "/testnet3/find/",
+    "/testnet3/latest/",
+    "/testnet3/memoryPool/",
+    "/testnet3/peers/all/metrics",
+    "/testnet3/program/",
+    "/testnet3/statePath/",
+    "/testnet3/transaction/",
+];
+
+/// The path prefixes of the endpoints that are disabled in the read replica profile.
+/// A read replica only serves reads, and does not accept transmissions.
+pub const READ_REPLICA_DISABLED_ENDPOINTS: &[&str] =
+    &["/testnet3/solution/broadcast", "/testnet3/transaction/broadcast"];
+
+/// The predefined profiles of enabled endpoints.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub enum EndpointProfile {
+    /// Every endpoint is enabled, for a public RPC node.
+    #[default]
+    PublicRpc,
+    /// The heavy and unnecessary endpoints are disabled, for a validator.
+    Validator,
+    /// The write endpoints are disabled, for a read replica.
+    ReadReplica,
+}
+
+/// A filter of the REST endpoints that are disabled, by path prefix.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct EndpointFilter {
+    /// The path prefixes of the disabled endpoints.
+    disabled_endpoints: Vec<String>,
+}
+
+impl EndpointFilter {
+    /// Initializes a new endpoint filter for the given profile.
+    pub fn new(profile: EndpointProfile) -> Self {
+        match profile {
+            EndpointProfile::PublicRpc => Self::default(),
+            EndpointProfile::Validator => Self::default().with_disabled_endpoints(VALIDATOR_DISABLED_ENDPOINTS.iter()),
+            EndpointProfile::ReadReplica => {
+                Self::default().with_disabled_endpoints(READ_REPLICA_DISABLED_ENDPOINTS.iter())
+            }
+        }
+    }
+
+    /// Sets the path prefixes of the disabled endpoints (or endpoint groups, e.g. `/testnet3/program/`).
+    pub fn with_disabled_endpoints(mut self, endpoints: impl IntoIterator<Item = impl ToString>) -> Self {
+        self.disabled_endpoints = endpoints.into_iter().map(|path| path.to_string()).collect();
+        self
+    }
+
+    /// Enables the endpoints with the given path prefixes, removing them from the disabled endpoints.
+    pub fn with_enabled_endpoints(mut self, endpoints: impl IntoIterator<Item = impl ToString>) -> Self {
+        let enabled = endpoints.into_iter().map(|path| path.to_string()).collect::<Vec<_>>();
+        self.disabled_endpoints.retain(|path| !enabled.contains(path));
+        self
+    }
+
+    /// Returns the path prefixes of the disabled endpoints.
+    pub fn disabled_endpoints(&self) -> &[String] {
+        &self.disabled_endpoints
+    }
+
+    /// Returns `true` if the given path is a disabled endpoint.
+    pub fn is_disabled(&self, path: &str) -> bool {
+        self.disabled_endpoints.iter().any(|prefix| path.starts_with(prefix.as_str()))
+    }
+}
+
+/// The middleware that responds to requests for the disabled endpoints with `404 Not Found`,
+/// as if the endpoints were not served at all.
+pub async fn endpoint_filter_middleware(
+    State(endpoint_filter): State<Arc<RwLock<EndpointFilter>>>,
+    request: Request<Body>,
+    next: Next,
+) -> Result<Response, Response> {
+    // If the requested endpoint is disabled, reject the request.
+    if endpoint_filter.read().is_disabled(request.uri().path()) {
+        debug!("Rejecting '{} {}' (endpoint is disabled)", request.method(), request.uri());
+        return Err(StatusCode::NOT_FOUND.into_response());
+    }
+
+    Ok(next.run(request).await)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{middleware, routing::get, Router};
+    use tower::ServiceExt;
+
+    /// Returns the status code of a GET request to the given path.
+    async fn status(router: &Router, path: &str) -> StatusCode {
+        let request = Request::builder().uri(path).body(Body::empty()).unwrap();
+        router.clone().oneshot(request).await.unwrap().status()
+    }
+
+    #[tokio::test]
+    async fn test_disabled_endpoints_are_not_served() {
+        // Initialize the router, with every endpoint enabled.
+        let state = Arc::new(RwLock::new(EndpointFilter::new(EndpointProfile::PublicRpc)));
+        let router = Router::new()
+            .route("/testnet3/health", get(|| async { "OK" }))
+            .route("/testnet3/block/latest", get(|| async { "{}" }))
+            .route("/testnet3/program/:id", get(|| async { "{}" }))
+            .route("/testnet3/transaction/:id", get(|| async { "{}" }))
+            .layer(middleware::from_fn_with_state(state.clone(), endpoint_filter_middleware));
+
+        // Ensure every endpoint is served for a public RPC node.
+        assert_eq!(status(&router, "/testnet3/health").await, StatusCode::OK);
+        assert_eq!(status(&router, "/testnet3/block/latest").await, StatusCode::OK);
+        assert_eq!(status(&router, "/testnet3/program/credits.aleo").await, StatusCode::OK);
+        assert_eq!(status(&router, "/testnet3/transaction/at1").await, StatusCode::OK);
+
+        // Ensure the heavy endpoints are not served for a validator.
+        *state.write() = EndpointFilter::new(EndpointProfile::Validator);
+        assert_eq!(status(&router, "/testnet3/health").await, StatusCode::OK);
+        assert_eq!(status(&router, "/testnet3/block/latest").await, StatusCode::OK);
+        assert_eq!(status(&router, "/testnet3/program/credits.aleo").await, StatusCode::NOT_FOUND);
+        assert_eq!(status(&router, "/testnet3/transaction/at1").await, StatusCode::NOT_FOUND);
+
+        // Ensure the write endpoints are not served for a read replica, while the reads are.
+        *state.write() = EndpointFilter::new(EndpointProfile::ReadReplica);
+        assert_eq!(status(&router, "/testnet3/transaction/at1").await, StatusCode::OK);
+        assert!(state.read().is_disabled("/testnet3/transaction/broadcast"));
+
+        // Ensure an endpoint group can be re-enabled on top of a profile.
+        *state.write() = EndpointFilter::new(EndpointProfile::Validator).with_enabled_endpoints(["/testnet3/program/"]);
+        assert_eq!(status(&router, "/testnet3/program/credits.aleo").await, StatusCode::OK);
+        assert_eq!(status(&router, "/testnet3/transaction/at1").await, StatusCode::NOT_FOUND);
+
+        // Ensure individual endpoints can be disabled.
+        *state.write() = EndpointFilter::default().with_disabled_endpoints(["/testnet3/block/latest"]);
+        assert_eq!(status(&router, "/testnet3/block/latest").await, StatusCode::NOT_FOUND);
+        assert_eq!(status(&router, "/testnet3/program/credits.aleo").await, StatusCode::OK);
+    }
+}
@@ -12,8 +12,23 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+mod admin;
+pub use admin::*;
+
 mod auth;
 pub use auth::*;
 
+mod cache;
+pub use cache::*;
+
+mod circuit_breaker;
+pub use circuit_breaker::*;
+
+mod endpoints;
+pub use endpoints::*;
+
 mod error;
 pub use error::*;
+
+mod submissions;
+pub use submissions::*;
@@ -0,0 +1,181 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkOS library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::RestError;
+
+use axum::{
+    http::StatusCode,
+    response::{IntoResponse, Response},
+};
+use axum_extra::response::ErasedJson;
+use serde::Serialize;
+use std::{fmt, future::Future};
+
+/// The header of a broadcast response, which indicates the status of the submitted transmission.
+pub const SUBMISSION_STATUS_HEADER: &str = "x-submission-status";
+
+/// The status of a submitted transmission.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum SubmissionStatus {
+    /// The transmission is newly accepted.
+    Accepted,
+    /// The transmission is already in the memory pool, and is pending confirmation.
+    Pending,
+    /// The transmission is already confirmed in the ledger.
+    Confirmed,
+}
+
+impl SubmissionStatus {
+    /// Returns the name of the status.
+    pub const fn as_str(&self) -> &'static str {
+        match self {
+            Self::Accepted => "accepted",
+            Self::Pending => "pending",
+            Self::Confirmed => "confirmed",
+        }
+    }
+
+    /// Returns the existing status of a submitted transmission, if it is already confirmed in the ledger,
+    /// or in the memory pool. Otherwise, returns `None`, as the transmission is new.
+    pub const fn existing(is_confirmed: bool, is_pending: bool) -> Option<Self> {
+        match (is_confirmed, is_pending) {
+            (true, _) => Some(Self::Confirmed),
+            (false, true) => Some(Self::Pending),
+            (false, false) => None,
+        }
+    }
+}
+
+impl fmt::Display for SubmissionStatus {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+/// The handling of a duplicate submission, i.e. of a transmission that is already pending or confirmed.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub enum DuplicateSubmissionPolicy {
+    /// The submission succeeds with the response of the original submission, and its existing status,
+    /// so that retries are safe.
+    #[default]
+    Idempotent,
+    /// The submission is rejected with `409 Conflict`.
+    Reject,
+}
+
+/// Returns the broadcast response for the given transmission ID, with its status in the status header.
+/// Note: The body is the same for every status, so the response to a duplicate submission matches the original.
+pub fn submission_response(id: impl Serialize, status: SubmissionStatus) -> Response {
+    ([(SUBMISSION_STATUS_HEADER, status.as_str())], ErasedJson::pretty(id)).into_response()
+}
+
+/// Responds to the submission of the given transmission: if it already has the given existing status,
+/// with that status (under the given policy), and otherwise by admitting it with `admit`.
+pub async fn submit<K: Serialize + fmt::Display>(
+    id: K,
+    existing_status: Option<SubmissionStatus>,
+    policy: DuplicateSubmissionPolicy,
+    admit: impl Future<Output = anyhow::Result<()>>,
+) -> Result<Response, RestError> {
+    match (existing_status, policy) {
+        (Some(status), DuplicateSubmissionPolicy::Idempotent) => Ok(submission_response(id, status)),
+        (Some(status), DuplicateSubmissionPolicy::Reject) => {
+            Ok((StatusCode::CONFLICT, format!("Transaction '{id}' is already {status}")).into_response())
+        }
+        (None, _) => {
+            admit.await?;
+            Ok(submission_response(id, SubmissionStatus::Accepted))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{body::Body, extract::State, http::Request, routing::post, Json, Router};
+    use parking_lot::Mutex;
+    use std::{collections::HashSet, sync::Arc};
+    use tower::ServiceExt;
+
+    /// The memory pool and the ledger of a sample node.
+    #[derive(Clone, Default)]
+    struct SampleNode {
+        mempool: Arc<Mutex<HashSet<String>>>,
+        ledger: Arc<Mutex<HashSet<String>>>,
+    }
+
+    /// Broadcasts the given transaction ID to the sample node, as the broadcast route does.
+    async fn broadcast(State(node): State<SampleNode>, Json(id): Json<String>) -> Result<Response, RestError> {
+        let existing_status =
+            SubmissionStatus::existing(node.ledger.lock().contains(&id), node.mempool.lock().contains(&id));
+        let mempool = node.mempool.clone();
+        let id_ = id.clone();
+        submit(id, existing_status, DuplicateSubmissionPolicy::Idempotent, async move {
+            mempool.lock().insert(id_);
+            Ok(())
+        })
+        .await
+    }
+
+    /// Posts the given transaction ID to the broadcast route, and returns the status header and body.
+    async fn post_transaction(router: &Router, id: &str) -> (String, String) {
+        let request = Request::post("/testnet3/transaction/broadcast")
+            .header("content-type", "application/json")
+            .body(Body::from(serde_json::to_vec(id).unwrap()))
+            .unwrap();
+        let response = router.clone().oneshot(request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let status = response.headers()[SUBMISSION_STATUS_HEADER].to_str().unwrap().to_string();
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        (status, String::from_utf8(body.to_vec()).unwrap())
+    }
+
+    #[tokio::test]
+    async fn test_duplicate_submission_is_idempotent() {
+        let node = SampleNode::default();
+        let router = Router::new().route("/testnet3/transaction/broadcast", post(broadcast)).with_state(node.clone());
+
+        // Submit a transaction, and ensure it is accepted into the memory pool.
+        let (first_status, first_body) = post_transaction(&router, "at1abc").await;
+        assert_eq!(first_status, "accepted");
+        assert!(node.mempool.lock().contains("at1abc"));
+
+        // Submit the same transaction again, and ensure the response matches, with the existing status.
+        let (second_status, second_body) = post_transaction(&router, "at1abc").await;
+        assert_eq!(second_status, "pending");
+        assert_eq!(second_body, first_body);
+
+        // Ensure a transaction received from elsewhere (e.g. gossip) is recognized as pending.
+        node.mempool.lock().insert("at1def".to_string());
+        assert_eq!(post_transaction(&router, "at1def").await.0, "pending");
+
+        // Ensure a transaction is confirmed, once it is in the ledger.
+        node.mempool.lock().remove("at1abc");
+        node.ledger.lock().insert("at1abc".to_string());
+        assert_eq!(post_transaction(&router, "at1abc").await.0, "confirmed");
+
+        // Ensure a transaction that was dropped from the memory pool may be submitted again.
+        node.mempool.lock().remove("at1def");
+        assert_eq!(post_transaction(&router, "at1def").await.0, "accepted");
+    }
+
+    #[tokio::test]
+    async fn test_duplicate_submission_is_rejected() {
+        // Ensure the duplicate submission is rejected, without being admitted.
+        let admit = async { Err(anyhow::anyhow!("The duplicate submission must not be admitted")) };
+        let status = Some(SubmissionStatus::Pending);
+        let response = submit("at1abc", status, DuplicateSubmissionPolicy::Reject, admit).await.unwrap();
+        assert_eq!(response.status(), StatusCode::CONFLICT);
+    }
+}
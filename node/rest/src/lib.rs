@@ -45,7 +45,8 @@ use axum::{
     Json,
 };
 use axum_extra::response::ErasedJson;
-use parking_lot::Mutex;
+use axum_server::tls_rustls::RustlsConfig;
+use parking_lot::{Mutex, RwLock};
 use std::{net::SocketAddr, sync::Arc};
 use tokio::{net::TcpListener, task::JoinHandle};
 use tower_governor::{governor::GovernorConfigBuilder, GovernorLayer};
@@ -63,6 +64,14 @@ pub struct Rest<N: Network, C: ConsensusStorage<N>, R: Routing<N>> {
     ledger: Ledger<N, C>,
     /// The node (routing).
     routing: Arc<R>,
+    /// The circuit breaker for the expensive endpoints, if one is set.
+    circuit_breaker: Arc<RwLock<Option<CircuitBreaker>>>,
+    /// The filter of the disabled endpoints.
+    endpoint_filter: Arc<RwLock<EndpointFilter>>,
+    /// The cache of the responses of the hot read endpoints, if one is set.
+    response_cache: Arc<RwLock<Option<ResponseCache>>>,
+    /// The handling of a duplicate transaction submission.
+    duplicate_submission_policy: Arc<RwLock<DuplicateSubmissionPolicy>>,
     /// The server handles.
     handles: Arc<Mutex<Vec<JoinHandle<()>>>>,
 }
@@ -77,7 +86,16 @@ impl<N: Network, C: 'static + ConsensusStorage<N>, R: Routing<N>> Rest<N, C, R>
         routing: Arc<R>,
     ) -> Result<Self> {
         // Initialize the server.
-        let mut server = Self { consensus, ledger, routing, handles: Default::default() };
+        let mut server = Self {
+            consensus,
+            ledger,
+            routing,
+            circuit_breaker: Default::default(),
+            endpoint_filter: Default::default(),
+            response_cache: Default::default(),
+            duplicate_submission_policy: Default::default(),
+            handles: Default::default(),
+        };
         // Spawn the server.
         server.spawn_server(rest_ip, rest_rps).await;
         // Return the server.
@@ -95,9 +113,94 @@ impl<N: Network, C: ConsensusStorage<N>, R: Routing<N>> Rest<N, C, R> {
     pub const fn handles(&self) -> &Arc<Mutex<Vec<JoinHandle<()>>>> {
         &self.handles
     }
+
+    /// Returns the circuit breaker for the expensive endpoints, if one is set.
+    pub fn circuit_breaker(&self) -> Option<CircuitBreaker> {
+        self.circuit_breaker.read().clone()
+    }
+
+    /// Sets the circuit breaker, which sheds the expensive endpoints with `503` while the system is under high load.
+    /// If `None` is given, no endpoints are shed.
+    pub fn set_circuit_breaker(&self, circuit_breaker: Option<CircuitBreaker>) {
+        *self.circuit_breaker.write() = circuit_breaker;
+    }
+
+    /// Returns the filter of the disabled endpoints.
+    pub fn endpoint_filter(&self) -> EndpointFilter {
+        self.endpoint_filter.read().clone()
+    }
+
+    /// Sets the filter of the disabled endpoints, which respond with `404` instead of being served.
+    pub fn set_endpoint_filter(&self, endpoint_filter: EndpointFilter) {
+        *self.endpoint_filter.write() = endpoint_filter;
+    }
+
+    /// Returns the cache of the responses of the hot read endpoints, if one is set.
+    pub fn response_cache(&self) -> Option<ResponseCache> {
+        self.response_cache.read().clone()
+    }
+
+    /// Sets the cache of the responses of the hot read endpoints, whose responses are invalidated on a new block.
+    /// If `None` is given, no responses are cached.
+    pub fn set_response_cache(&self, response_cache: Option<ResponseCache>) {
+        let response_cache = response_cache.map(|cache| cache.with_state_version(Arc::new(self.ledger.clone())));
+        *self.response_cache.write() = response_cache;
+    }
+
+    /// Returns the handling of a duplicate transaction submission.
+    pub fn duplicate_submission_policy(&self) -> DuplicateSubmissionPolicy {
+        *self.duplicate_submission_policy.read()
+    }
+
+    /// Sets the handling of a duplicate transaction submission, i.e. of a transaction that is
+    /// already confirmed, or in the memory pool.
+    pub fn set_duplicate_submission_policy(&self, policy: DuplicateSubmissionPolicy) {
+        *self.duplicate_submission_policy.write() = policy;
+    }
+}
+
+impl<N: Network, C: ConsensusStorage<N>> StateVersion for Ledger<N, C> {
+    /// Returns the latest block height, as the version of the node state.
+    fn version(&self) -> u64 {
+        self.latest_height() as u64
+    }
 }
 
 impl<N: Network, C: ConsensusStorage<N>, R: Routing<N>> Rest<N, C, R> {
+    /// Starts the admin listener, which serves the admin endpoints over TLS, to the holders of the admin token.
+    /// Note: The admin endpoints are never served by the public REST server.
+    pub async fn start_admin(&self, config: AdminConfig) -> Result<()> {
+        let AdminConfig { listener_ip, token, tls_cert_path, tls_key_path } = config;
+        // Load the TLS certificate and private key.
+        let tls_config = RustlsConfig::from_pem_file(tls_cert_path, tls_key_path).await?;
+
+        let router = axum::Router::new()
+            .route("/admin/peers/disconnect", post(Self::admin_disconnect_peer))
+            .route("/admin/peers/ban", post(Self::admin_ban_peer))
+            .route("/admin/standby/promote", post(Self::admin_promote_standby))
+            .route("/admin/transactions/:id/pin", post(Self::admin_pin_transaction))
+            .route("/admin/proposal/preview", get(Self::admin_get_proposal_preview))
+            // Respond to the unknown endpoints behind the auth layer, so that their absence is not revealed.
+            .fallback(|| async { StatusCode::NOT_FOUND })
+            // Pass in `Rest` to make things convenient.
+            .with_state(self.clone())
+            // Reject the requests without a valid admin token.
+            .layer(middleware::from_fn_with_state(token, admin_auth_middleware))
+            // Enable tower-http tracing.
+            .layer(TraceLayer::new_for_http())
+            // Custom logging.
+            .layer(middleware::from_fn(log_middleware));
+
+        info!("Starting the admin listener at '{listener_ip}'");
+        self.handles.lock().push(tokio::spawn(async move {
+            axum_server::bind_rustls(listener_ip, tls_config)
+                .serve(router.into_make_service_with_connect_info::<SocketAddr>())
+                .await
+                .expect("couldn't start admin server");
+        }));
+        Ok(())
+    }
+
     async fn spawn_server(&mut self, rest_ip: SocketAddr, rest_rps: u32) {
         let cors = CorsLayer::new()
             .allow_origin(Any)
@@ -152,7 +255,9 @@ impl<N: Network, C: ConsensusStorage<N>, R: Routing<N>> Rest<N, C, R> {
             // GET and POST ../transaction/..
             .route("/testnet3/transaction/:id", get(Self::get_transaction))
             .route("/testnet3/transaction/confirmed/:id", get(Self::get_confirmed_transaction))
+            .route("/testnet3/transaction/inclusion/:id", get(Self::get_transaction_inclusion))
             .route("/testnet3/transaction/broadcast", post(Self::transaction_broadcast))
+            .route("/testnet3/transaction/replace/:replaced_id", post(Self::transaction_replace))
 
             // POST ../solution/broadcast
             // .route("/testnet3/solution/broadcast", post(Self::solution_broadcast))
@@ -179,12 +284,21 @@ impl<N: Network, C: ConsensusStorage<N>, R: Routing<N>> Rest<N, C, R> {
             .route("/testnet3/memoryPool/transmissions", get(Self::get_memory_pool_transmissions))
             // .route("/testnet3/memoryPool/solutions", get(Self::get_memory_pool_solutions))
             .route("/testnet3/memoryPool/transactions", get(Self::get_memory_pool_transactions))
+            .route("/testnet3/memoryPool/receipt/:id", get(Self::get_memory_pool_receipt))
+            .route("/testnet3/dag/dot", get(Self::get_dag_dot))
             .route("/testnet3/statePath/:commitment", get(Self::get_state_path_for_commitment))
             .route("/testnet3/stateRoot/latest", get(Self::get_state_root_latest))
             .route("/testnet3/committee/latest", get(Self::get_committee_latest))
+            .route("/testnet3/health", get(Self::get_health))
 
             // Pass in `Rest` to make things convenient.
             .with_state(self.clone())
+            // Shed the expensive endpoints under high load.
+            .layer(middleware::from_fn_with_state(self.circuit_breaker.clone(), circuit_breaker_middleware))
+            // Serve the hot read endpoints from the response cache.
+            .layer(middleware::from_fn_with_state(self.response_cache.clone(), response_cache_middleware))
+            // Reject the requests to the disabled endpoints.
+            .layer(middleware::from_fn_with_state(self.endpoint_filter.clone(), endpoint_filter_middleware))
             // Enable tower-http tracing.
             .layer(TraceLayer::new_for_http())
             // Custom logging.
@@ -15,7 +15,7 @@
 use super::*;
 use snarkos_node_router::messages::UnconfirmedSolution;
 use snarkvm::{
-    ledger::coinbase::ProverSolution,
+    ledger::{coinbase::ProverSolution, narwhal::TransmissionID},
     prelude::{block::Transaction, Identifier, Plaintext},
 };
 
@@ -33,12 +33,28 @@ pub(crate) struct BlockRange {
     end: u32,
 }
 
+/// The `get_dag_dot` query object.
+#[derive(Deserialize, Serialize)]
+pub(crate) struct DagRounds {
+    /// The number of most recent rounds to export.
+    rounds: Option<u64>,
+}
+
 /// The `get_mapping_value` query object.
 #[derive(Deserialize, Serialize)]
 pub(crate) struct Metadata {
     metadata: bool,
 }
 
+/// The admin peer request object.
+#[derive(Deserialize, Serialize)]
+pub(crate) struct AdminPeer {
+    /// The IP address and port of the peer.
+    ip: SocketAddr,
+    /// The duration of the ban in seconds, capped at the maximum ban lifetime (defaults to the maximum).
+    duration_in_secs: Option<u64>,
+}
+
 #[allow(dead_code)]
 impl<N: Network, C: ConsensusStorage<N>, R: Routing<N>> Rest<N, C, R> {
     // ----------------- DEPRECATED FUNCTIONS -----------------
@@ -201,6 +217,54 @@ impl<N: Network, C: ConsensusStorage<N>, R: Routing<N>> Rest<N, C, R> {
         }
     }
 
+    // GET /testnet3/memoryPool/receipt/{transactionID}
+    pub(crate) async fn get_memory_pool_receipt(
+        State(rest): State<Self>,
+        Path(tx_id): Path<N::TransactionID>,
+    ) -> Result<ErasedJson, RestError> {
+        match rest.consensus {
+            Some(consensus) => match consensus.admission_receipt(&tx_id) {
+                Some(receipt) => Ok(ErasedJson::pretty(receipt)),
+                None => Err(RestError(format!("Missing an admission receipt for transaction '{tx_id}'"))),
+            },
+            None => Err(RestError("Route isn't available for this node type".to_string())),
+        }
+    }
+
+    // GET /testnet3/transaction/inclusion/{transactionID}
+    pub(crate) async fn get_transaction_inclusion(
+        State(rest): State<Self>,
+        Path(tx_id): Path<N::TransactionID>,
+    ) -> Result<ErasedJson, RestError> {
+        let Some(consensus) = rest.consensus else {
+            return Err(RestError("Route isn't available for this node type".to_string()));
+        };
+        // Retrieve the height of the block that contains the transaction.
+        let Some(block_hash) = rest.ledger.find_block_hash(&tx_id)? else {
+            return Err(RestError(format!("Missing a block for transaction '{tx_id}'")));
+        };
+        let height = rest.ledger.get_height(&block_hash)?;
+        // Prove the transaction is committed by the leader certificate of the block.
+        let transmission_id = TransmissionID::Transaction(tx_id);
+        Ok(ErasedJson::pretty(consensus.bft().primary().prove_transmission_inclusion(height, transmission_id)?))
+    }
+
+    // GET /testnet3/dag/dot
+    // GET /testnet3/dag/dot?rounds={rounds}
+    pub(crate) async fn get_dag_dot(
+        State(rest): State<Self>,
+        dag_rounds: Option<Query<DagRounds>>,
+    ) -> Result<String, RestError> {
+        /// The default number of most recent rounds to export.
+        const DEFAULT_DAG_ROUNDS: u64 = 10;
+
+        let rounds = dag_rounds.and_then(|query| query.0.rounds).unwrap_or(DEFAULT_DAG_ROUNDS);
+        match rest.consensus {
+            Some(consensus) => Ok(consensus.bft().storage().export_dot(rounds)),
+            None => Err(RestError("Route isn't available for this node type".to_string())),
+        }
+    }
+
     // GET /testnet3/program/{programID}
     pub(crate) async fn get_program(
         State(rest): State<Self>,
@@ -257,6 +321,11 @@ impl<N: Network, C: ConsensusStorage<N>, R: Routing<N>> Rest<N, C, R> {
         Ok(ErasedJson::pretty(rest.ledger.latest_committee()?))
     }
 
+    // GET /testnet3/health
+    pub(crate) async fn get_health() -> ErasedJson {
+        ErasedJson::pretty("OK")
+    }
+
     // GET /testnet3/peers/count
     pub(crate) async fn get_peers_count(State(rest): State<Self>) -> ErasedJson {
         ErasedJson::pretty(rest.routing.router().number_of_connected_peers())
@@ -313,24 +382,61 @@ impl<N: Network, C: ConsensusStorage<N>, R: Routing<N>> Rest<N, C, R> {
     pub(crate) async fn transaction_broadcast(
         State(rest): State<Self>,
         Json(tx): Json<Transaction<N>>,
-    ) -> Result<ErasedJson, RestError> {
-        // If the consensus module is enabled, add the unconfirmed transaction to the memory pool.
-        if let Some(consensus) = rest.consensus {
-            // Add the unconfirmed transaction to the memory pool.
-            consensus.add_unconfirmed_transaction(tx.clone()).await?;
-        }
-
-        // Prepare the unconfirmed transaction message.
+    ) -> Result<Response, RestError> {
         let tx_id = tx.id();
-        let message = Message::UnconfirmedTransaction(UnconfirmedTransaction {
-            transaction_id: tx_id,
-            transaction: Data::Object(tx),
-        });
+        // If the transaction is already confirmed, or in the memory pool, respond with its existing status.
+        let is_confirmed = rest.ledger.contains_transaction_id(&tx_id)?;
+        let is_pending = rest.consensus.as_ref().map_or(false, |c| c.contains_unconfirmed_transaction(&tx_id));
+        let existing_status = SubmissionStatus::existing(is_confirmed, is_pending);
+
+        submit(tx_id, existing_status, rest.duplicate_submission_policy(), async move {
+            // If the consensus module is enabled, add the unconfirmed transaction to the memory pool.
+            if let Some(consensus) = &rest.consensus {
+                consensus.add_unconfirmed_transaction(tx.clone()).await?;
+            }
 
-        // Broadcast the transaction.
-        rest.routing.propagate(message, &[]);
+            // Prepare the unconfirmed transaction message.
+            let message = Message::UnconfirmedTransaction(UnconfirmedTransaction {
+                transaction_id: tx_id,
+                transaction: Data::Object(tx),
+            });
 
-        Ok(ErasedJson::pretty(tx_id))
+            // Broadcast the transaction.
+            rest.routing.propagate(message, &[]);
+            Ok(())
+        })
+        .await
+    }
+
+    // POST /testnet3/transaction/replace/{replacedID}
+    pub(crate) async fn transaction_replace(
+        State(rest): State<Self>,
+        Path(replaced_id): Path<N::TransactionID>,
+        Json(tx): Json<Transaction<N>>,
+    ) -> Result<Response, RestError> {
+        // Ensure the consensus module is enabled, as only a validator queues transactions to replace.
+        let Some(consensus) = rest.consensus.clone() else {
+            return Err(RestError("Route isn't available for this node type".to_string()));
+        };
+        let tx_id = tx.id();
+        // If the transaction is already confirmed, or in the memory pool, it is not a replacement.
+        let is_confirmed = rest.ledger.contains_transaction_id(&tx_id)?;
+        let is_pending = consensus.contains_unconfirmed_transaction(&tx_id);
+        let existing_status = SubmissionStatus::existing(is_confirmed, is_pending);
+
+        submit(tx_id, existing_status, DuplicateSubmissionPolicy::Reject, async move {
+            // Replace the queued transaction in the memory pool.
+            consensus.replace_unconfirmed_transaction(replaced_id, tx.clone()).await?;
+
+            // Broadcast the replacement transaction.
+            let message = Message::UnconfirmedTransaction(UnconfirmedTransaction {
+                transaction_id: tx_id,
+                transaction: Data::Object(tx),
+            });
+            rest.routing.propagate(message, &[]);
+            Ok(())
+        })
+        .await
     }
 
     // POST /testnet3/solution/broadcast
@@ -356,4 +462,62 @@ impl<N: Network, C: ConsensusStorage<N>, R: Routing<N>> Rest<N, C, R> {
 
         Ok(ErasedJson::pretty(commitment))
     }
+
+    // Note: The following endpoints are only served by the admin listener.
+
+    // POST /admin/peers/disconnect
+    pub(crate) async fn admin_disconnect_peer(State(rest): State<Self>, Json(peer): Json<AdminPeer>) -> ErasedJson {
+        info!("Disconnecting from '{}' (admin request)", peer.ip);
+        rest.routing.router().disconnect(peer.ip);
+        ErasedJson::pretty(peer.ip)
+    }
+
+    // POST /admin/peers/ban
+    pub(crate) async fn admin_ban_peer(State(rest): State<Self>, Json(peer): Json<AdminPeer>) -> ErasedJson {
+        let router = rest.routing.router();
+        // Determine the duration of the ban.
+        let duration = match peer.duration_in_secs {
+            Some(duration_in_secs) => std::time::Duration::from_secs(duration_in_secs),
+            None => router.ban_policy().max_lifetime(),
+        };
+        info!("Banning '{}' for {}s (admin request)", peer.ip, duration.as_secs());
+        router.insert_banned_peer(peer.ip, duration);
+        router.disconnect(peer.ip);
+        ErasedJson::pretty(peer.ip)
+    }
+
+    // POST /admin/standby/promote
+    pub(crate) async fn admin_promote_standby(State(rest): State<Self>) -> Result<ErasedJson, RestError> {
+        match rest.consensus {
+            Some(consensus) => {
+                info!("Promoting the standby primary (admin request)");
+                consensus.bft().primary().promote()?;
+                Ok(ErasedJson::pretty(!consensus.bft().primary().is_standby()))
+            }
+            None => Err(RestError("Route isn't available for this node type".to_string())),
+        }
+    }
+
+    // GET /admin/proposal/preview
+    pub(crate) async fn admin_get_proposal_preview(State(rest): State<Self>) -> Result<ErasedJson, RestError> {
+        match rest.consensus {
+            Some(consensus) => Ok(ErasedJson::pretty(consensus.bft().primary().current_proposal_preview())),
+            None => Err(RestError("Route isn't available for this node type".to_string())),
+        }
+    }
+
+    // POST /admin/transactions/{transactionID}/pin
+    pub(crate) async fn admin_pin_transaction(
+        State(rest): State<Self>,
+        Path(tx_id): Path<N::TransactionID>,
+    ) -> Result<ErasedJson, RestError> {
+        match rest.consensus {
+            Some(consensus) => {
+                info!("Pinning transaction '{tx_id}' (admin request)");
+                consensus.bft().primary().pin_transmission(&tx_id)?;
+                Ok(ErasedJson::pretty(tx_id))
+            }
+            None => Err(RestError("Route isn't available for this node type".to_string())),
+        }
+    }
 }
@@ -0,0 +1,65 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkOS library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::*;
+
+use snarkvm::prelude::{FromBytes, ToBytes};
+
+use std::borrow::Cow;
+
+/// A refusal of a block request, as the peer is serving too many sync requests.
+/// The requester may request the blocks again (e.g. from another peer), without waiting for the request to time out.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct BlockRefusal {
+    /// The original block request.
+    pub request: BlockRequest,
+}
+
+impl MessageTrait for BlockRefusal {
+    /// Returns the message name.
+    #[inline]
+    fn name(&self) -> Cow<'static, str> {
+        format!("BlockRefusal {}", self.request).into()
+    }
+}
+
+impl ToBytes for BlockRefusal {
+    fn write_le<W: io::Write>(&self, writer: W) -> io::Result<()> {
+        self.request.write_le(writer)
+    }
+}
+
+impl FromBytes for BlockRefusal {
+    fn read_le<R: io::Read>(reader: R) -> io::Result<Self> {
+        Ok(Self { request: BlockRequest::read_le(reader)? })
+    }
+}
+
+#[cfg(test)]
+pub mod prop_tests {
+    use crate::{block_request::prop_tests::any_block_request, BlockRefusal, BlockRequest};
+    use snarkvm::utilities::{FromBytes, ToBytes};
+
+    use bytes::{Buf, BufMut, BytesMut};
+    use test_strategy::proptest;
+
+    #[proptest]
+    fn block_refusal_roundtrip(#[strategy(any_block_request())] request: BlockRequest) {
+        let block_refusal = BlockRefusal { request };
+        let mut bytes = BytesMut::default().writer();
+        block_refusal.write_le(&mut bytes).unwrap();
+        let decoded = BlockRefusal::read_le(&mut bytes.into_inner().reader()).unwrap();
+        assert_eq![decoded, block_refusal];
+    }
+}
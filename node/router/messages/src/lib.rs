@@ -20,6 +20,9 @@ extern crate tracing;
 pub mod helpers;
 pub use helpers::*;
 
+mod block_refusal;
+pub use block_refusal::BlockRefusal;
+
 mod block_request;
 pub use block_request::BlockRequest;
 
@@ -101,6 +104,7 @@ pub enum Message<N: Network> {
     PuzzleResponse(PuzzleResponse<N>),
     UnconfirmedSolution(UnconfirmedSolution<N>),
     UnconfirmedTransaction(UnconfirmedTransaction<N>),
+    BlockRefusal(BlockRefusal),
 }
 
 impl<N: Network> From<DisconnectReason> for Message<N> {
@@ -111,7 +115,7 @@ impl<N: Network> From<DisconnectReason> for Message<N> {
 
 impl<N: Network> Message<N> {
     /// The version of the network protocol; it can be incremented in order to force users to update.
-    pub const VERSION: u32 = 15;
+    pub const VERSION: u32 = 16;
 
     /// Returns the message name.
     #[inline]
@@ -130,6 +134,7 @@ impl<N: Network> Message<N> {
             Self::PuzzleResponse(message) => message.name(),
             Self::UnconfirmedSolution(message) => message.name(),
             Self::UnconfirmedTransaction(message) => message.name(),
+            Self::BlockRefusal(message) => message.name(),
         }
     }
 
@@ -150,6 +155,7 @@ impl<N: Network> Message<N> {
             Self::PuzzleResponse(..) => 10,
             Self::UnconfirmedSolution(..) => 11,
             Self::UnconfirmedTransaction(..) => 12,
+            Self::BlockRefusal(..) => 13,
         }
     }
 }
@@ -172,6 +178,7 @@ impl<N: Network> ToBytes for Message<N> {
             Self::PuzzleResponse(message) => message.write_le(writer),
             Self::UnconfirmedSolution(message) => message.write_le(writer),
             Self::UnconfirmedTransaction(message) => message.write_le(writer),
+            Self::BlockRefusal(message) => message.write_le(writer),
         }
     }
 }
@@ -198,7 +205,8 @@ impl<N: Network> FromBytes for Message<N> {
             10 => Self::PuzzleResponse(PuzzleResponse::read_le(&mut reader)?),
             11 => Self::UnconfirmedSolution(UnconfirmedSolution::read_le(&mut reader)?),
             12 => Self::UnconfirmedTransaction(UnconfirmedTransaction::read_le(&mut reader)?),
-            13.. => return Err(error("Unknown message ID {id}")),
+            13 => Self::BlockRefusal(BlockRefusal::read_le(&mut reader)?),
+            14.. => return Err(error("Unknown message ID {id}")),
         };
 
         // Ensure that there are no "dangling" bytes.
@@ -268,6 +268,14 @@ impl<N: Network> Router<N> {
         if self.is_restricted(&peer_ip) {
             bail!("Dropping connection request from '{peer_ip}' (restricted)")
         }
+        // Ensure the peer is not banned.
+        if self.is_banned(&peer_ip) {
+            bail!("Dropping connection request from '{peer_ip}' (banned)")
+        }
+        // Ensure the peer is an upstream peer, if the node is a read replica.
+        if self.is_read_replica() && !self.trusted_peers().contains(&peer_ip) {
+            bail!("Dropping connection request from '{peer_ip}' (read replicas only connect to trusted peers)")
+        }
         // Ensure the peer is not spamming connection attempts.
         if !peer_ip.ip().is_loopback() {
             // Add this connection attempt and retrieve the number of attempts.
@@ -21,6 +21,7 @@ use snarkvm::prelude::Network;
 
 use colored::Colorize;
 use rand::{prelude::IteratorRandom, rngs::OsRng};
+use std::time::Instant;
 
 /// A helper function to compute the maximum of two numbers.
 /// See Rust issue 92391: https://github.com/rust-lang/rust/issues/92391.
@@ -50,6 +51,8 @@ pub trait Heartbeat<N: Network>: Outbound<N> {
         self.remove_stale_connected_peers();
         // Remove the oldest connected peer.
         self.remove_oldest_connected_peer();
+        // Rotate the oldest connected peer for a fresh peer, if peer rotation is enabled.
+        self.rotate_connected_peer();
         // Keep the number of connected peers within the allowed range.
         self.handle_connected_peers();
         // Keep the bootstrap peers within the allowed range.
@@ -127,6 +130,20 @@ pub trait Heartbeat<N: Network>: Outbound<N> {
         }
     }
 
+    /// This function rotates the oldest connected peer for a fresh candidate peer, to diversify the peer set.
+    /// This function only triggers if a peer rotation policy is set, and the rotation interval has elapsed.
+    fn rotate_connected_peer(&self) {
+        // Select the peer to rotate out, and the peer to connect to.
+        if let Some((oldest, fresh)) = self.router().select_peer_rotation(Instant::now()) {
+            info!("Disconnecting from '{oldest}' (rotating to '{fresh}')");
+            let _ = self.send(oldest, Message::Disconnect(DisconnectReason::PeerRefresh.into()));
+            // Disconnect from the oldest peer.
+            self.router().disconnect(oldest);
+            // Connect to the fresh peer.
+            self.router().connect(fresh);
+        }
+    }
+
     /// TODO (howardwu): If the node is a validator, keep the validator.
     /// This function keeps the number of connected peers within the allowed range.
     fn handle_connected_peers(&self) {
@@ -0,0 +1,155 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkOS library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use anyhow::{anyhow, Result};
+use std::{
+    collections::HashMap,
+    fs,
+    io::ErrorKind,
+    net::SocketAddr,
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+/// The policy for banning peers, with an optional file to persist the bans across restarts.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct BanPolicy {
+    /// The maximum lifetime of a ban.
+    max_lifetime: Duration,
+    /// The file path to persist the bans to, if persistence is enabled.
+    path: Option<PathBuf>,
+}
+
+impl Default for BanPolicy {
+    /// Initializes a new ban policy, with the default maximum lifetime and no persistence.
+    fn default() -> Self {
+        Self::new(Self::DEFAULT_MAX_LIFETIME)
+    }
+}
+
+impl BanPolicy {
+    /// The default maximum lifetime of a ban.
+    pub const DEFAULT_MAX_LIFETIME: Duration = Duration::from_secs(24 * 60 * 60); // 1 day
+
+    /// Initializes a new ban policy with the given maximum lifetime, and no persistence.
+    pub fn new(max_lifetime: Duration) -> Self {
+        Self { max_lifetime, path: None }
+    }
+
+    /// Sets the file path to persist the bans to.
+    pub fn with_path(mut self, path: impl Into<PathBuf>) -> Self {
+        self.path = Some(path.into());
+        self
+    }
+
+    /// Returns the maximum lifetime of a ban.
+    pub const fn max_lifetime(&self) -> Duration {
+        self.max_lifetime
+    }
+
+    /// Returns the file path to persist the bans to, if persistence is enabled.
+    pub fn path(&self) -> Option<&Path> {
+        self.path.as_deref()
+    }
+}
+
+/// The set of banned peer IPs, mapped to the UNIX timestamp (in seconds) at which their ban expires.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct BanList {
+    /// The map of banned peer IPs to their expiry timestamp.
+    bans: HashMap<SocketAddr, i64>,
+}
+
+impl BanList {
+    /// Loads the ban list from the given file, purging the bans that have expired as of `now`.
+    /// If the file does not exist, an empty ban list is returned, and malformed entries are skipped.
+    pub fn load(path: &Path, now: i64) -> Result<Self> {
+        let contents = match fs::read_to_string(path) {
+            Ok(contents) => contents,
+            Err(error) if error.kind() == ErrorKind::NotFound => return Ok(Self::default()),
+            Err(error) => return Err(anyhow!("Failed to read the ban list at '{}' - {error}", path.display())),
+        };
+        // Parse each line as '<peer_ip> <expiry>'.
+        let mut bans = HashMap::new();
+        for line in contents.lines().map(str::trim).filter(|line| !line.is_empty()) {
+            let entry = line.split_once(' ').and_then(|(peer_ip, expiry)| {
+                Some((peer_ip.parse::<SocketAddr>().ok()?, expiry.trim().parse::<i64>().ok()?))
+            });
+            match entry {
+                Some((peer_ip, expiry)) => {
+                    bans.insert(peer_ip, expiry);
+                }
+                None => warn!("Skipping a malformed entry in the ban list at '{}' - '{line}'", path.display()),
+            }
+        }
+        let mut ban_list = Self { bans };
+        // Purge the bans that have expired.
+        ban_list.purge_expired(now);
+        Ok(ban_list)
+    }
+
+    /// Saves the ban list to the given file. The list is written to a temporary file, which then replaces
+    /// the given file, so that the file is never left partially written.
+    pub fn save(&self, path: &Path) -> Result<()> {
+        let contents: String = self.bans.iter().map(|(peer_ip, expiry)| format!("{peer_ip} {expiry}\n")).collect();
+        let mut temp_path = path.as_os_str().to_owned();
+        temp_path.push(".tmp");
+        let temp_path = PathBuf::from(temp_path);
+        fs::write(&temp_path, contents)
+            .and_then(|()| fs::rename(&temp_path, path))
+            .map_err(|error| anyhow!("Failed to write the ban list at '{}' - {error}", path.display()))
+    }
+
+    /// Bans the given peer IP until the given UNIX timestamp (in seconds).
+    /// If the peer is already banned for longer, the existing ban is kept.
+    pub fn insert(&mut self, peer_ip: SocketAddr, expiry: i64) {
+        let entry = self.bans.entry(peer_ip).or_insert(expiry);
+        *entry = (*entry).max(expiry);
+    }
+
+    /// Removes the ban on the given peer IP, returning `true` if the peer was banned.
+    pub fn remove(&mut self, peer_ip: &SocketAddr) -> bool {
+        self.bans.remove(peer_ip).is_some()
+    }
+
+    /// Returns `true` if the given peer IP is banned as of `now`.
+    pub fn is_banned(&self, peer_ip: &SocketAddr, now: i64) -> bool {
+        self.bans.get(peer_ip).map_or(false, |expiry| now < *expiry)
+    }
+
+    /// Returns the UNIX timestamp (in seconds) at which the ban on the given peer IP expires, if it is banned.
+    pub fn expiry(&self, peer_ip: &SocketAddr) -> Option<i64> {
+        self.bans.get(peer_ip).copied()
+    }
+
+    /// Removes the bans that have expired as of `now`.
+    pub fn purge_expired(&mut self, now: i64) {
+        self.bans.retain(|_, expiry| now < *expiry);
+    }
+
+    /// Returns the number of banned peer IPs.
+    pub fn len(&self) -> usize {
+        self.bans.len()
+    }
+
+    /// Returns `true` if there are no banned peer IPs.
+    pub fn is_empty(&self) -> bool {
+        self.bans.is_empty()
+    }
+
+    /// Returns the banned peer IPs.
+    pub fn peers(&self) -> impl '_ + Iterator<Item = SocketAddr> {
+        self.bans.keys().copied()
+    }
+}
@@ -12,6 +12,9 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+mod bans;
+pub use bans::*;
+
 mod cache;
 pub use cache::Cache;
 
@@ -20,3 +23,9 @@ pub use peer::*;
 
 mod resolver;
 pub use resolver::*;
+
+mod rotation;
+pub use rotation::*;
+
+mod sync_limiter;
+pub use sync_limiter::*;
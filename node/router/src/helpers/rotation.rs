@@ -0,0 +1,71 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkOS library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::Peer;
+use snarkvm::prelude::Network;
+
+use std::{collections::HashSet, net::SocketAddr, time::Duration};
+
+/// The policy for periodically rotating the oldest connected peer for a fresh peer, to diversify the peer set.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RotationPolicy {
+    /// The interval between rotations, which is also the minimum age of a rotated connection.
+    interval: Duration,
+    /// The flag indicating whether validators (i.e. committee peers) are exempt from rotation.
+    exempt_validators: bool,
+    /// The peer IPs that are exempt from rotation.
+    exempt_peers: HashSet<SocketAddr>,
+}
+
+impl RotationPolicy {
+    /// Initializes a new rotation policy with the given interval, where validators are exempt.
+    pub fn new(interval: Duration) -> Self {
+        Self { interval, exempt_validators: true, exempt_peers: Default::default() }
+    }
+
+    /// Sets whether validators (i.e. committee peers) are exempt from rotation.
+    pub fn with_exempt_validators(mut self, exempt_validators: bool) -> Self {
+        self.exempt_validators = exempt_validators;
+        self
+    }
+
+    /// Sets the peer IPs that are exempt from rotation.
+    pub fn with_exempt_peers(mut self, exempt_peers: impl IntoIterator<Item = SocketAddr>) -> Self {
+        self.exempt_peers = exempt_peers.into_iter().collect();
+        self
+    }
+
+    /// Returns the interval between rotations.
+    pub const fn interval(&self) -> Duration {
+        self.interval
+    }
+
+    /// Returns `true` if validators (i.e. committee peers) are exempt from rotation.
+    pub const fn exempt_validators(&self) -> bool {
+        self.exempt_validators
+    }
+
+    /// Returns the peer IPs that are exempt from rotation.
+    pub const fn exempt_peers(&self) -> &HashSet<SocketAddr> {
+        &self.exempt_peers
+    }
+
+    /// Returns `true` if the given peer is exempt from rotation, where `is_committee_member` indicates
+    /// whether the address of the peer is in the current committee.
+    ///
+    /// note: The node type of a peer is self-declared, so validators are exempt by committee membership.
+    pub fn is_exempt<N: Network>(&self, peer: &Peer<N>, is_committee_member: bool) -> bool {
+        (self.exempt_validators && is_committee_member) || self.exempt_peers.contains(&peer.ip())
+    }
+}
@@ -0,0 +1,206 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkOS library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use anyhow::{ensure, Result};
+use parking_lot::Mutex;
+use std::{collections::VecDeque, sync::Arc};
+use tokio::sync::oneshot;
+
+/// The limits on the sync (i.e. block) requests that are served to peers at once.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct SyncRequestLimits {
+    /// The maximum number of sync requests that are served concurrently.
+    max_concurrent: usize,
+    /// The maximum number of sync requests that wait to be served, after which they are refused.
+    max_queued: usize,
+    /// The number of concurrent slots that are reserved for the committee peers.
+    reserved_for_committee: usize,
+}
+
+impl SyncRequestLimits {
+    /// Initializes new sync request limits.
+    pub fn new(max_concurrent: usize, max_queued: usize, reserved_for_committee: usize) -> Result<Self> {
+        ensure!(max_concurrent > 0, "The maximum number of concurrent sync requests must be nonzero");
+        ensure!(
+            reserved_for_committee < max_concurrent,
+            "The reserved sync request slots ({reserved_for_committee}) must be fewer than {max_concurrent}"
+        );
+        Ok(Self { max_concurrent, max_queued, reserved_for_committee })
+    }
+
+    /// Returns the maximum number of sync requests that are served concurrently.
+    pub const fn max_concurrent(&self) -> usize {
+        self.max_concurrent
+    }
+
+    /// Returns the maximum number of sync requests that wait to be served.
+    pub const fn max_queued(&self) -> usize {
+        self.max_queued
+    }
+
+    /// Returns the number of concurrent slots that are reserved for the committee peers.
+    pub const fn reserved_for_committee(&self) -> usize {
+        self.reserved_for_committee
+    }
+}
+
+/// The priority of a sync request, based on the peer that sent it.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum SyncPriority {
+    /// The request is from a committee (or trusted) peer.
+    Committee,
+    /// The request is from any other peer.
+    Other,
+}
+
+/// The outcome of a sync request admission.
+pub enum SyncAdmission {
+    /// The request may be served now, until the permit is dropped.
+    Admitted(SyncPermit),
+    /// The request is queued, and may be served once the permit is received.
+    Queued(oneshot::Receiver<SyncPermit>),
+    /// The request is refused, as the queue is full.
+    Refused,
+}
+
+/// Admits the sync requests from peers within the limits, serving the committee peers first.
+#[derive(Clone)]
+pub struct SyncRequestLimiter {
+    /// The sync request limits.
+    limits: SyncRequestLimits,
+    /// The admission state.
+    state: Arc<Mutex<State>>,
+}
+
+#[derive(Default)]
+struct State {
+    /// The number of sync requests being served.
+    active: usize,
+    /// The queued sync requests from the committee peers.
+    committee: VecDeque<oneshot::Sender<SyncPermit>>,
+    /// The queued sync requests from the other peers.
+    other: VecDeque<oneshot::Sender<SyncPermit>>,
+}
+
+impl SyncRequestLimiter {
+    /// Initializes a new sync request limiter with the given limits.
+    pub fn new(limits: SyncRequestLimits) -> Self {
+        Self { limits, state: Default::default() }
+    }
+
+    /// Returns the sync request limits.
+    pub const fn limits(&self) -> SyncRequestLimits {
+        self.limits
+    }
+
+    /// Returns the number of sync requests being served.
+    pub fn num_active(&self) -> usize {
+        self.state.lock().active
+    }
+
+    /// Returns the number of sync requests waiting to be served.
+    pub fn num_queued(&self) -> usize {
+        let state = self.state.lock();
+        state.committee.len() + state.other.len()
+    }
+
+    /// Admits a sync request with the given priority, queueing it if there is no free slot,
+    /// and refusing it if the queue is full.
+    ///
+    /// note: If the queue is full, a committee request displaces the newest queued request from another peer.
+    pub fn try_acquire(&self, priority: SyncPriority) -> SyncAdmission {
+        let mut state = self.state.lock();
+        // Admit the request, if a slot is free to its priority.
+        if self.has_free_slot(&state, priority) {
+            return SyncAdmission::Admitted(self.grant(&mut state));
+        }
+        // If the queue is full, refuse the request, unless it displaces a request of a lower priority.
+        if state.committee.len() + state.other.len() >= self.limits.max_queued {
+            match priority {
+                SyncPriority::Committee if state.other.pop_back().is_some() => (),
+                _ => return SyncAdmission::Refused,
+            }
+        }
+        // Queue the request.
+        let (sender, receiver) = oneshot::channel();
+        match priority {
+            SyncPriority::Committee => state.committee.push_back(sender),
+            SyncPriority::Other => state.other.push_back(sender),
+        }
+        SyncAdmission::Queued(receiver)
+    }
+
+    /// Waits until a sync request with the given priority may be served, returning `None` if it is refused.
+    pub async fn acquire(&self, priority: SyncPriority) -> Option<SyncPermit> {
+        match self.try_acquire(priority) {
+            SyncAdmission::Admitted(permit) => Some(permit),
+            SyncAdmission::Queued(receiver) => receiver.await.ok(),
+            SyncAdmission::Refused => None,
+        }
+    }
+
+    /// Returns `true` if a slot is free to a request with the given priority.
+    fn has_free_slot(&self, state: &State, priority: SyncPriority) -> bool {
+        match priority {
+            SyncPriority::Committee => state.active < self.limits.max_concurrent,
+            // Note: The other peers may not use the reserved slots, nor overtake the queued committee requests.
+            SyncPriority::Other => {
+                state.committee.is_empty()
+                    && state.active < self.limits.max_concurrent - self.limits.reserved_for_committee
+            }
+        }
+    }
+
+    /// Takes a slot for a sync request.
+    fn grant(&self, state: &mut State) -> SyncPermit {
+        state.active += 1;
+        SyncPermit { limiter: Some(self.clone()) }
+    }
+
+    /// Releases a slot, and grants the free slots to the queued requests (committee requests first).
+    fn release(&self) {
+        let mut state = self.state.lock();
+        state.active = state.active.saturating_sub(1);
+        loop {
+            let sender = if self.has_free_slot(&state, SyncPriority::Committee) && !state.committee.is_empty() {
+                state.committee.pop_front()
+            } else if self.has_free_slot(&state, SyncPriority::Other) {
+                state.other.pop_front()
+            } else {
+                None
+            };
+            let Some(sender) = sender else { break };
+            let permit = self.grant(&mut state);
+            // If the requester is gone, reclaim the slot (without dropping the permit under the lock).
+            if let Err(mut permit) = sender.send(permit) {
+                permit.limiter = None;
+                state.active -= 1;
+            }
+        }
+    }
+}
+
+/// A permit to serve a sync request, which releases its slot once dropped.
+pub struct SyncPermit {
+    /// The limiter that granted the permit.
+    limiter: Option<SyncRequestLimiter>,
+}
+
+impl Drop for SyncPermit {
+    fn drop(&mut self) {
+        if let Some(limiter) = self.limiter.take() {
+            limiter.release();
+        }
+    }
+}
@@ -14,9 +14,11 @@
 
 use crate::{
     messages::{
+        BlockRefusal,
         BlockRequest,
         BlockResponse,
         DataBlocks,
+        DisconnectReason,
         Message,
         PeerResponse,
         Ping,
@@ -82,12 +84,49 @@ pub trait Inbound<N: Network>: Reading + Outbound<N> {
                     bail!("Block request from '{peer_ip}' has an excessive range ({start_height}..{end_height})")
                 }
 
+                // If sync requests are limited, wait for a free slot in a separate task, serving the committee
+                // peers first. Note: The wait must not stall the other messages from this peer.
+                if let Some(limiter) = self.router().sync_request_limiter() {
+                    let priority = self.router().sync_priority(&peer_ip);
+                    let node = self.clone();
+                    tokio::spawn(async move {
+                        let Some(permit) = limiter.acquire(priority).await else {
+                            // Refuse the block request, so the peer retries it from another peer.
+                            debug!("Refusing a block request from '{peer_ip}' (too many sync requests, retry later)");
+                            Outbound::send(&node, peer_ip, Message::BlockRefusal(BlockRefusal { request: message }));
+                            return;
+                        };
+                        let node_ = node.clone();
+                        let is_valid = spawn_blocking(move || node_.block_request(peer_ip, message)).await;
+                        // Release the slot, once the block request is served.
+                        drop(permit);
+                        if !matches!(is_valid, Ok(true)) {
+                            warn!("Disconnecting from '{peer_ip}' - Peer '{peer_ip}' sent an invalid block request");
+                            let disconnect = Message::Disconnect(DisconnectReason::ProtocolViolation.into());
+                            Outbound::send(&node, peer_ip, disconnect);
+                            node.router().disconnect(peer_ip);
+                        }
+                    });
+                    return Ok(());
+                }
+
                 let node = self.clone();
                 match spawn_blocking(move || node.block_request(peer_ip, message)).await? {
                     true => Ok(()),
                     false => bail!("Peer '{peer_ip}' sent an invalid block request"),
                 }
             }
+            Message::BlockRefusal(message) => {
+                // Remove the block request, checking if this node previously sent a block request to this peer.
+                if !self.router().cache.remove_outbound_block_request(peer_ip, &message.request) {
+                    bail!("Peer '{peer_ip}' is not following the protocol (unexpected block refusal)")
+                }
+                // Process the block refusal.
+                match self.block_refusal(peer_ip, message.request) {
+                    true => Ok(()),
+                    false => bail!("Peer '{peer_ip}' sent an invalid block refusal"),
+                }
+            }
             Message::BlockResponse(message) => {
                 let BlockResponse { request, blocks } = message;
 
@@ -260,6 +299,9 @@ pub trait Inbound<N: Network>: Reading + Outbound<N> {
     /// Handles a `BlockResponse` message.
     fn block_response(&self, peer_ip: SocketAddr, _blocks: Vec<Block<N>>) -> bool;
 
+    /// Handles a `BlockRefusal` message.
+    fn block_refusal(&self, peer_ip: SocketAddr, _request: BlockRequest) -> bool;
+
     /// Handles a `PeerRequest` message.
     fn peer_request(&self, peer_ip: SocketAddr) -> bool {
         // Retrieve the connected peers.
@@ -40,22 +40,31 @@ pub use routing::*;
 
 use crate::messages::NodeType;
 use snarkos_account::Account;
-use snarkos_node_tcp::{is_bogon_ip, is_unspecified_or_broadcast_ip, Config, Tcp};
+use snarkos_node_tcp::{is_bogon_ip, is_unspecified_or_broadcast_ip, Config, OutboundBandwidth, Tcp};
 use snarkvm::prelude::{Address, Network, PrivateKey, ViewKey};
 
 use anyhow::{bail, Result};
 use parking_lot::{Mutex, RwLock};
+use rand::{prelude::IteratorRandom, rngs::OsRng};
 use std::{
     collections::{HashMap, HashSet},
     future::Future,
     net::SocketAddr,
     ops::Deref,
+    path::Path,
     str::FromStr,
-    sync::Arc,
-    time::Instant,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+    time::{Duration, Instant},
 };
+use time::OffsetDateTime;
 use tokio::task::JoinHandle;
 
+/// The check for whether an address is in the current committee.
+pub type CommitteeMembership<N> = Arc<dyn Fn(&Address<N>) -> bool + Send + Sync>;
+
 #[derive(Clone)]
 pub struct Router<N: Network>(Arc<InnerRouter<N>>);
 
@@ -91,6 +100,23 @@ pub struct InnerRouter<N: Network> {
     candidate_peers: RwLock<HashSet<SocketAddr>>,
     /// The set of restricted peer IPs.
     restricted_peers: RwLock<HashMap<SocketAddr, Instant>>,
+    /// The peer ban policy.
+    ban_policy: RwLock<BanPolicy>,
+    /// The list of banned peer IPs.
+    banned_peers: RwLock<BanList>,
+    /// The lock that orders the writes of the persisted ban list.
+    ban_file_lock: Mutex<()>,
+    /// The peer rotation policy, if peer rotation is enabled.
+    rotation_policy: RwLock<Option<RotationPolicy>>,
+    /// The timestamp of the last peer rotation.
+    last_rotation: RwLock<Instant>,
+    /// The check for whether an address is in the current committee, if the node tracks the committee.
+    committee_membership: RwLock<Option<CommitteeMembership<N>>>,
+    /// The limiter of the sync requests served to peers, if sync requests are limited.
+    sync_request_limiter: RwLock<Option<SyncRequestLimiter>>,
+    /// The flag indicating whether the node is a read replica (i.e. it only syncs from its trusted peers,
+    /// and does not gossip unconfirmed transmissions).
+    read_replica: AtomicBool,
     /// The spawned handles.
     handles: Mutex<Vec<JoinHandle<()>>>,
     /// The boolean flag for the development mode.
@@ -105,6 +131,10 @@ impl<N: Network> Router<N> {
     /// The duration in seconds after which a connected peer is considered inactive or
     /// disconnected if no message has been received in the meantime.
     const RADIO_SILENCE_IN_SECS: u64 = 150; // 2.5 minutes
+    /// The outbound bandwidth weight of a connected validator.
+    const VALIDATOR_BANDWIDTH_WEIGHT: u32 = 4;
+    /// The outbound bandwidth weight of a connected trusted peer.
+    const TRUSTED_PEER_BANDWIDTH_WEIGHT: u32 = 2;
 }
 
 impl<N: Network> Router<N> {
@@ -131,6 +161,14 @@ impl<N: Network> Router<N> {
             connecting_peers: Default::default(),
             candidate_peers: Default::default(),
             restricted_peers: Default::default(),
+            ban_policy: Default::default(),
+            ban_file_lock: Default::default(),
+            banned_peers: Default::default(),
+            rotation_policy: Default::default(),
+            last_rotation: RwLock::new(Instant::now()),
+            committee_membership: Default::default(),
+            sync_request_limiter: Default::default(),
+            read_replica: Default::default(),
             handles: Default::default(),
             is_dev,
         })))
@@ -183,6 +221,14 @@ impl<N: Network> Router<N> {
         if self.is_restricted(&peer_ip) {
             bail!("Dropping connection attempt to '{peer_ip}' (restricted)")
         }
+        // Ensure the peer is not banned.
+        if self.is_banned(&peer_ip) {
+            bail!("Dropping connection attempt to '{peer_ip}' (banned)")
+        }
+        // Ensure the peer is an upstream peer, if the node is a read replica.
+        if self.is_read_replica() && !self.trusted_peers.contains(&peer_ip) {
+            bail!("Dropping connection attempt to '{peer_ip}' (read replicas only connect to trusted peers)")
+        }
         // Ensure the node is not already connecting to this peer.
         if !self.connecting_peers.lock().insert(peer_ip) {
             bail!("Dropping connection attempt to '{peer_ip}' (already shaking hands as the initiator)")
@@ -251,6 +297,28 @@ impl<N: Network> Router<N> {
         self.is_dev
     }
 
+    /// Returns `true` if the node is a read replica.
+    pub fn is_read_replica(&self) -> bool {
+        self.read_replica.load(Ordering::SeqCst)
+    }
+
+    /// Sets whether the node is a read replica. A read replica only connects to (and syncs from) its trusted peers,
+    /// and does not gossip unconfirmed solutions or transactions.
+    pub fn set_read_replica(&self, read_replica: bool) -> Result<()> {
+        // Ensure the read replica has an upstream peer to sync from.
+        if read_replica && self.trusted_peers.is_empty() {
+            bail!("A read replica requires at least one trusted peer to sync from")
+        }
+        self.read_replica.store(read_replica, Ordering::SeqCst);
+        // Remove the candidate peers, as the read replica will not connect to them.
+        if read_replica {
+            self.candidate_peers.write().clear();
+            #[cfg(feature = "metrics")]
+            self.update_metrics();
+        }
+        Ok(())
+    }
+
     /// Returns the listener IP address from the (ambiguous) peer address.
     pub fn resolve_to_listener(&self, peer_addr: &SocketAddr) -> Option<SocketAddr> {
         self.resolver.get_listener(peer_addr)
@@ -295,6 +363,11 @@ impl<N: Network> Router<N> {
             .unwrap_or(false)
     }
 
+    /// Returns `true` if the given IP is banned.
+    pub fn is_banned(&self, ip: &SocketAddr) -> bool {
+        self.banned_peers.read().is_banned(ip, OffsetDateTime::now_utc().unix_timestamp())
+    }
+
     /// Returns the maximum number of connected peers.
     pub fn max_connected_peers(&self) -> usize {
         self.tcp.config().max_connections as usize
@@ -330,6 +403,11 @@ impl<N: Network> Router<N> {
         self.restricted_peers.read().len()
     }
 
+    /// Returns the number of banned peers.
+    pub fn number_of_banned_peers(&self) -> usize {
+        self.banned_peers.read().len()
+    }
+
     /// Returns the connected peer given the peer IP, if it exists.
     pub fn get_connected_peer(&self, ip: &SocketAddr) -> Option<Peer<N>> {
         self.connected_peers.read().get(ip).cloned()
@@ -370,6 +448,11 @@ impl<N: Network> Router<N> {
         self.restricted_peers.read().keys().copied().collect()
     }
 
+    /// Returns the list of banned peers.
+    pub fn banned_peers(&self) -> Vec<SocketAddr> {
+        self.banned_peers.read().peers().collect()
+    }
+
     /// Returns the list of trusted peers.
     pub fn trusted_peers(&self) -> &HashSet<SocketAddr> {
         &self.trusted_peers
@@ -377,7 +460,7 @@ impl<N: Network> Router<N> {
 
     /// Returns the list of bootstrap peers.
     pub fn bootstrap_peers(&self) -> Vec<SocketAddr> {
-        if cfg!(feature = "test") || self.is_dev {
+        if cfg!(feature = "test") || self.is_dev || self.is_read_replica() {
             vec![]
         } else {
             vec![
@@ -389,6 +472,133 @@ impl<N: Network> Router<N> {
         }
     }
 
+    /// Returns the cap on the total outbound rate (in bytes per second), if one is set.
+    pub fn max_outbound_bytes_per_sec(&self) -> Option<u64> {
+        self.tcp.bandwidth().max_bytes_per_sec()
+    }
+
+    /// Sets the cap on the total outbound rate (in bytes per second), which is shared across the connected peers
+    /// in proportion to their bandwidth weights. If `None` is given, the outbound rate is unlimited.
+    pub fn set_max_outbound_bytes_per_sec(&self, max_bytes_per_sec: Option<u64>) {
+        self.tcp.bandwidth().set_max_bytes_per_sec(max_bytes_per_sec);
+    }
+
+    /// Returns the outbound bandwidth weight of the given peer, from its role; validators are prioritized
+    /// over trusted peers, which are prioritized over the other peers.
+    pub fn bandwidth_weight(&self, peer_ip: &SocketAddr, node_type: NodeType) -> u32 {
+        if node_type.is_validator() {
+            Self::VALIDATOR_BANDWIDTH_WEIGHT
+        } else if self.trusted_peers.contains(peer_ip) {
+            Self::TRUSTED_PEER_BANDWIDTH_WEIGHT
+        } else {
+            OutboundBandwidth::DEFAULT_WEIGHT
+        }
+    }
+
+    /// Returns the peer rotation policy, if peer rotation is enabled.
+    pub fn rotation_policy(&self) -> Option<RotationPolicy> {
+        self.rotation_policy.read().clone()
+    }
+
+    /// Sets the peer rotation policy. If `None` is given, peer rotation is disabled.
+    pub fn set_rotation_policy(&self, rotation_policy: Option<RotationPolicy>) {
+        *self.rotation_policy.write() = rotation_policy;
+    }
+
+    /// Sets the check for whether an address is in the current committee. If `None` is given,
+    /// no peer is considered a committee member.
+    pub fn set_committee_membership(&self, committee_membership: Option<CommitteeMembership<N>>) {
+        *self.committee_membership.write() = committee_membership;
+    }
+
+    /// Returns `true` if the given address is in the current committee.
+    pub fn is_committee_member(&self, address: &Address<N>) -> bool {
+        self.committee_membership.read().as_ref().map_or(false, |is_member| is_member(address))
+    }
+
+    /// Returns the limiter of the sync requests served to peers, if sync requests are limited.
+    pub fn sync_request_limiter(&self) -> Option<SyncRequestLimiter> {
+        self.sync_request_limiter.read().clone()
+    }
+
+    /// Sets the limits on the sync requests served to peers. If `None` is given, sync requests are not limited.
+    ///
+    /// note: The sync requests already being served are not counted against the new limits.
+    pub fn set_sync_request_limits(&self, limits: Option<SyncRequestLimits>) {
+        *self.sync_request_limiter.write() = limits.map(SyncRequestLimiter::new);
+    }
+
+    /// Returns the priority of the sync requests from the given peer.
+    /// Note: The peer is prioritized by the committee membership of its (authenticated) address,
+    /// as the node type is declared by the peer itself.
+    pub fn sync_priority(&self, peer_ip: &SocketAddr) -> SyncPriority {
+        let is_committee_member =
+            self.get_connected_peer(peer_ip).map_or(false, |peer| self.is_committee_member(&peer.address()));
+        match is_committee_member || self.trusted_peers().contains(peer_ip) {
+            true => SyncPriority::Committee,
+            false => SyncPriority::Other,
+        }
+    }
+
+    /// Selects the oldest connected peer to rotate out, and the candidate peer to connect to in its place.
+    /// Returns `None` if peer rotation is disabled, the rotation interval has not elapsed as of `now`,
+    /// or there is no eligible peer to rotate.
+    pub fn select_peer_rotation(&self, now: Instant) -> Option<(SocketAddr, SocketAddr)> {
+        // Retrieve the rotation policy.
+        let policy = self.rotation_policy()?;
+        // Ensure the rotation interval has elapsed since the last rotation.
+        let mut last_rotation = self.last_rotation.write();
+        if now.saturating_duration_since(*last_rotation) < policy.interval() {
+            return None;
+        }
+
+        // Retrieve the bootstrap peers.
+        let bootstrap = self.bootstrap_peers();
+        // Find the oldest connected peer, that is not trusted, not a bootstrap peer, and not exempt.
+        let oldest = self
+            .get_connected_peers()
+            .into_iter()
+            .filter(|peer| !self.trusted_peers.contains(&peer.ip()) && !bootstrap.contains(&peer.ip()))
+            .filter(|peer| !policy.is_exempt(peer, self.is_committee_member(&peer.address())))
+            .filter(|peer| now.saturating_duration_since(peer.first_seen()) >= policy.interval())
+            .min_by_key(|peer| peer.first_seen())?
+            .ip();
+        // Select a fresh candidate peer to connect to.
+        let fresh = self.candidate_peers().into_iter().filter(|peer_ip| *peer_ip != oldest).choose(&mut OsRng)?;
+
+        // Update the timestamp of the last rotation.
+        *last_rotation = now;
+        Some((oldest, fresh))
+    }
+
+    /// Returns the peer ban policy.
+    pub fn ban_policy(&self) -> BanPolicy {
+        self.ban_policy.read().clone()
+    }
+
+    /// Sets the peer ban policy. If the policy persists bans, the bans are reloaded from disk,
+    /// purging any that have expired, and merged with the existing bans.
+    pub fn set_ban_policy(&self, ban_policy: BanPolicy) -> Result<()> {
+        if let Some(path) = ban_policy.path() {
+            let now = OffsetDateTime::now_utc().unix_timestamp();
+            // Load the persisted bans.
+            let persisted = BanList::load(path, now)?;
+            let mut banned_peers = self.banned_peers.write();
+            for peer_ip in persisted.peers() {
+                if let Some(expiry) = persisted.expiry(&peer_ip) {
+                    // Ensure the reloaded ban does not exceed the maximum lifetime.
+                    let max_expiry = now.saturating_add(ban_policy.max_lifetime().as_secs() as i64);
+                    banned_peers.insert(peer_ip, expiry.min(max_expiry));
+                }
+            }
+            banned_peers.purge_expired(now);
+        }
+        *self.ban_policy.write() = ban_policy;
+        #[cfg(feature = "metrics")]
+        self.update_metrics();
+        Ok(())
+    }
+
     /// Returns the list of metrics for the connected peers.
     pub fn connected_metrics(&self) -> Vec<(SocketAddr, NodeType)> {
         self.connected_peers.read().iter().map(|(ip, peer)| (*ip, peer.node_type())).collect()
@@ -399,6 +609,7 @@ impl<N: Network> Router<N> {
         metrics::gauge(metrics::router::CONNECTED, self.connected_peers.read().len() as f64);
         metrics::gauge(metrics::router::CANDIDATE, self.candidate_peers.read().len() as f64);
         metrics::gauge(metrics::router::RESTRICTED, self.restricted_peers.read().len() as f64);
+        metrics::gauge(metrics::router::BANNED, self.banned_peers.read().len() as f64);
     }
 
     /// Inserts the given peer into the connected peers.
@@ -406,6 +617,8 @@ impl<N: Network> Router<N> {
         let peer_ip = peer.ip();
         // Adds a bidirectional map between the listener address and (ambiguous) peer address.
         self.resolver.insert_peer(peer_ip, peer_addr);
+        // Assign the outbound bandwidth weight of the peer, on its (ambiguous) connection address.
+        self.tcp.bandwidth().set_weight(peer_addr, self.bandwidth_weight(&peer_ip, peer.node_type()));
         // Add an entry for this `Peer` in the connected peers.
         self.connected_peers.write().insert(peer_ip, peer);
         // Remove this peer from the candidate peers, if it exists.
@@ -421,14 +634,21 @@ impl<N: Network> Router<N> {
     /// This method skips adding any given peers if the combined size exceeds the threshold,
     /// as the peer providing this list could be subverting the protocol.
     pub fn insert_candidate_peers(&self, peers: &[SocketAddr]) {
+        // If the node is a read replica, skip the candidate peers, as it only connects to its trusted peers.
+        if self.is_read_replica() {
+            return;
+        }
         // Compute the maximum number of candidate peers.
         let max_candidate_peers = Self::MAXIMUM_CANDIDATE_PEERS.saturating_sub(self.number_of_candidate_peers());
         // Ensure the combined number of peers does not surpass the threshold.
         let eligible_peers = peers
             .iter()
             .filter(|peer_ip| {
-                // Ensure the peer is not itself, is not already connected, and is not restricted.
-                !self.is_local_ip(peer_ip) && !self.is_connected(peer_ip) && !self.is_restricted(peer_ip)
+                // Ensure the peer is not itself, is not already connected, and is not restricted or banned.
+                !self.is_local_ip(peer_ip)
+                    && !self.is_connected(peer_ip)
+                    && !self.is_restricted(peer_ip)
+                    && !self.is_banned(peer_ip)
             })
             .take(max_candidate_peers);
 
@@ -448,6 +668,51 @@ impl<N: Network> Router<N> {
         self.update_metrics();
     }
 
+    /// Bans the given peer IP for the given duration, capped at the maximum ban lifetime.
+    /// If the ban policy persists bans, the ban list is saved to disk.
+    pub fn insert_banned_peer(&self, peer_ip: SocketAddr, duration: Duration) {
+        let ban_policy = self.ban_policy();
+        let now = OffsetDateTime::now_utc().unix_timestamp();
+        // Compute the expiry of the ban.
+        let duration = duration.min(ban_policy.max_lifetime());
+        let expiry = now.saturating_add(duration.as_secs() as i64);
+        // Remove this peer from the candidate peers, if it exists.
+        self.candidate_peers.write().remove(&peer_ip);
+        // Add the peer to the banned peers.
+        let mut banned_peers = self.banned_peers.write();
+        banned_peers.purge_expired(now);
+        banned_peers.insert(peer_ip, expiry);
+        drop(banned_peers);
+        // Persist the bans, if enabled.
+        if let Some(path) = ban_policy.path() {
+            self.persist_banned_peers(path);
+        }
+        #[cfg(feature = "metrics")]
+        self.update_metrics();
+    }
+
+    /// Removes the ban on the given peer IP, if it exists.
+    pub fn remove_banned_peer(&self, peer_ip: SocketAddr) {
+        if self.banned_peers.write().remove(&peer_ip) {
+            // Persist the bans, if enabled.
+            if let Some(path) = self.ban_policy().path() {
+                self.persist_banned_peers(path);
+            }
+        }
+        #[cfg(feature = "metrics")]
+        self.update_metrics();
+    }
+
+    /// Saves a snapshot of the banned peers to the given file, without holding the lock on the banned peers.
+    fn persist_banned_peers(&self, path: &Path) {
+        // Note: The snapshot is taken under the file lock, so that an older snapshot never overwrites a newer one.
+        let _file_lock = self.ban_file_lock.lock();
+        let banned_peers = self.banned_peers.read().clone();
+        if let Err(error) = banned_peers.save(path) {
+            warn!("{error}");
+        }
+    }
+
     /// Updates the connected peer with the given function.
     pub fn update_connected_peer<Fn: FnMut(&mut Peer<N>)>(
         &self,
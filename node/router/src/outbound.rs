@@ -79,6 +79,10 @@ pub trait Outbound<N: Network>: Writing<Message = Message<N>> {
 
     /// Sends the given message to every connected peer, excluding the sender and any specified peer IPs.
     fn propagate(&self, message: Message<N>, excluded_peers: &[SocketAddr]) {
+        // If the node is a read replica, do not gossip unconfirmed solutions or transactions.
+        if self.router().is_read_replica() && is_unconfirmed_transmission(&message) {
+            return;
+        }
         // TODO (howardwu): Serialize large messages once only.
         // // Perform ahead-of-time, non-blocking serialization just once for applicable objects.
         // if let Message::UnconfirmedSolution(ref mut message) = message {
@@ -107,6 +111,10 @@ pub trait Outbound<N: Network>: Writing<Message = Message<N>> {
 
     /// Sends the given message to every connected validator, excluding the sender and any specified IPs.
     fn propagate_to_validators(&self, message: Message<N>, excluded_peers: &[SocketAddr]) {
+        // If the node is a read replica, do not gossip unconfirmed solutions or transactions.
+        if self.router().is_read_replica() && is_unconfirmed_transmission(&message) {
+            return;
+        }
         // TODO (howardwu): Serialize large messages once only.
         // // Perform ahead-of-time, non-blocking serialization just once for applicable objects.
         // if let Message::UnconfirmedSolution(ref mut message) = message {
@@ -160,3 +168,8 @@ pub trait Outbound<N: Network>: Writing<Message = Message<N>> {
         }
     }
 }
+
+/// Returns `true` if the message is an unconfirmed solution or transaction.
+fn is_unconfirmed_transmission<N: Network>(message: &Message<N>) -> bool {
+    matches!(message, Message::UnconfirmedSolution(_) | Message::UnconfirmedTransaction(_))
+}
@@ -0,0 +1,80 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkOS library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+mod common;
+use common::*;
+
+use snarkos_node_router::{BanList, BanPolicy};
+
+use core::time::Duration;
+use std::net::SocketAddr;
+
+#[tokio::test]
+async fn test_ban_persists_across_restart() {
+    const MAX_LIFETIME: Duration = Duration::from_secs(60 * 60);
+
+    let path = std::env::temp_dir().join(format!("snarkos-test-bans-{}", std::process::id()));
+    let _ = std::fs::remove_file(&path);
+    let peer_ip: SocketAddr = "1.1.1.1:4130".parse().unwrap();
+
+    // Initialize the router, and ban the peer for longer than the maximum lifetime.
+    let node = client(0, 10).await;
+    node.set_ban_policy(BanPolicy::new(MAX_LIFETIME).with_path(&path)).unwrap();
+    node.insert_banned_peer(peer_ip, 2 * MAX_LIFETIME);
+    assert!(node.is_banned(&peer_ip));
+    // Ensure the peer is not admitted as a candidate peer.
+    node.insert_candidate_peers(&[peer_ip]);
+    assert_eq!(node.number_of_candidate_peers(), 0);
+    node.shut_down().await;
+
+    // Restart the router, and ensure the ban is still enforced.
+    let node = client(0, 10).await;
+    assert!(!node.is_banned(&peer_ip));
+    node.set_ban_policy(BanPolicy::new(MAX_LIFETIME).with_path(&path)).unwrap();
+    assert!(node.is_banned(&peer_ip));
+    assert!(node.connect(peer_ip).is_none());
+
+    // Ensure the ban was capped at the maximum lifetime, and is purged on load once expired.
+    let now = time::OffsetDateTime::now_utc().unix_timestamp();
+    let expiry = BanList::load(&path, now).unwrap().expiry(&peer_ip).unwrap();
+    assert!(expiry <= now + MAX_LIFETIME.as_secs() as i64);
+    assert!(BanList::load(&path, now).unwrap().is_banned(&peer_ip, expiry - 1));
+    assert!(BanList::load(&path, expiry).unwrap().is_empty());
+
+    // Ensure the ban can be lifted.
+    node.remove_banned_peer(peer_ip);
+    assert!(!node.is_banned(&peer_ip));
+    assert!(BanList::load(&path, now).unwrap().is_empty());
+
+    let _ = std::fs::remove_file(&path);
+}
+
+#[test]
+fn test_ban_list_skips_malformed_entries() {
+    let path = std::env::temp_dir().join(format!("snarkos-test-bans-malformed-{}", std::process::id()));
+    let peer_ip: SocketAddr = "1.1.1.1:4130".parse().unwrap();
+
+    // Ensure a malformed entry does not discard the valid entries.
+    std::fs::write(&path, "1.1.1.1:4130 100\nnot-an-ip 100\n2.2.2.2:4130\n3.3.3.3:4130 soon\n").unwrap();
+    let ban_list = BanList::load(&path, 0).unwrap();
+    assert_eq!(ban_list.len(), 1);
+    assert!(ban_list.is_banned(&peer_ip, 0));
+
+    // Ensure the saved list replaces the file, without leaving the temporary file behind.
+    ban_list.save(&path).unwrap();
+    assert_eq!(BanList::load(&path, 0).unwrap(), ban_list);
+    assert!(!path.with_file_name(format!("{}.tmp", path.file_name().unwrap().to_str().unwrap())).exists());
+
+    let _ = std::fs::remove_file(&path);
+}
@@ -163,6 +163,11 @@ impl<N: Network> Inbound<N> for TestRouter<N> {
         true
     }
 
+    /// Handles a `BlockRefusal` message.
+    fn block_refusal(&self, _peer_ip: SocketAddr, _request: BlockRequest) -> bool {
+        true
+    }
+
     /// Handles an `Ping` message.
     fn ping(&self, _peer_ip: SocketAddr, _message: Ping<N>) -> bool {
         true
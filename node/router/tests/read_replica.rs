@@ -0,0 +1,65 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkOS library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+mod common;
+use common::*;
+
+use snarkos_node_router::{messages::NodeType, Router};
+use snarkos_node_tcp::P2P;
+
+use core::time::Duration;
+use std::net::{IpAddr, Ipv4Addr, SocketAddr};
+
+#[tokio::test]
+async fn test_read_replica_only_connects_to_trusted_peers() {
+    // Initialize the upstream node, and an untrusted node.
+    let upstream = validator(0, 2).await;
+    let untrusted = client(0, 2).await;
+    upstream.tcp().enable_listener().await.unwrap();
+    untrusted.tcp().enable_listener().await.unwrap();
+
+    // Ensure a read replica requires a trusted peer.
+    let node = client(0, 2).await;
+    assert!(node.set_read_replica(true).is_err());
+    assert!(!node.is_read_replica());
+
+    // Initialize the read replica, with the upstream node as its trusted peer.
+    let replica: TestRouter<_> = Router::new(
+        SocketAddr::new(IpAddr::V4(Ipv4Addr::LOCALHOST), 0),
+        NodeType::Client,
+        sample_account(),
+        &[upstream.local_ip()],
+        2,
+        true,
+    )
+    .await
+    .unwrap()
+    .into();
+    replica.insert_candidate_peers(&[untrusted.local_ip()]);
+    replica.set_read_replica(true).unwrap();
+    assert!(replica.is_read_replica());
+    // Ensure the candidate peers were cleared, and are no longer admitted.
+    assert_eq!(replica.number_of_candidate_peers(), 0);
+    replica.insert_candidate_peers(&[untrusted.local_ip()]);
+    assert_eq!(replica.number_of_candidate_peers(), 0);
+    assert!(replica.bootstrap_peers().is_empty());
+
+    // Ensure the read replica only connects to the upstream node.
+    assert!(replica.connect(untrusted.local_ip()).is_none());
+    assert!(replica.connect(upstream.local_ip()).is_some());
+    // Sleep briefly.
+    tokio::time::sleep(Duration::from_millis(100)).await;
+    assert_eq!(replica.tcp().num_connected(), 1);
+    assert_eq!(untrusted.tcp().num_connected(), 0);
+}
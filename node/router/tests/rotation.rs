@@ -0,0 +1,91 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkOS library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+mod common;
+use common::*;
+
+use snarkos_node_router::{
+    messages::{ChallengeRequest, NodeType},
+    Peer,
+    RotationPolicy,
+};
+use snarkvm::prelude::Testnet3 as CurrentNetwork;
+
+use core::time::Duration;
+use std::{net::SocketAddr, sync::Arc, time::Instant};
+
+/// Returns a connected peer with the given IP and node type.
+fn sample_peer(peer_ip: SocketAddr, node_type: NodeType) -> Peer<CurrentNetwork> {
+    let challenge_request = ChallengeRequest {
+        version: 1,
+        listener_port: peer_ip.port(),
+        node_type,
+        address: sample_account().address(),
+        nonce: 0,
+    };
+    Peer::new(peer_ip, &challenge_request)
+}
+
+#[tokio::test]
+async fn test_rotate_oldest_client_connection() {
+    const INTERVAL: Duration = Duration::from_secs(60);
+
+    // Initialize the router.
+    let node = client(0, 10).await;
+    let start = Instant::now();
+
+    // Connect to an old client, and an old validator.
+    let client_ip: SocketAddr = "1.1.1.1:4130".parse().unwrap();
+    let validator_ip: SocketAddr = "2.2.2.2:4130".parse().unwrap();
+    let validator = sample_peer(validator_ip, NodeType::Validator);
+    let validator_address = validator.address();
+    node.insert_connected_peer(validator, validator_ip);
+    node.insert_connected_peer(sample_peer(client_ip, NodeType::Client), client_ip);
+    // Discover a fresh peer.
+    let fresh_ip: SocketAddr = "3.3.3.3:4130".parse().unwrap();
+    node.insert_candidate_peers(&[fresh_ip]);
+
+    // Ensure no peer is rotated while rotation is disabled.
+    assert!(node.select_peer_rotation(start + 2 * INTERVAL).is_none());
+
+    // Enable peer rotation.
+    node.set_rotation_policy(Some(RotationPolicy::new(INTERVAL)));
+    // Ensure no peer is rotated before the interval has elapsed.
+    assert!(node.select_peer_rotation(start + INTERVAL / 2).is_none());
+
+    // Advance past the interval, and ensure the old client is rotated out for the fresh peer.
+    let now = start + INTERVAL + Duration::from_secs(1);
+    assert_eq!(node.select_peer_rotation(now), Some((client_ip, fresh_ip)));
+    // Ensure no peer is rotated again until the next interval has elapsed.
+    assert!(node.select_peer_rotation(now + INTERVAL / 2).is_none());
+
+    // Ensure the validator is not exempt by its self-declared node type alone.
+    node.remove_connected_peer(client_ip);
+    assert_eq!(node.select_peer_rotation(now + 2 * INTERVAL).map(|(oldest, _)| oldest), Some(validator_ip));
+
+    // Ensure the validator is exempt from rotation, once it is in the committee.
+    node.set_committee_membership(Some(Arc::new(move |address| *address == validator_address)));
+    assert!(node.select_peer_rotation(now + 4 * INTERVAL).is_none());
+
+    // Ensure the validator is rotated, once validators are no longer exempt.
+    node.set_rotation_policy(Some(RotationPolicy::new(INTERVAL).with_exempt_validators(false)));
+    let (oldest, _) = node.select_peer_rotation(now + 6 * INTERVAL).unwrap();
+    assert_eq!(oldest, validator_ip);
+
+    // Ensure an exempt peer is never rotated.
+    node.set_rotation_policy(Some(
+        RotationPolicy::new(INTERVAL).with_exempt_validators(false).with_exempt_peers([validator_ip]),
+    ));
+    assert!(node.select_peer_rotation(now + 8 * INTERVAL).is_none());
+}
@@ -0,0 +1,147 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkOS library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+mod common;
+use common::*;
+
+use snarkos_node_router::{
+    messages::NodeType,
+    Router,
+    SyncAdmission,
+    SyncPriority,
+    SyncRequestLimiter,
+    SyncRequestLimits,
+};
+
+use core::time::Duration;
+use parking_lot::Mutex;
+use std::{
+    net::{IpAddr, Ipv4Addr, SocketAddr},
+    sync::{
+        atomic::{AtomicUsize, Ordering::*},
+        Arc,
+    },
+    time::Instant,
+};
+
+#[test]
+fn test_sync_request_admission() {
+    // Ensure the limits are validated.
+    assert!(SyncRequestLimits::new(0, 1, 0).is_err());
+    assert!(SyncRequestLimits::new(2, 1, 2).is_err());
+
+    // Serve up to 2 requests at once, with 1 slot reserved for the committee, and queue up to 2 requests.
+    let limiter = SyncRequestLimiter::new(SyncRequestLimits::new(2, 2, 1).unwrap());
+
+    // Ensure another peer is admitted into the unreserved slot, and then queued.
+    let SyncAdmission::Admitted(other_permit) = limiter.try_acquire(SyncPriority::Other) else { panic!() };
+    let SyncAdmission::Queued(mut displaced) = limiter.try_acquire(SyncPriority::Other) else { panic!() };
+    // Ensure a committee peer is admitted into the reserved slot, and then queued.
+    let SyncAdmission::Admitted(committee_permit) = limiter.try_acquire(SyncPriority::Committee) else { panic!() };
+    let SyncAdmission::Queued(mut first) = limiter.try_acquire(SyncPriority::Committee) else { panic!() };
+    assert_eq!(limiter.num_active(), 2);
+    assert_eq!(limiter.num_queued(), 2);
+
+    // Ensure another peer is refused, once the queue is full.
+    assert!(matches!(limiter.try_acquire(SyncPriority::Other), SyncAdmission::Refused));
+    // Ensure a committee peer displaces the queued request of another peer, once the queue is full.
+    let SyncAdmission::Queued(mut second) = limiter.try_acquire(SyncPriority::Committee) else { panic!() };
+    assert!(displaced.try_recv().is_err());
+    assert_eq!(limiter.num_queued(), 2);
+
+    // Ensure the queued committee requests are served in order, as the slots are released.
+    drop(other_permit);
+    let first_permit = first.try_recv().unwrap();
+    assert!(second.try_recv().is_err());
+    drop(committee_permit);
+    let second_permit = second.try_recv().unwrap();
+    assert_eq!(limiter.num_active(), 2);
+    assert_eq!(limiter.num_queued(), 0);
+
+    // Ensure the slots are released once the requests are served.
+    drop((first_permit, second_permit));
+    assert_eq!(limiter.num_active(), 0);
+}
+
+#[tokio::test]
+async fn test_sync_requests_are_limited_and_prioritized() {
+    const MAX_CONCURRENT: usize = 3;
+    const NUM_REQUESTERS: usize = 20;
+    const SERVE_TIME: Duration = Duration::from_millis(50);
+
+    // Initialize the router, with a trusted peer.
+    let trusted_ip: SocketAddr = "1.1.1.1:4130".parse().unwrap();
+    let node: TestRouter<_> = Router::new(
+        SocketAddr::new(IpAddr::V4(Ipv4Addr::LOCALHOST), 0),
+        NodeType::Validator,
+        sample_account(),
+        &[trusted_ip],
+        2,
+        true,
+    )
+    .await
+    .unwrap()
+    .into();
+    assert!(node.sync_request_limiter().is_none());
+    assert_eq!(node.sync_priority(&trusted_ip), SyncPriority::Committee);
+    assert_eq!(node.sync_priority(&"2.2.2.2:4130".parse().unwrap()), SyncPriority::Other);
+
+    // Limit the sync requests.
+    node.set_sync_request_limits(Some(SyncRequestLimits::new(MAX_CONCURRENT, 2 * NUM_REQUESTERS, 1).unwrap()));
+    let limiter = node.sync_request_limiter().unwrap();
+
+    // Hold every slot, so that the requesters are queued at once.
+    let held = (0..MAX_CONCURRENT)
+        .map(|i| {
+            let priority = if i == 0 { SyncPriority::Committee } else { SyncPriority::Other };
+            let SyncAdmission::Admitted(permit) = limiter.try_acquire(priority) else { panic!() };
+            permit
+        })
+        .collect::<Vec<_>>();
+
+    // Send many simultaneous sync requests, from committee peers and other peers alike.
+    let start = Instant::now();
+    let (active, max_active) = (Arc::new(AtomicUsize::new(0)), Arc::new(AtomicUsize::new(0)));
+    let waits = Arc::new(Mutex::new(Vec::new()));
+    let tasks = (0..NUM_REQUESTERS)
+        .map(|i| {
+            let priority = if i % 2 == 0 { SyncPriority::Committee } else { SyncPriority::Other };
+            let (limiter, waits) = (limiter.clone(), waits.clone());
+            let (active, max_active) = (active.clone(), max_active.clone());
+            tokio::spawn(async move {
+                let _permit = limiter.acquire(priority).await.unwrap();
+                waits.lock().push((priority, start.elapsed()));
+                max_active.fetch_max(active.fetch_add(1, SeqCst) + 1, SeqCst);
+                tokio::time::sleep(SERVE_TIME).await;
+                active.fetch_sub(1, SeqCst);
+            })
+        })
+        .collect::<Vec<_>>();
+    while limiter.num_queued() < NUM_REQUESTERS {
+        tokio::task::yield_now().await;
+    }
+    drop(held);
+    for task in tasks {
+        task.await.unwrap();
+    }
+
+    // Ensure no more than the limit of sync requests were served at once.
+    assert!(max_active.load(SeqCst) <= MAX_CONCURRENT);
+    assert_eq!(limiter.num_active(), 0);
+    // Ensure every committee request was served before any request from another peer.
+    let waits = waits.lock();
+    let last_committee = waits.iter().filter(|(p, _)| *p == SyncPriority::Committee).map(|(_, w)| *w).max().unwrap();
+    let first_other = waits.iter().filter(|(p, _)| *p == SyncPriority::Other).map(|(_, w)| *w).min().unwrap();
+    assert!(last_committee <= first_other, "{waits:?}");
+}
@@ -14,10 +14,10 @@
 
 mod router;
 
-use crate::traits::NodeInterface;
+use crate::{traits::NodeInterface, NodeConfig};
 use snarkos_account::Account;
 use snarkos_node_bft::ledger_service::CoreLedgerService;
-use snarkos_node_rest::Rest;
+use snarkos_node_rest::{AdminConfig, EndpointFilter, EndpointProfile, Rest};
 use snarkos_node_router::{
     messages::{Message, NodeType, UnconfirmedSolution},
     Heartbeat,
@@ -42,7 +42,7 @@ use snarkvm::{
 };
 
 use aleo_std::StorageMode;
-use anyhow::Result;
+use anyhow::{bail, Result};
 use core::future::Future;
 use parking_lot::Mutex;
 use std::{
@@ -83,6 +83,7 @@ impl<N: Network, C: ConsensusStorage<N>> Client<N, C> {
         genesis: Block<N>,
         cdn: Option<String>,
         storage_mode: StorageMode,
+        config: NodeConfig,
     ) -> Result<Self> {
         // Prepare the shutdown flag.
         let shutdown: Arc<AtomicBool> = Default::default();
@@ -100,7 +101,7 @@ impl<N: Network, C: ConsensusStorage<N>> Client<N, C> {
             if let Err((_, error)) =
                 snarkos_node_cdn::sync_ledger_with_cdn(&base_url, ledger.clone(), shutdown.clone()).await
             {
-                crate::log_clean_error(&storage_mode);
+                crate::log_clean_error(&storage_mode, config.alert_notifier.as_ref()).await;
                 return Err(error);
             }
         }
@@ -120,6 +121,13 @@ impl<N: Network, C: ConsensusStorage<N>> Client<N, C> {
             matches!(storage_mode, StorageMode::Development(_)),
         )
         .await?;
+        // Apply the configurations to the router.
+        config.apply_to_router(&router)?;
+        // Recognize the committee members among the peers, by the latest committee in the ledger.
+        let ledger_ = ledger.clone();
+        router.set_committee_membership(Some(Arc::new(move |address| {
+            ledger_.latest_committee().map_or(false, |committee| committee.is_committee_member(*address))
+        })));
         // Load the coinbase puzzle.
         let coinbase_puzzle = CoinbasePuzzle::<N>::load()?;
         // Initialize the node.
@@ -136,7 +144,14 @@ impl<N: Network, C: ConsensusStorage<N>> Client<N, C> {
 
         // Initialize the REST server.
         if let Some(rest_ip) = rest_ip {
-            node.rest = Some(Rest::start(rest_ip, rest_rps, None, ledger.clone(), Arc::new(node.clone())).await?);
+            let rest = Rest::start(rest_ip, rest_rps, None, ledger.clone(), Arc::new(node.clone())).await?;
+            // Apply the configurations to the REST server.
+            config.apply_to_rest(&rest)?;
+            node.rest = Some(rest);
+        }
+        // Run the client as a read replica, if configured, before the routing is started.
+        if config.read_replica {
+            node.enable_read_replica()?;
         }
         // Initialize the routing.
         node.initialize_routing().await;
@@ -159,6 +174,27 @@ impl<N: Network, C: ConsensusStorage<N>> Client<N, C> {
     pub fn rest(&self) -> &Option<Rest<N, C, Self>> {
         &self.rest
     }
+
+    /// Starts the admin listener of the REST server, which serves the admin endpoints over TLS.
+    pub async fn enable_admin(&self, config: AdminConfig) -> Result<()> {
+        match &self.rest {
+            Some(rest) => rest.start_admin(config).await,
+            None => bail!("The admin listener requires the REST server"),
+        }
+    }
+
+    /// Turns the client into a read replica, that syncs blocks from its trusted peers and serves the REST
+    /// read endpoints, but neither gossips unconfirmed transmissions, nor accepts them via REST.
+    fn enable_read_replica(&self) -> Result<()> {
+        // Restrict the router to the trusted peers, and disable gossip.
+        self.router.set_read_replica(true)?;
+        // Disable the REST write endpoints.
+        if let Some(rest) = &self.rest {
+            rest.set_endpoint_filter(EndpointFilter::new(EndpointProfile::ReadReplica));
+        }
+        info!("Client is running as a read replica of {:?}", self.router.trusted_peers());
+        Ok(())
+    }
 }
 
 impl<N: Network, C: ConsensusStorage<N>> Client<N, C> {
@@ -0,0 +1,333 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkOS library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use snarkos_account::Account;
+use snarkos_node_bft::{
+    helpers::{
+        AdmissionHook,
+        AdmissionOrder,
+        AdmissionQueue,
+        AlertNotifier,
+        AsnTable,
+        CacheWindow,
+        CertificatePenaltyPolicy,
+        GCMemoryPolicy,
+        ReplacementPolicy,
+        RetryPolicy,
+        RiskPolicy,
+        SenderRateLimit,
+        StaleProposalPolicy,
+        UnknownProgramPolicy,
+        WatchdogAction,
+        WebhookScorer,
+        WorkerAffinity,
+    },
+    Worker,
+};
+use snarkos_node_consensus::{
+    BlockExporter,
+    Compression,
+    Consensus,
+    ExportCursor,
+    FileBlockSink,
+    FileIndexSink,
+    IndexBlockSink,
+    MaintenanceWindow,
+};
+use snarkos_node_rest::{CircuitBreaker, DuplicateSubmissionPolicy, ResponseCache, Rest};
+use snarkos_node_router::{BanPolicy, Router, Routing, SyncRequestLimits};
+use snarkvm::prelude::{store::ConsensusStorage, Network};
+
+use anyhow::Result;
+use std::{fs::File, io::BufReader, net::IpAddr, path::PathBuf, sync::Arc, time::Duration};
+
+/// The number of committed blocks that are buffered for export.
+const BLOCK_EXPORT_BUFFER_SIZE: usize = 1024; // blocks
+
+/// The destination to which committed blocks are exported.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum BlockExportTarget {
+    /// Each block is appended as a line of JSON to the file at the given path.
+    File(PathBuf),
+    /// Each block is published to the given Kafka topic, on the given (comma-separated) brokers.
+    #[cfg(feature = "kafka")]
+    Kafka { brokers: String, topic: String },
+}
+
+impl BlockExportTarget {
+    /// Initializes a new block exporter to the target.
+    fn exporter<N: Network>(&self) -> Result<BlockExporter<N>> {
+        match self {
+            Self::File(path) => BlockExporter::new(FileBlockSink::open(path)?, BLOCK_EXPORT_BUFFER_SIZE),
+            #[cfg(feature = "kafka")]
+            Self::Kafka { brokers, topic } => BlockExporter::new(
+                snarkos_node_consensus::KafkaBlockSink::new(brokers, topic)?,
+                BLOCK_EXPORT_BUFFER_SIZE,
+            ),
+        }
+    }
+}
+
+/// The optional configurations of a node, which are applied before the node is started.
+/// Note: The default configuration leaves every optional behavior disabled.
+#[derive(Clone, Debug, Default)]
+pub struct NodeConfig {
+    /// The policy for retaining extra rounds in the BFT storage, based on memory pressure.
+    pub gc_memory_policy: Option<GCMemoryPolicy>,
+    /// If `true`, the primary runs as a warm standby, until it is promoted through the admin listener.
+    pub standby: bool,
+    /// The destination to which committed blocks are exported, if any.
+    pub block_export: Option<BlockExportTarget>,
+    /// The path of the file to which the incremental address-to-transaction index is exported, if any.
+    /// Note: The export cursor is persisted alongside it, with the `.cursor` suffix.
+    pub index_export: Option<PathBuf>,
+    /// The maximum number of transmissions from any single submitter in a batch, if fairness is enabled.
+    pub max_transmissions_per_submitter: Option<usize>,
+    /// The maximum number of transmissions that can be pinned in each worker, if pinning is enabled.
+    pub max_pinned_transmissions: Option<usize>,
+    /// The retry policy for broadcasting our certificates to peers, if not the default.
+    pub certificate_retry_policy: Option<RetryPolicy>,
+    /// The maximum number of rounds ahead of the latest committed round to buffer, if not the default.
+    pub max_open_rounds: Option<u64>,
+    /// If `true`, the workers sign admission receipts for the unconfirmed transactions they admit.
+    pub admission_receipts: bool,
+    /// The external scorer that is consulted on the admission of transmissions, its timeout, and whether
+    /// transmissions are admitted when it fails or times out, if any.
+    pub admission_scorer: Option<(WebhookScorer, Duration, bool)>,
+    /// The system load above which the REST server sheds its expensive endpoints, if any.
+    pub rest_shed_load_threshold: Option<f64>,
+    /// The TTL of the cached responses of the hot read endpoints, if the REST response cache is enabled.
+    pub rest_cache_ttl: Option<Duration>,
+    /// If `true`, the REST server rejects the transactions that are already pending or confirmed.
+    pub rest_reject_duplicates: bool,
+    /// The fraction of committee stake that must be connected, below which the primary is partitioned, if any.
+    pub partition_threshold: Option<f64>,
+    /// The webhook notifier for the critical node events, if alerting is enabled.
+    pub alert_notifier: Option<AlertNotifier>,
+    /// The window of the events cached by the gateway for rate limiting, if not the default.
+    pub cache_events_window: Option<CacheWindow>,
+    /// The window of the requests cached by the gateway for deduplication, if not the default.
+    pub cache_requests_window: Option<CacheWindow>,
+    /// The count of an insert above which a cached key is a likely spam source, if the spam hits are counted.
+    pub cache_spam_threshold: Option<usize>,
+    /// The workers dedicated to each transmission type, if any.
+    pub worker_affinity: Option<WorkerAffinity>,
+    /// The maximum time between the heartbeats of the critical tasks, if the watchdog is enabled.
+    pub watchdog_timeout: Option<Duration>,
+    /// The action of the watchdog on a stalled task.
+    pub watchdog_action: WatchdogAction,
+    /// The maximum number of transactions from any single sender, and the interval (in seconds) over which they
+    /// are counted, if the senders are rate limited.
+    pub sender_rate_limit: Option<(usize, i64)>,
+    /// The maximum number of certificates in a DAG snapshot served to the validators, if serving is enabled.
+    pub max_dag_snapshot_certificates: Option<usize>,
+    /// If `true`, the DAG is warmed up with a snapshot from a connected validator, once the ledger is synced.
+    pub dag_snapshot_warm_up: bool,
+    /// The IPs of the probes (e.g. health checks), which are excluded from the spam cache and the peer metrics.
+    pub probe_sources: Vec<IpAddr>,
+    /// The minimum number of previous certificates for a batch to reference, beyond the quorum (`0` for the quorum).
+    pub min_previous_certificates: usize,
+    /// The handling of the batch proposals that reference rounds at or below the GC round.
+    pub stale_proposal_policy: StaleProposalPolicy,
+    /// The maximum fraction of stake that may change in a committee transition, above which an alert is raised.
+    pub max_committee_change: Option<f64>,
+    /// The minimum fee bump (as a percentage) of a replacement transaction, if transaction replacement is enabled.
+    pub replacement_fee_bump_percent: Option<u64>,
+    /// The handling of unconfirmed transactions that reference programs unknown to the ledger, if any.
+    pub unknown_program_policy: Option<UnknownProgramPolicy>,
+    /// The admission order and the capacity of the ready queue, if its admission is bounded.
+    pub admission_queue: Option<(AdmissionOrder, usize)>,
+    /// The retention window of the transactions that are dropped for a full ready queue, if they are retained.
+    pub transient_rejection_window: Option<Duration>,
+    /// The policy for banning peers, if not the default.
+    pub ban_policy: Option<BanPolicy>,
+    /// The cap on the total outbound rate (in bytes per second) to the peers, if any.
+    pub max_outbound_bytes_per_sec: Option<u64>,
+    /// The limits on the sync requests served to peers, if sync requests are limited.
+    pub sync_request_limits: Option<SyncRequestLimits>,
+    /// If `true`, the client runs as a read replica of its trusted peers.
+    pub read_replica: bool,
+    /// The high and low consensus loads between which the deferrable background tasks are paused, and the names
+    /// of the deferrable tasks, if the maintenance window is enabled.
+    pub maintenance_window: Option<(usize, usize, Vec<String>)>,
+    /// The compression of the entries written to the persistent BFT store, if any.
+    pub bft_store_compression: Option<Compression>,
+    /// The write latency beyond which the persistent BFT store buffers its writes in memory, if degrading is enabled.
+    pub bft_store_degraded_threshold: Option<Duration>,
+    /// The graduated penalties for peers that send malformed certificates, if the penalties are enabled.
+    pub certificate_penalty_policy: Option<CertificatePenaltyPolicy>,
+    /// The maximum number of recent connection attempts from each ASN, if the limit is enabled.
+    pub max_connections_per_asn: Option<usize>,
+    /// The maximum number of recent connection attempts from each subnet, if the limit is enabled.
+    pub max_connections_per_subnet: Option<usize>,
+    /// The path to the IP-to-ASN dataset by which the connections are grouped, if any.
+    pub asn_dataset: Option<PathBuf>,
+    /// The graduated actions for peers by their combined risk score, if the risk score is enabled.
+    pub risk_policy: Option<RiskPolicy>,
+}
+
+impl NodeConfig {
+    /// Applies the configurations to the given consensus, before it is started.
+    pub(crate) fn apply_to_consensus<N: Network>(&self, consensus: &Consensus<N>) -> Result<()> {
+        // Set the GC memory policy of the BFT storage.
+        consensus.bft().storage().set_gc_memory_policy(self.gc_memory_policy)?;
+        // Set the primary as a warm standby, if configured.
+        if self.standby {
+            consensus.bft().primary().enable_standby();
+        }
+        // Set the block exporter, if configured.
+        if let Some(target) = &self.block_export {
+            consensus.set_block_exporter(target.exporter()?)?;
+        }
+        // Set the index exporter, resuming from its cursor, if configured.
+        if let Some(path) = &self.index_export {
+            let mut cursor_path = path.as_os_str().to_owned();
+            cursor_path.push(".cursor");
+            let sink = IndexBlockSink::new(FileIndexSink::open(path)?);
+            let cursor = ExportCursor::new(cursor_path);
+            consensus.set_index_exporter(BlockExporter::new_with_cursor(sink, BLOCK_EXPORT_BUFFER_SIZE, cursor)?)?;
+        }
+        // Set the retry policy for broadcasting certificates, if configured.
+        if let Some(policy) = self.certificate_retry_policy {
+            consensus.bft().primary().set_certificate_retry_policy(policy);
+        }
+        // Set the maximum number of open rounds, if configured.
+        if let Some(max_open_rounds) = self.max_open_rounds {
+            consensus.bft().primary().set_max_open_rounds(max_open_rounds)?;
+        }
+        // Set the partition threshold of the primary.
+        consensus.bft().primary().set_partition_threshold(self.partition_threshold)?;
+        // Set the maintenance window for the deferrable background tasks, if configured.
+        if let Some((high_load, low_load, tasks)) = &self.maintenance_window {
+            let maintenance_window = MaintenanceWindow::new(*high_load, *low_load)?.with_deferrable_tasks(tasks);
+            consensus.set_maintenance_window(maintenance_window)?;
+        }
+        // Set the penalties for peers that send malformed certificates.
+        consensus.bft().primary().gateway().set_certificate_penalty_policy(self.certificate_penalty_policy);
+        // Set the graduated actions for peers by their combined risk score.
+        consensus.bft().primary().gateway().set_risk_policy(self.risk_policy);
+        // Set the per-group connection limits, and the ASN dataset by which the connections are grouped.
+        consensus.bft().primary().gateway().set_max_connections_per_asn(self.max_connections_per_asn)?;
+        consensus.bft().primary().gateway().set_max_connections_per_subnet(self.max_connections_per_subnet)?;
+        if let Some(path) = &self.asn_dataset {
+            let asn_table = AsnTable::from_reader(BufReader::new(File::open(path)?))?;
+            consensus.bft().primary().gateway().set_asn_lookup(Some(Arc::new(asn_table)));
+        }
+        // Set the webhook notifier for the critical events.
+        consensus.bft().primary().set_alert_notifier(self.alert_notifier.clone());
+        // Set the windows of the gateway cache, if configured.
+        if let Some(window) = self.cache_events_window {
+            consensus.bft().primary().gateway().set_cache_events_window(window)?;
+        }
+        if let Some(window) = self.cache_requests_window {
+            consensus.bft().primary().gateway().set_cache_requests_window(window)?;
+        }
+        consensus.bft().primary().gateway().set_cache_spam_threshold(self.cache_spam_threshold);
+        // Set the workers dedicated to each transmission type, if configured.
+        if let Some(affinity) = &self.worker_affinity {
+            consensus.bft().primary().gateway().set_worker_affinity(affinity.clone());
+        }
+        // Set the watchdog of the critical tasks.
+        consensus.bft().primary().set_watchdog_timeout(self.watchdog_timeout)?;
+        consensus.bft().primary().set_watchdog_action(self.watchdog_action);
+        // Set the IPs of the probes, if configured.
+        if !self.probe_sources.is_empty() {
+            consensus.bft().primary().gateway().set_probe_sources(self.probe_sources.iter().copied());
+        }
+        // Set the minimum number of previous certificates for a batch to reference.
+        consensus.bft().primary().set_min_previous_certificates(self.min_previous_certificates)?;
+        // Set the handling of the stale batch proposals.
+        consensus.bft().primary().set_stale_proposal_policy(self.stale_proposal_policy);
+        // Set the maximum committee change, above which the committee transitions are alerted on.
+        consensus.bft().primary().set_max_committee_change(self.max_committee_change)?;
+        // Set the DAG snapshots that are served to, and requested from, the validators.
+        consensus.bft().primary().sync().set_max_dag_snapshot_certificates(self.max_dag_snapshot_certificates)?;
+        if self.dag_snapshot_warm_up {
+            consensus.bft().primary().sync().enable_dag_snapshot_warm_up();
+        }
+        // Set the rate limit on the transactions from any single sender, which is shared by the workers.
+        if let Some((max_transactions, interval_in_secs)) = self.sender_rate_limit {
+            let sender_rate_limit = SenderRateLimit::new(max_transactions, interval_in_secs)?;
+            consensus.bft().primary().set_sender_rate_limit(Some(sender_rate_limit));
+        }
+        Ok(())
+    }
+
+    /// Applies the configurations to the given workers, once they are initialized by the started consensus.
+    pub(crate) fn apply_to_workers<N: Network>(&self, workers: &[Worker<N>], account: &Account<N>) -> Result<()> {
+        for worker in workers {
+            // Set the fairness cap on the submitters in a batch.
+            worker.set_max_transmissions_per_submitter(self.max_transmissions_per_submitter)?;
+            // Enable pinning transmissions, if configured.
+            if let Some(max_pinned) = self.max_pinned_transmissions {
+                worker.set_max_pinned_transmissions(max_pinned)?;
+            }
+            // Sign the admission receipts with the validator account, if enabled.
+            if self.admission_receipts {
+                worker.set_receipt_signer(Some(account.clone()));
+            }
+            // Consult the external scorer on admission, if configured.
+            if let Some((scorer, timeout, fail_open)) = &self.admission_scorer {
+                let hook = AdmissionHook::new(Arc::new(scorer.clone()), *timeout).with_fail_open(*fail_open);
+                worker.set_admission_hook(Some(hook));
+            }
+            // Set the handling of the transactions that reference unknown programs.
+            worker.set_unknown_program_policy(self.unknown_program_policy);
+            // Bound the admission into the ready queue, if configured.
+            if let Some((order, capacity)) = self.admission_queue {
+                worker.set_admission_queue(Some(AdmissionQueue::new(order, capacity)?));
+            }
+            // Retain the transient rejections, so that a retry is recognized, if configured.
+            worker.set_transient_rejection_window(self.transient_rejection_window)?;
+            // Enable transaction replacement, if configured.
+            if let Some(min_fee_bump_percent) = self.replacement_fee_bump_percent {
+                worker.set_replacement_policy(Some(ReplacementPolicy::new(min_fee_bump_percent)));
+            }
+        }
+        Ok(())
+    }
+
+    /// Applies the configurations to the given router, before the routing is started.
+    pub(crate) fn apply_to_router<N: Network>(&self, router: &Router<N>) -> Result<()> {
+        // Set the peer ban policy, reloading the persisted bans, if configured.
+        if let Some(ban_policy) = &self.ban_policy {
+            router.set_ban_policy(ban_policy.clone())?;
+        }
+        // Set the cap on the outbound rate.
+        router.set_max_outbound_bytes_per_sec(self.max_outbound_bytes_per_sec);
+        // Set the limits on the sync requests served to peers.
+        router.set_sync_request_limits(self.sync_request_limits);
+        Ok(())
+    }
+
+    /// Applies the configurations to the given REST server, once it is started.
+    pub(crate) fn apply_to_rest<N: Network, C: ConsensusStorage<N>, R: Routing<N>>(
+        &self,
+        rest: &Rest<N, C, R>,
+    ) -> Result<()> {
+        // Shed the expensive endpoints under high load, if configured.
+        if let Some(threshold) = self.rest_shed_load_threshold {
+            rest.set_circuit_breaker(Some(CircuitBreaker::new(threshold)?));
+        }
+        // Cache the responses of the hot read endpoints, if configured.
+        if let Some(ttl) = self.rest_cache_ttl {
+            rest.set_response_cache(Some(ResponseCache::new(ttl)));
+        }
+        // Reject the duplicate transaction submissions, if configured.
+        if self.rest_reject_duplicates {
+            rest.set_duplicate_submission_policy(DuplicateSubmissionPolicy::Reject);
+        }
+        Ok(())
+    }
+}
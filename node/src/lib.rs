@@ -33,6 +33,9 @@ pub use snarkvm;
 mod client;
 pub use client::*;
 
+mod config;
+pub use config::*;
+
 mod prover;
 pub use prover::*;
 
@@ -47,8 +50,9 @@ pub use traits::*;
 
 use aleo_std::StorageMode;
 
-/// A helper to log instructions to recover.
-pub fn log_clean_error(storage_mode: &StorageMode) {
+/// A helper to log instructions to recover, and to alert the webhook of the storage corruption, if given.
+/// Note: The alert is delivered (or times out) before returning, as the node exits right after.
+pub async fn log_clean_error(storage_mode: &StorageMode, alert_notifier: Option<&bft::helpers::AlertNotifier>) {
     match storage_mode {
         StorageMode::Production => error!("Storage corruption detected! Run `snarkos clean` to reset storage"),
         StorageMode::Development(id) => {
@@ -58,6 +62,11 @@ pub fn log_clean_error(storage_mode: &StorageMode) {
             error!("Storage corruption detected! Run `snarkos clean --path {}` to reset storage", path.display())
         }
     }
+    // Alert the webhook of the storage corruption, if alerting is enabled.
+    if let Some(notifier) = alert_notifier {
+        let (event, timeout) = (bft::helpers::AlertEvent::StorageCorruption, bft::helpers::ALERT_EXIT_TIMEOUT_IN_SECS);
+        notifier.notify_and_wait(event, "Storage corruption detected", std::time::Duration::from_secs(timeout)).await;
+    }
 }
 
 use snarkvm::{
@@ -14,11 +14,11 @@
 
 mod router;
 
-use crate::traits::NodeInterface;
+use crate::{traits::NodeInterface, NodeConfig};
 use snarkos_account::Account;
 use snarkos_node_bft::{helpers::init_primary_channels, ledger_service::CoreLedgerService};
 use snarkos_node_consensus::Consensus;
-use snarkos_node_rest::Rest;
+use snarkos_node_rest::{AdminConfig, Rest};
 use snarkos_node_router::{
     messages::{NodeType, PuzzleResponse, UnconfirmedSolution, UnconfirmedTransaction},
     Heartbeat,
@@ -41,7 +41,7 @@ use snarkvm::prelude::{
 };
 
 use aleo_std::StorageMode;
-use anyhow::Result;
+use anyhow::{bail, Result};
 use core::future::Future;
 use parking_lot::Mutex;
 use std::{
@@ -83,6 +83,7 @@ impl<N: Network, C: ConsensusStorage<N>> Validator<N, C> {
         genesis: Block<N>,
         cdn: Option<String>,
         storage_mode: StorageMode,
+        config: NodeConfig,
     ) -> Result<Self> {
         // Prepare the shutdown flag.
         let shutdown: Arc<AtomicBool> = Default::default();
@@ -100,7 +101,7 @@ impl<N: Network, C: ConsensusStorage<N>> Validator<N, C> {
             if let Err((_, error)) =
                 snarkos_node_cdn::sync_ledger_with_cdn(&base_url, ledger.clone(), shutdown.clone()).await
             {
-                crate::log_clean_error(&storage_mode);
+                crate::log_clean_error(&storage_mode, config.alert_notifier.as_ref()).await;
                 return Err(error);
             }
         }
@@ -111,12 +112,23 @@ impl<N: Network, C: ConsensusStorage<N>> Validator<N, C> {
         let sync = BlockSync::new(BlockSyncMode::Gateway, ledger_service.clone());
 
         // Initialize the consensus.
-        let mut consensus =
-            Consensus::new(account.clone(), ledger_service, bft_ip, trusted_validators, storage_mode.clone())?;
+        let mut consensus = Consensus::new(
+            account.clone(),
+            ledger_service,
+            bft_ip,
+            trusted_validators,
+            storage_mode.clone(),
+            config.bft_store_compression,
+            config.bft_store_degraded_threshold,
+        )?;
+        // Apply the configurations to the consensus.
+        config.apply_to_consensus(&consensus)?;
         // Initialize the primary channels.
         let (primary_sender, primary_receiver) = init_primary_channels::<N>();
         // Start the consensus.
         consensus.run(primary_sender, primary_receiver).await?;
+        // Apply the configurations to the workers.
+        config.apply_to_workers(consensus.bft().primary().workers(), &account)?;
 
         // Initialize the node router.
         let router = Router::new(
@@ -128,6 +140,13 @@ impl<N: Network, C: ConsensusStorage<N>> Validator<N, C> {
             matches!(storage_mode, StorageMode::Development(_)),
         )
         .await?;
+        // Apply the configurations to the router.
+        config.apply_to_router(&router)?;
+        // Recognize the committee members among the peers, by the latest committee in the ledger.
+        let ledger_ = ledger.clone();
+        router.set_committee_membership(Some(Arc::new(move |address| {
+            ledger_.latest_committee().map_or(false, |committee| committee.is_committee_member(*address))
+        })));
 
         // Initialize the node.
         let mut node = Self {
@@ -144,8 +163,10 @@ impl<N: Network, C: ConsensusStorage<N>> Validator<N, C> {
 
         // Initialize the REST server.
         if let Some(rest_ip) = rest_ip {
-            node.rest =
-                Some(Rest::start(rest_ip, rest_rps, Some(consensus), ledger.clone(), Arc::new(node.clone())).await?);
+            let rest = Rest::start(rest_ip, rest_rps, Some(consensus), ledger.clone(), Arc::new(node.clone())).await?;
+            // Apply the configurations to the REST server.
+            config.apply_to_rest(&rest)?;
+            node.rest = Some(rest);
         }
         // Initialize the routing.
         node.initialize_routing().await;
@@ -153,6 +174,14 @@ impl<N: Network, C: ConsensusStorage<N>> Validator<N, C> {
         node.handles.lock().push(crate::start_notification_message_loop());
         // Pass the node to the signal handler.
         let _ = signal_node.set(node.clone());
+        // Shut down the node and exit, if the watchdog detects a stalled task.
+        // Note: This task is not in the handles, as they are aborted by the shutdown.
+        let node_ = node.clone();
+        tokio::spawn(async move {
+            node_.consensus.bft().primary().wait_for_watchdog_exit().await;
+            node_.shut_down().await;
+            std::process::exit(1);
+        });
         // Return the node.
         Ok(node)
     }
@@ -166,6 +195,14 @@ impl<N: Network, C: ConsensusStorage<N>> Validator<N, C> {
     pub fn rest(&self) -> &Option<Rest<N, C, Self>> {
         &self.rest
     }
+
+    /// Starts the admin listener of the REST server, which serves the admin endpoints over TLS.
+    pub async fn enable_admin(&self, config: AdminConfig) -> Result<()> {
+        match &self.rest {
+            Some(rest) => rest.start_admin(config).await,
+            None => bail!("The admin listener requires the REST server"),
+        }
+    }
 }
 
 impl<N: Network, C: ConsensusStorage<N>> Validator<N, C> {
@@ -496,6 +533,7 @@ mod tests {
             genesis,
             None,
             storage_mode,
+            Default::default(),
         )
         .await
         .unwrap();
@@ -170,6 +170,12 @@ impl<N: Network, C: ConsensusStorage<N>> Inbound<N> for Validator<N, C> {
         }
     }
 
+    /// Releases the refused block request, so it is retried from another peer.
+    fn block_refusal(&self, peer_ip: SocketAddr, request: BlockRequest) -> bool {
+        self.sync.process_block_refusal(peer_ip, request.start_height, request.end_height);
+        true
+    }
+
     /// Processes the block locators and sends back a `Pong` message.
     fn ping(&self, peer_ip: SocketAddr, message: Ping<N>) -> bool {
         // Check if the sync module is in router mode.
@@ -277,6 +277,15 @@ impl<N: Network> BlockSync<N> {
         Ok(())
     }
 
+    /// Processes the refusal of a block request from the given peer IP, releasing the blocks requested from the peer,
+    /// so that they are requested again without waiting for the requests to time out.
+    #[inline]
+    pub fn process_block_refusal(&self, peer_ip: SocketAddr, start_height: u32, end_height: u32) {
+        for height in start_height..end_height {
+            self.remove_block_request_to_peer(&peer_ip, height);
+        }
+    }
+
     /// Returns the next block to process, if one is ready.
     #[inline]
     pub fn process_next_block(&self, next_height: u32) -> Option<Block<N>> {
@@ -577,7 +586,6 @@ impl<N: Network> BlockSync<N> {
     }
 
     /// Removes the block request for the given peer IP, if it exists.
-    #[allow(dead_code)]
     fn remove_block_request_to_peer(&self, peer_ip: &SocketAddr, height: u32) {
         let mut can_revoke = self.responses.read().get(&height).is_none();
 
@@ -1317,5 +1325,29 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_block_refusal_releases_requests() {
+        let sync = sample_sync_at_height(0);
+
+        // Add a peer.
+        let peer_ip = sample_peer_ip(1);
+        sync.update_peer_locators(peer_ip, sample_block_locators(10)).unwrap();
+
+        // Request the blocks from the peer.
+        let requests = sync.prepare_block_requests();
+        assert_eq!(requests.len(), 10);
+        for (height, request) in requests {
+            sync.insert_block_request(height, request).unwrap();
+        }
+
+        // Ensure the refused block requests are released, so they are requested again.
+        sync.process_block_refusal(peer_ip, 1, 6);
+        for height in 1..=10 {
+            assert_eq!(sync.get_block_request(height).is_some(), height >= 6);
+            assert_eq!(sync.get_block_request_timestamp(height).is_some(), height >= 6);
+        }
+        assert_eq!(sync.prepare_block_requests().len(), 5);
+    }
+
     // TODO: duplicate responses, ensure fails.
 }
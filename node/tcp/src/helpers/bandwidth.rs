@@ -0,0 +1,234 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkOS library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::{
+    collections::{BTreeSet, HashMap},
+    net::SocketAddr,
+    time::Duration,
+};
+
+use parking_lot::Mutex;
+use tokio::{sync::Notify, time::Instant};
+
+/// A ticket in the outbound queue, ordered by its virtual finish tag, and then by its arrival.
+type Ticket = (u128, u64);
+
+/// Shares the outbound bandwidth of the Tcp across its peers, using (self-clocked) weighted fair queuing.
+/// Every outbound message waits for its turn, so that the backlogged peers share the total outbound rate
+/// in proportion to their weights, without any peer being fully starved.
+///
+/// note: If no rate cap is set, messages are never delayed.
+#[derive(Default)]
+pub struct OutboundBandwidth {
+    /// The scheduling state.
+    state: Mutex<State>,
+    /// Notifies the waiting messages that the head of the queue has changed.
+    notify: Notify,
+}
+
+#[derive(Default)]
+struct State {
+    /// The total outbound rate cap, in bytes per second, if one is set.
+    max_bytes_per_sec: Option<u64>,
+    /// The weights of the peers; peers without a weight have the default weight.
+    weights: HashMap<SocketAddr, u32>,
+    /// The virtual finish tag of the last queued message of each peer.
+    finish_tags: HashMap<SocketAddr, u128>,
+    /// The tickets of the waiting messages.
+    queue: BTreeSet<Ticket>,
+    /// The virtual time, i.e. the finish tag of the last message that was sent.
+    virtual_time: u128,
+    /// The sequence number of the next ticket.
+    next_seq: u64,
+    /// The instant at which the next message may be sent.
+    next_send_at: Option<Instant>,
+}
+
+impl OutboundBandwidth {
+    /// The weight of a peer that has not been assigned one.
+    pub const DEFAULT_WEIGHT: u32 = 1;
+
+    /// Returns the total outbound rate cap, in bytes per second, if one is set.
+    pub fn max_bytes_per_sec(&self) -> Option<u64> {
+        self.state.lock().max_bytes_per_sec
+    }
+
+    /// Sets the total outbound rate cap, in bytes per second. If `None` is given, the outbound rate is unlimited.
+    pub fn set_max_bytes_per_sec(&self, max_bytes_per_sec: Option<u64>) {
+        self.state.lock().max_bytes_per_sec = max_bytes_per_sec.map(|rate| rate.max(1));
+        // Wake the waiting messages, so they observe the new rate.
+        self.notify.notify_waiters();
+    }
+
+    /// Returns the weight of the given peer.
+    pub fn weight(&self, addr: SocketAddr) -> u32 {
+        self.state.lock().weights.get(&addr).copied().unwrap_or(Self::DEFAULT_WEIGHT)
+    }
+
+    /// Sets the weight of the given peer, i.e. its relative share of the outbound rate when backlogged.
+    pub fn set_weight(&self, addr: SocketAddr, weight: u32) {
+        self.state.lock().weights.insert(addr, weight.max(1));
+    }
+
+    /// Removes the weight and the scheduling history of the given peer.
+    pub fn remove(&self, addr: SocketAddr) {
+        let mut state = self.state.lock();
+        state.weights.remove(&addr);
+        state.finish_tags.remove(&addr);
+    }
+
+    /// Waits until a message of the given size may be sent to the given peer.
+    pub async fn acquire(&self, addr: SocketAddr, size: usize) {
+        // Queue the message, with a virtual finish tag proportional to its size over the weight of the peer.
+        let ticket = {
+            let mut state = self.state.lock();
+            if state.max_bytes_per_sec.is_none() {
+                return;
+            }
+            let weight = state.weights.get(&addr).copied().unwrap_or(Self::DEFAULT_WEIGHT);
+            let start = state.finish_tags.get(&addr).copied().unwrap_or(0).max(state.virtual_time);
+            let finish = start.saturating_add(((size as u128) << 16) / weight as u128);
+            state.finish_tags.insert(addr, finish);
+            let ticket = (finish, state.next_seq);
+            state.next_seq += 1;
+            state.queue.insert(ticket);
+            ticket
+        };
+        // Ensure the ticket is dequeued, even if the message is dropped while waiting.
+        let _guard = TicketGuard { bandwidth: self, ticket };
+
+        loop {
+            // Register for the notification before inspecting the queue, so that no notification is missed.
+            let notified = self.notify.notified();
+            let send_at = {
+                let mut state = self.state.lock();
+                let now = Instant::now();
+                match state.max_bytes_per_sec {
+                    // If the rate cap was lifted, send the message immediately.
+                    None => None,
+                    // If the message is at the head of the queue, it is sent once the rate allows.
+                    Some(rate) if state.queue.first() == Some(&ticket) => match state.next_send_at {
+                        Some(send_at) if send_at > now => Some(Some(send_at)),
+                        _ => {
+                            state.virtual_time = ticket.0;
+                            state.next_send_at = Some(now + Duration::from_secs_f64(size as f64 / rate as f64));
+                            None
+                        }
+                    },
+                    // Otherwise, wait for the head of the queue to change.
+                    Some(_) => Some(None),
+                }
+            };
+            match send_at {
+                None => break,
+                Some(Some(send_at)) => tokio::time::sleep_until(send_at).await,
+                Some(None) => notified.await,
+            }
+        }
+    }
+}
+
+/// Dequeues a ticket once its message is sent (or dropped), and wakes the next message in the queue.
+struct TicketGuard<'a> {
+    bandwidth: &'a OutboundBandwidth,
+    ticket: Ticket,
+}
+
+impl Drop for TicketGuard<'_> {
+    fn drop(&mut self) {
+        self.bandwidth.state.lock().queue.remove(&self.ticket);
+        self.bandwidth.notify.notify_waiters();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::{
+        net::{IpAddr, Ipv4Addr},
+        sync::{
+            atomic::{AtomicUsize, Ordering::*},
+            Arc,
+        },
+    };
+
+    #[tokio::test]
+    async fn test_unlimited_bandwidth_is_not_delayed() {
+        let bandwidth = OutboundBandwidth::default();
+        let addr = SocketAddr::new(IpAddr::V4(Ipv4Addr::LOCALHOST), 4130);
+        assert_eq!(bandwidth.max_bytes_per_sec(), None);
+        assert_eq!(bandwidth.weight(addr), OutboundBandwidth::DEFAULT_WEIGHT);
+
+        let start = Instant::now();
+        for _ in 0..1_000 {
+            bandwidth.acquire(addr, 1 << 20).await;
+        }
+        assert!(start.elapsed() < Duration::from_secs(1));
+    }
+
+    #[tokio::test]
+    async fn test_bandwidth_is_shared_per_weights() {
+        const RATE: u64 = 1_000_000; // bytes per second
+        const MESSAGE_SIZE: usize = 10_000;
+        const DURATION: Duration = Duration::from_secs(2);
+
+        let bandwidth = Arc::new(OutboundBandwidth::default());
+        bandwidth.set_max_bytes_per_sec(Some(RATE));
+
+        // Assign the weights, e.g. a committee peer, a reliable peer, and two other peers.
+        let weights = [4, 2, 1, 1];
+        let peers = (0..weights.len())
+            .map(|i| SocketAddr::new(IpAddr::V4(Ipv4Addr::LOCALHOST), 4130 + i as u16))
+            .collect::<Vec<_>>();
+        for (peer, weight) in peers.iter().zip(weights) {
+            bandwidth.set_weight(*peer, weight);
+        }
+
+        // Send bulk data to every peer at once, and count the bytes sent to each.
+        let sent = peers.iter().map(|_| Arc::new(AtomicUsize::new(0))).collect::<Vec<_>>();
+        let tasks = peers
+            .iter()
+            .zip(&sent)
+            .map(|(peer, sent)| {
+                let (bandwidth, peer, sent) = (bandwidth.clone(), *peer, sent.clone());
+                tokio::spawn(async move {
+                    loop {
+                        bandwidth.acquire(peer, MESSAGE_SIZE).await;
+                        sent.fetch_add(MESSAGE_SIZE, Relaxed);
+                    }
+                })
+            })
+            .collect::<Vec<_>>();
+        tokio::time::sleep(DURATION).await;
+        tasks.iter().for_each(|task| task.abort());
+        let sent = sent.iter().map(|sent| sent.load(Relaxed)).collect::<Vec<_>>();
+
+        // Ensure the total outbound rate is capped.
+        let total = sent.iter().sum::<usize>();
+        assert!(total as u64 <= RATE * DURATION.as_secs() + (weights.len() * MESSAGE_SIZE) as u64);
+        assert!(total as u64 >= RATE * DURATION.as_secs() / 2);
+        // Ensure the bandwidth is shared in proportion to the weights, without starving any peer.
+        let share = |i: usize| sent[i] as f64 / sent[3] as f64;
+        assert!(sent[3] > 0 && sent[2] > 0);
+        assert!((3.0..=5.0).contains(&share(0)), "{sent:?}");
+        assert!((1.5..=2.5).contains(&share(1)), "{sent:?}");
+        assert!((0.75..=1.25).contains(&share(2)), "{sent:?}");
+
+        // Ensure the dropped messages do not block the queue.
+        let start = Instant::now();
+        bandwidth.acquire(peers[3], MESSAGE_SIZE).await;
+        assert!(start.elapsed() < Duration::from_secs(1));
+    }
+}
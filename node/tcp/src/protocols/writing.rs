@@ -158,8 +158,10 @@ where
 #[async_trait]
 trait WritingInternal: Writing {
     /// Writes the given message to the network stream and returns the number of written bytes.
+    /// The message is only flushed once the outbound bandwidth allows it to be sent to the given address.
     async fn write_to_stream<W: AsyncWrite + Unpin + Send>(
         &self,
+        addr: SocketAddr,
         message: Self::Message,
         writer: &mut FramedWrite<W, Self::Codec>,
     ) -> Result<usize, <Self::Codec as Encoder<Self::Message>>::Error>;
@@ -172,11 +174,13 @@ trait WritingInternal: Writing {
 impl<W: Writing> WritingInternal for W {
     async fn write_to_stream<A: AsyncWrite + Unpin + Send>(
         &self,
+        addr: SocketAddr,
         message: Self::Message,
         writer: &mut FramedWrite<A, Self::Codec>,
     ) -> Result<usize, <Self::Codec as Encoder<Self::Message>>::Error> {
         writer.feed(message).await?;
         let len = writer.write_buffer().len();
+        self.tcp().bandwidth().acquire(addr, len).await;
         writer.flush().await?;
 
         Ok(len)
@@ -216,7 +220,7 @@ impl<W: Writing> WritingInternal for W {
             while let Some(wrapped_msg) = outbound_message_receiver.recv().await {
                 let msg = wrapped_msg.msg.downcast().unwrap();
 
-                match self_clone.write_to_stream(*msg, &mut framed).await {
+                match self_clone.write_to_stream(addr, *msg, &mut framed).await {
                     Ok(len) => {
                         let _ = wrapped_msg.delivery_notification.send(Ok(()));
                         node.known_peers().register_sent_message(addr, len);
@@ -41,6 +41,7 @@ use crate::{
     protocols::{Protocol, Protocols},
     Config,
     KnownPeers,
+    OutboundBandwidth,
     Stats,
 };
 
@@ -77,6 +78,8 @@ pub struct InnerTcp {
     known_peers: KnownPeers,
     /// Collects statistics related to the node itself.
     stats: Stats,
+    /// Shares the node's outbound bandwidth across its peers.
+    bandwidth: OutboundBandwidth,
     /// The node's tasks.
     pub(crate) tasks: Mutex<Vec<JoinHandle<()>>>,
 }
@@ -102,6 +105,7 @@ impl Tcp {
             connections: Default::default(),
             known_peers: Default::default(),
             stats: Default::default(),
+            bandwidth: Default::default(),
             tasks: Default::default(),
         }));
 
@@ -171,6 +175,12 @@ impl Tcp {
         &self.stats
     }
 
+    /// Returns a reference to the outbound bandwidth scheduler.
+    #[inline]
+    pub fn bandwidth(&self) -> &OutboundBandwidth {
+        &self.bandwidth
+    }
+
     /// Returns the tracing [`Span`] associated with Tcp.
     #[inline]
     pub fn span(&self) -> &Span {
@@ -278,6 +288,7 @@ impl Tcp {
             if conn.side() == ConnectionSide::Initiator {
                 self.known_peers().remove(conn.addr());
             }
+            self.bandwidth().remove(conn.addr());
 
             debug!(parent: self.span(), "Disconnected from {}", conn.addr());
         } else {
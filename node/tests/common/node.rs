@@ -30,6 +30,7 @@ pub async fn client() -> Client<CurrentNetwork, ConsensusMemory<CurrentNetwork>>
         sample_genesis_block(),
         None, // No CDN.
         StorageMode::Production,
+        Default::default(),
     )
     .await
     .expect("couldn't create client instance")
@@ -42,6 +43,7 @@ pub async fn prover() -> Prover<CurrentNetwork, ConsensusMemory<CurrentNetwork>>
         &[],
         sample_genesis_block(),
         StorageMode::Production,
+        Default::default(),
     )
     .await
     .expect("couldn't create prover instance")
@@ -59,6 +61,7 @@ pub async fn validator() -> Validator<CurrentNetwork, ConsensusMemory<CurrentNet
         sample_genesis_block(), // Should load the current network's genesis block.
         None,                   // No CDN.
         StorageMode::Production,
+        Default::default(),
     )
     .await
     .expect("couldn't create validator instance")